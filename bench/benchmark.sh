@@ -23,6 +23,11 @@ usage() {
     echo "  frida           Traditional FRI benchmarking (commitment + proof + verification)"
     echo "  single-frida    Single proof size and time analysis"
     echo "  defrida         Distributed deFRIDA workflow benchmarking"
+    echo "  hashers         Proof size and verification time across hashers and folding factors"
+    echo "  throughput      Verifier throughput with concurrent verifiers"
+    echo "  check           Regression check against a stored baseline (no command needed)"
+    echo "  calibrate       Measure the commitment cost model coefficients (no command needed)"
+    echo "  parallelism     Batched commitment time across parallel task sizes (no command needed)"
     echo ""
     echo "Commands:"
     echo "  full            Run comprehensive benchmark suite"
@@ -30,7 +35,9 @@ usage() {
     echo "  help            Show this help message"
     echo ""
     echo "Common Options:"
-    echo "  --output FILE   Output CSV file (default varies by benchmark type)"
+    echo "  --output FILE   Output file (default varies by benchmark type)"
+    echo "  --format FMT    Output format: csv or jsonl (default: csv)"
+    echo "  --append        Append to the output file instead of overwriting it"
     echo ""
     echo "Frida Custom Options:"
     echo "  --blowup-factor N           Blowup factor (required)"
@@ -56,13 +63,47 @@ usage() {
     echo "  --num-queries N             Number of queries (required)"
     echo "  --batch-size N              Batch size (default: 1)"
     echo ""
+    echo "Hashers Custom Options:"
+    echo "  --blowup-factor N           Blowup factor (required)"
+    echo "  --max-remainder-degree N    Maximum remainder degree (required)"
+    echo "  --data-size N               Data size in bytes (required)"
+    echo "  --num-queries N             Number of queries (default: 32)"
+    echo "  --folding-factors LIST      Comma-separated folding factors (default: 2,4,8,16)"
+    echo ""
+    echo "Throughput Custom Options:"
+    echo "  --blowup-factor N           Blowup factor (required)"
+    echo "  --folding-factor N          Folding factor (required)"
+    echo "  --max-remainder-degree N    Maximum remainder degree (required)"
+    echo "  --data-size N               Data size in bytes (required)"
+    echo "  --batch-size N              Batch size (default: 1)"
+    echo "  --num-proofs N              Proofs verified per thread count (default: 1024)"
+    echo "  --positions-per-proof N     Positions opened by each proof (default: 1)"
+    echo "  --threads LIST              Comma-separated thread counts (default: powers of two up to CPU count)"
+    echo ""
+    echo "Check Options:"
+    echo "  --baseline FILE             Baseline JSON (default: bench/baselines/check.json)"
+    echo "  --threshold PCT             Allowed slowdown in percent (default: 10)"
+    echo "  --update                    Record a new baseline instead of comparing"
+    echo ""
+    echo "Calibrate Options:"
+    echo "  --blowup-factor N           Blowup factor of the validation commits (default: 2)"
+    echo "  --folding-factor N          Folding factor of the validation commits (default: 4)"
+    echo "  --max-remainder-degree N    Maximum remainder degree (default: 3)"
+    echo ""
     echo "Examples:"
     echo "  $0 frida full"
     echo "  $0 frida custom --blowup-factor 8 --folding-factor 4 --max-remainder-degree 31 --data-size 65536"
     echo "  $0 single-frida full --output my_single_results.csv"
+    echo "  $0 single-frida full --format jsonl --output results.jsonl --append"
     echo "  $0 single-frida custom --blowup-factor 4 --folding-factor 2 --max-remainder-degree 15 --data-size 32768 --batch-size 8"
     echo "  $0 defrida full"
     echo "  $0 defrida custom --blowup-factor 8 --folding-factor 4 --max-remainder-degree 31 --data-size 65536 --num-validators 16 --num-queries 64"
+    echo "  $0 throughput custom --blowup-factor 2 --folding-factor 4 --max-remainder-degree 2 --data-size 131072 --threads 1,4,16"
+    echo "  $0 check --update"
+    echo "  $0 check --threshold 15"
+    echo "  $0 calibrate"
+    echo "  $0 parallelism --data-sizes 65536 --batch-sizes 4,64"
+    echo "  $0 hashers custom --blowup-factor 2 --max-remainder-degree 256 --data-size 65536 --folding-factors 2,4"
 }
 
 # Build the benchmark binary
@@ -85,7 +126,7 @@ ARGS=()
 # First argument should be benchmark type
 if [[ $# -gt 0 ]]; then
     case $1 in
-        frida|single-frida|defrida|help)
+        frida|single-frida|defrida|hashers|throughput|check|calibrate|parallelism|help)
             BENCHMARK_TYPE="$1"
             shift
             ;;
@@ -99,7 +140,7 @@ if [[ $# -gt 0 ]]; then
 fi
 
 # Second argument should be command
-if [[ $# -gt 0 && "$BENCHMARK_TYPE" != "help" ]]; then
+if [[ $# -gt 0 && "$BENCHMARK_TYPE" != "help" && "$BENCHMARK_TYPE" != "check" && "$BENCHMARK_TYPE" != "calibrate" && "$BENCHMARK_TYPE" != "parallelism" ]]; then
     case $1 in
         full|custom)
             COMMAND="$1"
@@ -183,6 +224,63 @@ case $BENCHMARK_TYPE in
                 ;;
         esac
         ;;
+    "hashers")
+        build_benchmark
+        case $COMMAND in
+            "full")
+                echo -e "${BLUE}Running full hasher sweep benchmark...${NC}"
+                echo "This benchmarks proof sizes and verification times for every supported hasher."
+                echo "Estimated time: 15-45 minutes depending on your hardware."
+                ./target/release/frida-bench hashers $COMMAND "${ARGS[@]}"
+                ;;
+            "custom")
+                echo -e "${BLUE}Running custom hasher sweep benchmark...${NC}"
+                ./target/release/frida-bench hashers $COMMAND "${ARGS[@]}"
+                ;;
+            *)
+                echo -e "${RED}Error: Missing or invalid command for hashers benchmark${NC}"
+                usage
+                exit 1
+                ;;
+        esac
+        ;;
+    "throughput")
+        build_benchmark
+        case $COMMAND in
+            "full")
+                echo -e "${BLUE}Running full verifier throughput benchmark...${NC}"
+                echo "This benchmarks concurrent proof verification against a single commitment."
+                echo "Estimated time: 10-30 minutes depending on your hardware."
+                ./target/release/frida-bench throughput $COMMAND "${ARGS[@]}"
+                ;;
+            "custom")
+                echo -e "${BLUE}Running custom verifier throughput benchmark...${NC}"
+                ./target/release/frida-bench throughput $COMMAND "${ARGS[@]}"
+                ;;
+            *)
+                echo -e "${RED}Error: Missing or invalid command for throughput benchmark${NC}"
+                usage
+                exit 1
+                ;;
+        esac
+        ;;
+    "check")
+        build_benchmark
+        echo -e "${BLUE}Running benchmark regression check...${NC}"
+        ./target/release/frida-bench check "${ARGS[@]}"
+        ;;
+    "calibrate")
+        build_benchmark
+        echo -e "${BLUE}Calibrating commitment cost model...${NC}"
+        ./target/release/frida-bench calibrate "${ARGS[@]}"
+        ;;
+    "parallelism")
+        # task sizes only matter when loops run in parallel
+        echo -e "${YELLOW}Building Frida benchmark binary with the concurrent feature...${NC}"
+        cargo build --bin frida-bench --release --features "bench concurrent" --quiet || exit 1
+        echo -e "${BLUE}Running parallelism tuning benchmark...${NC}"
+        ./target/release/frida-bench parallelism "${ARGS[@]}"
+        ;;
     *)
         echo -e "${RED}Error: Missing benchmark type${NC}"
         echo ""
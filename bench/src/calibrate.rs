@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant};
+use winter_crypto::{ElementHasher, MerkleTree};
+use winter_fri::FriOptions;
+use winter_math::fft;
+use winter_rand_utils::rand_vector;
+
+use frida_poc::prover::{builder::FridaProverBuilder, cost::CostModel};
+
+use crate::common::{Blake3F128, F128Element, RUNS};
+
+/// Size of the vectors the coefficients are measured over
+const CALIBRATION_SIZE: usize = 1 << 16;
+/// Row width hashed into each leaf while measuring the hashing coefficient
+const CALIBRATION_ROW: usize = 4;
+
+fn median_ns(mut samples: Vec<Duration>) -> f64 {
+    samples.sort();
+    samples[samples.len() / 2].as_nanos() as f64
+}
+
+fn time_runs(mut f: impl FnMut()) -> f64 {
+    median_ns(
+        (0..RUNS)
+            .map(|_| {
+                let timer = Instant::now();
+                f();
+                timer.elapsed()
+            })
+            .collect(),
+    )
+}
+
+/// Measures every coefficient of the cost model separately on this machine, for f128 with
+/// Blake3_256.
+fn measure_model() -> CostModel {
+    let n = CALIBRATION_SIZE;
+    let values = rand_vector::<F128Element>(n);
+
+    let twiddles = fft::get_twiddles::<F128Element>(n);
+    let fft_ns = time_runs(|| {
+        let mut evaluations = values.clone();
+        fft::evaluate_poly(&mut evaluations, &twiddles);
+    });
+    let butterflies = n / 2 * n.trailing_zeros() as usize;
+
+    let hash_ns = time_runs(|| {
+        for row in values.chunks(CALIBRATION_ROW) {
+            std::hint::black_box(Blake3F128::hash_elements(row));
+        }
+    });
+
+    let leaves = values
+        .chunks(CALIBRATION_ROW)
+        .map(Blake3F128::hash_elements)
+        .collect::<Vec<_>>();
+    let merge_ns = time_runs(|| {
+        std::hint::black_box(MerkleTree::<Blake3F128>::new(leaves.clone()).unwrap());
+    });
+
+    CostModel {
+        fft_ns_per_butterfly: fft_ns / butterflies as f64,
+        hash_ns_per_element: hash_ns / n as f64,
+        merge_ns_per_node: merge_ns / (leaves.len() - 1) as f64,
+    }
+}
+
+/// Measures the cost model coefficients of this machine and compares the commitment times it
+/// predicts against measured ones.
+pub fn run_calibration(blowup_factor: usize, folding_factor: usize, max_remainder_degree: usize) {
+    println!("Calibrating cost model (f128, blake3_256, {RUNS} runs per measurement)...");
+    let model = measure_model();
+    println!("  fft_ns_per_butterfly: {:.3}", model.fft_ns_per_butterfly);
+    println!("  hash_ns_per_element:  {:.3}", model.hash_ns_per_element);
+    println!("  merge_ns_per_node:    {:.3}", model.merge_ns_per_node);
+
+    let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+    println!(
+        "\nPredicted vs measured commitment time (blowup={blowup_factor}, folding={folding_factor}, remainder={max_remainder_degree}):"
+    );
+    for data_size in [64 * 1024, 256 * 1024, 1024 * 1024] {
+        let Ok(cost) = model.estimate_commit_cost::<F128Element>(data_size, &options) else {
+            println!("  {:>5}KB: unsupported parameters", data_size / 1024);
+            continue;
+        };
+        let data = rand_vector::<u8>(data_size);
+        let prover_builder = FridaProverBuilder::<F128Element, Blake3F128>::new(options.clone());
+        let measured_ms = time_runs(|| {
+            prover_builder.commitment(&data, 32).unwrap();
+        }) / 1e6;
+        println!(
+            "  {:>5}KB: predicted {:>9.3} ms, measured {:>9.3} ms ({:+.1}%)",
+            data_size / 1024,
+            cost.estimated_time_ms,
+            measured_ms,
+            (cost.estimated_time_ms - measured_ms) / measured_ms * 100.0
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_rand_utils::rand_vector;
+
+use frida_poc::{prover::builder::FridaProverBuilder, verifier::das::FridaDasVerifier};
+
+use crate::common::{self, Blake3F128, Blake3F64, F128Element, F64Element, RunMetadata, RUNS};
+
+/// Number of positions opened and verified in every check
+const CHECK_POSITIONS: usize = 32;
+const CHECK_NUM_QUERIES: usize = 32;
+
+/// A single entry of the regression-check matrix
+struct CheckCase {
+    name: &'static str,
+    options: (usize, usize, usize),
+    data_size: usize,
+    batch_size: usize,
+}
+
+/// The fixed matrix measured by `check`. It is deliberately small so that it completes in a
+/// couple of minutes; changing it invalidates previously stored baselines.
+const CHECK_MATRIX: &[CheckCase] = &[
+    CheckCase {
+        name: "f64/2-2-0/64KB/1",
+        options: (2, 2, 0),
+        data_size: 64 * 1024,
+        batch_size: 1,
+    },
+    CheckCase {
+        name: "f128/2-2-0/64KB/1",
+        options: (2, 2, 0),
+        data_size: 64 * 1024,
+        batch_size: 1,
+    },
+    CheckCase {
+        name: "f128/2-4-2/64KB/1",
+        options: (2, 4, 2),
+        data_size: 64 * 1024,
+        batch_size: 1,
+    },
+    CheckCase {
+        name: "f128/2-4-2/64KB/4",
+        options: (2, 4, 2),
+        data_size: 64 * 1024,
+        batch_size: 4,
+    },
+    CheckCase {
+        name: "f128/2-8-4/256KB/1",
+        options: (2, 8, 4),
+        data_size: 256 * 1024,
+        batch_size: 1,
+    },
+];
+
+/// Median timings of one matrix entry, in milliseconds
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckTimings {
+    pub name: String,
+    pub commit_ms: f64,
+    pub open_ms: f64,
+    pub verify_ms: f64,
+}
+
+/// Baseline file format
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    pub git_commit: String,
+    pub cpu_model: String,
+    pub num_threads: usize,
+    pub timings: Vec<CheckTimings>,
+}
+
+fn median_ms(mut samples: Vec<Duration>) -> f64 {
+    samples.sort();
+    samples[samples.len() / 2].as_secs_f64() * 1000.0
+}
+
+fn measure<E, H>(case: &CheckCase) -> CheckTimings
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let (blowup_factor, folding_factor, max_remainder_degree) = case.options;
+    let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+
+    let mut commit_times = Vec::with_capacity(RUNS);
+    let mut open_times = Vec::with_capacity(RUNS);
+    let mut verify_times = Vec::with_capacity(RUNS);
+
+    for _ in 0..RUNS {
+        let data_list = (0..case.batch_size)
+            .map(|_| rand_vector::<u8>(case.data_size))
+            .collect::<Vec<_>>();
+        let prover_builder = FridaProverBuilder::<E, H>::new(options.clone());
+
+        let timer = Instant::now();
+        let (com, prover) = if case.batch_size == 1 {
+            prover_builder.commit_and_prove(&data_list[0], CHECK_NUM_QUERIES)
+        } else {
+            prover_builder.commit_and_prove_batch(&data_list, CHECK_NUM_QUERIES)
+        }
+        .unwrap();
+        commit_times.push(timer.elapsed());
+
+        let positions = rand_vector::<u64>(CHECK_POSITIONS)
+            .into_iter()
+            .map(|v| (v as usize) % com.domain_size)
+            .collect::<Vec<_>>();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+
+        let timer = Instant::now();
+        let proof = prover.open(&positions).unwrap();
+        open_times.push(timer.elapsed());
+
+        let timer = Instant::now();
+        let (verifier, _coin) = FridaDasVerifier::<E, H, H>::new(com, options.clone()).unwrap();
+        verifier.verify(&proof, &evaluations, &positions).unwrap();
+        verify_times.push(timer.elapsed());
+    }
+
+    CheckTimings {
+        name: case.name.to_string(),
+        commit_ms: median_ms(commit_times),
+        open_ms: median_ms(open_times),
+        verify_ms: median_ms(verify_times),
+    }
+}
+
+fn run_matrix() -> Vec<CheckTimings> {
+    CHECK_MATRIX
+        .iter()
+        .map(|case| {
+            println!("Measuring {}...", case.name);
+            if case.name.starts_with("f64/") {
+                measure::<F64Element, Blake3F64>(case)
+            } else {
+                measure::<F128Element, Blake3F128>(case)
+            }
+        })
+        .collect()
+}
+
+/// Returns a description of every timing in `current` that is more than `threshold` percent
+/// slower than the matching baseline timing.
+fn find_regressions(
+    baseline: &[CheckTimings],
+    current: &[CheckTimings],
+    threshold: f64,
+) -> Vec<String> {
+    let mut regressions = Vec::new();
+    for timings in current {
+        let Some(base) = baseline.iter().find(|b| b.name == timings.name) else {
+            println!("  {}: no baseline entry, skipping", timings.name);
+            continue;
+        };
+        for (metric, before, after) in [
+            ("commit", base.commit_ms, timings.commit_ms),
+            ("open", base.open_ms, timings.open_ms),
+            ("verify", base.verify_ms, timings.verify_ms),
+        ] {
+            let change = if before > 0.0 {
+                (after - before) / before * 100.0
+            } else {
+                0.0
+            };
+            println!(
+                "  {:<20} {:<7} {:>10.3} ms -> {:>10.3} ms ({:+.1}%)",
+                timings.name, metric, before, after, change
+            );
+            if change > threshold {
+                regressions.push(format!(
+                    "{} {}: {:.3} ms -> {:.3} ms ({:+.1}%)",
+                    timings.name, metric, before, after, change
+                ));
+            }
+        }
+    }
+    regressions
+}
+
+/// Runs the regression-check matrix. With `update` set the results are written to `baseline_path`;
+/// otherwise they are compared against it and the process exits with status 1 if any timing
+/// regressed by more than `threshold` percent, or 2 if the baseline cannot be read.
+pub fn run_check(baseline_path: &str, threshold: f64, update: bool) {
+    println!("Running regression check ({RUNS} runs per case, median timings)...");
+    let timings = run_matrix();
+
+    if update {
+        let metadata = RunMetadata::collect();
+        let baseline = Baseline {
+            git_commit: metadata.git_commit,
+            cpu_model: metadata.cpu_model,
+            num_threads: metadata.num_threads,
+            timings,
+        };
+        common::ensure_output_dir(baseline_path).expect("Failed to create baseline directory");
+        fs::write(
+            baseline_path,
+            serde_json::to_string_pretty(&baseline).expect("Failed to serialize baseline"),
+        )
+        .expect("Failed to save baseline");
+        println!("Baseline saved to: {baseline_path}");
+        return;
+    }
+
+    let baseline = match fs::read_to_string(baseline_path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str::<Baseline>(&s).map_err(|e| e.to_string()))
+    {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Failed to read baseline {baseline_path}: {e}");
+            eprintln!("Run with --update to record a baseline first");
+            std::process::exit(2);
+        }
+    };
+
+    println!(
+        "Comparing against baseline from {} ({}, {} threads), threshold {threshold}%",
+        baseline.git_commit, baseline.cpu_model, baseline.num_threads
+    );
+    let regressions = find_regressions(&baseline.timings, &timings, threshold);
+
+    if regressions.is_empty() {
+        println!("No regressions above {threshold}%");
+    } else {
+        eprintln!("Performance regressions above {threshold}%:");
+        for regression in &regressions {
+            eprintln!("  {regression}");
+        }
+        std::process::exit(1);
+    }
+}
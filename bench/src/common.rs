@@ -1,4 +1,13 @@
-use std::{fs, io::Write, path::Path};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use winter_math::{
     fields::{f128, f64},
     FieldElement,
@@ -49,30 +58,195 @@ pub fn ensure_output_dir(output_path: &str) -> std::io::Result<()> {
     Ok(())
 }
 
-/// Saves results with CSV header to file
+/// Serialization format of benchmark results
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// One JSON object per line, with the column schema written next to the output file
+    Jsonl,
+}
+
+/// Where and how benchmark results are written
+#[derive(Clone, Debug)]
+pub struct OutputConfig {
+    pub path: String,
+    pub format: OutputFormat,
+    /// Appends to an existing output file instead of overwriting it
+    pub append: bool,
+}
+
+/// Describes the machine and revision a benchmark run was taken on. These columns are added to
+/// every result row so that results from different runs can be compared after appending.
+#[derive(Clone, Debug, Serialize)]
+pub struct RunMetadata {
+    pub git_commit: String,
+    pub cpu_model: String,
+    pub num_threads: usize,
+    pub timestamp: u64,
+}
+
+impl RunMetadata {
+    pub fn collect() -> Self {
+        let git_commit = Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let cpu_model = fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .find(|line| line.starts_with("model name"))
+                    .and_then(|line| line.split_once(':'))
+                    .map(|(_, model)| model.trim().to_string())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        // Rayon honours RAYON_NUM_THREADS when the `concurrent` feature is enabled
+        let num_threads = std::env::var("RAYON_NUM_THREADS")
+            .ok()
+            .and_then(|threads| threads.parse().ok())
+            .filter(|&threads| threads > 0)
+            .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+            .unwrap_or(1);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            git_commit,
+            cpu_model,
+            num_threads,
+            timestamp,
+        }
+    }
+
+    fn csv_header() -> &'static str {
+        "git_commit,cpu_model,num_threads,timestamp"
+    }
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},\"{}\",{},{}",
+            self.git_commit,
+            self.cpu_model.replace('"', "\"\""),
+            self.num_threads,
+            self.timestamp
+        )
+    }
+}
+
+/// Saves results to file in the configured format, tagging every row with [RunMetadata]
 pub fn save_results_with_header<T>(
     results: &[T],
-    output_path: &str,
+    output: &OutputConfig,
     header: &str,
     to_csv: fn(&T) -> String,
-) -> std::io::Result<()>
+) -> io::Result<()>
 where
-    T: std::fmt::Debug,
+    T: Serialize,
 {
-    ensure_output_dir(output_path)?;
+    ensure_output_dir(&output.path)?;
 
-    let mut file = fs::File::create(output_path)?;
-    writeln!(file, "{header}")?;
+    let metadata = RunMetadata::collect();
+    let existing = output.append
+        && fs::metadata(&output.path)
+            .map(|m| m.len() > 0)
+            .unwrap_or(false);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(output.append)
+        .truncate(!output.append)
+        .open(&output.path)?;
 
-    for result in results {
-        writeln!(file, "{}", to_csv(result))?;
+    match output.format {
+        OutputFormat::Csv => {
+            let header = format!("{header},{}", RunMetadata::csv_header());
+            if existing {
+                check_csv_header(&output.path, &header)?;
+            } else {
+                writeln!(file, "{header}")?;
+            }
+            for result in results {
+                writeln!(file, "{},{}", to_csv(result), metadata.to_csv())?;
+            }
+        }
+        OutputFormat::Jsonl => {
+            let mut rows = Vec::with_capacity(results.len());
+            for result in results {
+                rows.push(json_row(result, &metadata)?);
+            }
+            if let Some(first) = rows.first() {
+                write_jsonl_schema(&output.path, first)?;
+            }
+            for row in rows {
+                writeln!(file, "{row}")?;
+            }
+        }
     }
 
-    println!("Results saved to: {output_path}");
+    let verb = if existing { "appended to" } else { "saved to" };
+    println!("Results {verb}: {}", output.path);
     println!("Total results: {}", results.len());
     Ok(())
 }
 
+/// Refuses to append rows to a CSV file whose columns differ from the ones being written
+fn check_csv_header(path: &str, header: &str) -> io::Result<()> {
+    let contents = fs::read_to_string(path)?;
+    match contents.lines().next() {
+        Some(existing) if existing == header => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("cannot append to {path}: existing header does not match"),
+        )),
+    }
+}
+
+fn json_row<T: Serialize>(result: &T, metadata: &RunMetadata) -> io::Result<Map<String, Value>> {
+    let to_object = |value: Value| match value {
+        Value::Object(map) => Ok(map),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "benchmark results must serialize to JSON objects",
+        )),
+    };
+    let mut row = to_object(serde_json::to_value(result)?)?;
+    row.extend(to_object(serde_json::to_value(metadata)?)?);
+    Ok(row)
+}
+
+/// Writes the column names and types of the JSON Lines output to `<path>.schema.json`
+fn write_jsonl_schema(path: &str, row: &Map<String, Value>) -> io::Result<()> {
+    let columns = row
+        .iter()
+        .map(|(name, value)| {
+            let kind = match value {
+                Value::Number(n) if n.is_f64() => "float",
+                Value::Number(_) => "integer",
+                Value::String(_) => "string",
+                Value::Bool(_) => "boolean",
+                _ => "json",
+            };
+            serde_json::json!({ "name": name, "type": kind })
+        })
+        .collect::<Vec<_>>();
+    let schema = serde_json::json!({ "format": "jsonl", "columns": columns });
+    fs::write(
+        format!("{path}.schema.json"),
+        serde_json::to_string_pretty(&schema)?,
+    )
+}
+
 pub mod field_names {
     pub const F64: &str = "f64";
     pub const F128: &str = "f128";
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::time::{Duration, Instant};
 use winter_crypto::ElementHasher;
 use winter_fri::FriOptions;
@@ -6,7 +7,10 @@ use winter_rand_utils::rand_vector;
 
 use frida_poc::{
     constants,
-    core::data::{build_evaluations_from_data, encoded_data_element_count},
+    core::{
+        assignment::compute_position_assignments,
+        data::{build_evaluations_from_data, encoded_data_element_count},
+    },
     prover::{
         batch_data_to_evaluations, builder::FridaProverBuilder, get_evaluations_from_positions,
     },
@@ -15,10 +19,11 @@ use frida_poc::{
 
 use crate::common::{
     self, field_names, get_standard_data_sizes, get_standard_fri_options,
-    get_standard_validator_counts, Blake3F128, Blake3F64, F128Element, F64Element, RUNS,
+    get_standard_validator_counts, Blake3F128, Blake3F64, F128Element, F64Element, OutputConfig,
+    RUNS,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct DefridaBenchmarkResult {
     field_type: String,
     batch_size: usize,
@@ -62,46 +67,6 @@ impl DefridaBenchmarkResult {
     }
 }
 
-fn compute_position_assignments(
-    n_validators: usize,
-    query_positions: &[usize],
-    h: usize,
-) -> Vec<Vec<usize>> {
-    let s = query_positions.len();
-    let n = n_validators;
-    if n == 0 {
-        return vec![];
-    }
-    if n <= s {
-        let span_length = s.saturating_sub(h) + 1;
-        (1..=n)
-            .map(|i| {
-                let offset = (i - 1) % s;
-                (0..span_length)
-                    .map(|j| query_positions[(offset + j) % s])
-                    .collect()
-            })
-            .collect()
-    } else {
-        let n_prime = (n / s) * s;
-        if n_prime == 0 {
-            return vec![Vec::new(); n];
-        }
-        let replication_factor = n_prime / s;
-        let h_prime = h.saturating_sub(n - n_prime).div_ceil(replication_factor);
-        let base_subsets = compute_position_assignments(s, query_positions, h_prime);
-        (1..=n)
-            .map(|i| {
-                if i <= n_prime {
-                    base_subsets[(i - 1) % s].clone()
-                } else {
-                    Vec::new()
-                }
-            })
-            .collect()
-    }
-}
-
 fn benchmark_non_batched<E, H>(
     options: FriOptions,
     data_size: usize,
@@ -138,14 +103,12 @@ where
         let h = f + 1;
         let validator_positions = compute_position_assignments(num_validators, &base_positions, h);
 
-        for positions in &validator_positions {
-            if !positions.is_empty() {
-                let start = Instant::now();
-                let proof = prover.open(positions);
-                total_proof_times += start.elapsed();
-                total_proof_sizes += proof.size();
-                total_proofs_generated += 1;
-            }
+        let start = Instant::now();
+        let validator_proofs = prover.open_for_validators(&validator_positions).unwrap();
+        total_proof_times += start.elapsed();
+        for proof in validator_proofs.iter().flatten() {
+            total_proof_sizes += proof.size();
+            total_proofs_generated += 1;
         }
 
         let all_evaluations = build_evaluations_from_data::<E>(
@@ -161,12 +124,15 @@ where
                 .expect("Verifier initialization failed");
         total_verification_setup_time += setup_start.elapsed();
 
-        if let Some(positions) = validator_positions.iter().find(|p| !p.is_empty()) {
+        let first_opening = validator_positions
+            .iter()
+            .zip(&validator_proofs)
+            .find_map(|(positions, proof)| Some((positions, proof.as_ref()?)));
+        if let Some((positions, proof)) = first_opening {
             let evaluations: Vec<E> = positions.iter().map(|&p| all_evaluations[p]).collect();
-            let proof = prover.open(positions);
 
             let verify_start = Instant::now();
-            verifier.verify(&proof, &evaluations, positions).unwrap();
+            verifier.verify(proof, &evaluations, positions).unwrap();
             total_verification_time += verify_start.elapsed();
         }
     }
@@ -239,14 +205,12 @@ where
         let h = f + 1;
         let validator_positions = compute_position_assignments(num_validators, &base_positions, h);
 
-        for positions in &validator_positions {
-            if !positions.is_empty() {
-                let start = Instant::now();
-                let proof = prover.open(positions);
-                total_proof_times += start.elapsed();
-                total_proof_sizes += proof.size();
-                total_proofs_generated += 1;
-            }
+        let start = Instant::now();
+        let validator_proofs = prover.open_for_validators(&validator_positions).unwrap();
+        total_proof_times += start.elapsed();
+        for proof in validator_proofs.iter().flatten() {
+            total_proof_sizes += proof.size();
+            total_proofs_generated += 1;
         }
 
         let blowup_factor = options.blowup_factor();
@@ -268,6 +232,7 @@ where
             domain_size,
             blowup_factor,
             options.folding_factor(),
+            &Default::default(),
         )
         .unwrap();
 
@@ -277,7 +242,11 @@ where
                 .expect("Verifier initialization failed");
         total_verification_setup_time += setup_start.elapsed();
 
-        if let Some(positions) = validator_positions.iter().find(|p| !p.is_empty()) {
+        let first_opening = validator_positions
+            .iter()
+            .zip(&validator_proofs)
+            .find_map(|(positions, proof)| Some((positions, proof.as_ref()?)));
+        if let Some((positions, proof)) = first_opening {
             let evaluations = get_evaluations_from_positions(
                 &all_evaluations,
                 positions,
@@ -285,10 +254,9 @@ where
                 domain_size,
                 options.folding_factor(),
             );
-            let proof = prover.open(positions);
 
             let verify_start = Instant::now();
-            verifier.verify(&proof, &evaluations, positions).unwrap();
+            verifier.verify(proof, &evaluations, positions).unwrap();
             total_verification_time += verify_start.elapsed();
         }
     }
@@ -320,7 +288,7 @@ where
     }
 }
 
-pub fn run_full_benchmark(output_path: &str) {
+pub fn run_full_benchmark(output: &OutputConfig) {
     let fri_options = get_standard_fri_options();
     let data_sizes_f64 = get_standard_data_sizes::<F64Element>();
     let data_sizes_f128 = get_standard_data_sizes::<F128Element>();
@@ -407,7 +375,7 @@ pub fn run_full_benchmark(output_path: &str) {
 
     common::save_results_with_header(
         &results,
-        output_path,
+        output,
         &DefridaBenchmarkResult::csv_header(),
         |r| r.to_csv(),
     )
@@ -426,7 +394,7 @@ pub struct CustomDefridaBenchmarkConfig<'a> {
     pub num_validators: usize,
     pub num_queries: usize,
     pub batch_size: usize,
-    pub output_path: &'a str,
+    pub output: &'a OutputConfig,
 }
 
 pub fn run_custom_benchmark(config: CustomDefridaBenchmarkConfig) {
@@ -491,7 +459,7 @@ pub fn run_custom_benchmark(config: CustomDefridaBenchmarkConfig) {
 
     common::save_results_with_header(
         &results,
-        config.output_path,
+        config.output,
         &DefridaBenchmarkResult::csv_header(),
         |r| r.to_csv(),
     )
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::time::{Duration, Instant};
 use winter_crypto::ElementHasher;
 use winter_fri::FriOptions;
@@ -8,17 +9,17 @@ use frida_poc::{
     prover::{
         bench::{COMMIT_TIME, ERASURE_TIME},
         builder::FridaProverBuilder,
-        get_evaluations_from_positions, Commitment,
+        Commitment,
     },
     verifier::das::FridaDasVerifier,
 };
 
 use crate::common::{
     self, field_names, get_standard_batch_sizes, get_standard_data_sizes, get_standard_fri_options,
-    get_standard_num_queries, Blake3F128, Blake3F64, F128Element, F64Element, RUNS,
+    get_standard_num_queries, Blake3F128, Blake3F64, F128Element, F64Element, OutputConfig, RUNS,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct FridaBenchmarkResult {
     field_type: String,
     batch_size: usize,
@@ -112,29 +113,21 @@ where
             .map(|v| (v as usize) % com.domain_size)
             .collect::<Vec<_>>();
 
-        let evaluations = positions
-            .iter()
-            .map(|pos| {
-                prover.get_first_layer_evaluations()[(pos
-                    % (com.domain_size / options.folding_factor()))
-                    * options.folding_factor()
-                    + (pos / (com.domain_size / options.folding_factor()))]
-            })
-            .collect::<Vec<_>>();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
 
         // Benchmark proof generation for different position counts
         let timer = Instant::now();
-        let proof_1 = prover.open(&positions[0..1]);
+        let proof_1 = prover.open(&positions[0..1]).unwrap();
         total_proof_sizes.0 += proof_1.size();
         total_proof_times.0 += timer.elapsed();
 
         let timer = Instant::now();
-        let proof_16 = prover.open(&positions[0..16]);
+        let proof_16 = prover.open(&positions[0..16]).unwrap();
         total_proof_sizes.1 += proof_16.size();
         total_proof_times.1 += timer.elapsed();
 
         let timer = Instant::now();
-        let proof_32 = prover.open(&positions);
+        let proof_32 = prover.open(&positions).unwrap();
         total_proof_sizes.2 += proof_32.size();
         total_proof_times.2 += timer.elapsed();
 
@@ -239,27 +232,21 @@ where
             .map(|v| (v as usize) % com.domain_size)
             .collect::<Vec<_>>();
 
-        let evaluations = get_evaluations_from_positions(
-            prover.get_first_layer_evaluations(),
-            &positions,
-            batch_size,
-            com.domain_size,
-            options.folding_factor(),
-        );
+        let evaluations = prover.get_evaluations(&positions).unwrap();
 
         // Benchmark proof generation
         let timer = Instant::now();
-        let proof_1 = prover.open(&positions[0..1]);
+        let proof_1 = prover.open(&positions[0..1]).unwrap();
         total_proof_sizes.0 += proof_1.size();
         total_proof_times.0 += timer.elapsed();
 
         let timer = Instant::now();
-        let proof_16 = prover.open(&positions[0..16]);
+        let proof_16 = prover.open(&positions[0..16]).unwrap();
         total_proof_sizes.1 += proof_16.size();
         total_proof_times.1 += timer.elapsed();
 
         let timer = Instant::now();
-        let proof_32 = prover.open(&positions);
+        let proof_32 = prover.open(&positions).unwrap();
         total_proof_sizes.2 += proof_32.size();
         total_proof_times.2 += timer.elapsed();
 
@@ -320,7 +307,7 @@ where
     }
 }
 
-pub fn run_full_benchmark(output_path: &str) {
+pub fn run_full_benchmark(output: &OutputConfig) {
     let fri_options = get_standard_fri_options();
     let data_sizes_f64 = get_standard_data_sizes::<F64Element>();
     let data_sizes_f128 = get_standard_data_sizes::<F128Element>();
@@ -391,12 +378,9 @@ pub fn run_full_benchmark(output_path: &str) {
         }
     }
 
-    common::save_results_with_header(
-        &results,
-        output_path,
-        &FridaBenchmarkResult::csv_header(),
-        |r| r.to_csv(),
-    )
+    common::save_results_with_header(&results, output, &FridaBenchmarkResult::csv_header(), |r| {
+        r.to_csv()
+    })
     .expect("Failed to save results");
     println!(
         "Frida benchmark completed with {} successful results",
@@ -411,7 +395,7 @@ pub fn run_custom_benchmark(
     data_size: usize,
     batch_size: usize,
     num_queries: usize,
-    output_path: &str,
+    output: &OutputConfig,
 ) {
     let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
     let mut results = Vec::new();
@@ -463,12 +447,9 @@ pub fn run_custom_benchmark(
         results.push(result_f128);
     }
 
-    common::save_results_with_header(
-        &results,
-        output_path,
-        &FridaBenchmarkResult::csv_header(),
-        |r| r.to_csv(),
-    )
+    common::save_results_with_header(&results, output, &FridaBenchmarkResult::csv_header(), |r| {
+        r.to_csv()
+    })
     .expect("Failed to save results");
     println!("Custom Frida benchmark completed successfully");
 }
@@ -0,0 +1,340 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use winter_crypto::{
+    hashers::{Blake3_192, Blake3_256, Rp64_256, RpJive64_256, Sha3_256},
+    ElementHasher,
+};
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::Serializable;
+
+use frida_poc::{prover::builder::FridaProverBuilder, verifier::das::FridaDasVerifier};
+
+use crate::common::{
+    self, field_names, get_standard_data_sizes, get_standard_fri_options, F128Element, F64Element,
+    OutputConfig, RUNS,
+};
+
+/// Number of positions opened for the multi-position proof measurements
+const MULTI_OPEN_POSITIONS: usize = 32;
+
+#[derive(Debug, Serialize)]
+struct HasherBenchmarkResult {
+    field_type: String,
+    hasher: String,
+    digest_size_bytes: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    data_size_kb: usize,
+    num_queries: usize,
+    commitment_time_ms: f64,
+    commitment_size_bytes: usize,
+    proof_time_1_ms: f64,
+    proof_time_32_ms: f64,
+    proof_size_1_bytes: usize,
+    proof_size_32_bytes: usize,
+    verification_setup_ms: f64,
+    verification_1_ms: f64,
+    verification_32_ms: f64,
+}
+
+impl HasherBenchmarkResult {
+    fn csv_header() -> String {
+        "field_type,hasher,digest_size_bytes,blowup_factor,folding_factor,max_remainder_degree,data_size_kb,num_queries,commitment_time_ms,commitment_size_bytes,proof_time_1_ms,proof_time_32_ms,proof_size_1_bytes,proof_size_32_bytes,verification_setup_ms,verification_1_ms,verification_32_ms".to_string()
+    }
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{:.3},{},{:.3},{:.3},{},{},{:.3},{:.3},{:.3}",
+            self.field_type,
+            self.hasher,
+            self.digest_size_bytes,
+            self.blowup_factor,
+            self.folding_factor,
+            self.max_remainder_degree,
+            self.data_size_kb,
+            self.num_queries,
+            self.commitment_time_ms,
+            self.commitment_size_bytes,
+            self.proof_time_1_ms,
+            self.proof_time_32_ms,
+            self.proof_size_1_bytes,
+            self.proof_size_32_bytes,
+            self.verification_setup_ms,
+            self.verification_1_ms,
+            self.verification_32_ms
+        )
+    }
+}
+
+pub mod hasher_names {
+    pub const BLAKE3_256: &str = "blake3_256";
+    pub const BLAKE3_192: &str = "blake3_192";
+    pub const SHA3_256: &str = "sha3_256";
+    pub const RP64_256: &str = "rp64_256";
+    pub const RP_JIVE64_256: &str = "rp_jive64_256";
+}
+
+fn benchmark_hasher<E, H>(
+    options: FriOptions,
+    data_size: usize,
+    num_queries: usize,
+    field_name: &str,
+    hasher_name: &str,
+) -> HasherBenchmarkResult
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let mut total_commitment_time = Duration::ZERO;
+    let mut total_proof_times = (Duration::ZERO, Duration::ZERO);
+    let mut total_verify_times = (Duration::ZERO, Duration::ZERO, Duration::ZERO);
+    let mut total_commitment_size = 0;
+    let mut total_proof_sizes = (0, 0);
+    let mut digest_size = 0;
+
+    for _ in 0..RUNS {
+        let data = rand_vector::<u8>(data_size);
+        let prover_builder = FridaProverBuilder::<E, H>::new(options.clone());
+
+        let timer = Instant::now();
+        let (com, prover) = prover_builder.commit_and_prove(&data, num_queries).unwrap();
+        total_commitment_time += timer.elapsed();
+
+        digest_size = com.roots[0].to_bytes().len();
+        total_commitment_size += com.proof.size() + com.roots.len() * digest_size + 3;
+
+        let positions = rand_vector::<u64>(MULTI_OPEN_POSITIONS)
+            .into_iter()
+            .map(|v| (v as usize) % com.domain_size)
+            .collect::<Vec<_>>();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+
+        let timer = Instant::now();
+        let proof_1 = prover.open(&positions[0..1]).unwrap();
+        total_proof_times.0 += timer.elapsed();
+        total_proof_sizes.0 += proof_1.size();
+
+        let timer = Instant::now();
+        let proof_32 = prover.open(&positions).unwrap();
+        total_proof_times.1 += timer.elapsed();
+        total_proof_sizes.1 += proof_32.size();
+
+        let timer = Instant::now();
+        let (verifier, _coin) = FridaDasVerifier::<E, H, H>::new(com, options.clone()).unwrap();
+        total_verify_times.0 += timer.elapsed();
+
+        let timer = Instant::now();
+        verifier
+            .verify(&proof_1, &evaluations[0..1], &positions[0..1])
+            .unwrap();
+        total_verify_times.1 += timer.elapsed();
+
+        let timer = Instant::now();
+        verifier
+            .verify(&proof_32, &evaluations, &positions)
+            .unwrap();
+        total_verify_times.2 += timer.elapsed();
+    }
+
+    HasherBenchmarkResult {
+        field_type: field_name.to_string(),
+        hasher: hasher_name.to_string(),
+        digest_size_bytes: digest_size,
+        blowup_factor: options.blowup_factor(),
+        folding_factor: options.folding_factor(),
+        max_remainder_degree: options.remainder_max_degree(),
+        data_size_kb: data_size / 1024,
+        num_queries,
+        commitment_time_ms: total_commitment_time.as_secs_f64() * 1000.0 / RUNS as f64,
+        commitment_size_bytes: total_commitment_size / RUNS,
+        proof_time_1_ms: total_proof_times.0.as_secs_f64() * 1000.0 / RUNS as f64,
+        proof_time_32_ms: total_proof_times.1.as_secs_f64() * 1000.0 / RUNS as f64,
+        proof_size_1_bytes: total_proof_sizes.0 / RUNS,
+        proof_size_32_bytes: total_proof_sizes.1 / RUNS,
+        verification_setup_ms: total_verify_times.0.as_secs_f64() * 1000.0 / RUNS as f64,
+        verification_1_ms: total_verify_times.1.as_secs_f64() * 1000.0 / RUNS as f64,
+        verification_32_ms: total_verify_times.2.as_secs_f64() * 1000.0 / RUNS as f64,
+    }
+}
+
+/// Runs every supported hasher for one set of parameters. The algebraic Rescue hashers are only
+/// defined over the 64-bit field, so f128 is swept over the byte-oriented hashers alone.
+fn sweep_hashers(
+    options: &FriOptions,
+    data_size_f64: usize,
+    data_size_f128: usize,
+    num_queries: usize,
+    results: &mut Vec<HasherBenchmarkResult>,
+) {
+    macro_rules! run {
+        ($field:ty, $hasher:ty, $data_size:expr, $field_name:expr, $hasher_name:expr) => {
+            match std::panic::catch_unwind(|| {
+                benchmark_hasher::<$field, $hasher>(
+                    options.clone(),
+                    $data_size,
+                    num_queries,
+                    $field_name,
+                    $hasher_name,
+                )
+            }) {
+                Ok(result) => results.push(result),
+                Err(_) => println!(
+                    "Skipping {} / {}: benchmark failed for folding factor {}",
+                    $field_name,
+                    $hasher_name,
+                    options.folding_factor()
+                ),
+            }
+        };
+    }
+
+    use hasher_names::*;
+    run!(
+        F64Element,
+        Blake3_256<F64Element>,
+        data_size_f64,
+        field_names::F64,
+        BLAKE3_256
+    );
+    run!(
+        F64Element,
+        Blake3_192<F64Element>,
+        data_size_f64,
+        field_names::F64,
+        BLAKE3_192
+    );
+    run!(
+        F64Element,
+        Sha3_256<F64Element>,
+        data_size_f64,
+        field_names::F64,
+        SHA3_256
+    );
+    run!(
+        F64Element,
+        Rp64_256,
+        data_size_f64,
+        field_names::F64,
+        RP64_256
+    );
+    run!(
+        F64Element,
+        RpJive64_256,
+        data_size_f64,
+        field_names::F64,
+        RP_JIVE64_256
+    );
+    run!(
+        F128Element,
+        Blake3_256<F128Element>,
+        data_size_f128,
+        field_names::F128,
+        BLAKE3_256
+    );
+    run!(
+        F128Element,
+        Blake3_192<F128Element>,
+        data_size_f128,
+        field_names::F128,
+        BLAKE3_192
+    );
+    run!(
+        F128Element,
+        Sha3_256<F128Element>,
+        data_size_f128,
+        field_names::F128,
+        SHA3_256
+    );
+}
+
+pub fn run_full_benchmark(output: &OutputConfig) {
+    let fri_options = get_standard_fri_options();
+    let data_sizes_f64 = get_standard_data_sizes::<F64Element>();
+    let data_sizes_f128 = get_standard_data_sizes::<F128Element>();
+    let num_queries = 32;
+
+    let mut results = Vec::new();
+
+    println!("Running full hasher sweep benchmark...");
+    println!(
+        "Configurations: {} FRI options × {} data sizes × 8 field/hasher pairs",
+        fri_options.len(),
+        data_sizes_f64.len()
+    );
+
+    for &(blowup_factor, folding_factor, max_remainder_degree) in &fri_options {
+        let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+
+        for (&data_size_f64, &data_size_f128) in data_sizes_f64.iter().zip(data_sizes_f128.iter()) {
+            sweep_hashers(
+                &options,
+                data_size_f64,
+                data_size_f128,
+                num_queries,
+                &mut results,
+            );
+        }
+    }
+
+    common::save_results_with_header(
+        &results,
+        output,
+        &HasherBenchmarkResult::csv_header(),
+        |r| r.to_csv(),
+    )
+    .expect("Failed to save results");
+    println!(
+        "Hasher sweep benchmark completed with {} successful results",
+        results.len()
+    );
+}
+
+pub fn run_custom_benchmark(
+    blowup_factor: usize,
+    max_remainder_degree: usize,
+    data_size: usize,
+    num_queries: usize,
+    folding_factors: &[usize],
+    output: &OutputConfig,
+) {
+    let mut results = Vec::new();
+
+    println!("Running custom hasher sweep benchmark...");
+    println!(
+        "Parameters: blowup={}, folding={:?}, remainder={}, data={}KB, queries={}",
+        blowup_factor,
+        folding_factors,
+        max_remainder_degree,
+        data_size / 1024,
+        num_queries
+    );
+
+    for &folding_factor in folding_factors {
+        let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+        sweep_hashers(&options, data_size, data_size, num_queries, &mut results);
+    }
+
+    common::save_results_with_header(
+        &results,
+        output,
+        &HasherBenchmarkResult::csv_header(),
+        |r| r.to_csv(),
+    )
+    .expect("Failed to save results");
+    println!("Custom hasher sweep benchmark completed successfully");
+
+    println!("\nResults Summary:");
+    for result in &results {
+        println!(
+            "  {} / {} (folding {}): 32-position proof = {} bytes, verify = {:.3} ms",
+            result.field_type,
+            result.hasher,
+            result.folding_factor,
+            result.proof_size_32_bytes,
+            result.verification_32_ms
+        );
+    }
+}
@@ -1,11 +1,17 @@
 #![cfg(feature = "bench")]
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use common::{OutputConfig, OutputFormat};
 
+mod calibrate;
+mod check;
 mod common;
 mod defrida;
 mod frida;
+mod hashers;
+mod parallelism;
 mod single_frida;
+mod throughput;
 
 #[derive(Parser)]
 #[command(name = "frida-bench")]
@@ -32,6 +38,73 @@ enum Commands {
         #[command(subcommand)]
         subcommand: DefridaSubcommand,
     },
+    /// Proof size and verification time across Merkle hashers and folding factors
+    Hashers {
+        #[command(subcommand)]
+        subcommand: HashersSubcommand,
+    },
+    /// Verifier throughput with proofs checked concurrently against one commitment
+    Throughput {
+        #[command(subcommand)]
+        subcommand: ThroughputSubcommand,
+    },
+    /// Regression check of commit/open/verify times against a stored baseline
+    Check {
+        /// Baseline JSON file to compare against (or to write with --update)
+        #[arg(long, default_value = "bench/baselines/check.json")]
+        baseline: String,
+        /// Maximum allowed slowdown in percent before the check fails
+        #[arg(long, default_value = "10")]
+        threshold: f64,
+        /// Record the current timings as the new baseline instead of comparing
+        #[arg(long)]
+        update: bool,
+    },
+    /// Measure the coefficients of the commitment cost model on this machine
+    Calibrate {
+        #[arg(long, default_value = "2")]
+        blowup_factor: usize,
+        #[arg(long, default_value = "4")]
+        folding_factor: usize,
+        #[arg(long, default_value = "3")]
+        max_remainder_degree: usize,
+    },
+    /// Batched commitment time across parallel task sizes
+    Parallelism {
+        #[arg(long, default_value = "2")]
+        blowup_factor: usize,
+        #[arg(long, default_value = "2")]
+        folding_factor: usize,
+        #[arg(long, default_value = "255")]
+        max_remainder_degree: usize,
+        /// Comma-separated sizes of every blob of the batch, in bytes
+        #[arg(long, value_delimiter = ',', default_value = "4096,65536,262144")]
+        data_sizes: Vec<usize>,
+        /// Comma-separated numbers of blobs per batch
+        #[arg(long, value_delimiter = ',', default_value = "2,16,64")]
+        batch_sizes: Vec<usize>,
+    },
+}
+
+/// Output options shared by every benchmark
+#[derive(Args)]
+struct OutputFlags {
+    /// Format of the results file
+    #[arg(long, value_enum, default_value_t = OutputFormat::Csv)]
+    format: OutputFormat,
+    /// Append results to the output file instead of overwriting it
+    #[arg(long)]
+    append: bool,
+}
+
+impl OutputFlags {
+    fn into_config(self, path: String) -> OutputConfig {
+        OutputConfig {
+            path,
+            format: self.format,
+            append: self.append,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -39,6 +112,8 @@ enum BenchmarkSubcommand {
     Full {
         #[arg(long, default_value = "bench/results/frida_full.csv")]
         output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
     },
     Custom {
         #[arg(long)]
@@ -55,6 +130,8 @@ enum BenchmarkSubcommand {
         num_queries: usize,
         #[arg(long, default_value = "bench/results/frida_custom.csv")]
         output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
     },
 }
 
@@ -63,6 +140,8 @@ enum SingleFridaSubcommand {
     Full {
         #[arg(long, default_value = "bench/results/single_frida_full.csv")]
         output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
     },
     Custom {
         #[arg(long)]
@@ -77,6 +156,8 @@ enum SingleFridaSubcommand {
         batch_size: usize,
         #[arg(long, default_value = "bench/results/single_frida_custom.csv")]
         output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
     },
 }
 
@@ -85,6 +166,8 @@ enum DefridaSubcommand {
     Full {
         #[arg(long, default_value = "bench/results/defrida_full.csv")]
         output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
     },
     Custom {
         #[arg(long)]
@@ -103,6 +186,70 @@ enum DefridaSubcommand {
         batch_size: usize,
         #[arg(long, default_value = "bench/results/defrida_custom.csv")]
         output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
+    },
+}
+
+#[derive(Subcommand)]
+enum HashersSubcommand {
+    Full {
+        #[arg(long, default_value = "bench/results/hashers_full.csv")]
+        output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
+    },
+    Custom {
+        #[arg(long)]
+        blowup_factor: usize,
+        #[arg(long)]
+        max_remainder_degree: usize,
+        #[arg(long)]
+        data_size: usize,
+        #[arg(long, default_value = "32")]
+        num_queries: usize,
+        /// Comma-separated folding factors to sweep
+        #[arg(long, value_delimiter = ',', default_value = "2,4,8,16")]
+        folding_factors: Vec<usize>,
+        #[arg(long, default_value = "bench/results/hashers_custom.csv")]
+        output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
+    },
+}
+
+#[derive(Subcommand)]
+enum ThroughputSubcommand {
+    Full {
+        #[arg(long, default_value = "bench/results/throughput_full.csv")]
+        output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
+    },
+    Custom {
+        #[arg(long)]
+        blowup_factor: usize,
+        #[arg(long)]
+        folding_factor: usize,
+        #[arg(long)]
+        max_remainder_degree: usize,
+        #[arg(long)]
+        data_size: usize,
+        #[arg(long, default_value = "1")]
+        batch_size: usize,
+        /// Number of proofs verified per thread count
+        #[arg(long, default_value = "1024")]
+        num_proofs: usize,
+        /// Number of positions opened by each proof
+        #[arg(long, default_value = "1")]
+        positions_per_proof: usize,
+        /// Comma-separated thread counts (defaults to powers of two up to the available parallelism)
+        #[arg(long, value_delimiter = ',')]
+        threads: Vec<usize>,
+        #[arg(long, default_value = "bench/results/throughput_custom.csv")]
+        output: String,
+        #[command(flatten)]
+        flags: OutputFlags,
     },
 }
 
@@ -111,8 +258,8 @@ fn main() {
 
     match cli.command {
         Commands::Frida { subcommand } => match subcommand {
-            BenchmarkSubcommand::Full { output } => {
-                frida::run_full_benchmark(&output);
+            BenchmarkSubcommand::Full { output, flags } => {
+                frida::run_full_benchmark(&flags.into_config(output));
             }
             BenchmarkSubcommand::Custom {
                 blowup_factor,
@@ -122,7 +269,9 @@ fn main() {
                 batch_size,
                 num_queries,
                 output,
+                flags,
             } => {
+                let output = flags.into_config(output);
                 frida::run_custom_benchmark(
                     blowup_factor,
                     folding_factor,
@@ -135,8 +284,8 @@ fn main() {
             }
         },
         Commands::SingleFrida { subcommand } => match subcommand {
-            SingleFridaSubcommand::Full { output } => {
-                single_frida::run_full_benchmark(&output);
+            SingleFridaSubcommand::Full { output, flags } => {
+                single_frida::run_full_benchmark(&flags.into_config(output));
             }
             SingleFridaSubcommand::Custom {
                 blowup_factor,
@@ -145,7 +294,9 @@ fn main() {
                 data_size,
                 batch_size,
                 output,
+                flags,
             } => {
+                let output = flags.into_config(output);
                 single_frida::run_custom_benchmark(
                     blowup_factor,
                     folding_factor,
@@ -157,8 +308,8 @@ fn main() {
             }
         },
         Commands::Defrida { subcommand } => match subcommand {
-            DefridaSubcommand::Full { output } => {
-                defrida::run_full_benchmark(&output);
+            DefridaSubcommand::Full { output, flags } => {
+                defrida::run_full_benchmark(&flags.into_config(output));
             }
             DefridaSubcommand::Custom {
                 blowup_factor,
@@ -169,7 +320,9 @@ fn main() {
                 num_queries,
                 batch_size,
                 output,
+                flags,
             } => {
+                let output = flags.into_config(output);
                 let config = defrida::CustomDefridaBenchmarkConfig {
                     blowup_factor,
                     folding_factor,
@@ -178,10 +331,99 @@ fn main() {
                     num_validators,
                     num_queries,
                     batch_size,
-                    output_path: &output,
+                    output: &output,
                 };
                 defrida::run_custom_benchmark(config);
             }
         },
+        Commands::Hashers { subcommand } => match subcommand {
+            HashersSubcommand::Full { output, flags } => {
+                hashers::run_full_benchmark(&flags.into_config(output));
+            }
+            HashersSubcommand::Custom {
+                blowup_factor,
+                max_remainder_degree,
+                data_size,
+                num_queries,
+                folding_factors,
+                output,
+                flags,
+            } => {
+                let output = flags.into_config(output);
+                hashers::run_custom_benchmark(
+                    blowup_factor,
+                    max_remainder_degree,
+                    data_size,
+                    num_queries,
+                    &folding_factors,
+                    &output,
+                );
+            }
+        },
+        Commands::Throughput { subcommand } => match subcommand {
+            ThroughputSubcommand::Full { output, flags } => {
+                throughput::run_full_benchmark(&flags.into_config(output));
+            }
+            ThroughputSubcommand::Custom {
+                blowup_factor,
+                folding_factor,
+                max_remainder_degree,
+                data_size,
+                batch_size,
+                num_proofs,
+                positions_per_proof,
+                threads,
+                output,
+                flags,
+            } => {
+                let output = flags.into_config(output);
+                let thread_counts = if threads.is_empty() {
+                    throughput::default_thread_counts()
+                } else {
+                    threads
+                };
+                let config = throughput::CustomThroughputBenchmarkConfig {
+                    blowup_factor,
+                    folding_factor,
+                    max_remainder_degree,
+                    data_size,
+                    batch_size,
+                    num_proofs,
+                    positions_per_proof,
+                    thread_counts: &thread_counts,
+                    output: &output,
+                };
+                throughput::run_custom_benchmark(config);
+            }
+        },
+        Commands::Check {
+            baseline,
+            threshold,
+            update,
+        } => {
+            check::run_check(&baseline, threshold, update);
+        }
+        Commands::Calibrate {
+            blowup_factor,
+            folding_factor,
+            max_remainder_degree,
+        } => {
+            calibrate::run_calibration(blowup_factor, folding_factor, max_remainder_degree);
+        }
+        Commands::Parallelism {
+            blowup_factor,
+            folding_factor,
+            max_remainder_degree,
+            data_sizes,
+            batch_sizes,
+        } => {
+            parallelism::run_parallelism_benchmark(
+                blowup_factor,
+                folding_factor,
+                max_remainder_degree,
+                &data_sizes,
+                &batch_sizes,
+            );
+        }
     }
 }
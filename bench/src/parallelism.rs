@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+use winter_fri::FriOptions;
+use winter_rand_utils::rand_vector;
+
+use frida_poc::{prover::builder::FridaProverBuilder, utils::parallel::ParallelOptions};
+
+use crate::common::{Blake3F128, F128Element, RUNS};
+
+/// Number of rows per task previously hardcoded for every loop over the batch layer
+const LEGACY_ROWS_PER_TASK: usize = 1024;
+
+fn median_ms(mut samples: Vec<Duration>) -> f64 {
+    samples.sort();
+    samples[samples.len() / 2].as_secs_f64() * 1e3
+}
+
+/// Options compared for every configuration: the auto-tuned default, the legacy fixed split,
+/// and a few fixed splits around it.
+fn compared_options() -> Vec<(String, ParallelOptions)> {
+    let mut options = vec![("auto".to_string(), ParallelOptions::default())];
+    for rows_per_task in [16, 128, LEGACY_ROWS_PER_TASK, 8192] {
+        options.push((
+            format!("fixed {rows_per_task}"),
+            ParallelOptions::fixed(rows_per_task),
+        ));
+    }
+    options
+}
+
+/// Measures the batched commitment time of random data under every compared parallelism option,
+/// for every blob size and number of polynomials, with f128 and Blake3_256.
+pub fn run_parallelism_benchmark(
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    data_sizes: &[usize],
+    batch_sizes: &[usize],
+) {
+    if cfg!(not(feature = "concurrent")) {
+        println!("Note: built without the `concurrent` feature, every loop runs sequentially.");
+    }
+    let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+    println!(
+        "Batched commitment time (blowup={blowup_factor}, folding={folding_factor}, remainder={max_remainder_degree}, {RUNS} runs per measurement):"
+    );
+    for &data_size in data_sizes {
+        for &batch_size in batch_sizes {
+            let data_list = (0..batch_size)
+                .map(|_| rand_vector::<u8>(data_size))
+                .collect::<Vec<_>>();
+            println!("  {:>5}KB x {batch_size}:", data_size / 1024);
+            for (name, parallelism) in compared_options() {
+                let prover_builder =
+                    FridaProverBuilder::<F128Element, Blake3F128>::new(options.clone())
+                        .with_parallelism(parallelism);
+                let samples = (0..RUNS)
+                    .map(|_| {
+                        let timer = Instant::now();
+                        prover_builder.commitment_batch(&data_list, 32).unwrap();
+                        timer.elapsed()
+                    })
+                    .collect();
+                println!("    {name:>12}: {:>9.3} ms", median_ms(samples));
+            }
+        }
+    }
+}
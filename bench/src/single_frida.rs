@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::time::{Duration, Instant};
 use winter_crypto::ElementHasher;
 use winter_fri::FriOptions;
@@ -8,9 +9,11 @@ use frida_poc::{
     constants, core::data::encoded_data_element_count, prover::builder::FridaProverBuilder,
 };
 
-use crate::common::{self, field_names, Blake3F128, Blake3F64, F128Element, F64Element, RUNS};
+use crate::common::{
+    self, field_names, Blake3F128, Blake3F64, F128Element, F64Element, OutputConfig, RUNS,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct SingleFridaBenchmarkResult {
     field_type: String,
     batch_size: usize,
@@ -75,7 +78,7 @@ where
         let drawn_position = vec![base_positions[0]];
 
         let start = Instant::now();
-        let proof = prover.open(&drawn_position);
+        let proof = prover.open(&drawn_position).unwrap();
         total_proof_time += start.elapsed();
         total_proof_size += proof.size();
     }
@@ -133,7 +136,7 @@ where
         let drawn_position = vec![base_positions[0]];
 
         let start = Instant::now();
-        let proof = prover.open(&drawn_position);
+        let proof = prover.open(&drawn_position).unwrap();
         total_proof_time += start.elapsed();
         total_proof_size += proof.size();
     }
@@ -157,7 +160,7 @@ where
     }
 }
 
-pub fn run_full_benchmark(output_path: &str) {
+pub fn run_full_benchmark(output: &OutputConfig) {
     let fri_options = vec![
         (2, 2, 0),
         (2, 2, 256),
@@ -251,7 +254,7 @@ pub fn run_full_benchmark(output_path: &str) {
 
     common::save_results_with_header(
         &results,
-        output_path,
+        output,
         &SingleFridaBenchmarkResult::csv_header(),
         |r| r.to_csv(),
     )
@@ -268,7 +271,7 @@ pub fn run_custom_benchmark(
     max_remainder_degree: usize,
     data_size: usize,
     batch_size: usize,
-    output_path: &str,
+    output: &OutputConfig,
 ) {
     let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
     let mut results = Vec::new();
@@ -321,7 +324,7 @@ pub fn run_custom_benchmark(
 
     common::save_results_with_header(
         &results,
-        output_path,
+        output,
         &SingleFridaBenchmarkResult::csv_header(),
         |r| r.to_csv(),
     )
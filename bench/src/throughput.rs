@@ -0,0 +1,295 @@
+use serde::Serialize;
+use std::time::Instant;
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_rand_utils::rand_vector;
+
+use frida_poc::{
+    prover::{builder::FridaProverBuilder, proof::FridaProof},
+    verifier::das::FridaDasVerifier,
+};
+
+use crate::common::{
+    self, field_names, get_standard_data_sizes, get_standard_fri_options, Blake3F128, Blake3F64,
+    F128Element, F64Element, OutputConfig,
+};
+
+#[derive(Debug, Serialize)]
+struct ThroughputBenchmarkResult {
+    field_type: String,
+    batch_size: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    data_size_kb: usize,
+    num_proofs: usize,
+    positions_per_proof: usize,
+    num_threads: usize,
+    total_time_ms: f64,
+    proofs_per_second: f64,
+    samples_per_second: f64,
+}
+
+impl ThroughputBenchmarkResult {
+    fn csv_header() -> String {
+        "field_type,batch_size,blowup_factor,folding_factor,max_remainder_degree,data_size_kb,num_proofs,positions_per_proof,num_threads,total_time_ms,proofs_per_second,samples_per_second".to_string()
+    }
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{:.3},{:.1},{:.1}",
+            self.field_type,
+            self.batch_size,
+            self.blowup_factor,
+            self.folding_factor,
+            self.max_remainder_degree,
+            self.data_size_kb,
+            self.num_proofs,
+            self.positions_per_proof,
+            self.num_threads,
+            self.total_time_ms,
+            self.proofs_per_second,
+            self.samples_per_second
+        )
+    }
+}
+
+/// Proofs opened against a single commitment, together with what is needed to verify them
+struct Sample<E> {
+    proof: FridaProof,
+    evaluations: Vec<E>,
+    positions: Vec<usize>,
+}
+
+/// Returns the thread counts swept by the full benchmark: powers of two up to the available
+/// parallelism, plus the available parallelism itself.
+pub fn default_thread_counts() -> Vec<usize> {
+    let available = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let mut counts = std::iter::successors(Some(1usize), |n| Some(n * 2))
+        .take_while(|&n| n < available)
+        .collect::<Vec<_>>();
+    counts.push(available);
+    counts
+}
+
+/// Commits to random data, opens `num_proofs` proofs and measures how long `num_threads` threads
+/// sharing one verifier take to verify all of them. One result is returned per thread count.
+fn benchmark_throughput<E, H>(
+    options: FriOptions,
+    data_size: usize,
+    batch_size: usize,
+    num_proofs: usize,
+    positions_per_proof: usize,
+    thread_counts: &[usize],
+    field_name: &str,
+) -> Vec<ThroughputBenchmarkResult>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField> + Sync,
+{
+    let data_list = (0..batch_size)
+        .map(|_| rand_vector::<u8>(data_size))
+        .collect::<Vec<_>>();
+    let prover_builder = FridaProverBuilder::<E, H>::new(options.clone());
+    let (com, prover) = if batch_size == 1 {
+        prover_builder.commit_and_prove(&data_list[0], 32)
+    } else {
+        prover_builder.commit_and_prove_batch(&data_list, 32)
+    }
+    .unwrap();
+    let domain_size = com.domain_size;
+
+    let samples = (0..num_proofs)
+        .map(|_| {
+            let positions = rand_vector::<u64>(positions_per_proof)
+                .into_iter()
+                .map(|v| (v as usize) % domain_size)
+                .collect::<Vec<_>>();
+            let evaluations = prover.get_evaluations(&positions).unwrap();
+            Sample {
+                proof: prover.open(&positions).unwrap(),
+                evaluations,
+                positions,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let (verifier, _coin) = FridaDasVerifier::<E, H, H>::new(com, options.clone()).unwrap();
+
+    thread_counts
+        .iter()
+        .map(|&num_threads| {
+            let chunk_size = samples.len().div_ceil(num_threads).max(1);
+            let timer = Instant::now();
+            std::thread::scope(|scope| {
+                for chunk in samples.chunks(chunk_size) {
+                    let verifier = &verifier;
+                    scope.spawn(move || {
+                        for sample in chunk {
+                            verifier
+                                .verify(&sample.proof, &sample.evaluations, &sample.positions)
+                                .unwrap();
+                        }
+                    });
+                }
+            });
+            let elapsed = timer.elapsed().as_secs_f64();
+
+            ThroughputBenchmarkResult {
+                field_type: field_name.to_string(),
+                batch_size,
+                blowup_factor: options.blowup_factor(),
+                folding_factor: options.folding_factor(),
+                max_remainder_degree: options.remainder_max_degree(),
+                data_size_kb: data_size / 1024,
+                num_proofs,
+                positions_per_proof,
+                num_threads,
+                total_time_ms: elapsed * 1000.0,
+                proofs_per_second: num_proofs as f64 / elapsed,
+                samples_per_second: (num_proofs * positions_per_proof) as f64 / elapsed,
+            }
+        })
+        .collect()
+}
+
+pub fn run_full_benchmark(output: &OutputConfig) {
+    let fri_options = get_standard_fri_options();
+    let data_sizes_f64 = get_standard_data_sizes::<F64Element>();
+    let data_sizes_f128 = get_standard_data_sizes::<F128Element>();
+    let thread_counts = default_thread_counts();
+    let num_proofs = 1024;
+    let positions_per_proof = 1;
+
+    let mut results = Vec::new();
+
+    println!("Running full verifier throughput benchmark...");
+    println!(
+        "Configurations: {} FRI options × {} data sizes × {} thread counts × 2 field types",
+        fri_options.len(),
+        data_sizes_f64.len(),
+        thread_counts.len()
+    );
+
+    for &(blowup_factor, folding_factor, max_remainder_degree) in &fri_options {
+        let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+
+        for (&data_size_f64, &data_size_f128) in data_sizes_f64.iter().zip(data_sizes_f128.iter()) {
+            if let Ok(result) = std::panic::catch_unwind(|| {
+                benchmark_throughput::<F64Element, Blake3F64>(
+                    options.clone(),
+                    data_size_f64,
+                    1,
+                    num_proofs,
+                    positions_per_proof,
+                    &thread_counts,
+                    field_names::F64,
+                )
+            }) {
+                results.extend(result);
+            }
+
+            if let Ok(result) = std::panic::catch_unwind(|| {
+                benchmark_throughput::<F128Element, Blake3F128>(
+                    options.clone(),
+                    data_size_f128,
+                    1,
+                    num_proofs,
+                    positions_per_proof,
+                    &thread_counts,
+                    field_names::F128,
+                )
+            }) {
+                results.extend(result);
+            }
+        }
+    }
+
+    common::save_results_with_header(
+        &results,
+        output,
+        &ThroughputBenchmarkResult::csv_header(),
+        |r| r.to_csv(),
+    )
+    .expect("Failed to save results");
+    println!(
+        "Verifier throughput benchmark completed with {} successful results",
+        results.len()
+    );
+}
+
+pub struct CustomThroughputBenchmarkConfig<'a> {
+    pub blowup_factor: usize,
+    pub folding_factor: usize,
+    pub max_remainder_degree: usize,
+    pub data_size: usize,
+    pub batch_size: usize,
+    pub num_proofs: usize,
+    pub positions_per_proof: usize,
+    pub thread_counts: &'a [usize],
+    pub output: &'a OutputConfig,
+}
+
+pub fn run_custom_benchmark(config: CustomThroughputBenchmarkConfig) {
+    let options = FriOptions::new(
+        config.blowup_factor,
+        config.folding_factor,
+        config.max_remainder_degree,
+    );
+    let mut results = Vec::new();
+
+    println!("Running custom verifier throughput benchmark...");
+    println!(
+        "Parameters: blowup={}, folding={}, remainder={}, data={}KB, batch_size={}, proofs={}, positions_per_proof={}, threads={:?}",
+        config.blowup_factor,
+        config.folding_factor,
+        config.max_remainder_degree,
+        config.data_size / 1024,
+        config.batch_size,
+        config.num_proofs,
+        config.positions_per_proof,
+        config.thread_counts
+    );
+
+    results.extend(benchmark_throughput::<F64Element, Blake3F64>(
+        options.clone(),
+        config.data_size,
+        config.batch_size,
+        config.num_proofs,
+        config.positions_per_proof,
+        config.thread_counts,
+        field_names::F64,
+    ));
+    results.extend(benchmark_throughput::<F128Element, Blake3F128>(
+        options.clone(),
+        config.data_size,
+        config.batch_size,
+        config.num_proofs,
+        config.positions_per_proof,
+        config.thread_counts,
+        field_names::F128,
+    ));
+
+    common::save_results_with_header(
+        &results,
+        config.output,
+        &ThroughputBenchmarkResult::csv_header(),
+        |r| r.to_csv(),
+    )
+    .expect("Failed to save results");
+    println!("Custom verifier throughput benchmark completed successfully");
+
+    println!("\nResults Summary:");
+    for result in &results {
+        println!(
+            "  {} with {} threads: {:.1} proofs/s, {:.1} samples/s",
+            result.field_type,
+            result.num_threads,
+            result.proofs_per_second,
+            result.samples_per_second
+        );
+    }
+}
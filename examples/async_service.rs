@@ -0,0 +1,59 @@
+//! A minimal TCP service committing to blobs without blocking its runtime.
+//!
+//! Each connection sends a single blob and closes its write half; the service replies with the
+//! serialized commitment. Try it with:
+//!
+//! ```sh
+//! cargo run --example async_service --features async
+//! head -c 4096 /dev/urandom | nc -N 127.0.0.1 4000 | wc -c
+//! ```
+
+use std::sync::Arc;
+
+use frida_poc::prover::builder::FridaProverBuilder;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use winter_crypto::hashers::Blake3_256;
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_utils::Serializable;
+
+type Blake3 = Blake3_256<BaseElement>;
+type Builder = FridaProverBuilder<BaseElement, Blake3>;
+
+const NUM_QUERIES: usize = 32;
+
+/// Reads a blob from `stream` and writes back its commitment, or an error message.
+async fn handle(builder: Arc<Builder>, mut stream: TcpStream) -> std::io::Result<()> {
+    let mut blob = Vec::new();
+    stream.read_to_end(&mut blob).await?;
+    if blob.is_empty() {
+        return stream.write_all(b"empty blob").await;
+    }
+
+    // the heavy lifting runs on the blocking pool, so other connections keep being served
+    let response = match builder.commit_async(blob, NUM_QUERIES).await {
+        Ok((commitment, _prover)) => commitment.to_bytes(),
+        Err(err) => err.to_string().into_bytes(),
+    };
+    stream.write_all(&response).await
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let builder = Arc::new(Builder::new(FriOptions::new(8, 2, 7)));
+    let listener = TcpListener::bind("127.0.0.1:4000").await?;
+    println!("listening on {}", listener.local_addr()?);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let builder = builder.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle(builder, stream).await {
+                eprintln!("{peer}: {err}");
+            }
+        });
+    }
+}
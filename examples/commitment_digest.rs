@@ -0,0 +1,39 @@
+//! Prints the commitments of fixed blobs, one per line, so that builds with different features
+//! can be compared. CI checks that the `concurrent` feature leaves them unchanged:
+//!
+//! ```sh
+//! cargo run --example commitment_digest > sequential.txt
+//! RAYON_NUM_THREADS=8 cargo run --example commitment_digest --features concurrent > concurrent.txt
+//! diff sequential.txt concurrent.txt
+//! ```
+
+use frida_poc::{
+    prover::{builder::FridaProverBuilder, LayerStorage},
+    utils::encoding::to_hex,
+};
+use winter_crypto::hashers::Blake3_256;
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_utils::Serializable;
+
+type Builder = FridaProverBuilder<BaseElement, Blake3_256<BaseElement>>;
+
+fn main() {
+    let data = (0..100_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect::<Vec<_>>();
+    let batch = data.chunks(25_000).map(<[u8]>::to_vec).collect::<Vec<_>>();
+    let builders = [
+        Builder::new(FriOptions::new(2, 2, 1)),
+        Builder::new(FriOptions::new(2, 4, 3)).with_layer_storage(LayerStorage::Lazy),
+        Builder::new(FriOptions::new(4, 2, 1)).with_folding_schedule(vec![8, 2]),
+        Builder::new(FriOptions::new(2, 8, 1)).with_ood_check(true),
+    ];
+
+    for builder in &builders {
+        let (single, _) = builder.commit_and_prove(&data, 32).unwrap();
+        let (batched, _) = builder.commit_and_prove_batch(&batch, 32).unwrap();
+        println!("{}", to_hex(&single.to_bytes()));
+        println!("{}", to_hex(&batched.to_bytes()));
+    }
+}
@@ -0,0 +1,174 @@
+//! Disperses a blob to a few validators over QUIC, each running its own libp2p swarm, and checks
+//! the attestations they answer with. Run it with:
+//!
+//! ```sh
+//! cargo run --example dispersal --features network
+//! ```
+
+use std::{error::Error, time::Duration};
+
+use frida_poc::{
+    network::{
+        transport::{dispersal_behaviour, DispersalBehaviour, IdentitySigner},
+        Producer, Validator,
+    },
+    prover::{builder::FridaProverBuilder, ProverCommitment},
+};
+use futures::StreamExt;
+use libp2p::{
+    identity::{Keypair, PublicKey},
+    request_response::{Event, Message},
+    swarm::{dial_opts::DialOpts, SwarmEvent},
+    Multiaddr, PeerId, Swarm, SwarmBuilder,
+};
+use tokio::sync::oneshot;
+use winter_crypto::hashers::Blake3_256;
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+type Blake3 = Blake3_256<BaseElement>;
+type Behaviour = DispersalBehaviour<BaseElement, Blake3>;
+
+const NUM_VALIDATORS: usize = 4;
+const NUM_QUERIES: usize = 32;
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn new_swarm(keypair: Keypair) -> Result<Swarm<Behaviour>, Box<dyn Error>> {
+    Ok(SwarmBuilder::with_existing_identity(keypair)
+        .with_tokio()
+        .with_quic()
+        .with_behaviour(|_| dispersal_behaviour(TIMEOUT))?
+        .with_swarm_config(|config| config.with_idle_connection_timeout(TIMEOUT))
+        .build())
+}
+
+/// Listens for shares of the blob committed to by `published`, and answers each with an
+/// attestation, or `None` if it is rejected.
+async fn run_validator(
+    index: u32,
+    options: FriOptions,
+    published: ProverCommitment<Blake3>,
+    ready: oneshot::Sender<(PeerId, PublicKey, Multiaddr)>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let keypair = Keypair::generate_ed25519();
+    let public_key = keypair.public();
+    let mut swarm = new_swarm(keypair.clone()).map_err(|e| e.to_string())?;
+    swarm.listen_on("/ip4/127.0.0.1/udp/0/quic-v1".parse()?)?;
+    let validator = Validator::new(index, options, IdentitySigner(keypair));
+
+    let mut ready = Some(ready);
+    loop {
+        match swarm.select_next_some().await {
+            SwarmEvent::NewListenAddr { address, .. } => {
+                if let Some(ready) = ready.take() {
+                    let _ = ready.send((*swarm.local_peer_id(), public_key.clone(), address));
+                }
+            }
+            SwarmEvent::Behaviour(Event::Message {
+                message:
+                    Message::Request {
+                        request, channel, ..
+                    },
+                ..
+            }) => {
+                let attestation = validator.handle_share(&request, &published).ok();
+                let _ = swarm.behaviour_mut().send_response(channel, attestation);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let options = FriOptions::new(8, 2, 7);
+
+    // the producer commits to the blob, and splits the queried positions between validators
+    let builder = FridaProverBuilder::<BaseElement, Blake3>::new(options.clone());
+    let (commitment, prover, positions) =
+        builder.commitment(&rand_vector::<u8>(64 * 1024), NUM_QUERIES)?;
+    let assignments = (0..NUM_VALIDATORS)
+        .map(|i| {
+            positions
+                .iter()
+                .copied()
+                .skip(i)
+                .step_by(NUM_VALIDATORS)
+                .collect()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    let producer = Producer::new(prover);
+    println!("published commitment {}", commitment.to_hex());
+
+    // validators learn the published commitment independently of the shares they receive
+    let mut validators = Vec::with_capacity(NUM_VALIDATORS);
+    for index in 0..NUM_VALIDATORS {
+        let (ready, listening) = oneshot::channel();
+        let published = producer.commitment();
+        tokio::spawn(run_validator(
+            index as u32,
+            options.clone(),
+            published,
+            ready,
+        ));
+        validators.push(listening.await?);
+    }
+
+    let mut swarm = new_swarm(Keypair::generate_ed25519())?;
+    let mut pending = Vec::new();
+    for (share, (peer, _, address)) in producer.shares(&assignments)?.into_iter().zip(&validators) {
+        let Some(share) = share else { continue };
+        swarm.dial(
+            DialOpts::peer_id(*peer)
+                .addresses(vec![address.clone()])
+                .build(),
+        )?;
+        pending.push(swarm.behaviour_mut().send_request(peer, share));
+    }
+
+    while !pending.is_empty() {
+        match swarm.select_next_some().await {
+            SwarmEvent::Behaviour(Event::Message {
+                peer,
+                message:
+                    Message::Response {
+                        request_id,
+                        response,
+                    },
+                ..
+            }) => {
+                pending.retain(|id| *id != request_id);
+                let index = validators
+                    .iter()
+                    .position(|(id, _, _)| *id == peer)
+                    .unwrap();
+                match response {
+                    Some(attestation) => {
+                        producer.check_attestation(
+                            &attestation,
+                            &assignments,
+                            &validators[index].1,
+                        )?;
+                        println!(
+                            "validator {index} attested to positions {:?}",
+                            assignments[index]
+                        );
+                    }
+                    None => println!("validator {index} rejected its share"),
+                }
+            }
+            SwarmEvent::Behaviour(Event::OutboundFailure {
+                peer,
+                request_id,
+                error,
+                ..
+            }) => {
+                pending.retain(|id| *id != request_id);
+                println!("share for {peer} was not delivered: {error}");
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
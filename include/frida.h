@@ -0,0 +1,137 @@
+/* Generated with cbindgen from src/capi.rs; do not edit by hand. */
+
+#ifndef FRIDA_H
+#define FRIDA_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Version of the exported functions and their error codes, bumped on any breaking change.
+ */
+#define FRIDA_ABI_VERSION 1
+
+/**
+ * The proof is valid.
+ */
+#define FRIDA_OK 0
+
+/**
+ * The proof was rejected by the verifier.
+ */
+#define FRIDA_INVALID_PROOF 1
+
+/**
+ * A required pointer was null.
+ */
+#define FRIDA_ERR_NULL_POINTER -1
+
+/**
+ * The commitment could not be deserialized, or failed its own verification.
+ */
+#define FRIDA_ERR_COMMITMENT -2
+
+/**
+ * The proof could not be deserialized.
+ */
+#define FRIDA_ERR_PROOF -3
+
+/**
+ * The evaluations are not a whole number of field elements, or one of them is not canonical.
+ */
+#define FRIDA_ERR_EVALUATIONS -4
+
+/**
+ * The verifier panicked, e.g. because of unsupported FRI options.
+ */
+#define FRIDA_ERR_PANIC -5
+
+/**
+ * A verifier bound to a commitment, created by `frida_verifier_new`.
+ */
+typedef struct FridaVerifier FridaVerifier;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns `FRIDA_ABI_VERSION`.
+ */
+uint32_t frida_abi_version(void);
+
+/**
+ * Verifies that `evaluations` at `positions` are opened by `proof` against `commitment`, which
+ * was made with the given FRI options.
+ *
+ * Returns `FRIDA_OK` if the proof is valid, `FRIDA_INVALID_PROOF` if it is not, and one of the
+ * negative error codes if the inputs are malformed.
+ *
+ * # Safety
+ * Every pointer must be valid for reads of its length, and may only be null if its length is 0.
+ */
+int32_t frida_verify(const uint8_t *commitment_ptr,
+                     size_t commitment_len,
+                     const uint8_t *proof_ptr,
+                     size_t proof_len,
+                     const uint8_t *evaluations_ptr,
+                     size_t evaluations_len,
+                     const size_t *positions_ptr,
+                     size_t positions_len,
+                     size_t blowup_factor,
+                     size_t folding_factor,
+                     size_t max_remainder_degree);
+
+/**
+ * Creates a verifier for `commitment`, so that many openings can be verified against it without
+ * checking the commitment again. On success, the verifier is written to `out` and must be
+ * released with `frida_verifier_free`.
+ *
+ * Returns `FRIDA_OK` on success, and one of the negative error codes otherwise.
+ *
+ * # Safety
+ * `commitment_ptr` must be valid for reads of `commitment_len` bytes, and `out` must be valid
+ * for writes.
+ */
+int32_t frida_verifier_new(const uint8_t *commitment_ptr,
+                           size_t commitment_len,
+                           size_t blowup_factor,
+                           size_t folding_factor,
+                           size_t max_remainder_degree,
+                           FridaVerifier **out);
+
+/**
+ * Verifies that `evaluations` at `positions` are opened by `proof` against the commitment of
+ * `verifier`.
+ *
+ * Returns `FRIDA_OK` if the proof is valid, `FRIDA_INVALID_PROOF` if it is not, and one of the
+ * negative error codes if the inputs are malformed.
+ *
+ * # Safety
+ * `verifier` must have been created by `frida_verifier_new` and not freed yet, and every other
+ * pointer must be valid for reads of its length, and may only be null if its length is 0.
+ */
+int32_t frida_verifier_verify(const FridaVerifier *verifier,
+                              const uint8_t *proof_ptr,
+                              size_t proof_len,
+                              const uint8_t *evaluations_ptr,
+                              size_t evaluations_len,
+                              const size_t *positions_ptr,
+                              size_t positions_len);
+
+/**
+ * Releases a verifier created by `frida_verifier_new`. Does nothing if `verifier` is null.
+ *
+ * # Safety
+ * `verifier` must have been created by `frida_verifier_new`, and must not be used afterwards.
+ */
+void frida_verifier_free(FridaVerifier *verifier);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FRIDA_H */
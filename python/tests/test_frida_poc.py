@@ -0,0 +1,69 @@
+import pytest
+
+import frida_poc
+
+DATA = bytes(range(256)) * 4
+MODULUS = 2**128 - 45 * 2**40 + 1
+
+
+@pytest.fixture(scope="module")
+def committed():
+    num_queries = frida_poc.calculate_num_queries(len(DATA))
+    return frida_poc.commit(DATA, num_queries)
+
+
+def test_open_and_verify(committed):
+    commitment, prover = committed
+    positions = [1, 7, 12]
+    proof = prover.open(positions)
+    evaluations = prover.evaluations(positions)
+    assert frida_poc.verify(commitment, proof, evaluations, positions)
+
+    evaluations[0] = (evaluations[0] + 1) % MODULUS
+    assert not frida_poc.verify(commitment, proof, evaluations, positions)
+
+
+def test_prover_round_trip(committed):
+    _, prover = committed
+    restored = frida_poc.Prover.from_bytes(prover.to_bytes())
+    assert restored.domain_size() == prover.domain_size()
+    assert restored.evaluations([0, 3]) == prover.evaluations([0, 3])
+
+
+def test_encode_and_decode():
+    domain_size = 1 << 10
+    evaluations = frida_poc.encode(DATA, domain_size, 8)
+    assert len(evaluations) == domain_size
+
+    positions = list(range(0, domain_size, 2))[: domain_size // 8]
+    sampled = [evaluations[p] for p in positions]
+    assert frida_poc.decode(sampled, positions, domain_size, 8) == DATA
+
+
+def test_invalid_options_raise():
+    with pytest.raises(frida_poc.FridaError):
+        frida_poc.commit(DATA, 4, blowup_factor=3)
+    with pytest.raises(frida_poc.FridaError):
+        frida_poc.commit(DATA, 4, folding_factor=3)
+    with pytest.raises(frida_poc.FridaError):
+        frida_poc.calculate_num_queries(len(DATA), max_remainder_degree=6)
+
+
+def test_invalid_inputs_raise(committed):
+    commitment, prover = committed
+    with pytest.raises(frida_poc.FridaError):
+        prover.open([prover.domain_size()])
+    with pytest.raises(frida_poc.FridaError):
+        prover.evaluations([0, prover.domain_size()])
+    with pytest.raises(ValueError):
+        frida_poc.verify(b"not a commitment", b"", [], [])
+    with pytest.raises(ValueError):
+        frida_poc.verify(commitment, b"not a proof", [0], [0])
+    with pytest.raises(ValueError):
+        frida_poc.encode(DATA, 1000, 8)
+    with pytest.raises(ValueError):
+        frida_poc.encode(DATA, 1 << 10, 0)
+    with pytest.raises(ValueError):
+        frida_poc.decode([0], [1 << 10], 1 << 10, 8)
+    with pytest.raises(frida_poc.FridaError):
+        frida_poc.decode([0], [0], 1 << 10, 8)
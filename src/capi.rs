@@ -0,0 +1,305 @@
+//! C ABI of the verifier, exported with the `capi` feature so that node software written in other
+//! languages can verify Frida proofs. The matching header, `include/frida.h`, is generated with
+//! `cbindgen --config cbindgen.toml --output include/frida.h`.
+//!
+//! Commitments and proofs are passed in their serialized form. Evaluations are passed as the
+//! concatenated 16 byte little-endian encodings of f128 field elements, one per position, and
+//! hashing is done with Blake3, like the CLI. Functions never unwind into the caller; a panic is
+//! reported as `FRIDA_ERR_PANIC`.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+use winter_utils::{Deserializable, SliceReader};
+
+use crate::{
+    prover::{proof::FridaProof, Commitment},
+    verifier::das::FridaDasVerifier,
+    winterfell::{f128::BaseElement, Blake3_256, FieldElement, FriOptions},
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Verifier = FridaDasVerifier<BaseElement, Blake3, Blake3>;
+
+/// Version of the exported functions and their error codes, bumped on any breaking change.
+pub const FRIDA_ABI_VERSION: u32 = 1;
+
+/// The proof is valid.
+pub const FRIDA_OK: i32 = 0;
+/// The proof was rejected by the verifier.
+pub const FRIDA_INVALID_PROOF: i32 = 1;
+/// A required pointer was null.
+pub const FRIDA_ERR_NULL_POINTER: i32 = -1;
+/// The commitment could not be deserialized, or failed its own verification.
+pub const FRIDA_ERR_COMMITMENT: i32 = -2;
+/// The proof could not be deserialized.
+pub const FRIDA_ERR_PROOF: i32 = -3;
+/// The evaluations are not a whole number of field elements, or one of them is not canonical.
+pub const FRIDA_ERR_EVALUATIONS: i32 = -4;
+/// The verifier panicked, e.g. because of unsupported FRI options.
+pub const FRIDA_ERR_PANIC: i32 = -5;
+
+/// A verifier bound to a commitment, created by `frida_verifier_new`.
+pub struct FridaVerifier(Verifier);
+
+/// Returns `FRIDA_ABI_VERSION`.
+#[no_mangle]
+pub extern "C" fn frida_abi_version() -> u32 {
+    FRIDA_ABI_VERSION
+}
+
+/// Verifies that `evaluations` at `positions` are opened by `proof` against `commitment`, which
+/// was made with the given FRI options.
+///
+/// Returns `FRIDA_OK` if the proof is valid, `FRIDA_INVALID_PROOF` if it is not, and one of the
+/// negative error codes if the inputs are malformed.
+///
+/// # Safety
+/// Every pointer must be valid for reads of its length, and may only be null if its length is 0.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn frida_verify(
+    commitment_ptr: *const u8,
+    commitment_len: usize,
+    proof_ptr: *const u8,
+    proof_len: usize,
+    evaluations_ptr: *const u8,
+    evaluations_len: usize,
+    positions_ptr: *const usize,
+    positions_len: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+) -> i32 {
+    let mut verifier = std::ptr::null_mut();
+    let code = frida_verifier_new(
+        commitment_ptr,
+        commitment_len,
+        blowup_factor,
+        folding_factor,
+        max_remainder_degree,
+        &mut verifier,
+    );
+    if code != FRIDA_OK {
+        return code;
+    }
+
+    let code = frida_verifier_verify(
+        verifier,
+        proof_ptr,
+        proof_len,
+        evaluations_ptr,
+        evaluations_len,
+        positions_ptr,
+        positions_len,
+    );
+    frida_verifier_free(verifier);
+    code
+}
+
+/// Creates a verifier for `commitment`, so that many openings can be verified against it without
+/// checking the commitment again. On success, the verifier is written to `out` and must be
+/// released with `frida_verifier_free`.
+///
+/// Returns `FRIDA_OK` on success, and one of the negative error codes otherwise.
+///
+/// # Safety
+/// `commitment_ptr` must be valid for reads of `commitment_len` bytes, and `out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn frida_verifier_new(
+    commitment_ptr: *const u8,
+    commitment_len: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    out: *mut *mut FridaVerifier,
+) -> i32 {
+    if out.is_null() {
+        return FRIDA_ERR_NULL_POINTER;
+    }
+    let Some(commitment) = bytes(commitment_ptr, commitment_len) else {
+        return FRIDA_ERR_NULL_POINTER;
+    };
+
+    catch_panic(|| {
+        let Ok(commitment) = Commitment::<Blake3>::read_from_bytes(commitment) else {
+            return FRIDA_ERR_COMMITMENT;
+        };
+        let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+        match Verifier::new(commitment, options) {
+            Ok((verifier, _)) => {
+                *out = Box::into_raw(Box::new(FridaVerifier(verifier)));
+                FRIDA_OK
+            }
+            Err(_) => FRIDA_ERR_COMMITMENT,
+        }
+    })
+}
+
+/// Verifies that `evaluations` at `positions` are opened by `proof` against the commitment of
+/// `verifier`.
+///
+/// Returns `FRIDA_OK` if the proof is valid, `FRIDA_INVALID_PROOF` if it is not, and one of the
+/// negative error codes if the inputs are malformed.
+///
+/// # Safety
+/// `verifier` must have been created by `frida_verifier_new` and not freed yet, and every other
+/// pointer must be valid for reads of its length, and may only be null if its length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn frida_verifier_verify(
+    verifier: *const FridaVerifier,
+    proof_ptr: *const u8,
+    proof_len: usize,
+    evaluations_ptr: *const u8,
+    evaluations_len: usize,
+    positions_ptr: *const usize,
+    positions_len: usize,
+) -> i32 {
+    let (Some(verifier), Some(proof), Some(evaluations), Some(positions)) = (
+        verifier.as_ref(),
+        bytes(proof_ptr, proof_len),
+        bytes(evaluations_ptr, evaluations_len),
+        slice_from_raw(positions_ptr, positions_len),
+    ) else {
+        return FRIDA_ERR_NULL_POINTER;
+    };
+
+    catch_panic(|| {
+        let limits = verifier.0.proof_limits(positions.len());
+        let Ok(proof) = FridaProof::read_with_limits(&mut SliceReader::new(proof), &limits) else {
+            return FRIDA_ERR_PROOF;
+        };
+        if evaluations.len() % BaseElement::ELEMENT_BYTES != 0 {
+            return FRIDA_ERR_EVALUATIONS;
+        }
+        let Ok(evaluations) = evaluations
+            .chunks(BaseElement::ELEMENT_BYTES)
+            .map(BaseElement::read_from_bytes)
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            return FRIDA_ERR_EVALUATIONS;
+        };
+        match verifier.0.verify(&proof, &evaluations, positions) {
+            Ok(()) => FRIDA_OK,
+            Err(_) => FRIDA_INVALID_PROOF,
+        }
+    })
+}
+
+/// Releases a verifier created by `frida_verifier_new`. Does nothing if `verifier` is null.
+///
+/// # Safety
+/// `verifier` must have been created by `frida_verifier_new`, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn frida_verifier_free(verifier: *mut FridaVerifier) {
+    if !verifier.is_null() {
+        drop(Box::from_raw(verifier));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn catch_panic(f: impl FnOnce() -> i32) -> i32 {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(FRIDA_ERR_PANIC)
+}
+
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    slice_from_raw(ptr, len)
+}
+
+/// Returns the slice at `ptr`, or `None` if `ptr` is null and the slice is not empty.
+unsafe fn slice_from_raw<'a, T>(ptr: *const T, len: usize) -> Option<&'a [T]> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::TestFridaProverBuilder;
+    use winter_rand_utils::rand_vector;
+    use winter_utils::Serializable;
+
+    #[test]
+    fn test_frida_verify() {
+        let (blowup_factor, folding_factor, max_remainder_degree) = (2, 2, 1);
+        let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+        let (commitment, prover) = TestFridaProverBuilder::new(options)
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+
+        let commitment = commitment.to_bytes();
+        let positions = [1usize, 5, 6];
+        let proof = prover.open(&positions).unwrap().to_bytes();
+        let mut evaluations = prover
+            .get_evaluations(&positions)
+            .unwrap()
+            .iter()
+            .flat_map(|e| e.to_bytes())
+            .collect::<Vec<u8>>();
+
+        let verify = |commitment: &[u8], evaluations: &[u8], positions: &[usize]| unsafe {
+            frida_verify(
+                commitment.as_ptr(),
+                commitment.len(),
+                proof.as_ptr(),
+                proof.len(),
+                evaluations.as_ptr(),
+                evaluations.len(),
+                positions.as_ptr(),
+                positions.len(),
+                blowup_factor,
+                folding_factor,
+                max_remainder_degree,
+            )
+        };
+
+        assert_eq!(verify(&commitment, &evaluations, &positions), FRIDA_OK);
+        assert_eq!(
+            verify(&commitment, &evaluations, &[1, 5, 7]),
+            FRIDA_INVALID_PROOF
+        );
+        assert_eq!(
+            verify(
+                &commitment[..commitment.len() - 1],
+                &evaluations,
+                &positions
+            ),
+            FRIDA_ERR_COMMITMENT
+        );
+        assert_eq!(
+            verify(&commitment, &evaluations[1..], &positions),
+            FRIDA_ERR_EVALUATIONS
+        );
+
+        evaluations[0] ^= 1;
+        assert_eq!(
+            verify(&commitment, &evaluations, &positions),
+            FRIDA_INVALID_PROOF
+        );
+
+        let null = unsafe {
+            frida_verify(
+                std::ptr::null(),
+                commitment.len(),
+                proof.as_ptr(),
+                proof.len(),
+                evaluations.as_ptr(),
+                evaluations.len(),
+                positions.as_ptr(),
+                positions.len(),
+                blowup_factor,
+                folding_factor,
+                max_remainder_degree,
+            )
+        };
+        assert_eq!(null, FRIDA_ERR_NULL_POINTER);
+    }
+}
@@ -1,3 +1,4 @@
+use super::generate_data::{Format, Pattern};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -26,13 +27,22 @@ pub(super) enum Commands {
         #[arg(long, default_value = "7")]
         max_remainder_degree: usize,
     },
-    /// Generate a file with random data
+    /// Generate a file with random or patterned data
     GenerateData {
         /// The size of the data to generate, in bytes
         size: usize,
         /// Path to write the data file
         #[arg(long, default_value = "data/data.bin")]
         data_path: PathBuf,
+        /// Seed of random data, so that the same data is generated every time
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Contents of the data
+        #[arg(long, value_enum, default_value_t = Pattern::Random)]
+        pattern: Pattern,
+        /// Encoding of the data file
+        #[arg(long, value_enum, default_value_t = Format::Raw)]
+        format: Format,
     },
     /// Commit to the data and generate a full proof for a set of queries
     Commit {
@@ -41,40 +51,48 @@ pub(super) enum Commands {
         /// Path to the data file to commit to
         #[arg(long, default_value = "data/data.bin")]
         data_path: PathBuf,
-        /// Path to write the resulting commitment file
-        #[arg(long, default_value = "data/commitment.bin")]
-        commitment_path: PathBuf,
+        /// Directory of the store the commitment and prover are saved to
+        #[arg(long, default_value = "data/store")]
+        store_path: PathBuf,
+        /// Report every stage of the commitment, and the time it took, as it completes
+        #[arg(long)]
+        progress: bool,
     },
     /// Open a proof for a given set of positions
     Open {
         /// The positions (indices) to open in the proof
         positions: Vec<usize>,
-        /// Path to write the positions file
-        #[arg(long, default_value = "data/positions.bin")]
-        positions_path: PathBuf,
-        /// Path to write the opened evaluations file
-        #[arg(long, default_value = "data/evaluations.bin")]
-        evaluations_path: PathBuf,
-        /// Path to the source data file
-        #[arg(long, default_value = "data/data.bin")]
-        data_path: PathBuf,
-        /// Path to write the resulting proof file
-        #[arg(long, default_value = "data/proof.bin")]
-        proof_path: PathBuf,
+        /// Hex encoded key of the commitment to open, defaults to the last commitment
+        #[arg(long)]
+        commitment: Option<String>,
+        /// Directory of the store holding the prover, where the opening is cached
+        #[arg(long, default_value = "data/store")]
+        store_path: PathBuf,
     },
     /// Verify a proof against a commitment
     Verify {
-        /// Path to the commitment file
-        #[arg(long, default_value = "data/commitment.bin")]
-        commitment_path: PathBuf,
-        /// Path to the positions file
-        #[arg(long, default_value = "data/positions.bin")]
-        positions_path: PathBuf,
-        /// Path to the evaluations file
-        #[arg(long, default_value = "data/evaluations.bin")]
-        evaluations_path: PathBuf,
-        /// Path to the proof file
-        #[arg(long, default_value = "data/proof.bin")]
-        proof_path: PathBuf,
+        /// The positions (indices) of the opening to verify
+        positions: Vec<usize>,
+        /// Hex encoded key of the commitment to verify against, defaults to the last commitment
+        #[arg(long)]
+        commitment: Option<String>,
+        /// Directory of the store holding the commitment and the opening
+        #[arg(long, default_value = "data/store")]
+        store_path: PathBuf,
+    },
+    /// Save the prover options and the last commitment, so that they can be restored later
+    SaveSession {
+        /// Path to write the session file
+        #[arg(long, default_value = "data/session.json")]
+        session_path: PathBuf,
+    },
+    /// Restore the prover options and the last commitment of a saved session
+    LoadSession {
+        /// Path to the session file
+        #[arg(long, default_value = "data/session.json")]
+        session_path: PathBuf,
+        /// Directory of the store holding the prover of the last commitment
+        #[arg(long, default_value = "data/store")]
+        store_path: PathBuf,
     },
 }
@@ -1,52 +1,105 @@
+use super::input::read_input;
 use crate::{
+    error::FridaError,
     prover::{builder::FridaProverBuilder, Commitment},
-    utils::test_utils::{read_file_to_vec, write_to_file},
+    store::{key_to_hex, FridaStore},
 };
-use std::path::Path;
+use std::{path::Path, time::Instant};
 use winter_crypto::hashers::Blake3_256;
 use winter_math::fields::f128::BaseElement;
-use winter_utils::{Deserializable, Serializable};
 
 type Blake3 = Blake3_256<BaseElement>;
 type FridaProverBuilderType = FridaProverBuilder<BaseElement, Blake3>;
+type FridaStoreType = FridaStore<BaseElement, Blake3>;
+type CommitResult = Result<([u8; 32], Commitment<Blake3>), Box<dyn std::error::Error>>;
 
-/// Runs the commitment process, saving the commitment to a file.
+/// Reports the stages of a commitment on stderr, along with the time each of them took.
+struct Progress {
+    enabled: bool,
+    stage: usize,
+    start: Instant,
+}
+
+impl Progress {
+    const STAGES: usize = 5;
+
+    fn new(enabled: bool) -> Self {
+        Progress {
+            enabled,
+            stage: 0,
+            start: Instant::now(),
+        }
+    }
+
+    /// Reports the end of the current stage, described by `message`.
+    fn done(&mut self, message: &str) {
+        self.stage += 1;
+        if self.enabled {
+            eprintln!(
+                "[{}/{}] {message} in {:.3}s",
+                self.stage,
+                Self::STAGES,
+                self.start.elapsed().as_secs_f64()
+            );
+        }
+        self.start = Instant::now();
+    }
+}
+
+/// Runs the commitment process, saving the commitment and a snapshot of the prover to the store.
+/// With `progress`, the stages of the commitment are reported on stderr as they complete.
 pub fn run(
     prover_builder: &mut FridaProverBuilderType,
     num_queries: usize,
     data_path: &Path,
-    commitment_path: &Path,
-) -> Result<Commitment<Blake3>, Box<dyn std::error::Error>> {
-    // Read data from file
-    let data = read_file_to_vec(data_path)?;
-
-    // Create commitment from data
-    let (commitment, _) = prover_builder
-        .commit_and_prove(&data, num_queries)
-        .map_err(|e| -> Box<dyn std::error::Error> {
-            format!("Prover commit error: {e}").into()
-        })?;
-
-    // Write commitment to file
-    let commitment_bytes = commitment.to_bytes();
-    write_to_file(commitment_path, &commitment_bytes)?;
+    store: &FridaStoreType,
+    progress: bool,
+) -> CommitResult {
+    let mut progress = Progress::new(progress);
+    let prover_error = |e: FridaError| -> Box<dyn std::error::Error> {
+        format!("Prover commit error: {e}").into()
+    };
+
+    // Read data from file, without copying it when it is memory-mapped
+    let data = read_input(data_path)?;
+    progress.done(&format!(
+        "{} {} bytes",
+        if data.is_mapped() { "Mapped" } else { "Read" },
+        data.len()
+    ));
+
+    // Create commitment from data, stage by stage; this is the same as `commit_and_prove`
+    let encoding = prover_builder
+        .encode(&data, num_queries)
+        .map_err(prover_error)?;
+    progress.done(&format!(
+        "Encoded {} polynomial(s) over a domain of {}",
+        encoding.poly_count(),
+        encoding.domain_size()
+    ));
+    drop(data);
+
+    let (prover_commitment, prover, positions) =
+        prover_builder.commit(encoding).map_err(prover_error)?;
+    progress.done(&format!(
+        "Committed to {} FRI layers",
+        prover_commitment.roots.len()
+    ));
+
+    let proof = prover.open(&positions).map_err(prover_error)?;
+    let commitment = prover_commitment.attach_proof(proof, num_queries);
+    progress.done(&format!("Opened {num_queries} queries"));
+
+    // Keep the prover around, so that later openings don't need to commit again
+    let key = store.put_commitment(&commitment)?;
+    store.put_prover(&prover)?;
+    progress.done("Saved to the store");
 
     println!(
-        "Commitment created and saved to {}",
-        commitment_path.display()
+        "Commitment {} created and saved to the store",
+        key_to_hex(&key)
     );
-    Ok(commitment)
-}
-
-/// Reads the commitment from a file.
-pub fn read_commitment_from_file(
-    file_path: &Path,
-) -> Result<Commitment<Blake3>, Box<dyn std::error::Error>> {
-    let commitment_bytes = read_file_to_vec(file_path)?;
-    let commitment = Commitment::<Blake3>::read_from_bytes(&commitment_bytes).map_err(
-        |e| -> Box<dyn std::error::Error> { format!("Deserialization error: {e}").into() },
-    )?;
-    Ok(commitment)
+    Ok((key, commitment))
 }
 
 #[cfg(test)]
@@ -58,23 +111,30 @@ mod tests {
     #[test]
     fn test_commit() {
         let data_path = Path::new("data/data_commit.bin");
-        let commitment_path = Path::new("data/commitment_commit.bin");
+        let store_path = Path::new("data/store_commit");
 
-        let _cleanup = CleanupFiles::new(vec![data_path, commitment_path]);
+        let _cleanup = CleanupFiles::new(vec![data_path, store_path]);
 
         if !data_path.exists() {
-            generate_data::run(200, data_path).unwrap();
+            generate_data::run(200, data_path, &Default::default()).unwrap();
         }
 
         let mut prover_builder = FridaProverBuilder::new(FriOptions::new(8, 2, 7));
+        let store = FridaStoreType::open(store_path).unwrap();
 
         // Run the commitment process
-        let commitment = run(&mut prover_builder, 31, data_path, commitment_path).unwrap();
+        let (key, commitment) = run(&mut prover_builder, 31, data_path, &store, true).unwrap();
+
+        // Read the commitment from the store
+        let stored = store.get_commitment(&key).unwrap();
 
-        // Read the commitment from the file
-        let commitment_file = read_commitment_from_file(commitment_path).unwrap();
+        // The commitment is the one of `commit_and_prove`
+        let data = read_input(data_path).unwrap();
+        let (expected, _) = prover_builder.commit_and_prove(&data, 31).unwrap();
+        assert_eq!(commitment, expected);
 
         // Verify the commitment
-        assert_eq!(commitment, commitment_file, "Commitment does not match.");
+        assert_eq!(Some(commitment), stored, "Commitment does not match.");
+        assert!(store.get_prover(&key).unwrap().is_some());
     }
 }
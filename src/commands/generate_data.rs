@@ -1,10 +1,66 @@
-use crate::utils::test_utils::write_to_file;
+use crate::utils::{encoding::to_hex, test_utils::write_to_file};
+use clap::ValueEnum;
 use std::{fs, io, path::Path};
-use winter_rand_utils::rand_vector;
+use winter_rand_utils::{prng_vector, rand_vector};
+
+const TEXT: &[u8] = b"The quick brown fox jumps over the lazy dog. ";
+
+/// Contents of generated data.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Pattern {
+    /// Random bytes, reproducible when a seed is given
+    #[default]
+    Random,
+    /// All bytes set to zero
+    Zeros,
+    /// Byte `i` set to `i mod 256`
+    Counter,
+    /// Repeated ASCII text
+    Text,
+}
 
-pub fn run(size: usize, file_path: &Path) -> Result<Vec<u8>, GenerateDataError> {
-    // Generate random data
-    let data = rand_vector::<u8>(size);
+/// Encoding of the generated data file.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// The bytes themselves, as expected by `commit`
+    #[default]
+    Raw,
+    /// Lowercase hex encoding of the bytes
+    Hex,
+}
+
+/// Options of the `generate-data` command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    pub pattern: Pattern,
+    /// Seed of random data; fresh randomness is drawn when absent.
+    pub seed: Option<u64>,
+    pub format: Format,
+}
+
+/// Generates `size` bytes of data as configured by `options`.
+pub fn generate(size: usize, options: &GenerateOptions) -> Vec<u8> {
+    match options.pattern {
+        Pattern::Random => match options.seed {
+            Some(seed) => {
+                let mut prng_seed = [0; 32];
+                prng_seed[..8].copy_from_slice(&seed.to_le_bytes());
+                prng_vector::<u8>(prng_seed, size)
+            }
+            None => rand_vector::<u8>(size),
+        },
+        Pattern::Zeros => vec![0; size],
+        Pattern::Counter => (0..size).map(|i| i as u8).collect(),
+        Pattern::Text => TEXT.iter().copied().cycle().take(size).collect(),
+    }
+}
+
+pub fn run(
+    size: usize,
+    file_path: &Path,
+    options: &GenerateOptions,
+) -> Result<Vec<u8>, GenerateDataError> {
+    let data = generate(size, options);
 
     // Ensure directory exists
     if let Some(parent) = std::path::Path::new(file_path).parent() {
@@ -12,7 +68,11 @@ pub fn run(size: usize, file_path: &Path) -> Result<Vec<u8>, GenerateDataError>
     }
 
     // Write data to file
-    write_to_file(file_path, &data).map_err(GenerateDataError::IoError)?;
+    let contents = match options.format {
+        Format::Raw => data.clone(),
+        Format::Hex => to_hex(&data).into_bytes(),
+    };
+    write_to_file(file_path, &contents).map_err(GenerateDataError::IoError)?;
 
     // Print success message
     println!(
@@ -37,7 +97,7 @@ mod tests {
         let _cleanup = CleanupFiles::new(vec![file_path]);
 
         // Generate data and write to file
-        let data = run(size, file_path)?;
+        let data = run(size, file_path, &GenerateOptions::default())?;
 
         // Read data from file
         let file_data = read_file_to_vec(file_path).map_err(GenerateDataError::IoError)?;
@@ -47,6 +107,48 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_generate_data_patterns() {
+        let seeded = |seed| GenerateOptions {
+            seed: Some(seed),
+            ..Default::default()
+        };
+        assert_eq!(generate(100, &seeded(7)), generate(100, &seeded(7)));
+        assert_ne!(generate(100, &seeded(7)), generate(100, &seeded(8)));
+        assert_eq!(generate(100, &seeded(7))[..50], generate(50, &seeded(7)));
+
+        let with_pattern = |pattern| GenerateOptions {
+            pattern,
+            ..Default::default()
+        };
+        assert_eq!(generate(3, &with_pattern(Pattern::Zeros)), vec![0; 3]);
+        assert_eq!(
+            generate(258, &with_pattern(Pattern::Counter))[254..],
+            [254, 255, 0, 1]
+        );
+        assert_eq!(generate(9, &with_pattern(Pattern::Text)), b"The quick");
+    }
+
+    #[test]
+    fn test_generate_data_hex() -> Result<(), GenerateDataError> {
+        let file_path = Path::new("data/data_generate_hex.bin");
+        let _cleanup = CleanupFiles::new(vec![file_path]);
+
+        let options = GenerateOptions {
+            pattern: Pattern::Counter,
+            format: Format::Hex,
+            ..Default::default()
+        };
+        let data = run(4, file_path, &options)?;
+        assert_eq!(data, [0, 1, 2, 3]);
+        assert_eq!(
+            read_file_to_vec(file_path).map_err(GenerateDataError::IoError)?,
+            b"00010203"
+        );
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
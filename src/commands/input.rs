@@ -0,0 +1,77 @@
+use std::{fs::File, io, ops::Deref, path::Path};
+
+#[cfg(not(feature = "mmap"))]
+use std::io::Read;
+
+/// Contents of a data file given to a command: memory-mapped with the `mmap` feature, so that
+/// committing to GB-scale files does not hold a second copy of them in memory, and read into a
+/// buffer otherwise.
+pub enum InputData {
+    Buffered(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for InputData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            InputData::Buffered(data) => data,
+            #[cfg(feature = "mmap")]
+            InputData::Mapped(map) => map,
+        }
+    }
+}
+
+impl InputData {
+    /// Returns true if the file is memory-mapped rather than read into a buffer.
+    pub fn is_mapped(&self) -> bool {
+        !matches!(self, InputData::Buffered(_))
+    }
+}
+
+/// Opens the data file at `path`, memory-mapping it with the `mmap` feature.
+#[cfg(feature = "mmap")]
+pub fn read_input(path: &Path) -> Result<InputData, io::Error> {
+    let file = File::open(path)?;
+    // empty files cannot be mapped on every platform
+    if file.metadata()?.len() == 0 {
+        return Ok(InputData::Buffered(Vec::new()));
+    }
+    // SAFETY: the map is read-only and dropped by the end of the command; as with any mapping,
+    // the file must not be truncated by another process while it is mapped
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(InputData::Mapped(map))
+}
+
+/// Opens the data file at `path`, memory-mapping it with the `mmap` feature.
+#[cfg(not(feature = "mmap"))]
+pub fn read_input(path: &Path) -> Result<InputData, io::Error> {
+    let mut file = File::open(path)?;
+    let mut data = Vec::with_capacity(file.metadata()?.len() as usize);
+    file.read_to_end(&mut data)?;
+    Ok(InputData::Buffered(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands::generate_data, utils::test_utils::CleanupFiles};
+
+    #[test]
+    fn test_read_input() {
+        let data_path = Path::new("data/data_input.bin");
+        let empty_path = Path::new("data/data_input_empty.bin");
+        let _cleanup = CleanupFiles::new(vec![data_path, empty_path]);
+
+        let data = generate_data::run(300, data_path, &Default::default()).unwrap();
+        let input = read_input(data_path).unwrap();
+        assert_eq!(&input[..], &data[..]);
+        assert_eq!(input.is_mapped(), cfg!(feature = "mmap"));
+
+        generate_data::run(0, empty_path, &Default::default()).unwrap();
+        assert!(read_input(empty_path).unwrap().is_empty());
+        assert!(read_input(Path::new("data/data_input_missing.bin")).is_err());
+    }
+}
@@ -1,14 +1,18 @@
 pub mod commit;
 pub mod generate_data;
+pub mod input;
 pub mod open;
+pub mod session;
 pub mod verify;
 
 mod cli;
 
 use self::cli::{Cli, Commands};
 use crate::{
+    core::schedule::fri_options,
     prover::builder::FridaProverBuilder,
-    winterfell::{f128::BaseElement, winter_crypto::hashers::Blake3_256, FriOptions},
+    store::{key_from_hex, FridaStore},
+    winterfell::{f128::BaseElement, winter_crypto::hashers::Blake3_256},
 };
 use clap::Parser;
 use std::{
@@ -17,22 +21,38 @@ use std::{
 };
 
 type Blake3 = Blake3_256<BaseElement>;
+type Key = [u8; 32];
 type FridaProverBuilderType = FridaProverBuilder<BaseElement, Blake3>;
+type FridaStoreType = FridaStore<BaseElement, Blake3>;
 
 /// Runs the main interactive CLI loop.
 pub fn run_cli() {
     let mut prover_builder: Option<FridaProverBuilderType> = None;
+    let mut last_commitment: Option<Key> = None;
 
     loop {
-        if let Err(e) = handle_iteration(&mut prover_builder) {
+        if let Err(e) = handle_iteration(&mut prover_builder, &mut last_commitment) {
             eprintln!("Error: {e}");
         }
     }
 }
 
+/// Resolves the commitment a command refers to, defaulting to the last one created.
+fn commitment_key(
+    commitment: Option<String>,
+    last_commitment: &Option<Key>,
+) -> Result<Key, Box<dyn std::error::Error>> {
+    match commitment {
+        Some(hex) => Ok(key_from_hex(&hex)?),
+        None => last_commitment
+            .ok_or_else(|| "No commitment given. Pass --commitment or run 'commit' first.".into()),
+    }
+}
+
 /// Handles a single iteration of the command loop.
 fn handle_iteration(
     prover_builder: &mut Option<FridaProverBuilderType>,
+    last_commitment: &mut Option<Key>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cli = read_and_parse_command()?;
 
@@ -47,8 +67,8 @@ fn handle_iteration(
                 "Initializing prover with FRI options: blowup={blowup_factor}, folding={folding_factor}, max_degree={max_remainder_degree}"
             );
 
-            // Check if the data file exists before initializing
-            fs::read(&data_path).map_err(|e| {
+            // Check if the data file exists before initializing, without reading it
+            fs::metadata(&data_path).map_err(|e| {
                 format!(
                     "Failed to read data file '{}': {}. Use the 'generate-data' command first.",
                     data_path.display(),
@@ -56,60 +76,73 @@ fn handle_iteration(
                 )
             })?;
 
-            let options = FriOptions::new(blowup_factor, folding_factor, max_remainder_degree);
+            let options = fri_options(blowup_factor, folding_factor, max_remainder_degree)?;
             *prover_builder = Some(FridaProverBuilderType::new(options));
             println!("Prover initialized successfully.");
         }
-        Commands::GenerateData { size, data_path } => {
-            generate_data::run(size, &data_path)?;
+        Commands::GenerateData {
+            size,
+            data_path,
+            seed,
+            pattern,
+            format,
+        } => {
+            let options = generate_data::GenerateOptions {
+                pattern,
+                seed,
+                format,
+            };
+            generate_data::run(size, &data_path, &options)?;
         }
         Commands::Commit {
             num_queries,
             data_path,
-            commitment_path,
+            store_path,
+            progress,
         } => {
             let builder = prover_builder
                 .as_mut()
                 .ok_or("Prover not initialized. Please run the 'init' command first.")?;
-            commit::run(builder, num_queries, &data_path, &commitment_path)?;
+            let store = FridaStoreType::open(store_path)?;
+            let (key, _) = commit::run(builder, num_queries, &data_path, &store, progress)?;
+            *last_commitment = Some(key);
         }
         Commands::Open {
             positions,
-            positions_path,
-            evaluations_path,
-            data_path,
-            proof_path,
+            commitment,
+            store_path,
         } => {
-            let builder = prover_builder
-                .as_mut()
-                .ok_or("Prover not initialized. Please run the 'init' command first.")?;
-            open::run(
-                builder,
-                &positions,
-                &positions_path,
-                &evaluations_path,
-                &data_path,
-                &proof_path,
-            )?;
+            let key = commitment_key(commitment, last_commitment)?;
+            let store = FridaStoreType::open(store_path)?;
+            open::run(&store, &key, &positions)?;
         }
         Commands::Verify {
-            commitment_path,
-            positions_path,
-            evaluations_path,
-            proof_path,
+            positions,
+            commitment,
+            store_path,
         } => {
             let builder = prover_builder
                 .as_ref()
                 .ok_or("Prover not initialized. Please run the 'init' command first.")?;
-            verify::run(
-                &commitment_path,
-                &positions_path,
-                &evaluations_path,
-                &proof_path,
-                builder.options.clone(),
-            )?;
+            let key = commitment_key(commitment, last_commitment)?;
+            let store = FridaStoreType::open(store_path)?;
+            verify::run(&store, &key, &positions, builder.options.clone())?;
             println!("Verification successful!");
         }
+        Commands::SaveSession { session_path } => {
+            let builder = prover_builder
+                .as_ref()
+                .ok_or("Prover not initialized. Please run the 'init' command first.")?;
+            session::save(&session_path, &builder.options, last_commitment)?;
+        }
+        Commands::LoadSession {
+            session_path,
+            store_path,
+        } => {
+            let (options, key) = session::load(&session_path, &store_path)?;
+            *prover_builder = Some(FridaProverBuilderType::new(options));
+            *last_commitment = key;
+        }
     }
     Ok(())
 }
@@ -0,0 +1,129 @@
+use crate::{
+    core::schedule::fri_options,
+    store::{key_from_hex, key_to_hex, FridaStore},
+};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+use winter_crypto::hashers::Blake3_256;
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+
+type Blake3 = Blake3_256<BaseElement>;
+type Key = [u8; 32];
+type FridaStoreType = FridaStore<BaseElement, Blake3>;
+
+/// State of the interactive CLI that outlives a single run. Provers are not part of it, as their
+/// snapshots are already kept in the store.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct Session {
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    /// Hex encoded key of the last commitment, if any.
+    last_commitment: Option<String>,
+}
+
+/// Saves the FRI options the prover was initialized with and the last commitment to `path`.
+pub fn save(
+    path: &Path,
+    options: &FriOptions,
+    last_commitment: &Option<Key>,
+) -> Result<(), Box<dyn Error>> {
+    let session = Session {
+        blowup_factor: options.blowup_factor(),
+        folding_factor: options.folding_factor(),
+        max_remainder_degree: options.remainder_max_degree(),
+        last_commitment: last_commitment.as_ref().map(key_to_hex),
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&session)?)?;
+
+    println!("Session saved to {}", path.display());
+    Ok(())
+}
+
+/// Loads a session saved by `save`, checking that the prover of its last commitment can still be
+/// found in the store at `store_path`.
+pub fn load(path: &Path, store_path: &Path) -> Result<(FriOptions, Option<Key>), Box<dyn Error>> {
+    let session: Session = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let options = fri_options(
+        session.blowup_factor,
+        session.folding_factor,
+        session.max_remainder_degree,
+    )?;
+    let last_commitment = session
+        .last_commitment
+        .map(|hex| key_from_hex(&hex))
+        .transpose()?;
+
+    if let Some(key) = last_commitment.as_ref() {
+        if FridaStoreType::open(store_path)?.get_prover(key)?.is_none() {
+            return Err(format!(
+                "No prover stored for commitment {} in {}",
+                key_to_hex(key),
+                store_path.display()
+            )
+            .into());
+        }
+    }
+
+    println!("Session loaded from {}", path.display());
+    Ok((options, last_commitment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::{commit, generate_data, open},
+        prover::builder::FridaProverBuilder,
+        utils::test_utils::CleanupFiles,
+    };
+    use winter_rand_utils::rand_value;
+
+    #[test]
+    fn test_session() {
+        let data_path = Path::new("data/data_session.bin");
+        let store_path = Path::new("data/store_session");
+        // the session file of the CLI itself lives in data/, which the test must not touch
+        let session_path =
+            std::env::temp_dir().join(format!("frida-session-{:x}.json", rand_value::<u64>()));
+        let session_path = session_path.as_path();
+
+        let _cleanup = CleanupFiles::new(vec![data_path, store_path, session_path]);
+
+        generate_data::run(200, data_path, &Default::default()).unwrap();
+        let options = FriOptions::new(8, 2, 7);
+        let mut prover_builder = FridaProverBuilder::new(options.clone());
+        let store = FridaStoreType::open(store_path).unwrap();
+        let (key, _) = commit::run(&mut prover_builder, 31, data_path, &store, false).unwrap();
+
+        save(session_path, &options, &Some(key)).unwrap();
+        let (loaded_options, loaded_key) = load(session_path, store_path).unwrap();
+        assert_eq!(loaded_options.blowup_factor(), 8);
+        assert_eq!(loaded_options.folding_factor(), 2);
+        assert_eq!(loaded_options.remainder_max_degree(), 7);
+        assert_eq!(loaded_key, Some(key));
+
+        // the restored commitment can be opened from the store
+        open::run(&store, &loaded_key.unwrap(), &[1, 2]).unwrap();
+
+        // a session cannot be resumed without the prover of its last commitment
+        store.remove(&key).unwrap();
+        assert!(load(session_path, store_path).is_err());
+
+        save(session_path, &options, &None).unwrap();
+        assert_eq!(load(session_path, store_path).unwrap().1, None);
+
+        // invalid options are rejected rather than making the CLI panic
+        fs::write(
+            session_path,
+            r#"{"blowup_factor":8,"folding_factor":3,"max_remainder_degree":7,"last_commitment":null}"#,
+        )
+        .unwrap();
+        assert!(load(session_path, store_path).is_err());
+    }
+}
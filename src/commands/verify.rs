@@ -1,36 +1,37 @@
 use crate::{
-    commands::open::read_and_deserialize_proof, prover::Commitment, verifier::das::FridaDasVerifier,
+    store::{key_to_hex, FridaStore},
+    verifier::das::FridaDasVerifier,
 };
-use std::{error::Error, fs, path::Path};
+use std::error::Error;
 use winter_crypto::hashers::Blake3_256;
 use winter_fri::FriOptions;
 use winter_math::fields::f128::BaseElement;
-use winter_utils::Deserializable;
 
 type Blake3 = Blake3_256<BaseElement>;
+type Key = [u8; 32];
+type FridaStoreType = FridaStore<BaseElement, Blake3>;
 type FriVerifierType = FridaDasVerifier<BaseElement, Blake3, Blake3>;
 
+/// Verifies the cached opening of `positions` against the stored commitment.
 pub fn run(
-    commitment_path: &Path,
-    positions_path: &Path,
-    evaluations_path: &Path,
-    proof_path: &Path,
+    store: &FridaStoreType,
+    key: &Key,
+    positions: &[usize],
     fri_options: FriOptions,
 ) -> Result<(), Box<dyn Error>> {
-    // Read and deserialize
-    let commitment_bytes = fs::read(commitment_path)?;
-    let commitment = Commitment::<Blake3_256<BaseElement>>::read_from_bytes(&commitment_bytes)
-        .map_err(|e| format!("Deserialization error: {e}"))?;
-
-    let (positions, evaluations, proof) =
-        read_and_deserialize_proof(positions_path, evaluations_path, proof_path)?;
+    let commitment = store
+        .get_commitment(key)?
+        .ok_or_else(|| format!("No commitment stored for {}", key_to_hex(key)))?;
+    let (evaluations, proof) = store
+        .get_opening(key, positions)?
+        .ok_or("No opening stored for these positions. Use the 'open' command first.")?;
 
     let (verifier, _) = FriVerifierType::new(commitment, fri_options.clone())
         .map_err(|e| format!("Verifier initialization error: {e}"))?;
 
     // Verify the proof
     verifier
-        .verify(&proof, &evaluations, &positions)
+        .verify(&proof, &evaluations, positions)
         .map_err(|e| format!("Verification error: {e}"))?;
 
     Ok(())
@@ -44,56 +45,36 @@ mod tests {
         prover::builder::FridaProverBuilder,
         utils::test_utils::CleanupFiles,
     };
-    use winter_crypto::hashers::Blake3_256;
-    use winter_fri::FriOptions;
-    use winter_math::fields::f128::BaseElement;
+    use std::path::Path;
 
-    type Blake3 = Blake3_256<BaseElement>;
     type FridaProverBuilderType = FridaProverBuilder<BaseElement, Blake3>;
 
     #[test]
     fn test_verify() {
         let data_path = Path::new("data/data_verify.bin");
-        let commitment_path = Path::new("data/commitment_verify.bin");
-        let positions_path = Path::new("data/positions_verify.bin");
-        let evaluations_path = Path::new("data/evaluations_verify.bin");
-        let proof_path = Path::new("data/proof_verify.bin");
+        let store_path = Path::new("data/store_verify");
 
-        let _cleanup = CleanupFiles::new(vec![
-            data_path,
-            commitment_path,
-            positions_path,
-            evaluations_path,
-            proof_path,
-        ]);
+        let _cleanup = CleanupFiles::new(vec![data_path, store_path]);
 
         // Generate data
-        generate_data::run(200, data_path).unwrap();
+        generate_data::run(200, data_path, &Default::default()).unwrap();
 
         // Initialize prover
         let mut prover_builder = FridaProverBuilderType::new(FriOptions::new(8, 2, 7));
+        let store = FridaStoreType::open(store_path).unwrap();
         let num_queries = 31;
-        commit::run(&mut prover_builder, num_queries, data_path, commitment_path).unwrap();
+        let (key, _) =
+            commit::run(&mut prover_builder, num_queries, data_path, &store, false).unwrap();
+
+        // Openings must exist before they can be verified
+        let positions = [1, 2, 3];
+        assert!(run(&store, &key, &positions, prover_builder.options.clone()).is_err());
 
         // Open the commitment
-        open::run(
-            &mut prover_builder,
-            &[1, 2, 3],
-            positions_path,
-            evaluations_path,
-            data_path,
-            proof_path,
-        )
-        .unwrap();
+        open::run(&store, &key, &positions).unwrap();
 
         // Verify the proof
-        let result = run(
-            commitment_path,
-            positions_path,
-            evaluations_path,
-            proof_path,
-            prover_builder.options.clone(),
-        );
+        let result = run(&store, &key, &positions, prover_builder.options.clone());
         assert!(result.is_ok(), "{:?}", result.err().unwrap());
     }
 }
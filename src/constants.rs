@@ -1,2 +1,59 @@
+use crate::core::schedule::SUPPORTED_FOLDING_FACTORS;
+
 pub const MIN_DOMAIN_SIZE: usize = 8;
 pub const MAX_DOMAIN_SIZE: usize = 16777216; // 16*(Felt Bytes)Mb (256Mb if 128bit)
+/// Largest number of FRI layers of a commitment: layers folded by 2 from the largest domain down
+/// to a remainder of 2 evaluations, the smallest a blowup factor of at least 2 leaves.
+pub const MAX_LAYERS: usize = MAX_DOMAIN_SIZE.ilog2() as usize - 1;
+/// Largest serialized proof accepted by verifiers, whatever the number of opened positions.
+pub const MAX_PROOF_BYTES: usize = 64 << 20;
+
+// LIMITS
+// ================================================================================================
+
+/// Limits enforced by the prover and verifier, so that front-end services can validate user
+/// inputs before they reach the prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct Limits {
+    pub min_domain_size: usize,
+    pub max_domain_size: usize,
+    pub max_folding_factor: usize,
+    pub max_layers: usize,
+    pub max_proof_bytes: usize,
+}
+
+/// Returns the limits enforced by the prover and verifier.
+pub const fn limits() -> Limits {
+    Limits {
+        min_domain_size: MIN_DOMAIN_SIZE,
+        max_domain_size: MAX_DOMAIN_SIZE,
+        max_folding_factor: SUPPORTED_FOLDING_FACTORS[SUPPORTED_FOLDING_FACTORS.len() - 1],
+        max_layers: MAX_LAYERS,
+        max_proof_bytes: MAX_PROOF_BYTES,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winter_fri::FriOptions;
+
+    use super::*;
+    use crate::core::schedule::layer_folding_factors;
+
+    #[test]
+    fn test_limits_bound_every_commitment() {
+        let limits = limits();
+        assert_eq!(limits.max_folding_factor, 16);
+        assert_eq!(limits.max_layers, 23);
+
+        // the deepest commitment folds the largest domain by 2 down to the smallest remainder
+        let options = FriOptions::new(2, 2, 0);
+        let factors = layer_folding_factors(&options, &[2], limits.max_domain_size);
+        assert_eq!(factors.len(), limits.max_layers);
+        assert_eq!(
+            options.num_fri_layers(limits.max_domain_size),
+            limits.max_layers
+        );
+    }
+}
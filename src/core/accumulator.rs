@@ -0,0 +1,362 @@
+use winter_crypto::Hasher;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use crate::{error::FridaError, prover::ProverCommitment};
+
+/// Domain separator of leaves, so that a leaf cannot be passed off as an inner node.
+const LEAF_DOMAIN: &[u8] = b"frida-mmr-leaf-v1";
+
+// COMMITMENT ACCUMULATOR
+// ================================================================================================
+
+/// Append-only Merkle Mountain Range over the commitments of successive blocks.
+///
+/// Every block appends a leaf binding its number to its commitment, and the accumulator keeps a
+/// single running root over all of them. A light client which trusts the root of any later block
+/// verifies with an `InclusionProof` that a commitment was the one of block `N`; the blobs of the
+/// commitment are then checked against it as usual, e.g. with `FridaDasVerifier`.
+///
+/// The leaves are split into perfect Merkle trees of decreasing size, the mountains, whose roots
+/// are the peaks; the root hashes the number of leaves together with every peak.
+#[derive(Debug)]
+pub struct CommitmentAccumulator<H: Hasher> {
+    blocks: Vec<u64>,
+    leaves: Vec<H::Digest>,
+    /// Height and root of every mountain, highest first.
+    peaks: Vec<(u32, H::Digest)>,
+}
+
+impl<H: Hasher> Default for CommitmentAccumulator<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher> CommitmentAccumulator<H> {
+    pub fn new() -> Self {
+        CommitmentAccumulator {
+            blocks: Vec::new(),
+            leaves: Vec::new(),
+            peaks: Vec::new(),
+        }
+    }
+
+    /// Appends the commitment of `block`, and returns the index of its leaf.
+    ///
+    /// # Errors
+    /// Returns `FridaError::NonIncreasingBlock` unless `block` is higher than every block
+    /// appended before it.
+    pub fn append(
+        &mut self,
+        block: u64,
+        commitment: &ProverCommitment<H>,
+    ) -> Result<usize, FridaError> {
+        if self.blocks.last().is_some_and(|&last| block <= last) {
+            return Err(FridaError::NonIncreasingBlock(block));
+        }
+        self.blocks.push(block);
+        self.push_leaf(leaf_hash::<H>(block, &commitment.binding()));
+        Ok(self.leaves.len() - 1)
+    }
+
+    fn push_leaf(&mut self, leaf: H::Digest) {
+        self.leaves.push(leaf);
+
+        // merge mountains of the same height, as a binary counter carries
+        self.peaks.push((0, leaf));
+        while let [.., (left_height, left), (right_height, right)] = self.peaks[..] {
+            if left_height != right_height {
+                break;
+            }
+            self.peaks.truncate(self.peaks.len() - 2);
+            self.peaks.push((left_height + 1, H::merge(&[left, right])));
+        }
+    }
+
+    /// Running root over every commitment appended so far.
+    pub fn root(&self) -> H::Digest {
+        bag_peaks::<H>(self.len(), self.peaks.iter().map(|(_, peak)| *peak))
+    }
+
+    /// Number of commitments appended so far.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Builds the proof that the commitment of `block` is included under the current root.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnknownBlock` if no commitment was appended for `block`.
+    pub fn prove(&self, block: u64) -> Result<InclusionProof<H>, FridaError> {
+        let leaf_index = self
+            .blocks
+            .binary_search(&block)
+            .map_err(|_| FridaError::UnknownBlock(block))?;
+        let (start, height) = mountain_of(leaf_index, self.len())
+            .expect("the leaf index is smaller than the number of leaves");
+
+        // rebuild the mountain of the leaf level by level, collecting the sibling at each level
+        let mut level = self.leaves[start..start + (1 << height)].to_vec();
+        let mut index = leaf_index - start;
+        let mut path = Vec::with_capacity(height as usize);
+        while level.len() > 1 {
+            path.push(level[index ^ 1]);
+            level = level
+                .chunks(2)
+                .map(|pair| H::merge(&[pair[0], pair[1]]))
+                .collect();
+            index /= 2;
+        }
+
+        Ok(InclusionProof {
+            block,
+            leaf_index,
+            num_leaves: self.len(),
+            path,
+            peaks: self.peaks.iter().map(|(_, peak)| *peak).collect(),
+        })
+    }
+}
+
+// INCLUSION PROOF
+// ================================================================================================
+
+/// Proof that a commitment was appended for `block` to an accumulator with the given number of
+/// leaves.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InclusionProof<H: Hasher> {
+    pub block: u64,
+    pub leaf_index: usize,
+    pub num_leaves: usize,
+    /// Siblings of the leaf in its mountain, from the leaf up.
+    pub path: Vec<H::Digest>,
+    /// Every peak of the accumulator, highest mountain first.
+    pub peaks: Vec<H::Digest>,
+}
+
+impl<H: Hasher> Clone for InclusionProof<H> {
+    fn clone(&self) -> Self {
+        InclusionProof {
+            block: self.block,
+            leaf_index: self.leaf_index,
+            num_leaves: self.num_leaves,
+            path: self.path.clone(),
+            peaks: self.peaks.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> InclusionProof<H> {
+    /// Checks that `commitment` was appended for `self.block` to the accumulator with `root`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidInclusionProof` if the proof does not lead to `root`.
+    pub fn verify(
+        &self,
+        root: &H::Digest,
+        commitment: &ProverCommitment<H>,
+    ) -> Result<(), FridaError> {
+        let (start, height) = mountain_of(self.leaf_index, self.num_leaves)
+            .ok_or(FridaError::InvalidInclusionProof)?;
+        let mountain = mountain_heights(self.num_leaves)
+            .take_while(|&h| h != height)
+            .count();
+        if self.path.len() != height as usize
+            || self.peaks.len() != self.num_leaves.count_ones() as usize
+        {
+            return Err(FridaError::InvalidInclusionProof);
+        }
+
+        let mut node = leaf_hash::<H>(self.block, &commitment.binding());
+        let mut index = self.leaf_index - start;
+        for sibling in self.path.iter() {
+            node = match index % 2 {
+                0 => H::merge(&[node, *sibling]),
+                _ => H::merge(&[*sibling, node]),
+            };
+            index /= 2;
+        }
+
+        let expected = bag_peaks::<H>(self.num_leaves, self.peaks.iter().copied());
+        if node != self.peaks[mountain] || expected != *root {
+            return Err(FridaError::InvalidInclusionProof);
+        }
+        Ok(())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+fn leaf_hash<H: Hasher>(block: u64, binding: &H::Digest) -> H::Digest {
+    let mut bytes = LEAF_DOMAIN.to_vec();
+    block.write_into(&mut bytes);
+    binding.write_into(&mut bytes);
+    H::hash(&bytes)
+}
+
+fn bag_peaks<H: Hasher>(num_leaves: usize, peaks: impl Iterator<Item = H::Digest>) -> H::Digest {
+    let mut bytes = Vec::new();
+    num_leaves.write_into(&mut bytes);
+    peaks.for_each(|peak| peak.write_into(&mut bytes));
+    H::hash(&bytes)
+}
+
+/// Heights of the mountains of an accumulator with `num_leaves` leaves, highest first.
+fn mountain_heights(num_leaves: usize) -> impl Iterator<Item = u32> {
+    (0..usize::BITS)
+        .rev()
+        .filter(move |&height| num_leaves & (1 << height) != 0)
+}
+
+/// Returns the index of the first leaf, and the height, of the mountain holding `leaf_index`.
+fn mountain_of(leaf_index: usize, num_leaves: usize) -> Option<(usize, u32)> {
+    let mut start = 0;
+    for height in mountain_heights(num_leaves) {
+        if leaf_index < start + (1 << height) {
+            return Some((start, height));
+        }
+        start += 1 << height;
+    }
+    None
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for CommitmentAccumulator<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.blocks.write_into(target);
+        self.leaves.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for CommitmentAccumulator<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blocks = Vec::<u64>::read_from(source)?;
+        let leaves = Vec::<H::Digest>::read_from(source)?;
+        if blocks.len() != leaves.len() || blocks.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(DeserializationError::InvalidValue(
+                "accumulator blocks must be increasing, with one leaf each".to_string(),
+            ));
+        }
+
+        // the peaks are rebuilt from the leaves
+        let mut accumulator = CommitmentAccumulator::new();
+        for (block, leaf) in blocks.into_iter().zip(leaves) {
+            accumulator.blocks.push(block);
+            accumulator.push_leaf(leaf);
+        }
+        Ok(accumulator)
+    }
+}
+
+impl<H: Hasher> Serializable for InclusionProof<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block.write_into(target);
+        self.leaf_index.write_into(target);
+        self.num_leaves.write_into(target);
+        self.path.write_into(target);
+        self.peaks.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for InclusionProof<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block = u64::read_from(source)?;
+        let leaf_index = usize::read_from(source)?;
+        let num_leaves = usize::read_from(source)?;
+        let path = Vec::<H::Digest>::read_from(source)?;
+        let peaks = Vec::<H::Digest>::read_from(source)?;
+
+        Ok(InclusionProof {
+            block,
+            leaf_index,
+            num_leaves,
+            path,
+            peaks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use winter_rand_utils::rand_vector;
+
+    fn commit(size: usize) -> ProverCommitment<Blake3> {
+        let builder = TestFridaProverBuilder::new(test_options());
+        builder.commitment(&rand_vector::<u8>(size), 4).unwrap().0
+    }
+
+    #[test]
+    fn test_inclusion_proofs() {
+        let commitments = (0..11).map(|i| commit(100 + i)).collect::<Vec<_>>();
+        let mut accumulator = CommitmentAccumulator::<Blake3>::new();
+        let mut roots = Vec::new();
+        for (i, commitment) in commitments.iter().enumerate() {
+            assert_eq!(accumulator.append(10 * i as u64, commitment), Ok(i));
+            roots.push(accumulator.root());
+        }
+
+        for (i, commitment) in commitments.iter().enumerate() {
+            let block = 10 * i as u64;
+            let proof = accumulator.prove(block).unwrap();
+            assert_eq!(proof.verify(&accumulator.root(), commitment), Ok(()));
+            let decoded = InclusionProof::read_from_bytes(&proof.to_bytes()).unwrap();
+            assert_eq!(decoded, proof);
+
+            // proofs only hold for their block, commitment and root
+            let other = &commitments[(i + 1) % commitments.len()];
+            assert_eq!(
+                proof.verify(&accumulator.root(), other),
+                Err(FridaError::InvalidInclusionProof)
+            );
+            assert_eq!(
+                proof.verify(&roots[5], commitment),
+                Err(FridaError::InvalidInclusionProof)
+            );
+            let mut moved = proof;
+            moved.block += 1;
+            assert_eq!(
+                moved.verify(&accumulator.root(), commitment),
+                Err(FridaError::InvalidInclusionProof)
+            );
+        }
+        assert_eq!(accumulator.prove(15), Err(FridaError::UnknownBlock(15)));
+    }
+
+    #[test]
+    fn test_blocks_must_increase() {
+        let commitment = commit(100);
+        let mut accumulator = CommitmentAccumulator::<Blake3>::new();
+        assert!(accumulator.is_empty());
+        accumulator.append(3, &commitment).unwrap();
+        assert_eq!(
+            accumulator.append(3, &commitment),
+            Err(FridaError::NonIncreasingBlock(3))
+        );
+        assert_eq!(
+            accumulator.append(2, &commitment),
+            Err(FridaError::NonIncreasingBlock(2))
+        );
+        assert_eq!(accumulator.len(), 1);
+    }
+
+    #[test]
+    fn test_accumulator_serialization() {
+        let mut accumulator = CommitmentAccumulator::<Blake3>::new();
+        for block in 0..6 {
+            accumulator.append(block, &commit(120)).unwrap();
+        }
+        let decoded =
+            CommitmentAccumulator::<Blake3>::read_from_bytes(&accumulator.to_bytes()).unwrap();
+        assert_eq!(decoded.root(), accumulator.root());
+        assert_eq!(decoded.prove(4), accumulator.prove(4));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::error::FridaError;
+
+// POSITION ASSIGNMENTS
+// ================================================================================================
+
+/// Splits `query_positions` between `n_validators`, so that any `h` of them together hold every
+/// position (see `coverage::min_honest_validators`).
+///
+/// With at most as many validators as positions, validator `i` holds the `s - h + 1` positions
+/// starting at `i`, wrapping around. With more validators, the largest multiple of `s` of them
+/// is split into replicas of the assignment of `s` validators, and the others hold no position.
+pub fn compute_position_assignments(
+    n_validators: usize,
+    query_positions: &[usize],
+    h: usize,
+) -> Vec<Vec<usize>> {
+    let s = query_positions.len();
+    let n = n_validators;
+    if n == 0 {
+        return vec![];
+    }
+    if n <= s {
+        let span_length = s.saturating_sub(h) + 1;
+        (1..=n)
+            .map(|i| {
+                let offset = (i - 1) % s;
+                (0..span_length)
+                    .map(|j| query_positions[(offset + j) % s])
+                    .collect()
+            })
+            .collect()
+    } else {
+        let n_prime = (n / s) * s;
+        if n_prime == 0 {
+            return vec![Vec::new(); n];
+        }
+        let replication_factor = n_prime / s;
+        let h_prime = h.saturating_sub(n - n_prime).div_ceil(replication_factor);
+        let base_subsets = compute_position_assignments(s, query_positions, h_prime);
+        (1..=n)
+            .map(|i| {
+                if i <= n_prime {
+                    base_subsets[(i - 1) % s].clone()
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits `query_positions` between validators in proportion to their `weights`, e.g. their
+/// stake, so that every position is held by `replication` distinct validators.
+///
+/// Every validator holds at least `min_load` positions, whatever its weight; when these minimum
+/// loads alone exceed `replication * s` positions, some positions are held by one more validator.
+/// A validator holds each position at most once, so loads are capped at `s`, and whatever a
+/// capped validator cannot hold is shared by the others in proportion to their weights.
+///
+/// # Errors
+/// Returns `FridaError::BadValidatorWeights` if no validator has a positive weight, and
+/// `FridaError::BadReplicationFactor` if `replication` is zero or exceeds the number of
+/// validators.
+pub fn weighted_position_assignments(
+    weights: &[u64],
+    query_positions: &[usize],
+    replication: usize,
+    min_load: usize,
+) -> Result<Vec<Vec<usize>>, FridaError> {
+    let n = weights.len();
+    let s = query_positions.len();
+    if weights.iter().all(|&weight| weight == 0) {
+        return Err(FridaError::BadValidatorWeights);
+    }
+    if replication == 0 || replication > n {
+        return Err(FridaError::BadReplicationFactor(replication));
+    }
+
+    let mut loads = vec![min_load.min(s); n];
+    let mut remaining = (replication * s).saturating_sub(loads.iter().sum());
+    while remaining > 0 {
+        let open = (0..n).filter(|&i| loads[i] < s).collect::<Vec<_>>();
+        // validators without stake only take positions the others cannot hold
+        let staked = open.iter().any(|&i| weights[i] > 0);
+        let weight = |i: usize| if staked { weights[i] as u128 } else { 1 };
+        let total = open.iter().map(|&i| weight(i)).sum::<u128>();
+
+        // largest remainder apportionment of the remaining positions, capped at `s`
+        let mut remainders = Vec::with_capacity(open.len());
+        let mut given = 0;
+        for &i in open.iter() {
+            let share = remaining as u128 * weight(i);
+            let quota = ((share / total) as usize).min(s - loads[i]);
+            loads[i] += quota;
+            given += quota;
+            remainders.push((share % total, i));
+        }
+        remainders.sort_by(|a, b| b.cmp(a));
+        for (_, i) in remainders {
+            if given == remaining {
+                break;
+            }
+            if loads[i] < s && weight(i) > 0 {
+                loads[i] += 1;
+                given += 1;
+            }
+        }
+        remaining -= given;
+    }
+
+    // validators hold consecutive positions, wrapping around, so that loads of at most `s` never
+    // hold a position twice
+    let mut offset = 0;
+    Ok(loads
+        .into_iter()
+        .map(|load| {
+            let positions = (0..load)
+                .map(|j| query_positions[(offset + j) % s])
+                .collect();
+            offset = (offset + load) % s.max(1);
+            positions
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::coverage::{min_honest_validators, replication_counts};
+
+    #[test]
+    fn test_weighted_loads_are_proportional() {
+        let positions = (0..40).collect::<Vec<_>>();
+        let assignments = weighted_position_assignments(&[1, 1, 2, 4], &positions, 2, 0).unwrap();
+        let loads = assignments.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(loads, vec![10, 10, 20, 40]);
+        assert!(replication_counts(&assignments, &positions)
+            .iter()
+            .all(|&count| count == 2));
+    }
+
+    #[test]
+    fn test_weighted_min_load_and_cap() {
+        let positions = (0..10).map(|p| p * 7).collect::<Vec<_>>();
+        // the largest validator is capped at every position, and its surplus is shared
+        let weights = [100, 1, 1, 0];
+        let assignments = weighted_position_assignments(&weights, &positions, 2, 3).unwrap();
+        let loads = assignments.iter().map(Vec::len).collect::<Vec<_>>();
+        assert!(loads.iter().all(|&load| load >= 3));
+        assert_eq!(loads, vec![10, 3, 4, 3]);
+        for positions in assignments.iter() {
+            let mut sorted = positions.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            assert_eq!(sorted.len(), positions.len());
+        }
+        assert!(min_honest_validators(&assignments, &positions).is_some());
+    }
+
+    #[test]
+    fn test_weighted_uses_unstaked_validators_last() {
+        let positions = (0..8).collect::<Vec<_>>();
+        let assignments = weighted_position_assignments(&[5, 0, 0], &positions, 2, 0).unwrap();
+        let loads = assignments.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(loads, vec![8, 4, 4]);
+        assert!(replication_counts(&assignments, &positions)
+            .iter()
+            .all(|&count| count == 2));
+    }
+
+    #[test]
+    fn test_weighted_bad_parameters() {
+        let positions = [1, 2, 3];
+        assert_eq!(
+            weighted_position_assignments(&[0, 0], &positions, 1, 0),
+            Err(FridaError::BadValidatorWeights)
+        );
+        assert_eq!(
+            weighted_position_assignments(&[], &positions, 1, 0),
+            Err(FridaError::BadValidatorWeights)
+        );
+        assert_eq!(
+            weighted_position_assignments(&[1, 2], &positions, 3, 0),
+            Err(FridaError::BadReplicationFactor(3))
+        );
+        assert_eq!(
+            weighted_position_assignments(&[1, 2], &positions, 0, 0),
+            Err(FridaError::BadReplicationFactor(0))
+        );
+    }
+}
@@ -0,0 +1,86 @@
+// COVERAGE ANALYSIS
+// ================================================================================================
+
+/// Returns, for every one of the query `positions`, the number of validators it is assigned to.
+///
+/// Validator `i` holds `assignments[i]`; a position listed twice for the same validator is
+/// counted once.
+pub fn replication_counts(assignments: &[Vec<usize>], positions: &[usize]) -> Vec<usize> {
+    positions
+        .iter()
+        .map(|position| {
+            assignments
+                .iter()
+                .filter(|assigned| assigned.contains(position))
+                .count()
+        })
+        .collect()
+}
+
+/// Returns the maximum number of validators which can withhold their positions, whichever they
+/// are, while the remaining ones still hold every query position. Returns `None` if a position
+/// is not assigned to any validator, so that even with every validator answering it is missing.
+///
+/// This is one less than the smallest replication count among the positions.
+pub fn max_suppression_tolerance(assignments: &[Vec<usize>], positions: &[usize]) -> Option<usize> {
+    let min_replication = replication_counts(assignments, positions)
+        .into_iter()
+        .min()
+        .unwrap_or(assignments.len() + 1);
+    min_replication.checked_sub(1)
+}
+
+/// Returns the smallest `h` such that any `h` validators together hold every query position,
+/// i.e. how many honest validators the assignment needs to recover all of the positions
+/// regardless of which validators the adversary controls. Returns `None` if a position is not
+/// assigned to any validator.
+///
+/// A position held by `r` validators is missed by a set of `n - r` validators, so `h` is
+/// `n - t`, where `t` is the `max_suppression_tolerance` of the assignment.
+pub fn min_honest_validators(assignments: &[Vec<usize>], positions: &[usize]) -> Option<usize> {
+    let tolerance = max_suppression_tolerance(assignments, positions)?;
+    Some(assignments.len().saturating_sub(tolerance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replication_counts() {
+        let assignments = vec![vec![1, 2], vec![2, 3, 3], vec![], vec![1, 2]];
+        assert_eq!(replication_counts(&assignments, &[1, 2, 3]), vec![2, 3, 1]);
+        assert_eq!(max_suppression_tolerance(&assignments, &[1, 2, 3]), Some(0));
+        assert_eq!(min_honest_validators(&assignments, &[1, 2, 3]), Some(4));
+        assert_eq!(max_suppression_tolerance(&assignments, &[1, 2]), Some(1));
+        assert_eq!(min_honest_validators(&assignments, &[1, 2]), Some(3));
+    }
+
+    #[test]
+    fn test_unassigned_position() {
+        let assignments = vec![vec![1], vec![1]];
+        assert_eq!(replication_counts(&assignments, &[1, 5]), vec![2, 0]);
+        assert_eq!(max_suppression_tolerance(&assignments, &[1, 5]), None);
+        assert_eq!(min_honest_validators(&assignments, &[1, 5]), None);
+    }
+
+    #[test]
+    fn test_cyclic_spans() {
+        // validator i holds the `s - h + 1` positions starting at i, wrapping around, so that any
+        // `h` validators hold every position
+        let (s, h) = (12, 5);
+        let positions = (0..s).map(|p| p * 3).collect::<Vec<_>>();
+        let assignments = (0..s)
+            .map(|i| (0..s - h + 1).map(|j| positions[(i + j) % s]).collect())
+            .collect::<Vec<Vec<usize>>>();
+
+        assert!(replication_counts(&assignments, &positions)
+            .iter()
+            .all(|&count| count == s - h + 1));
+        assert_eq!(min_honest_validators(&assignments, &positions), Some(h));
+        assert_eq!(
+            max_suppression_tolerance(&assignments, &positions),
+            Some(s - h)
+        );
+    }
+}
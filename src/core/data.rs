@@ -1,11 +1,164 @@
-use crate::error::FridaError;
-use core::mem;
+use crate::{
+    constants::{MAX_DOMAIN_SIZE, MIN_DOMAIN_SIZE},
+    error::FridaError,
+};
+use core::{mem, ops::Range};
 use winter_math::{fft, polynom, FieldElement, StarkField};
 
+// ENCODING OPTIONS
+// ================================================================================================
+
+/// How the bytes of the data, prefixed with its length, are packed into field elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Packing {
+    /// `ELEMENT_BYTES - 1` bytes per element, leaving its most significant byte empty.
+    #[default]
+    Element,
+    /// `floor(log2(p) / 8)` bytes per base field element, e.g. 7 for f64 and 15 for f128, which
+    /// also keeps extension field elements below the prime in every coordinate.
+    BaseFieldBytes,
+    /// `floor(log2(p))` bits per base field element, for maximal density. Bytes of the data may
+    /// straddle two elements.
+    Bits,
+}
+
+/// Options controlling how data is encoded into evaluations. Data must be decoded with the same
+/// options it was encoded with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodingOptions {
+    pub packing: Packing,
+    /// Splits data which barely overflows a power-of-two domain into two shards, each committed
+    /// on its own smaller domain, instead of doubling the domain; see `encoding_plan`.
+    pub sharding: bool,
+}
+
+impl EncodingOptions {
+    pub fn new(packing: Packing) -> Self {
+        EncodingOptions {
+            packing,
+            sharding: false,
+        }
+    }
+
+    pub fn with_sharding(mut self, sharding: bool) -> Self {
+        self.sharding = sharding;
+        self
+    }
+
+    /// Returns the number of bits of data held by each element of `E`.
+    pub fn element_bits<E: FieldElement>(&self) -> usize {
+        match self.packing {
+            Packing::Element => (E::ELEMENT_BYTES - 1) * 8,
+            Packing::BaseFieldBytes => base_field_bits::<E>() / 8 * 8 * E::EXTENSION_DEGREE,
+            Packing::Bits => base_field_bits::<E>() * E::EXTENSION_DEGREE,
+        }
+    }
+
+    /// Returns the number of elements of `E` needed to encode `data_size` bytes of data.
+    pub fn element_count<E: FieldElement>(&self, data_size: usize) -> usize {
+        ((mem::size_of::<u64>() + data_size) * 8).div_ceil(self.element_bits::<E>())
+    }
+
+    /// Returns the largest number of bytes of data that can be encoded into `element_count`
+    /// elements of `E`.
+    pub fn capacity<E: FieldElement>(&self, element_count: usize) -> usize {
+        (element_count * self.element_bits::<E>() / 8).saturating_sub(mem::size_of::<u64>())
+    }
+
+    /// Returns the smallest evaluation domain holding `data_size` bytes of data extended by
+    /// `blowup_factor`, over which the data is encoded.
+    pub fn domain_size<E: FieldElement>(&self, data_size: usize, blowup_factor: usize) -> usize {
+        usize::max(
+            self.element_count::<E>(data_size).next_power_of_two() * blowup_factor,
+            MIN_DOMAIN_SIZE,
+        )
+    }
+}
+
+/// Number of bits that can be stored in a base field element without reaching the prime.
+fn base_field_bits<E: FieldElement>() -> usize {
+    E::BaseField::MODULUS_BITS as usize - 1
+}
+
 pub fn encoded_data_element_count<E: FieldElement>(data_size: usize) -> usize {
-    let element_size = E::ELEMENT_BYTES - 1;
-    // element_size - 1 is to force a round up
-    (mem::size_of::<u64>() + data_size).div_ceil(element_size)
+    EncodingOptions::default().element_count::<E>(data_size)
+}
+
+// ENCODING PLAN
+// ================================================================================================
+
+/// Layout of a blob over evaluation domains, as decided by `encoding_plan`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingPlan {
+    /// Number of bytes of the data held by each shard, in order. Holds a single entry when the
+    /// data is not split.
+    pub shard_sizes: Vec<usize>,
+    /// Size of the evaluation domain of each shard, in the same order.
+    pub domain_sizes: Vec<usize>,
+}
+
+impl EncodingPlan {
+    pub fn is_sharded(&self) -> bool {
+        self.shard_sizes.len() > 1
+    }
+
+    /// Returns the number of evaluations committed over the domains of every shard.
+    pub fn total_domain_size(&self) -> usize {
+        self.domain_sizes.iter().sum()
+    }
+
+    /// Splits `data`, which must be of the size this plan was made for, into its shards.
+    pub fn split<'a>(&self, data: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut rest = data;
+        self.shard_sizes
+            .iter()
+            .map(|&size| {
+                let (shard, tail) = rest.split_at(size.min(rest.len()));
+                rest = tail;
+                shard
+            })
+            .collect()
+    }
+}
+
+/// Decides how `data_size` bytes of data are laid out over evaluation domains.
+///
+/// Without sharding, or when the encoded data fills a power-of-two number of elements, the data is
+/// encoded over the single domain of `EncodingOptions::domain_size`. With sharding, data needing
+/// `2^k + ε` elements is split into a first shard filling exactly `2^k` elements and a second
+/// shard holding the rest over the smallest domain `2^j` that fits it, each encoded over its own
+/// domain and committed to on its own. The data is only split if both domains together are
+/// smaller than the single domain of `2^(k+1)` elements.
+pub fn encoding_plan<E: FieldElement>(
+    data_size: usize,
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> EncodingPlan {
+    let single = EncodingPlan {
+        shard_sizes: vec![data_size],
+        domain_sizes: vec![encoding.domain_size::<E>(data_size, blowup_factor)],
+    };
+    let element_count = encoding.element_count::<E>(data_size);
+    if !encoding.sharding || element_count.is_power_of_two() {
+        return single;
+    }
+
+    let first = encoding.capacity::<E>(element_count.next_power_of_two() / 2);
+    let Some(rest) = data_size.checked_sub(first) else {
+        return single;
+    };
+    let domain_sizes = vec![
+        encoding.domain_size::<E>(first, blowup_factor),
+        encoding.domain_size::<E>(rest, blowup_factor),
+    ];
+    if domain_sizes.iter().sum::<usize>() >= single.total_domain_size() {
+        return single;
+    }
+
+    EncodingPlan {
+        shard_sizes: vec![first, rest],
+        domain_sizes,
+    }
 }
 
 fn encode_data<E: FieldElement>(data: &[u8], domain_size: usize, blowup_factor: usize) -> Vec<u8> {
@@ -37,6 +190,40 @@ fn encode_data<E: FieldElement>(data: &[u8], domain_size: usize, blowup_factor:
     encoded_data
 }
 
+/// Packs the length of `data` and `data` itself into `element_count` elements worth of bytes,
+/// `bits` bits at a time in the least significant bits of every base field element.
+fn pack_data<E: FieldElement>(data: &[u8], element_count: usize, bits: usize) -> Vec<u8> {
+    let base_bytes = E::BaseField::ELEMENT_BYTES;
+    let mut packed = vec![0; element_count * E::ELEMENT_BYTES];
+
+    let data_size_bytes = (data.len() as u64).to_be_bytes();
+    for (i, byte) in data_size_bytes.iter().chain(data).enumerate() {
+        for bit in (0..8usize).filter(|bit| (byte >> bit) & 1 == 1) {
+            let (index, shift) = packed_bit(i * 8 + bit, bits, base_bytes);
+            packed[index] |= 1u8 << shift;
+        }
+    }
+    packed
+}
+
+/// Reads `len` bytes starting at bit `start` of data packed by `pack_data`.
+fn unpack_data(packed: &[u8], start: usize, len: usize, bits: usize, base_bytes: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| {
+            (0..8usize).fold(0u8, |byte, bit| {
+                let (index, shift) = packed_bit(start + i * 8 + bit, bits, base_bytes);
+                byte | (((packed[index] >> shift) & 1) << bit)
+            })
+        })
+        .collect()
+}
+
+/// Returns the byte and the bit within it holding bit `position` of the packed data.
+fn packed_bit(position: usize, bits: usize, base_bytes: usize) -> (usize, usize) {
+    let offset = position % bits;
+    ((position / bits) * base_bytes + offset / 8, offset % 8)
+}
+
 fn data_to_field_element<E: FieldElement>(
     encoded_data: &[u8],
     domain_size: usize,
@@ -57,11 +244,63 @@ pub fn build_evaluations_from_data<E: FieldElement>(
     domain_size: usize,
     blowup_factor: usize,
 ) -> Result<Vec<E>, FridaError> {
-    let encoded_data = encode_data::<E>(data, domain_size, blowup_factor);
+    build_evaluations_with_encoding(
+        data,
+        domain_size,
+        blowup_factor,
+        &EncodingOptions::default(),
+    )
+}
+
+/// Same as `build_evaluations_from_data`, packing the data as configured by `encoding`.
+pub fn build_evaluations_with_encoding<E: FieldElement>(
+    data: &[u8],
+    domain_size: usize,
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> Result<Vec<E>, FridaError> {
+    let coefficients =
+        build_coefficients_with_encoding(data, domain_size, blowup_factor, encoding)?;
+    Ok(evaluate_coefficients(coefficients, domain_size))
+}
+
+/// Evaluates the polynomial with the given coefficients over the domain of `domain_size`.
+pub fn evaluate_coefficients<E: FieldElement>(
+    mut coefficients: Vec<E>,
+    domain_size: usize,
+) -> Vec<E> {
+    coefficients.resize(domain_size, E::default());
+    let twiddles = fft::get_twiddles(domain_size);
+    fft::evaluate_poly(&mut coefficients, &twiddles);
+    coefficients
+}
+
+/// Same as `build_evaluations_with_encoding`, returning the `domain_size / blowup_factor`
+/// coefficients of the polynomial of the data instead of its evaluations over the domain, e.g. to
+/// evaluate it over a part of the domain only.
+pub fn build_coefficients_with_encoding<E: FieldElement>(
+    data: &[u8],
+    domain_size: usize,
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> Result<Vec<E>, FridaError> {
+    let encoded_data = match encoding.packing {
+        Packing::Element => encode_data::<E>(data, domain_size, blowup_factor),
+        Packing::BaseFieldBytes | Packing::Bits => {
+            let element_count = encoding.element_count::<E>(data.len());
+            assert!(
+                element_count <= domain_size / blowup_factor,
+                "Data size will exceed the maximum degree after encoding"
+            );
+            let bits = encoding.element_bits::<E>() / E::EXTENSION_DEGREE;
+            pack_data::<E>(data, element_count, bits)
+        }
+    };
     let mut symbols: Vec<E> = data_to_field_element(&encoded_data, domain_size)?;
     symbols.resize(domain_size / blowup_factor, E::default());
 
-    reed_solomon_encode_data(&mut symbols, domain_size / blowup_factor, blowup_factor);
+    let inv_twiddles = fft::get_inv_twiddles(symbols.len());
+    fft::interpolate_poly(&mut symbols, &inv_twiddles);
 
     Ok(symbols)
 }
@@ -115,22 +354,30 @@ fn extract_and_decode_data<E: FieldElement>(
     evaluations: &[E],
     domain_size: usize,
     blowup_factor: usize,
+    encoding: &EncodingOptions,
 ) -> Result<Vec<u8>, FridaError> {
     if evaluations.len() != domain_size {
         return Err(FridaError::NotEnoughEvaluationsForDecoding());
     }
+    if encoding.packing != Packing::Element {
+        return extract_and_unpack_data(evaluations, domain_size, blowup_factor, encoding);
+    }
 
     let element_size = E::ELEMENT_BYTES - 1;
+    let prefix_size = mem::size_of::<u64>();
     let data_len = u64::from_be_bytes(
-        evaluations[0].as_bytes()[0..core::mem::size_of::<u64>()]
+        evaluations[0].as_bytes()[0..prefix_size]
             .try_into()
             .unwrap(),
-    ) as usize;
-    let encoded_element_count = encoded_data_element_count::<E>(data_len);
+    );
 
-    if encoded_element_count > domain_size / blowup_factor {
-        return Err(FridaError::BadDataLength());
+    // the prefix is untrusted, so check it before sizing anything off of it
+    let capacity = encoding.capacity::<E>(domain_size / blowup_factor);
+    if data_len > capacity as u64 {
+        return Err(FridaError::DataLengthOutOfBounds(data_len, capacity));
     }
+    let data_len = data_len as usize;
+    let encoded_element_count = encoded_data_element_count::<E>(data_len);
 
     let decoded = evaluations
         .iter()
@@ -144,26 +391,351 @@ fn extract_and_decode_data<E: FieldElement>(
     Ok(decoded)
 }
 
+/// Decodes data packed with `Packing::BaseFieldBytes` or `Packing::Bits`. Elements are read through
+/// their canonical serialization, so this works for any field.
+fn extract_and_unpack_data<E: FieldElement>(
+    evaluations: &[E],
+    domain_size: usize,
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> Result<Vec<u8>, FridaError> {
+    let element_count = domain_size / blowup_factor;
+    let bits = encoding.element_bits::<E>() / E::EXTENSION_DEGREE;
+    let base_bytes = E::BaseField::ELEMENT_BYTES;
+    let prefix_size = mem::size_of::<u64>();
+
+    let packed = evaluations
+        .iter()
+        .step_by(blowup_factor)
+        .take(element_count)
+        .flat_map(|e| e.to_bytes())
+        .collect::<Vec<u8>>();
+    let data_len = u64::from_be_bytes(
+        unpack_data(&packed, 0, prefix_size, bits, base_bytes)
+            .try_into()
+            .unwrap(),
+    );
+
+    let capacity = encoding.capacity::<E>(element_count);
+    if data_len > capacity as u64 {
+        return Err(FridaError::DataLengthOutOfBounds(data_len, capacity));
+    }
+    Ok(unpack_data(
+        &packed,
+        prefix_size * 8,
+        data_len as usize,
+        bits,
+        base_bytes,
+    ))
+}
+
 pub fn recover_data_from_evaluations<E: FieldElement>(
     evaluations: &[E],
     positions: &[usize],
     domain_size: usize,
     blowup_factor: usize,
+) -> Result<Vec<u8>, FridaError> {
+    recover_data_with_encoding(
+        evaluations,
+        positions,
+        domain_size,
+        blowup_factor,
+        &EncodingOptions::default(),
+    )
+}
+
+/// Same as `recover_data_from_evaluations`, for data encoded with `encoding`.
+pub fn recover_data_with_encoding<E: FieldElement>(
+    evaluations: &[E],
+    positions: &[usize],
+    domain_size: usize,
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
 ) -> Result<Vec<u8>, FridaError> {
     // Need to reconstruct if we don't have all the data
     if evaluations.len() != domain_size {
         let evaluations =
             reconstruct_evaluations(evaluations, positions, domain_size, blowup_factor)?;
-        return extract_and_decode_data(&evaluations, domain_size, blowup_factor);
+        return extract_and_decode_data(&evaluations, domain_size, blowup_factor, encoding);
     }
 
-    extract_and_decode_data(evaluations, domain_size, blowup_factor)
+    extract_and_decode_data(evaluations, domain_size, blowup_factor, encoding)
+}
+
+/// Recovers data split into shards by `encoding_plan` from the evaluations of every shard at some
+/// positions of its own domain, given in the order of the shards along with the domain sizes of
+/// their commitments. The shards are decoded separately with `recover_data_with_encoding` and
+/// concatenated back into the original data.
+///
+/// # Errors
+/// Returns `FridaError::XYCoordinateLengthMismatch` if `shards` and `domain_sizes` differ in
+/// length, and the errors of `recover_data_with_encoding` for the first shard which cannot be
+/// decoded.
+pub fn recover_sharded_data<E: FieldElement>(
+    shards: &[(&[E], &[usize])],
+    domain_sizes: &[usize],
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> Result<Vec<u8>, FridaError> {
+    if shards.len() != domain_sizes.len() {
+        return Err(FridaError::XYCoordinateLengthMismatch());
+    }
+    let mut data = Vec::new();
+    for (&(evaluations, positions), &domain_size) in shards.iter().zip(domain_sizes) {
+        data.extend(recover_data_with_encoding(
+            evaluations,
+            positions,
+            domain_size,
+            blowup_factor,
+            encoding,
+        )?);
+    }
+    Ok(data)
+}
+
+// ERASURE-CODED SHARES
+// ================================================================================================
+
+/// Evaluation of the Reed-Solomon extension of some data at position `index` of its domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share<E: FieldElement> {
+    pub index: usize,
+    pub value: E,
+}
+
+/// Reed-Solomon extends `data` by `blowup_factor` without committing to it, returning one share
+/// per position of the smallest domain holding it. Any `domain_size / blowup_factor` of the
+/// shares recover the data with `recover_data_from_shares`.
+///
+/// # Errors
+/// Returns `FridaError::InvalidBlowupFactor` if `blowup_factor` is not a power of two greater
+/// than one, and `FridaError::DomainSizeTooBig` if the data does not fit in the largest domain.
+pub fn erasure_encode_shares<E: FieldElement>(
+    data: &[u8],
+    blowup_factor: usize,
+) -> Result<Vec<Share<E>>, FridaError> {
+    erasure_encode_shares_with_encoding(data, blowup_factor, &EncodingOptions::default())
+}
+
+/// Same as `erasure_encode_shares`, packing the data as configured by `encoding`.
+pub fn erasure_encode_shares_with_encoding<E: FieldElement>(
+    data: &[u8],
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> Result<Vec<Share<E>>, FridaError> {
+    if blowup_factor <= 1 || !blowup_factor.is_power_of_two() {
+        return Err(FridaError::InvalidBlowupFactor);
+    }
+    let domain_size = encoding.domain_size::<E>(data.len(), blowup_factor);
+    if domain_size > MAX_DOMAIN_SIZE {
+        return Err(FridaError::DomainSizeTooBig(domain_size));
+    }
+
+    let evaluations = build_evaluations_with_encoding(data, domain_size, blowup_factor, encoding)?;
+    Ok(evaluations
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| Share { index, value })
+        .collect())
+}
+
+/// Recomputes every share of a domain of `domain_size` from any `domain_size / blowup_factor`
+/// of them, e.g. to repair the shares lost by a storage node.
+///
+/// # Errors
+/// Returns `FridaError::PositionOutOfDomain` for a share outside of the domain,
+/// `FridaError::DuplicateShare` for a position shared twice, and `FridaError::NotEnoughDataPoints`
+/// if too few shares are given.
+pub fn reconstruct_shares<E: FieldElement>(
+    shares: &[Share<E>],
+    domain_size: usize,
+    blowup_factor: usize,
+) -> Result<Vec<Share<E>>, FridaError> {
+    let (evaluations, positions) = split_shares(shares, domain_size)?;
+    let evaluations = if shares.len() == domain_size {
+        evaluations
+    } else {
+        reconstruct_evaluations(&evaluations, &positions, domain_size, blowup_factor)?
+    };
+    Ok(evaluations
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| Share { index, value })
+        .collect())
+}
+
+/// Recovers data extended with `erasure_encode_shares` over a domain of `domain_size` from any
+/// `domain_size / blowup_factor` of its shares.
+///
+/// # Errors
+/// Returns the errors of `reconstruct_shares`, and those of `recover_data_from_evaluations` if
+/// the shares do not decode.
+pub fn recover_data_from_shares<E: FieldElement>(
+    shares: &[Share<E>],
+    domain_size: usize,
+    blowup_factor: usize,
+) -> Result<Vec<u8>, FridaError> {
+    recover_data_from_shares_with_encoding(
+        shares,
+        domain_size,
+        blowup_factor,
+        &EncodingOptions::default(),
+    )
+}
+
+/// Same as `recover_data_from_shares`, for data encoded with
+/// `erasure_encode_shares_with_encoding` and `encoding`.
+pub fn recover_data_from_shares_with_encoding<E: FieldElement>(
+    shares: &[Share<E>],
+    domain_size: usize,
+    blowup_factor: usize,
+    encoding: &EncodingOptions,
+) -> Result<Vec<u8>, FridaError> {
+    let (evaluations, positions) = split_shares(shares, domain_size)?;
+    recover_data_with_encoding(
+        &evaluations,
+        &positions,
+        domain_size,
+        blowup_factor,
+        encoding,
+    )
+}
+
+/// Splits `shares` into their values and positions, sorted by position, checking that every
+/// position lies in the domain and is shared at most once.
+fn split_shares<E: FieldElement>(
+    shares: &[Share<E>],
+    domain_size: usize,
+) -> Result<(Vec<E>, Vec<usize>), FridaError> {
+    let mut shares = shares.to_vec();
+    shares.sort_unstable_by_key(|share| share.index);
+    for (i, share) in shares.iter().enumerate() {
+        if share.index >= domain_size {
+            return Err(FridaError::PositionOutOfDomain(share.index, domain_size));
+        }
+        if i > 0 && shares[i - 1].index == share.index {
+            return Err(FridaError::DuplicateShare(share.index));
+        }
+    }
+    Ok(shares
+        .into_iter()
+        .map(|share| (share.value, share.index))
+        .unzip())
+}
+
+/// Returns the sorted positions of the evaluations holding bytes `range` of the data encoded with
+/// `blowup_factor` and the default `Packing::Element` over a domain of `domain_size`. The position
+/// of the length prefix, 0, is always included, so that the range can be checked against the
+/// length of the data.
+///
+/// # Errors
+/// Returns `FridaError::ByteRangeOutOfBounds` if the range is reversed, or if it is not held by
+/// the domain.
+pub fn byte_range_positions<E: FieldElement>(
+    range: &Range<usize>,
+    blowup_factor: usize,
+    domain_size: usize,
+) -> Result<Vec<usize>, FridaError> {
+    let element_size = E::ELEMENT_BYTES - 1;
+    let prefix_size = mem::size_of::<u64>();
+    let out_of_bounds = FridaError::ByteRangeOutOfBounds(range.start, range.end);
+
+    if range.start > range.end {
+        return Err(out_of_bounds);
+    }
+    if range.is_empty() {
+        return Ok(vec![0]);
+    }
+    // the range is checked against the domain before any position is allocated
+    let element = |offset: usize| offset.checked_add(prefix_size).map(|o| o / element_size);
+    let (Some(first), Some(last)) = (element(range.start), element(range.end - 1)) else {
+        return Err(out_of_bounds);
+    };
+    if last
+        .checked_mul(blowup_factor)
+        .is_none_or(|position| position >= domain_size)
+    {
+        return Err(out_of_bounds);
+    }
+
+    Ok([0]
+        .into_iter()
+        .chain(usize::max(first, 1)..=last)
+        .map(|e| e * blowup_factor)
+        .collect())
+}
+
+/// Extracts bytes `range` of the data from the evaluations at `byte_range_positions`, one per
+/// position.
+pub fn decode_byte_range<E: FieldElement>(
+    evaluations: &[E],
+    range: &Range<usize>,
+) -> Result<Vec<u8>, FridaError> {
+    let element_size = E::ELEMENT_BYTES - 1;
+    let prefix_size = mem::size_of::<u64>();
+
+    let data_len = u64::from_be_bytes(
+        evaluations
+            .first()
+            .ok_or(FridaError::NotEnoughEvaluationsForDecoding())?
+            .as_bytes()[0..prefix_size]
+            .try_into()
+            .unwrap(),
+    );
+    let out_of_bounds = FridaError::ByteRangeOutOfBounds(range.start, range.end);
+    if range.start > range.end || range.end as u64 > data_len {
+        return Err(out_of_bounds);
+    }
+    let start = range.start.checked_add(prefix_size).ok_or(out_of_bounds)?;
+    if range.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // the element holding the prefix only precedes the range if it does not overlap with it
+    let first = start / element_size;
+    let bytes = evaluations
+        .iter()
+        .skip(usize::from(first > 0))
+        .flat_map(|e| e.as_bytes()[..element_size].to_vec())
+        .skip(start % element_size)
+        .take(range.len())
+        .collect::<Vec<u8>>();
+    if bytes.len() != range.len() {
+        return Err(FridaError::NotEnoughEvaluationsForDecoding());
+    }
+    Ok(bytes)
+}
+
+/// Returns the range of the bytes of the data held by data element `index`, i.e. the evaluation
+/// at position `index * blowup_factor`, of data encoded with the default `Packing::Element`. The
+/// length of the data is read from `length_prefix`, the evaluation at position 0. The range is
+/// empty for elements only holding the length prefix.
+///
+/// # Errors
+/// Returns `FridaError::ValueOutOfBounds` if the element holds neither the data nor its length.
+pub fn value_byte_range<E: FieldElement>(
+    length_prefix: &E,
+    index: usize,
+) -> Result<Range<usize>, FridaError> {
+    let element_size = E::ELEMENT_BYTES - 1;
+    let prefix_size = mem::size_of::<u64>();
+
+    let data_len = u64::from_be_bytes(length_prefix.as_bytes()[0..prefix_size].try_into().unwrap());
+    let data_len = usize::try_from(data_len).unwrap_or(usize::MAX);
+    let element_count = prefix_size.saturating_add(data_len).div_ceil(element_size);
+    if index >= element_count {
+        return Err(FridaError::ValueOutOfBounds(index, element_count));
+    }
+
+    let start = (index * element_size).saturating_sub(prefix_size);
+    let end = ((index + 1) * element_size).saturating_sub(prefix_size);
+    Ok(start.min(data_len)..end.min(data_len))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use winter_math::fields::f128::BaseElement;
+    use winter_math::fields::{f128::BaseElement, f64, QuadExtension};
 
     #[test]
     fn test_build_evaluations_from_data() {
@@ -325,8 +897,13 @@ mod tests {
         let evaluations =
             build_evaluations_from_data::<BaseElement>(&data, domain_size, blowup_factor).unwrap();
 
-        let recovered_data =
-            extract_and_decode_data(&evaluations, domain_size, blowup_factor).unwrap();
+        let recovered_data = extract_and_decode_data(
+            &evaluations,
+            domain_size,
+            blowup_factor,
+            &Default::default(),
+        )
+        .unwrap();
 
         assert_eq!(data, recovered_data);
 
@@ -334,6 +911,7 @@ mod tests {
             &evaluations[0..evaluations.len() - 1],
             domain_size,
             blowup_factor,
+            &Default::default(),
         )
         .unwrap_err();
 
@@ -343,6 +921,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_and_decode_data_bad_length_prefix() {
+        let data = b"Test string";
+        let blowup_factor = 2;
+        let domain_size = 32;
+        let capacity = domain_size / blowup_factor * (BaseElement::ELEMENT_BYTES - 1) - 8;
+
+        let evaluations =
+            build_evaluations_from_data::<BaseElement>(data, domain_size, blowup_factor).unwrap();
+        let with_prefix = |data_len: u64| {
+            let mut bytes = evaluations[0].as_bytes().to_vec();
+            bytes[..8].copy_from_slice(&data_len.to_be_bytes());
+            let mut evaluations = evaluations.clone();
+            evaluations[0] = BaseElement::new(u128::from_le_bytes(bytes.try_into().unwrap()));
+            evaluations
+        };
+
+        // the largest length that fits is decoded as is
+        let decoded = extract_and_decode_data(
+            &with_prefix(capacity as u64),
+            domain_size,
+            blowup_factor,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(capacity, decoded.len());
+        assert_eq!(data, &decoded[..data.len()]);
+
+        for data_len in [capacity as u64 + 1, u32::MAX as u64, u64::MAX] {
+            assert_eq!(
+                Err(FridaError::DataLengthOutOfBounds(data_len, capacity)),
+                extract_and_decode_data(
+                    &with_prefix(data_len),
+                    domain_size,
+                    blowup_factor,
+                    &Default::default()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_packing_element_bits() {
+        type QuadElement = QuadExtension<f64::BaseElement>;
+        let bits = |packing| {
+            let encoding = EncodingOptions::new(packing);
+            [
+                encoding.element_bits::<BaseElement>(),
+                encoding.element_bits::<f64::BaseElement>(),
+                encoding.element_bits::<QuadElement>(),
+            ]
+        };
+        assert_eq!([120, 56, 120], bits(Packing::Element));
+        assert_eq!([120, 56, 112], bits(Packing::BaseFieldBytes));
+        assert_eq!([127, 63, 126], bits(Packing::Bits));
+    }
+
+    fn packing_round_trip<E: FieldElement>(data: &[u8], encoding: &EncodingOptions) {
+        let blowup_factor = 2;
+        let domain_size =
+            encoding.element_count::<E>(data.len()).next_power_of_two() * blowup_factor;
+        let evaluations =
+            build_evaluations_with_encoding::<E>(data, domain_size, blowup_factor, encoding)
+                .unwrap();
+
+        let positions = (0..domain_size).collect::<Vec<usize>>();
+        let recovered = recover_data_with_encoding(
+            &evaluations,
+            &positions,
+            domain_size,
+            blowup_factor,
+            encoding,
+        )
+        .unwrap();
+        assert_eq!(data, recovered);
+
+        let positions = (0..domain_size)
+            .step_by(blowup_factor)
+            .collect::<Vec<usize>>();
+        let queried = positions
+            .iter()
+            .map(|&p| evaluations[p])
+            .collect::<Vec<_>>();
+        let recovered =
+            recover_data_with_encoding(&queried, &positions, domain_size, blowup_factor, encoding)
+                .unwrap();
+        assert_eq!(data, recovered);
+    }
+
+    #[test]
+    fn test_packing_round_trip() {
+        let data = [0xff; 10]
+            .into_iter()
+            .chain((0..290u32).map(|i| (i * 13) as u8))
+            .collect::<Vec<u8>>();
+
+        for packing in [Packing::Element, Packing::BaseFieldBytes, Packing::Bits] {
+            packing_round_trip::<BaseElement>(&data, &EncodingOptions::new(packing));
+        }
+        for packing in [Packing::BaseFieldBytes, Packing::Bits] {
+            let encoding = EncodingOptions::new(packing);
+            packing_round_trip::<f64::BaseElement>(&data, &encoding);
+            packing_round_trip::<QuadExtension<f64::BaseElement>>(&data, &encoding);
+        }
+
+        let bits = EncodingOptions::new(Packing::Bits);
+        assert!(
+            bits.element_count::<BaseElement>(data.len())
+                < encoded_data_element_count::<BaseElement>(data.len())
+        );
+    }
+
+    #[test]
+    fn test_unpack_bad_length_prefix() {
+        let encoding = EncodingOptions::new(Packing::Bits);
+        let blowup_factor = 2;
+        let domain_size = 16;
+        let capacity = encoding.capacity::<BaseElement>(domain_size / blowup_factor);
+
+        // a prefix of all ones, followed by nothing
+        let mut evaluations = vec![BaseElement::new(u64::MAX as u128); domain_size / blowup_factor];
+        reed_solomon_encode_data(&mut evaluations, domain_size / blowup_factor, blowup_factor);
+        assert_eq!(
+            Err(FridaError::DataLengthOutOfBounds(u64::MAX, capacity)),
+            extract_and_decode_data(&evaluations, domain_size, blowup_factor, &encoding)
+        );
+    }
+
     #[test]
     fn test_encoded_data_element_count() {
         let element_size = BaseElement::ELEMENT_BYTES - 1;
@@ -354,4 +1060,204 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_encoding_plan() {
+        let blowup_factor = 2;
+        let encoding = EncodingOptions::default().with_sharding(true);
+        let capacity = encoding.capacity::<BaseElement>(64);
+
+        // data filling a power of two number of elements is never split
+        let plan = encoding_plan::<BaseElement>(capacity, blowup_factor, &encoding);
+        assert_eq!(
+            plan,
+            EncodingPlan {
+                shard_sizes: vec![capacity],
+                domain_sizes: vec![128],
+            }
+        );
+
+        // a few elements more go to a second, smaller domain instead of doubling the domain
+        let plan = encoding_plan::<BaseElement>(capacity + 100, blowup_factor, &encoding);
+        assert_eq!(plan.shard_sizes, vec![capacity, 100]);
+        assert_eq!(plan.domain_sizes, vec![128, 16]);
+        assert!(plan.total_domain_size() < 256);
+
+        let plan = encoding_plan::<BaseElement>(
+            capacity + 100,
+            blowup_factor,
+            &EncodingOptions::default(),
+        );
+        assert!(!plan.is_sharded());
+        assert_eq!(plan.domain_sizes, vec![256]);
+
+        // data barely short of the next power of two gains nothing from splitting
+        let plan = encoding_plan::<BaseElement>(
+            encoding.capacity::<BaseElement>(127),
+            blowup_factor,
+            &encoding,
+        );
+        assert!(!plan.is_sharded());
+
+        // splitting never goes below the minimum domain size
+        let plan = encoding_plan::<BaseElement>(30, blowup_factor, &encoding);
+        assert!(!plan.is_sharded());
+        assert_eq!(plan.domain_sizes, vec![MIN_DOMAIN_SIZE]);
+    }
+
+    #[test]
+    fn test_recover_sharded_data() {
+        let blowup_factor = 2;
+        let encoding = EncodingOptions::default().with_sharding(true);
+        let data = (0..1100usize).map(|i| (i * 7) as u8).collect::<Vec<u8>>();
+        let plan = encoding_plan::<BaseElement>(data.len(), blowup_factor, &encoding);
+        assert!(plan.is_sharded());
+
+        let shards = plan
+            .split(&data)
+            .into_iter()
+            .zip(&plan.domain_sizes)
+            .map(|(shard, &domain_size)| {
+                let evaluations =
+                    build_evaluations_from_data::<BaseElement>(shard, domain_size, blowup_factor)
+                        .unwrap();
+                let positions = (1..domain_size).step_by(2).collect::<Vec<_>>();
+                let evaluations = positions
+                    .iter()
+                    .map(|&p| evaluations[p])
+                    .collect::<Vec<_>>();
+                (evaluations, positions)
+            })
+            .collect::<Vec<_>>();
+        let samples = shards
+            .iter()
+            .map(|(evaluations, positions)| (&evaluations[..], &positions[..]))
+            .collect::<Vec<_>>();
+
+        let recovered =
+            recover_sharded_data(&samples, &plan.domain_sizes, blowup_factor, &encoding).unwrap();
+        assert_eq!(recovered, data);
+
+        assert_eq!(
+            recover_sharded_data(&samples[..1], &plan.domain_sizes, blowup_factor, &encoding),
+            Err(FridaError::XYCoordinateLengthMismatch())
+        );
+    }
+
+    #[test]
+    fn test_decode_byte_range() {
+        let data = (0..200u32).map(|i| (i * 7) as u8).collect::<Vec<u8>>();
+        let blowup_factor = 4;
+        let domain_size = 64;
+        let evaluations =
+            build_evaluations_from_data::<BaseElement>(&data, domain_size, blowup_factor).unwrap();
+
+        for range in [0..0, 0..1, 0..7, 7..8, 3..40, 150..200, 0..200, 199..200] {
+            let positions =
+                byte_range_positions::<BaseElement>(&range, blowup_factor, domain_size).unwrap();
+            assert_eq!(positions[0], 0);
+            assert!(positions.windows(2).all(|w| w[0] < w[1]));
+
+            let queried = positions
+                .iter()
+                .map(|&p| evaluations[p])
+                .collect::<Vec<_>>();
+            assert_eq!(
+                decode_byte_range(&queried, &range).unwrap(),
+                data[range.clone()]
+            );
+        }
+
+        let range = 190..201;
+        let positions =
+            byte_range_positions::<BaseElement>(&range, blowup_factor, domain_size).unwrap();
+        let queried = positions
+            .iter()
+            .map(|&p| evaluations[p])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decode_byte_range(&queried, &range),
+            Err(FridaError::ByteRangeOutOfBounds(190, 201))
+        );
+
+        // ranges which the domain does not hold are rejected before their positions are listed
+        for range in [0..1000, usize::MAX - 1..usize::MAX] {
+            assert_eq!(
+                byte_range_positions::<BaseElement>(&range, blowup_factor, domain_size),
+                Err(FridaError::ByteRangeOutOfBounds(range.start, range.end))
+            );
+        }
+    }
+
+    #[test]
+    fn test_erasure_encode_shares_round_trip() {
+        let data = (0..300u32).map(|i| (i * 13) as u8).collect::<Vec<u8>>();
+        let blowup_factor = 4;
+        let shares = erasure_encode_shares::<BaseElement>(&data, blowup_factor).unwrap();
+        let domain_size = shares.len();
+        assert_eq!(
+            domain_size,
+            EncodingOptions::default().domain_size::<BaseElement>(data.len(), blowup_factor)
+        );
+        assert!(shares.iter().enumerate().all(|(i, share)| share.index == i));
+
+        // any quarter of the shares, in any order, recovers the data and the other shares
+        let kept = shares
+            .iter()
+            .rev()
+            .step_by(blowup_factor)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            recover_data_from_shares(&kept, domain_size, blowup_factor).unwrap(),
+            data
+        );
+        assert_eq!(
+            reconstruct_shares(&kept, domain_size, blowup_factor).unwrap(),
+            shares
+        );
+
+        let options = EncodingOptions::new(Packing::Bits);
+        let shares =
+            erasure_encode_shares_with_encoding::<BaseElement>(&data, blowup_factor, &options)
+                .unwrap();
+        let domain_size = shares.len();
+        let kept = &shares[domain_size - domain_size / blowup_factor..];
+        assert_eq!(
+            recover_data_from_shares_with_encoding(kept, domain_size, blowup_factor, &options)
+                .unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_erasure_shares_errors() {
+        let data = b"Test string".repeat(10);
+        assert_eq!(
+            erasure_encode_shares::<BaseElement>(&data, 3),
+            Err(FridaError::InvalidBlowupFactor)
+        );
+
+        let shares = erasure_encode_shares::<BaseElement>(&data, 2).unwrap();
+        let domain_size = shares.len();
+        let kept = &shares[..domain_size / 2];
+        assert_eq!(
+            reconstruct_shares(&kept[1..], domain_size, 2),
+            Err(FridaError::NotEnoughDataPoints())
+        );
+
+        let mut duplicated = kept.to_vec();
+        duplicated[0].index = 1;
+        assert_eq!(
+            recover_data_from_shares(&duplicated, domain_size, 2),
+            Err(FridaError::DuplicateShare(1))
+        );
+
+        let mut outside = kept.to_vec();
+        outside[0].index = domain_size;
+        assert_eq!(
+            reconstruct_shares(&outside, domain_size, 2),
+            Err(FridaError::PositionOutOfDomain(domain_size, domain_size))
+        );
+    }
 }
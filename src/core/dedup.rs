@@ -0,0 +1,325 @@
+use crate::error::FridaError;
+use core::ops::Range;
+use std::collections::HashMap;
+use winter_utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// CHUNKING OPTIONS
+// ================================================================================================
+
+/// Parameters of the content-defined chunking used to deduplicate batches. Boundaries only
+/// depend on the bytes preceding them, so pages shared by several blobs are cut into the same
+/// chunks wherever they appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkingOptions {
+    /// No boundary is placed less than `min_size` bytes after the previous one.
+    pub min_size: usize,
+    /// Expected chunk size past `min_size`; must be a power of two.
+    pub avg_size: usize,
+    /// A boundary is forced once a chunk reaches `max_size` bytes.
+    pub max_size: usize,
+}
+
+impl Default for ChunkingOptions {
+    fn default() -> Self {
+        ChunkingOptions {
+            min_size: 1024,
+            avg_size: 4096,
+            max_size: 16384,
+        }
+    }
+}
+
+/// Gear hash table, filled deterministically with splitmix64 so that every party cuts the same
+/// boundaries.
+const GEAR: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+};
+
+/// Splits `data` into content-defined chunks, returned as consecutive ranges covering it.
+///
+/// # Panics
+/// Panics if `avg_size` is not a power of two or if `min_size > max_size`.
+pub fn chunk_boundaries(data: &[u8], options: &ChunkingOptions) -> Vec<Range<usize>> {
+    assert!(
+        options.avg_size.is_power_of_two(),
+        "average chunk size must be a power of two"
+    );
+    assert!(
+        options.min_size <= options.max_size,
+        "minimum chunk size exceeds the maximum"
+    );
+    // the high bits of the gear hash depend on the widest window of preceding bytes
+    let mask_bits = options.avg_size.trailing_zeros();
+    let mask = ((1u64 << mask_bits) - 1)
+        .checked_shl(64 - mask_bits)
+        .unwrap_or(0);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < data.len() {
+        let end = usize::min(start + options.max_size.max(1), data.len());
+        let mut cut = end;
+        let mut hash = 0u64;
+        for (i, &byte) in data[start..end].iter().enumerate() {
+            hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+            if i + 1 >= options.min_size && hash & mask == 0 {
+                cut = start + i + 1;
+                break;
+            }
+        }
+        chunks.push(start..cut);
+        start = cut;
+    }
+    chunks
+}
+
+// DEDUPLICATION
+// ================================================================================================
+
+/// Records how the blobs of a batch are rebuilt from a store of unique chunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DedupManifest {
+    /// Size of every unique chunk, in the order they are laid out in the store.
+    pub chunk_sizes: Vec<usize>,
+    /// For every blob of the batch, the indexes of its chunks in `chunk_sizes`.
+    pub blobs: Vec<Vec<usize>>,
+}
+
+impl DedupManifest {
+    /// Returns the number of bytes of the store this manifest describes.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidDedupManifest` if the chunk sizes add up past `usize::MAX`.
+    pub fn store_size(&self) -> Result<usize, FridaError> {
+        self.chunk_sizes
+            .iter()
+            .try_fold(0usize, |total, &size| total.checked_add(size))
+            .ok_or(FridaError::InvalidDedupManifest)
+    }
+
+    /// Rebuilds the blobs of the batch from the store of unique chunks.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidDedupManifest` if the chunk sizes do not add up to the size of
+    /// `store`, if a blob refers to a chunk the manifest does not have, or if a blob would be
+    /// larger than `usize::MAX` bytes.
+    pub fn reassemble(&self, store: &[u8]) -> Result<Vec<Vec<u8>>, FridaError> {
+        if store.len() != self.store_size()? {
+            return Err(FridaError::InvalidDedupManifest);
+        }
+        let mut chunks = Vec::with_capacity(self.chunk_sizes.len());
+        let mut rest = store;
+        for &size in &self.chunk_sizes {
+            let (chunk, tail) = rest
+                .split_at_checked(size)
+                .ok_or(FridaError::InvalidDedupManifest)?;
+            chunks.push(chunk);
+            rest = tail;
+        }
+
+        self.blobs
+            .iter()
+            .map(|indexes| {
+                let blob_chunks = indexes
+                    .iter()
+                    .map(|&index| chunks.get(index).ok_or(FridaError::InvalidDedupManifest))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // the size is checked before anything is allocated for the blob
+                let blob_size = blob_chunks
+                    .iter()
+                    .try_fold(0usize, |total, chunk| total.checked_add(chunk.len()))
+                    .ok_or(FridaError::InvalidDedupManifest)?;
+                let mut blob = Vec::with_capacity(blob_size);
+                for chunk in blob_chunks {
+                    blob.extend_from_slice(chunk);
+                }
+                Ok(blob)
+            })
+            .collect()
+    }
+}
+
+/// Cuts every blob of `data_list` into content-defined chunks and keeps a single copy of each
+/// distinct chunk. Returns the manifest along with the store of unique chunks.
+pub fn dedup_batch(data_list: &[Vec<u8>], options: &ChunkingOptions) -> (DedupManifest, Vec<u8>) {
+    let mut manifest = DedupManifest::default();
+    let mut store = Vec::new();
+    let mut seen = HashMap::<&[u8], usize>::new();
+
+    for data in data_list {
+        let indexes = chunk_boundaries(data, options)
+            .into_iter()
+            .map(|range| {
+                let chunk = &data[range];
+                *seen.entry(chunk).or_insert_with(|| {
+                    store.extend_from_slice(chunk);
+                    manifest.chunk_sizes.push(chunk.len());
+                    manifest.chunk_sizes.len() - 1
+                })
+            })
+            .collect();
+        manifest.blobs.push(indexes);
+    }
+    (manifest, store)
+}
+
+/// Deduplicates `data_list` into a single blob holding the serialized manifest followed by the
+/// store, so that committing to the blob also commits to the manifest.
+pub fn encode_deduped(
+    data_list: &[Vec<u8>],
+    options: &ChunkingOptions,
+) -> (DedupManifest, Vec<u8>) {
+    let (manifest, store) = dedup_batch(data_list, options);
+    let mut blob = manifest.to_bytes();
+    blob.extend_from_slice(&store);
+    (manifest, blob)
+}
+
+/// Rebuilds the blobs of a batch from a blob produced by `encode_deduped`.
+pub fn decode_deduped(blob: &[u8]) -> Result<Vec<Vec<u8>>, FridaError> {
+    let manifest = DedupManifest::read_from(&mut SliceReader::new(blob))
+        .map_err(FridaError::DeserializationError)?;
+    let offset = manifest.to_bytes().len();
+    manifest.reassemble(&blob[offset..])
+}
+
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for DedupManifest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.chunk_sizes.write_into(target);
+        self.blobs.write_into(target);
+    }
+}
+
+impl Deserializable for DedupManifest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let chunk_sizes = read_usizes(source)?;
+        let num_blobs = source.read_usize()?;
+        let mut blobs = Vec::new();
+        for _ in 0..num_blobs {
+            blobs.push(read_usizes(source)?);
+        }
+
+        Ok(DedupManifest { chunk_sizes, blobs })
+    }
+}
+
+/// Reads a length-prefixed list of `usize`s without reserving space for the claimed length
+/// upfront, so that the memory taken by a manifest is bounded by the bytes it is read from.
+fn read_usizes<R: ByteReader>(source: &mut R) -> Result<Vec<usize>, DeserializationError> {
+    let len = source.read_usize()?;
+    let mut values = Vec::new();
+    for _ in 0..len {
+        values.push(source.read_usize()?);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winter_rand_utils::rand_vector;
+
+    #[test]
+    fn test_chunk_boundaries_cover_data() {
+        let options = ChunkingOptions::default();
+        let data = rand_vector::<u8>(100_000);
+        let chunks = chunk_boundaries(&data, &options);
+
+        assert_eq!(chunks.first().unwrap().start, 0);
+        assert_eq!(chunks.last().unwrap().end, data.len());
+        for pair in chunks.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.len() >= options.min_size && chunk.len() <= options.max_size);
+        }
+    }
+
+    #[test]
+    fn test_shared_pages_are_stored_once() {
+        let options = ChunkingOptions::default();
+        let shared = rand_vector::<u8>(64 * 1024);
+        let data_list = (0..4)
+            .map(|_| {
+                let mut blob = rand_vector::<u8>(500);
+                blob.extend_from_slice(&shared);
+                blob
+            })
+            .collect::<Vec<_>>();
+
+        let (manifest, store) = dedup_batch(&data_list, &options);
+        assert_eq!(manifest.store_size().unwrap(), store.len());
+        assert!(store.len() < data_list.iter().map(Vec::len).sum::<usize>() / 2);
+        assert_eq!(manifest.reassemble(&store).unwrap(), data_list);
+    }
+
+    #[test]
+    fn test_deduped_blob_roundtrip() {
+        let options = ChunkingOptions::default();
+        let data_list = vec![rand_vector::<u8>(20_000), vec![], rand_vector::<u8>(300)];
+
+        let (manifest, blob) = encode_deduped(&data_list, &options);
+        assert_eq!(manifest.blobs.len(), 3);
+        assert_eq!(decode_deduped(&blob).unwrap(), data_list);
+
+        assert_eq!(
+            decode_deduped(&blob[..blob.len() - 1]),
+            Err(FridaError::InvalidDedupManifest)
+        );
+    }
+
+    #[test]
+    fn test_invalid_manifests_are_rejected() {
+        // chunk sizes overflowing the store size
+        let manifest = DedupManifest {
+            chunk_sizes: vec![usize::MAX, 2],
+            blobs: vec![vec![0, 1]],
+        };
+        assert_eq!(manifest.store_size(), Err(FridaError::InvalidDedupManifest));
+        assert_eq!(
+            manifest.reassemble(&[0; 1]),
+            Err(FridaError::InvalidDedupManifest)
+        );
+
+        // a chunk the manifest does not have
+        let manifest = DedupManifest {
+            chunk_sizes: vec![1],
+            blobs: vec![vec![1]],
+        };
+        assert_eq!(
+            manifest.reassemble(&[0; 1]),
+            Err(FridaError::InvalidDedupManifest)
+        );
+
+        // lengths claimed past the end of the input are not reserved before failing
+        let mut blob = Vec::new();
+        blob.write_usize(1 << 60);
+        assert!(matches!(
+            decode_deduped(&blob),
+            Err(FridaError::DeserializationError(_))
+        ));
+        let mut blob = Vec::new();
+        blob.write_usize(0);
+        blob.write_usize(1 << 60);
+        assert!(matches!(
+            decode_deduped(&blob),
+            Err(FridaError::DeserializationError(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use winter_crypto::{Digest, ElementHasher};
+use winter_math::{batch_inversion, fft, polynom, FieldElement, StarkField};
+use winter_utils::{ByteReader, Deserializable, DeserializationError, Serializable, SliceReader};
+
+use crate::{core::random::FridaRandom, error::FridaError};
+
+// OUT-OF-DOMAIN SAMPLE
+// ================================================================================================
+
+/// Out-of-domain sample of the first-layer polynomial `f` of a commitment built with
+/// `FridaProverBuilder::with_ood_check`.
+///
+/// FRI then tests the DEEP quotient `(f(x) - value) / (x - point) * (1 + comb * x)` in place of
+/// `f`. The quotient is of low degree only if `f(point) = value`, and `point` is drawn outside of
+/// the evaluation domain once `f` is committed, so a prover can no longer pass by committing to
+/// evaluations which are merely close to several low-degree polynomials, as is possible with a
+/// low blowup factor. Multiplying by `1 + comb * x` restores the degree lost by the division, so
+/// that the quotient is tested against the same bound as `f`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OodSample<E: FieldElement> {
+    pub point: E,
+    /// Value of the first-layer polynomial at `point`.
+    pub value: E,
+    pub comb: E,
+}
+
+impl<E: FieldElement> OodSample<E> {
+    /// Returns the value of the DEEP quotient at `x`, given the value `evaluation` of the
+    /// first-layer polynomial at `x`.
+    pub fn quotient(&self, x: E, evaluation: E) -> E {
+        (evaluation - self.value) / (x - self.point) * (E::ONE + self.comb * x)
+    }
+
+    /// Replaces transposed evaluations of the first-layer polynomial, as committed in rows of N
+    /// values over the domain of `N * rows.len()` elements shifted by `domain_offset`, by those of
+    /// the DEEP quotient.
+    pub fn apply_to_rows<const N: usize>(&self, rows: &mut [[E; N]], domain_offset: E::BaseField) {
+        // row i holds positions i + j * rows.len() of the domain
+        let row_count = rows.len();
+        let generator = E::BaseField::get_root_of_unity((row_count * N).ilog2());
+        let folding_roots = (0..N)
+            .map(|j| generator.exp_vartime(((row_count * j) as u64).into()))
+            .collect::<Vec<_>>();
+        let mut xs = Vec::with_capacity(row_count * N);
+        let mut row_x = domain_offset;
+        for _ in 0..row_count {
+            xs.extend(folding_roots.iter().map(|&root| E::from(row_x * root)));
+            row_x *= generator;
+        }
+
+        let denominators = batch_inversion(&xs.iter().map(|&x| x - self.point).collect::<Vec<_>>());
+        for ((evaluation, &x), &denominator) in
+            rows.iter_mut().flatten().zip(&xs).zip(&denominators)
+        {
+            *evaluation = (*evaluation - self.value) * denominator * (E::ONE + self.comb * x);
+        }
+    }
+}
+
+/// Draws the out-of-domain point from `public_coin`, once reseeded with the root of the first
+/// layer, and absorbs the value of the first-layer polynomial at that point, as returned by
+/// `evaluate`, before drawing the combination coefficient of the DEEP quotient.
+pub fn draw_ood_sample<E, HHst, HRandom>(
+    public_coin: &mut FridaRandom<E, HHst, HRandom>,
+    evaluate: impl FnOnce(E) -> E,
+) -> Result<OodSample<E>, FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    let point = public_coin.draw()?;
+    let value = evaluate(point);
+    public_coin.reseed(&HRandom::hash_elements(&[value]).as_bytes());
+    let comb = public_coin.draw()?;
+    // move the coin past `comb`, so that the folding randomness drawn next is independent of it
+    public_coin.reseed(E::elements_as_bytes(&[comb]));
+
+    Ok(OodSample { point, value, comb })
+}
+
+/// Evaluates at `point` the polynomial taking the values `evaluations` over the domain of
+/// `evaluations.len()` elements shifted by `domain_offset`.
+pub fn evaluate_out_of_domain<E: FieldElement>(
+    evaluations: &[E],
+    domain_offset: E::BaseField,
+    point: E,
+) -> E {
+    let mut coefficients = evaluations.to_vec();
+    let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(coefficients.len());
+    fft::interpolate_poly_with_offset(&mut coefficients, &inv_twiddles, domain_offset);
+    polynom::eval(&coefficients, point)
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+/// Serializes the value of `sample` as carried by commitments, which is empty without a sample.
+pub fn ood_evaluation_bytes<E: FieldElement>(sample: Option<&OodSample<E>>) -> Vec<u8> {
+    sample
+        .map(|sample| sample.value.to_bytes())
+        .unwrap_or_default()
+}
+
+/// Parses the out-of-domain evaluation carried by a commitment, if any.
+pub fn parse_ood_evaluation<E: FieldElement>(
+    bytes: &[u8],
+) -> Result<Option<E>, DeserializationError> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    let mut reader = SliceReader::new(bytes);
+    let value = E::read_from(&mut reader)?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winter_math::fields::f128::BaseElement;
+    use winter_utils::transpose_slice;
+
+    #[test]
+    fn test_apply_to_rows_matches_quotient() {
+        let offset = BaseElement::GENERATOR;
+        let coefficients = (1..=8u64).map(BaseElement::from).collect::<Vec<_>>();
+        let twiddles = fft::get_twiddles::<BaseElement>(coefficients.len());
+        let evaluations = fft::evaluate_poly_with_offset(&coefficients, &twiddles, offset, 4);
+        let domain_size = evaluations.len();
+
+        let point = BaseElement::from(1234u64);
+        let sample = OodSample {
+            point,
+            value: evaluate_out_of_domain(&evaluations, offset, point),
+            comb: BaseElement::from(7u64),
+        };
+        assert_eq!(sample.value, polynom::eval(&coefficients, point));
+
+        let mut rows = transpose_slice::<_, 4>(&evaluations);
+        sample.apply_to_rows(&mut rows, offset);
+        let generator = BaseElement::get_root_of_unity(domain_size.ilog2());
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                let position = i + j * rows.len();
+                let x = offset * generator.exp(position as u128);
+                assert_eq!(value, sample.quotient(x, evaluations[position]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_ood_evaluation() {
+        let value = BaseElement::from(42u64);
+        let sample = OodSample {
+            point: BaseElement::ONE,
+            value,
+            comb: BaseElement::ONE,
+        };
+
+        let bytes = ood_evaluation_bytes(Some(&sample));
+        assert_eq!(parse_ood_evaluation::<BaseElement>(&bytes), Ok(Some(value)));
+        assert_eq!(parse_ood_evaluation::<BaseElement>(&[]), Ok(None));
+        assert!(parse_ood_evaluation::<BaseElement>(&bytes[1..]).is_err());
+    }
+}
@@ -0,0 +1,768 @@
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+
+use winter_math::{ExtensionOf, FieldElement, StarkField};
+use winter_utils::{
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    Serializable, SliceReader,
+};
+
+// SMALL FIELD
+// ================================================================================================
+
+// A 31-bit prime field, with modulus `15 * 2^27 + 1` (BabyBear), and its degree-4 extension
+// `F[x] / (x^4 - 11)`. Evaluations of a commitment are elements of the extension, so that folding
+// and batching challenges are drawn from a field of about 2^124 elements; the base field alone is
+// far too small for them.
+//
+// The base field holds less data per byte of evaluation than the 64-bit and 128-bit fields: every
+// 32-bit coordinate stores 3 bytes with `Packing::BaseFieldBytes`, 75% of its size, against 15 of
+// 16 bytes for the 128-bit field with the default `Packing::Element`. `Packing::Bits` stores 30
+// bits per coordinate, 93.75% of its size, which matches the 128-bit field. The default packing
+// cannot be used with the extension: it fills every coordinate but the last one completely, so
+// that most data would not fit below the modulus.
+
+/// Field modulus, `15 * 2^27 + 1`.
+const M: u32 = 2013265921;
+
+/// `x^4 = W` in the extension.
+const W: u32 = 11;
+
+/// `W^((M - 1) / 4)`, by which the Frobenius map multiplies `x`.
+const FROBENIUS_ROOT: u32 = 1728404513;
+
+/// 2^27 root of unity, `GENERATOR^15`.
+const G: u32 = 440564289;
+
+/// Number of bytes needed to represent a base field element.
+const ELEMENT_BYTES: usize = mem::size_of::<u32>();
+
+// BASE FIELD ELEMENT
+// ================================================================================================
+
+/// Element of the 31-bit field, stored in canonical form as a `u32` in `[0, M)`.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct BaseElement(u32);
+
+impl BaseElement {
+    /// Creates a new field element from `value`, reduced modulo the field modulus.
+    pub const fn new(value: u32) -> BaseElement {
+        BaseElement(value % M)
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = u64;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = BaseElement(0);
+    const ONE: Self = BaseElement(1);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+    const IS_CANONICAL: bool = true;
+
+    fn inv(self) -> Self {
+        // x^(M - 2) = x^-1 by Fermat's little theorem, and 0 is mapped to 0
+        self.exp((M - 2) as u64)
+    }
+
+    fn conjugate(&self) -> Self {
+        *self
+    }
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        elements_from_bytes(bytes)
+    }
+}
+
+impl StarkField for BaseElement {
+    const MODULUS: Self::PositiveInteger = M as u64;
+    const MODULUS_BITS: u32 = 31;
+    const GENERATOR: Self = BaseElement(31);
+    const TWO_ADICITY: u32 = 27;
+    const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement(G);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        M.to_le_bytes().to_vec()
+    }
+
+    fn as_int(&self) -> Self::PositiveInteger {
+        self.0 as u64
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    /// Reads the first 4 bytes as a little-endian integer with its top bit cleared, and rejects
+    /// it if it is not below the field modulus, so that elements are drawn uniformly; one draw in
+    /// 16 is rejected.
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..ELEMENT_BYTES)?.try_into().ok()?;
+        let value = u32::from_le_bytes(bytes) & (u32::MAX >> 1);
+        (value < M).then_some(BaseElement(value))
+    }
+}
+
+impl Debug for BaseElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// BASE FIELD ARITHMETIC
+// ------------------------------------------------------------------------------------------------
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // both values are below 2^31, so their sum cannot overflow
+        let sum = self.0 + rhs.0;
+        BaseElement(if sum >= M { sum - M } else { sum })
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        BaseElement(if self.0 >= rhs.0 {
+            self.0 - rhs.0
+        } else {
+            self.0 + M - rhs.0
+        })
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        BaseElement((self.0 as u64 * rhs.0 as u64 % M as u64) as u32)
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        BaseElement::ZERO - self
+    }
+}
+
+// BASE FIELD CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element, reducing it modulo the field modulus.
+    fn from(value: u32) -> Self {
+        BaseElement::new(value)
+    }
+}
+
+impl From<u16> for BaseElement {
+    fn from(value: u16) -> Self {
+        BaseElement(value as u32)
+    }
+}
+
+impl From<u8> for BaseElement {
+    fn from(value: u8) -> Self {
+        BaseElement(value as u32)
+    }
+}
+
+impl TryFrom<u64> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value >= M as u64 {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(BaseElement(value as u32))
+        }
+    }
+}
+
+impl TryFrom<u128> for BaseElement {
+    type Error = String;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        u64::try_from(value)
+            .map_err(|e| e.to_string())
+            .and_then(Self::try_from)
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = DeserializationError;
+
+    /// Converts 4 little-endian bytes into a field element; returns an error if they do not
+    /// encode a value below the field modulus.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        read_exact(bytes)
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.0);
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = source.read_u32()?;
+        Self::try_from(value as u64).map_err(DeserializationError::InvalidValue)
+    }
+}
+
+// QUARTIC EXTENSION ELEMENT
+// ================================================================================================
+
+/// Element `a0 + a1 * x + a2 * x^2 + a3 * x^3` of the degree-4 extension of the 31-bit field,
+/// with `x^4 = 11`.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct QuarticElement([BaseElement; 4]);
+
+impl QuarticElement {
+    /// Creates a new extension element from its coordinates, lowest degree first.
+    pub const fn new(coordinates: [BaseElement; 4]) -> Self {
+        QuarticElement(coordinates)
+    }
+
+    /// Returns the coordinates of this element, lowest degree first.
+    pub const fn to_base_elements(self) -> [BaseElement; 4] {
+        self.0
+    }
+
+    /// Raises this element to the `power`, which may exceed the 64 bits of `exp`.
+    fn exp_u128(self, power: u128) -> Self {
+        let mut r = Self::ONE;
+        let mut b = self;
+        let mut p = power;
+        while p > 0 {
+            if p & 1 == 1 {
+                r *= b;
+            }
+            p >>= 1;
+            b = b.square();
+        }
+        r
+    }
+
+    /// Returns `self^M`, multiplying coordinate `i` by `FROBENIUS_ROOT^i`.
+    fn frobenius(self) -> Self {
+        let root = BaseElement(FROBENIUS_ROOT);
+        let mut power = BaseElement::ONE;
+        let mut result = self.0;
+        for coordinate in result.iter_mut() {
+            *coordinate *= power;
+            power *= root;
+        }
+        QuarticElement(result)
+    }
+}
+
+impl FieldElement for QuarticElement {
+    type PositiveInteger = u64;
+    type BaseField = BaseElement;
+
+    const EXTENSION_DEGREE: usize = 4;
+
+    const ZERO: Self = QuarticElement([BaseElement::ZERO; 4]);
+    const ONE: Self = QuarticElement([
+        BaseElement::ONE,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+    ]);
+
+    const ELEMENT_BYTES: usize = 4 * ELEMENT_BYTES;
+    const IS_CANONICAL: bool = true;
+
+    fn inv(self) -> Self {
+        // x^(M^4 - 2) = x^-1, the order of the multiplicative group being M^4 - 1
+        let order = (M as u128).pow(4) - 1;
+        self.exp_u128(order - 1)
+    }
+
+    fn conjugate(&self) -> Self {
+        self.frobenius()
+    }
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        self.0[i]
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        let ptr = elements.as_ptr();
+        let len = elements.len() * Self::EXTENSION_DEGREE;
+        unsafe { slice::from_raw_parts(ptr as *const Self::BaseField, len) }
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        assert!(
+            elements.len() % Self::EXTENSION_DEGREE == 0,
+            "number of base elements must be divisible by 4, but was {}",
+            elements.len()
+        );
+
+        let ptr = elements.as_ptr();
+        let len = elements.len() / Self::EXTENSION_DEGREE;
+        unsafe { slice::from_raw_parts(ptr as *const Self, len) }
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        BaseElement::elements_as_bytes(Self::slice_as_base_elements(elements))
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        elements_from_bytes(bytes)
+    }
+}
+
+impl ExtensionOf<BaseElement> for QuarticElement {
+    fn mul_base(self, other: BaseElement) -> Self {
+        QuarticElement(self.0.map(|a| a * other))
+    }
+}
+
+impl Randomizable for QuarticElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    /// Reads every coordinate from 4 of the first 16 bytes as `BaseElement::from_random_bytes`
+    /// does, rejecting the bytes if any coordinate is rejected, so that elements are drawn
+    /// uniformly; about a quarter of the draws are rejected.
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..Self::ELEMENT_BYTES)?;
+        let mut coordinates = [BaseElement::ZERO; 4];
+        for (coordinate, chunk) in coordinates.iter_mut().zip(bytes.chunks(ELEMENT_BYTES)) {
+            *coordinate = BaseElement::from_random_bytes(chunk)?;
+        }
+        Some(QuarticElement(coordinates))
+    }
+}
+
+impl Debug for QuarticElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for QuarticElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let [a0, a1, a2, a3] = self.0;
+        write!(f, "({a0}, {a1}, {a2}, {a3})")
+    }
+}
+
+// EXTENSION FIELD ARITHMETIC
+// ------------------------------------------------------------------------------------------------
+
+impl Add for QuarticElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        QuarticElement([
+            self.0[0] + rhs.0[0],
+            self.0[1] + rhs.0[1],
+            self.0[2] + rhs.0[2],
+            self.0[3] + rhs.0[3],
+        ])
+    }
+}
+
+impl AddAssign for QuarticElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for QuarticElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        QuarticElement([
+            self.0[0] - rhs.0[0],
+            self.0[1] - rhs.0[1],
+            self.0[2] - rhs.0[2],
+            self.0[3] - rhs.0[3],
+        ])
+    }
+}
+
+impl SubAssign for QuarticElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for QuarticElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let [a0, a1, a2, a3] = self.0;
+        let [b0, b1, b2, b3] = rhs.0;
+        let w = BaseElement(W);
+
+        // terms of degree 4 and above wrap around multiplied by x^4 = W
+        QuarticElement([
+            a0 * b0 + w * (a1 * b3 + a2 * b2 + a3 * b1),
+            a0 * b1 + a1 * b0 + w * (a2 * b3 + a3 * b2),
+            a0 * b2 + a1 * b1 + a2 * b0 + w * (a3 * b3),
+            a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0,
+        ])
+    }
+}
+
+impl MulAssign for QuarticElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for QuarticElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for QuarticElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for QuarticElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        QuarticElement(self.0.map(|a| -a))
+    }
+}
+
+// EXTENSION FIELD CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl From<BaseElement> for QuarticElement {
+    fn from(value: BaseElement) -> Self {
+        QuarticElement([
+            value,
+            BaseElement::ZERO,
+            BaseElement::ZERO,
+            BaseElement::ZERO,
+        ])
+    }
+}
+
+impl From<u32> for QuarticElement {
+    fn from(value: u32) -> Self {
+        Self::from(BaseElement::from(value))
+    }
+}
+
+impl From<u16> for QuarticElement {
+    fn from(value: u16) -> Self {
+        Self::from(BaseElement::from(value))
+    }
+}
+
+impl From<u8> for QuarticElement {
+    fn from(value: u8) -> Self {
+        Self::from(BaseElement::from(value))
+    }
+}
+
+impl TryFrom<u64> for QuarticElement {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        BaseElement::try_from(value).map(Self::from)
+    }
+}
+
+impl TryFrom<u128> for QuarticElement {
+    type Error = String;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        BaseElement::try_from(value).map(Self::from)
+    }
+}
+
+impl TryFrom<&[u8]> for QuarticElement {
+    type Error = DeserializationError;
+
+    /// Converts 16 bytes, the coordinates in order as 4 little-endian bytes each, into an
+    /// extension element; returns an error if a coordinate is not below the field modulus.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        read_exact(bytes)
+    }
+}
+
+impl AsBytes for QuarticElement {
+    fn as_bytes(&self) -> &[u8] {
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
+    }
+}
+
+impl Serializable for QuarticElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        for coordinate in self.0.iter() {
+            coordinate.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for QuarticElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(QuarticElement([
+            BaseElement::read_from(source)?,
+            BaseElement::read_from(source)?,
+            BaseElement::read_from(source)?,
+            BaseElement::read_from(source)?,
+        ]))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads an element from exactly `E::ELEMENT_BYTES` bytes.
+fn read_exact<E: FieldElement>(bytes: &[u8]) -> Result<E, DeserializationError> {
+    if bytes.len() != E::ELEMENT_BYTES {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected {} bytes for a field element, but was {} bytes",
+            E::ELEMENT_BYTES,
+            bytes.len(),
+        )));
+    }
+    E::read_from(&mut SliceReader::new(bytes))
+}
+
+/// Reinterprets `bytes` as elements of `E`, whose base elements are `u32`s.
+///
+/// # Safety
+/// The values of the elements are not checked to be below the field modulus.
+unsafe fn elements_from_bytes<E: FieldElement>(bytes: &[u8]) -> Result<&[E], DeserializationError> {
+    if bytes.len() % E::ELEMENT_BYTES != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of bytes ({}) does not divide into whole number of field elements",
+            bytes.len(),
+        )));
+    }
+
+    let p = bytes.as_ptr();
+    if (p as usize) % mem::align_of::<u32>() != 0 {
+        return Err(DeserializationError::InvalidValue(
+            "slice memory alignment is not valid for this field element type".to_string(),
+        ));
+    }
+
+    Ok(slice::from_raw_parts(
+        p as *const E,
+        bytes.len() / E::ELEMENT_BYTES,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use winter_math::fft;
+    use winter_rand_utils::{rand_value, rand_vector};
+
+    use super::*;
+
+    #[test]
+    fn test_base_field_arithmetic() {
+        let a = BaseElement::new(M - 1);
+        assert_eq!(a + BaseElement::ONE, BaseElement::ZERO);
+        assert_eq!(BaseElement::ZERO - BaseElement::ONE, a);
+        assert_eq!(a * a, BaseElement::ONE);
+
+        let r: BaseElement = rand_value();
+        assert_eq!(
+            r * r.inv(),
+            if r == BaseElement::ZERO {
+                r
+            } else {
+                BaseElement::ONE
+            }
+        );
+
+        let root = BaseElement::TWO_ADIC_ROOT_OF_UNITY;
+        assert_eq!(root.exp(1 << 27), BaseElement::ONE);
+        assert_ne!(root.exp(1 << 26), BaseElement::ONE);
+    }
+
+    #[test]
+    fn test_random_bytes_are_rejected_above_modulus() {
+        let draw = |value: u32| BaseElement::from_random_bytes(&value.to_le_bytes());
+        assert_eq!(draw(M - 1), Some(BaseElement(M - 1)));
+        assert_eq!(draw(M), None);
+        // the top bit is cleared rather than reduced
+        assert_eq!(draw((1 << 31) | 5), Some(BaseElement(5)));
+        assert_eq!(draw((1 << 31) | M), None);
+
+        let mut bytes = [0u8; 16];
+        bytes[12..].copy_from_slice(&M.to_le_bytes());
+        assert_eq!(QuarticElement::from_random_bytes(&bytes), None);
+        bytes[12..].copy_from_slice(&7u32.to_le_bytes());
+        assert_eq!(
+            QuarticElement::from_random_bytes(&bytes),
+            Some(QuarticElement::new([
+                BaseElement::ZERO,
+                BaseElement::ZERO,
+                BaseElement::ZERO,
+                BaseElement(7)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_extension_field_arithmetic() {
+        let a: QuarticElement = rand_value();
+        let b: QuarticElement = rand_value();
+        let c: QuarticElement = rand_value();
+        assert_eq!(a * (b + c), a * b + a * c);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a * a.inv(), QuarticElement::ONE);
+        assert_eq!(a / b * b, a);
+
+        // x^4 = W
+        let x = QuarticElement::new([
+            BaseElement::ZERO,
+            BaseElement::ONE,
+            BaseElement::ZERO,
+            BaseElement::ZERO,
+        ]);
+        assert_eq!(x.exp(4), QuarticElement::from(W));
+
+        // the Frobenius map is raising to the power M, and fixes the base field
+        assert_eq!(a.conjugate(), a.exp(M as u64));
+        let base = QuarticElement::from(BaseElement::new(12345));
+        assert_eq!(base.conjugate(), base);
+        assert_eq!(
+            <QuarticElement as ExtensionOf<BaseElement>>::mul_base(a, BaseElement::new(3)),
+            a * QuarticElement::from(3u32)
+        );
+    }
+
+    #[test]
+    fn test_extension_serialization() {
+        let elements: Vec<QuarticElement> = rand_vector(8);
+        let bytes = elements
+            .iter()
+            .flat_map(Serializable::to_bytes)
+            .collect::<Vec<u8>>();
+        assert_eq!(bytes, QuarticElement::elements_as_bytes(&elements));
+        for (element, chunk) in elements.iter().zip(bytes.chunks(16)) {
+            assert_eq!(&QuarticElement::try_from(chunk).unwrap(), element);
+        }
+
+        let base_elements = QuarticElement::slice_as_base_elements(&elements);
+        assert_eq!(base_elements.len(), 32);
+        assert_eq!(
+            QuarticElement::slice_from_base_elements(base_elements),
+            elements
+        );
+
+        // coordinates must be below the modulus
+        let mut bytes = QuarticElement::ONE.to_bytes();
+        bytes[4..8].copy_from_slice(&M.to_le_bytes());
+        assert!(QuarticElement::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_polynomial_evaluation() {
+        // evaluations over the domain interpolate back to the coefficients
+        let coefficients: Vec<QuarticElement> = rand_vector(16);
+        let mut evaluations = coefficients.clone();
+        evaluations.resize(64, QuarticElement::ZERO);
+        fft::evaluate_poly(&mut evaluations, &fft::get_twiddles::<BaseElement>(64));
+        fft::interpolate_poly(&mut evaluations, &fft::get_inv_twiddles::<BaseElement>(64));
+        assert_eq!(evaluations[..16], coefficients);
+        assert!(evaluations[16..].iter().all(|e| *e == QuarticElement::ZERO));
+    }
+}
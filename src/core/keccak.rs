@@ -0,0 +1,243 @@
+use core::marker::PhantomData;
+
+use winter_crypto::{Digest, ElementHasher, Hasher};
+use winter_math::{FieldElement, StarkField};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use crate::core::random::FridaRandom;
+
+// KECCAK TRANSCRIPT
+// ================================================================================================
+
+/// Public coin deriving every challenge with Keccak256, as a Solidity verifier computes them with
+/// the `keccak256` builtin.
+///
+/// Challenges are derived as by any other `FridaRandom`, all integers being encoded as fixed-width
+/// big-endian words, as `abi.encodePacked` does:
+/// * reseeding with `root` sets `hst = keccak256(root || hst || uint64(counter))`, starting from
+///   `hst = 0x7b`;
+/// * a folding challenge is read from `keccak256(hst[:ELEMENT_BYTES])`;
+/// * the i-th batching coefficient is read from `keccak256(hst || uint64(i))`;
+/// * the i-th query position is `uint64(bytes8(keccak256(hst || uint64(i)))) & (domain_size - 1)`.
+///
+/// Field elements are read from the first `ELEMENT_BYTES` bytes of a digest as a little-endian
+/// integer, as `FieldElement::from_random_bytes` does for every transcript, and rejected if they
+/// are not smaller than the modulus; a Solidity verifier reverses these bytes before comparing
+/// them with the modulus. A rejected digest is replaced by `keccak256(digest || uint64(attempt))`,
+/// counting attempts from 1.
+pub type KeccakRandom<E> = FridaRandom<
+    E,
+    Keccak256<<E as FieldElement>::BaseField>,
+    Keccak256<<E as FieldElement>::BaseField>,
+>;
+
+// KECCAK256 HASHER
+// ================================================================================================
+
+/// Keccak256 hasher over elements of field `B`, computing the same digests as the `keccak256`
+/// builtin of the EVM; see `KeccakRandom`.
+///
+/// Digests are merged by hashing their concatenation, integers are encoded as `uint64`
+/// big-endian words, and field elements as big-endian words of `B::ELEMENT_BYTES` bytes, base
+/// elements of extension fields in order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = KeccakDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> KeccakDigest {
+        KeccakDigest(keccak256(bytes))
+    }
+
+    fn merge(values: &[KeccakDigest; 2]) -> KeccakDigest {
+        Self::merge_many(values)
+    }
+
+    fn merge_many(values: &[KeccakDigest]) -> KeccakDigest {
+        let bytes = values
+            .iter()
+            .flat_map(|digest| digest.0)
+            .collect::<Vec<_>>();
+        Self::hash(&bytes)
+    }
+
+    fn merge_with_int(seed: KeccakDigest, value: u64) -> KeccakDigest {
+        Self::hash(&[&seed.0[..], &value.to_be_bytes()].concat())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = B>>(elements: &[E]) -> KeccakDigest {
+        let mut bytes = Vec::with_capacity(elements.len() * E::ELEMENT_BYTES);
+        for element in E::slice_as_base_elements(elements) {
+            // elements serialize to canonical little-endian bytes
+            let mut word = element.to_bytes();
+            word.reverse();
+            bytes.extend_from_slice(&word);
+        }
+        Self::hash(&bytes)
+    }
+}
+
+/// Digest of a `Keccak256` hasher, i.e. a `bytes32` of Solidity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeccakDigest([u8; 32]);
+
+impl KeccakDigest {
+    pub fn new(bytes: [u8; 32]) -> Self {
+        KeccakDigest(bytes)
+    }
+}
+
+impl Digest for KeccakDigest {
+    fn as_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl Serializable for KeccakDigest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.0);
+    }
+
+    fn get_size_hint(&self) -> usize {
+        32
+    }
+}
+
+impl Deserializable for KeccakDigest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(KeccakDigest(source.read_array()?))
+    }
+}
+
+// KECCAK256
+// ================================================================================================
+
+/// Hashes `bytes` with Keccak256, i.e. with the original Keccak padding rather than the one of
+/// SHA3-256.
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    <sha3::Keccak256 as sha3::Digest>::digest(bytes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use winter_math::fields::f128::BaseElement;
+    use winter_rand_utils::rand_vector;
+
+    use super::*;
+    use crate::{
+        prover::builder::FridaProverBuilder,
+        utils::{encoding::from_hex, test_utils::test_options},
+        verifier::das::FridaDasVerifier,
+    };
+
+    type Keccak = Keccak256<BaseElement>;
+
+    fn digest(hex: &str) -> KeccakDigest {
+        KeccakDigest(from_hex(hex).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn keccak256_matches_evm() {
+        // keccak256("") and keccak256("abc"), as returned by the EVM
+        assert_eq!(
+            Keccak::hash(b""),
+            digest("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+        assert_eq!(
+            Keccak::hash(b"abc"),
+            digest("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45")
+        );
+        // inputs around the rate, which are padded into one or two blocks
+        assert_eq!(
+            Keccak::hash(&[b'a'; 135]),
+            digest("34367dc248bbd832f4e3e69dfaac2f92638bd0bbd18f2912ba4ef454919cf446")
+        );
+        assert_eq!(
+            Keccak::hash(&[b'a'; 136]),
+            digest("a6c4d403279fe3e0af03729caada8374b5ca54d8065329a3ebcaeb4b60aa386e")
+        );
+        assert_eq!(
+            Keccak::hash(&[b'a'; 300]),
+            digest("5b7e0e47a96f32a88b4f14ca177982790807c40e1a105742ba0fc1babe1ef826")
+        );
+    }
+
+    #[test]
+    fn encodings_match_evm() {
+        let r0 = Keccak::hash(b"frida root 0");
+        let r1 = Keccak::hash(b"frida root 1");
+        // keccak256(abi.encodePacked(r0, r1))
+        assert_eq!(
+            Keccak::merge(&[r0, r1]),
+            digest("4ad6e4ca0bc1128c5e8fcd7b7e4fed052a6ca21639180689501d1ca420563f7d")
+        );
+        // keccak256(abi.encodePacked(r0, uint64(7)))
+        assert_eq!(
+            Keccak::merge_with_int(r0, 7),
+            digest("c8a43782a8b77007c3048bcf4dbfabaa964d0fe9d402163fd641a13353f69854")
+        );
+        // keccak256(abi.encodePacked(uint128(0), uint128(1), uint128(p - 1), uint128(2^64 + 5)))
+        let elements = [
+            BaseElement::ZERO,
+            BaseElement::ONE,
+            -BaseElement::ONE,
+            BaseElement::new((1 << 64) + 5),
+        ];
+        assert_eq!(
+            Keccak::hash_elements(&elements),
+            digest("e5466bddd7f2ab62b5666119f1b55bbae1a08f204a865f096e407714ebac0f19")
+        );
+    }
+
+    #[test]
+    fn challenges_match_evm() {
+        let mut coin = KeccakRandom::<BaseElement>::new();
+        coin.reseed(&Keccak::hash(b"frida root 0").as_bytes());
+        assert_eq!(
+            coin.draw().unwrap(),
+            BaseElement::new(67925079858220729244139502522406218779)
+        );
+        assert_eq!(
+            coin.draw_xi(2).unwrap(),
+            [
+                BaseElement::new(272174220592057007540753032477621931939),
+                BaseElement::new(34200201063723374676000899113474408399),
+            ]
+        );
+
+        coin.reseed(&Keccak::hash(b"frida root 1").as_bytes());
+        assert_eq!(
+            coin.draw().unwrap(),
+            BaseElement::new(260788102354780478900071422506926503037)
+        );
+        assert_eq!(coin.draw_query_positions(4, 64).unwrap(), [26, 11, 15, 10]);
+    }
+
+    #[test]
+    fn keccak_commitments_verify() {
+        let options = test_options();
+        let builder = FridaProverBuilder::<BaseElement, Keccak, Keccak>::new(options.clone());
+        let data = rand_vector::<u8>(500);
+        let (commitment, prover, transcript) =
+            builder.commit_and_prove_with_transcript(&data, 4).unwrap();
+        transcript.verify::<Keccak, Keccak>().unwrap();
+
+        let (verifier, _) =
+            FridaDasVerifier::<BaseElement, Keccak, Keccak>::new(commitment, options).unwrap();
+        let positions = [0, 5, 17];
+        verifier
+            .verify(
+                &prover.open(&positions).unwrap(),
+                &prover.get_evaluations(&positions).unwrap(),
+                &positions,
+            )
+            .unwrap();
+    }
+}
@@ -1,6 +1,19 @@
+pub mod accumulator;
+pub mod assignment;
+pub mod coverage;
 pub mod data;
+pub mod dedup;
+pub mod deep;
+#[cfg(feature = "f31")]
+pub mod f31;
+#[cfg(feature = "keccak")]
+pub mod keccak;
+pub mod positions;
 pub mod queries;
 pub mod random;
+pub mod schedule;
+pub mod stir;
+pub mod transcript;
 
 // Re-export commonly used items for convenience
 pub use data::*;
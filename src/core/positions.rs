@@ -0,0 +1,245 @@
+use winter_utils::{ByteReader, ByteWriter, DeserializationError};
+
+use crate::constants::MAX_DOMAIN_SIZE;
+
+/// Tag of position lists written as the deltas between consecutive positions.
+const DELTAS: u8 = 0;
+/// Tag of position lists written as runs of positions in arithmetic progression.
+const RUNS: u8 = 1;
+
+// COMPACT POSITIONS
+// ================================================================================================
+
+/// Writes `positions` in the shorter of two encodings, both starting with a tag byte and the
+/// number of positions:
+/// - deltas: every position as the zigzag-encoded difference from the previous one (0 for the
+///   first), as a vint64;
+/// - runs: the number of runs, then for every maximal run of positions in arithmetic progression,
+///   the difference of its first position from the last position of the previous run, its
+///   length and, for runs of more than one position, their stride, all as vint64s with the
+///   differences zigzag-encoded.
+///
+/// Assignments are typically contiguous or strided ranges, which take a few bytes as runs, and
+/// sorted random positions take fewer bytes as deltas than as a plain list. Positions are kept in
+/// order, so that decoding returns the list as written.
+pub fn write_positions<W: ByteWriter>(positions: &[usize], target: &mut W) {
+    let deltas = encode_deltas(positions);
+    let runs = encode_runs(positions);
+    if runs.len() < deltas.len() {
+        target.write_u8(RUNS);
+        target.write_usize(positions.len());
+        target.write_bytes(&runs);
+    } else {
+        target.write_u8(DELTAS);
+        target.write_usize(positions.len());
+        target.write_bytes(&deltas);
+    }
+}
+
+/// Reads positions written by `write_positions`.
+///
+/// `max_positions` is capped at `MAX_DOMAIN_SIZE`, so that the list allocated for the positions
+/// stays bounded whatever the caller passes, e.g. a domain size read from the same untrusted input.
+///
+/// # Errors
+/// Returns an error if the list holds more than `max_positions` positions, e.g. more than the
+/// domain they index, if its runs do not add up to its length, or if the tag is unknown.
+pub fn read_positions<R: ByteReader>(
+    source: &mut R,
+    max_positions: usize,
+) -> Result<Vec<usize>, DeserializationError> {
+    let max_positions = max_positions.min(MAX_DOMAIN_SIZE);
+    let tag = source.read_u8()?;
+    let num_positions = source.read_usize()?;
+    if num_positions > max_positions {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected at most {max_positions} positions, but got {num_positions}"
+        )));
+    }
+
+    let mut positions = Vec::with_capacity(num_positions);
+    let mut previous = 0usize;
+    match tag {
+        DELTAS => {
+            for _ in 0..num_positions {
+                previous = previous.wrapping_add(unzigzag(source.read_usize()?));
+                positions.push(previous);
+            }
+        }
+        RUNS => {
+            let num_runs = source.read_usize()?;
+            for _ in 0..num_runs {
+                let start = previous.wrapping_add(unzigzag(source.read_usize()?));
+                let length = source.read_usize()?;
+                if length == 0 || length > num_positions - positions.len() {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "run of {length} positions does not fit in a list of {num_positions}"
+                    )));
+                }
+                let stride = if length > 1 {
+                    unzigzag(source.read_usize()?)
+                } else {
+                    0
+                };
+                for i in 0..length {
+                    previous = start.wrapping_add(stride.wrapping_mul(i));
+                    positions.push(previous);
+                }
+            }
+            if positions.len() != num_positions {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "runs hold {} positions, but the list has {num_positions}",
+                    positions.len()
+                )));
+            }
+        }
+        _ => {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unknown positions encoding {tag}"
+            )))
+        }
+    }
+    Ok(positions)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn encode_deltas(positions: &[usize]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut previous = 0usize;
+    for &position in positions {
+        bytes.write_usize(zigzag(position.wrapping_sub(previous)));
+        previous = position;
+    }
+    bytes
+}
+
+fn encode_runs(positions: &[usize]) -> Vec<u8> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < positions.len() {
+        let stride = positions
+            .get(start + 1)
+            .map_or(0, |next| next.wrapping_sub(positions[start]));
+        let mut end = start + 1;
+        while end < positions.len() && positions[end].wrapping_sub(positions[end - 1]) == stride {
+            end += 1;
+        }
+        runs.push((start, end - start, stride));
+        start = end;
+    }
+
+    let mut bytes = Vec::new();
+    bytes.write_usize(runs.len());
+    let mut previous = 0usize;
+    for (start, length, stride) in runs {
+        bytes.write_usize(zigzag(positions[start].wrapping_sub(previous)));
+        bytes.write_usize(length);
+        if length > 1 {
+            bytes.write_usize(zigzag(stride));
+        }
+        previous = positions[start + length - 1];
+    }
+    bytes
+}
+
+/// Maps a wrapping difference of positions to a small value if it is small in either direction.
+fn zigzag(delta: usize) -> usize {
+    let delta = delta as isize;
+    ((delta << 1) ^ (delta >> (isize::BITS - 1))) as usize
+}
+
+fn unzigzag(value: usize) -> usize {
+    (value >> 1) ^ (value & 1).wrapping_neg()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winter_rand_utils::rand_value;
+    use winter_utils::{Serializable, SliceReader};
+
+    fn roundtrip(positions: &[usize]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_positions(positions, &mut bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(
+            read_positions(&mut reader, positions.len()).unwrap(),
+            positions
+        );
+        assert!(!reader.has_more_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_positions_roundtrip() {
+        let random = (0..100)
+            .map(|_| rand_value::<u64>() as usize % 4096)
+            .collect::<Vec<_>>();
+        for positions in [
+            vec![],
+            vec![7],
+            vec![3, 3, 3],
+            vec![9, 5, 1, 0, usize::MAX, 2],
+            random,
+        ] {
+            roundtrip(&positions);
+        }
+    }
+
+    #[test]
+    fn test_progressions_are_compact() {
+        let contiguous = (1000..1500).collect::<Vec<_>>();
+        assert!(roundtrip(&contiguous).len() < 10);
+        let strided = (0..64)
+            .map(|i| 3 + 256 * i)
+            .chain(500..510)
+            .collect::<Vec<_>>();
+        assert!(roundtrip(&strided).len() < 16);
+
+        // lists without progressions are written as deltas, shorter than a plain list
+        let mut sorted = (0..100)
+            .map(|_| rand_value::<u64>() as usize % (1 << 20))
+            .collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let mut plain = Vec::new();
+        sorted.write_into(&mut plain);
+        let encoded = roundtrip(&sorted);
+        assert_eq!(encoded[0], DELTAS);
+        assert!(encoded.len() < plain.len());
+    }
+
+    #[test]
+    fn test_invalid_positions_are_rejected() {
+        let mut bytes = Vec::new();
+        write_positions(&(0..100).collect::<Vec<_>>(), &mut bytes);
+        assert!(read_positions(&mut SliceReader::new(&bytes), 99).is_err());
+
+        // a run longer than the list
+        let mut bytes = vec![RUNS];
+        for value in [2, 1, 0, 5, 2] {
+            bytes.write_usize(value);
+        }
+        assert!(read_positions(&mut SliceReader::new(&bytes), 10).is_err());
+
+        // runs shorter than the list
+        let mut bytes = vec![RUNS];
+        for value in [3, 1, 0, 2, 2] {
+            bytes.write_usize(value);
+        }
+        assert!(read_positions(&mut SliceReader::new(&bytes), 10).is_err());
+
+        // lists are bounded by the largest domain, whatever the caller allows
+        let mut bytes = vec![RUNS];
+        for value in [MAX_DOMAIN_SIZE + 1, 1, 0, MAX_DOMAIN_SIZE + 1, 1] {
+            bytes.write_usize(value);
+        }
+        assert!(read_positions(&mut SliceReader::new(&bytes), usize::MAX).is_err());
+
+        // an unknown encoding
+        let mut bytes = vec![2];
+        bytes.write_usize(0);
+        assert!(read_positions(&mut SliceReader::new(&bytes), 10).is_err());
+    }
+}
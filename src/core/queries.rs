@@ -1,8 +1,8 @@
 use crate::{
     constants,
-    core::data::encoded_data_element_count,
+    core::data::{encoded_data_element_count, EncodingOptions},
     error::FridaError,
-    winterfell::{f128::BaseElement, FriOptions},
+    winterfell::{f128::BaseElement, FieldElement, FriOptions, StarkField},
 };
 
 /// Calculates the required number of FRI queries (`σ`) for a given security level.
@@ -33,6 +33,61 @@ pub fn calculate_num_queries(
         constants::MIN_DOMAIN_SIZE,
     );
 
+    Ok(num_queries_for_domain(
+        domain_size,
+        options,
+        batch_size,
+        lambda_security,
+    ))
+}
+
+/// Same as [calculate_num_queries], for data packed with `encoding` into evaluations in `E`.
+///
+/// Unlike the 128-bit field assumed by [calculate_num_queries], small fields limit the security
+/// of the folding and batching challenges drawn from them, whatever the number of queries; see
+/// [field_security_level].
+///
+/// # Errors
+/// Returns `FridaError::FieldTooSmall` if the challenges drawn from `E` cannot reach
+/// `lambda_security` bits, and `FridaError::InvalidBlowupFactor` if the blowup factor is not
+/// greater than 1.
+pub fn calculate_num_queries_in_field<E: FieldElement>(
+    data_size: usize,
+    options: &FriOptions,
+    batch_size: usize,
+    lambda_security: u32,
+    encoding: &EncodingOptions,
+) -> Result<usize, FridaError> {
+    if options.blowup_factor() <= 1 {
+        return Err(FridaError::InvalidBlowupFactor);
+    }
+
+    let domain_size = usize::max(
+        encoding.element_count::<E>(data_size).next_power_of_two() * options.blowup_factor(),
+        constants::MIN_DOMAIN_SIZE,
+    );
+    let field_level = field_security_level::<E>(domain_size, options, batch_size);
+    if field_level < lambda_security {
+        return Err(FridaError::FieldTooSmall(lambda_security, field_level));
+    }
+
+    Ok(num_queries_for_domain(
+        domain_size,
+        options,
+        batch_size,
+        lambda_security,
+    ))
+}
+
+/// Number of queries reaching `lambda_security` bits over an evaluation domain of `domain_size`.
+fn num_queries_for_domain(
+    domain_size: usize,
+    options: &FriOptions,
+    batch_size: usize,
+    lambda_security: u32,
+) -> usize {
+    let blowup_factor = options.blowup_factor();
+
     // The degree of the polynomial.
     let degree = (domain_size / blowup_factor) - 1;
 
@@ -59,7 +114,115 @@ pub fn calculate_num_queries(
     // The number of queries cannot exceed the number of available points in the domain.
     let max_possible_queries = domain_size.saturating_sub(1);
 
-    Ok(calculated_queries.min(max_possible_queries))
+    calculated_queries.min(max_possible_queries)
+}
+
+/// Calculates the security level, in bits, of a batched commitment opened at `num_queries`
+/// positions.
+///
+/// Folding `batch_size` polynomials with random `xi` coefficients adds a correlated agreement term
+/// to the soundness error: the combination may be close to the code even though one of the
+/// polynomials is not. This term is bounded (conservatively) by `(batch_size - 1) * domain_size /
+/// |F|`, so the returned level is the smaller of the query security and the batching security.
+///
+/// # Parameters
+/// - `data_size`: The size of the largest blob in the batch, in bytes.
+/// - `options`: The `FriOptions` struct containing blowup factor, folding factor, etc.
+/// - `batch_size`: The number of polynomials being batched together.
+/// - `num_queries`: The number of queries the commitment is opened at.
+pub fn calculate_batch_security_level<E: FieldElement>(
+    data_size: usize,
+    options: &FriOptions,
+    batch_size: usize,
+    num_queries: usize,
+) -> Result<u32, FridaError> {
+    if options.blowup_factor() <= 1 {
+        return Err(FridaError::InvalidBlowupFactor);
+    }
+
+    let encoded_element_count = encoded_data_element_count::<E>(data_size);
+    let domain_size = usize::max(
+        encoded_element_count.next_power_of_two() * options.blowup_factor(),
+        constants::MIN_DOMAIN_SIZE,
+    );
+
+    Ok(batch_security_level::<E>(
+        domain_size,
+        options,
+        batch_size,
+        num_queries,
+    ))
+}
+
+/// Same as [calculate_batch_security_level], for an already known evaluation domain size.
+pub(crate) fn batch_security_level<E: FieldElement>(
+    domain_size: usize,
+    options: &FriOptions,
+    batch_size: usize,
+    num_queries: usize,
+) -> u32 {
+    let blowup_factor = options.blowup_factor();
+    let degree = (domain_size / blowup_factor) - 1;
+    let security_loss = security_loss_due_to_folding(
+        degree,
+        options.folding_factor(),
+        options.remainder_max_degree(),
+    );
+    let log2_batch_size = if batch_size > 0 {
+        (batch_size as f64).log2()
+    } else {
+        0.0
+    };
+
+    // inverse of the formula used by `calculate_num_queries`
+    let query_bits =
+        (num_queries as f64 - security_loss - log2_batch_size) * (blowup_factor as f64).log2();
+
+    let field_level = field_security_bits::<E>(domain_size, options, batch_size);
+    query_bits.min(field_level).max(0.0).floor() as u32
+}
+
+/// Calculates the security level, in bits, that the challenges drawn from `E` allow for a
+/// commitment to `batch_size` polynomials over an evaluation domain of `domain_size`, whatever
+/// the number of queries.
+///
+/// Both batching with random `xi` coefficients and every folding round may map polynomials far
+/// from the code to one close to it; these are bounded (conservatively) by
+/// `(batch_size - 1) * domain_size / |E|` and `domain_size / |E|` per round respectively. The
+/// bounds only matter for small fields: they leave more than 100 bits in the 128-bit field, but
+/// only about 100 bits in the degree-4 extension of `core::f31`.
+pub fn field_security_level<E: FieldElement>(
+    domain_size: usize,
+    options: &FriOptions,
+    batch_size: usize,
+) -> u32 {
+    field_security_bits::<E>(domain_size, options, batch_size)
+        .max(0.0)
+        .floor() as u32
+}
+
+fn field_security_bits<E: FieldElement>(
+    domain_size: usize,
+    options: &FriOptions,
+    batch_size: usize,
+) -> f64 {
+    let field_bits = (E::BaseField::MODULUS_BITS as usize * E::EXTENSION_DEGREE) as f64;
+    let batching_bits = if batch_size > 1 {
+        field_bits - ((batch_size - 1) as f64 * domain_size as f64).log2()
+    } else {
+        field_bits
+    };
+
+    // every layer is folded until its polynomial fits in the remainder
+    let mut num_coefficients = domain_size / options.blowup_factor();
+    let mut num_rounds = 1;
+    while num_coefficients > options.remainder_max_degree() + 1 {
+        num_coefficients /= options.folding_factor();
+        num_rounds += 1;
+    }
+    let folding_bits = field_bits - (num_rounds as f64 * domain_size as f64).log2();
+
+    batching_bits.min(folding_bits)
 }
 
 /// Calculates the security loss incurred from using a folding factor greater than 2.
@@ -161,6 +324,39 @@ mod tests {
         assert_eq!(result, Err(FridaError::InvalidBlowupFactor));
     }
 
+    #[test]
+    fn test_batch_security_level_meets_target() {
+        let options = FriOptions::new(8, 4, 63);
+        for batch_size in [2, 16, 64] {
+            let queries = calculate_num_queries(1024 * 32, &options, batch_size, 100).unwrap();
+            let level = calculate_batch_security_level::<BaseElement>(
+                1024 * 32,
+                &options,
+                batch_size,
+                queries,
+            )
+            .unwrap();
+            assert!(level >= 100, "batch size {batch_size}: {level} bits");
+        }
+    }
+
+    #[test]
+    fn test_batch_security_level() {
+        let options = FriOptions::new(2, 2, 0);
+        // domain_size = 16384, no folding loss, so query bits = (140 - log2(32)) * 1 = 135, and
+        // batching bits = 128 - log2(31 * 16384) ~ 109.05
+        let level = calculate_batch_security_level::<BaseElement>(1024 * 64, &options, 32, 140);
+        assert_eq!(level, Ok(109));
+
+        // too few queries cannot be made up for by the field size
+        let level = calculate_batch_security_level::<BaseElement>(1024 * 64, &options, 32, 40);
+        assert_eq!(level, Ok(35));
+
+        let options = FriOptions::new(1, 4, 7);
+        let result = calculate_batch_security_level::<BaseElement>(100, &options, 2, 32);
+        assert_eq!(result, Err(FridaError::InvalidBlowupFactor));
+    }
+
     #[test]
     fn test_zero_lambda() {
         let options = FriOptions::new(8, 4, 3);
@@ -4,6 +4,10 @@ use crate::{constants, error::FridaError};
 use winter_crypto::{Digest, ElementHasher};
 use winter_math::FieldElement;
 
+/// Number of times the digest of a challenge is rehashed when its bytes do not encode a field
+/// element, before the draw fails.
+const MAX_DRAW_ATTEMPTS: u64 = 1000;
+
 #[derive(Debug)]
 pub struct FridaRandom<E: FieldElement, HashHst: ElementHasher, HashRandom: ElementHasher> {
     counter: u64,
@@ -51,16 +55,12 @@ impl<
 
     pub fn draw(&mut self) -> Result<E, FridaError> {
         let random_value = HashRandom::hash(&self.hst[..E::ELEMENT_BYTES]);
+        let element = element_from_digest::<E, HashRandom>(random_value)?;
 
-        let bytes = &random_value.as_bytes()[..E::ELEMENT_BYTES];
-        if let Some(element) = E::from_random_bytes(bytes) {
-            #[cfg(test)]
-            self.drawn_alphas.push(element);
-
-            return Ok(element);
-        }
+        #[cfg(test)]
+        self.drawn_alphas.push(element);
 
-        Err(FridaError::DrawError())
+        Ok(element)
     }
 
     pub fn draw_query_positions(
@@ -83,7 +83,7 @@ impl<
 
         let mut values = Vec::with_capacity(num_queries);
         let mask = (domain_size - 1) as u64;
-        for i in 0..num_queries {
+        for i in 0..num_queries as u64 {
             let to_be_hashed = [&self.hst[..], &i.to_be_bytes()].concat();
             let random_value = HashRandom::hash(&to_be_hashed);
             let bytes: [u8; 8] = random_value.as_bytes()[..8].try_into().unwrap();
@@ -104,15 +104,10 @@ impl<
 
     pub fn draw_xi(&self, count: usize) -> Result<Vec<E>, FridaError> {
         let mut values = Vec::with_capacity(count);
-        for i in 0..count {
+        for i in 0..count as u64 {
             let to_be_hashed = [&self.hst[..], &i.to_be_bytes()].concat();
             let random_value = HashRandom::hash(&to_be_hashed);
-            let bytes = &random_value.as_bytes()[..E::ELEMENT_BYTES];
-            if let Some(element) = E::from_random_bytes(bytes) {
-                values.push(element);
-            } else {
-                return Err(FridaError::DrawError());
-            }
+            values.push(element_from_digest::<E, HashRandom>(random_value)?);
         }
 
         if values.len() < count {
@@ -136,3 +131,19 @@ impl<
         self.drawn_alphas.clone()
     }
 }
+
+/// Reads a field element from the first `E::ELEMENT_BYTES` bytes of `digest`. Digests whose bytes
+/// are rejected by the field, e.g. values not below a small modulus, are rehashed with the number
+/// of the attempt as `H(digest || uint64(attempt))`, so that challenges are drawn uniformly rather
+/// than reduced.
+fn element_from_digest<E: FieldElement, H: ElementHasher>(
+    mut digest: H::Digest,
+) -> Result<E, FridaError> {
+    for attempt in 1..=MAX_DRAW_ATTEMPTS {
+        if let Some(element) = E::from_random_bytes(&digest.as_bytes()[..E::ELEMENT_BYTES]) {
+            return Ok(element);
+        }
+        digest = H::hash(&[&digest.as_bytes()[..], &attempt.to_be_bytes()].concat());
+    }
+    Err(FridaError::DrawError())
+}
@@ -0,0 +1,190 @@
+use winter_fri::FriOptions;
+
+use crate::error::FridaError;
+
+// FOLDING SCHEDULE
+// ================================================================================================
+
+/// Folding factors supported by the prover and verifier.
+pub const SUPPORTED_FOLDING_FACTORS: [usize; 4] = [2, 4, 8, 16];
+
+/// Checks that every factor of `schedule` is supported, and that a non-empty schedule can fold
+/// domains down to the remainder size of `options`.
+///
+/// # Errors
+/// Returns `FridaError::UnsupportedFoldingFactor` for the first unsupported factor, and
+/// `FridaError::UnreachableRemainder` if `(remainder_max_degree + 1) * blowup_factor` is not a
+/// power of two, which power-of-two factors cannot fold a domain down to exactly.
+pub fn validate_folding_schedule(
+    options: &FriOptions,
+    schedule: &[usize],
+) -> Result<(), FridaError> {
+    if let Some(&factor) = schedule
+        .iter()
+        .find(|f| !SUPPORTED_FOLDING_FACTORS.contains(f))
+    {
+        return Err(FridaError::UnsupportedFoldingFactor(factor));
+    }
+    let max_remainder_size = (options.remainder_max_degree() + 1) * options.blowup_factor();
+    if !schedule.is_empty() && !max_remainder_size.is_power_of_two() {
+        return Err(FridaError::UnreachableRemainder(max_remainder_size));
+    }
+    Ok(())
+}
+
+/// Returns the folding factor of every FRI layer of a commitment over `domain_size`, the batch
+/// layer included.
+///
+/// An empty schedule folds every layer by `options.folding_factor()`, exactly as winter-fri does.
+/// Otherwise layer `i` folds by `schedule[i]`, the last factor being repeated for the remaining
+/// layers, e.g. `[16, 16, 4]` folds by 16 twice and then by 4. A factor which would fold past the
+/// remainder is lowered to the largest power of two which does not, so that the last layer leaves
+/// exactly `(remainder_max_degree + 1) * blowup_factor` evaluations for schedules accepted by
+/// `validate_folding_schedule`; otherwise folding stops once no factor of 2 or more fits.
+pub fn layer_folding_factors(
+    options: &FriOptions,
+    schedule: &[usize],
+    domain_size: usize,
+) -> Vec<usize> {
+    let Some(&last) = schedule.last() else {
+        return vec![options.folding_factor(); options.num_fri_layers(domain_size)];
+    };
+
+    let max_remainder_size = (options.remainder_max_degree() + 1) * options.blowup_factor();
+    let mut factors = Vec::new();
+    let mut domain_size = domain_size;
+    while domain_size > max_remainder_size {
+        let factor = schedule.get(factors.len()).copied().unwrap_or(last);
+        let factor = usize::min(factor, prev_power_of_two(domain_size / max_remainder_size));
+        if factor < 2 {
+            break;
+        }
+        factors.push(factor);
+        domain_size /= factor;
+    }
+    factors
+}
+
+/// Largest power of two not above `n`, or 0 for 0.
+fn prev_power_of_two(n: usize) -> usize {
+    match n {
+        0 => 0,
+        _ => 1 << n.ilog2(),
+    }
+}
+
+// FRI OPTIONS
+// ================================================================================================
+
+/// Same as `FriOptions::new`, returning an error rather than panicking if the parameters are not
+/// valid options, e.g. when they come from user input.
+///
+/// # Errors
+/// Returns `FridaError::InvalidBlowupFactor` if `blowup_factor` is not a power of two greater
+/// than 1, `FridaError::UnsupportedFoldingFactor` if `folding_factor` is not supported, and
+/// `FridaError::InvalidRemainderDegree` if `remainder_max_degree + 1` is not a power of two.
+pub fn fri_options(
+    blowup_factor: usize,
+    folding_factor: usize,
+    remainder_max_degree: usize,
+) -> Result<FriOptions, FridaError> {
+    if blowup_factor < 2 || !blowup_factor.is_power_of_two() {
+        return Err(FridaError::InvalidBlowupFactor);
+    }
+    if !SUPPORTED_FOLDING_FACTORS.contains(&folding_factor) {
+        return Err(FridaError::UnsupportedFoldingFactor(folding_factor));
+    }
+    if !remainder_max_degree
+        .checked_add(1)
+        .is_some_and(usize::is_power_of_two)
+    {
+        return Err(FridaError::InvalidRemainderDegree(remainder_max_degree));
+    }
+    Ok(FriOptions::new(
+        blowup_factor,
+        folding_factor,
+        remainder_max_degree,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_schedule_matches_options() {
+        let options = FriOptions::new(2, 4, 3);
+        for domain_size in [32, 256, 1 << 12] {
+            assert_eq!(
+                layer_folding_factors(&options, &[], domain_size),
+                vec![4; options.num_fri_layers(domain_size)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_schedule_repeats_and_clamps() {
+        let options = FriOptions::new(2, 4, 3);
+        // 2^14 down to 8 = (3 + 1) * 2 evaluations
+        assert_eq!(
+            layer_folding_factors(&options, &[16, 16, 4], 1 << 14),
+            vec![16, 16, 4, 2]
+        );
+        assert_eq!(layer_folding_factors(&options, &[8], 1 << 9), vec![8, 8]);
+        assert!(layer_folding_factors(&options, &[8], 8).is_empty());
+    }
+
+    #[test]
+    fn test_clamped_factors_are_powers_of_two() {
+        let options = FriOptions::new(2, 4, 3);
+        // 2^14 / 16 / 16 = 64 evaluations, folded by 8 rather than 16 down to 8
+        assert_eq!(
+            layer_folding_factors(&options, &[16], 1 << 14),
+            vec![16, 16, 8]
+        );
+        // 12 evaluations are left once no factor of 2 or more fits above the 8 of the remainder,
+        // rather than folding by 12 / 8 = 1 forever
+        assert_eq!(
+            layer_folding_factors(&options, &[16], 3 << 10),
+            vec![16, 16]
+        );
+    }
+
+    #[test]
+    fn test_validate_folding_schedule() {
+        let options = FriOptions::new(2, 4, 3);
+        assert!(validate_folding_schedule(&options, &[16, 4, 2]).is_ok());
+        assert_eq!(
+            validate_folding_schedule(&options, &[16, 3]),
+            Err(FridaError::UnsupportedFoldingFactor(3))
+        );
+    }
+    #[test]
+    fn test_fri_options() {
+        let options = fri_options(8, 4, 7).unwrap();
+        assert_eq!(options.blowup_factor(), 8);
+        assert_eq!(options.folding_factor(), 4);
+        assert_eq!(options.remainder_max_degree(), 7);
+
+        assert_eq!(
+            fri_options(3, 4, 7).err(),
+            Some(FridaError::InvalidBlowupFactor)
+        );
+        assert_eq!(
+            fri_options(1, 4, 7).err(),
+            Some(FridaError::InvalidBlowupFactor)
+        );
+        assert_eq!(
+            fri_options(8, 3, 7).err(),
+            Some(FridaError::UnsupportedFoldingFactor(3))
+        );
+        assert_eq!(
+            fri_options(8, 4, 6).err(),
+            Some(FridaError::InvalidRemainderDegree(6))
+        );
+        assert_eq!(
+            fri_options(8, 4, usize::MAX).err(),
+            Some(FridaError::InvalidRemainderDegree(usize::MAX))
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::{polynom, FieldElement, StarkField};
+
+use crate::{constants, core::random::FridaRandom, error::FridaError};
+
+// STIR ROUNDS
+// ================================================================================================
+
+/// Shape of a single round of the experimental STIR protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StirRound {
+    /// Size of the domain the tested polynomial is evaluated over.
+    pub domain_size: usize,
+    /// Degree bound of the tested polynomial.
+    pub degree: usize,
+    /// Number of positions of the folded domain queried in this round.
+    pub num_queries: usize,
+}
+
+/// Returns the rounds of a STIR proof for a blob encoded over `domain_size` elements.
+///
+/// Every round folds the tested polynomial by `options.folding_factor()` but only halves its
+/// domain, so the rate improves by a factor of `folding_factor / 2` per round and each round
+/// draws just enough queries to match the security of `num_queries` queries at the initial rate.
+/// Rounds stop once the folded degree reaches `remainder_max_degree + 1`, or once it could no
+/// longer absorb the out-of-domain point and the queries of a round.
+///
+/// # Errors
+/// Returns `FridaError::UnsupportedFoldingFactor` unless the folding factor is 4, 8 or 16, as
+/// folding by 2 does not improve the rate, `FridaError::InvalidBlowupFactor` for a blowup factor
+/// of 1, and `FridaError::NotEnoughDataPoints` if the domain is too small to be folded once.
+pub fn stir_rounds(
+    options: &FriOptions,
+    domain_size: usize,
+    num_queries: usize,
+) -> Result<Vec<StirRound>, FridaError> {
+    let folding_factor = options.folding_factor();
+    if !matches!(folding_factor, 4 | 8 | 16) {
+        return Err(FridaError::UnsupportedFoldingFactor(folding_factor));
+    }
+    if options.blowup_factor() <= 1 {
+        return Err(FridaError::InvalidBlowupFactor);
+    }
+
+    let mut domain_size = domain_size;
+    let mut degree = domain_size / options.blowup_factor();
+    if degree < folding_factor || domain_size / folding_factor < constants::MIN_DOMAIN_SIZE {
+        return Err(FridaError::NotEnoughDataPoints());
+    }
+
+    let security_bits = num_queries * options.blowup_factor().ilog2() as usize;
+    let mut rounds = Vec::new();
+    loop {
+        let rate_bits = (domain_size / degree).ilog2() as usize;
+        let num_queries = security_bits
+            .div_ceil(rate_bits)
+            .clamp(1, domain_size / folding_factor - 1);
+        rounds.push(StirRound {
+            domain_size,
+            degree,
+            num_queries,
+        });
+
+        // the next polynomial is divided by the out-of-domain point and every queried point
+        let next_degree = degree / folding_factor;
+        if next_degree <= options.remainder_max_degree() + 1
+            || next_degree < num_queries + 2
+            || domain_size / 2 / folding_factor < constants::MIN_DOMAIN_SIZE
+        {
+            return Ok(rounds);
+        }
+        domain_size /= 2;
+        degree = next_degree;
+    }
+}
+
+/// Draws the distinct positions of the folded domain queried in `round`, in ascending order.
+pub fn draw_round_indexes<E, HHst, HRandom>(
+    public_coin: &FridaRandom<E, HHst, HRandom>,
+    round: &StirRound,
+    folding_factor: usize,
+) -> Result<Vec<usize>, FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    let mut indexes =
+        public_coin.draw_query_positions(round.num_queries, round.domain_size / folding_factor)?;
+    indexes.sort_unstable();
+    indexes.dedup();
+    Ok(indexes)
+}
+
+/// Returns the point at `index` of the domain of `domain_size` elements shifted by
+/// `domain_offset`, once folded by `folding_factor`.
+pub fn folded_domain_point<B: StarkField>(
+    domain_size: usize,
+    folding_factor: usize,
+    domain_offset: B,
+    index: usize,
+) -> B {
+    let generator = B::get_root_of_unity((domain_size / folding_factor).ilog2());
+    domain_offset.exp_vartime((folding_factor as u64).into())
+        * generator.exp_vartime((index as u64).into())
+}
+
+/// Folds the coefficients of `f(x) = sum_j x^j * f_j(x^k)`, with `k = folding_factor`, into those
+/// of `sum_j alpha^j * f_j(x)`.
+pub fn fold_coefficients<E: FieldElement>(
+    coefficients: &[E],
+    folding_factor: usize,
+    alpha: E,
+) -> Vec<E> {
+    coefficients
+        .chunks(folding_factor)
+        .map(|chunk| polynom::eval(chunk, alpha))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winter_math::fields::f128::BaseElement;
+
+    #[test]
+    fn test_stir_rounds_reduce_queries() {
+        let options = FriOptions::new(2, 8, 7);
+        let rounds = stir_rounds(&options, 1 << 16, 32).unwrap();
+
+        assert!(rounds.len() > 1);
+        assert_eq!(rounds[0].num_queries, 32);
+        for pair in rounds.windows(2) {
+            assert_eq!(pair[1].domain_size, pair[0].domain_size / 2);
+            assert_eq!(pair[1].degree, pair[0].degree / 8);
+            assert!(pair[1].num_queries < pair[0].num_queries);
+        }
+    }
+
+    #[test]
+    fn test_stir_rounds_reject_unsupported_options() {
+        assert_eq!(
+            stir_rounds(&FriOptions::new(2, 2, 1), 1 << 10, 8),
+            Err(FridaError::UnsupportedFoldingFactor(2))
+        );
+        assert_eq!(
+            stir_rounds(&FriOptions::new(2, 16, 15), 32, 8),
+            Err(FridaError::NotEnoughDataPoints())
+        );
+    }
+
+    #[test]
+    fn test_fold_coefficients() {
+        let coefficients = (1..=8u64).map(BaseElement::from).collect::<Vec<_>>();
+        let alpha = BaseElement::from(3u64);
+        let folded = fold_coefficients(&coefficients, 4, alpha);
+
+        // f_j(y) are the coefficients at indexes j, j + 4
+        let x = BaseElement::from(5u64);
+        let expected = (0..4).fold(BaseElement::ZERO, |acc, j| {
+            acc + alpha.exp(j as u128) * (coefficients[j] + coefficients[j + 4] * x)
+        });
+        assert_eq!(polynom::eval(&folded, x), expected);
+    }
+}
@@ -0,0 +1,172 @@
+use winter_crypto::{Digest, ElementHasher};
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use crate::{
+    constants,
+    core::{deep, random::FridaRandom},
+    error::FridaError,
+};
+
+// TRANSCRIPT
+// ================================================================================================
+
+/// Step of the Fiat-Shamir transcript of a prover channel: either a commitment absorbed into the
+/// public coin, or a challenge squeezed from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEntry<E: FieldElement, D: Digest> {
+    /// Root of a layer, or hash of the remainder, absorbed into the public coin.
+    Commitment(D),
+    /// Folding challenge of a layer.
+    Alpha(E),
+    /// Batching coefficients of the polynomials of a batch.
+    Xi(Vec<E>),
+    /// Out-of-domain sample of the first layer; `value` is absorbed between drawing `point` and
+    /// `comb`.
+    OodSample { point: E, value: E, comb: E },
+    /// Query positions drawn over a domain of `domain_size` elements, before deduplication.
+    Positions {
+        domain_size: usize,
+        positions: Vec<usize>,
+    },
+}
+
+/// Ordered record of everything a prover channel absorbed and squeezed, which lets an auditor
+/// re-derive the randomness of a commitment independently of the prover.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript<E: FieldElement, D: Digest> {
+    pub entries: Vec<TranscriptEntry<E, D>>,
+}
+
+impl<E: FieldElement, D: Digest> Default for Transcript<E, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: FieldElement, D: Digest> Transcript<E, D> {
+    pub fn new() -> Self {
+        Transcript {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, entry: TranscriptEntry<E, D>) {
+        self.entries.push(entry);
+    }
+
+    /// Replays the transcript against a fresh public coin, re-deriving every challenge from the
+    /// commitments absorbed before it.
+    ///
+    /// # Errors
+    /// Returns `FridaError::TranscriptMismatch` with the index of the first entry whose
+    /// challenges differ from the re-derived ones.
+    pub fn verify<HHst, HRandom>(&self) -> Result<(), FridaError>
+    where
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        HRandom: ElementHasher<BaseField = E::BaseField, Digest = D>,
+    {
+        let mut public_coin = FridaRandom::<E, HHst, HRandom>::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let matches = match entry {
+                TranscriptEntry::Commitment(root) => {
+                    public_coin.reseed(&root.as_bytes());
+                    true
+                }
+                TranscriptEntry::Alpha(alpha) => public_coin.draw()? == *alpha,
+                TranscriptEntry::Xi(xi) => public_coin.draw_xi(xi.len())? == *xi,
+                TranscriptEntry::OodSample { point, value, comb } => {
+                    let sample = deep::draw_ood_sample(&mut public_coin, |_| *value)?;
+                    sample.point == *point && sample.comb == *comb
+                }
+                TranscriptEntry::Positions {
+                    domain_size,
+                    positions,
+                } => {
+                    // a domain the public coin would refuse to draw from cannot match
+                    *domain_size >= constants::MIN_DOMAIN_SIZE
+                        && domain_size.is_power_of_two()
+                        && positions.len() < *domain_size
+                        && public_coin.draw_query_positions(positions.len(), *domain_size)?
+                            == *positions
+                }
+            };
+            if !matches {
+                return Err(FridaError::TranscriptMismatch(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement, D: Digest> Serializable for TranscriptEntry<E, D> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            TranscriptEntry::Commitment(root) => {
+                target.write_u8(0);
+                root.write_into(target);
+            }
+            TranscriptEntry::Alpha(alpha) => {
+                target.write_u8(1);
+                alpha.write_into(target);
+            }
+            TranscriptEntry::Xi(xi) => {
+                target.write_u8(2);
+                xi.write_into(target);
+            }
+            TranscriptEntry::OodSample { point, value, comb } => {
+                target.write_u8(3);
+                point.write_into(target);
+                value.write_into(target);
+                comb.write_into(target);
+            }
+            TranscriptEntry::Positions {
+                domain_size,
+                positions,
+            } => {
+                target.write_u8(4);
+                domain_size.write_into(target);
+                positions.write_into(target);
+            }
+        }
+    }
+}
+
+impl<E: FieldElement, D: Digest> Deserializable for TranscriptEntry<E, D> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TranscriptEntry::Commitment(D::read_from(source)?)),
+            1 => Ok(TranscriptEntry::Alpha(E::read_from(source)?)),
+            2 => Ok(TranscriptEntry::Xi(Vec::<E>::read_from(source)?)),
+            3 => Ok(TranscriptEntry::OodSample {
+                point: E::read_from(source)?,
+                value: E::read_from(source)?,
+                comb: E::read_from(source)?,
+            }),
+            4 => Ok(TranscriptEntry::Positions {
+                domain_size: usize::read_from(source)?,
+                positions: Vec::<usize>::read_from(source)?,
+            }),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "unknown transcript entry tag {tag}"
+            ))),
+        }
+    }
+}
+
+impl<E: FieldElement, D: Digest> Serializable for Transcript<E, D> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.entries.write_into(target);
+    }
+}
+
+impl<E: FieldElement, D: Digest> Deserializable for Transcript<E, D> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Transcript {
+            entries: Vec::read_from(source)?,
+        })
+    }
+}
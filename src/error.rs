@@ -1,4 +1,5 @@
 use std::fmt;
+use winter_fri::VerifierError;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FridaError {
@@ -13,7 +14,8 @@ pub enum FridaError {
     DomainSizeTooBig(usize),
     BadNumQueries(usize),
     InvalidDASCommitment,
-    FailToVerify,
+    /// The proof was rejected by the verifier for the given reason.
+    FailToVerify(VerifierError),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
     UnsupportedFoldingFactor(usize),
@@ -21,6 +23,175 @@ pub enum FridaError {
     ProofPolyCountMismatch,
     /// Occurs when the blowup factor is less than or equal to 1.
     InvalidBlowupFactor,
+    /// Security level of a commitment (in bits) is below the configured minimum.
+    InsufficientSecurity(u32, u32),
+    /// A sample receipt was opened against a different commitment.
+    ReceiptBindingMismatch,
+    /// An opening request was refused because the open queue is full.
+    QueueFull,
+    /// A queued opening request was evicted, or its queue was shut down, before it was served.
+    OpeningCancelled,
+    /// A shared position does not lie within the common domain of two commitments.
+    PositionOutOfDomain(usize, usize),
+    /// A byte range extends past the end of the committed data.
+    ByteRangeOutOfBounds(usize, usize),
+    /// The bytes decoded from a byte range opening differ from the claimed ones.
+    ByteRangeMismatch,
+    /// The length prefix of the decoded data exceeds the number of bytes the domain can hold.
+    DataLengthOutOfBounds(u64, usize),
+    /// The data decoded for the given blob does not hash to the checksum in its commitment.
+    DataChecksumMismatch(usize),
+    /// A cell lies outside of the rows or columns of its commitment.
+    CellOutOfBounds(usize, usize),
+    /// The commitment does not bind its blobs to application ids.
+    AppIdsNotCommitted,
+    /// No blob of the commitment was bound to the requested application id.
+    UnknownAppId,
+    /// The same application id was given for several blobs of a batch.
+    DuplicateAppId,
+    /// An app opening does not prove that its blob belongs to the requested application.
+    AppIdMismatch,
+    /// Two commitments differ in domain size, folding factor or polynomial count.
+    IncompatibleCommitments,
+    /// A deduplication manifest references chunks missing from its store.
+    InvalidDedupManifest,
+    /// The builder is configured for a different protocol than the requested commitment.
+    ProtocolMismatch,
+    /// A party of an interactive run received a message which does not fit its current round.
+    UnexpectedMessage,
+    /// The challenges of the given transcript entry differ from those re-derived from the
+    /// commitments absorbed before it.
+    TranscriptMismatch(usize),
+    /// Commitments to the same data in two fields decode to different bytes.
+    FieldDataMismatch,
+    /// A position declared as missing is not among the positions of the proof.
+    UnknownErasure(usize),
+    /// A supersession record is not validly signed, or replaces a commitment by itself.
+    InvalidSupersession,
+    /// A supersession record replaces a commitment which is not the canonical one of its block,
+    /// or does not carry the next epoch of the block.
+    StaleSupersession,
+    /// An availability attestation is bound to another commitment, covers other positions than
+    /// those assigned to its validator, or is not validly signed.
+    InvalidAttestation,
+    /// The attestation threshold is zero or exceeds the number of validators, or the validators
+    /// are not given one key and one assignment each.
+    BadAttestationThreshold(usize, usize),
+    /// No validator of a weighted assignment has a positive weight.
+    BadValidatorWeights,
+    /// Positions cannot be held by the given number of distinct validators.
+    BadReplicationFactor(usize),
+    /// A commitment is appended for a block which is not higher than the last appended one.
+    NonIncreasingBlock(u64),
+    /// No commitment was appended for the given block.
+    UnknownBlock(u64),
+    /// An inclusion proof does not lead to the root of the accumulator.
+    InvalidInclusionProof,
+    /// The commitment is not batched with `BatchLeafEncoding::PerBlob`, so single blobs cannot be
+    /// opened.
+    BlobOpeningUnsupported,
+    /// A blob opening does not lead to the root of the first layer.
+    InvalidBlobOpening,
+    /// An oblivious query asks for more positions, sampled and decoys together, than its domain
+    /// holds.
+    ObliviousQueryTooLarge(usize, usize),
+    /// The receipts answering an oblivious query do not open its positions in order.
+    ObliviousResponseMismatch,
+    /// No positions were given to open.
+    NoPositions,
+    /// The position of a layer was pruned from the prover, so it cannot be opened anymore.
+    PositionPruned(usize),
+    /// The Merkle tree of a layer could not be built or opened.
+    MerkleError(String),
+    /// The named field of a serialized structure, at the given byte offset, does not conform to
+    /// its `spec::WireSpec`.
+    NonConformingEncoding(String, usize),
+    /// A proof opens fewer distinct positions than the hard threshold of its verification.
+    TooFewQueries(usize, usize),
+    /// A root digest does not match the roots and parameters of the commitment it identifies.
+    RootDigestMismatch,
+    /// Openings of two commitments claimed for the same blob agree at every shared position.
+    NoEquivocation,
+    /// No field is known under the given id.
+    UnsupportedFieldId(u8),
+    /// No hasher is known under the given id.
+    UnsupportedHasherId(u8),
+    /// More partitions were requested than the smallest layer of a commitment has rows.
+    TooManyPartitions(usize, usize),
+    /// The builder is set up with an option that partition workers cannot apply on their own.
+    DistributedProvingUnsupported,
+    /// A message was expected from every partition, but the given number of messages was received.
+    PartitionCountMismatch(usize, usize),
+    /// A message received from the given partition does not match what it was asked for.
+    InvalidPartitionShare(usize),
+    /// A row was requested from a partition which does not hold it.
+    RowOutsidePartition(usize, usize),
+    /// A commitment renewed for an epoch does not commit to the same data as the one it renews.
+    EpochDiscontinuity,
+    /// A response to a challenge with the given deadline was received at the given time, after
+    /// the deadline.
+    ChallengeExpired(u64, u64),
+    /// A batched proof cannot be converted into a winter-fri proof without dropping its batch
+    /// layer.
+    BatchLayerNotConvertible,
+    /// The data element at the given index is not one of the given number of elements holding the
+    /// data.
+    ValueOutOfBounds(usize, usize),
+    /// Challenges drawn from the field cannot reach the given target security level, in bits, but
+    /// only the given one, whatever the number of queries.
+    FieldTooSmall(u32, u32),
+    /// The given number of values cannot be split into evaluations of the given number of
+    /// batched polynomials.
+    BatchValuesMismatch(usize, usize),
+    /// The commitments of a multiproof do not share their domain and folding factors, or do not
+    /// match the openings of the proof.
+    MultiProofMismatch,
+    /// An encoding made with one blowup factor (first) is committed to by a builder using another
+    /// (second).
+    EncodingMismatch(usize, usize),
+    /// Two erasure-coded shares hold the same position of the domain.
+    DuplicateShare(usize),
+    /// The remainder of a commitment is evaluated before any proof carrying it was checked.
+    RemainderNotVerified,
+    /// The commitment was built without data roots.
+    DataRootsNotCommitted,
+    /// Chunks up to the first value are opened, but the blob only has the second value.
+    DataChunksOutOfBounds(usize, usize),
+    /// The opened chunks do not match the data root of the blob.
+    InvalidDataInclusion,
+    /// The operation was aborted through its cancellation token.
+    Cancelled,
+    /// A folding schedule cannot fold the domain down to the remainder size given by the options,
+    /// which is not a power of two.
+    UnreachableRemainder(usize),
+    /// A commitment does not carry the out-of-domain evaluation its verifier requires.
+    OodEvaluationMissing,
+    /// A proof was made for the given number of partitions, but its commitment for another one.
+    PartitionMismatch(usize, usize),
+    /// The commitment was made with other FRI parameters than the verifier's.
+    ProfileMismatch,
+    /// A hard query threshold is 0 or exceeds the number of queries of the commitment.
+    InvalidQueryThreshold(usize, usize),
+    /// An integer of a recursive statement does not fit in 32 bits.
+    RecursiveInputOutOfRange(usize),
+    /// The commitment carried by a share is not the one published for its blob.
+    ShareCommitmentMismatch,
+    /// The commitment was built without data checksums.
+    DataChecksumsNotCommitted,
+    /// The remainder degree plus one is not a power of two.
+    InvalidRemainderDegree(usize),
+    /// The first layer of a prover was pruned, or the prover was committed without any FRI layer.
+    FirstLayerUnavailable,
+    /// A wire specification was requested for an empty domain, or for a batch of polynomials
+    /// committed without any FRI layer to batch them in.
+    InvalidWireSpec(usize, usize),
+    /// The domain size is not a power of two, or the domain is too small to hold the remainder of
+    /// its FRI options.
+    InvalidDomainSize(usize),
+    /// A lazy layer regenerated from the previous one does not match its committed root.
+    RegeneratedLayerMismatch(usize),
+    /// A blob index is not one of the blobs of a commitment, given with its number of blobs.
+    BlobOutOfRange(usize, usize),
 }
 
 impl fmt::Display for FridaError {
@@ -47,7 +218,7 @@ impl fmt::Display for FridaError {
             FridaError::DomainSizeTooBig(size) => write!(f, "Domain size too big: {size}"),
             FridaError::BadNumQueries(num) => write!(f, "Bad number of queries: {num}"),
             FridaError::InvalidDASCommitment => write!(f, "Invalid DAS commitment"),
-            FridaError::FailToVerify => write!(f, "Failed to verify"),
+            FridaError::FailToVerify(e) => write!(f, "Failed to verify: {e}"),
             FridaError::DegreeTruncation(layer, degree, factor) => write!(
                 f,
                 "Degree truncation error at layer {layer}: degree {degree}, factor {factor}"
@@ -65,6 +236,260 @@ impl fmt::Display for FridaError {
                     "Blowup factor must be greater than 1 for query calculation."
                 )
             }
+            FridaError::InsufficientSecurity(level, required) => write!(
+                f,
+                "Security level of {level} bits is below the required {required} bits"
+            ),
+            FridaError::ReceiptBindingMismatch => {
+                write!(f, "Sample receipt is bound to a different commitment")
+            }
+            FridaError::QueueFull => write!(f, "Open queue is full"),
+            FridaError::OpeningCancelled => write!(f, "Opening was cancelled before being served"),
+            FridaError::PositionOutOfDomain(position, domain_size) => write!(
+                f,
+                "Position {position} is outside of the shared domain of size {domain_size}"
+            ),
+            FridaError::ByteRangeOutOfBounds(start, end) => {
+                write!(
+                    f,
+                    "Byte range {start}..{end} is out of the bounds of the data"
+                )
+            }
+            FridaError::ByteRangeMismatch => {
+                write!(f, "Opened bytes do not match the claimed byte range")
+            }
+            FridaError::DataLengthOutOfBounds(len, capacity) => write!(
+                f,
+                "Data length {len} exceeds the capacity of {capacity} bytes"
+            ),
+            FridaError::DataChecksumMismatch(blob) => {
+                write!(f, "Data of blob {blob} does not match its checksum")
+            }
+            FridaError::CellOutOfBounds(row, col) => {
+                write!(f, "Cell ({row}, {col}) is outside of the committed grid")
+            }
+            FridaError::AppIdsNotCommitted => {
+                write!(f, "Commitment does not bind blobs to application ids")
+            }
+            FridaError::UnknownAppId => write!(f, "No blob was committed for the application id"),
+            FridaError::DuplicateAppId => write!(f, "Application id is bound to several blobs"),
+            FridaError::AppIdMismatch => {
+                write!(
+                    f,
+                    "Opened blob does not belong to the requested application"
+                )
+            }
+            FridaError::IncompatibleCommitments => write!(
+                f,
+                "Commitments differ in domain size, folding factor or polynomial count"
+            ),
+            FridaError::InvalidDedupManifest => {
+                write!(f, "Deduplication manifest does not match its chunk store")
+            }
+            FridaError::ProtocolMismatch => {
+                write!(f, "Builder is configured for a different protocol")
+            }
+            FridaError::UnexpectedMessage => {
+                write!(f, "Unexpected message in interactive protocol run")
+            }
+            FridaError::TranscriptMismatch(index) => {
+                write!(
+                    f,
+                    "Transcript entry {index} does not match the re-derived challenges"
+                )
+            }
+            FridaError::FieldDataMismatch => {
+                write!(f, "Commitments in both fields decode to different data")
+            }
+            FridaError::UnknownErasure(position) => {
+                write!(f, "Missing position {position} is not opened by the proof")
+            }
+            FridaError::InvalidSupersession => write!(f, "Invalid supersession record"),
+            FridaError::StaleSupersession => {
+                write!(
+                    f,
+                    "Supersession record replaces a non-canonical commitment or is out of epoch"
+                )
+            }
+            FridaError::InvalidAttestation => write!(f, "Invalid availability attestation"),
+            FridaError::BadAttestationThreshold(threshold, validators) => write!(
+                f,
+                "Bad attestation threshold {threshold} for {validators} validators"
+            ),
+            FridaError::BadValidatorWeights => write!(f, "No validator has a positive weight"),
+            FridaError::BadReplicationFactor(replication) => {
+                write!(f, "Bad replication factor: {replication}")
+            }
+            FridaError::NonIncreasingBlock(block) => {
+                write!(
+                    f,
+                    "Block {block} is not higher than the last appended block"
+                )
+            }
+            FridaError::UnknownBlock(block) => write!(f, "No commitment for block {block}"),
+            FridaError::InvalidInclusionProof => write!(f, "Invalid inclusion proof"),
+            FridaError::BlobOpeningUnsupported => {
+                write!(f, "The commitment does not support opening single blobs")
+            }
+            FridaError::InvalidBlobOpening => write!(f, "Invalid blob opening"),
+            FridaError::ObliviousQueryTooLarge(positions, domain_size) => write!(
+                f,
+                "Cannot request {positions} positions from a domain of size {domain_size}"
+            ),
+            FridaError::ObliviousResponseMismatch => {
+                write!(
+                    f,
+                    "Receipts do not answer the positions of the oblivious query"
+                )
+            }
+            FridaError::NoPositions => write!(f, "No positions to open"),
+            FridaError::PositionPruned(position) => {
+                write!(f, "Position {position} was pruned from the prover")
+            }
+            FridaError::MerkleError(e) => write!(f, "Merkle tree error: {e}"),
+            FridaError::NonConformingEncoding(field, offset) => write!(
+                f,
+                "Field {field} at byte {offset} does not conform to the specification"
+            ),
+            FridaError::TooFewQueries(positions, hard) => write!(
+                f,
+                "Proof opens {positions} distinct positions, fewer than the required {hard}"
+            ),
+            FridaError::RootDigestMismatch => {
+                write!(f, "Root digest does not match the commitment")
+            }
+            FridaError::NoEquivocation => {
+                write!(f, "Openings agree at every shared position")
+            }
+            FridaError::UnsupportedFieldId(id) => write!(f, "Unsupported field id {id}"),
+            FridaError::UnsupportedHasherId(id) => write!(f, "Unsupported hasher id {id}"),
+            FridaError::TooManyPartitions(partitions, rows) => write!(
+                f,
+                "Cannot split layers into {partitions} partitions, the smallest one has {rows} rows"
+            ),
+            FridaError::DistributedProvingUnsupported => {
+                write!(f, "Commitments of this builder cannot be distributed")
+            }
+            FridaError::PartitionCountMismatch(expected, actual) => write!(
+                f,
+                "Expected messages from {expected} partitions, but received {actual}"
+            ),
+            FridaError::InvalidPartitionShare(partition) => {
+                write!(f, "Invalid message from partition {partition}")
+            }
+            FridaError::RowOutsidePartition(row, partition) => {
+                write!(f, "Row {row} is not held by partition {partition}")
+            }
+            FridaError::EpochDiscontinuity => {
+                write!(f, "Epoch commitment does not renew the previous commitment")
+            }
+            FridaError::ChallengeExpired(deadline, now) => write!(
+                f,
+                "Challenge response received at {now}, after the deadline of {deadline}"
+            ),
+            FridaError::BatchLayerNotConvertible => {
+                write!(f, "Batched proofs have no winter-fri equivalent")
+            }
+            FridaError::ValueOutOfBounds(index, count) => write!(
+                f,
+                "Data element {index} is out of the {count} elements holding the data"
+            ),
+            FridaError::FieldTooSmall(target, level) => write!(
+                f,
+                "Field only allows {level} bits of security, below the target of {target}"
+            ),
+            FridaError::BatchValuesMismatch(values, poly_count) => write!(
+                f,
+                "{values} values are not evaluations of {poly_count} batched polynomials"
+            ),
+            FridaError::MultiProofMismatch => {
+                write!(f, "Multiproof does not match the commitments it proves")
+            }
+            FridaError::EncodingMismatch(encoded, expected) => {
+                write!(
+                    f,
+                    "Encoding has blowup factor {encoded}, but the builder uses {expected}"
+                )
+            }
+            FridaError::DuplicateShare(index) => {
+                write!(f, "Position {index} is shared more than once")
+            }
+            FridaError::RemainderNotVerified => {
+                write!(f, "No proof carrying the remainder has been checked yet")
+            }
+            FridaError::DataRootsNotCommitted => {
+                write!(f, "Commitment does not carry data roots")
+            }
+            FridaError::DataChunksOutOfBounds(end, count) => {
+                write!(f, "Chunks up to {end} are opened, but the blob has {count}")
+            }
+            FridaError::InvalidDataInclusion => {
+                write!(f, "Opened chunks do not match the data root of the blob")
+            }
+            FridaError::Cancelled => {
+                write!(f, "Operation was cancelled")
+            }
+            FridaError::UnreachableRemainder(size) => {
+                write!(
+                    f,
+                    "Folding schedule cannot reach a remainder of {size} evaluations"
+                )
+            }
+            FridaError::OodEvaluationMissing => {
+                write!(
+                    f,
+                    "Commitment does not carry the required out-of-domain evaluation"
+                )
+            }
+            FridaError::PartitionMismatch(expected, actual) => write!(
+                f,
+                "Proof is split into {actual} partitions, but the commitment into {expected}"
+            ),
+            FridaError::ProfileMismatch => write!(
+                f,
+                "Commitment was made with other FRI parameters than the verifier's"
+            ),
+            FridaError::InvalidQueryThreshold(hard, num_queries) => write!(
+                f,
+                "Hard threshold of {hard} queries is not within 1..={num_queries}"
+            ),
+            FridaError::RecursiveInputOutOfRange(value) => write!(
+                f,
+                "Recursive statement cannot hold {value}, which does not fit in 32 bits"
+            ),
+            FridaError::ShareCommitmentMismatch => {
+                write!(
+                    f,
+                    "Share commitment does not match the published commitment"
+                )
+            }
+            FridaError::DataChecksumsNotCommitted => {
+                write!(f, "Commitment does not carry data checksums")
+            }
+            FridaError::InvalidRemainderDegree(degree) => {
+                write!(
+                    f,
+                    "Remainder degree must be one less than a power of two, but was {degree}"
+                )
+            }
+            FridaError::FirstLayerUnavailable => {
+                write!(f, "First layer evaluations were pruned or never committed")
+            }
+            FridaError::InvalidWireSpec(domain_size, poly_count) => write!(
+                f,
+                "No wire specification for a domain of {domain_size} with {poly_count} polynomial(s)"
+            ),
+            FridaError::InvalidDomainSize(domain_size) => write!(
+                f,
+                "Domain of size {domain_size} cannot be folded with the given FRI options"
+            ),
+            FridaError::RegeneratedLayerMismatch(layer) => {
+                write!(f, "Regenerated layer {layer} does not match its committed root")
+            }
+            FridaError::BlobOutOfRange(blob, poly_count) => write!(
+                f,
+                "Blob {blob} is out of range for a commitment to {poly_count} blobs"
+            ),
         }
     }
 }
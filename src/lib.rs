@@ -9,14 +9,43 @@
 //! - **Prover (`prover`):** Contains the `FridaProverBuilder` to construct FRI proofs over data.
 //! - **Verifier (`verifier`):** Contains the `FridaDasVerifier` to verify FRI proofs.
 //! - **Data Handling (`core::data`):** Includes functions for Reed-Solomon encoding data into polynomials.
+//! - **Keccak transcript (`keccak` feature):** Derives challenges with Keccak256, as a Solidity verifier computes them.
 //! - **Queries (`core::queries`):** Provides functionality to calculate the number of queries needed for a target security level.
+//! - **Wire specification (`spec`):** Describes the byte layout of serialized commitments and proofs, and checks encodings against it.
+//! - **Store (`store`):** Keeps commitments, prover snapshots and cached openings on disk, keyed by commitment.
+//! - **DA oracles (`oracle`):** Pluggable transports publishing commitments and serving shares to samplers, in memory or on disk.
+//! - **Python bindings (`python` feature):** Exposes commitment, opening, verification and data encoding to Python.
+//! - **Dispersal (`network` feature):** Reference protocol sending validators their shares over libp2p, answered with signed attestations.
+//! - **C API (`capi` feature):** Exports the verifier with a stable C ABI, declared in `include/frida.h`.
+#![cfg_attr(
+    all(
+        feature = "forbid-unsafe",
+        not(any(
+            feature = "bench",
+            feature = "capi",
+            feature = "f31",
+            feature = "mmap",
+            feature = "python"
+        ))
+    ),
+    forbid(unsafe_code)
+)]
 
+#[cfg(feature = "capi")]
+pub mod capi;
 #[cfg(any(test, feature = "cli"))]
 pub mod commands;
 pub mod constants;
 pub mod core;
 pub mod error;
+#[cfg(feature = "network")]
+pub mod network;
+pub mod oracle;
 pub mod prover;
+#[cfg(feature = "python")]
+mod python;
+pub mod spec;
+pub mod store;
 pub mod utils;
 pub mod verifier;
 pub mod winterfell;
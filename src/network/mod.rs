@@ -0,0 +1,314 @@
+//! Reference protocol dispersing a committed blob to validators.
+//!
+//! The producer publishes the commitment of a blob, then sends every validator a `Share`: the
+//! commitment along with the evaluations at the positions assigned to that validator and the
+//! proof opening them. A validator checks that the share carries the published commitment,
+//! verifies the share against it and answers with an `Attestation`, signed with its own key,
+//! stating that it holds the assigned evaluations. The producer checks attestations with
+//! `Producer::check_attestation`.
+//!
+//! The protocol itself is transport agnostic; `transport` carries it over libp2p
+//! request/response streams (see `examples/dispersal.rs`). Signatures are made and checked by the
+//! application through `RecordSigner` and `RecordVerifier`.
+
+use winter_crypto::{ElementHasher, Hasher};
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use crate::{
+    core::positions::{read_positions, write_positions},
+    error::FridaError,
+    prover::{
+        proof::FridaProof,
+        supersede::{RecordSigner, RecordVerifier},
+        FridaProver, ProverCommitment,
+    },
+    verifier::{attestation::Attestation, das::FridaDasVerifier},
+};
+
+pub mod transport;
+
+// MESSAGES
+// ================================================================================================
+
+/// Share of a committed blob sent to one validator.
+///
+/// Positions are serialized with `core::positions::write_positions`, so that contiguous and
+/// strided assignments take a few bytes whatever their length.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Share<E: FieldElement, H: Hasher> {
+    pub commitment: ProverCommitment<H>,
+    pub positions: Vec<usize>,
+    /// Evaluations at `positions`, as returned by `FridaProver::get_evaluations`.
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+}
+
+// PRODUCER
+// ================================================================================================
+
+/// Producer of a committed blob, which builds the share of every validator and checks their
+/// attestations.
+pub struct Producer<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    prover: FridaProver<E, H>,
+}
+
+impl<E, H> Producer<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Creates a producer of the blob committed to by `prover`.
+    pub fn new(prover: FridaProver<E, H>) -> Self {
+        Producer { prover }
+    }
+
+    /// Commitment to publish before the shares are sent.
+    pub fn commitment(&self) -> ProverCommitment<H> {
+        self.prover.commitment()
+    }
+
+    /// Builds the share of every validator from its assigned positions, or `None` for validators
+    /// without positions.
+    ///
+    /// # Errors
+    /// Returns the errors of `FridaProver::open` if the positions of a validator cannot be opened.
+    pub fn shares(
+        &self,
+        assignments: &[Vec<usize>],
+    ) -> Result<Vec<Option<Share<E, H>>>, FridaError> {
+        self.prover
+            .open_for_validators(assignments)?
+            .into_iter()
+            .zip(assignments)
+            .map(|(proof, positions)| {
+                let Some(proof) = proof else {
+                    return Ok(None);
+                };
+                Ok(Some(Share {
+                    commitment: self.prover.commitment(),
+                    positions: positions.clone(),
+                    evaluations: self.prover.get_evaluations(positions)?,
+                    proof,
+                }))
+            })
+            .collect()
+    }
+
+    /// Checks that `attestation` was signed with `key` for the positions assigned to its
+    /// validator in `assignments`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidAttestation` if the attestation is bound to another
+    /// commitment, covers other positions, or is not validly signed.
+    pub fn check_attestation(
+        &self,
+        attestation: &Attestation<H>,
+        assignments: &[Vec<usize>],
+        key: &impl RecordVerifier,
+    ) -> Result<(), FridaError> {
+        let positions = assignments
+            .get(attestation.validator_id as usize)
+            .ok_or(FridaError::InvalidAttestation)?;
+        attestation.verify(key, &self.prover.commitment(), positions)
+    }
+}
+
+// VALIDATOR
+// ================================================================================================
+
+/// Validator receiving shares, which attests to those it could verify.
+pub struct Validator<S: RecordSigner> {
+    index: u32,
+    options: FriOptions,
+    key: S,
+}
+
+impl<S: RecordSigner> Validator<S> {
+    /// Creates validator `index`, verifying shares committed with `options` and signing its
+    /// attestations with `key`.
+    pub fn new(index: u32, options: FriOptions, key: S) -> Self {
+        Validator {
+            index,
+            options,
+            key,
+        }
+    }
+
+    /// Verifies `share` against `published`, the commitment the validator obtained for the blob
+    /// on its own (e.g. from a `DaOracle`), and attests to it.
+    ///
+    /// The commitment carried by the share is only checked against `published`, so that a
+    /// producer cannot have a share attested for a commitment it did not publish.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ShareCommitmentMismatch` if the share carries another commitment, and
+    /// an error if the commitment cannot be verified or the evaluations of the share are rejected.
+    pub fn handle_share<E, H>(
+        &self,
+        share: &Share<E, H>,
+        published: &ProverCommitment<H>,
+    ) -> Result<Attestation<H>, FridaError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        if share.commitment != *published {
+            return Err(FridaError::ShareCommitmentMismatch);
+        }
+        let verifier =
+            FridaDasVerifier::<E, H, H>::from_commitment(published, self.options.clone())?;
+        verifier.verify(&share.proof, &share.evaluations, &share.positions)?;
+
+        Ok(Attestation::new(
+            published,
+            self.index,
+            &share.positions,
+            &self.key,
+        ))
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement, H: Hasher> Serializable for Share<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.commitment.write_into(target);
+        write_positions(&self.positions, target);
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for Share<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let commitment = ProverCommitment::<H>::read_from(source)?;
+        // positions index the domain of the commitment
+        let positions = read_positions(source, commitment.domain_size)?;
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(Share {
+            commitment,
+            positions,
+            evaluations,
+            proof,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{utils::test_utils::*, winterfell::f128::BaseElement};
+    use winter_rand_utils::rand_vector;
+
+    #[test]
+    fn test_dispersal_round() {
+        let options = test_options();
+        let (_, prover, _) = TestFridaProverBuilder::new(options.clone())
+            .commitment(&rand_vector::<u8>(300), 4)
+            .unwrap();
+        let producer = Producer::new(prover);
+
+        let assignments = vec![vec![1, 9], vec![], vec![4, 17, 30]];
+        let shares = producer.shares(&assignments).unwrap();
+        assert!(shares[1].is_none());
+
+        for (index, share) in shares.iter().enumerate() {
+            let Some(share) = share else { continue };
+            let decoded = Share::read_from_bytes(&share.to_bytes()).unwrap();
+            assert_eq!(&decoded, share);
+
+            let validator = Validator::new(index as u32, options.clone(), TestKey(index as u32));
+            let attestation = validator
+                .handle_share(&decoded, &producer.commitment())
+                .unwrap();
+            let attestation = Attestation::read_from_bytes(&attestation.to_bytes()).unwrap();
+            assert_eq!(
+                producer.check_attestation(&attestation, &assignments, &TestKey(index as u32)),
+                Ok(())
+            );
+
+            // attestations only count for the key and positions of their validator
+            assert_eq!(
+                producer.check_attestation(&attestation, &assignments, &TestKey(7)),
+                Err(FridaError::InvalidAttestation)
+            );
+            let mut moved = attestation;
+            moved.validator_id = 1;
+            assert_eq!(
+                producer.check_attestation(&moved, &assignments, &TestKey(index as u32)),
+                Err(FridaError::InvalidAttestation)
+            );
+        }
+    }
+
+    #[test]
+    fn test_share_positions_are_compact() {
+        let options = test_options();
+        let (_, prover, _) = TestFridaProverBuilder::new(options.clone())
+            .commitment(&rand_vector::<u8>(300), 4)
+            .unwrap();
+        let producer = Producer::new(prover);
+
+        let assignment = (4..20).collect::<Vec<_>>();
+        let share = producer.shares(&[assignment]).unwrap().remove(0).unwrap();
+        let bytes = share.to_bytes();
+        assert_eq!(Share::read_from_bytes(&bytes).unwrap(), share);
+
+        let plain_size = share.commitment.to_bytes().len()
+            + share.positions.to_bytes().len()
+            + share.evaluations.to_bytes().len()
+            + share.proof.to_bytes().len();
+        assert!(bytes.len() < plain_size);
+
+        // shares cannot hold more positions than the domain of their commitment
+        let mut oversized = share;
+        oversized.positions = (0..oversized.commitment.domain_size + 1).collect();
+        assert!(Share::<BaseElement, Blake3>::read_from_bytes(&oversized.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_invalid_share_is_not_attested() {
+        let options = test_options();
+        let (_, prover, _) = TestFridaProverBuilder::new(options.clone())
+            .commitment(&rand_vector::<u8>(300), 4)
+            .unwrap();
+        let producer = Producer::new(prover);
+
+        let mut share = producer.shares(&[vec![3, 5]]).unwrap().remove(0).unwrap();
+        share.evaluations.swap(0, 1);
+        let validator = Validator::new(0, options, TestKey(0));
+        assert!(matches!(
+            validator.handle_share(&share, &producer.commitment()),
+            Err(FridaError::FailToVerify(_))
+        ));
+    }
+
+    #[test]
+    fn test_share_of_unpublished_commitment_is_not_attested() {
+        let options = test_options();
+        let builder = TestFridaProverBuilder::new(options.clone());
+        let (published, _, _) = builder.commitment(&rand_vector::<u8>(300), 4).unwrap();
+        let (_, prover, _) = builder.commitment(&rand_vector::<u8>(300), 4).unwrap();
+        let producer = Producer::new(prover);
+
+        // the share is consistent with the commitment it carries, but that one was not published
+        let share = producer.shares(&[vec![3, 5]]).unwrap().remove(0).unwrap();
+        let validator = Validator::new(0, options, TestKey(0));
+        assert!(validator
+            .handle_share(&share, &producer.commitment())
+            .is_ok());
+        assert_eq!(
+            validator.handle_share(&share, &published).err(),
+            Some(FridaError::ShareCommitmentMismatch)
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::{io, marker::PhantomData, time::Duration};
+
+use async_trait::async_trait;
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libp2p::{
+    identity::{Keypair, PublicKey},
+    request_response::{self, ProtocolSupport},
+    StreamProtocol,
+};
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+use winter_utils::{Deserializable, Serializable};
+
+use super::{Attestation, Share};
+use crate::prover::supersede::{RecordSigner, RecordVerifier};
+
+// LIBP2P TRANSPORT
+// ================================================================================================
+
+/// Protocol name of the dispersal request/response streams.
+pub const DISPERSAL_PROTOCOL: StreamProtocol = StreamProtocol::new("/frida/dispersal/1");
+
+/// Largest message read from a stream, so that a peer cannot make us buffer without bound.
+const MAX_MESSAGE_SIZE: u64 = 64 << 20;
+
+/// Request/response behaviour sending shares to validators, which answer with an attestation,
+/// or `None` if they rejected their share.
+pub type DispersalBehaviour<E, H> = request_response::Behaviour<DispersalCodec<E, H>>;
+
+/// Creates the dispersal behaviour, supporting both inbound and outbound requests.
+pub fn dispersal_behaviour<E, H>(request_timeout: Duration) -> DispersalBehaviour<E, H>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + 'static,
+{
+    request_response::Behaviour::with_codec(
+        DispersalCodec::default(),
+        [(DISPERSAL_PROTOCOL, ProtocolSupport::Full)],
+        request_response::Config::default().with_request_timeout(request_timeout),
+    )
+}
+
+/// Codec writing every message as its serialized bytes, delimited by the end of its stream.
+pub struct DispersalCodec<E, H> {
+    _marker: PhantomData<fn() -> (E, H)>,
+}
+
+impl<E, H> Clone for DispersalCodec<E, H> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<E, H> Default for DispersalCodec<E, H> {
+    fn default() -> Self {
+        DispersalCodec {
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<E, H> request_response::Codec for DispersalCodec<E, H>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + 'static,
+{
+    type Protocol = StreamProtocol;
+    type Request = Share<E, H>;
+    type Response = Option<Attestation<H>>;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<Share<E, H>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let bytes = read_message(io).await?;
+        Share::read_from_bytes(&bytes).map_err(invalid_data)
+    }
+
+    async fn read_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+    ) -> io::Result<Option<Attestation<H>>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let bytes = read_message(io).await?;
+        match bytes.split_first() {
+            Some((0, [])) => Ok(None),
+            Some((1, attestation)) => Attestation::read_from_bytes(attestation)
+                .map(Some)
+                .map_err(invalid_data),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid attestation response",
+            )),
+        }
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        share: Share<E, H>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(&share.to_bytes()).await?;
+        io.close().await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        attestation: Option<Attestation<H>>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let bytes = match attestation {
+            Some(attestation) => [&[1][..], &attestation.to_bytes()].concat(),
+            None => vec![0],
+        };
+        io.write_all(&bytes).await?;
+        io.close().await
+    }
+}
+
+/// Signs attestations with the libp2p identity of a validator, so that the producer can check
+/// them against the public key of the peer it sent the share to.
+pub struct IdentitySigner(pub Keypair);
+
+impl RecordSigner for IdentitySigner {
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.0
+            .sign(message)
+            .expect("identity keys must support signing")
+    }
+}
+
+impl RecordVerifier for PublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        PublicKey::verify(self, message, signature)
+    }
+}
+
+async fn read_message<T: AsyncRead + Unpin + Send>(io: &mut T) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    io.take(MAX_MESSAGE_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .await?;
+    if bytes.len() as u64 > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "dispersal message is too large",
+        ));
+    }
+    Ok(bytes)
+}
+
+fn invalid_data(error: winter_utils::DeserializationError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
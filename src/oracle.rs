@@ -0,0 +1,490 @@
+//! Adapters to data availability oracles, through which producers publish commitments and
+//! samplers fetch them along with shares of the committed data.
+//!
+//! Sampling and reconstruction are written against `DaOracle`, so that they run the same over any
+//! transport. Two reference oracles are provided: `MemoryOracle`, keeping published commitments in
+//! memory for tests, and `FsOracle`, keeping them on disk for demos spanning several processes.
+//! Commitments are identified by their `ProverCommitment::root_digest`.
+//!
+//! ```text
+//! <root>/<root digest>/commitment.bin
+//! <root>/<root digest>/prover.bin
+//! ```
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt, fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+use winter_utils::{Deserializable, DeserializationError, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{proof::FridaProof, FridaProver, ProverCommitment},
+    utils::encoding,
+};
+
+const COMMITMENT_FILE: &str = "commitment.bin";
+const PROVER_FILE: &str = "prover.bin";
+
+/// Number of provers an `FsOracle` keeps parsed, so that the shares of the commitments sampled
+/// the most recently are opened without reading and rebuilding their provers again.
+const PROVER_CACHE_CAPACITY: usize = 8;
+
+// ERRORS
+// ================================================================================================
+
+#[derive(Debug)]
+pub enum OracleError {
+    IoError(io::Error),
+    DeserializationError(DeserializationError),
+    /// The requested positions could not be opened.
+    OpeningError(FridaError),
+    /// A published entry does not hash to the root digest it is published under.
+    DigestMismatch,
+}
+
+impl fmt::Display for OracleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OracleError::IoError(e) => write!(f, "IO error: {e}"),
+            OracleError::DeserializationError(e) => write!(f, "Deserialization error: {e}"),
+            OracleError::OpeningError(e) => write!(f, "Opening error: {e}"),
+            OracleError::DigestMismatch => {
+                write!(f, "Published entry does not match its root digest")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OracleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OracleError::IoError(e) => Some(e),
+            OracleError::OpeningError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for OracleError {
+    fn from(err: io::Error) -> Self {
+        OracleError::IoError(err)
+    }
+}
+
+impl From<DeserializationError> for OracleError {
+    fn from(err: DeserializationError) -> Self {
+        OracleError::DeserializationError(err)
+    }
+}
+
+impl From<FridaError> for OracleError {
+    fn from(err: FridaError) -> Self {
+        OracleError::OpeningError(err)
+    }
+}
+
+// DA ORACLE
+// ================================================================================================
+
+/// Evaluations of a commitment at some positions, along with the proof opening them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleShare<E: FieldElement> {
+    pub positions: Vec<usize>,
+    /// Evaluations at `positions`, as returned by `FridaProver::get_evaluations`.
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+}
+
+/// Data availability layer on which producers publish commitments, and from which samplers and
+/// reconstructing clients fetch them along with shares of the committed data.
+pub trait DaOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Publishes the commitment of `prover`, and makes any share of the committed data available.
+    /// Returns the root digest the commitment is published under.
+    fn publish_commitment(&mut self, prover: FridaProver<E, H>) -> Result<[u8; 32], OracleError>;
+
+    /// Returns the commitment published under `digest`, if any.
+    fn fetch_commitment(
+        &self,
+        digest: &[u8; 32],
+    ) -> Result<Option<ProverCommitment<H>>, OracleError>;
+
+    /// Returns the share of the commitment published under `digest` opening `positions`, or
+    /// `None` if no commitment was published under `digest`.
+    fn fetch_share(
+        &self,
+        digest: &[u8; 32],
+        positions: &[usize],
+    ) -> Result<Option<OracleShare<E>>, OracleError>;
+}
+
+// MEMORY ORACLE
+// ================================================================================================
+
+/// Oracle keeping published commitments in memory, for tests.
+#[derive(Debug)]
+pub struct MemoryOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    provers: BTreeMap<[u8; 32], FridaProver<E, H>>,
+}
+
+impl<E, H> MemoryOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    pub fn new() -> Self {
+        MemoryOracle {
+            provers: BTreeMap::new(),
+        }
+    }
+}
+
+impl<E, H> Default for MemoryOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, H> DaOracle<E, H> for MemoryOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn publish_commitment(&mut self, prover: FridaProver<E, H>) -> Result<[u8; 32], OracleError> {
+        let digest = prover.commitment().root_digest();
+        self.provers.insert(digest, prover);
+        Ok(digest)
+    }
+
+    fn fetch_commitment(
+        &self,
+        digest: &[u8; 32],
+    ) -> Result<Option<ProverCommitment<H>>, OracleError> {
+        Ok(self.provers.get(digest).map(FridaProver::commitment))
+    }
+
+    fn fetch_share(
+        &self,
+        digest: &[u8; 32],
+        positions: &[usize],
+    ) -> Result<Option<OracleShare<E>>, OracleError> {
+        self.provers
+            .get(digest)
+            .map(|prover| open_share(prover, positions))
+            .transpose()
+    }
+}
+
+// FILESYSTEM ORACLE
+// ================================================================================================
+
+/// Oracle keeping published commitments on disk, so that producers and samplers can run in
+/// separate processes sharing a directory.
+///
+/// Entries are immutable once published under their root digest, so the provers of the last few
+/// commitments shares were fetched for are kept parsed in memory.
+pub struct FsOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    root: PathBuf,
+    /// Provers from the least to the most recently used.
+    provers: Mutex<VecDeque<([u8; 32], Arc<FridaProver<E, H>>)>>,
+    _phantom_field_element: PhantomData<E>,
+    _phantom_hasher: PhantomData<H>,
+}
+
+impl<E, H> FsOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens the oracle at `root`, creating the directory if it does not exist yet.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, OracleError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(FsOracle {
+            root,
+            provers: Mutex::new(VecDeque::new()),
+            _phantom_field_element: PhantomData,
+            _phantom_hasher: PhantomData,
+        })
+    }
+
+    fn entry_path(&self, digest: &[u8; 32]) -> PathBuf {
+        self.root.join(encoding::to_hex(digest))
+    }
+
+    /// Writes through a temporary file, so that readers never observe a partially written entry.
+    fn write(&self, digest: &[u8; 32], file: &Path, bytes: &[u8]) -> Result<(), OracleError> {
+        let path = self.entry_path(digest).join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn read(&self, digest: &[u8; 32], file: &Path) -> Result<Option<Vec<u8>>, OracleError> {
+        match fs::read(self.entry_path(digest).join(file)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the prover published under `digest`, parsing it only if it is not cached.
+    fn prover(&self, digest: &[u8; 32]) -> Result<Option<Arc<FridaProver<E, H>>>, OracleError> {
+        let mut provers = self.provers.lock().unwrap();
+        if let Some(index) = provers.iter().position(|(key, _)| key == digest) {
+            let entry = provers.remove(index).expect("index is in bounds");
+            let prover = entry.1.clone();
+            provers.push_back(entry);
+            return Ok(Some(prover));
+        }
+        drop(provers);
+
+        let Some(bytes) = self.read(digest, Path::new(PROVER_FILE))? else {
+            return Ok(None);
+        };
+        let prover = FridaProver::<E, H>::read_from_bytes(&bytes)?;
+        if prover.commitment().root_digest() != *digest {
+            return Err(OracleError::DigestMismatch);
+        }
+        let prover = Arc::new(prover);
+        self.cache_prover(*digest, prover.clone());
+        Ok(Some(prover))
+    }
+
+    fn cache_prover(&self, digest: [u8; 32], prover: Arc<FridaProver<E, H>>) {
+        let mut provers = self.provers.lock().unwrap();
+        provers.retain(|(key, _)| *key != digest);
+        if provers.len() == PROVER_CACHE_CAPACITY {
+            provers.pop_front();
+        }
+        provers.push_back((digest, prover));
+    }
+}
+
+impl<E, H> DaOracle<E, H> for FsOracle<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// The prover is written before the commitment, so that a commitment which can be fetched
+    /// always has its shares available.
+    fn publish_commitment(&mut self, prover: FridaProver<E, H>) -> Result<[u8; 32], OracleError> {
+        let commitment = prover.commitment();
+        let digest = commitment.root_digest();
+        self.write(&digest, Path::new(PROVER_FILE), &prover.to_bytes())?;
+        self.write(&digest, Path::new(COMMITMENT_FILE), &commitment.to_bytes())?;
+        self.cache_prover(digest, Arc::new(prover));
+        Ok(digest)
+    }
+
+    fn fetch_commitment(
+        &self,
+        digest: &[u8; 32],
+    ) -> Result<Option<ProverCommitment<H>>, OracleError> {
+        let Some(bytes) = self.read(digest, Path::new(COMMITMENT_FILE))? else {
+            return Ok(None);
+        };
+        let commitment = ProverCommitment::<H>::read_from_bytes(&bytes)?;
+        if commitment.root_digest() != *digest {
+            return Err(OracleError::DigestMismatch);
+        }
+        Ok(Some(commitment))
+    }
+
+    fn fetch_share(
+        &self,
+        digest: &[u8; 32],
+        positions: &[usize],
+    ) -> Result<Option<OracleShare<E>>, OracleError> {
+        self.prover(digest)?
+            .map(|prover| open_share(&prover, positions))
+            .transpose()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn open_share<E, H>(
+    prover: &FridaProver<E, H>,
+    positions: &[usize],
+) -> Result<OracleShare<E>, OracleError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    Ok(OracleShare {
+        positions: positions.to_vec(),
+        evaluations: prover.get_evaluations(positions)?,
+        proof: prover.open(positions)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use winter_math::fields::f128::BaseElement;
+    use winter_rand_utils::{rand_value, rand_vector};
+
+    type TestMemoryOracle = MemoryOracle<BaseElement, Blake3>;
+    type TestFsOracle = FsOracle<BaseElement, Blake3>;
+
+    /// An oracle in a fresh temporary directory, removed when dropped.
+    struct TempOracle(TestFsOracle);
+
+    impl TempOracle {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("frida-oracle-{:x}", rand_value::<u64>()));
+            TempOracle(TestFsOracle::open(root).unwrap())
+        }
+    }
+
+    impl Drop for TempOracle {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0.root);
+        }
+    }
+
+    /// Publishes a blob on `oracle`, then samples and reconstructs it from what the oracle serves.
+    fn check_sampling_and_reconstruction<O: DaOracle<BaseElement, Blake3>>(oracle: &mut O) {
+        let options = test_options();
+        let builder = TestFridaProverBuilder::new(options.clone());
+        let data = rand_vector::<u8>(200);
+        let (_, prover) = builder.commit_and_prove(&data, 4).unwrap();
+        let digest = oracle.publish_commitment(prover).unwrap();
+
+        // sampling
+        let commitment = oracle.fetch_commitment(&digest).unwrap().unwrap();
+        let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+        verifier.check_root_digest(&digest).unwrap();
+        let share = oracle.fetch_share(&digest, &[1, 6, 9]).unwrap().unwrap();
+        verifier
+            .verify(&share.proof, &share.evaluations, &share.positions)
+            .unwrap();
+
+        // reconstruction
+        let positions = (1..commitment.domain_size).step_by(2).collect::<Vec<_>>();
+        let share = oracle.fetch_share(&digest, &positions).unwrap().unwrap();
+        verifier
+            .verify(&share.proof, &share.evaluations, &share.positions)
+            .unwrap();
+        let samples = positions
+            .into_iter()
+            .zip(share.evaluations)
+            .collect::<Vec<_>>();
+        assert_eq!(verifier.reconstruct_poly(0, &samples).unwrap(), data);
+
+        let unknown = [0; 32];
+        assert!(oracle.fetch_commitment(&unknown).unwrap().is_none());
+        assert!(oracle.fetch_share(&unknown, &[1]).unwrap().is_none());
+        assert!(matches!(
+            oracle.fetch_share(&digest, &[commitment.domain_size]),
+            Err(OracleError::OpeningError(_))
+        ));
+    }
+
+    #[test]
+    fn test_memory_oracle() {
+        check_sampling_and_reconstruction(&mut TestMemoryOracle::new());
+    }
+
+    #[test]
+    fn test_fs_oracle() {
+        check_sampling_and_reconstruction(&mut TempOracle::new().0);
+    }
+
+    #[test]
+    fn test_fs_oracle_detects_mismatched_entries() {
+        let mut oracle = TempOracle::new();
+        let builder = TestFridaProverBuilder::new(test_options());
+        let (_, prover) = builder
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+        let (_, other) = builder
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+
+        let digest = oracle.0.publish_commitment(prover).unwrap();
+        let other_digest = oracle.0.publish_commitment(other).unwrap();
+        for file in [COMMITMENT_FILE, PROVER_FILE] {
+            fs::copy(
+                oracle.0.entry_path(&other_digest).join(file),
+                oracle.0.entry_path(&digest).join(file),
+            )
+            .unwrap();
+        }
+        // another process reads the entries from disk
+        let reader = TestFsOracle::open(&oracle.0.root).unwrap();
+        assert!(matches!(
+            reader.fetch_commitment(&digest),
+            Err(OracleError::DigestMismatch)
+        ));
+        assert!(matches!(
+            reader.fetch_share(&digest, &[1]),
+            Err(OracleError::DigestMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_fs_oracle_caches_parsed_provers() {
+        let mut oracle = TempOracle::new();
+        let builder = TestFridaProverBuilder::new(test_options());
+        let (_, prover) = builder
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+        let digest = oracle.0.publish_commitment(prover).unwrap();
+
+        // the prover is parsed once, and its shares are then opened from memory
+        let reader = TestFsOracle::open(&oracle.0.root).unwrap();
+        let share = reader.fetch_share(&digest, &[1, 6]).unwrap().unwrap();
+        fs::remove_file(reader.entry_path(&digest).join(PROVER_FILE)).unwrap();
+        assert_eq!(
+            reader.fetch_share(&digest, &[1, 6]).unwrap().unwrap(),
+            share
+        );
+        assert!(TestFsOracle::open(&oracle.0.root)
+            .unwrap()
+            .fetch_share(&digest, &[1, 6])
+            .unwrap()
+            .is_none());
+
+        // only the provers of the last commitments are kept
+        for _ in 0..PROVER_CACHE_CAPACITY {
+            let (_, prover) = builder
+                .commit_and_prove(&rand_vector::<u8>(200), 4)
+                .unwrap();
+            oracle.0.publish_commitment(prover).unwrap();
+        }
+        assert_eq!(
+            oracle.0.provers.lock().unwrap().len(),
+            PROVER_CACHE_CAPACITY
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::{panic, sync::Arc};
+
+use tokio::task::{self, JoinError};
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+
+use super::{builder::FridaProverBuilder, proof::FridaProof, Commitment, FridaProver};
+use crate::error::FridaError;
+
+// ASYNC WRAPPERS
+// ================================================================================================
+
+// Encoding, hashing and opening are CPU-bound, so running them directly inside a task would stall
+// every other task scheduled on the same worker. These wrappers move them to tokio's blocking pool
+// instead; they must therefore be awaited from within a tokio runtime.
+
+impl<E, H, HHst> FridaProverBuilder<E, H, HHst>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+    HHst: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+{
+    /// Runs `commit_and_prove` on the blocking thread pool.
+    pub async fn commit_async(
+        self: Arc<Self>,
+        data: Vec<u8>,
+        num_queries: usize,
+    ) -> Result<(Commitment<H>, FridaProver<E, H>), FridaError> {
+        task::spawn_blocking(move || self.commit_and_prove(&data, num_queries))
+            .await
+            .unwrap_or_else(propagate)
+    }
+
+    /// Runs `commit_and_prove_batch` on the blocking thread pool.
+    pub async fn commit_batch_async(
+        self: Arc<Self>,
+        data_list: Vec<Vec<u8>>,
+        num_queries: usize,
+    ) -> Result<(Commitment<H>, FridaProver<E, H>), FridaError> {
+        task::spawn_blocking(move || self.commit_and_prove_batch(&data_list, num_queries))
+            .await
+            .unwrap_or_else(propagate)
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+{
+    /// Runs `open` on the blocking thread pool.
+    pub async fn open_async(
+        self: Arc<Self>,
+        positions: Vec<usize>,
+    ) -> Result<FridaProof, FridaError> {
+        task::spawn_blocking(move || self.open(&positions))
+            .await
+            .unwrap_or_else(propagate)
+    }
+}
+
+/// Re-raises a panic of the blocking task in the awaiting task, as the synchronous call would.
+fn propagate<T>(err: JoinError) -> T {
+    match err.try_into_panic() {
+        Ok(payload) => panic::resume_unwind(payload),
+        Err(err) => panic!("blocking prover task was cancelled: {err}"),
+    }
+}
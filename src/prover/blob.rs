@@ -0,0 +1,233 @@
+use winter_crypto::{ElementHasher, Hasher};
+use winter_fri::folding;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{BatchLeafEncoding, FridaProver};
+use crate::{
+    constants::MAX_DOMAIN_SIZE,
+    core::positions::{read_positions, write_positions},
+    error::FridaError,
+};
+
+// LEAF ENCODING
+// ================================================================================================
+
+impl BatchLeafEncoding {
+    /// Hashes a leaf of the first layer of a batched commitment, holding `folding_factor` rows of
+    /// the values of each of the `poly_count` blobs.
+    pub fn hash_leaf<E, H>(&self, bucket: &[E], poly_count: usize) -> H::Digest
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        match self {
+            BatchLeafEncoding::Flat => H::hash_elements(bucket),
+            BatchLeafEncoding::PerBlob => H::merge_many(&blob_digests::<E, H>(bucket, poly_count)),
+        }
+    }
+}
+
+/// Returns the values of blob `blob` in a leaf of the first layer, in row order.
+fn blob_values<E: FieldElement>(bucket: &[E], poly_count: usize, blob: usize) -> Vec<E> {
+    bucket
+        .iter()
+        .skip(blob)
+        .step_by(poly_count)
+        .copied()
+        .collect()
+}
+
+/// Returns the digest of the values of every blob in a leaf of the first layer, in blob order.
+fn blob_digests<E, H>(bucket: &[E], poly_count: usize) -> Vec<H::Digest>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    (0..poly_count)
+        .map(|blob| H::hash_elements(&blob_values(bucket, poly_count, blob)))
+        .collect()
+}
+
+// BLOB OPENING
+// ================================================================================================
+
+/// Opening of the evaluations of a single blob of a batched commitment at some positions,
+/// proven against the root of its first layer without revealing the values of the other blobs.
+///
+/// Requires a commitment built with `BatchLeafEncoding::PerBlob`. Every leaf of the first layer
+/// holds `folding_factor` rows, so the values of the blob are opened at every row of the leaves
+/// of the positions. Only the membership of the values in the first layer is proven; the
+/// commitment itself is what shows that the blobs are low-degree.
+///
+/// Positions are serialized with `core::positions::write_positions`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BlobOpening<E: FieldElement, H: Hasher> {
+    pub blob: usize,
+    pub positions: Vec<usize>,
+    /// Values of the blob in every opened leaf, `folding_factor` per leaf, in the order of the
+    /// folded positions.
+    pub values: Vec<E>,
+    /// Digests of the values of the other blobs in every opened leaf, `poly_count - 1` per leaf.
+    pub siblings: Vec<H::Digest>,
+    /// Internal nodes of the batch Merkle proof of the opened leaves.
+    pub paths: Vec<u8>,
+}
+
+impl<E: FieldElement, H: Hasher> Clone for BlobOpening<E, H> {
+    fn clone(&self) -> Self {
+        BlobOpening {
+            blob: self.blob,
+            positions: self.positions.clone(),
+            values: self.values.clone(),
+            siblings: self.siblings.clone(),
+            paths: self.paths.clone(),
+        }
+    }
+}
+
+impl<E, H> BlobOpening<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Recomputes the `num_leaves` opened leaves of the first layer, or returns `None` if the
+    /// opening does not hold the values and sibling digests of that many leaves.
+    pub(crate) fn leaves(
+        &self,
+        num_leaves: usize,
+        folding_factor: usize,
+        poly_count: usize,
+    ) -> Option<Vec<H::Digest>> {
+        let num_siblings = poly_count - 1;
+        if self.blob >= poly_count
+            || self.values.len() != num_leaves * folding_factor
+            || self.siblings.len() != num_leaves * num_siblings
+        {
+            return None;
+        }
+        let leaves = self
+            .values
+            .chunks(folding_factor)
+            .zip(self.siblings.chunks(num_siblings))
+            .map(|(values, siblings)| {
+                let mut digests = siblings.to_vec();
+                digests.insert(self.blob, H::hash_elements(values));
+                H::merge_many(&digests)
+            })
+            .collect();
+        Some(leaves)
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens the evaluations of blob `blob` at `positions`, along with the digests of the other
+    /// blobs needed to prove them; see `BlobOpening`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BlobOpeningUnsupported` if the commitment is not batched with
+    /// `BatchLeafEncoding::PerBlob`, or its layers are split into several partitions, as blob
+    /// openings do not carry any; `FridaError::NoPositions` if `positions` is empty,
+    /// `FridaError::CellOutOfBounds` if `blob` or a position lies outside of the commitment, and
+    /// `FridaError::PositionPruned` if the prover was pruned and a position was not retained.
+    pub fn open_blob(
+        &self,
+        blob: usize,
+        positions: &[usize],
+    ) -> Result<BlobOpening<E, H>, FridaError> {
+        if self.poly_count < 2
+            || self.batch_leaf_encoding != BatchLeafEncoding::PerBlob
+            || self.num_partitions > 1
+        {
+            return Err(FridaError::BlobOpeningUnsupported);
+        }
+        if positions.is_empty() {
+            return Err(FridaError::NoPositions);
+        }
+        if let Some(&position) = positions
+            .iter()
+            .find(|&&position| blob >= self.poly_count || position >= self.domain_size)
+        {
+            return Err(FridaError::CellOutOfBounds(blob, position));
+        }
+
+        let folding_factor = self.folding_factors[0];
+        let bucket_size = self.poly_count * folding_factor;
+        let indexes = folding::fold_positions(positions, self.domain_size, folding_factor);
+        let layer = &self.layers[0];
+
+        let mut values = Vec::with_capacity(indexes.len() * folding_factor);
+        let mut siblings = Vec::with_capacity(indexes.len() * (self.poly_count - 1));
+        for &index in indexes.iter() {
+            let bucket = layer.row(index, bucket_size)?;
+            let mut digests = blob_digests::<E, H>(bucket, self.poly_count);
+            digests.remove(blob);
+            siblings.extend(digests);
+            values.extend(blob_values(bucket, self.poly_count, blob));
+        }
+
+        Ok(BlobOpening {
+            blob,
+            positions: positions.to_vec(),
+            values,
+            siblings,
+            paths: layer.prove(&indexes, &indexes)?.serialize_nodes(),
+        })
+    }
+}
+
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<E: FieldElement, H: Hasher> Serializable for BlobOpening<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.blob as u32);
+        write_positions(&self.positions, target);
+        self.values.write_into(target);
+        self.siblings.write_into(target);
+        self.paths.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for BlobOpening<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blob = source.read_u32()? as usize;
+        let positions = read_positions(source, MAX_DOMAIN_SIZE)?;
+        let values = Vec::<E>::read_from(source)?;
+        let siblings = Vec::<H::Digest>::read_from(source)?;
+        let paths = Vec::<u8>::read_from(source)?;
+
+        Ok(BlobOpening {
+            blob,
+            positions,
+            values,
+            siblings,
+            paths,
+        })
+    }
+}
+
+impl Serializable for BatchLeafEncoding {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(match self {
+            BatchLeafEncoding::Flat => 0,
+            BatchLeafEncoding::PerBlob => 1,
+        });
+    }
+}
+
+impl Deserializable for BatchLeafEncoding {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(BatchLeafEncoding::Flat),
+            1 => Ok(BatchLeafEncoding::PerBlob),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "unknown batch leaf encoding {value}"
+            ))),
+        }
+    }
+}
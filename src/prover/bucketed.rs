@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use winter_crypto::Hasher;
+
+use super::{disclosure, ProverCommitment};
+
+// BUCKETED COMMITMENT
+// ================================================================================================
+
+/// Commitments to a batch of blobs of heterogeneous sizes, grouped into buckets of blobs sharing
+/// the same domain, as built by `FridaProverBuilder::commitment_bucketed`.
+///
+/// Batched commitments pad every blob to the domain of the largest one, so that a few large blobs
+/// make every small blob of the batch as expensive as them. Buckets only pad blobs to the next
+/// power of two of their own size, and the super root binds the commitments of every bucket
+/// together, so that the batch is still identified by a single digest.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BucketedCommitment<H: Hasher> {
+    /// Root of the Merkle tree over the binding of the commitment of every bucket, in order.
+    pub super_root: H::Digest,
+    /// Buckets, by increasing domain size.
+    pub buckets: Vec<CommitmentBucket<H>>,
+    pub padding: PaddingReport,
+}
+
+/// The blobs of a size class, committed together; a bucket of a single blob holds a commitment to
+/// that blob alone.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CommitmentBucket<H: Hasher> {
+    /// Index of every blob of the bucket in the input batch, in the order they were committed.
+    pub blobs: Vec<usize>,
+    pub commitment: ProverCommitment<H>,
+}
+
+/// Number of evaluations committed by a bucketed commitment, compared with the evaluations its
+/// blobs actually need and with those of a single batched commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaddingReport {
+    /// Blowup factor times the number of encoded elements of every blob.
+    pub required_evaluations: usize,
+    /// Evaluations committed over every bucket.
+    pub committed_evaluations: usize,
+    /// Evaluations `FridaProverBuilder::commitment_batch` would commit to, padding every blob to
+    /// the domain of the largest one.
+    pub batched_evaluations: usize,
+}
+
+impl PaddingReport {
+    /// Returns the fraction of committed evaluations which only pad the blobs.
+    pub fn padding_overhead(&self) -> f64 {
+        overhead(self.committed_evaluations, self.required_evaluations)
+    }
+
+    /// Returns the fraction of evaluations which would only pad the blobs in a single batch.
+    pub fn batched_overhead(&self) -> f64 {
+        overhead(self.batched_evaluations, self.required_evaluations)
+    }
+}
+
+fn overhead(committed: usize, required: usize) -> f64 {
+    if committed == 0 {
+        return 0.0;
+    }
+    (committed - required) as f64 / committed as f64
+}
+
+impl<H: Hasher> BucketedCommitment<H> {
+    /// Returns the siblings of the leaf of bucket `index` in the tree of the super root, from the
+    /// bottom up.
+    ///
+    /// # Panics
+    /// Panics if `index` is not the index of a bucket.
+    pub fn bucket_path(&self, index: usize) -> Vec<H::Digest> {
+        assert!(index < self.buckets.len(), "bucket {index} does not exist");
+        disclosure::app_ids_path::<H>(&bucket_leaves(&self.buckets), index)
+    }
+}
+
+/// Returns the super root over the commitments of `buckets`, which reuses the tree of app ids.
+pub(super) fn super_root<H: Hasher>(buckets: &[CommitmentBucket<H>]) -> H::Digest {
+    disclosure::app_ids_root::<H>(&bucket_leaves(buckets))
+}
+
+fn bucket_leaves<H: Hasher>(buckets: &[CommitmentBucket<H>]) -> Vec<H::Digest> {
+    buckets
+        .iter()
+        .map(|bucket| bucket.commitment.binding())
+        .collect()
+}
+
+/// Checks that `commitment` is the commitment of bucket `index` under `super_root`, given the path
+/// returned by `BucketedCommitment::bucket_path`.
+pub fn verify_bucket<H: Hasher>(
+    super_root: &H::Digest,
+    index: usize,
+    commitment: &ProverCommitment<H>,
+    path: &[H::Digest],
+) -> bool {
+    disclosure::verify_app_ids_path::<H>(super_root, index, commitment.binding(), path)
+}
+
+/// Groups the indexes of blobs by the domain size they need, by increasing domain size.
+pub(super) fn size_classes(domain_sizes: &[usize]) -> BTreeMap<usize, Vec<usize>> {
+    let mut classes = BTreeMap::<usize, Vec<usize>>::new();
+    for (blob, &domain_size) in domain_sizes.iter().enumerate() {
+        classes.entry(domain_size).or_default().push(blob);
+    }
+    classes
+}
@@ -1,58 +1,381 @@
 use core::marker::PhantomData;
+use std::sync::Arc;
 #[cfg(feature = "bench")]
 use std::time::Instant;
 
-use winter_crypto::{ElementHasher, MerkleTree};
+use winter_crypto::{Digest, ElementHasher, MerkleTree};
 use winter_fri::folding;
 use winter_fri::utils::hash_values;
 use winter_fri::{FriOptions, ProverChannel};
 use winter_math::{fft, FieldElement};
-#[cfg(feature = "concurrent")]
-use winter_utils::iterators::*;
-use winter_utils::{flatten_vector_elements, iter_mut, transpose_slice, uninit_vector};
+use winter_utils::{
+    flatten_vector_elements, group_slice_elements, transpose_slice, Deserializable, Serializable,
+};
 
 use super::{
-    batch_data_to_evaluations, channel::FridaProverChannel, Commitment, FridaLayer, FridaProver,
-    FridaRemainder, ProverCommitment,
+    bucketed::{self, BucketedCommitment, CommitmentBucket, PaddingReport},
+    cache::{CachedCommitment, CommitmentCache},
+    cancel::CancellationToken,
+    channel::FridaProverChannel,
+    data_root::DataRoots,
+    deadline::DispersalHint,
+    disclosure,
+    distributed::{PartitionCoordinator, PartitionTask},
+    epoch::{EpochChallenge, EpochCommitment},
+    interleave_evaluations, merkle_error,
+    progress::{Phase, ProgressSink},
+    scheduler::CommitProfile,
+    stir::{self, StirCommitment, StirProver},
+    BatchLeafEncoding, Commitment, FridaLayer, FridaProver, FridaRemainder, LayerStorage,
+    LazyLayers, Protocol, ProverCommitment, ProverLayer,
 };
 
 use crate::{
     constants,
-    core::data::{build_evaluations_from_data, encoded_data_element_count},
+    core::{
+        data::{
+            build_coefficients_with_encoding, encoding_plan, evaluate_coefficients, EncodingOptions,
+        },
+        dedup::{encode_deduped, ChunkingOptions, DedupManifest},
+        deep::{self, ood_evaluation_bytes, OodSample},
+        queries::batch_security_level,
+        schedule::{layer_folding_factors, validate_folding_schedule},
+        transcript::Transcript,
+    },
     error::FridaError,
+    utils::{alloc::output_vector, parallel::ParallelOptions},
 };
 
 #[cfg(feature = "bench")]
 use super::bench;
 
-type Channel<E, H> = FridaProverChannel<E, H, H>;
+type Channel<E, H, HHst> = FridaProverChannel<E, HHst, H>;
 type CommitmentResult<H, E> =
     Result<(ProverCommitment<H>, FridaProver<E, H>, Vec<usize>), FridaError>;
-type ProverStateResult<E, H> = Result<(Channel<E, H>, FridaProver<E, H>), FridaError>;
+type ProverStateResult<E, H, HHst> = Result<(Channel<E, H, HHst>, FridaProver<E, H>), FridaError>;
+
+/// Reed-Solomon encoding of the data of a commitment, made by `FridaProverBuilder::encode` or
+/// `FridaProverBuilder::encode_batch` before any FRI layer is built.
+///
+/// An encoding can be committed to with `FridaProverBuilder::commit` any number of times, e.g. by
+/// builders with different partition or leaf encoding settings, without encoding the data again,
+/// and its evaluations can be stored as erasure-coded shares.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Encoding<E: FieldElement, H: ElementHasher> {
+    evaluations: Vec<Vec<E>>,
+    domain_size: usize,
+    num_queries: usize,
+    blowup_factor: usize,
+    data_checksums: Vec<H::Digest>,
+    data_roots: Option<DataRoots<H>>,
+}
+
+impl<E: FieldElement, H: ElementHasher> Clone for Encoding<E, H> {
+    fn clone(&self) -> Self {
+        Encoding {
+            evaluations: self.evaluations.clone(),
+            domain_size: self.domain_size,
+            num_queries: self.num_queries,
+            blowup_factor: self.blowup_factor,
+            data_checksums: self.data_checksums.clone(),
+            data_roots: self.data_roots.clone(),
+        }
+    }
+}
+
+impl<E: FieldElement, H: ElementHasher> Encoding<E, H> {
+    /// Evaluations of every encoded polynomial over the domain, in natural order.
+    pub fn evaluations(&self) -> &[Vec<E>] {
+        &self.evaluations
+    }
+
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
 
-pub struct FridaProverBuilder<E, H>
+    /// Number of encoded polynomials: one for a blob, one per blob of a batch.
+    pub fn poly_count(&self) -> usize {
+        self.evaluations.len()
+    }
+
+    /// Number of queries the encoding was validated for, which its commitments draw.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+}
+
+/// Builds provers and commitments for a fixed set of FRI options.
+///
+/// `H` is the hasher used for the Merkle trees of every FRI layer, and therefore determines the
+/// digest type of the commitment roots. `HHst` is the hasher used to absorb those roots into the
+/// Fiat-Shamir transcript; it defaults to `H`, but can be set to a different hasher (with a
+/// different digest size) as long as the verifier is instantiated with the same pair.
+pub struct FridaProverBuilder<E, H, HHst = H>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
 {
     pub options: FriOptions,
+    min_batch_security: Option<u32>,
+    layer_storage: LayerStorage,
+    data_checksums: bool,
+    data_root_chunk_size: Option<usize>,
+    encoding: EncodingOptions,
+    folding_schedule: Vec<usize>,
+    protocol: Protocol,
+    ood_check: bool,
+    batch_leaf_encoding: BatchLeafEncoding,
+    dispersal_hint: Option<DispersalHint>,
+    commit_profile: Option<CommitProfile>,
+    direct_remainder: bool,
+    num_partitions: usize,
+    commitment_cache: Option<CommitmentCache<H>>,
+    parallelism: ParallelOptions,
+    progress_sink: Option<Arc<dyn ProgressSink>>,
+    cancellation: Option<CancellationToken>,
     _phantom_field_element: PhantomData<E>,
     _phantom_hasher: PhantomData<H>,
+    _phantom_hasher_hst: PhantomData<HHst>,
 }
 
-impl<E, H> FridaProverBuilder<E, H>
+impl<E, H, HHst> FridaProverBuilder<E, H, HHst>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
 {
     pub fn new(options: FriOptions) -> Self {
         FridaProverBuilder {
             options,
+            min_batch_security: None,
+            layer_storage: LayerStorage::Full,
+            data_checksums: false,
+            data_root_chunk_size: None,
+            encoding: EncodingOptions::default(),
+            folding_schedule: Vec::new(),
+            protocol: Protocol::Fri,
+            ood_check: false,
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            dispersal_hint: None,
+            commit_profile: None,
+            direct_remainder: false,
+            num_partitions: 1,
+            commitment_cache: None,
+            parallelism: ParallelOptions::default(),
+            progress_sink: None,
+            cancellation: None,
             _phantom_field_element: PhantomData,
             _phantom_hasher: PhantomData,
+            _phantom_hasher_hst: PhantomData,
         }
     }
 
+    /// Makes batched commitments fail with `FridaError::InsufficientSecurity` when their
+    /// security level, as computed by `core::queries::calculate_batch_security_level`, is below
+    /// `bits`.
+    pub fn with_min_batch_security(mut self, bits: u32) -> Self {
+        self.min_batch_security = Some(bits);
+        self
+    }
+
+    /// Selects which layers built provers keep in memory; see `LayerStorage` for the tradeoff.
+    pub fn with_layer_storage(mut self, layer_storage: LayerStorage) -> Self {
+        self.layer_storage = layer_storage;
+        self
+    }
+
+    /// Makes commitments carry the hash of the raw bytes of every blob, so that reconstructing
+    /// clients can check the data they decode with `ProverCommitment::check_data`.
+    pub fn with_data_checksums(mut self, enabled: bool) -> Self {
+        self.data_checksums = enabled;
+        self
+    }
+
+    /// Makes commitments carry, next to their FRI layer roots, a plain Merkle root over the raw
+    /// bytes of every blob split into chunks of `chunk_size` bytes, built in the same pass. Holders
+    /// of the data can then prove that some bytes belong to a blob with a
+    /// `data_root::DataInclusionProof`, checked with `ProverCommitment::verify_data_inclusion`,
+    /// while availability is still sampled through FRI.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    pub fn with_data_roots(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "data chunks must hold at least one byte");
+        self.data_root_chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Selects how data is packed into field elements. Reconstructing clients must decode with the
+    /// same options, e.g. through `core::data::recover_data_with_encoding`. Sharding, if the options
+    /// enable it, only applies to `commitment_sharded`.
+    pub fn with_encoding(mut self, encoding: EncodingOptions) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Folds the FRI layers by a non-uniform schedule of factors instead of
+    /// `options.folding_factor()`, e.g. `vec![16, 16, 4]` to fold by 16 for the first two layers
+    /// and by 4 afterwards; see `core::schedule::layer_folding_factors`. Larger early factors
+    /// shorten proofs at the cost of prover time. Verifiers must be created with the same
+    /// schedule, and the batch security level is still computed from `options.folding_factor()`.
+    pub fn with_folding_schedule(mut self, schedule: Vec<usize>) -> Self {
+        self.folding_schedule = schedule;
+        self
+    }
+
+    /// Selects the proof system of commitments; see `Protocol`. With `Protocol::Stir`, only
+    /// `commit_and_prove_stir` is available and every other commitment fails with
+    /// `FridaError::ProtocolMismatch`.
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Draws an out-of-domain point once the first layer is committed, and makes commitments
+    /// carry the evaluation of the first layer at that point; FRI then tests the DEEP quotient
+    /// of the first layer by that evaluation, see `core::deep::OodSample`. This strengthens
+    /// soundness for low blowup factors at the cost of an interpolation over the domain. Verifiers
+    /// check the evaluation whenever a commitment carries one, and reject commitments without one
+    /// with `FridaDasVerifier::require_ood`.
+    pub fn with_ood_check(mut self, enabled: bool) -> Self {
+        self.ood_check = enabled;
+        self
+    }
+
+    /// Selects how the leaves of the first layer of batched commitments hash their values; see
+    /// `BatchLeafEncoding`. Has no effect on commitments to a single blob.
+    pub fn with_batch_leaf_encoding(mut self, encoding: BatchLeafEncoding) -> Self {
+        self.batch_leaf_encoding = encoding;
+        self
+    }
+
+    /// Makes commitments announce a dispersal deadline and the number of shares expected to be
+    /// sampled by then, so that verifiers can flag commitments whose dispersal window passed
+    /// without enough coverage; see `ProverCommitment::dispersal_status`.
+    pub fn with_dispersal_hint(mut self, hint: DispersalHint) -> Self {
+        self.dispersal_hint = Some(hint);
+        self
+    }
+
+    /// Commits with the FRI parameters of `profile`, replacing the options of this builder, and
+    /// records the profile in commitments so that verifiers know the options to check them with,
+    /// e.g. when the parameters are selected per commitment by a `scheduler::CommitmentScheduler`.
+    pub fn with_commit_profile(mut self, profile: CommitProfile) -> Self {
+        self.options = profile.options();
+        self.commit_profile = Some(profile);
+        self
+    }
+
+    /// Commits to single blobs too small to be folded, e.g. short control messages, with no FRI
+    /// layer at all instead of failing with `FridaError::NotEnoughDataPoints`: the commitment is
+    /// the hash of the polynomial of the blob, which every proof carries in the clear as its
+    /// remainder, and openings are checked by evaluating it. Larger blobs and batches are not
+    /// affected.
+    pub fn with_direct_remainder(mut self, enabled: bool) -> Self {
+        self.direct_remainder = enabled;
+        self
+    }
+
+    /// Splits the domain of every layer into `num_partitions` partitions, as in Winterfell's
+    /// distributed proving: row `i` of a layer is committed to in partition `i % num_partitions`,
+    /// and every partition occupies a contiguous range of leaves of the Merkle tree of the layer,
+    /// so that partitions can be hashed, and their subtrees stored, independently. Folding never
+    /// mixes rows of different partitions. Commitments carry the number of partitions, bound by
+    /// their root digest, from which verifiers locate the opened leaves, and proofs made for
+    /// another number are rejected.
+    ///
+    /// Commitments fail with `FridaError::TooManyPartitions` if the smallest layer has fewer rows
+    /// than partitions. Provers with more than one partition cannot open single blobs with
+    /// `FridaProver::open_blob`.
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` is zero or is not a power of two.
+    pub fn with_num_partitions(mut self, num_partitions: usize) -> Self {
+        assert!(
+            num_partitions.is_power_of_two(),
+            "number of partitions must be a power of two, but was {num_partitions}"
+        );
+        self.num_partitions = num_partitions;
+        self
+    }
+
+    /// Makes `commitment` and `commitment_batch` return the commitment, prover and positions
+    /// computed earlier for the same data, number of queries and builder configuration, e.g. when
+    /// an identical blob is proposed again, instead of encoding and committing to it once more.
+    /// Up to `capacity` commitments are kept, the least recently used being evicted first.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn with_commitment_cache(mut self, capacity: usize) -> Self {
+        self.commitment_cache = Some(CommitmentCache::new(capacity));
+        self
+    }
+
+    /// Reports the phases of every commitment to `sink` as they start: the encoding and FFT of
+    /// every blob, the hashing of every FRI layer and the opening of the query positions. Phases
+    /// which are skipped, e.g. for commitments returned by the commitment cache or for encodings
+    /// committed to with `commit`, are not reported. Commitments are the same with or without a
+    /// sink.
+    pub fn with_progress_sink(mut self, sink: impl ProgressSink + 'static) -> Self {
+        self.progress_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Makes commitments, and the openings of their proofs, fail with `FridaError::Cancelled`
+    /// once `token` is cancelled, at the start of the next phase reported to the progress sink;
+    /// see `CancellationToken`. Cancelled commitments are not cached.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Sets how the loops over the rows of the batch layer are split into parallel tasks with the
+    /// `concurrent` feature; commitments and proofs are the same whatever the options.
+    pub fn with_parallelism(mut self, parallelism: ParallelOptions) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Returns the commitment cache of this builder, if enabled with `with_commitment_cache`.
+    pub fn commitment_cache(&self) -> Option<&CommitmentCache<H>> {
+        self.commitment_cache.as_ref()
+    }
+
+    /// Returns the folding factor of every FRI layer over `domain_size`.
+    fn folding_factors(&self, domain_size: usize) -> Vec<usize> {
+        layer_folding_factors(&self.options, &self.folding_schedule, domain_size)
+    }
+
+    /// Returns the folding factor of every FRI layer over `domain_size`, once the folding schedule
+    /// of this builder is checked against its options.
+    ///
+    /// # Errors
+    /// Returns the errors of `core::schedule::validate_folding_schedule`.
+    pub(crate) fn checked_folding_factors(
+        &self,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, FridaError> {
+        validate_folding_schedule(&self.options, &self.folding_schedule)?;
+        Ok(self.folding_factors(domain_size))
+    }
+
+    /// Commits to a single blob with the experimental STIR protocol, returning a commitment to be
+    /// checked by `verifier::stir::StirVerifier` and a prover of samples of the blob. Requires
+    /// `with_protocol(Protocol::Stir)`; layer storage, checksums and folding schedules do not
+    /// apply to this mode.
+    pub fn commit_and_prove_stir(
+        &self,
+        data: &[u8],
+        num_queries: usize,
+    ) -> Result<(StirCommitment<H>, StirProver<E, H>), FridaError> {
+        if self.protocol != Protocol::Stir {
+            return Err(FridaError::ProtocolMismatch);
+        }
+        let evaluations = self.reed_solomon_encode(data, num_queries)?;
+        stir::prove::<E, H, HHst>(&self.options, evaluations, num_queries)
+    }
+
     /// Builds a prover for a specific data, along with a channel that should be used for commitment.
     pub fn commit_and_prove(
         &self,
@@ -65,6 +388,108 @@ where
         Ok((commitment, prover))
     }
 
+    /// Starts a commitment to `data` whose layers are encoded, hashed and folded by one worker per
+    /// partition set with `with_num_partitions`, e.g. on the machines of a cluster, so that
+    /// blocks too large for a single machine can be committed to. Returns the coordinator of the
+    /// commitment and the task of every worker; see `distributed::PartitionCoordinator`. The
+    /// commitment is the same as the one of `commit_and_prove`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::DistributedProvingUnsupported` if the builder checks an out-of-domain
+    /// sample or keeps lazy layers, as these need the whole first layer, or if a partition would
+    /// hold a single row of the first layer; and the errors of `commit_and_prove` otherwise.
+    pub fn distribute(
+        &self,
+        data: &[u8],
+        num_queries: usize,
+    ) -> Result<(PartitionCoordinator<'_, E, H, HHst>, Vec<PartitionTask<E>>), FridaError> {
+        if self.protocol != Protocol::Fri {
+            return Err(FridaError::ProtocolMismatch);
+        }
+        validate_folding_schedule(&self.options, &self.folding_schedule)?;
+        let blowup_factor = self.options.blowup_factor();
+        if self.ood_check || self.layer_storage == LayerStorage::Lazy {
+            return Err(FridaError::DistributedProvingUnsupported);
+        }
+
+        let domain_size = self.encoding_domain_size(data.len(), num_queries)?;
+        let folding_factors = self.folding_factors(domain_size);
+        let Some(&folding_factor) = folding_factors.first() else {
+            return Err(FridaError::NotEnoughDataPoints());
+        };
+        self.check_num_partitions(domain_size, &folding_factors)?;
+        if domain_size / folding_factor / self.num_partitions < 2 {
+            return Err(FridaError::DistributedProvingUnsupported);
+        }
+
+        // workers keep folding their partitions as long as these hold at least two rows, since a
+        // subtree of a single row cannot be proven against
+        let mut layer_size = domain_size;
+        let distributed_factors = folding_factors
+            .into_iter()
+            .take_while(|&folding_factor| {
+                let has_rows = layer_size / folding_factor / self.num_partitions >= 2;
+                layer_size /= folding_factor;
+                has_rows
+            })
+            .collect();
+
+        let coefficients =
+            build_coefficients_with_encoding(data, domain_size, blowup_factor, &self.encoding)?;
+        let data_checksum = self.data_checksums.then(|| H::hash(data));
+        let data_roots = self.data_roots(&[data])?;
+        Ok(PartitionCoordinator::new(
+            self,
+            domain_size,
+            distributed_factors,
+            self.num_partitions,
+            num_queries,
+            coefficients,
+            data_checksum,
+            data_roots,
+        ))
+    }
+
+    /// Re-commits to stored `data` for the epoch of `challenge`, and opens the commitment at the
+    /// positions the challenge samples with `EpochChallenge::positions`, so that auditors can
+    /// check that the data is still held with `EpochCommitment::verify`. The commitment is the
+    /// one of `commit_and_prove` under the options of this builder.
+    ///
+    /// # Errors
+    /// Returns the errors of `commit_and_prove`, `EpochChallenge::positions` and
+    /// `FridaProver::open`.
+    pub fn recommit(
+        &self,
+        data: &[u8],
+        challenge: &EpochChallenge,
+    ) -> Result<(EpochCommitment<E, H>, FridaProver<E, H>), FridaError> {
+        let (_, prover) = self.commit_and_prove(data, challenge.num_queries)?;
+        let commitment = prover.commitment();
+        let positions = challenge.positions::<E, HHst, H>(&commitment)?;
+        let proof = prover.open(&positions)?;
+        let evaluations = prover.get_evaluations(&positions)?;
+
+        let epoch_commitment = EpochCommitment {
+            commitment,
+            evaluations,
+            proof,
+        };
+        Ok((epoch_commitment, prover))
+    }
+
+    /// Same as `commit_and_prove`, additionally returning the Fiat-Shamir transcript of the
+    /// commitment, from which auditors can re-derive every challenge with `Transcript::verify`.
+    pub fn commit_and_prove_with_transcript(
+        &self,
+        data: &[u8],
+        num_queries: usize,
+    ) -> Result<(Commitment<H>, FridaProver<E, H>, Transcript<E, H::Digest>), FridaError> {
+        let (mut channel, prover) = self.prepare_prover_state(data, num_queries)?;
+
+        let commitment = self.finish_commitment(&prover, &mut channel)?;
+        Ok((commitment, prover, channel.transcript().clone()))
+    }
+
     /// Builds a prover for a specific batched data, along with a channel that should be used for commitment. This produces a commitment, and also produces a proof for num_queries number of position.
     pub fn commit_and_prove_batch(
         &self,
@@ -77,22 +502,34 @@ where
         Ok((commitment, prover))
     }
 
+    /// Deduplicates the blobs of a batch with content-defined chunking and commits to the result
+    /// as a single blob, which holds the manifest followed by the unique chunks. Blobs sharing
+    /// pages thus encode and hash only one copy of them; `core::dedup::decode_deduped` rebuilds
+    /// the batch from the decoded blob.
+    pub fn commit_and_prove_deduped(
+        &self,
+        data_list: &[Vec<u8>],
+        chunking: &ChunkingOptions,
+        num_queries: usize,
+    ) -> Result<(Commitment<H>, FridaProver<E, H>, DedupManifest), FridaError> {
+        let (manifest, blob) = encode_deduped(data_list, chunking);
+        let (commitment, prover) = self.commit_and_prove(&blob, num_queries)?;
+        Ok((commitment, prover, manifest))
+    }
+
     /// This method returns a commitment containing only the Merkle roots and metadata,
     /// and a stateful `FridaProver` instance which can be used generate many
     /// proofs for different query sets.
     pub fn commitment(&self, data: &[u8], num_queries: usize) -> CommitmentResult<H, E> {
-        // We use a dummy num_queries here because we are not generating a proof yet.
-        let (mut channel, prover) = self.prepare_prover_state(data, num_queries)?;
-
-        let commitment = ProverCommitment {
-            roots: channel.commitments.clone(),
-            domain_size: prover.domain_size,
-            poly_count: prover.poly_count,
-        };
-
-        let base_positions: Vec<usize> = channel.draw_query_positions();
+        self.cached_commitment(&[data], false, num_queries, || {
+            self.compute_commitment(data, num_queries)
+        })
+    }
 
-        Ok((commitment, prover, base_positions))
+    fn compute_commitment(&self, data: &[u8], num_queries: usize) -> CommitmentResult<H, E> {
+        // We use a dummy num_queries here because we are not generating a proof yet.
+        let (channel, prover) = self.prepare_prover_state(data, num_queries)?;
+        Ok(prover_commitment(channel, prover))
     }
 
     pub fn commitment_batch(
@@ -100,64 +537,264 @@ where
         data_list: &[Vec<u8>],
         num_queries: usize,
     ) -> CommitmentResult<H, E> {
-        let (mut channel, prover) = self.prepare_prover_state_batch(data_list, num_queries)?;
+        let blobs = data_list.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        self.cached_commitment(&blobs, true, num_queries, || {
+            self.compute_commitment_batch(data_list, num_queries)
+        })
+    }
 
-        let commitment = ProverCommitment {
-            roots: channel.commitments.clone(),
-            domain_size: prover.domain_size,
-            poly_count: prover.poly_count,
-        };
+    fn compute_commitment_batch(
+        &self,
+        data_list: &[Vec<u8>],
+        num_queries: usize,
+    ) -> CommitmentResult<H, E> {
+        let (channel, prover) = self.prepare_prover_state_batch(data_list, num_queries)?;
+        Ok(prover_commitment(channel, prover))
+    }
+
+    /// Same as `commitment_batch`, additionally binding blob `i` to application `app_ids[i]`.
+    /// The commitment carries the root of a Merkle tree over the hashes of the app ids, against
+    /// which the openings of `FridaProver::open_app` are checked.
+    pub fn commitment_batch_with_app_ids(
+        &self,
+        data_list: &[Vec<u8>],
+        app_ids: &[Vec<u8>],
+        num_queries: usize,
+    ) -> CommitmentResult<H, E> {
+        if app_ids.len() != data_list.len() {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+        let leaves = app_ids.iter().map(|id| H::hash(id)).collect::<Vec<_>>();
+        if (1..leaves.len()).any(|i| leaves[..i].contains(&leaves[i])) {
+            return Err(FridaError::DuplicateAppId);
+        }
 
-        let base_positions: Vec<usize> = channel.draw_query_positions();
+        let (mut commitment, mut prover, base_positions) =
+            self.commitment_batch(data_list, num_queries)?;
+        commitment.app_ids_root = Some(disclosure::app_ids_root::<H>(&leaves));
+        prover.app_ids = leaves;
 
         Ok((commitment, prover, base_positions))
     }
 
-    /// It calculates the domain size and generates the initial evaluations.
-    fn prepare_prover_state(&self, data: &[u8], num_queries: usize) -> ProverStateResult<E, H> {
-        if num_queries == 0 {
-            return Err(FridaError::BadNumQueries(num_queries));
+    /// Commits to blobs of heterogeneous sizes without padding every one of them to the domain of
+    /// the largest: blobs are grouped into buckets of blobs needing the same domain, each bucket
+    /// is committed on its own, and the commitments of the buckets are bound under a super root;
+    /// see `BucketedCommitment`. Returns one prover per bucket, in the order of the buckets.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BadDataLength` if `data_list` is empty, and the errors of
+    /// `commitment` or `commitment_batch` for the first bucket which cannot be committed.
+    pub fn commitment_bucketed(
+        &self,
+        data_list: &[Vec<u8>],
+        num_queries: usize,
+    ) -> Result<(BucketedCommitment<H>, Vec<FridaProver<E, H>>), FridaError> {
+        if data_list.is_empty() {
+            return Err(FridaError::BadDataLength());
         }
-
         let blowup_factor = self.options.blowup_factor();
-        let encoded_element_count = encoded_data_element_count::<E>(data.len());
+        let element_counts = data_list
+            .iter()
+            .map(|data| self.encoding.element_count::<E>(data.len()))
+            .collect::<Vec<_>>();
+        let domain_sizes = data_list
+            .iter()
+            .map(|data| self.encoding.domain_size::<E>(data.len(), blowup_factor))
+            .collect::<Vec<_>>();
 
-        let domain_size = usize::max(
-            encoded_element_count.next_power_of_two() * blowup_factor,
-            constants::MIN_DOMAIN_SIZE,
-        );
+        let mut buckets = Vec::new();
+        let mut provers = Vec::new();
+        for blobs in bucketed::size_classes(&domain_sizes).into_values() {
+            let (commitment, prover, _) = match blobs[..] {
+                [blob] => self.commitment(&data_list[blob], num_queries)?,
+                _ => {
+                    let bucket_data = blobs
+                        .iter()
+                        .map(|&blob| data_list[blob].clone())
+                        .collect::<Vec<_>>();
+                    self.commitment_batch(&bucket_data, num_queries)?
+                }
+            };
+            buckets.push(CommitmentBucket { blobs, commitment });
+            provers.push(prover);
+        }
 
-        if domain_size > constants::MAX_DOMAIN_SIZE {
-            return Err(FridaError::DomainSizeTooBig(domain_size));
+        let padding = PaddingReport {
+            required_evaluations: element_counts.iter().sum::<usize>() * blowup_factor,
+            committed_evaluations: buckets
+                .iter()
+                .map(|bucket| bucket.commitment.poly_count * bucket.commitment.domain_size)
+                .sum(),
+            batched_evaluations: data_list.len() * domain_sizes.iter().max().unwrap(),
+        };
+        let commitment = BucketedCommitment {
+            super_root: bucketed::super_root(&buckets),
+            buckets,
+            padding,
+        };
+        Ok((commitment, provers))
+    }
+
+    /// Commits to `data` split into shards by `core::data::encoding_plan`, each shard over its own
+    /// domain, so that data barely overflowing a power-of-two domain is not committed over twice
+    /// the domain it needs. Returns the commitment and prover of every shard, in the order of the
+    /// data; a single one if the encoding options of this builder do not enable sharding or the
+    /// data gains nothing from it. `core::data::recover_sharded_data` reassembles the data from
+    /// samples of every shard.
+    ///
+    /// # Errors
+    /// Returns the errors of `commitment` for the first shard which cannot be committed.
+    pub fn commitment_sharded(
+        &self,
+        data: &[u8],
+        num_queries: usize,
+    ) -> Result<Vec<(ProverCommitment<H>, FridaProver<E, H>)>, FridaError> {
+        let plan = encoding_plan::<E>(data.len(), self.options.blowup_factor(), &self.encoding);
+        plan.split(data)
+            .into_iter()
+            .map(|shard| {
+                let (commitment, prover, _) = self.commitment(shard, num_queries)?;
+                Ok((commitment, prover))
+            })
+            .collect()
+    }
+
+    /// Returns the commitment cached for `blobs`, or computes it with `commit` and caches it.
+    /// Without a commitment cache, just calls `commit`.
+    fn cached_commitment(
+        &self,
+        blobs: &[&[u8]],
+        batched: bool,
+        num_queries: usize,
+        commit: impl FnOnce() -> CommitmentResult<H, E>,
+    ) -> CommitmentResult<H, E> {
+        let Some(cache) = &self.commitment_cache else {
+            return commit();
+        };
+        let key = self.cache_key(blobs, batched, num_queries);
+        if let Some(cached) = cache.get(&key) {
+            let commitment = ProverCommitment::read_from_bytes(&cached.commitment)
+                .map_err(FridaError::DeserializationError)?;
+            let prover = FridaProver::read_from_bytes(&cached.prover)
+                .map_err(FridaError::DeserializationError)?;
+            return Ok((commitment, prover, cached.positions));
         }
 
-        let evaluations = build_evaluations_from_data(data, domain_size, blowup_factor)?;
+        let (commitment, prover, positions) = commit()?;
+        let cached = CachedCommitment {
+            commitment: commitment.to_bytes(),
+            prover: prover.to_bytes(),
+            positions: positions.clone(),
+        };
+        cache.insert(key, cached);
+        Ok((commitment, prover, positions))
+    }
 
-        if num_queries >= domain_size {
-            return Err(FridaError::BadNumQueries(num_queries));
+    /// Hashes `blobs` along with every setting of this builder which affects their commitment.
+    fn cache_key(&self, blobs: &[&[u8]], batched: bool, num_queries: usize) -> H::Digest {
+        let mut bytes = Vec::new();
+        self.options.blowup_factor().write_into(&mut bytes);
+        self.options.folding_factor().write_into(&mut bytes);
+        self.options.remainder_max_degree().write_into(&mut bytes);
+        self.min_batch_security.unwrap_or(0).write_into(&mut bytes);
+        self.folding_schedule.write_into(&mut bytes);
+        bytes.extend_from_slice(&[
+            self.layer_storage as u8,
+            self.data_checksums as u8,
+            self.encoding.packing as u8,
+            self.protocol as u8,
+            self.ood_check as u8,
+            self.direct_remainder as u8,
+            batched as u8,
+        ]);
+        self.batch_leaf_encoding.write_into(&mut bytes);
+        self.num_partitions.write_into(&mut bytes);
+        self.data_root_chunk_size
+            .unwrap_or(0)
+            .write_into(&mut bytes);
+        match &self.dispersal_hint {
+            Some(hint) => {
+                bytes.push(1);
+                hint.write_into(&mut bytes);
+            }
+            None => bytes.push(0),
         }
-        if self.options.num_fri_layers(domain_size) == 0 {
-            return Err(FridaError::NotEnoughDataPoints());
+        match &self.commit_profile {
+            Some(profile) => {
+                bytes.push(1);
+                profile.write_into(&mut bytes);
+            }
+            None => bytes.push(0),
+        }
+        num_queries.write_into(&mut bytes);
+        blobs.len().write_into(&mut bytes);
+        for blob in blobs {
+            bytes.extend_from_slice(&H::hash(blob).as_bytes());
         }
+        H::hash(&bytes)
+    }
 
-        let mut channel = Channel::<E, H>::new(domain_size, num_queries);
-        let prover = self.build_layers(&mut channel, evaluations, 1, None);
-        Ok((channel, prover))
+    /// It calculates the domain size and generates the initial evaluations.
+    fn prepare_prover_state(
+        &self,
+        data: &[u8],
+        num_queries: usize,
+    ) -> ProverStateResult<E, H, HHst> {
+        let encoding = self.encode(data, num_queries)?;
+        self.prepare_prover_state_from_encoding(encoding)
     }
 
     fn prepare_prover_state_batch(
         &self,
         data_list: &[Vec<u8>],
         num_queries: usize,
-    ) -> ProverStateResult<E, H> {
+    ) -> ProverStateResult<E, H, HHst> {
+        let encoding = self.encode_batch(data_list, num_queries)?;
+        self.prepare_prover_state_from_encoding(encoding)
+    }
+
+    /// Reed-Solomon encodes `data` over the domain of its commitment without building any FRI
+    /// layer; see `Encoding`.
+    ///
+    /// # Errors
+    /// Returns the errors of `commitment` for the parameters of this builder, `num_queries` and
+    /// the length of `data`.
+    pub fn encode(&self, data: &[u8], num_queries: usize) -> Result<Encoding<E, H>, FridaError> {
+        let evaluations = self.encode_evaluations(data, num_queries)?;
+        Ok(Encoding {
+            domain_size: evaluations.len(),
+            evaluations: vec![evaluations],
+            num_queries,
+            blowup_factor: self.options.blowup_factor(),
+            data_checksums: self.checksums(&[data]),
+            data_roots: self.data_roots(&[data])?,
+        })
+    }
+
+    /// Reed-Solomon encodes every blob of `data_list` over the common domain of their batched
+    /// commitment without building any FRI layer; see `Encoding`.
+    ///
+    /// # Errors
+    /// Returns the errors of `commitment_batch` for the parameters of this builder, `num_queries`
+    /// and the blobs of `data_list`.
+    pub fn encode_batch(
+        &self,
+        data_list: &[Vec<u8>],
+        num_queries: usize,
+    ) -> Result<Encoding<E, H>, FridaError> {
         #[cfg(feature = "bench")]
         unsafe {
             bench::TIMER = Some(Instant::now());
         }
 
+        if self.protocol != Protocol::Fri {
+            return Err(FridaError::ProtocolMismatch);
+        }
         if num_queries == 0 {
             return Err(FridaError::BadNumQueries(num_queries));
         }
+        validate_folding_schedule(&self.options, &self.folding_schedule)?;
 
         let poly_count = data_list.len();
         if poly_count <= 1 {
@@ -166,7 +803,7 @@ where
 
         let blowup_factor = self.options.blowup_factor();
 
-        let max_data_len = encoded_data_element_count::<E>(
+        let max_data_len = self.encoding.element_count::<E>(
             data_list
                 .iter()
                 .map(|data| data.len())
@@ -179,26 +816,23 @@ where
             constants::MIN_DOMAIN_SIZE,
         );
 
-        let folding_factor = self.options.folding_factor();
-
         if domain_size > constants::MAX_DOMAIN_SIZE {
             return Err(FridaError::DomainSizeTooBig(domain_size));
         }
         if num_queries >= domain_size {
             return Err(FridaError::BadNumQueries(num_queries));
         }
-        if self.options.num_fri_layers(domain_size) == 0 {
+        if self.folding_factors(domain_size).is_empty() {
             // Verification currently cannot work without FRI layers
             return Err(FridaError::NotEnoughDataPoints());
         }
+        self.check_batch_security(domain_size, poly_count, num_queries)?;
 
-        let evaluations = batch_data_to_evaluations::<E>(
-            data_list,
-            poly_count,
-            domain_size,
-            blowup_factor,
-            folding_factor,
-        )?;
+        let evaluations = data_list
+            .iter()
+            .enumerate()
+            .map(|(blob, data)| self.evaluate_data(data, domain_size, blob, poly_count))
+            .collect::<Result<Vec<_>, _>>()?;
 
         #[cfg(feature = "bench")]
         unsafe {
@@ -207,9 +841,205 @@ where
             bench::TIMER = Some(Instant::now());
         }
 
-        let mut channel = Channel::<E, H>::new(domain_size, num_queries);
-        let prover = self.build_layers_batched(&mut channel, evaluations, domain_size)?;
+        let blobs = data_list.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        Ok(Encoding {
+            evaluations,
+            domain_size,
+            num_queries,
+            blowup_factor,
+            data_checksums: self.checksums(&blobs),
+            data_roots: self.data_roots(&blobs)?,
+        })
+    }
+
+    /// Builds the FRI layers over `encoding` and commits to them, like `commitment` or
+    /// `commitment_batch` would for the encoded data. The checksums of the data are those
+    /// computed by the builder which made the encoding, and the commitment is not cached.
+    ///
+    /// `encoding` is consumed, as its evaluations become the first layer of the prover, so an
+    /// encoding committed to several times is cloned for every commitment but the last.
+    ///
+    /// # Errors
+    /// Returns `FridaError::EncodingMismatch` if `encoding` was made with a different blowup
+    /// factor, `FridaError::ProtocolMismatch` if this builder is not configured for FRI, and the
+    /// errors of `commitment` or `commitment_batch` raised while building the layers.
+    pub fn commit(&self, encoding: Encoding<E, H>) -> CommitmentResult<H, E> {
+        if self.protocol != Protocol::Fri {
+            return Err(FridaError::ProtocolMismatch);
+        }
+        if encoding.blowup_factor != self.options.blowup_factor() {
+            return Err(FridaError::EncodingMismatch(
+                encoding.blowup_factor,
+                self.options.blowup_factor(),
+            ));
+        }
+        validate_folding_schedule(&self.options, &self.folding_schedule)?;
+
+        let (channel, prover) = self.prepare_prover_state_from_encoding(encoding)?;
+        Ok(prover_commitment(channel, prover))
+    }
+
+    /// Builds the FRI layers over `encoding`, batching them if it holds several polynomials.
+    fn prepare_prover_state_from_encoding(
+        &self,
+        encoding: Encoding<E, H>,
+    ) -> ProverStateResult<E, H, HHst> {
+        let Encoding {
+            evaluations,
+            domain_size,
+            num_queries,
+            data_checksums,
+            data_roots,
+            ..
+        } = encoding;
+        let poly_count = evaluations.len();
+
+        let (channel, mut prover) = if poly_count == 1 {
+            let evaluations = evaluations.into_iter().next().expect("one polynomial");
+            self.prepare_prover_state_from_evaluations(evaluations, num_queries)?
+        } else {
+            let Some(&folding_factor) = self.folding_factors(domain_size).first() else {
+                return Err(FridaError::NotEnoughDataPoints());
+            };
+            self.check_batch_security(domain_size, poly_count, num_queries)?;
+            let evaluations = interleave_evaluations(&evaluations, domain_size, folding_factor);
+
+            let mut channel = Channel::<E, H, HHst>::new(domain_size, num_queries);
+            let prover = self.build_layers_batched(&mut channel, evaluations, domain_size)?;
+            (channel, prover)
+        };
+        prover.data_checksums = data_checksums;
+        prover.data_roots = data_roots;
+        Ok((channel, prover))
+    }
+
+    /// Checks the security level of a batch of `poly_count` polynomials against the minimum set
+    /// with `with_min_batch_security`, if any.
+    fn check_batch_security(
+        &self,
+        domain_size: usize,
+        poly_count: usize,
+        num_queries: usize,
+    ) -> Result<(), FridaError> {
+        if let Some(required) = self.min_batch_security {
+            let level =
+                batch_security_level::<E>(domain_size, &self.options, poly_count, num_queries);
+            if level < required {
+                return Err(FridaError::InsufficientSecurity(level, required));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the checksums of `blobs` if data checksums are enabled, and none otherwise.
+    fn checksums(&self, blobs: &[&[u8]]) -> Vec<H::Digest> {
+        if !self.data_checksums {
+            return Vec::new();
+        }
+        blobs.iter().map(|data| H::hash(data)).collect()
+    }
+
+    /// Returns the data roots of `blobs` if enabled with `with_data_roots`, and none otherwise.
+    fn data_roots(&self, blobs: &[&[u8]]) -> Result<Option<DataRoots<H>>, FridaError> {
+        self.data_root_chunk_size
+            .map(|chunk_size| DataRoots::build(blobs, chunk_size))
+            .transpose()
+    }
+
+    /// Validates the parameters for a single blob and Reed-Solomon encodes it over the domain.
+    pub(crate) fn encode_evaluations(
+        &self,
+        data: &[u8],
+        num_queries: usize,
+    ) -> Result<Vec<E>, FridaError> {
+        if self.protocol != Protocol::Fri {
+            return Err(FridaError::ProtocolMismatch);
+        }
+        validate_folding_schedule(&self.options, &self.folding_schedule)?;
+
+        let evaluations = self.reed_solomon_encode(data, num_queries)?;
+        if self.folding_factors(evaluations.len()).is_empty() && !self.direct_remainder {
+            return Err(FridaError::NotEnoughDataPoints());
+        }
+        Ok(evaluations)
+    }
+
+    /// Checks the number of queries and Reed-Solomon encodes `data` over the smallest domain
+    /// that holds it.
+    fn reed_solomon_encode(&self, data: &[u8], num_queries: usize) -> Result<Vec<E>, FridaError> {
+        let domain_size = self.encoding_domain_size(data.len(), num_queries)?;
+        self.evaluate_data(data, domain_size, 0, 1)
+    }
+
+    /// Same as `core::data::build_evaluations_with_encoding` with the options of this builder,
+    /// reporting the encoding and FFT phases of blob `blob` of `num_blobs`.
+    fn evaluate_data(
+        &self,
+        data: &[u8],
+        domain_size: usize,
+        blob: usize,
+        num_blobs: usize,
+    ) -> Result<Vec<E>, FridaError> {
+        self.start_phase(Phase::Encoding { blob, num_blobs })?;
+        let blowup_factor = self.options.blowup_factor();
+        let coefficients =
+            build_coefficients_with_encoding(data, domain_size, blowup_factor, &self.encoding)?;
+
+        self.start_phase(Phase::Fft { blob, num_blobs })?;
+        Ok(evaluate_coefficients(coefficients, domain_size))
+    }
+
+    /// Fails with `FridaError::Cancelled` if the cancellation token of this builder was
+    /// cancelled, and reports `phase` to the progress sink, if any, otherwise.
+    fn start_phase(&self, phase: Phase) -> Result<(), FridaError> {
+        if let Some(token) = &self.cancellation {
+            token.check()?;
+        }
+        if let Some(sink) = &self.progress_sink {
+            sink.on_phase(phase);
+        }
+        Ok(())
+    }
+
+    /// Checks the number of queries and returns the smallest domain that holds `data_len` bytes.
+    pub(super) fn encoding_domain_size(
+        &self,
+        data_len: usize,
+        num_queries: usize,
+    ) -> Result<usize, FridaError> {
+        if num_queries == 0 {
+            return Err(FridaError::BadNumQueries(num_queries));
+        }
+
+        let domain_size = self
+            .encoding
+            .domain_size::<E>(data_len, self.options.blowup_factor());
+        if domain_size > constants::MAX_DOMAIN_SIZE {
+            return Err(FridaError::DomainSizeTooBig(domain_size));
+        }
+        if num_queries >= domain_size {
+            return Err(FridaError::BadNumQueries(num_queries));
+        }
+
+        Ok(domain_size)
+    }
 
+    /// Builds the FRI layers over evaluations produced by `encode_evaluations`.
+    pub(crate) fn prepare_prover_state_from_evaluations(
+        &self,
+        evaluations: Vec<E>,
+        num_queries: usize,
+    ) -> ProverStateResult<E, H, HHst> {
+        let domain_size = evaluations.len();
+        let mut channel = Channel::<E, H, HHst>::new(domain_size, num_queries);
+        let prover = self.build_layers(
+            &mut channel,
+            evaluations,
+            domain_size,
+            1,
+            Vec::new(),
+            Vec::new(),
+        )?;
         Ok((channel, prover))
     }
 
@@ -217,10 +1047,30 @@ where
     pub fn build_commitment(
         &self,
         prover: &FridaProver<E, H>,
-        mut channel: Channel<E, H>,
+        mut channel: Channel<E, H, HHst>,
+    ) -> Result<Commitment<H>, FridaError> {
+        self.finish_commitment(prover, &mut channel)
+    }
+
+    /// Draws the query positions of the commitment from `channel` and opens them.
+    fn finish_commitment(
+        &self,
+        prover: &FridaProver<E, H>,
+        channel: &mut Channel<E, H, HHst>,
     ) -> Result<Commitment<H>, FridaError> {
         let query_positions = channel.draw_query_positions();
-        let proof = prover.open(&query_positions);
+        self.open_commitment(prover, channel, &query_positions)
+    }
+
+    /// Opens `query_positions`, drawn from `channel`, as the proof of the commitment.
+    pub(super) fn open_commitment(
+        &self,
+        prover: &FridaProver<E, H>,
+        channel: &Channel<E, H, HHst>,
+        query_positions: &[usize],
+    ) -> Result<Commitment<H>, FridaError> {
+        self.start_phase(Phase::ProofBuilding)?;
+        let proof = prover.open_cancellable(query_positions, self.cancellation.as_ref())?;
 
         #[cfg(feature = "bench")]
         unsafe {
@@ -231,145 +1081,234 @@ where
         let num_queries = channel.num_queries;
 
         let commitment = Commitment {
-            roots: channel.commitments,
+            roots: channel.commitments.clone(),
             proof,
             domain_size: prover.domain_size,
             num_queries,
             poly_count: prover.poly_count,
+            ood_evaluation: ood_evaluation_bytes(prover.ood_sample.as_ref()),
+            batch_leaf_encoding: prover.batch_leaf_encoding,
+            num_partitions: prover.num_partitions,
+            data_roots: prover.data_roots.clone(),
+            profile: prover.profile,
         };
 
         Ok(commitment)
     }
 
-    fn build_layers(
+    /// Builds the FRI layers over `evaluations`, which are the first layer unless `first_layers`
+    /// are given: these leading layers are then already committed to, along with their folding
+    /// randomness, and `evaluations` are the folding of the last one. The first layer is batched
+    /// with the coefficients `xi` if `poly_count` is more than one.
+    pub(super) fn build_layers(
         &self,
-        channel: &mut Channel<E, H>,
+        channel: &mut Channel<E, H, HHst>,
         evaluations: Vec<E>,
+        domain_size: usize,
         poly_count: usize,
-        batch_layer: Option<FridaLayer<E, H>>,
-    ) -> FridaProver<E, H> {
-        let is_batched = batch_layer.is_some();
-        assert!(!is_batched && poly_count == 1 || is_batched && poly_count > 1);
+        first_layers: Vec<(ProverLayer<E, H>, E)>,
+        xi: Vec<E>,
+    ) -> Result<FridaProver<E, H>, FridaError> {
+        assert!(poly_count == 1 || !first_layers.is_empty());
+        let folding_factors = self.folding_factors(domain_size);
+        self.check_num_partitions(domain_size, &folding_factors)?;
 
-        // reduce the degree by folding_factor at each iteration until the remaining polynomial
+        // reduce the degree by the folding factor of each layer until the remaining polynomial
         // has small enough degree
         let mut evaluations = evaluations;
-        let domain_size = if is_batched {
-            evaluations.len() * self.options.folding_factor()
-        } else {
-            evaluations.len()
-        };
-
-        let num_fri_layers = self.options.num_fri_layers(domain_size);
+        let num_fri_layers = folding_factors.len();
         let mut layers = Vec::with_capacity(num_fri_layers);
-        if let Some(batch_layer) = batch_layer {
-            layers.push(batch_layer);
-        }
-        let start = if is_batched { 1 } else { 0 };
-        for _ in start..num_fri_layers {
-            let (new_evaluations, frida_layer) = match self.options.folding_factor() {
-                2 => self.build_layer::<2>(channel, &evaluations),
-                4 => self.build_layer::<4>(channel, &evaluations),
-                8 => self.build_layer::<8>(channel, &evaluations),
-                16 => self.build_layer::<16>(channel, &evaluations),
-                _ => unimplemented!(
-                    "folding factor {} is not supported",
-                    self.options.folding_factor()
-                ),
+        let mut alphas = Vec::with_capacity(num_fri_layers);
+        let start = first_layers.len();
+        for (layer, alpha) in first_layers {
+            layers.push(layer);
+            alphas.push(alpha);
+        }
+        let mut ood_sample = None;
+        for (depth, &folding_factor) in folding_factors.iter().enumerate().skip(start) {
+            self.start_phase(Phase::LayerHashing {
+                layer: depth,
+                num_layers: num_fri_layers,
+            })?;
+            let ood_check = depth == 0 && self.ood_check;
+            let (new_evaluations, frida_layer, alpha, ood) = match folding_factor {
+                2 => self.build_layer::<2>(channel, &evaluations, ood_check)?,
+                4 => self.build_layer::<4>(channel, &evaluations, ood_check)?,
+                8 => self.build_layer::<8>(channel, &evaluations, ood_check)?,
+                16 => self.build_layer::<16>(channel, &evaluations, ood_check)?,
+                _ => return Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
             };
-            layers.push(frida_layer);
+            ood_sample = ood_sample.or(ood);
+            layers.push(ProverLayer::Full(frida_layer));
+            alphas.push(alpha);
             evaluations = new_evaluations;
         }
 
         let remainder_poly = self.build_remainder(channel, &mut evaluations);
 
-        FridaProver {
+        // inner layers can be recomputed from the first one and the folding randomness
+        let lazy = match self.layer_storage {
+            LayerStorage::Full => None,
+            LayerStorage::Lazy => {
+                for layer in layers.iter_mut().skip(1) {
+                    *layer = ProverLayer::Lazy(layer.root());
+                }
+                Some(LazyLayers {
+                    alphas,
+                    xi,
+                    domain_offset: self.options.domain_offset(),
+                })
+            }
+        };
+
+        Ok(FridaProver {
             layers,
             poly_count,
             remainder_poly,
             domain_size,
-            folding_factor: self.options.folding_factor(),
+            folding_factors,
+            lazy,
+            ood_sample,
+            data_checksums: Vec::new(),
+            data_roots: None,
+            app_ids: Vec::new(),
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            dispersal_hint: self.dispersal_hint,
+            profile: self.commit_profile,
+            num_partitions: self.num_partitions,
+        })
+    }
+
+    /// Checks that the smallest layer over `domain_size`, folded by `folding_factors`, has at
+    /// least one row per partition.
+    fn check_num_partitions(
+        &self,
+        domain_size: usize,
+        folding_factors: &[usize],
+    ) -> Result<(), FridaError> {
+        let min_rows = domain_size / folding_factors.iter().product::<usize>();
+        if self.num_partitions > min_rows {
+            return Err(FridaError::TooManyPartitions(self.num_partitions, min_rows));
         }
+        Ok(())
     }
 
     fn build_layers_batched(
         &self,
-        channel: &mut Channel<E, H>,
+        channel: &mut Channel<E, H, HHst>,
         evaluations: Vec<E>,
         domain_size: usize,
     ) -> Result<FridaProver<E, H>, FridaError> {
         let poly_count = evaluations.len() / domain_size;
-        let folding_factor = self.options.folding_factor();
+        let folding_factors = self.folding_factors(domain_size);
+        self.check_num_partitions(domain_size, &folding_factors)?;
+        let folding_factor = folding_factors[0];
         let bucket_count = domain_size / folding_factor;
         let bucket_size = poly_count * folding_factor;
 
-        let mut hashed_evaluations: Vec<H::Digest> = unsafe { uninit_vector(bucket_count) };
-        iter_mut!(hashed_evaluations, 1024)
-            .enumerate()
-            .for_each(|(i, r)| {
-                *r = H::hash_elements(&evaluations[i * bucket_size..i * bucket_size + bucket_size]);
+        self.start_phase(Phase::LayerHashing {
+            layer: 0,
+            num_layers: folding_factors.len(),
+        })?;
+        let encoding = self.batch_leaf_encoding;
+        let mut hashed_evaluations: Vec<H::Digest> = output_vector(bucket_count);
+        self.parallelism
+            .for_each_row_mut(&mut hashed_evaluations, bucket_size, |i, r| {
+                let bucket = &evaluations[i * bucket_size..i * bucket_size + bucket_size];
+                *r = encoding.hash_leaf::<E, H>(bucket, poly_count);
             });
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
+        let hashed_evaluations = partition_leaves(hashed_evaluations, self.num_partitions);
+        let evaluation_tree = MerkleTree::<H>::new(hashed_evaluations).map_err(merkle_error)?;
         channel.commit_fri_layer(*evaluation_tree.root());
 
         let xi = channel.draw_xi(poly_count)?;
+        let combined = combine_batch(&evaluations, poly_count, &xi, &self.parallelism);
+        let ood_sample = self
+            .ood_check
+            .then(|| {
+                // the combined rows hold positions i + j * bucket_count of the domain
+                assert_eq!(combined.len(), domain_size);
+                let mut first_layer: Vec<E> = output_vector(domain_size);
+                for (index, &value) in combined.iter().enumerate() {
+                    first_layer[index % folding_factor * bucket_count + index / folding_factor] =
+                        value;
+                }
+                self.draw_ood_sample(channel, &first_layer)
+            })
+            .transpose()?;
         let alpha = channel.draw_fri_alpha();
+        let offset = self.options.domain_offset();
+        let ood = ood_sample.as_ref();
         let second_layer = match folding_factor {
-            2 => apply_drp_batched::<_, 2>(&evaluations, poly_count, &self.options, xi, alpha),
-            4 => apply_drp_batched::<_, 4>(&evaluations, poly_count, &self.options, xi, alpha),
-            8 => apply_drp_batched::<_, 8>(&evaluations, poly_count, &self.options, xi, alpha),
-            16 => apply_drp_batched::<_, 16>(&evaluations, poly_count, &self.options, xi, alpha),
-            _ => unimplemented!("folding factor {} is not supported", folding_factor),
+            2 => fold_first_layer::<_, 2>(&combined, offset, ood, alpha),
+            4 => fold_first_layer::<_, 4>(&combined, offset, ood, alpha),
+            8 => fold_first_layer::<_, 8>(&combined, offset, ood, alpha),
+            16 => fold_first_layer::<_, 16>(&combined, offset, ood, alpha),
+            _ => return Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
         };
 
-        Ok(self.build_layers(
+        let mut prover = self.build_layers(
             channel,
             second_layer,
+            domain_size,
             poly_count,
-            Some(FridaLayer {
-                tree: evaluation_tree,
-                evaluations,
-            }),
-        ))
+            vec![(
+                ProverLayer::Full(FridaLayer {
+                    tree: evaluation_tree,
+                    evaluations,
+                }),
+                alpha,
+            )],
+            xi,
+        )?;
+        prover.ood_sample = ood_sample;
+        prover.batch_leaf_encoding = encoding;
+        Ok(prover)
     }
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
-    /// alpha from the channel and use it to perform degree-respecting projection.
+    /// alpha from the channel and use it to perform degree-respecting projection. With
+    /// `ood_check`, an out-of-domain sample is drawn before alpha, and the DEEP quotient of the
+    /// layer is folded in its place.
     fn build_layer<const N: usize>(
         &self,
-        channel: &mut Channel<E, H>,
+        channel: &mut Channel<E, H, HHst>,
         evaluations: &[E],
-    ) -> (Vec<E>, FridaLayer<E, H>) {
-        // commit to the evaluations at the current layer; we do this by first transposing the
-        // evaluations into a matrix of N columns, and then building a Merkle tree from the
-        // rows of this matrix; we do this so that we could de-commit to N values with a single
-        // Merkle authentication path.
-        let transposed_evaluations = transpose_slice(evaluations);
-        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
-
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
+        ood_check: bool,
+    ) -> Result<(Vec<E>, FridaLayer<E, H>, E, Option<OodSample<E>>), FridaError> {
+        let layer = commit_partitioned_layer::<E, H, N>(evaluations, self.num_partitions)?;
+        channel.commit_fri_layer(*layer.tree.root());
+        let ood_sample = ood_check
+            .then(|| self.draw_ood_sample(channel, evaluations))
+            .transpose()?;
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
         let alpha = channel.draw_fri_alpha();
-        let evaluations =
-            folding::apply_drp(&transposed_evaluations, self.options.domain_offset(), alpha);
-        (
-            evaluations,
-            FridaLayer {
-                tree: evaluation_tree,
-                evaluations: flatten_vector_elements(transposed_evaluations),
-            },
-        )
+        let evaluations = fold_first_layer::<E, N>(
+            &layer.evaluations,
+            self.options.domain_offset(),
+            ood_sample.as_ref(),
+            alpha,
+        );
+        Ok((evaluations, layer, alpha, ood_sample))
+    }
+
+    /// Draws an out-of-domain sample of the committed first layer, given by its `evaluations` in
+    /// natural order; see `core::deep::OodSample`.
+    fn draw_ood_sample(
+        &self,
+        channel: &mut Channel<E, H, HHst>,
+        evaluations: &[E],
+    ) -> Result<OodSample<E>, FridaError> {
+        let offset = self.options.domain_offset();
+        channel.draw_ood_sample(|point| deep::evaluate_out_of_domain(evaluations, offset, point))
     }
 
     /// Creates remainder polynomial in coefficient form from a vector of `evaluations` over a domain.
     fn build_remainder(
         &self,
-        channel: &mut Channel<E, H>,
+        channel: &mut Channel<E, H, HHst>,
         evaluations: &mut [E],
     ) -> FridaRemainder<E> {
         let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
@@ -385,36 +1324,136 @@ where
     #[cfg(test)]
     pub fn test_build_layers(
         &self,
-        channel: &mut Channel<E, H>,
+        channel: &mut Channel<E, H, HHst>,
         evaluations: Vec<E>,
-    ) -> FridaProver<E, H> {
-        self.build_layers(channel, evaluations, 1, None)
+    ) -> Result<FridaProver<E, H>, FridaError> {
+        let domain_size = evaluations.len();
+        self.build_layers(channel, evaluations, domain_size, 1, Vec::new(), Vec::new())
     }
 }
 
-fn apply_drp_batched<E: FieldElement, const N: usize>(
+/// Returns the commitment of `prover`, along with the query positions drawn from `channel`.
+fn prover_commitment<E, H, HHst>(
+    mut channel: Channel<E, H, HHst>,
+    prover: FridaProver<E, H>,
+) -> CommitmentResult<H, E>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    let commitment = ProverCommitment {
+        roots: channel.commitments.clone(),
+        domain_size: prover.domain_size,
+        poly_count: prover.poly_count,
+        data_checksums: prover.data_checksums.clone(),
+        app_ids_root: None,
+        ood_evaluation: ood_evaluation_bytes(prover.ood_sample.as_ref()),
+        batch_leaf_encoding: prover.batch_leaf_encoding,
+        num_partitions: prover.num_partitions,
+        dispersal_hint: prover.dispersal_hint,
+        data_roots: prover.data_roots.clone(),
+        profile: prover.profile,
+    };
+
+    let base_positions: Vec<usize> = channel.draw_query_positions();
+
+    Ok((commitment, prover, base_positions))
+}
+
+/// Commits to the evaluations of a FRI layer; we do this by first transposing the evaluations
+/// into a matrix of N columns, and then building a Merkle tree from the rows of this matrix; we do
+/// this so that we could de-commit to N values with a single Merkle authentication path.
+pub(super) fn commit_layer<E, H, const N: usize>(
     evaluations: &[E],
-    poly_count: usize,
-    options: &FriOptions,
-    xi: Vec<E>,
+) -> Result<FridaLayer<E, H>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    commit_partitioned_layer::<E, H, N>(evaluations, 1)
+}
+
+/// Same as `commit_layer`, with the rows of the layer split into `num_partitions` partitions; see
+/// `partition_leaves`. Evaluations are kept in their natural row order.
+pub(super) fn commit_partitioned_layer<E, H, const N: usize>(
+    evaluations: &[E],
+    num_partitions: usize,
+) -> Result<FridaLayer<E, H>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let transposed_evaluations = transpose_slice::<E, N>(evaluations);
+    let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
+    let hashed_evaluations = partition_leaves(hashed_evaluations, num_partitions);
+    let tree = MerkleTree::<H>::new(hashed_evaluations).map_err(merkle_error)?;
+
+    Ok(FridaLayer {
+        tree,
+        evaluations: flatten_vector_elements(transposed_evaluations),
+    })
+}
+
+/// Reorders the hashed rows of a layer into the leaves of its Merkle tree, with row `i` placed in
+/// partition `i % num_partitions`, at leaf `i / num_partitions` of the partition; this is the
+/// layout expected by `winter_fri::utils::map_positions_to_indexes`.
+pub(super) fn partition_leaves<T: Copy>(rows: Vec<T>, num_partitions: usize) -> Vec<T> {
+    if num_partitions == 1 {
+        return rows;
+    }
+    let partition_size = rows.len() / num_partitions;
+    let mut leaves = rows.clone();
+    for (row, value) in rows.into_iter().enumerate() {
+        leaves[row % num_partitions * partition_size + row / num_partitions] = value;
+    }
+    leaves
+}
+
+/// Folds the transposed evaluations of a committed FRI layer into the evaluations of the next one.
+pub(super) fn fold_layer<E: FieldElement, const N: usize>(
+    layer_evaluations: &[E],
+    domain_offset: E::BaseField,
     alpha: E,
 ) -> Vec<E> {
-    let domain_size = evaluations.len() / poly_count;
-    let bucket_count = domain_size / options.folding_factor();
-    let bucket_size = poly_count * N;
-
-    let mut final_eval: Vec<[E; N]> = vec![[E::default(); N]; bucket_count];
-    iter_mut!(final_eval, 1024).enumerate().for_each(|(i, b)| {
-        iter_mut!(b, 1024).enumerate().for_each(|(j, f)| {
-            let start = i * bucket_size + poly_count * j;
-            evaluations[start..start + poly_count]
-                .iter()
-                .enumerate()
-                .for_each(|(j, e)| {
-                    *f += *e * xi[j];
-                });
-        });
-    });
+    folding::apply_drp(
+        group_slice_elements::<E, N>(layer_evaluations),
+        domain_offset,
+        alpha,
+    )
+}
+
+/// Folds the transposed evaluations of the first layer, combined into a single polynomial, into
+/// the evaluations of the next layer, replacing them by those of their DEEP quotient first if
+/// `ood` is set.
+pub(super) fn fold_first_layer<E: FieldElement, const N: usize>(
+    layer_evaluations: &[E],
+    domain_offset: E::BaseField,
+    ood: Option<&OodSample<E>>,
+    alpha: E,
+) -> Vec<E> {
+    let Some(sample) = ood else {
+        return fold_layer::<E, N>(layer_evaluations, domain_offset, alpha);
+    };
+    let mut rows = group_slice_elements::<E, N>(layer_evaluations).to_vec();
+    sample.apply_to_rows(&mut rows, domain_offset);
+    folding::apply_drp(&rows, domain_offset, alpha)
+}
 
-    folding::apply_drp(&final_eval, options.domain_offset(), alpha)
+/// Combines the `poly_count` polynomials of a batched first layer with `xi`, keeping the
+/// transposed layout of its rows.
+pub(super) fn combine_batch<E: FieldElement>(
+    evaluations: &[E],
+    poly_count: usize,
+    xi: &[E],
+    parallelism: &ParallelOptions,
+) -> Vec<E> {
+    let mut combined: Vec<E> = output_vector(evaluations.len() / poly_count);
+    parallelism.for_each_row_mut(&mut combined, poly_count, |i, f| {
+        *f = evaluations[i * poly_count..(i + 1) * poly_count]
+            .iter()
+            .zip(xi)
+            .fold(E::ZERO, |acc, (&e, &x)| acc + e * x);
+    });
+    combined
 }
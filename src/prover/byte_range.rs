@@ -0,0 +1,104 @@
+use std::ops::Range;
+
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{proof::FridaProof, FridaProver};
+use crate::{
+    core::data::{byte_range_positions, decode_byte_range},
+    error::FridaError,
+};
+
+// BYTE RANGE PROOF
+// ================================================================================================
+
+/// An opening of the evaluations holding a range of bytes of a committed blob, starting at
+/// `offset`.
+///
+/// `evaluations` holds the values of every committed polynomial at the positions returned by
+/// `byte_range_positions`, of which the verifier decodes those of polynomial `blob`. The length of
+/// the range is given by the bytes it is verified against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteRangeProof<E: FieldElement> {
+    pub blob: usize,
+    pub offset: usize,
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+}
+
+impl<E: FieldElement> ByteRangeProof<E> {
+    /// Returns the evaluations of polynomial `blob`, one per opened position.
+    pub(crate) fn blob_evaluations(&self, poly_count: usize) -> Vec<E> {
+        self.evaluations
+            .iter()
+            .skip(self.blob)
+            .step_by(poly_count)
+            .copied()
+            .collect()
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens bytes `range` of blob `blob`, which is always 0 unless the commitment is batched.
+    /// `blowup_factor` must be the one the data was committed with.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ProofPolyCountMismatch` if `blob` is not one of the committed blobs,
+    /// and `FridaError::ByteRangeOutOfBounds` if the range extends past the end of the blob.
+    pub fn open_byte_range(
+        &self,
+        blob: usize,
+        range: Range<usize>,
+        blowup_factor: usize,
+    ) -> Result<ByteRangeProof<E>, FridaError> {
+        if blob >= self.poly_count {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+
+        // the length prefix is enough to check the range against the length of the blob
+        let prefix = self.get_evaluations(&[0])?;
+        if range.start > range.end
+            || decode_byte_range(&prefix[blob..blob + 1], &(range.end..range.end)).is_err()
+        {
+            return Err(FridaError::ByteRangeOutOfBounds(range.start, range.end));
+        }
+
+        let positions = byte_range_positions::<E>(&range, blowup_factor, self.domain_size)?;
+        Ok(ByteRangeProof {
+            blob,
+            offset: range.start,
+            evaluations: self.get_evaluations(&positions)?,
+            proof: self.open(&positions)?,
+        })
+    }
+}
+
+impl<E: FieldElement> Serializable for ByteRangeProof<E> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.blob.write_into(target);
+        self.offset.write_into(target);
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for ByteRangeProof<E> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blob = usize::read_from(source)?;
+        let offset = usize::read_from(source)?;
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(ByteRangeProof {
+            blob,
+            offset,
+            evaluations,
+            proof,
+        })
+    }
+}
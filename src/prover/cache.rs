@@ -0,0 +1,106 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use winter_crypto::Hasher;
+
+/// Serialized commitment cached by `CommitmentCache`, along with a snapshot of its prover and the
+/// positions drawn for it.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedCommitment {
+    pub commitment: Vec<u8>,
+    pub prover: Vec<u8>,
+    pub positions: Vec<usize>,
+}
+
+/// Counters of a `CommitmentCache`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitmentCacheMetrics {
+    /// Commitments currently cached.
+    pub entries: usize,
+    /// Commitments returned from the cache.
+    pub hits: u64,
+    /// Commitments computed because they were not cached.
+    pub misses: u64,
+}
+
+// COMMITMENT CACHE
+// ================================================================================================
+
+/// Least recently used commitments of a `FridaProverBuilder`, keyed by the hash of the committed
+/// data and of the configuration it was committed with; see
+/// `FridaProverBuilder::with_commitment_cache`.
+///
+/// Commitments and provers are cached as serialized snapshots, so that every hit hands out a
+/// prover of its own.
+#[derive(Debug)]
+pub struct CommitmentCache<H: Hasher> {
+    capacity: usize,
+    state: Mutex<State<H>>,
+}
+
+#[derive(Debug)]
+struct State<H: Hasher> {
+    /// Entries from the least to the most recently used.
+    entries: VecDeque<(H::Digest, CachedCommitment)>,
+    metrics: CommitmentCacheMetrics,
+}
+
+impl<H: Hasher> CommitmentCache<H> {
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub(crate) fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "commitment cache capacity must be greater than zero"
+        );
+        CommitmentCache {
+            capacity,
+            state: Mutex::new(State {
+                entries: VecDeque::with_capacity(capacity),
+                metrics: CommitmentCacheMetrics::default(),
+            }),
+        }
+    }
+
+    /// Maximum number of commitments cached.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn metrics(&self) -> CommitmentCacheMetrics {
+        let state = self.state.lock().unwrap();
+        CommitmentCacheMetrics {
+            entries: state.entries.len(),
+            ..state.metrics
+        }
+    }
+
+    /// Drops every cached commitment.
+    pub fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+    }
+
+    /// Returns the commitment cached under `key`, if any, and marks it as the most recently used.
+    pub(crate) fn get(&self, key: &H::Digest) -> Option<CachedCommitment> {
+        let mut state = self.state.lock().unwrap();
+        let Some(index) = state.entries.iter().position(|(k, _)| k == key) else {
+            state.metrics.misses += 1;
+            return None;
+        };
+        state.metrics.hits += 1;
+        let entry = state.entries.remove(index).expect("index is in bounds");
+        let cached = entry.1.clone();
+        state.entries.push_back(entry);
+        Some(cached)
+    }
+
+    /// Caches `commitment` under `key`, evicting the least recently used commitment if the cache
+    /// is full.
+    pub(crate) fn insert(&self, key: H::Digest, commitment: CachedCommitment) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.retain(|(k, _)| *k != key);
+        if state.entries.len() == self.capacity {
+            state.entries.pop_front();
+        }
+        state.entries.push_back((key, commitment));
+    }
+}
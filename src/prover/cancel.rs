@@ -0,0 +1,43 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::error::FridaError;
+
+// CANCELLATION
+// ================================================================================================
+
+/// Flag aborting the commitments of a builder, or an opening, set up with it, e.g. when the block
+/// proposal being committed to is superseded; see `FridaProverBuilder::with_cancellation` and
+/// `FridaProver::open_with_cancellation`.
+///
+/// Cancellation is cooperative: the flag is checked before the encoding of every blob, every FFT,
+/// the hashing of every FRI layer and the opening of every layer, so that the CPU is freed once
+/// the step in progress completes. Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every operation checking this token, or a clone of it; operations which already
+    /// completed are not affected.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns `FridaError::Cancelled` if this token was cancelled.
+    pub(crate) fn check(&self) -> Result<(), FridaError> {
+        if self.is_cancelled() {
+            return Err(FridaError::Cancelled);
+        }
+        Ok(())
+    }
+}
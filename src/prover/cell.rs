@@ -0,0 +1,112 @@
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{proof::FridaProof, FridaProver};
+use crate::error::FridaError;
+
+// CELL
+// ================================================================================================
+
+/// A single entry of a batched commitment viewed as a grid, with one row per committed polynomial
+/// and one column per position of the evaluation domain.
+///
+/// Every position of the first layer commits to the values of all rows at once, so verifying a
+/// cell needs the values of the other rows in its column as well; they are carried by `proof`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell<E: FieldElement> {
+    pub row: usize,
+    pub col: usize,
+    pub value: E,
+    pub proof: CellProof<E>,
+}
+
+/// Opening of the column of a `Cell`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellProof<E: FieldElement> {
+    /// Values of every row of the column except the one of the cell, in row order.
+    pub siblings: Vec<E>,
+    pub proof: FridaProof,
+}
+
+impl<E: FieldElement> Cell<E> {
+    /// Returns the values of every row of the column of this cell, in row order.
+    pub fn column(&self) -> Vec<E> {
+        let mut column = self.proof.siblings.clone();
+        column.insert(self.row.min(column.len()), self.value);
+        column
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens the value of polynomial `row` at position `col`. Non-batched commitments have a
+    /// single row.
+    pub fn get_cell(&self, row: usize, col: usize) -> Result<Cell<E>, FridaError> {
+        if row >= self.poly_count || col >= self.domain_size {
+            return Err(FridaError::CellOutOfBounds(row, col));
+        }
+
+        let mut siblings = self.get_evaluations(&[col])?;
+        let value = siblings.remove(row);
+        Ok(Cell {
+            row,
+            col,
+            value,
+            proof: CellProof {
+                siblings,
+                proof: self.open(&[col])?,
+            },
+        })
+    }
+}
+
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+// A cell is laid out as `row || col || value || siblings || proof`, where `row` and `col` are
+// written as little-endian u32s so that cells have a fixed-size header, and `siblings` is prefixed
+// with its length.
+impl<E: FieldElement> Serializable for Cell<E> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.row as u32);
+        target.write_u32(self.col as u32);
+        self.value.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for Cell<E> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let row = source.read_u32()? as usize;
+        let col = source.read_u32()? as usize;
+        let value = E::read_from(source)?;
+        let proof = CellProof::read_from(source)?;
+
+        Ok(Cell {
+            row,
+            col,
+            value,
+            proof,
+        })
+    }
+}
+
+impl<E: FieldElement> Serializable for CellProof<E> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.siblings.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for CellProof<E> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let siblings = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(CellProof { siblings, proof })
+    }
+}
@@ -2,7 +2,11 @@ use winter_crypto::{Digest, ElementHasher};
 use winter_fri::ProverChannel;
 use winter_math::FieldElement;
 
-use crate::core::random::FridaRandom;
+use crate::core::{
+    deep::{self, OodSample},
+    random::FridaRandom,
+    transcript::{Transcript, TranscriptEntry},
+};
 use crate::{constants, error::FridaError};
 
 #[derive(Debug)]
@@ -16,6 +20,7 @@ where
     pub public_coin: FridaRandom<E, HHst, HRandom>,
     pub domain_size: usize,
     pub num_queries: usize,
+    transcript: Transcript<E, HRandom::Digest>,
 }
 
 impl<E, HHst, HRandom> FridaProverChannel<E, HHst, HRandom>
@@ -48,6 +53,7 @@ where
             num_queries,
             public_coin: FridaRandom::new(),
             commitments: Vec::new(),
+            transcript: Transcript::new(),
         }
     }
 
@@ -61,6 +67,10 @@ where
             .public_coin
             .draw_query_positions(self.num_queries, self.domain_size)
             .expect("failed to draw query position");
+        self.transcript.push(TranscriptEntry::Positions {
+            domain_size: self.domain_size,
+            positions: positions.clone(),
+        });
 
         // TODO: Decide if dedup is ok or if we want to strictly hit the num_queries goal. Winterfell uses dedup.
         positions.dedup();
@@ -68,7 +78,30 @@ where
     }
 
     pub fn draw_xi(&mut self, count: usize) -> Result<Vec<E>, FridaError> {
-        self.public_coin.draw_xi(count)
+        let xi = self.public_coin.draw_xi(count)?;
+        self.transcript.push(TranscriptEntry::Xi(xi.clone()));
+        Ok(xi)
+    }
+
+    /// Draws an out-of-domain sample of the first layer, absorbing its value as returned by
+    /// `evaluate`; see `core::deep::draw_ood_sample`.
+    pub fn draw_ood_sample(
+        &mut self,
+        evaluate: impl FnOnce(E) -> E,
+    ) -> Result<OodSample<E>, FridaError> {
+        let sample = deep::draw_ood_sample(&mut self.public_coin, evaluate)?;
+        self.transcript.push(TranscriptEntry::OodSample {
+            point: sample.point,
+            value: sample.value,
+            comb: sample.comb,
+        });
+        Ok(sample)
+    }
+
+    /// Returns the commitments absorbed and the challenges squeezed so far, in order, so that
+    /// they can be re-derived independently with `Transcript::verify`.
+    pub fn transcript(&self) -> &Transcript<E, HRandom::Digest> {
+        &self.transcript
     }
 }
 
@@ -88,9 +121,13 @@ where
     ) {
         self.commitments.push(layer_root);
         self.public_coin.reseed(&layer_root.as_bytes());
+        self.transcript
+            .push(TranscriptEntry::Commitment(layer_root));
     }
 
     fn draw_fri_alpha(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw FRI alpha")
+        let alpha = self.public_coin.draw().expect("failed to draw FRI alpha");
+        self.transcript.push(TranscriptEntry::Alpha(alpha));
+        alpha
     }
 }
@@ -0,0 +1,109 @@
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+
+use crate::{constants, core::data::EncodingOptions, error::FridaError};
+
+// COMMIT COST
+// ================================================================================================
+
+/// Predicted work of committing to a blob, as returned by `estimate_commit_cost`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommitCost {
+    /// Size of the evaluation domain the data is Reed-Solomon encoded over.
+    pub fft_size: usize,
+    /// Total number of Merkle leaves over every FRI layer.
+    pub merkle_leaves: usize,
+    /// Number of FRI layers, excluding the remainder.
+    pub num_layers: usize,
+    /// Predicted commitment time according to the cost model.
+    pub estimated_time_ms: f64,
+}
+
+/// Linear time model of a commitment, counting FFT butterflies, field elements hashed into
+/// Merkle leaves and internal Merkle nodes.
+///
+/// The defaults are rough figures for f128 with `Blake3_256` on a desktop x86-64 CPU without the
+/// `concurrent` feature; `frida-bench calibrate` measures the coefficients of a given machine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostModel {
+    pub fft_ns_per_butterfly: f64,
+    /// Also covers folding, which touches every element of a layer once.
+    pub hash_ns_per_element: f64,
+    pub merge_ns_per_node: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel {
+            fft_ns_per_butterfly: 20.0,
+            hash_ns_per_element: 15.0,
+            merge_ns_per_node: 100.0,
+        }
+    }
+}
+
+impl CostModel {
+    /// Predicts the cost of committing to `data_size` bytes under `options`, with the default
+    /// encoding.
+    ///
+    /// # Errors
+    /// Returns `FridaError::DomainSizeTooBig` if the data does not fit the largest domain, and
+    /// `FridaError::NotEnoughDataPoints` if the domain is too small to be folded.
+    pub fn estimate_commit_cost<E: FieldElement>(
+        &self,
+        data_size: usize,
+        options: &FriOptions,
+    ) -> Result<CommitCost, FridaError> {
+        let blowup_factor = options.blowup_factor();
+        let folding_factor = options.folding_factor();
+        let element_count = EncodingOptions::default().element_count::<E>(data_size);
+        let fft_size = usize::max(
+            element_count.next_power_of_two() * blowup_factor,
+            constants::MIN_DOMAIN_SIZE,
+        );
+        if fft_size > constants::MAX_DOMAIN_SIZE {
+            return Err(FridaError::DomainSizeTooBig(fft_size));
+        }
+        let num_layers = options.num_fri_layers(fft_size);
+        if num_layers == 0 {
+            return Err(FridaError::NotEnoughDataPoints());
+        }
+
+        // interpolation over the data, evaluation over the domain, and remainder interpolation
+        let remainder_size = fft_size / folding_factor.pow(num_layers as u32);
+        let butterflies = [fft_size / blowup_factor, fft_size, remainder_size]
+            .iter()
+            .map(|&n| n / 2 * n.trailing_zeros() as usize)
+            .sum::<usize>();
+
+        let mut merkle_leaves = 0;
+        let mut hashed_elements = 0;
+        let mut domain_size = fft_size;
+        for _ in 0..num_layers {
+            hashed_elements += domain_size;
+            domain_size /= folding_factor;
+            merkle_leaves += domain_size;
+        }
+        let merkle_nodes = merkle_leaves - num_layers;
+
+        let estimated_time_ns = butterflies as f64 * self.fft_ns_per_butterfly
+            + hashed_elements as f64 * self.hash_ns_per_element
+            + merkle_nodes as f64 * self.merge_ns_per_node;
+
+        Ok(CommitCost {
+            fft_size,
+            merkle_leaves,
+            num_layers,
+            estimated_time_ms: estimated_time_ns / 1e6,
+        })
+    }
+}
+
+/// Predicts the cost of committing to `data_size` bytes under `options` with the default
+/// `CostModel`, for capacity planning.
+pub fn estimate_commit_cost<E: FieldElement>(
+    data_size: usize,
+    options: &FriOptions,
+) -> Result<CommitCost, FridaError> {
+    CostModel::default().estimate_commit_cost::<E>(data_size, options)
+}
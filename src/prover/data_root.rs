@@ -0,0 +1,248 @@
+use core::ops::Range;
+
+use winter_crypto::{BatchMerkleProof, Hasher, MerkleTree};
+use winter_utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+use super::merkle_error;
+use crate::error::FridaError;
+
+// DATA ROOTS
+// ================================================================================================
+
+/// Plain Merkle roots over the raw bytes of every blob of a commitment, built in the same pass
+/// as its FRI layers when the builder is set up with `FridaProverBuilder::with_data_roots`.
+///
+/// Each blob is split into chunks of `chunk_size` bytes, the last one possibly shorter, and each
+/// chunk is hashed along with the length of the blob into a leaf. The roots let applications
+/// prove that some bytes are part of a blob with a `DataInclusionProof`, without any FRI opening,
+/// while the FRI layers of the same commitment prove that the blob is available. The roots are
+/// bound by the binding and root digest of the commitment, and carried by `Commitment` so that
+/// verifiers hold them as checked; see `FridaDasVerifier::data_roots`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DataRoots<H: Hasher> {
+    pub chunk_size: usize,
+    /// Root of the tree of the chunks of every blob, in blob order.
+    pub roots: Vec<H::Digest>,
+}
+
+impl<H: Hasher> Clone for DataRoots<H> {
+    fn clone(&self) -> Self {
+        DataRoots {
+            chunk_size: self.chunk_size,
+            roots: self.roots.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> DataRoots<H> {
+    /// Builds the roots of `blobs` split into chunks of `chunk_size` bytes.
+    pub(crate) fn build(blobs: &[&[u8]], chunk_size: usize) -> Result<Self, FridaError> {
+        let roots = blobs
+            .iter()
+            .map(|data| Ok(*data_tree::<H>(data, chunk_size)?.root()))
+            .collect::<Result<Vec<_>, FridaError>>()?;
+        Ok(DataRoots { chunk_size, roots })
+    }
+
+    /// Checks that `proof` opens chunks of one of the blobs of these roots.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ProofPolyCountMismatch` if the proof is for a blob without a root,
+    /// `FridaError::DataChunksOutOfBounds` if it opens chunks past the end of the blob, and
+    /// `FridaError::InvalidDataInclusion` if the chunks do not match the root of the blob.
+    pub fn verify(&self, proof: &DataInclusionProof) -> Result<(), FridaError> {
+        let root = self
+            .roots
+            .get(proof.blob)
+            .ok_or(FridaError::ProofPolyCountMismatch)?;
+        let data_len =
+            usize::try_from(proof.data_len).map_err(|_| FridaError::InvalidDataInclusion)?;
+        let num_chunks = chunk_count(data_len, self.chunk_size);
+        let end = proof.first_chunk.saturating_add(proof.chunks.len());
+        if proof.chunks.is_empty() || end > num_chunks {
+            return Err(FridaError::DataChunksOutOfBounds(end, num_chunks));
+        }
+
+        let length_digest = H::hash(&proof.data_len.to_le_bytes());
+        let mut leaves = Vec::with_capacity(proof.chunks.len());
+        for (index, chunk) in (proof.first_chunk..end).zip(&proof.chunks) {
+            let start = index * self.chunk_size;
+            if chunk.len() != usize::min(self.chunk_size, data_len - start) {
+                return Err(FridaError::InvalidDataInclusion);
+            }
+            leaves.push(H::merge(&[H::hash(chunk), length_digest]));
+        }
+
+        let depth = num_chunks
+            .checked_next_power_of_two()
+            .ok_or(FridaError::InvalidDataInclusion)?
+            .max(2)
+            .ilog2() as u8;
+        let mut reader = SliceReader::new(&proof.paths);
+        let merkle_proof = BatchMerkleProof::<H>::deserialize(&mut reader, leaves, depth)
+            .map_err(|_| FridaError::InvalidDataInclusion)?;
+        if reader.has_more_bytes() {
+            return Err(FridaError::InvalidDataInclusion);
+        }
+        let indexes = (proof.first_chunk..end).collect::<Vec<_>>();
+        MerkleTree::verify_batch(root, &indexes, &merkle_proof)
+            .map_err(|_| FridaError::InvalidDataInclusion)
+    }
+}
+
+/// Proof that `chunks` are the consecutive chunks of blob `blob`, of `data_len` bytes, starting
+/// at chunk `first_chunk`, against the `DataRoots` of a commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataInclusionProof {
+    pub blob: usize,
+    pub data_len: u64,
+    pub first_chunk: usize,
+    pub chunks: Vec<Vec<u8>>,
+    /// Internal nodes of the batch Merkle proof of the opened chunks.
+    pub paths: Vec<u8>,
+}
+
+impl DataInclusionProof {
+    /// Opens chunks `chunks` of blob `blob` of a commitment, whose raw bytes are `data`, split
+    /// into chunks of `chunk_size` bytes.
+    ///
+    /// # Errors
+    /// Returns `FridaError::DataChunksOutOfBounds` if `chunks` is empty or extends past the last
+    /// chunk of `data`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    pub fn new<H: Hasher>(
+        data: &[u8],
+        blob: usize,
+        chunk_size: usize,
+        chunks: Range<usize>,
+    ) -> Result<Self, FridaError> {
+        let num_chunks = chunk_count(data.len(), chunk_size);
+        if chunks.is_empty() || chunks.end > num_chunks {
+            return Err(FridaError::DataChunksOutOfBounds(chunks.end, num_chunks));
+        }
+
+        let tree = data_tree::<H>(data, chunk_size)?;
+        let indexes = chunks.clone().collect::<Vec<_>>();
+        let paths = tree.prove_batch(&indexes).map_err(merkle_error)?;
+        Ok(DataInclusionProof {
+            blob,
+            data_len: data.len() as u64,
+            first_chunk: chunks.start,
+            chunks: chunks
+                .map(|index| chunk(data, chunk_size, index).to_vec())
+                .collect(),
+            paths: paths.serialize_nodes(),
+        })
+    }
+
+    /// Same as `new`, opening the chunks which hold bytes `range` of `data`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ByteRangeOutOfBounds` if `range` is empty or extends past the end of
+    /// `data`.
+    pub fn for_byte_range<H: Hasher>(
+        data: &[u8],
+        blob: usize,
+        chunk_size: usize,
+        range: Range<usize>,
+    ) -> Result<Self, FridaError> {
+        if range.is_empty() || range.end > data.len() {
+            return Err(FridaError::ByteRangeOutOfBounds(range.start, range.end));
+        }
+        let chunks = range.start / chunk_size..range.end.div_ceil(chunk_size);
+        Self::new::<H>(data, blob, chunk_size, chunks)
+    }
+
+    /// Offset in the blob of the first opened byte, for chunks of `chunk_size` bytes.
+    pub fn offset(&self, chunk_size: usize) -> usize {
+        self.first_chunk * chunk_size
+    }
+
+    /// Opened bytes, in order.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.chunks.concat()
+    }
+}
+
+/// Returns the number of chunks of `chunk_size` bytes `data_len` bytes are split into; empty
+/// data still has one, empty, chunk.
+fn chunk_count(data_len: usize, chunk_size: usize) -> usize {
+    data_len.div_ceil(chunk_size).max(1)
+}
+
+/// Returns the number of leaves of the tree of `num_chunks` chunks.
+fn tree_size(num_chunks: usize) -> usize {
+    num_chunks.next_power_of_two().max(2)
+}
+
+fn chunk(data: &[u8], chunk_size: usize, index: usize) -> &[u8] {
+    let start = index * chunk_size;
+    &data[start..usize::min(start + chunk_size, data.len())]
+}
+
+/// Builds the tree of the chunks of `data`, padded with default digests to a power of two.
+fn data_tree<H: Hasher>(data: &[u8], chunk_size: usize) -> Result<MerkleTree<H>, FridaError> {
+    let num_chunks = chunk_count(data.len(), chunk_size);
+    let length_digest = H::hash(&(data.len() as u64).to_le_bytes());
+    let mut leaves = (0..num_chunks)
+        .map(|index| H::merge(&[H::hash(chunk(data, chunk_size, index)), length_digest]))
+        .collect::<Vec<_>>();
+    leaves.resize(tree_size(num_chunks), H::Digest::default());
+    MerkleTree::new(leaves).map_err(merkle_error)
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for DataRoots<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.chunk_size.write_into(target);
+        self.roots.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for DataRoots<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let chunk_size = usize::read_from(source)?;
+        if chunk_size == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "data chunks must hold at least one byte".to_string(),
+            ));
+        }
+        let roots = Vec::<H::Digest>::read_from(source)?;
+
+        Ok(DataRoots { chunk_size, roots })
+    }
+}
+
+impl Serializable for DataInclusionProof {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.blob as u32);
+        self.data_len.write_into(target);
+        self.first_chunk.write_into(target);
+        self.chunks.write_into(target);
+        self.paths.write_into(target);
+    }
+}
+
+impl Deserializable for DataInclusionProof {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blob = source.read_u32()? as usize;
+        let data_len = u64::read_from(source)?;
+        let first_chunk = usize::read_from(source)?;
+        let chunks = Vec::<Vec<u8>>::read_from(source)?;
+        let paths = Vec::<u8>::read_from(source)?;
+
+        Ok(DataInclusionProof {
+            blob,
+            data_len,
+            first_chunk,
+            chunks,
+            paths,
+        })
+    }
+}
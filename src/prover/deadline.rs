@@ -0,0 +1,82 @@
+use winter_crypto::Hasher;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::ProverCommitment;
+
+// DISPERSAL HINT
+// ================================================================================================
+
+/// Dispersal terms announced by a commitment: by `deadline`, at least `expected_shares` distinct
+/// positions of it should have been sampled successfully.
+///
+/// The deadline is expressed in whatever clock the deployment agrees on, e.g. a unix timestamp in
+/// seconds or a block height; it is only ever compared with the `now` of the verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispersalHint {
+    pub deadline: u64,
+    pub expected_shares: usize,
+}
+
+/// Where the dispersal of a commitment stands with respect to its `DispersalHint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispersalStatus {
+    /// Not enough shares were sampled yet, but the deadline has not passed.
+    Pending,
+    /// At least the expected number of shares were sampled.
+    Dispersed,
+    /// The deadline passed without the expected number of shares being sampled, which suggests
+    /// that the data is withheld.
+    Overdue,
+}
+
+impl DispersalHint {
+    /// Returns the status of a dispersal at time `now`, once `sampled_shares` distinct positions
+    /// were sampled successfully. The deadline itself still belongs to the dispersal window.
+    pub fn status(&self, now: u64, sampled_shares: usize) -> DispersalStatus {
+        if sampled_shares >= self.expected_shares {
+            DispersalStatus::Dispersed
+        } else if now > self.deadline {
+            DispersalStatus::Overdue
+        } else {
+            DispersalStatus::Pending
+        }
+    }
+}
+
+impl<H: Hasher> ProverCommitment<H> {
+    /// Returns the status of the dispersal of this commitment at time `now`, once
+    /// `sampled_shares` distinct positions were sampled successfully, or `None` if the commitment
+    /// was built without `FridaProverBuilder::with_dispersal_hint`.
+    pub fn dispersal_status(&self, now: u64, sampled_shares: usize) -> Option<DispersalStatus> {
+        self.dispersal_hint
+            .map(|hint| hint.status(now, sampled_shares))
+    }
+
+    /// True if the dispersal window of this commitment passed without enough sampled shares; see
+    /// `DispersalStatus::Overdue`. Always false for commitments without a dispersal hint.
+    pub fn is_overdue(&self, now: u64, sampled_shares: usize) -> bool {
+        self.dispersal_status(now, sampled_shares) == Some(DispersalStatus::Overdue)
+    }
+}
+
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for DispersalHint {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.deadline);
+        target.write_u64(self.expected_shares as u64);
+    }
+}
+
+impl Deserializable for DispersalHint {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let deadline = source.read_u64()?;
+        let expected_shares = source.read_u64()? as usize;
+
+        Ok(DispersalHint {
+            deadline,
+            expected_shares,
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+
+use super::{FridaProver, ProverLayer};
+use crate::error::FridaError;
+
+// COMMITMENT DIFFING
+// ================================================================================================
+
+/// Returns the sorted indexes of the first-layer buckets whose leaves differ between two provers,
+/// e.g. two versions of a large blob, so that a DA provider already holding `prover_a` only needs
+/// to fetch the evaluations of those buckets to rebuild `prover_b`.
+///
+/// Both provers must share their domain size, folding factors, polynomial count and number of
+/// partitions; otherwise `FridaError::IncompatibleCommitments` is returned. Provers committed
+/// without any FRI layer have no buckets, and fail with `FridaError::NotEnoughDataPoints`, and
+/// provers whose first layer was pruned fail with `FridaError::FirstLayerUnavailable`.
+/// `bucket_positions` lists the positions held by a bucket.
+pub fn diff_commitments<E, H>(
+    prover_a: &FridaProver<E, H>,
+    prover_b: &FridaProver<E, H>,
+) -> Result<Vec<usize>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    if prover_a.domain_size != prover_b.domain_size
+        || prover_a.folding_factors != prover_b.folding_factors
+        || prover_a.poly_count != prover_b.poly_count
+        || prover_a.num_partitions != prover_b.num_partitions
+    {
+        return Err(FridaError::IncompatibleCommitments);
+    }
+    if prover_a.layers.is_empty() {
+        return Err(FridaError::NotEnoughDataPoints());
+    }
+
+    let (leaves_a, leaves_b) = match (&prover_a.layers[0], &prover_b.layers[0]) {
+        (ProverLayer::Full(a), ProverLayer::Full(b)) => (a.tree.leaves(), b.tree.leaves()),
+        _ => return Err(FridaError::FirstLayerUnavailable),
+    };
+
+    // leaves are laid out by partition, see `FridaProverBuilder::with_num_partitions`
+    let num_partitions = prover_a.num_partitions;
+    let partition_size = leaves_a.len() / num_partitions;
+    let mut buckets = leaves_a
+        .iter()
+        .zip(leaves_b)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(leaf, _)| leaf % partition_size * num_partitions + leaf / partition_size)
+        .collect::<Vec<_>>();
+    buckets.sort_unstable();
+    Ok(buckets)
+}
+
+/// Returns the positions whose evaluations are hashed together into first-layer bucket `bucket`.
+pub fn bucket_positions(bucket: usize, domain_size: usize, folding_factor: usize) -> Vec<usize> {
+    let bucket_count = domain_size / folding_factor;
+    (0..folding_factor)
+        .map(|i| bucket + i * bucket_count)
+        .collect()
+}
@@ -0,0 +1,162 @@
+use winter_crypto::{ElementHasher, Hasher};
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{proof::FridaProof, FridaProver};
+use crate::{
+    constants::MAX_DOMAIN_SIZE,
+    core::positions::{read_positions, write_positions},
+    error::FridaError,
+};
+
+// APP OPENING
+// ================================================================================================
+
+/// An opening of the blob of one application within a batched commitment.
+///
+/// `app_id_path` authenticates that blob `blob` belongs to the requested application against the
+/// app ids root of the commitment, so that a verifier cannot be served another blob of the same
+/// bucket. `evaluations` holds the values of every committed polynomial at `positions`, as needed
+/// to check `proof`, of which only those of `blob` are handed back by the verifier.
+///
+/// Positions are serialized with `core::positions::write_positions`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AppOpening<E: FieldElement, H: Hasher> {
+    pub blob: usize,
+    pub app_id_path: Vec<H::Digest>,
+    pub positions: Vec<usize>,
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+}
+
+impl<E: FieldElement, H: Hasher> Clone for AppOpening<E, H> {
+    fn clone(&self) -> Self {
+        AppOpening {
+            blob: self.blob,
+            app_id_path: self.app_id_path.clone(),
+            positions: self.positions.clone(),
+            evaluations: self.evaluations.clone(),
+            proof: self.proof.clone(),
+        }
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens `positions` of the blob committed for `app_id`, which must have been bound with
+    /// `FridaProverBuilder::commitment_batch_with_app_ids`.
+    pub fn open_app(
+        &self,
+        app_id: &[u8],
+        positions: &[usize],
+    ) -> Result<AppOpening<E, H>, FridaError> {
+        if self.app_ids.is_empty() {
+            return Err(FridaError::AppIdsNotCommitted);
+        }
+        let leaf = H::hash(app_id);
+        let blob = self
+            .app_ids
+            .iter()
+            .position(|id| *id == leaf)
+            .ok_or(FridaError::UnknownAppId)?;
+
+        Ok(AppOpening {
+            blob,
+            app_id_path: app_ids_path::<H>(&self.app_ids, blob),
+            positions: positions.to_vec(),
+            evaluations: self.get_evaluations(positions)?,
+            proof: self.open(positions)?,
+        })
+    }
+}
+
+// APP IDS TREE
+// ------------------------------------------------------------------------------------------------
+
+/// Returns the levels of the Merkle tree over `leaves`, padded with default digests to a power of
+/// two, from the leaves up to the root.
+fn app_ids_levels<H: Hasher>(leaves: &[H::Digest]) -> Vec<Vec<H::Digest>> {
+    let mut level = leaves.to_vec();
+    level.resize(leaves.len().next_power_of_two(), H::Digest::default());
+
+    let mut levels = vec![level];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| H::merge(&[pair[0], pair[1]]))
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Returns the root of the tree of app id hashes, in blob order.
+pub(crate) fn app_ids_root<H: Hasher>(leaves: &[H::Digest]) -> H::Digest {
+    app_ids_levels::<H>(leaves).last().unwrap()[0]
+}
+
+/// Returns the siblings of leaf `index` from the bottom of the tree up.
+pub(crate) fn app_ids_path<H: Hasher>(leaves: &[H::Digest], index: usize) -> Vec<H::Digest> {
+    let levels = app_ids_levels::<H>(leaves);
+    levels[..levels.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(depth, level)| level[(index >> depth) ^ 1])
+        .collect()
+}
+
+/// Checks that `leaf` is found at `index` of the tree with the given root.
+pub(crate) fn verify_app_ids_path<H: Hasher>(
+    root: &H::Digest,
+    index: usize,
+    leaf: H::Digest,
+    path: &[H::Digest],
+) -> bool {
+    if path.len() >= usize::BITS as usize || index >> path.len() != 0 {
+        return false;
+    }
+    let computed = path
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (depth, sibling)| match (index >> depth) & 1 {
+            0 => H::merge(&[node, *sibling]),
+            _ => H::merge(&[*sibling, node]),
+        });
+    computed == *root
+}
+
+// SERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<E: FieldElement, H: Hasher> Serializable for AppOpening<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.blob.write_into(target);
+        self.app_id_path.write_into(target);
+        write_positions(&self.positions, target);
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for AppOpening<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blob = usize::read_from(source)?;
+        let app_id_path = Vec::<H::Digest>::read_from(source)?;
+        let positions = read_positions(source, MAX_DOMAIN_SIZE)?;
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(AppOpening {
+            blob,
+            app_id_path,
+            positions,
+            evaluations,
+            proof,
+        })
+    }
+}
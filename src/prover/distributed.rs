@@ -0,0 +1,693 @@
+use std::collections::BTreeMap;
+
+use winter_crypto::{ElementHasher, MerkleTree};
+use winter_fri::{folding::fold_positions, ProverChannel};
+use winter_math::{fft, FieldElement, StarkField};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{
+    builder::{commit_layer, fold_layer, FridaProverBuilder},
+    channel::FridaProverChannel,
+    data_root::DataRoots,
+    merkle_error, Commitment, FridaLayer, FridaProver, ProverLayer, PrunedLayer,
+};
+use crate::error::FridaError;
+
+// DISTRIBUTED PROVING
+// ================================================================================================
+
+// With `num_partitions` partitions, row `i` of every layer belongs to partition
+// `i % num_partitions`, and the rows of a partition are the leaves of a subtree of the layer (see
+// `FridaProverBuilder::with_num_partitions`). The rows of a partition of the first layer hold the
+// evaluations of the blob over a coset of the domain, and fold into the rows of the same partition
+// of the next layer, so that every worker can build its partition of the layers on its own. A
+// commitment is then distributed as follows:
+//
+// 1. the coordinator hands every worker its `PartitionTask`, holding the polynomial of the blob
+//    reduced to the coset of its partition, no larger than the partition itself;
+// 2. every worker encodes and hashes its partition of the first layer, and sends the root of its
+//    subtree back;
+// 3. the coordinator merges the subtree roots into the root of the layer, and sends the folding
+//    challenge drawn from it to every worker;
+// 4. every worker folds its partition by the challenge into its partition of the next layer,
+//    which it commits to as in step 2, until the partitions of the next layer would hold a single
+//    row; the workers then send their last folded partitions, a few evaluations each, from which
+//    the coordinator builds the remaining layers on its own and draws the query positions;
+// 5. every worker opens the rows of the query positions it holds in each of its layers, which the
+//    coordinator checks against their subtree roots before opening the commitment.
+//
+// Workers only ever send subtree roots, their last folded partitions and openings, so that no
+// layer is ever held by a single machine.
+
+/// Work of the worker of one partition of a distributed commitment; see
+/// `FridaProverBuilder::distribute`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionTask<E: FieldElement> {
+    pub partition: usize,
+    pub num_partitions: usize,
+    pub domain_size: usize,
+    /// Folding factors of the layers built by the workers, starting with the first layer.
+    pub folding_factors: Vec<usize>,
+    /// Offset of the FRI domain, as set by the options of the commitment.
+    pub domain_offset: E::BaseField,
+    /// Coefficients of the polynomial of the blob, reduced to the coset of the partition; see
+    /// `reduce_to_coset`.
+    pub coefficients: Vec<E>,
+}
+
+/// Answer of a worker to a folding challenge of the coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionFold<E: FieldElement, D> {
+    /// Root of the subtree of the partition of the next layer, which the worker committed to.
+    Root(D),
+    /// Evaluations of the partition of the next layer, at positions
+    /// `partition + k * num_partitions` in order, once the worker has built all its layers.
+    Evaluations(Vec<E>),
+}
+
+/// Rows of the partitions of the layers of a worker, opened along with their authentication
+/// paths in the subtrees of the partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionOpening<E: FieldElement, D> {
+    pub partition: usize,
+    /// Opened rows of every layer, numbered as rows of their layer.
+    pub rows: Vec<Vec<usize>>,
+    /// `folding_factor` values of every opened row, in order, by layer.
+    pub values: Vec<Vec<E>>,
+    pub paths: Vec<Vec<Vec<D>>>,
+}
+
+// PARTITION WORKER
+// ================================================================================================
+
+/// Worker encoding, hashing and folding one partition of the layers of a distributed commitment.
+#[derive(Debug)]
+pub struct PartitionWorker<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    partition: usize,
+    num_partitions: usize,
+    domain_size: usize,
+    folding_factors: Vec<usize>,
+    domain_offset: E::BaseField,
+    /// Partitions of the layers built so far, each committed to by its subtree.
+    layers: Vec<FridaLayer<E, H>>,
+    /// Whether the last layer was folded into the evaluations sent to the coordinator.
+    folded: bool,
+}
+
+impl<E, H> PartitionWorker<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Evaluates the blob of `task` over the positions of its partition, and commits to them.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnsupportedFoldingFactor` if a folding factor of `task` is not
+    /// supported, and `FridaError::InvalidPartitionShare` if its partition does not hold at least
+    /// two rows of every layer, or if its coefficients do not fit the partition.
+    pub fn new(task: &PartitionTask<E>) -> Result<Self, FridaError> {
+        let PartitionTask {
+            partition,
+            num_partitions,
+            domain_size,
+            ..
+        } = *task;
+        if !num_partitions.is_power_of_two()
+            || partition >= num_partitions
+            || !domain_size.is_power_of_two()
+            || domain_size.ilog2() > E::BaseField::TWO_ADICITY
+            || task.folding_factors.is_empty()
+        {
+            return Err(FridaError::InvalidPartitionShare(partition));
+        }
+        let mut layer_size = domain_size;
+        for &folding_factor in &task.folding_factors {
+            if !matches!(folding_factor, 2 | 4 | 8 | 16) {
+                return Err(FridaError::UnsupportedFoldingFactor(folding_factor));
+            }
+            if layer_size / folding_factor / num_partitions < 2 {
+                return Err(FridaError::InvalidPartitionShare(partition));
+            }
+            layer_size /= folding_factor;
+        }
+        let partition_size = domain_size / num_partitions;
+        let num_coefficients = task.coefficients.len();
+        if num_coefficients < 2
+            || !num_coefficients.is_power_of_two()
+            || num_coefficients > partition_size
+        {
+            return Err(FridaError::InvalidPartitionShare(partition));
+        }
+
+        // the positions of the partition, `partition + k * num_partitions`, are the coset of the
+        // subgroup of order `domain_size / num_partitions` shifted by `g^partition`
+        let generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+        let shift = generator.exp_vartime((partition as u64).into());
+        let twiddles = fft::get_twiddles(num_coefficients);
+        let evaluations = fft::evaluate_poly_with_offset(
+            &task.coefficients,
+            &twiddles,
+            shift,
+            partition_size / num_coefficients,
+        );
+        let layer = commit_partition::<E, H>(&evaluations, task.folding_factors[0])?;
+
+        Ok(PartitionWorker {
+            partition,
+            num_partitions,
+            domain_size,
+            folding_factors: task.folding_factors.clone(),
+            domain_offset: task.domain_offset,
+            layers: vec![layer],
+            folded: false,
+        })
+    }
+
+    pub fn partition(&self) -> usize {
+        self.partition
+    }
+
+    /// Root of the subtree of the partition of the last layer built by the worker.
+    pub fn root(&self) -> H::Digest {
+        let layer = self
+            .layers
+            .last()
+            .expect("workers build the first layer when created");
+        *layer.tree.root()
+    }
+
+    /// Folds the partition of the last layer by the challenge `alpha` of the coordinator into the
+    /// partition of the next layer, at positions `partition + k * num_partitions`. The worker
+    /// commits to the folded partition and returns its subtree root, unless it has built all its
+    /// layers, in which case the folded evaluations are returned to the coordinator.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnexpectedMessage` if the evaluations were already returned.
+    pub fn fold(&mut self, alpha: E) -> Result<PartitionFold<E, H::Digest>, FridaError> {
+        if self.folded {
+            return Err(FridaError::UnexpectedMessage);
+        }
+        let depth = self.layers.len() - 1;
+        let layer_size = self.domain_size / self.folding_factors[..depth].iter().product::<usize>();
+
+        // the partition is a FRI layer of its own, over the coset of its positions
+        let generator = E::BaseField::get_root_of_unity(layer_size.ilog2());
+        let offset = self.domain_offset * generator.exp_vartime((self.partition as u64).into());
+        let evaluations = &self.layers[depth].evaluations;
+        let folded = match self.folding_factors[depth] {
+            2 => fold_layer::<E, 2>(evaluations, offset, alpha),
+            4 => fold_layer::<E, 4>(evaluations, offset, alpha),
+            8 => fold_layer::<E, 8>(evaluations, offset, alpha),
+            16 => fold_layer::<E, 16>(evaluations, offset, alpha),
+            _ => unreachable!("folding factors are checked when the worker is created"),
+        };
+
+        let Some(&folding_factor) = self.folding_factors.get(depth + 1) else {
+            self.folded = true;
+            return Ok(PartitionFold::Evaluations(folded));
+        };
+        let layer = commit_partition::<E, H>(&folded, folding_factor)?;
+        let root = *layer.tree.root();
+        self.layers.push(layer);
+        Ok(PartitionFold::Root(root))
+    }
+
+    /// Opens `rows` of every layer, as requested by the coordinator.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnexpectedMessage` if the worker has not built all its layers or
+    /// `rows` are not given for each of them, and `FridaError::RowOutsidePartition` if a row is
+    /// not held by this partition.
+    pub fn open(&self, rows: &[Vec<usize>]) -> Result<PartitionOpening<E, H::Digest>, FridaError> {
+        if !self.folded || rows.len() != self.layers.len() {
+            return Err(FridaError::UnexpectedMessage);
+        }
+        let mut values = Vec::with_capacity(rows.len());
+        let mut paths = Vec::with_capacity(rows.len());
+        let mut layer_size = self.domain_size;
+        for ((layer, rows), &folding_factor) in
+            self.layers.iter().zip(rows).zip(&self.folding_factors)
+        {
+            let num_rows = layer_size / folding_factor;
+            let mut layer_values = Vec::with_capacity(rows.len() * folding_factor);
+            let mut layer_paths = Vec::with_capacity(rows.len());
+            for &row in rows {
+                if row >= num_rows || row % self.num_partitions != self.partition {
+                    return Err(FridaError::RowOutsidePartition(row, self.partition));
+                }
+                let index = row / self.num_partitions;
+                let start = index * folding_factor;
+                layer_values.extend_from_slice(&layer.evaluations[start..start + folding_factor]);
+                layer_paths.push(layer.tree.prove(index).map_err(merkle_error)?);
+            }
+            values.push(layer_values);
+            paths.push(layer_paths);
+            layer_size = num_rows;
+        }
+
+        Ok(PartitionOpening {
+            partition: self.partition,
+            rows: rows.to_vec(),
+            values,
+            paths,
+        })
+    }
+}
+
+/// Commits to the partition of a layer folded by `folding_factor`, given by its `evaluations` in
+/// natural order.
+fn commit_partition<E, H>(
+    evaluations: &[E],
+    folding_factor: usize,
+) -> Result<FridaLayer<E, H>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    match folding_factor {
+        2 => commit_layer::<E, H, 2>(evaluations),
+        4 => commit_layer::<E, H, 4>(evaluations),
+        8 => commit_layer::<E, H, 8>(evaluations),
+        16 => commit_layer::<E, H, 16>(evaluations),
+        _ => Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
+    }
+}
+
+/// Reduces the polynomial of `coefficients` modulo `x^size - shift^size`, which vanishes over the
+/// coset of the subgroup of order `size` shifted by `shift`, so that it keeps its evaluations over
+/// the coset with at most `size` coefficients.
+fn reduce_to_coset<E: FieldElement>(
+    coefficients: &[E],
+    shift: E::BaseField,
+    size: usize,
+) -> Vec<E> {
+    if coefficients.len() <= size {
+        return coefficients.to_vec();
+    }
+    let factor = E::from(shift.exp_vartime((size as u64).into()));
+    let mut reduced = vec![E::ZERO; size];
+    let mut power = E::ONE;
+    for chunk in coefficients.chunks(size) {
+        for (r, &c) in reduced.iter_mut().zip(chunk) {
+            *r += c * power;
+        }
+        power *= factor;
+    }
+    reduced
+}
+
+// PARTITION COORDINATOR
+// ================================================================================================
+
+/// Coordinator of a commitment whose layers are split across partition workers, as started by
+/// `FridaProverBuilder::distribute`.
+///
+/// The coordinator answers the subtree roots of every layer of the workers with
+/// `commit_partitions`, their last folded partitions with `fold_partitions`, and completes the
+/// commitment from their openings with `finish`; calls made out of this order fail with
+/// `FridaError::UnexpectedMessage`.
+#[derive(Debug)]
+pub struct PartitionCoordinator<'a, E, H, HHst>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    builder: &'a FridaProverBuilder<E, H, HHst>,
+    channel: FridaProverChannel<E, HHst, H>,
+    domain_size: usize,
+    /// Folding factors of the layers built by the workers.
+    folding_factors: Vec<usize>,
+    num_partitions: usize,
+    data_checksum: Option<H::Digest>,
+    data_roots: Option<DataRoots<H>>,
+    /// Layers committed to by the workers so far, along with their folding challenges.
+    layers: Vec<(MergedLayer<H>, E)>,
+    round: Round<E, H>,
+}
+
+#[derive(Debug)]
+enum Round<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Waiting for the subtree roots of the next layer of the workers.
+    Commit,
+    /// Waiting for the last folded partitions of the workers.
+    Fold,
+    /// Waiting for the openings of the rows at the query positions.
+    Open {
+        prover: FridaProver<E, H>,
+        positions: Vec<usize>,
+        rows: Vec<Vec<Vec<usize>>>,
+    },
+}
+
+/// Subtree roots of a layer, along with the tree merging them into its root unless there is a
+/// single partition.
+#[derive(Debug)]
+struct MergedLayer<H: ElementHasher> {
+    roots: Vec<H::Digest>,
+    tree: Option<MerkleTree<H>>,
+}
+
+impl<H: ElementHasher> MergedLayer<H> {
+    fn root(&self) -> H::Digest {
+        self.tree
+            .as_ref()
+            .map_or(self.roots[0], |tree| *tree.root())
+    }
+
+    /// Returns the path from the subtree root of `partition` to the root, without the subtree
+    /// root itself.
+    fn upper_path(&self, partition: usize) -> Result<Vec<H::Digest>, FridaError> {
+        let Some(tree) = &self.tree else {
+            return Ok(Vec::new());
+        };
+        let mut path = tree.prove(partition).map_err(merkle_error)?;
+        path.remove(0);
+        Ok(path)
+    }
+}
+
+impl<'a, E, H, HHst> PartitionCoordinator<'a, E, H, HHst>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new(
+        builder: &'a FridaProverBuilder<E, H, HHst>,
+        domain_size: usize,
+        folding_factors: Vec<usize>,
+        num_partitions: usize,
+        num_queries: usize,
+        coefficients: Vec<E>,
+        data_checksum: Option<H::Digest>,
+        data_roots: Option<DataRoots<H>>,
+    ) -> (Self, Vec<PartitionTask<E>>) {
+        let partition_size = domain_size / num_partitions;
+        let generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+        let tasks = (0..num_partitions)
+            .map(|partition| {
+                let shift = generator.exp_vartime((partition as u64).into());
+                PartitionTask {
+                    partition,
+                    num_partitions,
+                    domain_size,
+                    folding_factors: folding_factors.clone(),
+                    domain_offset: builder.options.domain_offset(),
+                    coefficients: reduce_to_coset(&coefficients, shift, partition_size),
+                }
+            })
+            .collect();
+        let coordinator = PartitionCoordinator {
+            builder,
+            channel: FridaProverChannel::new(domain_size, num_queries),
+            domain_size,
+            folding_factors,
+            num_partitions,
+            data_checksum,
+            data_roots,
+            layers: Vec::new(),
+            round: Round::Commit,
+        };
+        (coordinator, tasks)
+    }
+
+    /// Commits to the next layer from the subtree `roots` of the workers, given by partition, and
+    /// returns the challenge every worker folds its partition by.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PartitionCountMismatch` if there is not one root per partition.
+    pub fn commit_partitions(&mut self, roots: &[H::Digest]) -> Result<E, FridaError> {
+        if !matches!(self.round, Round::Commit) {
+            return Err(FridaError::UnexpectedMessage);
+        }
+        if roots.len() != self.num_partitions {
+            return Err(FridaError::PartitionCountMismatch(
+                self.num_partitions,
+                roots.len(),
+            ));
+        }
+
+        let tree = (roots.len() > 1)
+            .then(|| MerkleTree::<H>::new(roots.to_vec()).map_err(merkle_error))
+            .transpose()?;
+        let layer = MergedLayer {
+            roots: roots.to_vec(),
+            tree,
+        };
+        self.channel.commit_fri_layer(layer.root());
+        let alpha = self.channel.draw_fri_alpha();
+        self.layers.push((layer, alpha));
+        if self.layers.len() == self.folding_factors.len() {
+            self.round = Round::Fold;
+        }
+        Ok(alpha)
+    }
+
+    /// Builds the remaining layers from the last partitions `folded` by the workers, given by
+    /// partition, and draws the query positions of the commitment. Returns the rows of every
+    /// layer each worker must open, by partition.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PartitionCountMismatch` if there is not one folded partition per
+    /// partition, `FridaError::InvalidPartitionShare` if a partition does not have the size of a
+    /// partition of the next layer, and the errors of `commit_and_prove` otherwise.
+    pub fn fold_partitions(
+        &mut self,
+        folded: &[Vec<E>],
+    ) -> Result<Vec<Vec<Vec<usize>>>, FridaError> {
+        if !matches!(self.round, Round::Fold) {
+            return Err(FridaError::UnexpectedMessage);
+        }
+        if folded.len() != self.num_partitions {
+            return Err(FridaError::PartitionCountMismatch(
+                self.num_partitions,
+                folded.len(),
+            ));
+        }
+        let num_rows = self.domain_size / self.folding_factors.iter().product::<usize>();
+        let partition_size = num_rows / self.num_partitions;
+        if let Some(partition) = folded.iter().position(|f| f.len() != partition_size) {
+            return Err(FridaError::InvalidPartitionShare(partition));
+        }
+
+        let mut evaluations = vec![E::ZERO; num_rows];
+        for (partition, values) in folded.iter().enumerate() {
+            for (k, &value) in values.iter().enumerate() {
+                evaluations[partition + k * self.num_partitions] = value;
+            }
+        }
+        // the rows of the layers of the workers are retained once they open them
+        let first_layers = self
+            .layers
+            .iter()
+            .map(|(layer, alpha)| {
+                let pruned = ProverLayer::Pruned(PrunedLayer {
+                    root: layer.root(),
+                    rows: BTreeMap::new(),
+                });
+                (pruned, *alpha)
+            })
+            .collect();
+        let prover = self.builder.build_layers(
+            &mut self.channel,
+            evaluations,
+            self.domain_size,
+            1,
+            first_layers,
+            Vec::new(),
+        )?;
+
+        let positions = self.channel.draw_query_positions();
+        let mut rows = vec![vec![Vec::new(); self.folding_factors.len()]; self.num_partitions];
+        let mut layer_positions = positions.clone();
+        let mut layer_size = self.domain_size;
+        for (depth, &folding_factor) in self.folding_factors.iter().enumerate() {
+            layer_positions = fold_positions(&layer_positions, layer_size, folding_factor);
+            for &row in &layer_positions {
+                rows[row % self.num_partitions][depth].push(row);
+            }
+            layer_size /= folding_factor;
+        }
+        self.round = Round::Open {
+            prover,
+            positions,
+            rows: rows.clone(),
+        };
+        Ok(rows)
+    }
+
+    /// Checks the `openings` of the workers, given by partition, against their subtree roots, and
+    /// returns the commitment along with its prover. The layers of the prover built by the
+    /// workers only hold the opened rows, as if pruned with `PruneLevel::Positions` to the query
+    /// positions of the commitment.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PartitionCountMismatch` if there is not one opening per partition,
+    /// and `FridaError::InvalidPartitionShare` if an opening does not open the requested rows of
+    /// its partition.
+    pub fn finish(
+        self,
+        openings: &[PartitionOpening<E, H::Digest>],
+    ) -> Result<(Commitment<H>, FridaProver<E, H>), FridaError> {
+        let Round::Open {
+            mut prover,
+            positions,
+            rows,
+        } = self.round
+        else {
+            return Err(FridaError::UnexpectedMessage);
+        };
+        if openings.len() != self.num_partitions {
+            return Err(FridaError::PartitionCountMismatch(
+                self.num_partitions,
+                openings.len(),
+            ));
+        }
+
+        let num_layers = self.folding_factors.len();
+        for (partition, opening) in openings.iter().enumerate() {
+            if opening.partition != partition
+                || opening.rows != rows[partition]
+                || opening.values.len() != num_layers
+                || opening.paths.len() != num_layers
+            {
+                return Err(FridaError::InvalidPartitionShare(partition));
+            }
+            for (depth, ((merged, _), &folding_factor)) in
+                self.layers.iter().zip(&self.folding_factors).enumerate()
+            {
+                let (rows, values, paths) = (
+                    &opening.rows[depth],
+                    &opening.values[depth],
+                    &opening.paths[depth],
+                );
+                if values.len() != rows.len() * folding_factor || paths.len() != rows.len() {
+                    return Err(FridaError::InvalidPartitionShare(partition));
+                }
+                let ProverLayer::Pruned(layer) = &mut prover.layers[depth] else {
+                    unreachable!("the layers of the workers are pruned");
+                };
+                let upper_path = merged.upper_path(partition)?;
+                let subtree_root = merged.roots[partition];
+                for ((&row, values), path) in
+                    rows.iter().zip(values.chunks(folding_factor)).zip(paths)
+                {
+                    let index = row / self.num_partitions;
+                    if path.first() != Some(&H::hash_elements(values))
+                        || MerkleTree::<H>::verify(subtree_root, index, path).is_err()
+                    {
+                        return Err(FridaError::InvalidPartitionShare(partition));
+                    }
+                    let mut path = path.clone();
+                    path.extend_from_slice(&upper_path);
+                    layer.rows.insert(row, (values.to_vec(), path));
+                }
+            }
+        }
+        if let Some(checksum) = self.data_checksum {
+            prover.data_checksums = vec![checksum];
+        }
+        prover.data_roots = self.data_roots;
+
+        let commitment = self
+            .builder
+            .open_commitment(&prover, &self.channel, &positions)?;
+        Ok((commitment, prover))
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement> Serializable for PartitionTask<E> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.partition.write_into(target);
+        self.num_partitions.write_into(target);
+        self.domain_size.write_into(target);
+        self.folding_factors.write_into(target);
+        self.domain_offset.write_into(target);
+        self.coefficients.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for PartitionTask<E> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let partition = usize::read_from(source)?;
+        let num_partitions = usize::read_from(source)?;
+        let domain_size = usize::read_from(source)?;
+        let folding_factors = Vec::<usize>::read_from(source)?;
+        let domain_offset = E::BaseField::read_from(source)?;
+        let coefficients = Vec::<E>::read_from(source)?;
+
+        Ok(PartitionTask {
+            partition,
+            num_partitions,
+            domain_size,
+            folding_factors,
+            domain_offset,
+            coefficients,
+        })
+    }
+}
+
+impl<E: FieldElement, D: Serializable> Serializable for PartitionFold<E, D> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            PartitionFold::Root(root) => {
+                target.write_u8(0);
+                root.write_into(target);
+            }
+            PartitionFold::Evaluations(evaluations) => {
+                target.write_u8(1);
+                evaluations.write_into(target);
+            }
+        }
+    }
+}
+
+impl<E: FieldElement, D: Deserializable> Deserializable for PartitionFold<E, D> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(PartitionFold::Root(D::read_from(source)?)),
+            1 => Ok(PartitionFold::Evaluations(Vec::<E>::read_from(source)?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "unknown partition fold {tag}"
+            ))),
+        }
+    }
+}
+
+impl<E: FieldElement, D: Serializable> Serializable for PartitionOpening<E, D> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.partition.write_into(target);
+        self.rows.write_into(target);
+        self.values.write_into(target);
+        self.paths.write_into(target);
+    }
+}
+
+impl<E: FieldElement, D: Deserializable> Deserializable for PartitionOpening<E, D> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let partition = usize::read_from(source)?;
+        let rows = Vec::<Vec<usize>>::read_from(source)?;
+        let values = Vec::<Vec<E>>::read_from(source)?;
+        let paths = Vec::<Vec<Vec<D>>>::read_from(source)?;
+
+        Ok(PartitionOpening {
+            partition,
+            rows,
+            values,
+            paths,
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use winter_crypto::{ElementHasher, Hasher};
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_rand_utils::rand_array;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{proof::FridaProof, ProverCommitment};
+use crate::{core::random::FridaRandom, error::FridaError, verifier::das::FridaDasVerifier};
+
+/// Domain separator absorbed along with the epoch and its salt, so that audit positions cannot
+/// collide with positions drawn by other protocols from the same commitment.
+const EPOCH_DOMAIN: &[u8] = b"frida-epoch-v2";
+
+// EPOCH COMMITMENT
+// ================================================================================================
+
+// Long-lived data is audited once per epoch: its holder re-commits to the stored data with
+// `FridaProverBuilder::recommit`, and opens the commitment at a window of positions sampled from
+// the commitment and the `EpochChallenge` of the epoch. The challenge is set by auditors rather
+// than by the holder: its salt is fresh randomness, and its epoch number rotates the domain the
+// positions are drawn under, so that no two epochs sample the same window even if a salt is
+// reused. As neither is known in advance, answering the audit requires the data itself; as the
+// commitment is deterministic, renewing it under the same options yields the commitment being
+// audited, which auditors check before verifying the openings against it.
+
+/// Audit of a commitment for `epoch`, set by the auditors of the epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochChallenge {
+    pub epoch: u64,
+    pub salt: [u8; 32],
+    /// Number of positions drawn for the epoch, before deduplication.
+    pub num_queries: usize,
+}
+
+impl EpochChallenge {
+    /// Creates the challenge of `epoch` from a fresh random salt.
+    pub fn random(epoch: u64, num_queries: usize) -> Self {
+        EpochChallenge {
+            epoch,
+            salt: rand_array(),
+            num_queries,
+        }
+    }
+
+    /// Returns the positions of `commitment` sampled for this epoch, sorted and deduplicated; see
+    /// `draw_seeded_positions`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BadNumQueries` if the number of queries of the challenge is zero or
+    /// not smaller than the domain.
+    pub fn positions<E, HHst, H>(
+        &self,
+        commitment: &ProverCommitment<H>,
+    ) -> Result<Vec<usize>, FridaError>
+    where
+        E: FieldElement,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let domain = [EPOCH_DOMAIN, &self.epoch.to_be_bytes()].concat();
+        draw_seeded_positions::<E, HHst, H>(commitment, &domain, &self.salt, self.num_queries)
+    }
+}
+
+/// Renewal of a commitment answering an `EpochChallenge`, opening it at the positions sampled for
+/// the epoch; see `FridaProverBuilder::recommit`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EpochCommitment<E: FieldElement, H: Hasher> {
+    /// Commitment re-computed from the stored data.
+    pub commitment: ProverCommitment<H>,
+    /// Evaluations at the positions returned by `EpochChallenge::positions`, laid out as for
+    /// `FridaDasVerifier::verify`.
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> EpochCommitment<E, H> {
+    /// Checks that this renewal commits to the same data as `previous`, made with `options` and
+    /// with `HHst` as the transcript hasher, and that it opens `previous` at the positions
+    /// sampled for `challenge`. Returns these positions.
+    ///
+    /// # Errors
+    /// Returns `FridaError::EpochDiscontinuity` if the renewed commitment differs from `previous`,
+    /// the errors of `EpochChallenge::positions`, and the errors of `FridaDasVerifier::verify` if
+    /// the openings do not verify.
+    pub fn verify<HHst>(
+        &self,
+        previous: &ProverCommitment<H>,
+        challenge: &EpochChallenge,
+        options: &FriOptions,
+    ) -> Result<Vec<usize>, FridaError>
+    where
+        HHst: ElementHasher<BaseField = E::BaseField>,
+    {
+        if self.commitment.root_digest() != previous.root_digest() {
+            return Err(FridaError::EpochDiscontinuity);
+        }
+
+        let positions = challenge.positions::<E, HHst, H>(previous)?;
+        let verifier = FridaDasVerifier::<E, HHst, H>::from_commitment(previous, options.clone())?;
+        verifier.verify(&self.proof, &self.evaluations, &positions)?;
+        Ok(positions)
+    }
+}
+
+/// Draws `num_queries` positions of `commitment` from a public coin seeded with the root digest
+/// of the commitment, then with `seed` under the domain separator `domain`, and returns them
+/// sorted and deduplicated.
+pub(super) fn draw_seeded_positions<E, HHst, H>(
+    commitment: &ProverCommitment<H>,
+    domain: &[u8],
+    seed: &[u8],
+    num_queries: usize,
+) -> Result<Vec<usize>, FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    if num_queries == 0 || num_queries >= commitment.domain_size {
+        return Err(FridaError::BadNumQueries(num_queries));
+    }
+
+    let mut public_coin = FridaRandom::<E, HHst, H>::new();
+    public_coin.reseed(&commitment.root_digest());
+    public_coin.reseed(&[domain, seed].concat());
+    let mut positions = public_coin.draw_query_positions(num_queries, commitment.domain_size)?;
+    positions.sort_unstable();
+    positions.dedup();
+    Ok(positions)
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for EpochChallenge {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.epoch);
+        target.write_bytes(&self.salt);
+        self.num_queries.write_into(target);
+    }
+}
+
+impl Deserializable for EpochChallenge {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let epoch = source.read_u64()?;
+        let salt = source.read_array()?;
+        let num_queries = usize::read_from(source)?;
+
+        Ok(EpochChallenge {
+            epoch,
+            salt,
+            num_queries,
+        })
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Serializable for EpochCommitment<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.commitment.write_into(target);
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for EpochCommitment<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let commitment = ProverCommitment::<H>::read_from(source)?;
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(EpochCommitment {
+            commitment,
+            evaluations,
+            proof,
+        })
+    }
+}
@@ -0,0 +1,300 @@
+use std::io::{self, Read, Write};
+
+use winter_crypto::{hashers::Blake3_256, Digest, Hasher};
+use winter_math::fields::f128::BaseElement;
+use winter_utils::{Deserializable, Serializable};
+
+use super::proof::{read_array, read_bytes, FridaProof, FridaProofBatchLayer, FridaProofLayer};
+
+// FRAMES
+// ================================================================================================
+
+/// Bytes of a frame ahead of its payload: its kind, index, frame count and payload length.
+const FRAME_HEADER_SIZE: usize = 9;
+/// Bytes of the checksum closing a frame.
+const CHECKSUM_SIZE: usize = 32;
+
+/// Part of a proof carried by a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    /// Whether the proof has a batch layer, its number of FRI layers and the log2 of its number
+    /// of partitions, one byte each.
+    Header = 0,
+    BatchLayer = 1,
+    Layer = 2,
+    Remainder = 3,
+}
+
+/// Encodes a frame as its kind, index and count of frames of the proof, payload length and
+/// payload, followed by the Blake3 checksum of all of these.
+fn encode_frame(kind: FrameKind, index: usize, count: usize, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len() + CHECKSUM_SIZE);
+    frame.push(kind as u8);
+    frame.extend_from_slice(&(index as u16).to_le_bytes());
+    frame.extend_from_slice(&(count as u16).to_le_bytes());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(payload);
+    let checksum = frame_checksum(&frame);
+    frame.extend_from_slice(&checksum);
+    frame
+}
+
+/// Blake3 digest of `bytes`, which the field of the hasher does not affect.
+fn frame_checksum(bytes: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    Blake3_256::<BaseElement>::hash(bytes).as_bytes()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// FRAMED PROOF WRITER
+// ================================================================================================
+
+/// Splits a proof into self-describing frames, one for the header of the proof, one per layer and
+/// one for its remainder, each closed by a checksum.
+///
+/// Frames are numbered, so that a transfer interrupted at any point can resume from the first
+/// frame the `FramedProofReader` on the other end is missing, as returned by its `next_frame()`.
+pub struct FramedProofWriter<'a> {
+    proof: &'a FridaProof,
+}
+
+impl<'a> FramedProofWriter<'a> {
+    pub fn new(proof: &'a FridaProof) -> Self {
+        FramedProofWriter { proof }
+    }
+
+    /// Returns the number of frames of the proof.
+    pub fn num_frames(&self) -> usize {
+        let (batch_layer, layers, ..) = self.proof.parts();
+        2 + usize::from(batch_layer.is_some()) + layers.len()
+    }
+
+    /// Returns frame `index` of the proof.
+    ///
+    /// # Panics
+    /// Panics if `index` is not smaller than `num_frames()`.
+    pub fn frame(&self, index: usize) -> Vec<u8> {
+        let count = self.num_frames();
+        assert!(index < count, "frame {index} of {count} does not exist");
+        let (batch_layer, layers, remainder, num_partitions) = self.proof.parts();
+        let first_layer = 1 + usize::from(batch_layer.is_some());
+
+        let (kind, payload) = match (index, batch_layer) {
+            (0, _) => {
+                let header = [
+                    batch_layer.is_some() as u8,
+                    layers.len() as u8,
+                    num_partitions,
+                ];
+                (FrameKind::Header, header.to_vec())
+            }
+            (index, _) if index == count - 1 => (FrameKind::Remainder, remainder.to_vec()),
+            (1, Some(batch_layer)) => (FrameKind::BatchLayer, batch_layer.to_bytes()),
+            (index, _) => (FrameKind::Layer, layers[index - first_layer].to_bytes()),
+        };
+        encode_frame(kind, index, count, &payload)
+    }
+
+    /// Writes every frame from frame `start` on to `target`, e.g. `start = 0` for a new transfer,
+    /// or the `next_frame()` of the receiving reader to resume an interrupted one.
+    pub fn write_frames<W: Write>(&self, target: &mut W, start: usize) -> io::Result<()> {
+        for index in start..self.num_frames() {
+            target.write_all(&self.frame(index))?;
+        }
+        target.flush()
+    }
+}
+
+// FRAMED PROOF READER
+// ================================================================================================
+
+/// Reassembles a proof from the frames of a `FramedProofWriter`, checking every frame as soon as
+/// it is received.
+///
+/// Frames must be received in order; a frame which is truncated, corrupted or out of order is
+/// rejected without altering the frames received so far, so that the transfer can resume from
+/// `next_frame()`.
+#[derive(Debug, Default)]
+pub struct FramedProofReader {
+    /// Number of frames of the proof, or 0 until its header frame is received.
+    count: usize,
+    has_batch_layer: bool,
+    num_partitions: u8,
+    batch_layer: Option<FridaProofBatchLayer>,
+    layers: Vec<FridaProofLayer>,
+    remainder: Option<Vec<u8>>,
+    next_frame: usize,
+}
+
+impl FramedProofReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the index of the next frame expected, from which an interrupted transfer resumes.
+    pub fn next_frame(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Returns true once every frame of the proof was received.
+    pub fn is_complete(&self) -> bool {
+        self.remainder.is_some()
+    }
+
+    /// Checks and accepts a single frame.
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if the frame is malformed, its checksum does not
+    /// match, it is not the next frame expected, or its payload is not a valid part of a proof.
+    pub fn push_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        if frame.len() < FRAME_HEADER_SIZE + CHECKSUM_SIZE {
+            return Err(invalid_data(format!(
+                "a frame holds at least {} bytes, but {} were received",
+                FRAME_HEADER_SIZE + CHECKSUM_SIZE,
+                frame.len()
+            )));
+        }
+        let (body, checksum) = frame.split_at(frame.len() - CHECKSUM_SIZE);
+        let length = u32::from_le_bytes(body[5..9].try_into().expect("header is 9 bytes")) as usize;
+        if body.len() != FRAME_HEADER_SIZE + length {
+            return Err(invalid_data(format!(
+                "frame announces {length} payload bytes, but holds {}",
+                body.len() - FRAME_HEADER_SIZE
+            )));
+        }
+        if frame_checksum(body) != checksum {
+            return Err(invalid_data("frame checksum does not match".to_string()));
+        }
+
+        let kind = body[0];
+        let index = u16::from_le_bytes([body[1], body[2]]) as usize;
+        let count = u16::from_le_bytes([body[3], body[4]]) as usize;
+        self.accept(kind, index, count, &body[FRAME_HEADER_SIZE..])
+    }
+
+    /// Reads a single frame from `source` and accepts it; see `push_frame`. No more bytes than
+    /// the frame spans are consumed.
+    ///
+    /// # Errors
+    /// Returns the errors of `push_frame`, and an error of kind `UnexpectedEof` if the stream
+    /// ends before the frame does.
+    pub fn read_frame<R: Read>(&mut self, source: &mut R) -> io::Result<()> {
+        let header = read_array::<R, FRAME_HEADER_SIZE>(source)?;
+        let length = u32::from_le_bytes(header[5..9].try_into().expect("header is 9 bytes"));
+        let mut frame = header.to_vec();
+        frame.extend(read_bytes(source, length as usize + CHECKSUM_SIZE)?);
+        self.push_frame(&frame)
+    }
+
+    /// Reads frames from `source` until the proof is complete.
+    ///
+    /// # Errors
+    /// Returns the errors of `read_frame`; the frames read before the error are kept.
+    pub fn read_frames<R: Read>(&mut self, source: &mut R) -> io::Result<()> {
+        while !self.is_complete() {
+            self.read_frame(source)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the proof carried by the frames received.
+    ///
+    /// # Errors
+    /// Returns an error of kind `UnexpectedEof` if some frames were not received yet.
+    pub fn finish(self) -> io::Result<FridaProof> {
+        let Some(remainder) = self.remainder else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "frame {} of {} was not received",
+                    self.next_frame, self.count
+                ),
+            ));
+        };
+        Ok(FridaProof::from_parts(
+            self.batch_layer,
+            self.layers,
+            remainder,
+            self.num_partitions,
+        ))
+    }
+
+    fn accept(&mut self, kind: u8, index: usize, count: usize, payload: &[u8]) -> io::Result<()> {
+        if self.is_complete() {
+            return Err(invalid_data(format!(
+                "every frame was received, but frame {index} was sent"
+            )));
+        }
+        if index != self.next_frame {
+            return Err(invalid_data(format!(
+                "expected frame {}, but received frame {index}",
+                self.next_frame
+            )));
+        }
+
+        if index == 0 {
+            let &[has_batch_layer, num_layers, num_partitions] = payload else {
+                return Err(invalid_data("header frame must hold 3 bytes".to_string()));
+            };
+            if kind != FrameKind::Header as u8 || has_batch_layer > 1 {
+                return Err(invalid_data(
+                    "first frame is not a valid header".to_string(),
+                ));
+            }
+            if count != 2 + has_batch_layer as usize + num_layers as usize {
+                return Err(invalid_data(format!(
+                    "header announces {num_layers} layers, but {count} frames"
+                )));
+            }
+            self.count = count;
+            self.has_batch_layer = has_batch_layer == 1;
+            self.num_partitions = num_partitions;
+            self.batch_layer = None;
+            self.layers = Vec::with_capacity(num_layers as usize);
+        } else {
+            if count != self.count {
+                return Err(invalid_data(format!(
+                    "frame {index} announces {count} frames instead of {}",
+                    self.count
+                )));
+            }
+            let expected_kind = if index == self.count - 1 {
+                FrameKind::Remainder
+            } else if index == 1 && self.has_batch_layer {
+                FrameKind::BatchLayer
+            } else {
+                FrameKind::Layer
+            };
+            if kind != expected_kind as u8 {
+                return Err(invalid_data(format!(
+                    "frame {index} is not a {expected_kind:?} frame"
+                )));
+            }
+
+            let parse_error = |e| invalid_data(format!("frame {index} is malformed: {e}"));
+            match expected_kind {
+                FrameKind::BatchLayer => {
+                    self.batch_layer =
+                        Some(FridaProofBatchLayer::read_from_bytes(payload).map_err(parse_error)?)
+                }
+                FrameKind::Layer => self
+                    .layers
+                    .push(FridaProofLayer::read_from_bytes(payload).map_err(parse_error)?),
+                FrameKind::Remainder => {
+                    if payload.len() > u16::MAX as usize {
+                        return Err(invalid_data(format!(
+                            "remainder of {} bytes cannot be serialized",
+                            payload.len()
+                        )));
+                    }
+                    self.remainder = Some(payload.to_vec());
+                }
+                FrameKind::Header => unreachable!("only the first frame is a header"),
+            }
+        }
+        self.next_frame += 1;
+        Ok(())
+    }
+}
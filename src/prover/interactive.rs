@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+
+use winter_crypto::{Digest, ElementHasher};
+use winter_fri::FriOptions;
+use winter_math::{fft, FieldElement};
+
+use super::{
+    builder::{commit_layer, fold_layer, FridaProverBuilder},
+    proof::FridaProof,
+    BatchLeafEncoding, FridaLayer, FridaProver, FridaRemainder, ProverLayer,
+};
+use crate::{core::schedule::layer_folding_factors, error::FridaError};
+
+// MESSAGES
+// ================================================================================================
+
+/// Party of an interactive run of the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Party {
+    Prover,
+    Verifier,
+}
+
+/// Message exchanged during an interactive run of the protocol, in which the verifier sends its
+/// challenges itself instead of both parties drawing them from a Fiat-Shamir transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message<E: FieldElement, D: Digest> {
+    /// Root of the layer committed by the prover in a commit round.
+    LayerRoot(D),
+    /// Challenge the verifier answers a layer root with, used to fold that layer.
+    Challenge(E),
+    /// Coefficients of the remainder polynomial, which end the commit phase.
+    Remainder(Vec<E>),
+    /// Positions queried by the verifier once the commit phase is over.
+    Queries(Vec<usize>),
+    /// Evaluations of the committed blob at the queried positions, and the proof opening them.
+    Answer {
+        evaluations: Vec<E>,
+        proof: FridaProof,
+    },
+}
+
+/// In-memory transport between the parties of an interactive run, which keeps a transcript of
+/// every message sent for later analysis.
+#[derive(Debug)]
+pub struct InMemoryTransport<E: FieldElement, D: Digest> {
+    pending: VecDeque<(Party, Message<E, D>)>,
+    transcript: Vec<(Party, Message<E, D>)>,
+}
+
+impl<E: FieldElement, D: Digest> Default for InMemoryTransport<E, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: FieldElement, D: Digest> InMemoryTransport<E, D> {
+    pub fn new() -> Self {
+        InMemoryTransport {
+            pending: VecDeque::new(),
+            transcript: Vec::new(),
+        }
+    }
+
+    /// Sends `message` from party `from` to the other party.
+    pub fn send(&mut self, from: Party, message: Message<E, D>) {
+        self.transcript.push((from, message.clone()));
+        self.pending.push_back((from, message));
+    }
+
+    /// Receives the oldest message sent to party `to`, if any.
+    pub fn receive(&mut self, to: Party) -> Option<Message<E, D>> {
+        let index = self.pending.iter().position(|(from, _)| *from != to)?;
+        self.pending.remove(index).map(|(_, message)| message)
+    }
+
+    /// Returns every message sent so far, along with its sender, in order.
+    pub fn transcript(&self) -> &[(Party, Message<E, D>)] {
+        &self.transcript
+    }
+}
+
+// INTERACTIVE PROVER
+// ================================================================================================
+
+/// Prover of a single blob taking part in an interactive run of the protocol.
+///
+/// Every commit round commits to the current layer, and the challenge received in answer folds
+/// it into the next one; once every layer is committed, the prover sends the remainder and
+/// answers the queries of the verifier. The state of every round can be inspected in between.
+#[derive(Debug)]
+pub struct InteractiveProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    options: FriOptions,
+    domain_size: usize,
+    folding_factors: Vec<usize>,
+    /// Evaluations of the layer to be committed next, in natural order.
+    evaluations: Vec<E>,
+    layers: Vec<FridaLayer<E, H>>,
+    challenges: Vec<E>,
+    /// Set once the remainder is sent.
+    prover: Option<FridaProver<E, H>>,
+}
+
+impl<E, H> InteractiveProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Reed-Solomon encodes `data` as `FridaProverBuilder::commit_and_prove` does, without
+    /// committing to it yet.
+    pub fn new(options: FriOptions, data: &[u8]) -> Result<Self, FridaError> {
+        let evaluations =
+            FridaProverBuilder::<E, H>::new(options.clone()).encode_evaluations(data, 1)?;
+        let domain_size = evaluations.len();
+        let folding_factors = layer_folding_factors(&options, &[], domain_size);
+
+        Ok(InteractiveProver {
+            options,
+            domain_size,
+            folding_factors,
+            evaluations,
+            layers: Vec::new(),
+            challenges: Vec::new(),
+            prover: None,
+        })
+    }
+
+    /// Handles a message of the verifier, or starts the run if `message` is `None`, and returns
+    /// the message to answer it with.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnexpectedMessage` if `message` does not fit the current round, and
+    /// the errors of `FridaProver::open` if the queried positions cannot be opened.
+    pub fn respond(
+        &mut self,
+        message: Option<Message<E, H::Digest>>,
+    ) -> Result<Message<E, H::Digest>, FridaError> {
+        match message {
+            None if self.layers.is_empty() && self.prover.is_none() => self.commit_round(),
+            Some(Message::Challenge(alpha)) if self.awaits_challenge() => {
+                self.fold(alpha)?;
+                self.commit_round()
+            }
+            Some(Message::Queries(positions)) if self.prover.is_some() => {
+                if let Some(&position) = positions.iter().find(|&&p| p >= self.domain_size) {
+                    return Err(FridaError::PositionOutOfDomain(position, self.domain_size));
+                }
+                self.answer(&positions)
+            }
+            _ => Err(FridaError::UnexpectedMessage),
+        }
+    }
+
+    /// Commits to the current layer, or to the remainder once every layer is committed.
+    fn commit_round(&mut self) -> Result<Message<E, H::Digest>, FridaError> {
+        let Some(&folding_factor) = self.folding_factors.get(self.layers.len()) else {
+            return Ok(self.commit_remainder());
+        };
+        let layer = match folding_factor {
+            2 => commit_layer::<E, H, 2>(&self.evaluations)?,
+            4 => commit_layer::<E, H, 4>(&self.evaluations)?,
+            8 => commit_layer::<E, H, 8>(&self.evaluations)?,
+            16 => commit_layer::<E, H, 16>(&self.evaluations)?,
+            _ => return Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
+        };
+        let root = *layer.tree.root();
+        self.layers.push(layer);
+        Ok(Message::LayerRoot(root))
+    }
+
+    /// Folds the last committed layer by `alpha` into the next layer.
+    fn fold(&mut self, alpha: E) -> Result<(), FridaError> {
+        let layer = self.layers.last().expect("a layer was committed");
+        let folding_factor = self.folding_factors[self.layers.len() - 1];
+        let offset = self.options.domain_offset();
+        self.evaluations = match folding_factor {
+            2 => fold_layer::<E, 2>(&layer.evaluations, offset, alpha),
+            4 => fold_layer::<E, 4>(&layer.evaluations, offset, alpha),
+            8 => fold_layer::<E, 8>(&layer.evaluations, offset, alpha),
+            16 => fold_layer::<E, 16>(&layer.evaluations, offset, alpha),
+            _ => return Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
+        };
+        self.challenges.push(alpha);
+        Ok(())
+    }
+
+    fn commit_remainder(&mut self) -> Message<E, H::Digest> {
+        let mut coefficients = self.evaluations.clone();
+        let inv_twiddles = fft::get_inv_twiddles(coefficients.len());
+        fft::interpolate_poly_with_offset(
+            &mut coefficients,
+            &inv_twiddles,
+            self.options.domain_offset(),
+        );
+        coefficients.truncate(coefficients.len() / self.options.blowup_factor());
+
+        self.prover = Some(FridaProver {
+            layers: self.layers.drain(..).map(ProverLayer::Full).collect(),
+            poly_count: 1,
+            remainder_poly: FridaRemainder(coefficients.clone()),
+            domain_size: self.domain_size,
+            folding_factors: self.folding_factors.clone(),
+            lazy: None,
+            ood_sample: None,
+            data_checksums: Vec::new(),
+            data_roots: None,
+            app_ids: Vec::new(),
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            dispersal_hint: None,
+            profile: None,
+            num_partitions: 1,
+        });
+        Message::Remainder(coefficients)
+    }
+
+    fn answer(&self, positions: &[usize]) -> Result<Message<E, H::Digest>, FridaError> {
+        let prover = self.prover.as_ref().expect("the remainder was sent");
+        Ok(Message::Answer {
+            evaluations: prover.get_evaluations(positions)?,
+            proof: prover.open(positions)?,
+        })
+    }
+
+    /// True while the last committed layer has not been folded yet.
+    fn awaits_challenge(&self) -> bool {
+        self.prover.is_none() && self.layers.len() > self.challenges.len()
+    }
+
+    // STATE
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluations of the layer to be committed in the next round, or of the polynomial the
+    /// remainder is interpolated from once every layer is committed.
+    pub fn current_evaluations(&self) -> &[E] {
+        &self.evaluations
+    }
+
+    /// Challenges received so far, one per folded layer.
+    pub fn challenges(&self) -> &[E] {
+        &self.challenges
+    }
+
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Number of layers committed before the remainder.
+    pub fn num_layers(&self) -> usize {
+        self.folding_factors.len()
+    }
+
+    /// Returns the prover of the committed layers once the remainder was sent, e.g. to open
+    /// further positions outside of the run.
+    pub fn prover(&self) -> Option<&FridaProver<E, H>> {
+        self.prover.as_ref()
+    }
+}
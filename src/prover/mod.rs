@@ -1,24 +1,69 @@
-use winter_crypto::{ElementHasher, Hasher, MerkleTree};
-use winter_fri::folding;
-use winter_math::FieldElement;
-use winter_utils::{
-    group_slice_elements, uninit_vector, ByteReader, Deserializable, DeserializationError,
-    Serializable,
-};
-// #[cfg(feature = "concurrent")]
-// use winter_utils::iterators::*;
+use std::collections::BTreeMap;
+
+use winter_crypto::{BatchMerkleProof, Digest, ElementHasher, Hasher, MerkleTree, MerkleTreeError};
+use winter_fri::{folding, utils::map_positions_to_indexes};
+use winter_math::{polynom, FieldElement, StarkField};
+#[cfg(feature = "concurrent")]
+use winter_utils::iterators::*;
+use winter_utils::{iter, ByteReader, Deserializable, DeserializationError, Serializable};
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod blob;
+pub mod bucketed;
 pub mod builder;
+pub mod byte_range;
+pub mod cache;
+pub mod cancel;
+pub mod cell;
 pub mod channel;
+pub mod cost;
+pub mod data_root;
+pub mod deadline;
+pub mod diff;
+pub mod disclosure;
+pub mod distributed;
+pub mod epoch;
+pub mod framed;
+pub mod interactive;
+pub mod multi_field;
+pub mod multiproof;
+pub mod pipeline;
+pub mod progress;
 pub mod proof;
+pub mod queue;
+pub mod receipt;
+pub mod retrievability;
+pub mod scheduler;
+#[cfg(feature = "f31")]
+pub mod small_field;
+pub mod stir;
+pub mod supersede;
+pub mod traits;
+pub mod value;
+
+pub use traits::FridaProvable;
 
 #[cfg(test)]
 mod tests;
 
 use crate::{
-    core::data::build_evaluations_from_data,
+    constants::{MAX_DOMAIN_SIZE, MIN_DOMAIN_SIZE},
+    core::{
+        data::{build_evaluations_with_encoding, EncodingOptions},
+        deep::{ood_evaluation_bytes, OodSample},
+    },
     error::FridaError,
-    prover::proof::{FridaProof, FridaProofBatchLayer, FridaProofLayer},
+    prover::{
+        cancel::CancellationToken,
+        data_root::{DataInclusionProof, DataRoots},
+        deadline::DispersalHint,
+        proof::{FridaProof, FridaProofBatchLayer, FridaProofLayer},
+        receipt::SampleReceipt,
+        scheduler::CommitProfile,
+    },
+    utils::{alloc::output_vector, encoding, parallel::ParallelOptions},
+    verifier::das::FridaDasVerifier,
 };
 
 /// Prover configured to work with specific data.
@@ -28,11 +73,26 @@ where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
-    layers: Vec<FridaLayer<E, H>>,
+    layers: Vec<ProverLayer<E, H>>,
     poly_count: usize,
     remainder_poly: FridaRemainder<E>,
     domain_size: usize,
-    folding_factor: usize,
+    /// Folding factor of every layer, the batch layer included.
+    folding_factors: Vec<usize>,
+    lazy: Option<LazyLayers<E>>,
+    /// Out-of-domain sample of the first layer, if built with `FridaProverBuilder::with_ood_check`.
+    ood_sample: Option<OodSample<E>>,
+    data_checksums: Vec<H::Digest>,
+    /// Roots over the raw chunks of every blob, if built with `FridaProverBuilder::with_data_roots`.
+    data_roots: Option<DataRoots<H>>,
+    /// Hash of the application id of every blob, or empty if no app ids were bound.
+    app_ids: Vec<H::Digest>,
+    batch_leaf_encoding: BatchLeafEncoding,
+    dispersal_hint: Option<DispersalHint>,
+    profile: Option<CommitProfile>,
+    /// Partitions the rows of every layer are split into in its Merkle tree; see
+    /// `FridaProverBuilder::with_num_partitions`.
+    num_partitions: usize,
 }
 
 #[derive(Debug)]
@@ -45,6 +105,87 @@ where
     pub evaluations: Vec<E>,
 }
 
+/// A layer of a prover, which may have been pruned down to the rows of a few positions, or
+/// reduced to its root until it is regenerated from the previous layer.
+#[derive(Debug)]
+enum ProverLayer<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    Full(FridaLayer<E, H>),
+    Pruned(PrunedLayer<E, H>),
+    Lazy(H::Digest),
+}
+
+/// The root of a layer along with the values and authentication path of each retained row.
+#[derive(Debug)]
+struct PrunedLayer<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    root: H::Digest,
+    rows: BTreeMap<usize, (Vec<E>, Vec<H::Digest>)>,
+}
+
+/// Parts of a prover to retain when pruning it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PruneLevel {
+    /// Keep only what is needed to open any subset of the given positions.
+    Positions(Vec<usize>),
+    /// Keep only the layer roots and the remainder, so that `commitment()` remains available but
+    /// no position can be opened anymore.
+    Commitment,
+}
+
+/// Which layers a prover keeps in memory after being built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayerStorage {
+    /// Keep the evaluations and Merkle trees of every layer, so that openings only query them.
+    #[default]
+    Full,
+    /// Keep only the first layer, the roots of the other layers, and the randomness they were
+    /// folded with. Every `open()` call folds and hashes the inner layers again, which roughly
+    /// halves the memory held by a prover, at a cost close to that of the commitment itself.
+    Lazy,
+}
+
+/// Proof system used to show that the committed evaluations are a Reed-Solomon codeword.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Protocol {
+    /// FRI, as used by every commitment of `FridaProverBuilder` but `commit_and_prove_stir`.
+    #[default]
+    Fri,
+    /// Experimental STIR mode. Every round folds by the folding factor but only halves the
+    /// domain, and binds the folded polynomial with an out-of-domain sample, so that the rate
+    /// improves from round to round and later rounds need fewer queries than in FRI.
+    Stir,
+}
+
+/// How the leaves of the first layer of a batched commitment hash the values they hold, i.e. the
+/// `folding_factor` evaluations of every one of the `poly_count` blobs at a row of the domain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchLeafEncoding {
+    /// A leaf is the hash of all of its values, so that opening it reveals every blob.
+    #[default]
+    Flat,
+    /// A leaf is the hash of the digests of the values of every blob, so that one blob can be
+    /// opened with the digests of the others; see `FridaProver::open_blob`.
+    PerBlob,
+}
+
+/// Folding randomness needed to regenerate the inner layers of a prover built with
+/// `LayerStorage::Lazy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LazyLayers<E: FieldElement> {
+    /// Alpha used to fold each layer into the next one.
+    alphas: Vec<E>,
+    /// Batching coefficients of the first layer, empty if it is not batched.
+    xi: Vec<E>,
+    domain_offset: E::BaseField,
+}
+
 #[derive(Debug, Clone)]
 pub struct FridaRemainder<E: FieldElement>(Vec<E>);
 
@@ -56,6 +197,21 @@ pub struct Commitment<HRoot: ElementHasher> {
     pub domain_size: usize,
     pub num_queries: usize,
     pub poly_count: usize,
+    /// Serialized out-of-domain evaluation of the first layer, empty unless the commitment was
+    /// built with `FridaProverBuilder::with_ood_check`.
+    pub ood_evaluation: Vec<u8>,
+    /// Leaf encoding of the first layer, as set with `FridaProverBuilder::with_batch_leaf_encoding`;
+    /// always `Flat` unless the commitment is batched.
+    pub batch_leaf_encoding: BatchLeafEncoding,
+    /// Partitions the rows of every layer are split into in its Merkle tree, as set with
+    /// `FridaProverBuilder::with_num_partitions`; proofs must be made for the same number.
+    pub num_partitions: usize,
+    /// Merkle roots over the raw chunks of every committed blob, if the commitment was built with
+    /// `FridaProverBuilder::with_data_roots`; bound by its root digest.
+    pub data_roots: Option<DataRoots<HRoot>>,
+    /// FRI parameters of the commitment, if it was built with
+    /// `FridaProverBuilder::with_commit_profile`; bound by its root digest.
+    pub profile: Option<CommitProfile>,
 }
 
 /// A commitment to the data, containing only the Merkle roots and metadata.
@@ -65,6 +221,69 @@ pub struct ProverCommitment<H: Hasher> {
     pub roots: Vec<H::Digest>,
     pub domain_size: usize,
     pub poly_count: usize,
+    /// Hash of the raw bytes of every committed blob, or empty if the commitment was built
+    /// without `FridaProverBuilder::with_data_checksums`.
+    pub data_checksums: Vec<H::Digest>,
+    /// Root of the tree of app id hashes of the blobs, if the commitment was built with
+    /// `FridaProverBuilder::commitment_batch_with_app_ids`.
+    pub app_ids_root: Option<H::Digest>,
+    /// Serialized out-of-domain evaluation of the first layer, empty unless the commitment was
+    /// built with `FridaProverBuilder::with_ood_check`.
+    pub ood_evaluation: Vec<u8>,
+    /// Leaf encoding of the first layer; always `Flat` unless the commitment is batched.
+    pub batch_leaf_encoding: BatchLeafEncoding,
+    /// Partitions the rows of every layer are split into in its Merkle tree; see
+    /// `FridaProverBuilder::with_num_partitions`.
+    pub num_partitions: usize,
+    /// Dispersal deadline and expected share count, if the commitment was built with
+    /// `FridaProverBuilder::with_dispersal_hint`.
+    pub dispersal_hint: Option<DispersalHint>,
+    /// Merkle roots over the raw chunks of every committed blob, if the commitment was built with
+    /// `FridaProverBuilder::with_data_roots`.
+    pub data_roots: Option<DataRoots<H>>,
+    /// FRI parameters of the commitment, if it was built with
+    /// `FridaProverBuilder::with_commit_profile`, e.g. by a `scheduler::CommitmentScheduler`.
+    pub profile: Option<CommitProfile>,
+}
+
+impl<H: Hasher> ProverCommitment<H> {
+    /// Checks that `data`, as decoded by a reconstructing client, hashes to the checksum of blob
+    /// `blob`.
+    ///
+    /// Checksums are bound by neither the root digest nor the binding of the commitment, so they
+    /// only catch data decoded wrongly, not data or checksums replaced by whoever relays the
+    /// commitment; use `verify_data_inclusion` against data roots for that.
+    ///
+    /// # Errors
+    /// Returns `FridaError::DataChecksumsNotCommitted` if the commitment carries no checksums,
+    /// `FridaError::BlobOutOfRange` if `blob` is not one of the committed blobs, and
+    /// `FridaError::DataChecksumMismatch` if `data` does not hash to the checksum of the blob.
+    pub fn check_data(&self, blob: usize, data: &[u8]) -> Result<(), FridaError> {
+        if self.data_checksums.is_empty() {
+            return Err(FridaError::DataChecksumsNotCommitted);
+        }
+        let checksum = self
+            .data_checksums
+            .get(blob)
+            .ok_or(FridaError::BlobOutOfRange(blob, self.poly_count))?;
+        if *checksum != H::hash(data) {
+            return Err(FridaError::DataChecksumMismatch(blob));
+        }
+        Ok(())
+    }
+
+    /// Checks that `proof` opens raw chunks of one of the committed blobs against the data roots
+    /// of the commitment.
+    ///
+    /// # Errors
+    /// Returns `FridaError::DataRootsNotCommitted` if the commitment carries no data roots, and
+    /// the errors of `DataRoots::verify` otherwise.
+    pub fn verify_data_inclusion(&self, proof: &DataInclusionProof) -> Result<(), FridaError> {
+        self.data_roots
+            .as_ref()
+            .ok_or(FridaError::DataRootsNotCommitted)?
+            .verify(proof)
+    }
 }
 
 impl<H: Hasher> Serializable for ProverCommitment<H>
@@ -75,6 +294,38 @@ where
         self.roots.write_into(target);
         self.domain_size.write_into(target);
         self.poly_count.write_into(target);
+        self.data_checksums.write_into(target);
+        match &self.app_ids_root {
+            Some(root) => {
+                target.write_u8(1);
+                root.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        self.ood_evaluation.write_into(target);
+        self.batch_leaf_encoding.write_into(target);
+        self.num_partitions.write_into(target);
+        match &self.dispersal_hint {
+            Some(hint) => {
+                target.write_u8(1);
+                hint.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        match &self.data_roots {
+            Some(roots) => {
+                target.write_u8(1);
+                roots.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        match &self.profile {
+            Some(profile) => {
+                target.write_u8(1);
+                profile.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
     }
 }
 
@@ -84,17 +335,190 @@ where
 {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let roots = Vec::<H::Digest>::read_from(source)?;
-        let domain_size = usize::read_from(source)?;
+        let domain_size = read_domain_size(source)?;
         let poly_count = usize::read_from(source)?;
+        let data_checksums = Vec::<H::Digest>::read_from(source)?;
+        let app_ids_root = match read_presence_tag(source, "app ids root")? {
+            true => Some(H::Digest::read_from(source)?),
+            false => None,
+        };
+        let ood_evaluation = Vec::<u8>::read_from(source)?;
+        let batch_leaf_encoding = BatchLeafEncoding::read_from(source)?;
+        let num_partitions = read_num_partitions(source)?;
+        let dispersal_hint = match read_presence_tag(source, "dispersal hint")? {
+            true => Some(DispersalHint::read_from(source)?),
+            false => None,
+        };
+        let data_roots = match read_presence_tag(source, "data roots")? {
+            true => Some(DataRoots::read_from(source)?),
+            false => None,
+        };
+        let profile = match read_presence_tag(source, "profile")? {
+            true => Some(CommitProfile::read_from(source)?),
+            false => None,
+        };
 
         Ok(ProverCommitment {
             roots,
             domain_size,
             poly_count,
+            data_checksums,
+            app_ids_root,
+            ood_evaluation,
+            batch_leaf_encoding,
+            num_partitions,
+            dispersal_hint,
+            data_roots,
+            profile,
         })
     }
 }
 
+impl<H: ElementHasher> ProverCommitment<H> {
+    /// Re-attaches `proof`, made for `num_queries` queries, to a commitment stripped with
+    /// `Commitment::strip_proof`. Data checksums, app ids and the dispersal hint are dropped, as
+    /// `Commitment` does not carry them, and the root digest of the result is the one of the
+    /// commitment without them.
+    ///
+    /// The proof is not checked against the roots; verifiers do so when building from the result
+    /// with `FridaDasVerifier::new`.
+    pub fn attach_proof(self, proof: FridaProof, num_queries: usize) -> Commitment<H> {
+        Commitment {
+            roots: self.roots,
+            proof,
+            domain_size: self.domain_size,
+            num_queries,
+            poly_count: self.poly_count,
+            ood_evaluation: self.ood_evaluation,
+            batch_leaf_encoding: self.batch_leaf_encoding,
+            num_partitions: self.num_partitions,
+            data_roots: self.data_roots,
+            profile: self.profile,
+        }
+    }
+}
+
+/// String encodings of the serialized commitment, for JSON-RPC payloads, log lines and CLIs.
+impl<H: Hasher> ProverCommitment<H> {
+    pub fn to_hex(&self) -> String {
+        encoding::to_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, DeserializationError> {
+        let bytes = encoding::from_hex(hex)
+            .ok_or_else(|| DeserializationError::InvalidValue("invalid hex string".to_string()))?;
+        Self::read_from_bytes(&bytes)
+    }
+
+    pub fn to_base64(&self) -> String {
+        encoding::to_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(base64: &str) -> Result<Self, DeserializationError> {
+        let bytes = encoding::from_base64(base64).ok_or_else(|| {
+            DeserializationError::InvalidValue("invalid base64 string".to_string())
+        })?;
+        Self::read_from_bytes(&bytes)
+    }
+
+    /// Returns a short identifier for operators to tell commitments apart: the hex encoding of
+    /// the first 8 bytes of the hash chain of the roots.
+    pub fn fingerprint(&self) -> String {
+        let chain = self.roots.iter().fold(H::Digest::default(), |chain, root| {
+            H::merge(&[chain, *root])
+        });
+        encoding::to_hex(&chain.as_bytes()[..8])
+    }
+
+    /// Returns the canonical identifier of this commitment, binding the roots of all of its layers
+    /// and the parameters they were committed with, e.g. to key stored commitments or to sign
+    /// attestations.
+    ///
+    /// The digest is the byte encoding of `binding`, which hashes every field but the data
+    /// checksums as in `Serializable`, with length prefixes and presence tags; verifiers recompute
+    /// it with `FridaDasVerifier::check_root_digest`.
+    pub fn root_digest(&self) -> [u8; 32] {
+        self.binding().as_bytes()
+    }
+}
+
+impl<HRoot: ElementHasher> Commitment<HRoot> {
+    /// Returns the root digest of this commitment, equal to the one of the matching
+    /// `ProverCommitment` without app ids or dispersal hint, which `Commitment` does not carry;
+    /// see `ProverCommitment::root_digest`.
+    pub fn root_digest(&self) -> [u8; 32] {
+        ProverCommitment::<HRoot> {
+            roots: self.roots.clone(),
+            domain_size: self.domain_size,
+            poly_count: self.poly_count,
+            data_checksums: Vec::new(),
+            app_ids_root: None,
+            ood_evaluation: self.ood_evaluation.clone(),
+            batch_leaf_encoding: self.batch_leaf_encoding,
+            num_partitions: self.num_partitions,
+            dispersal_hint: None,
+            data_roots: self.data_roots.clone(),
+            profile: self.profile,
+        }
+        .root_digest()
+    }
+
+    /// Drops the proof of this commitment, keeping its roots and metadata, e.g. for archival nodes
+    /// which do not re-verify commitments they already accepted. `Commitment` carries no data
+    /// checksums, app ids or dispersal hint, which are left empty in the result; the root digest
+    /// is unchanged. The proof can be attached back with
+    /// `ProverCommitment::attach_proof`.
+    pub fn strip_proof(self) -> ProverCommitment<HRoot> {
+        ProverCommitment {
+            roots: self.roots,
+            domain_size: self.domain_size,
+            poly_count: self.poly_count,
+            data_checksums: Vec::new(),
+            app_ids_root: None,
+            ood_evaluation: self.ood_evaluation,
+            batch_leaf_encoding: self.batch_leaf_encoding,
+            num_partitions: self.num_partitions,
+            dispersal_hint: None,
+            data_roots: self.data_roots,
+            profile: self.profile,
+        }
+    }
+
+    /// Same as `strip_proof`, keeping this commitment.
+    pub fn to_prover_commitment(&self) -> ProverCommitment<HRoot> {
+        ProverCommitment {
+            roots: self.roots.clone(),
+            domain_size: self.domain_size,
+            poly_count: self.poly_count,
+            data_checksums: Vec::new(),
+            app_ids_root: None,
+            ood_evaluation: self.ood_evaluation.clone(),
+            batch_leaf_encoding: self.batch_leaf_encoding,
+            num_partitions: self.num_partitions,
+            dispersal_hint: None,
+            data_roots: self.data_roots.clone(),
+            profile: self.profile,
+        }
+    }
+
+    /// Returns the distinct positions opened by the proof of this commitment, sorted, e.g. to
+    /// audit which samples a commitment vouches for.
+    ///
+    /// Proofs do not serialize the positions they open: these are recovered from the transcript
+    /// the proof is bound to, drawn with `HHst` over field `E`. That the proof actually opens them
+    /// is not checked; see `FridaDasVerifier::verify_commitment_positions`.
+    pub fn covered_positions<E, HHst>(&self) -> Result<Vec<usize>, FridaError>
+    where
+        E: FieldElement<BaseField = HRoot::BaseField>,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+    {
+        let mut positions = FridaDasVerifier::<E, HHst, HRoot>::replay_positions(self)?;
+        positions.sort_unstable();
+        positions.dedup();
+        Ok(positions)
+    }
+}
+
 impl<HRoot: ElementHasher> Serializable for Commitment<HRoot>
 where
     HRoot::Digest: Serializable,
@@ -105,11 +529,44 @@ where
         self.domain_size.write_into(target);
         self.num_queries.write_into(target);
         self.poly_count.write_into(target);
+        self.ood_evaluation.write_into(target);
+        self.batch_leaf_encoding.write_into(target);
+        self.num_partitions.write_into(target);
+        match &self.data_roots {
+            Some(roots) => {
+                target.write_u8(1);
+                roots.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        match &self.profile {
+            Some(profile) => {
+                target.write_u8(1);
+                profile.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
     }
 
     fn get_size_hint(&self) -> usize {
-        // 24 + 104 + 8 + 8 + 8
-        152
+        // roots may come from any hasher, so their size is taken from the digests themselves
+        let digest_size = self.roots.first().map_or(0, |root| root.to_bytes().len());
+        self.roots.len().get_size_hint()
+            + self.roots.len() * digest_size
+            + self.proof.size()
+            + self.domain_size.get_size_hint()
+            + self.num_queries.get_size_hint()
+            + self.poly_count.get_size_hint()
+            + self.ood_evaluation.get_size_hint()
+            + 1
+            + self.num_partitions.get_size_hint()
+            + 1
+            + self
+                .data_roots
+                .as_ref()
+                .map_or(0, |roots| roots.to_bytes().len())
+            + 1
+            + self.profile.map_or(0, |profile| profile.to_bytes().len())
     }
 }
 
@@ -120,9 +577,20 @@ where
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let roots = Vec::<HRoot::Digest>::read_from(source)?;
         let proof = FridaProof::read_from(source)?;
-        let domain_size = usize::read_from(source)?;
+        let domain_size = read_domain_size(source)?;
         let num_queries = usize::read_from(source)?;
         let poly_count = usize::read_from(source)?;
+        let ood_evaluation = Vec::<u8>::read_from(source)?;
+        let batch_leaf_encoding = BatchLeafEncoding::read_from(source)?;
+        let num_partitions = read_num_partitions(source)?;
+        let data_roots = match read_presence_tag(source, "data roots")? {
+            true => Some(DataRoots::read_from(source)?),
+            false => None,
+        };
+        let profile = match read_presence_tag(source, "profile")? {
+            true => Some(CommitProfile::read_from(source)?),
+            false => None,
+        };
 
         Ok(Commitment {
             roots,
@@ -130,10 +598,275 @@ where
             domain_size,
             num_queries,
             poly_count,
+            ood_evaluation,
+            batch_leaf_encoding,
+            num_partitions,
+            data_roots,
+            profile,
         })
     }
 }
 
+// PROVER SNAPSHOTS
+// ================================================================================================
+
+/// A prover is serialized as the leaves and evaluations of every full layer, the root and
+/// retained rows of every pruned one, and the root of every lazy one along with the randomness
+/// needed to regenerate it; the inner nodes of the Merkle trees of full layers are recomputed when
+/// it is read back.
+impl<E, H> Serializable for FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn write_into<W: winter_utils::ByteWriter>(&self, target: &mut W) {
+        self.layers.len().write_into(target);
+        for layer in self.layers.iter() {
+            match layer {
+                ProverLayer::Full(layer) => {
+                    target.write_u8(0);
+                    layer.tree.leaves().write_into(target);
+                    layer.evaluations.write_into(target);
+                }
+                ProverLayer::Pruned(layer) => {
+                    target.write_u8(1);
+                    layer.root.write_into(target);
+                    layer.rows.len().write_into(target);
+                    for (index, (values, path)) in layer.rows.iter() {
+                        index.write_into(target);
+                        values.write_into(target);
+                        path.write_into(target);
+                    }
+                }
+                ProverLayer::Lazy(root) => {
+                    target.write_u8(2);
+                    root.write_into(target);
+                }
+            }
+        }
+        self.remainder_poly.0.write_into(target);
+        self.poly_count.write_into(target);
+        self.domain_size.write_into(target);
+        self.folding_factors.write_into(target);
+        match &self.lazy {
+            Some(lazy) => {
+                target.write_u8(1);
+                lazy.alphas.write_into(target);
+                lazy.xi.write_into(target);
+                lazy.domain_offset.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        match &self.ood_sample {
+            Some(sample) => {
+                target.write_u8(1);
+                sample.point.write_into(target);
+                sample.value.write_into(target);
+                sample.comb.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        self.data_checksums.write_into(target);
+        match &self.data_roots {
+            Some(roots) => {
+                target.write_u8(1);
+                roots.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        self.app_ids.write_into(target);
+        self.batch_leaf_encoding.write_into(target);
+        match &self.dispersal_hint {
+            Some(hint) => {
+                target.write_u8(1);
+                hint.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        match &self.profile {
+            Some(profile) => {
+                target.write_u8(1);
+                profile.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+        self.num_partitions.write_into(target);
+    }
+}
+
+impl<E, H> Deserializable for FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_layers = usize::read_from(source)?;
+        let layers = (0..num_layers)
+            .map(|_| match source.read_u8()? {
+                0 => {
+                    let leaves = Vec::<H::Digest>::read_from(source)?;
+                    let tree = MerkleTree::new(leaves)
+                        .map_err(|e| DeserializationError::InvalidValue(e.to_string()))?;
+                    let evaluations = Vec::<E>::read_from(source)?;
+                    Ok(ProverLayer::Full(FridaLayer { tree, evaluations }))
+                }
+                1 => {
+                    let root = H::Digest::read_from(source)?;
+                    let num_rows = usize::read_from(source)?;
+                    let rows = (0..num_rows)
+                        .map(|_| {
+                            let index = usize::read_from(source)?;
+                            let values = Vec::<E>::read_from(source)?;
+                            let path = Vec::<H::Digest>::read_from(source)?;
+                            Ok((index, (values, path)))
+                        })
+                        .collect::<Result<_, DeserializationError>>()?;
+                    Ok(ProverLayer::Pruned(PrunedLayer { root, rows }))
+                }
+                2 => Ok(ProverLayer::Lazy(H::Digest::read_from(source)?)),
+                tag => Err(DeserializationError::InvalidValue(format!(
+                    "unknown prover layer tag {tag}"
+                ))),
+            })
+            .collect::<Result<Vec<_>, DeserializationError>>()?;
+        let remainder_poly = FridaRemainder(Vec::<E>::read_from(source)?);
+        let poly_count = usize::read_from(source)?;
+        let domain_size = read_domain_size(source)?;
+        let folding_factors = Vec::<usize>::read_from(source)?;
+        let lazy = match read_presence_tag(source, "lazy layers")? {
+            false => None,
+            true => Some(LazyLayers {
+                alphas: Vec::<E>::read_from(source)?,
+                xi: Vec::<E>::read_from(source)?,
+                domain_offset: E::BaseField::read_from(source)?,
+            }),
+        };
+        let ood_sample = match read_presence_tag(source, "out-of-domain sample")? {
+            false => None,
+            true => Some(OodSample {
+                point: E::read_from(source)?,
+                value: E::read_from(source)?,
+                comb: E::read_from(source)?,
+            }),
+        };
+        let data_checksums = Vec::<H::Digest>::read_from(source)?;
+        let data_roots = match read_presence_tag(source, "data roots")? {
+            true => Some(DataRoots::read_from(source)?),
+            false => None,
+        };
+        let app_ids = Vec::<H::Digest>::read_from(source)?;
+        let batch_leaf_encoding = BatchLeafEncoding::read_from(source)?;
+        let dispersal_hint = match read_presence_tag(source, "dispersal hint")? {
+            true => Some(DispersalHint::read_from(source)?),
+            false => None,
+        };
+        let profile = match read_presence_tag(source, "profile")? {
+            true => Some(CommitProfile::read_from(source)?),
+            false => None,
+        };
+        let num_partitions = read_num_partitions(source)?;
+
+        let prover = FridaProver {
+            layers,
+            poly_count,
+            remainder_poly,
+            domain_size,
+            folding_factors,
+            lazy,
+            ood_sample,
+            data_checksums,
+            data_roots,
+            app_ids,
+            batch_leaf_encoding,
+            dispersal_hint,
+            profile,
+            num_partitions,
+        };
+        prover.check_snapshot()?;
+        Ok(prover)
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Checks that the parts of a prover read from a snapshot fit together, so that a corrupted
+    /// or forged snapshot is rejected when it is read rather than when the prover opens positions.
+    fn check_snapshot(&self) -> Result<(), DeserializationError> {
+        let invalid = |message: &str| Err(DeserializationError::InvalidValue(message.to_string()));
+        // only single blobs may be committed directly to their remainder
+        if self.poly_count == 0 || (self.layers.is_empty() && self.poly_count > 1) {
+            return invalid("prover has no layers for its polynomials");
+        }
+        if self.folding_factors.len() != self.layers.len() {
+            return invalid("prover folding factors do not match its layers");
+        }
+        let num_layers = self.layers.len();
+        match &self.lazy {
+            Some(lazy) if lazy.alphas.len() != num_layers => {
+                return invalid("prover folding randomness does not match its layers");
+            }
+            None if self
+                .layers
+                .iter()
+                .any(|layer| matches!(layer, ProverLayer::Lazy(_))) =>
+            {
+                return invalid("prover has lazy layers but no randomness to regenerate them");
+            }
+            _ => {}
+        }
+        if ![0, self.poly_count].contains(&self.data_checksums.len())
+            || ![0, self.poly_count].contains(&self.app_ids.len())
+        {
+            return invalid("prover checksums or app ids do not match its polynomials");
+        }
+
+        let mut domain_size = self.domain_size;
+        for (depth, (layer, &folding_factor)) in
+            self.layers.iter().zip(&self.folding_factors).enumerate()
+        {
+            if ![2, 4, 8, 16].contains(&folding_factor) || domain_size < folding_factor {
+                return invalid("prover layer cannot be folded by its folding factor");
+            }
+            let num_rows = domain_size / folding_factor;
+            let row_size = match depth {
+                0 => self.poly_count * folding_factor,
+                _ => folding_factor,
+            };
+            let fits = match layer {
+                ProverLayer::Full(layer) => {
+                    layer.tree.leaves().len() == num_rows
+                        && layer.evaluations.len() == num_rows * row_size
+                }
+                ProverLayer::Pruned(layer) => layer
+                    .rows
+                    .iter()
+                    .all(|(&index, (values, _))| index < num_rows && values.len() == row_size),
+                // lazy layers are regenerated from the full or regenerated layer before them
+                ProverLayer::Lazy(_) => {
+                    if depth == 0 || matches!(self.layers[depth - 1], ProverLayer::Pruned(_)) {
+                        return invalid("prover lazy layer has no layer to be regenerated from");
+                    }
+                    true
+                }
+            };
+            if !fits {
+                return invalid("prover layer does not match its domain");
+            }
+            domain_size = num_rows;
+        }
+        if num_layers > 0 && self.num_partitions > domain_size {
+            return invalid("prover has more partitions than rows in its last layer");
+        }
+        if self.remainder_poly.0.len() > domain_size {
+            return invalid("prover remainder exceeds its domain");
+        }
+        Ok(())
+    }
+}
+
 #[cfg(feature = "bench")]
 pub mod bench {
     use std::time::{Duration, Instant};
@@ -151,101 +884,668 @@ where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
-    /// Opens given position, building a proof for it.
-    pub fn open(&self, positions: &[usize]) -> FridaProof {
-        let folding_factor = self.folding_factor;
-        let layers_len = self.layers.len();
-        let is_batch = self.poly_count > 1;
+    /// Opens given position, building a proof for it. Layers are queried in parallel with the
+    /// `concurrent` feature, unless the prover holds lazy layers; the proof is the same either way.
+    ///
+    /// # Errors
+    /// Returns `FridaError::NoPositions` if `positions` is empty,
+    /// `FridaError::PositionOutOfDomain` if a position lies outside of the domain, and
+    /// `FridaError::PositionPruned` if the prover was pruned and a position was not retained.
+    pub fn open(&self, positions: &[usize]) -> Result<FridaProof, FridaError> {
+        self.open_cancellable(positions, None)
+    }
 
-        let (layers, batch_layer) = {
-            let mut positions = positions.to_vec();
-            let mut domain_size = self.domain_size;
+    /// Same as `open`, checking `token` before opening every layer, so that large openings can
+    /// be aborted.
+    ///
+    /// # Errors
+    /// Returns `FridaError::Cancelled` once `token` is cancelled, and the errors of `open`.
+    pub fn open_with_cancellation(
+        &self,
+        positions: &[usize],
+        token: &CancellationToken,
+    ) -> Result<FridaProof, FridaError> {
+        self.open_cancellable(positions, Some(token))
+    }
 
-            let batch_layer = if is_batch {
-                positions = folding::fold_positions(&positions, domain_size, folding_factor);
-                let proof = self.layers[0]
-                    .tree
-                    .prove_batch(&positions)
-                    .expect("failed to generate a Merkle proof for FRI layer queries");
-                let evaluations = &self.layers[0].evaluations;
-                let bucket_size = self.poly_count * folding_factor;
-                let mut queried_values: Vec<E> = Vec::with_capacity(positions.len() * bucket_size);
-                for &position in positions.iter() {
-                    evaluations[bucket_size * position..bucket_size * position + bucket_size]
-                        .iter()
-                        .for_each(|e| {
-                            queried_values.push(*e);
-                        });
+    fn open_cancellable(
+        &self,
+        positions: &[usize],
+        token: Option<&CancellationToken>,
+    ) -> Result<FridaProof, FridaError> {
+        let check = || token.map_or(Ok(()), CancellationToken::check);
+        check()?;
+        self.check_positions(positions)?;
+        let layer_positions = self.layer_positions(positions);
+        let layer_indexes = self.layer_indexes(&layer_positions);
+        let start = usize::from(self.poly_count > 1);
+        let batch_layer = (start == 1)
+            .then(|| self.query_batch_layer(&layer_positions[0], &layer_indexes[0]))
+            .transpose()?;
+
+        // once positions are folded, layers are queried independently of each other, in parallel
+        // with the `concurrent` feature; lazy layers are regenerated in order instead
+        let has_lazy_layers = self
+            .layers
+            .iter()
+            .any(|layer| matches!(layer, ProverLayer::Lazy(_)));
+        let layers = if has_lazy_layers {
+            let mut layers = Vec::with_capacity(self.layers.len() - start);
+            self.visit_layers(|i, layer| {
+                if i >= start {
+                    check()?;
+                    let (positions, indexes) = (&layer_positions[i], &layer_indexes[i]);
+                    let folding_factor = self.folding_factors[i];
+                    layers.push(query_fri_layer(layer, positions, indexes, folding_factor)?);
                 }
+                Ok(())
+            })?;
+            layers
+        } else {
+            iter!(self.layers[start..])
+                .zip(iter!(layer_positions[start..]))
+                .zip(iter!(layer_indexes[start..]))
+                .zip(iter!(self.folding_factors[start..]))
+                .map(|(((layer, positions), indexes), &folding_factor)| {
+                    check()?;
+                    query_fri_layer(layer, positions, indexes, folding_factor)
+                })
+                .collect::<Result<Vec<_>, FridaError>>()?
+        };
+
+        // use the remaining polynomial values directly as proof
+        let remainder = self.remainder_poly.0.clone();
+        Ok(FridaProof::new(
+            batch_layer,
+            layers,
+            remainder,
+            self.num_partitions,
+        ))
+    }
+
+    /// Returns `positions` folded into the rows of every layer they are opened at.
+    fn layer_positions(&self, positions: &[usize]) -> Vec<Vec<usize>> {
+        let mut positions = positions.to_vec();
+        let mut domain_size = self.domain_size;
+        self.folding_factors
+            .iter()
+            .map(|&folding_factor| {
+                positions = folding::fold_positions(&positions, domain_size, folding_factor);
                 domain_size /= folding_factor;
-                Some(FridaProofBatchLayer::new(queried_values, proof))
+                positions.clone()
+            })
+            .collect()
+    }
+
+    /// Returns the leaves committing to the rows of every layer in `layer_positions`.
+    fn layer_indexes(&self, layer_positions: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        let mut row_count = self.domain_size;
+        layer_positions
+            .iter()
+            .zip(self.folding_factors.iter())
+            .map(|(rows, &folding_factor)| {
+                row_count /= folding_factor;
+                self.leaf_indexes(rows, row_count)
+            })
+            .collect()
+    }
+
+    /// Returns the leaves committing to `rows` in a layer of `row_count` rows, which differ from
+    /// the rows themselves if the layer is split into several partitions.
+    fn leaf_indexes(&self, rows: &[usize], row_count: usize) -> Vec<usize> {
+        map_positions_to_indexes(rows, row_count, 1, self.num_partitions)
+    }
+
+    /// Queries the first layer of a batched commitment, whose rows hold the evaluations of every
+    /// polynomial, at the given (folded) positions, committed to by the leaves at `indexes`.
+    fn query_batch_layer(
+        &self,
+        positions: &[usize],
+        indexes: &[usize],
+    ) -> Result<FridaProofBatchLayer, FridaError> {
+        let layer = &self.layers[0];
+        let proof = layer.prove(positions, indexes)?;
+        let bucket_size = self.poly_count * self.folding_factors[0];
+        let mut queried_values: Vec<E> = Vec::with_capacity(positions.len() * bucket_size);
+        for &position in positions.iter() {
+            queried_values.extend_from_slice(layer.row(position, bucket_size)?);
+        }
+        Ok(FridaProofBatchLayer::new(queried_values, proof))
+    }
+
+    /// Opens a single position as a self-contained receipt, bound to the commitment of this
+    /// prover.
+    ///
+    /// # Errors
+    /// Returns the errors of `open()`.
+    pub fn sample_receipt(&self, position: usize) -> Result<SampleReceipt<E, H>, FridaError> {
+        Ok(SampleReceipt {
+            position,
+            evaluations: self.get_evaluations(&[position])?,
+            proof: self.open(&[position])?,
+            binding: self.commitment().binding(),
+        })
+    }
+
+    /// Opens each of `positions` as a self-contained receipt, e.g. to answer the positions of a
+    /// `verifier::oblivious::ObliviousQuery` without learning which of them are sampled.
+    ///
+    /// # Errors
+    /// Returns the errors of `open()` for the first position which cannot be opened.
+    pub fn sample_receipts(
+        &self,
+        positions: &[usize],
+    ) -> Result<Vec<SampleReceipt<E, H>>, FridaError> {
+        let binding = self.commitment().binding();
+        positions
+            .iter()
+            .map(|&position| {
+                Ok(SampleReceipt {
+                    position,
+                    evaluations: self.get_evaluations(&[position])?,
+                    proof: self.open(&[position])?,
+                    binding,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the commitment to the layers of this prover, which matches the commitment
+    /// produced by the builder alongside it.
+    pub fn commitment(&self) -> ProverCommitment<H> {
+        let mut roots = self
+            .layers
+            .iter()
+            .map(|layer| layer.root())
+            .collect::<Vec<_>>();
+        roots.push(H::hash_elements(&self.remainder_poly.0));
+
+        ProverCommitment {
+            roots,
+            domain_size: self.domain_size,
+            poly_count: self.poly_count,
+            data_checksums: self.data_checksums.clone(),
+            app_ids_root: (!self.app_ids.is_empty())
+                .then(|| disclosure::app_ids_root::<H>(&self.app_ids)),
+            ood_evaluation: ood_evaluation_bytes(self.ood_sample.as_ref()),
+            batch_leaf_encoding: self.batch_leaf_encoding,
+            num_partitions: self.num_partitions,
+            dispersal_hint: self.dispersal_hint,
+            data_roots: self.data_roots.clone(),
+            profile: self.profile,
+        }
+    }
+
+    /// Opens the positions assigned to every validator, returning one proof per validator, or
+    /// `None` for validators without positions. Proofs are built in parallel with the
+    /// `concurrent` feature; lazy layers are regenerated for every proof.
+    ///
+    /// # Errors
+    /// Returns the errors of `open()` if the positions of any validator cannot be opened.
+    pub fn open_for_validators(
+        &self,
+        assignments: &[Vec<usize>],
+    ) -> Result<Vec<Option<FridaProof>>, FridaError> {
+        iter!(assignments)
+            .map(|positions| {
+                (!positions.is_empty())
+                    .then(|| self.open(positions))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Returns the evaluations of every committed polynomial at the given positions, in the order
+    /// expected by the verifier.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PositionOutOfDomain` if a position lies outside of the domain, and
+    /// `FridaError::PositionPruned` if the prover was pruned and a position was not retained.
+    pub fn get_evaluations(&self, positions: &[usize]) -> Result<Vec<E>, FridaError> {
+        if let Some(&position) = positions.iter().find(|&&p| p >= self.domain_size) {
+            return Err(FridaError::PositionOutOfDomain(position, self.domain_size));
+        }
+        let Some(&folding_factor) = self.folding_factors.first() else {
+            return Ok(self.remainder_evaluations(positions));
+        };
+        let bucket_count = self.domain_size / folding_factor;
+        let bucket_size = self.poly_count * folding_factor;
+
+        let mut evaluations = Vec::with_capacity(positions.len() * self.poly_count);
+        for &position in positions.iter() {
+            let offset = self.poly_count * (position / bucket_count);
+            let row = self.layers[0].row(position % bucket_count, bucket_size)?;
+            evaluations.extend_from_slice(&row[offset..offset + self.poly_count]);
+        }
+        Ok(evaluations)
+    }
+
+    /// Evaluates the remainder at `positions` of the domain, for provers without any FRI layer;
+    /// see `FridaProverBuilder::with_direct_remainder`.
+    fn remainder_evaluations(&self, positions: &[usize]) -> Vec<E> {
+        // the domain is shifted by the offset of `FriOptions::domain_offset`
+        let offset = E::BaseField::GENERATOR;
+        let generator = E::BaseField::get_root_of_unity(self.domain_size.ilog2());
+        positions
+            .iter()
+            .map(|&position| {
+                let x = offset * generator.exp_vartime((position as u64).into());
+                polynom::eval(&self.remainder_poly.0, E::from(x))
+            })
+            .collect()
+    }
+
+    /// Checks that `positions` can be passed to `open()`, short of pruning.
+    fn check_positions(&self, positions: &[usize]) -> Result<(), FridaError> {
+        if positions.is_empty() {
+            return Err(FridaError::NoPositions);
+        }
+        match positions.iter().find(|&&p| p >= self.domain_size) {
+            Some(&position) => Err(FridaError::PositionOutOfDomain(position, self.domain_size)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the expected size of a proof opening `num_positions` random positions, without
+    /// opening it; see `proof::estimate_proof_size`.
+    pub fn proof_size_estimate(&self, num_positions: usize) -> usize {
+        proof::proof_size::<E, H>(
+            self.domain_size,
+            &self.folding_factors,
+            self.remainder_poly.0.len(),
+            self.poly_count,
+            num_positions,
+        )
+    }
+
+    /// Returns the evaluation of every committed polynomial, in blob order, at `position` of the
+    /// domain in natural order, wherever the first layer stores it.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PositionOutOfDomain` if the position lies outside of the domain, and
+    /// `FridaError::PositionPruned` if the prover was pruned and the position was not retained.
+    pub fn evaluation_at(&self, position: usize) -> Result<Vec<E>, FridaError> {
+        self.get_evaluations(&[position])
+    }
+
+    /// Returns the evaluations of every committed polynomial at each of `positions`, as returned
+    /// by `evaluation_at`.
+    ///
+    /// # Errors
+    /// Returns the errors of `evaluation_at` for the first position which cannot be read.
+    pub fn evaluations_at(&self, positions: &[usize]) -> Result<Vec<Vec<E>>, FridaError> {
+        positions
+            .iter()
+            .map(|&position| self.evaluation_at(position))
+            .collect()
+    }
+
+    /// Returns the evaluations of the first layer, in the transposed, interleaved layout it is
+    /// committed in.
+    ///
+    /// # Errors
+    /// Returns `FridaError::FirstLayerUnavailable` if the prover was pruned, or committed without
+    /// any FRI layer.
+    #[deprecated(
+        note = "exposes the internal layout of the first layer; use `evaluation_at` or `evaluations_at`"
+    )]
+    pub fn get_first_layer_evaluations(&self) -> Result<&[E], FridaError> {
+        self.first_layer_evaluations()
+    }
+
+    /// # Errors
+    /// Returns `FridaError::FirstLayerUnavailable` if the prover was pruned, or committed without
+    /// any FRI layer.
+    pub(crate) fn first_layer_evaluations(&self) -> Result<&[E], FridaError> {
+        match self.layers.first() {
+            Some(ProverLayer::Full(layer)) => Ok(&layer.evaluations),
+            Some(ProverLayer::Pruned(_)) | None => Err(FridaError::FirstLayerUnavailable),
+            Some(ProverLayer::Lazy(_)) => unreachable!("the first layer is never lazy"),
+        }
+    }
+
+    // PRUNING
+    // --------------------------------------------------------------------------------------------
+
+    /// Drops the parts of the layers which are not needed to open the positions selected by
+    /// `keep`, replacing every layer by its root and the rows queried by those positions.
+    ///
+    /// After `PruneLevel::Positions(positions)`, `open()` and `sample_receipt()` may only be
+    /// called with positions in `positions`, and produce the same proofs as before pruning; a
+    /// commitment's own query positions can be kept this way to serve its `Commitment` flow.
+    /// After `PruneLevel::Commitment`, only `commitment()` remains available. Any other opening
+    /// fails with `FridaError::PositionPruned`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PositionOutOfDomain` if a position lies outside of the domain, and
+    /// `FridaError::PositionPruned` if the prover was already pruned and some of the positions
+    /// were not retained; the prover is left untouched in that case.
+    pub fn prune(&mut self, keep: PruneLevel) -> Result<(), FridaError> {
+        let positions = match keep {
+            PruneLevel::Positions(positions) => positions,
+            PruneLevel::Commitment => Vec::new(),
+        };
+        if let Some(&position) = positions.iter().find(|&&p| p >= self.domain_size) {
+            return Err(FridaError::PositionOutOfDomain(position, self.domain_size));
+        }
+
+        let mut domain_size = self.domain_size;
+        let mut positions = positions;
+        let mut pruned = Vec::with_capacity(self.layers.len());
+        self.visit_layers(|i, layer| {
+            let folding_factor = self.folding_factors[i];
+            positions = folding::fold_positions(&positions, domain_size, folding_factor);
+            domain_size /= folding_factor;
+            let row_size = if i == 0 {
+                self.poly_count * folding_factor
             } else {
-                None
+                folding_factor
             };
+            let indexes = self.leaf_indexes(&positions, domain_size);
+            let rows = positions
+                .iter()
+                .zip(indexes)
+                .map(|(&p, i)| Ok((p, (layer.row(p, row_size)?.to_vec(), layer.path(p, i)?))))
+                .collect::<Result<_, FridaError>>()?;
+            pruned.push(ProverLayer::Pruned(PrunedLayer {
+                root: layer.root(),
+                rows,
+            }));
+            Ok(())
+        })?;
+        self.layers = pruned;
+        self.lazy = None;
+        Ok(())
+    }
 
-            // for all FRI layers, except the last one, record tree root, determine a set of query
-            // positions, and query the layer at these positions.
-            let start = if is_batch { 1 } else { 0 };
-            let layers = (start..layers_len)
-                .map(|i| {
-                    positions = folding::fold_positions(&positions, domain_size, folding_factor);
-
-                    let layer = &self.layers[i];
-                    // sort of a static dispatch for folding_factor parameter
-                    let proof_layer = match folding_factor {
-                        2 => query_layer::<E, H, 2>(layer, &positions),
-                        4 => query_layer::<E, H, 4>(layer, &positions),
-                        8 => query_layer::<E, H, 8>(layer, &positions),
-                        16 => query_layer::<E, H, 16>(layer, &positions),
-                        _ => unimplemented!("folding factor {} is not supported", folding_factor),
-                    };
-
-                    domain_size /= folding_factor;
-                    proof_layer
-                })
-                .collect::<Vec<_>>();
-            (layers, batch_layer)
+    /// Returns true if `open()` can be called with `positions`, which is always the case for
+    /// positions in the domain unless the prover was pruned.
+    pub fn can_open(&self, positions: &[usize]) -> bool {
+        if self.check_positions(positions).is_err() {
+            return false;
+        }
+        let mut domain_size = self.domain_size;
+        let mut positions = positions.to_vec();
+        self.layers
+            .iter()
+            .zip(&self.folding_factors)
+            .all(|(layer, &folding_factor)| {
+                positions = folding::fold_positions(&positions, domain_size, folding_factor);
+                domain_size /= folding_factor;
+                match layer {
+                    ProverLayer::Full(layer) => {
+                        positions.iter().all(|&p| p < layer.tree.leaves().len())
+                    }
+                    ProverLayer::Pruned(layer) => {
+                        positions.iter().all(|p| layer.rows.contains_key(p))
+                    }
+                    ProverLayer::Lazy(_) => positions.iter().all(|&p| p < domain_size),
+                }
+            })
+    }
+
+    // LAZY LAYERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Calls `f` with every layer in order, regenerating lazy layers from the previous one so that
+    /// at most two regenerated layers are held at any time. Stops at the first error of `f`.
+    fn visit_layers(
+        &self,
+        mut f: impl FnMut(usize, &ProverLayer<E, H>) -> Result<(), FridaError>,
+    ) -> Result<(), FridaError> {
+        let mut regenerated: Option<ProverLayer<E, H>> = None;
+        for (i, layer) in self.layers.iter().enumerate() {
+            if let ProverLayer::Lazy(root) = layer {
+                let previous = regenerated.as_ref().unwrap_or(&self.layers[i - 1]);
+                let layer = self.regenerate_layer(i, previous)?;
+                if layer.root() != *root {
+                    return Err(FridaError::RegeneratedLayerMismatch(i));
+                }
+                f(i, &layer)?;
+                regenerated = Some(layer);
+            } else {
+                f(i, layer)?;
+                regenerated = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Folds `previous` into layer `index` and commits to it again.
+    fn regenerate_layer(
+        &self,
+        index: usize,
+        previous: &ProverLayer<E, H>,
+    ) -> Result<ProverLayer<E, H>, FridaError> {
+        let ProverLayer::Full(previous) = previous else {
+            unreachable!("lazy layers always follow a full one");
         };
+        let lazy = self
+            .lazy
+            .as_ref()
+            .expect("lazy layers require folding randomness");
+        let alpha = lazy.alphas[index - 1];
+        let offset = lazy.domain_offset;
+        let previous = &previous.evaluations;
+        let is_first = index == 1;
 
-        // use the remaining polynomial values directly as proof
-        let remainder = self.remainder_poly.0.clone();
-        FridaProof::new(batch_layer, layers, remainder, 1)
+        // the previous layer is folded by its own factor, and committed by the factor of this one
+        let (poly_count, xi, ood) = (self.poly_count, &lazy.xi, self.ood_sample.as_ref());
+        let evaluations = match self.folding_factors[index - 1] {
+            2 => fold_previous::<E, 2>(previous, poly_count, offset, xi, ood, alpha, is_first),
+            4 => fold_previous::<E, 4>(previous, poly_count, offset, xi, ood, alpha, is_first),
+            8 => fold_previous::<E, 8>(previous, poly_count, offset, xi, ood, alpha, is_first),
+            16 => fold_previous::<E, 16>(previous, poly_count, offset, xi, ood, alpha, is_first),
+            f => return Err(FridaError::UnsupportedFoldingFactor(f)),
+        };
+        let partitions = self.num_partitions;
+        let layer = match self.folding_factors[index] {
+            2 => builder::commit_partitioned_layer::<E, H, 2>(&evaluations, partitions)?,
+            4 => builder::commit_partitioned_layer::<E, H, 4>(&evaluations, partitions)?,
+            8 => builder::commit_partitioned_layer::<E, H, 8>(&evaluations, partitions)?,
+            16 => builder::commit_partitioned_layer::<E, H, 16>(&evaluations, partitions)?,
+            f => return Err(FridaError::UnsupportedFoldingFactor(f)),
+        };
+        Ok(ProverLayer::Full(layer))
+    }
+}
+
+/// Folds a layer into the next one; the first layer is folded as the combination of its
+/// polynomials, replaced by its DEEP quotient if the prover has an out-of-domain sample.
+fn fold_previous<E: FieldElement, const N: usize>(
+    previous: &[E],
+    poly_count: usize,
+    domain_offset: E::BaseField,
+    xi: &[E],
+    ood: Option<&OodSample<E>>,
+    alpha: E,
+    is_first: bool,
+) -> Vec<E> {
+    if !is_first {
+        builder::fold_layer::<E, N>(previous, domain_offset, alpha)
+    } else if poly_count > 1 {
+        // regenerated layers are not tied to the builder, whose parallelism options do not change
+        // the values anyway
+        let combined =
+            builder::combine_batch(previous, poly_count, xi, &ParallelOptions::default());
+        builder::fold_first_layer::<E, N>(&combined, domain_offset, ood, alpha)
+    } else {
+        builder::fold_first_layer::<E, N>(previous, domain_offset, ood, alpha)
+    }
+}
+
+impl<E, H> ProverLayer<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn root(&self) -> H::Digest {
+        match self {
+            ProverLayer::Full(layer) => *layer.tree.root(),
+            ProverLayer::Pruned(layer) => layer.root,
+            ProverLayer::Lazy(root) => *root,
+        }
+    }
+
+    /// Returns the values committed to in the leaf at `index`.
+    fn row(&self, index: usize, row_size: usize) -> Result<&[E], FridaError> {
+        match self {
+            ProverLayer::Full(layer) => layer
+                .evaluations
+                .get(index * row_size..(index + 1) * row_size)
+                .ok_or(FridaError::PositionOutOfDomain(
+                    index,
+                    layer.evaluations.len() / row_size,
+                )),
+            ProverLayer::Pruned(layer) => layer
+                .rows
+                .get(&index)
+                .map(|(row, _)| row.as_slice())
+                .ok_or(FridaError::PositionPruned(index)),
+            ProverLayer::Lazy(_) => unreachable!("{LAZY_LAYER}"),
+        }
     }
 
-    pub fn get_first_layer_evaluations(&self) -> &[E] {
-        &self.layers[0].evaluations
+    /// Returns the authentication path of the leaf at `index`, which commits to row `row`.
+    fn path(&self, row: usize, index: usize) -> Result<Vec<H::Digest>, FridaError> {
+        match self {
+            ProverLayer::Full(layer) => layer.tree.prove(index).map_err(merkle_error),
+            ProverLayer::Pruned(layer) => layer
+                .rows
+                .get(&row)
+                .map(|(_, path)| path.clone())
+                .ok_or(FridaError::PositionPruned(row)),
+            ProverLayer::Lazy(_) => unreachable!("{LAZY_LAYER}"),
+        }
+    }
+
+    /// Proves the leaves at `indexes`, which commit to the rows at `rows`.
+    fn prove(&self, rows: &[usize], indexes: &[usize]) -> Result<BatchMerkleProof<H>, FridaError> {
+        match self {
+            ProverLayer::Full(layer) => layer.tree.prove_batch(indexes).map_err(merkle_error),
+            ProverLayer::Pruned(_) => {
+                let paths = rows
+                    .iter()
+                    .zip(indexes)
+                    .map(|(&row, &index)| self.path(row, index))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(BatchMerkleProof::from_paths(&paths, indexes))
+            }
+            ProverLayer::Lazy(_) => unreachable!("{LAZY_LAYER}"),
+        }
     }
 }
 
+const LAZY_LAYER: &str = "lazy layers must be regenerated before being queried";
+
+/// Wraps an error of the Merkle tree of a layer.
+pub(super) fn merkle_error(error: MerkleTreeError) -> FridaError {
+    FridaError::MerkleError(error.to_string())
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Reads the size of the domain of a commitment, which must be a power of two between
+/// `MIN_DOMAIN_SIZE` and `MAX_DOMAIN_SIZE`, so that it can bound what is read after it.
+fn read_domain_size<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+    let domain_size = usize::read_from(source)?;
+    if !domain_size.is_power_of_two() || !(MIN_DOMAIN_SIZE..=MAX_DOMAIN_SIZE).contains(&domain_size)
+    {
+        return Err(DeserializationError::InvalidValue(format!(
+            "domain size must be a power of two between {MIN_DOMAIN_SIZE} and {MAX_DOMAIN_SIZE}, \
+             but was {domain_size}"
+        )));
+    }
+    Ok(domain_size)
+}
+
+/// Reads the tag written before an optional field, which must be 0 if the field is absent or 1
+/// if it is present.
+pub(crate) fn read_presence_tag<R: ByteReader>(
+    source: &mut R,
+    field: &str,
+) -> Result<bool, DeserializationError> {
+    match source.read_u8()? {
+        0 => Ok(false),
+        1 => Ok(true),
+        tag => Err(DeserializationError::InvalidValue(format!(
+            "{field} tag must be 0 or 1, but was {tag}"
+        ))),
+    }
+}
+
+/// Reads a number of partitions, which must be a power of two.
+fn read_num_partitions<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+    let num_partitions = usize::read_from(source)?;
+    if !num_partitions.is_power_of_two() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of partitions must be a power of two, but was {num_partitions}"
+        )));
+    }
+    Ok(num_partitions)
+}
+
 pub fn batch_data_to_evaluations<E>(
     data_list: &[Vec<u8>],
     poly_count: usize,
     domain_size: usize,
     blowup_factor: usize,
     folding_factor: usize,
+    encoding: &EncodingOptions,
 ) -> Result<Vec<E>, FridaError>
 where
     E: FieldElement,
 {
+    // every position is written exactly once only if each blob fills the domain
+    if data_list.len() != poly_count {
+        return Err(FridaError::ProofPolyCountMismatch);
+    }
+
+    let polys = data_list
+        .iter()
+        .map(|data| {
+            build_evaluations_with_encoding::<E>(data, domain_size, blowup_factor, encoding)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(interleave_evaluations(&polys, domain_size, folding_factor))
+}
+
+/// Interleaves the evaluations of `polys` over the domain, in natural order, into the layout of a
+/// batched first layer: rows of `folding_factor` points, each holding the evaluations of every
+/// polynomial at that point.
+///
+/// # Panics
+/// Panics if a polynomial does not have `domain_size` evaluations, or if `folding_factor` does not
+/// divide `domain_size`.
+pub fn interleave_evaluations<E: FieldElement>(
+    polys: &[Vec<E>],
+    domain_size: usize,
+    folding_factor: usize,
+) -> Vec<E> {
+    let poly_count = polys.len();
     let bucket_count = domain_size / folding_factor;
     let bucket_size = poly_count * folding_factor;
 
-    let mut evaluations = unsafe { uninit_vector(poly_count * domain_size) };
-    for (i, data) in data_list.iter().enumerate() {
-        build_evaluations_from_data::<E>(data, domain_size, blowup_factor)?
-            .into_iter()
-            .enumerate()
-            .for_each(|(j, e)| {
-                let bucket = j % bucket_count;
-                let position = i + poly_count * (j / bucket_count);
-                evaluations[bucket * bucket_size + position] = e;
-            });
+    // every slot of the output is written once, as long as every polynomial fills the domain
+    assert!(
+        polys.iter().all(|poly| poly.len() == domain_size),
+        "every polynomial must have {domain_size} evaluations"
+    );
+    assert_eq!(
+        domain_size % folding_factor,
+        0,
+        "folding factor must divide the domain size"
+    );
+    let mut evaluations = output_vector(poly_count * domain_size);
+    for (i, poly) in polys.iter().enumerate() {
+        poly.iter().enumerate().for_each(|(j, &e)| {
+            let bucket = j % bucket_count;
+            let position = i + poly_count * (j / bucket_count);
+            evaluations[bucket * bucket_size + position] = e;
+        });
     }
-
-    Ok(evaluations)
+    evaluations
 }
 
 pub fn get_evaluations_from_positions<E: FieldElement>(
@@ -271,23 +1571,43 @@ pub fn get_evaluations_from_positions<E: FieldElement>(
     evaluations
 }
 
+/// Queries a FRI layer at the specified positions; sort of a static dispatch for the
+/// folding_factor parameter.
+fn query_fri_layer<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>>(
+    layer: &ProverLayer<E, H>,
+    positions: &[usize],
+    indexes: &[usize],
+    folding_factor: usize,
+) -> Result<FridaProofLayer, FridaError> {
+    match folding_factor {
+        2 => query_layer::<E, H, 2>(layer, positions, indexes),
+        4 => query_layer::<E, H, 4>(layer, positions, indexes),
+        8 => query_layer::<E, H, 8>(layer, positions, indexes),
+        16 => query_layer::<E, H, 16>(layer, positions, indexes),
+        _ => Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
+    }
+}
+
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
-/// specified positions.
+/// specified positions, committed to by the leaves at `indexes`.
 fn query_layer<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>, const N: usize>(
-    layer: &FridaLayer<E, H>,
+    layer: &ProverLayer<E, H>,
     positions: &[usize],
-) -> FridaProofLayer {
+    indexes: &[usize],
+) -> Result<FridaProofLayer, FridaError> {
     // build Merkle authentication paths for all query positions
-    let proof = layer
-        .tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for FRI layer queries");
+    let proof = layer.prove(positions, indexes)?;
 
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
     // are stored in transposed form, a position refers to N evaluations which are committed
-    // in a single leaf
-    let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
-    let queried_values: Vec<[E; N]> = positions.iter().map(|&pos| evaluations[pos]).collect();
+    // in a single leaf; rows are gathered in parallel with the `concurrent` feature, which pays
+    // off for large position sets
+    let queried_values = iter!(positions)
+        .map(|&pos| {
+            let row = layer.row(pos, N)?;
+            Ok(<[E; N]>::try_from(row).expect("rows of a layer hold N values"))
+        })
+        .collect::<Result<Vec<_>, FridaError>>()?;
 
-    FridaProofLayer::new(queried_values, proof)
+    Ok(FridaProofLayer::new(queried_values, proof))
 }
@@ -0,0 +1,76 @@
+use winter_crypto::{hashers::Blake3_256, ElementHasher};
+use winter_fri::FriOptions;
+use winter_math::{
+    fields::{f128, f64},
+    FieldElement,
+};
+
+use super::{builder::FridaProverBuilder, Commitment, FridaProver};
+use crate::error::FridaError;
+
+// MULTI-FIELD COMMITMENTS
+// ================================================================================================
+
+/// Commitments to the same data in two fields, along with their provers.
+///
+/// Committing in both fields eases a migration from one field to the other, and decoding both
+/// commitments with `verifier::cross::verify_same_data` catches encoding bugs which only show in
+/// one of them.
+#[derive(Debug)]
+pub struct MultiFieldCommitment<EA, HA, EB, HB>
+where
+    EA: FieldElement,
+    HA: ElementHasher<BaseField = EA::BaseField>,
+    EB: FieldElement,
+    HB: ElementHasher<BaseField = EB::BaseField>,
+{
+    pub commitment_a: Commitment<HA>,
+    pub prover_a: FridaProver<EA, HA>,
+    pub commitment_b: Commitment<HB>,
+    pub prover_b: FridaProver<EB, HB>,
+}
+
+/// Commitments to the same data in the 64-bit and 128-bit fields, hashed with BLAKE3.
+pub type F64F128Commitment = MultiFieldCommitment<
+    f64::BaseElement,
+    Blake3_256<f64::BaseElement>,
+    f128::BaseElement,
+    Blake3_256<f128::BaseElement>,
+>;
+
+/// Commits to `data` with both builders, as `FridaProverBuilder::commit_and_prove` does.
+pub fn commit_multi_field<EA, HA, EB, HB>(
+    builder_a: &FridaProverBuilder<EA, HA>,
+    builder_b: &FridaProverBuilder<EB, HB>,
+    data: &[u8],
+    num_queries: usize,
+) -> Result<MultiFieldCommitment<EA, HA, EB, HB>, FridaError>
+where
+    EA: FieldElement,
+    HA: ElementHasher<BaseField = EA::BaseField>,
+    EB: FieldElement,
+    HB: ElementHasher<BaseField = EB::BaseField>,
+{
+    let (commitment_a, prover_a) = builder_a.commit_and_prove(data, num_queries)?;
+    let (commitment_b, prover_b) = builder_b.commit_and_prove(data, num_queries)?;
+    Ok(MultiFieldCommitment {
+        commitment_a,
+        prover_a,
+        commitment_b,
+        prover_b,
+    })
+}
+
+/// Commits to `data` in the 64-bit and 128-bit fields with the same `options`.
+pub fn commit_f64_f128(
+    options: FriOptions,
+    data: &[u8],
+    num_queries: usize,
+) -> Result<F64F128Commitment, FridaError> {
+    commit_multi_field(
+        &FridaProverBuilder::new(options.clone()),
+        &FridaProverBuilder::new(options),
+        data,
+        num_queries,
+    )
+}
@@ -0,0 +1,200 @@
+use winter_crypto::ElementHasher;
+use winter_fri::folding::fold_positions;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{builder::FridaProverBuilder, proof::FridaProofBatchLayer, Commitment, FridaProver};
+use crate::{error::FridaError, verifier::multiproof::multiproof_coefficients};
+
+// MULTIPROOF
+// ================================================================================================
+
+// The commitments of a block are checked together rather than one embedded proof at a time. The
+// polynomials of all of them are combined with coefficients drawn from every commitment, and the
+// combination is committed to and proven low degree like a single blob. Its proof fixes the query
+// positions, at which the first layer of every commitment is opened and recombined into the
+// evaluations the combined proof must open. As the combination is low degree only if, with high
+// probability, every combined polynomial is, a full node verifies one FRI proof and one Merkle
+// proof per commitment instead of one FRI proof per commitment. No setup is involved: every
+// challenge comes from the public coin.
+
+/// Opening of the first layer of one of the commitments of a `MultiProof`, at the rows queried
+/// by the combined proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirstLayerOpening {
+    /// Partitions the rows of the first layer are split into in the Merkle tree of the
+    /// commitment; see `FridaProverBuilder::with_num_partitions`.
+    pub num_partitions: usize,
+    pub layer: FridaProofBatchLayer,
+}
+
+/// Proof that the first layers of a list of commitments, sharing options and domain size, are
+/// evaluations of low degree polynomials; see `verifier::multiproof::verify_multiproof`.
+#[derive(Debug, PartialEq)]
+pub struct MultiProof<H: ElementHasher> {
+    /// Commitment to the combination of every polynomial of the commitments, whose proof opens
+    /// the positions drawn from its transcript.
+    pub combined: Commitment<H>,
+    /// Opening of the first layer of every commitment at these positions, in commitment order.
+    pub openings: Vec<FirstLayerOpening>,
+}
+
+impl<E, H, HHst> FridaProverBuilder<E, H, HHst>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Proves the commitments of `provers`, built with the options of this builder, with a single
+    /// combined proof of `num_queries` queries.
+    ///
+    /// # Errors
+    /// Returns `FridaError::MultiProofMismatch` if `provers` is empty, or if the provers do not
+    /// share the domain size and folding factors of this builder, which are checked before the
+    /// combination is computed, `FridaError::PositionPruned` if a prover was pruned, and the
+    /// errors of `commit_and_prove` for the combined commitment.
+    pub fn prove_multi(
+        &self,
+        provers: &[&FridaProver<E, H>],
+        num_queries: usize,
+    ) -> Result<MultiProof<H>, FridaError> {
+        let domain_size = provers
+            .first()
+            .ok_or(FridaError::MultiProofMismatch)?
+            .domain_size;
+        if provers
+            .iter()
+            .any(|prover| prover.domain_size != domain_size)
+        {
+            return Err(FridaError::MultiProofMismatch);
+        }
+
+        let folding_factors = self.checked_folding_factors(domain_size)?;
+        if provers
+            .iter()
+            .any(|prover| prover.folding_factors != folding_factors)
+        {
+            return Err(FridaError::MultiProofMismatch);
+        }
+
+        let commitments = provers
+            .iter()
+            .map(|prover| prover.commitment())
+            .collect::<Vec<_>>();
+        let coefficients = multiproof_coefficients::<E, HHst, H>(&commitments)?;
+        let combined = combine_provers(provers, &coefficients)?;
+
+        let (mut channel, combined_prover) =
+            self.prepare_prover_state_from_evaluations(combined, num_queries)?;
+        let query_positions = channel.draw_query_positions();
+        let combined = self.open_commitment(&combined_prover, &channel, &query_positions)?;
+
+        let openings = provers
+            .iter()
+            .map(|prover| prover.open_first_layer(&query_positions))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MultiProof { combined, openings })
+    }
+}
+
+/// Returns the combination of the polynomials of every prover with `coefficients` over their
+/// domain, in natural order.
+fn combine_provers<E, H>(
+    provers: &[&FridaProver<E, H>],
+    coefficients: &[E],
+) -> Result<Vec<E>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let domain_size = provers[0].domain_size;
+    let positions = (0..domain_size).collect::<Vec<_>>();
+    let mut combined = vec![E::ZERO; domain_size];
+    let mut coefficients = coefficients;
+    for prover in provers {
+        let (prover_coefficients, rest) = coefficients.split_at(prover.poly_count);
+        coefficients = rest;
+        let evaluations = prover.get_evaluations(&positions)?;
+        for (value, point) in combined
+            .iter_mut()
+            .zip(evaluations.chunks(prover.poly_count))
+        {
+            *value += point
+                .iter()
+                .zip(prover_coefficients)
+                .fold(E::ZERO, |acc, (&e, &c)| acc + e * c);
+        }
+    }
+    Ok(combined)
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens the rows of the first layer queried by `positions`, without the following layers.
+    ///
+    /// # Errors
+    /// Returns `FridaError::MultiProofMismatch` if the prover has no FRI layer, and the errors of
+    /// `open()`.
+    pub(crate) fn open_first_layer(
+        &self,
+        positions: &[usize],
+    ) -> Result<FirstLayerOpening, FridaError> {
+        self.check_positions(positions)?;
+        let Some(&folding_factor) = self.folding_factors.first() else {
+            return Err(FridaError::MultiProofMismatch);
+        };
+        let rows = fold_positions(positions, self.domain_size, folding_factor);
+        let indexes = self.leaf_indexes(&rows, self.domain_size / folding_factor);
+        Ok(FirstLayerOpening {
+            num_partitions: self.num_partitions,
+            layer: self.query_batch_layer(&rows, &indexes)?,
+        })
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for FirstLayerOpening {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.num_partitions.write_into(target);
+        self.layer.write_into(target);
+    }
+}
+
+impl Deserializable for FirstLayerOpening {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_partitions = usize::read_from(source)?;
+        let layer = FridaProofBatchLayer::read_from(source)?;
+
+        Ok(FirstLayerOpening {
+            num_partitions,
+            layer,
+        })
+    }
+}
+
+impl<H: ElementHasher> Serializable for MultiProof<H>
+where
+    H::Digest: Serializable,
+{
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.combined.write_into(target);
+        self.openings.write_into(target);
+    }
+}
+
+impl<H: ElementHasher> Deserializable for MultiProof<H>
+where
+    H::Digest: Deserializable,
+{
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let combined = Commitment::<H>::read_from(source)?;
+        let openings = Vec::<FirstLayerOpening>::read_from(source)?;
+
+        Ok(MultiProof { combined, openings })
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::VecDeque,
+    sync::{mpsc, Arc, Condvar, Mutex, Weak},
+    task::Waker,
+    thread,
+};
+#[cfg(feature = "async")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+
+use super::{builder::FridaProverBuilder, Commitment, FridaProver};
+use crate::error::FridaError;
+
+type PipelineResult<E, H> = Result<(Commitment<H>, FridaProver<E, H>), FridaError>;
+
+// BATCH PIPELINE
+// ================================================================================================
+
+/// Commits to a sequence of independent blobs, overlapping the Reed-Solomon encoding of the next
+/// blob with the Merkle hashing of the current one.
+///
+/// Each call to `commit_all` spawns one encoding thread and one hashing thread connected by a
+/// queue holding at most `queue_depth` encoded blobs. Results are produced in input order, and are
+/// identical to calling `FridaProverBuilder::commit_and_prove` on every blob in turn.
+pub struct FridaBatchPipeline<E, H, HHst = H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    builder: Arc<FridaProverBuilder<E, H, HHst>>,
+    num_queries: usize,
+    queue_depth: usize,
+}
+
+impl<E, H, HHst> FridaBatchPipeline<E, H, HHst>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+    HHst: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+{
+    pub fn new(builder: FridaProverBuilder<E, H, HHst>, num_queries: usize) -> Self {
+        FridaBatchPipeline {
+            builder: Arc::new(builder),
+            num_queries,
+            queue_depth: 1,
+        }
+    }
+
+    /// Sets how many encoded blobs may wait for the hashing thread; must be at least one.
+    pub fn with_queue_depth(mut self, queue_depth: usize) -> Self {
+        assert!(queue_depth > 0, "queue depth must be at least one");
+        self.queue_depth = queue_depth;
+        self
+    }
+
+    /// Starts committing to `blobs` in the background and returns the stream of results.
+    ///
+    /// A blob which cannot be committed to yields an error without interrupting the following
+    /// ones. Dropping the returned stream stops both threads after the blob they are working on.
+    pub fn commit_all(&self, blobs: Vec<Vec<u8>>) -> CommitmentStream<E, H> {
+        let shared = Arc::new(Shared::default());
+        let (encoded_tx, encoded_rx) =
+            mpsc::sync_channel::<Result<Vec<E>, FridaError>>(self.queue_depth);
+
+        let builder = self.builder.clone();
+        let num_queries = self.num_queries;
+        thread::spawn(move || {
+            for blob in blobs {
+                if encoded_tx
+                    .send(builder.encode_evaluations(&blob, num_queries))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let builder = self.builder.clone();
+        let output = Arc::downgrade(&shared);
+        thread::spawn(move || {
+            let output = CloseOnDrop(output);
+            for encoded in encoded_rx {
+                let result = encoded.and_then(|evaluations| {
+                    let (channel, prover) =
+                        builder.prepare_prover_state_from_evaluations(evaluations, num_queries)?;
+                    let commitment = builder.build_commitment(&prover, channel)?;
+                    Ok((commitment, prover))
+                });
+                if !Shared::push(&output.0, result) {
+                    return;
+                }
+            }
+        });
+
+        CommitmentStream { shared }
+    }
+}
+
+// COMMITMENT STREAM
+// ================================================================================================
+
+/// Results of a `FridaBatchPipeline`, in the order the blobs were submitted.
+///
+/// It can be consumed as a blocking `Iterator`, or as a `futures_core::Stream` when the `async`
+/// feature is enabled.
+pub struct CommitmentStream<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    shared: Arc<Shared<PipelineResult<E, H>>>,
+}
+
+impl<E, H> Iterator for CommitmentStream<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type Item = PipelineResult<E, H>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut state = self.shared.state.lock().unwrap();
+        loop {
+            if let Some(item) = state.items.pop_front() {
+                return Some(item);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.shared.ready.wait(state).unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<E, H> futures_core::Stream for CommitmentStream<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type Item = PipelineResult<E, H>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if let Some(item) = state.items.pop_front() {
+            return Poll::Ready(Some(item));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+// SHARED STATE
+// ================================================================================================
+
+/// Output queue written by the hashing thread, which only holds a weak reference to it so that
+/// it notices when the consumer goes away.
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    ready: Condvar,
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl<T> Default for Shared<T> {
+    fn default() -> Self {
+        Shared {
+            state: Mutex::new(State {
+                items: VecDeque::new(),
+                closed: false,
+                waker: None,
+            }),
+            ready: Condvar::new(),
+        }
+    }
+}
+
+/// Closes the output queue once the hashing thread exits, including when it panics.
+struct CloseOnDrop<T>(Weak<Shared<T>>);
+
+impl<T> Drop for CloseOnDrop<T> {
+    fn drop(&mut self) {
+        Shared::close(&self.0);
+    }
+}
+
+impl<T> Shared<T> {
+    /// Returns false if the consumer has been dropped.
+    fn push(shared: &Weak<Self>, item: T) -> bool {
+        Self::update(shared, |state| state.items.push_back(item))
+    }
+
+    fn close(shared: &Weak<Self>) {
+        Self::update(shared, |state| state.closed = true);
+    }
+
+    fn update(shared: &Weak<Self>, f: impl FnOnce(&mut State<T>)) -> bool {
+        let Some(shared) = shared.upgrade() else {
+            return false;
+        };
+        let waker = {
+            let mut state = shared.state.lock().unwrap();
+            f(&mut state);
+            state.waker.take()
+        };
+        shared.ready.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        true
+    }
+}
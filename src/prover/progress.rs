@@ -0,0 +1,30 @@
+// PROGRESS
+// ================================================================================================
+
+/// Phase of a commitment reported to a `ProgressSink` when it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Packing blob `blob` of `num_blobs` into field elements and interpolating its polynomial.
+    Encoding { blob: usize, num_blobs: usize },
+    /// Evaluating the polynomial of blob `blob` of `num_blobs` over the domain.
+    Fft { blob: usize, num_blobs: usize },
+    /// Hashing FRI layer `layer` of `num_layers` into its Merkle tree, and folding it.
+    LayerHashing { layer: usize, num_layers: usize },
+    /// Opening the query positions of the commitment.
+    ProofBuilding,
+}
+
+/// Receives the phases of the commitments of a builder as they start, e.g. to render a progress
+/// bar or to detect stalled commitments; see `FridaProverBuilder::with_progress_sink`.
+///
+/// Sinks are called on the threads running the commitment, e.g. by both threads of a
+/// `pipeline::FridaBatchPipeline`, and should return quickly.
+pub trait ProgressSink: Send + Sync {
+    fn on_phase(&self, phase: Phase);
+}
+
+impl<F: Fn(Phase) + Send + Sync> ProgressSink for F {
+    fn on_phase(&self, phase: Phase) {
+        self(phase)
+    }
+}
@@ -1,9 +1,15 @@
+use std::io::{self, BufWriter, Read, Write};
+
 use winter_crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use winter_fri::{FriOptions, FriProof};
 use winter_math::FieldElement;
 use winter_utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
+use super::{read_presence_tag, BatchLeafEncoding};
+use crate::{constants, core::schedule::layer_folding_factors, error::FridaError};
+
 // FRI PROOF
 // ================================================================================================
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -58,6 +64,32 @@ impl FridaProof {
         }
     }
 
+    /// Reassembles a proof from the parts returned by `parts()`.
+    pub(crate) fn from_parts(
+        batch_layer: Option<FridaProofBatchLayer>,
+        layers: Vec<FridaProofLayer>,
+        remainder: Vec<u8>,
+        num_partitions: u8,
+    ) -> Self {
+        FridaProof {
+            batch_layer,
+            layers,
+            remainder,
+            num_partitions,
+        }
+    }
+
+    /// Returns the batch layer, the FRI layers, the remainder bytes and the log2 of the number of
+    /// partitions of this proof, as they are serialized.
+    pub(crate) fn parts(&self) -> (Option<&FridaProofBatchLayer>, &[FridaProofLayer], &[u8], u8) {
+        (
+            self.batch_layer.as_ref(),
+            &self.layers,
+            &self.remainder,
+            self.num_partitions,
+        )
+    }
+
     /// Creates a dummy `FriProof` for use in tests.
     pub fn new_dummy() -> Self {
         Self {
@@ -99,11 +131,7 @@ impl FridaProof {
         self.layers
             .iter()
             .fold(self.remainder.len() + 4, |acc, layer| acc + layer.size())
-            + if self.batch_layer.is_none() {
-                0
-            } else {
-                self.batch_layer.as_ref().unwrap().size()
-            }
+            + self.batch_layer.as_ref().map_or(0, |layer| layer.size())
     }
 
     // PARSING
@@ -112,20 +140,23 @@ impl FridaProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
     /// authentication paths for each query (grouped into batch Merkle proofs).
     ///
+    /// `folding_factors` holds the folding factor of every layer of this proof, the batch layer
+    /// excluded.
+    ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// * Any folding factor is smaller than two or is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size` and `folding_factors`.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
     pub fn parse_layers<HRandom, E>(
         &self,
         domain_size: usize,
-        folding_factor: usize,
+        folding_factors: &[usize],
     ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<HRandom>>), DeserializationError>
     where
         E: FieldElement,
@@ -135,22 +166,29 @@ impl FridaProof {
             domain_size.is_power_of_two(),
             "domain size must be a power of two"
         );
-        assert!(
-            folding_factor.is_power_of_two(),
-            "folding factor must be a power of two"
-        );
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
+        for &folding_factor in folding_factors {
+            assert!(
+                folding_factor.is_power_of_two(),
+                "folding factor must be a power of two"
+            );
+            assert!(folding_factor > 1, "folding factor must be greater than 1");
+        }
+        if self.layers.len() > folding_factors.len() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} FRI layers, but the proof has {}",
+                folding_factors.len(),
+                self.layers.len()
+            )));
+        }
 
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
         let mut domain_size = domain_size;
 
         // parse all layers
-        for (i, layer) in self.layers.iter().enumerate() {
+        for (i, &folding_factor) in folding_factors.iter().enumerate().take(self.layers.len()) {
             domain_size /= folding_factor;
-            let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
-                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
-            })?;
+            let (qv, mp) = self.parse_layer(i, domain_size, folding_factor)?;
             layer_proofs.push(mp);
             layer_queries.push(qv);
         }
@@ -158,12 +196,36 @@ impl FridaProof {
         Ok((layer_queries, layer_proofs))
     }
 
+    /// Parses FRI layer `index` alone, folded by `folding_factor` into a domain of `domain_size`,
+    /// so that layers can be parsed, and dropped, one at a time.
+    ///
+    /// # Errors
+    /// Returns an error if the proof has no such layer, or if the layer could not be parsed.
+    pub fn parse_layer<HRandom, E>(
+        &self,
+        index: usize,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<(Vec<E>, BatchMerkleProof<HRandom>), DeserializationError>
+    where
+        E: FieldElement,
+        HRandom: ElementHasher<BaseField = E::BaseField>,
+    {
+        let layer = self.layers.get(index).ok_or_else(|| {
+            DeserializationError::InvalidValue(format!("FRI layer {index} does not exist"))
+        })?;
+        layer.parse(domain_size, folding_factor).map_err(|err| {
+            DeserializationError::InvalidValue(format!("failed to parse FRI layer {index}: {err}"))
+        })
+    }
+
     #[allow(clippy::type_complexity)]
     pub fn parse_batch_layer<H, E>(
         &self,
         domain_size: usize,
         folding_factor: usize,
         poly_count: usize,
+        leaf_encoding: BatchLeafEncoding,
     ) -> Result<(Vec<E>, BatchMerkleProof<H>), DeserializationError>
     where
         E: FieldElement,
@@ -181,13 +243,129 @@ impl FridaProof {
         assert!(poly_count > 1, "poly_count must be greater than 1");
 
         if let Some(layer) = self.batch_layer.as_ref() {
-            return layer.parse::<H, E>(domain_size, folding_factor, poly_count);
+            return layer.parse::<H, E>(domain_size, folding_factor, poly_count, leaf_encoding);
         }
         Err(DeserializationError::InvalidValue(
             "failed to parse Batch Layer: it does not exist".to_owned(),
         ))
     }
 
+    /// Checks that this proof is no larger than `limits` allow, without parsing any of its
+    /// layers, so that oversized proofs are rejected before their Merkle paths are read.
+    ///
+    /// # Errors
+    /// Returns an error if the proof is larger than `constants::MAX_PROOF_BYTES`, or has more
+    /// layers, remainder bytes, query value bytes or Merkle path bytes than `limits` allow.
+    pub fn check_limits(&self, limits: &ProofLimits) -> Result<(), DeserializationError> {
+        if self.size() > constants::MAX_PROOF_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} proof bytes, but the proof has {}",
+                constants::MAX_PROOF_BYTES,
+                self.size()
+            )));
+        }
+        if self.layers.len() > limits.max_layers {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} FRI layers, but the proof has {}",
+                limits.max_layers,
+                self.layers.len()
+            )));
+        }
+        if self.remainder.len() > limits.max_remainder_bytes {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} remainder bytes, but the proof has {}",
+                limits.max_remainder_bytes,
+                self.remainder.len()
+            )));
+        }
+        let values_bytes = self
+            .layers
+            .iter()
+            .map(|layer| layer.values.len())
+            .chain(self.batch_layer.iter().map(|layer| layer.values.len()))
+            .sum::<usize>();
+        if values_bytes > limits.max_values_bytes {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} bytes of query values, but the proof has {values_bytes}",
+                limits.max_values_bytes
+            )));
+        }
+        let paths_bytes = self
+            .layers
+            .iter()
+            .map(|layer| layer.paths.len())
+            .chain(self.batch_layer.iter().map(|layer| layer.paths.len()))
+            .sum::<usize>();
+        if paths_bytes > limits.max_paths_bytes {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} bytes of Merkle paths, but the proof has {paths_bytes}",
+                limits.max_paths_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reads a proof from `source`, rejecting it as soon as it holds more than `limits` allow,
+    /// before the bytes of any oversized layer or remainder are read, so that the memory taken by
+    /// a proof is bounded by `limits` rather than by the lengths it claims.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source, or if the proof does
+    /// not pass `check_limits`.
+    pub fn read_with_limits<R: ByteReader>(
+        source: &mut R,
+        limits: &ProofLimits,
+    ) -> Result<Self, DeserializationError> {
+        let mut values_left = limits.max_values_bytes;
+        let mut paths_left = limits.max_paths_bytes;
+
+        // read batch layer
+        let batch_layer = match read_presence_tag(source, "batch layer")? {
+            false => None,
+            true => {
+                let (values, paths) = read_layer_bytes(source, &mut values_left, &mut paths_left)?;
+                Some(FridaProofBatchLayer { values, paths })
+            }
+        };
+
+        // read layers
+        let num_layers = source.read_u8()? as usize;
+        if num_layers > limits.max_layers {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} FRI layers, but the proof has {num_layers}",
+                limits.max_layers
+            )));
+        }
+        let layers = (0..num_layers)
+            .map(|_| {
+                read_layer_bytes(source, &mut values_left, &mut paths_left)
+                    .map(|(values, paths)| FridaProofLayer { values, paths })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // read remainder
+        let num_remainder_bytes = source.read_u16()? as usize;
+        if num_remainder_bytes > limits.max_remainder_bytes {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected at most {} remainder bytes, but the proof has {num_remainder_bytes}",
+                limits.max_remainder_bytes
+            )));
+        }
+        let remainder = source.read_vec(num_remainder_bytes)?;
+
+        // read number of partitions
+        let num_partitions = source.read_u8()?;
+
+        let proof = FridaProof {
+            batch_layer,
+            layers,
+            remainder,
+            num_partitions,
+        };
+        proof.check_limits(limits)?;
+        Ok(proof)
+    }
+
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
     ///
     /// # Errors
@@ -214,13 +392,28 @@ impl FridaProof {
     }
 }
 
+#[cfg(test)]
+impl FridaProof {
+    pub fn test_batch_layer_mut(&mut self) -> Option<&mut FridaProofBatchLayer> {
+        self.batch_layer.as_mut()
+    }
+
+    pub fn test_layers_mut(&mut self) -> &mut Vec<FridaProofLayer> {
+        &mut self.layers
+    }
+
+    pub fn test_remainder_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.remainder
+    }
+}
+
 impl Serializable for FridaProof {
     /// Serializes `self` and writes the resulting bytes into the `target` writer.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write batch layer
         target.write_u8(self.batch_layer.is_some() as u8);
-        if self.batch_layer.is_some() {
-            self.batch_layer.as_ref().unwrap().write_into(target);
+        if let Some(batch_layer) = self.batch_layer.as_ref() {
+            batch_layer.write_into(target);
         }
 
         // write layers
@@ -245,11 +438,9 @@ impl Deserializable for FridaProof {
     /// Returns an error if a valid proof could not be read from the source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read batch layer
-        let has_batch_layer = source.read_u8()? == 1;
-        let batch_layer = if has_batch_layer {
-            Some(source.read()?)
-        } else {
-            None
+        let batch_layer = match read_presence_tag(source, "batch layer")? {
+            false => None,
+            true => Some(source.read()?),
         };
 
         // read layers
@@ -272,6 +463,332 @@ impl Deserializable for FridaProof {
     }
 }
 
+// PROOF LIMITS
+// ================================================================================================
+
+/// Largest proof a verifier accepts for an opening of `num_positions` positions, as implied by
+/// the options and shape of the commitment; see `FridaProof::check_limits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofLimits {
+    /// Number of FRI layers, the batch layer excluded.
+    pub max_layers: usize,
+    pub max_remainder_bytes: usize,
+    /// Bytes of query values over every layer, the batch layer included.
+    pub max_values_bytes: usize,
+    /// Bytes of Merkle path nodes over every layer, the batch layer included.
+    pub max_paths_bytes: usize,
+}
+
+impl ProofLimits {
+    /// Limits of a proof opening `num_positions` positions of a commitment to `poly_count`
+    /// polynomials over `domain_size`, whose layers, the batch layer included, are folded by
+    /// `folding_factors`, with Merkle trees hashed by `H`.
+    pub fn new<E: FieldElement, H: Hasher>(
+        options: &FriOptions,
+        domain_size: usize,
+        folding_factors: &[usize],
+        poly_count: usize,
+        num_positions: usize,
+    ) -> Self {
+        let digest_size = H::Digest::default().to_bytes().len();
+        let mut max_values_bytes = 0;
+        let mut max_paths_bytes = 0;
+        let mut layer_domain_size = domain_size;
+        for (i, &folding_factor) in folding_factors.iter().enumerate() {
+            layer_domain_size /= folding_factor;
+            let row_size = if i == 0 && poly_count > 1 {
+                poly_count * folding_factor
+            } else {
+                folding_factor
+            };
+            // every queried row is opened once, whatever the number of positions falling in it
+            let rows = num_positions.min(layer_domain_size);
+            max_values_bytes += rows * row_size * E::ELEMENT_BYTES;
+            // +1 for the number of paths, +1 per row for the number of nodes in its path
+            let depth = layer_domain_size.ilog2() as usize;
+            max_paths_bytes += 1 + rows * (1 + depth * digest_size);
+        }
+        let remainder_len = domain_size
+            / options.blowup_factor()
+            / folding_factors.iter().product::<usize>().max(1);
+
+        ProofLimits {
+            max_layers: folding_factors
+                .len()
+                .saturating_sub(usize::from(poly_count > 1)),
+            max_remainder_bytes: remainder_len.max(1) * E::ELEMENT_BYTES,
+            max_values_bytes,
+            max_paths_bytes,
+        }
+    }
+}
+
+// SIZE ESTIMATION
+// ================================================================================================
+
+/// Estimates the size, as reported by `FridaProof::size`, of a proof opening `num_positions`
+/// positions drawn uniformly at random from a commitment to `poly_count` polynomials over
+/// `domain_size`, without building any layer.
+///
+/// Positions falling into the same row, and Merkle paths sharing nodes, are only included once
+/// in a proof; the estimate is the expected size over random positions, and exact for a single
+/// position.
+///
+/// # Errors
+/// Returns `FridaError::InvalidDomainSize` if `domain_size` is not a power of two, or is smaller
+/// than the domain of the remainder.
+pub fn estimate_proof_size<E, H>(
+    options: &FriOptions,
+    domain_size: usize,
+    poly_count: usize,
+    num_positions: usize,
+) -> Result<usize, FridaError>
+where
+    E: FieldElement,
+    H: Hasher,
+{
+    if !domain_size.is_power_of_two() {
+        return Err(FridaError::InvalidDomainSize(domain_size));
+    }
+    let folding_factors = layer_folding_factors(options, &[], domain_size);
+    let remainder_len =
+        domain_size / folding_factors.iter().product::<usize>() / options.blowup_factor();
+    if remainder_len == 0 {
+        return Err(FridaError::InvalidDomainSize(domain_size));
+    }
+
+    Ok(proof_size::<E, H>(
+        domain_size,
+        &folding_factors,
+        remainder_len,
+        poly_count,
+        num_positions,
+    ))
+}
+
+/// Expected size of a proof over a prover with the given shape; see `estimate_proof_size`.
+pub(crate) fn proof_size<E, H>(
+    domain_size: usize,
+    folding_factors: &[usize],
+    remainder_len: usize,
+    poly_count: usize,
+    num_positions: usize,
+) -> usize
+where
+    E: FieldElement,
+    H: Hasher,
+{
+    let digest_size = H::Digest::default().to_bytes().len();
+    let num_positions = num_positions as f64;
+
+    // +1 for number of layers, +1 for remainder length, +1 for number of partitions, +1 for has_batch_layer
+    let mut size = (remainder_len * E::ELEMENT_BYTES + 4) as f64;
+    let mut domain_size = domain_size;
+    for (i, &folding_factor) in folding_factors.iter().enumerate() {
+        domain_size /= folding_factor;
+        let row_size = if i == 0 && poly_count > 1 {
+            poly_count * folding_factor
+        } else {
+            folding_factor
+        };
+
+        // a level of `m` nodes contributes one node for every pair with a single queried member
+        let rows = expected_distinct(domain_size, num_positions);
+        let mut nodes = 0.0;
+        let mut level_size = domain_size;
+        while level_size > 1 {
+            let m = level_size as f64;
+            nodes +=
+                m * ((1.0 - 1.0 / m).powf(num_positions) - (1.0 - 2.0 / m).powf(num_positions));
+            level_size /= 2;
+        }
+
+        // +4 for length of values, +4 for length of paths, +1 and +1 per row for path lengths
+        size += rows * (row_size * E::ELEMENT_BYTES) as f64 + 8.0;
+        size += 1.0 + rows + nodes * digest_size as f64;
+    }
+    size.round() as usize
+}
+
+/// Expected number of distinct values among `samples` drawn uniformly at random from `n`.
+fn expected_distinct(n: usize, samples: f64) -> f64 {
+    let n = n as f64;
+    n * (1.0 - (1.0 - 1.0 / n).powf(samples))
+}
+
+// WINTER-FRI CONVERSION
+// ================================================================================================
+
+// Apart from the leading byte flagging the batch layer, a Frida proof is serialized exactly as a
+// winter-fri `FriProof`, so that proofs without a batch layer convert both ways without loss. Only
+// the structure of the proofs is shared: the Frida transcript draws the folding randomness and the
+// query positions differently from winter-fri's public coins, so a converted proof only verifies
+// against a winter-fri verifier whose coin replays the Frida transcript.
+
+impl FridaProof {
+    /// Converts this proof into a winter-fri `FriProof`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BatchLayerNotConvertible` if this proof has a batch layer; see
+    /// `to_fri_proof_lossy`.
+    pub fn to_fri_proof(&self) -> Result<FriProof, FridaError> {
+        if self.has_batch_layer() {
+            return Err(FridaError::BatchLayerNotConvertible);
+        }
+        Ok(self.to_fri_proof_lossy())
+    }
+
+    /// Converts this proof into a winter-fri `FriProof`, dropping its batch layer, if any.
+    ///
+    /// Without the batch layer, the converted proof of a batched commitment only opens the
+    /// combination of the polynomials at the positions folded by the batch layer.
+    pub fn to_fri_proof_lossy(&self) -> FriProof {
+        let mut bytes = Vec::with_capacity(self.size());
+        bytes.write_u8(self.layers.len() as u8);
+        bytes.write_many(&self.layers);
+        bytes.write_u16(self.remainder.len() as u16);
+        bytes.write_bytes(&self.remainder);
+        bytes.write_u8(self.num_partitions);
+        FriProof::read_from_bytes(&bytes).expect("layouts of the proofs match")
+    }
+
+    /// Converts a winter-fri `FriProof` into a proof without a batch layer.
+    pub fn from_fri_proof(proof: &FriProof) -> Self {
+        let mut bytes = vec![0];
+        proof.write_into(&mut bytes);
+        FridaProof::read_from_bytes(&bytes).expect("layouts of the proofs match")
+    }
+}
+
+// STREAMING
+// ================================================================================================
+
+/// Capacity of the buffer proofs are written through, and the most bytes allocated ahead of the
+/// data actually received when reading one, so that a forged length prefix cannot trigger a large
+/// allocation.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+impl FridaProof {
+    /// Writes this proof to `target` in the same format as `Serializable::write_into`, without
+    /// building its byte representation in memory first.
+    pub fn write_to<W: Write>(&self, target: &mut W) -> io::Result<()> {
+        let mut writer = StreamWriter {
+            target: BufWriter::with_capacity(STREAM_CHUNK_SIZE, target),
+            error: None,
+        };
+        self.write_into(&mut writer);
+        match writer.error {
+            Some(err) => Err(err),
+            None => writer.target.flush(),
+        }
+    }
+
+    /// Reads a proof written by `write_to` or `Serializable::write_into` from `source`.
+    ///
+    /// No more bytes than the proof spans are consumed, so several proofs can be read from the
+    /// same stream; wrapping unbuffered sources in a `BufReader` is left to the caller.
+    ///
+    /// # Errors
+    /// Returns an error of kind `InvalidData` if the bytes do not form a valid proof, and
+    /// `UnexpectedEof` if the stream ends before the proof does.
+    pub fn read_from_reader<R: Read>(source: &mut R) -> io::Result<Self> {
+        let mut reader = StreamReader {
+            source,
+            buffer: Vec::new(),
+            error: None,
+        };
+        Self::read_from(&mut reader).map_err(|err| {
+            reader
+                .error
+                .take()
+                .unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+        })
+    }
+}
+
+/// Writes serialized bytes through to an `io::Write`, keeping the first error it returns, as
+/// `ByteWriter` cannot fail.
+struct StreamWriter<W: Write> {
+    target: BufWriter<W>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> ByteWriter for StreamWriter<W> {
+    fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        if self.error.is_none() {
+            self.error = self.target.write_all(values).err();
+        }
+    }
+}
+
+/// Reads serialized bytes from an `io::Read` as they are requested, keeping the error which
+/// interrupted the read, if any. The stream cannot be looked ahead of, so `peek_u8` is not
+/// supported and the end of the input is only detected when it is reached.
+struct StreamReader<'a, R: Read> {
+    source: &'a mut R,
+    /// Bytes returned by the last call to `read_slice`.
+    buffer: Vec<u8>,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> StreamReader<'_, R> {
+    fn fail(&mut self, err: io::Error) -> DeserializationError {
+        let message = err.to_string();
+        self.error = Some(err);
+        DeserializationError::InvalidValue(message)
+    }
+}
+
+impl<R: Read> ByteReader for StreamReader<'_, R> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        self.read_array::<1>().map(|[value]| value)
+    }
+
+    fn peek_u8(&self) -> Result<u8, DeserializationError> {
+        Err(DeserializationError::InvalidValue(
+            "cannot peek into a stream".to_string(),
+        ))
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<&[u8], DeserializationError> {
+        self.buffer = read_bytes(self.source, len).map_err(|err| self.fail(err))?;
+        Ok(&self.buffer)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        read_array(self.source).map_err(|err| self.fail(err))
+    }
+
+    fn check_eor(&self, _num_bytes: usize) -> Result<(), DeserializationError> {
+        Ok(())
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        true
+    }
+}
+
+pub(super) fn read_array<R: Read, const N: usize>(source: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    source.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads exactly `len` bytes, growing the buffer as they arrive rather than allocating `len`
+/// bytes upfront.
+pub(super) fn read_bytes<R: Read>(source: &mut R, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(len.min(STREAM_CHUNK_SIZE));
+    source.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
 // FRI PROOF LAYER
 // ================================================================================================
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -338,7 +855,7 @@ impl FridaProofLayer {
     {
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
-        if self.values.len() % num_query_bytes != 0 {
+        if !self.values.len().is_multiple_of(num_query_bytes) {
             return Err(DeserializationError::InvalidValue(format!(
                 "number of value bytes ({}) does not divide into whole number of queries",
                 self.values.len(),
@@ -378,6 +895,17 @@ impl FridaProofLayer {
     }
 }
 
+#[cfg(test)]
+impl FridaProofLayer {
+    pub fn test_values_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.values
+    }
+
+    pub fn test_paths_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.paths
+    }
+}
+
 impl Serializable for FridaProofLayer {
     /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
@@ -397,19 +925,8 @@ impl Deserializable for FridaProofLayer {
     /// # Errors
     /// Returns an error if a valid layer could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // read values
-        let num_value_bytes = source.read_u32()?;
-        if num_value_bytes == 0 {
-            return Err(DeserializationError::InvalidValue(
-                "a FRI proof layer must contain at least one queried evaluation".to_string(),
-            ));
-        }
-        let values = source.read_vec(num_value_bytes as usize)?;
-
-        // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
-
+        let (mut values_left, mut paths_left) = (usize::MAX, usize::MAX);
+        let (values, paths) = read_layer_bytes(source, &mut values_left, &mut paths_left)?;
         Ok(FridaProofLayer { values, paths })
     }
 }
@@ -452,6 +969,7 @@ impl FridaProofBatchLayer {
         domain_size: usize,
         folding_factor: usize,
         poly_count: usize,
+        leaf_encoding: BatchLeafEncoding,
     ) -> Result<(Vec<E>, BatchMerkleProof<H>), DeserializationError>
     where
         E: FieldElement,
@@ -461,7 +979,7 @@ impl FridaProofBatchLayer {
 
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * bucket_size;
-        if self.values.len() % num_query_bytes != 0 {
+        if !self.values.len().is_multiple_of(num_query_bytes) {
             return Err(DeserializationError::InvalidValue(format!(
                 "number of value bytes ({}) does not divide into whole number of queries",
                 self.values.len(),
@@ -482,8 +1000,8 @@ impl FridaProofBatchLayer {
         let query_values = reader.read_many::<E>(num_queries * bucket_size)?;
 
         for (i, query_hash) in hashed_queries.iter_mut().enumerate() {
-            *query_hash =
-                H::hash_elements(&query_values[i * bucket_size..i * bucket_size + bucket_size]);
+            let bucket = &query_values[i * bucket_size..i * bucket_size + bucket_size];
+            *query_hash = leaf_encoding.hash_leaf::<E, H>(bucket, poly_count);
         }
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
@@ -501,6 +1019,17 @@ impl FridaProofBatchLayer {
     }
 }
 
+#[cfg(test)]
+impl FridaProofBatchLayer {
+    pub fn test_values_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.values
+    }
+
+    pub fn test_paths_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.paths
+    }
+}
+
 impl Serializable for FridaProofBatchLayer {
     /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
@@ -520,19 +1049,46 @@ impl Deserializable for FridaProofBatchLayer {
     /// # Errors
     /// Returns an error if a valid layer could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // read values
-        let num_value_bytes = source.read_u32()?;
-        if num_value_bytes == 0 {
-            return Err(DeserializationError::InvalidValue(
-                "a FRI proof layer must contain at least one queried evaluation".to_string(),
-            ));
-        }
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let (mut values_left, mut paths_left) = (usize::MAX, usize::MAX);
+        let (values, paths) = read_layer_bytes(source, &mut values_left, &mut paths_left)?;
+        Ok(FridaProofBatchLayer { values, paths })
+    }
+}
 
-        // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
+/// Reads the values and paths of a proof layer, each prefixed with its length, failing before
+/// reading either if it is longer than `values_left` or `paths_left`, which are reduced by the
+/// bytes read.
+fn read_layer_bytes<R: ByteReader>(
+    source: &mut R,
+    values_left: &mut usize,
+    paths_left: &mut usize,
+) -> Result<(Vec<u8>, Vec<u8>), DeserializationError> {
+    // read values
+    let num_value_bytes = source.read_u32()? as usize;
+    if num_value_bytes == 0 {
+        return Err(DeserializationError::InvalidValue(
+            "a FRI proof layer must contain at least one queried evaluation".to_string(),
+        ));
+    }
+    if num_value_bytes > *values_left {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected at most {values_left} more bytes of query values, but the layer has \
+             {num_value_bytes}"
+        )));
+    }
+    *values_left -= num_value_bytes;
+    let values = source.read_vec(num_value_bytes)?;
 
-        Ok(FridaProofBatchLayer { values, paths })
+    // read paths
+    let num_paths_bytes = source.read_u32()? as usize;
+    if num_paths_bytes > *paths_left {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected at most {paths_left} more bytes of Merkle paths, but the layer has \
+             {num_paths_bytes}"
+        )));
     }
+    *paths_left -= num_paths_bytes;
+    let paths = source.read_vec(num_paths_bytes)?;
+
+    Ok((values, paths))
 }
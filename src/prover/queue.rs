@@ -0,0 +1,311 @@
+use std::{
+    collections::VecDeque,
+    sync::{mpsc, Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+
+use super::{proof::FridaProof, FridaProver};
+use crate::error::FridaError;
+
+type OpenResult = Result<FridaProof, FridaError>;
+
+// PRIORITY CLASSES
+// ================================================================================================
+
+/// Scheduling class of an opening request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Openings consensus depends on, such as validator assignments. They are always served
+    /// before best-effort requests, and evict them when the queue is full.
+    Critical,
+    /// Openings that may be delayed or dropped under load, such as light client samples.
+    BestEffort,
+}
+
+/// Counters of a single priority class.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClassMetrics {
+    /// Requests currently waiting for a worker.
+    pub queued: usize,
+    pub submitted: u64,
+    pub completed: u64,
+    /// Requests refused with `FridaError::QueueFull`.
+    pub rejected: u64,
+    /// Queued requests cancelled to make room for a critical one.
+    pub evicted: u64,
+}
+
+/// Snapshot of the state of an `OpenQueue`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpenQueueMetrics {
+    pub critical: ClassMetrics,
+    pub best_effort: ClassMetrics,
+    /// Openings currently being computed by a worker.
+    pub in_flight: usize,
+}
+
+impl OpenQueueMetrics {
+    fn class_mut(&mut self, priority: Priority) -> &mut ClassMetrics {
+        match priority {
+            Priority::Critical => &mut self.critical,
+            Priority::BestEffort => &mut self.best_effort,
+        }
+    }
+}
+
+// OPEN QUEUE
+// ================================================================================================
+
+/// A bounded work queue computing openings on a fixed number of worker threads.
+///
+/// At most `capacity` requests may be waiting at once. Once the queue is full, best-effort
+/// requests are rejected with `FridaError::QueueFull`, while critical requests evict the most
+/// recently queued best-effort request, and are only rejected when no such request is left.
+/// Workers always pick the oldest critical request first.
+///
+/// Dropping the queue cancels all waiting requests and waits for in-flight openings to finish.
+pub struct OpenQueue<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    shared: Arc<Shared<E, H>>,
+    capacity: usize,
+    workers: Vec<JoinHandle<()>>,
+}
+
+struct Job<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    prover: Arc<FridaProver<E, H>>,
+    positions: Vec<usize>,
+    priority: Priority,
+    reply: mpsc::Sender<OpenResult>,
+}
+
+struct Shared<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    state: Mutex<State<E, H>>,
+    available: Condvar,
+}
+
+struct State<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    critical: VecDeque<Job<E, H>>,
+    best_effort: VecDeque<Job<E, H>>,
+    metrics: OpenQueueMetrics,
+    shutdown: bool,
+}
+
+impl<E, H> State<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn len(&self) -> usize {
+        self.critical.len() + self.best_effort.len()
+    }
+
+    fn pop(&mut self) -> Option<Job<E, H>> {
+        let job = self
+            .critical
+            .pop_front()
+            .or_else(|| self.best_effort.pop_front())?;
+        self.metrics.class_mut(job.priority).queued -= 1;
+        self.metrics.in_flight += 1;
+        Some(job)
+    }
+}
+
+impl<E, H> OpenQueue<E, H>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+{
+    /// Starts `concurrency` workers serving a queue of at most `capacity` waiting requests.
+    pub fn new(concurrency: usize, capacity: usize) -> Self {
+        assert!(concurrency > 0, "at least one worker is required");
+        let mut queue = Self::without_workers(capacity);
+        queue.spawn_workers(concurrency);
+        queue
+    }
+
+    fn without_workers(capacity: usize) -> Self {
+        assert!(capacity > 0, "queue capacity must be at least one");
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                critical: VecDeque::new(),
+                best_effort: VecDeque::new(),
+                metrics: OpenQueueMetrics::default(),
+                shutdown: false,
+            }),
+            available: Condvar::new(),
+        });
+        OpenQueue {
+            shared,
+            capacity,
+            workers: Vec::new(),
+        }
+    }
+
+    /// Queues the opening of `positions` from `prover`.
+    ///
+    /// Returns `FridaError::QueueFull` if the request cannot be queued; the returned ticket
+    /// resolves to `FridaError::OpeningCancelled` if the request is evicted later on.
+    pub fn submit(
+        &self,
+        prover: Arc<FridaProver<E, H>>,
+        positions: Vec<usize>,
+        priority: Priority,
+    ) -> Result<OpenTicket, FridaError> {
+        let (reply, result) = mpsc::channel();
+        let mut state = self.shared.state.lock().unwrap();
+        state.metrics.class_mut(priority).submitted += 1;
+
+        if state.len() >= self.capacity {
+            let evicted = match priority {
+                Priority::Critical => state.best_effort.pop_back(),
+                Priority::BestEffort => None,
+            };
+            let Some(evicted) = evicted else {
+                state.metrics.class_mut(priority).rejected += 1;
+                return Err(FridaError::QueueFull);
+            };
+            state.metrics.best_effort.queued -= 1;
+            state.metrics.best_effort.evicted += 1;
+            // the receiver may already be gone, in which case nobody is waiting for the result
+            let _ = evicted.reply.send(Err(FridaError::OpeningCancelled));
+        }
+
+        let job = Job {
+            prover,
+            positions,
+            priority,
+            reply,
+        };
+        match priority {
+            Priority::Critical => state.critical.push_back(job),
+            Priority::BestEffort => state.best_effort.push_back(job),
+        }
+        state.metrics.class_mut(priority).queued += 1;
+        drop(state);
+
+        self.shared.available.notify_one();
+        Ok(OpenTicket { result })
+    }
+
+    pub fn metrics(&self) -> OpenQueueMetrics {
+        self.shared.state.lock().unwrap().metrics
+    }
+
+    fn spawn_workers(&mut self, concurrency: usize) {
+        self.workers.extend((0..concurrency).map(|_| {
+            let shared = self.shared.clone();
+            thread::spawn(move || Self::work(&shared))
+        }));
+    }
+
+    fn work(shared: &Shared<E, H>) {
+        loop {
+            let job = {
+                let mut state = shared.state.lock().unwrap();
+                loop {
+                    if state.shutdown {
+                        return;
+                    }
+                    if let Some(job) = state.pop() {
+                        break job;
+                    }
+                    state = shared.available.wait(state).unwrap();
+                }
+            };
+
+            let result = job.prover.open(&job.positions);
+
+            // counted before replying, so that metrics already include the opening once its
+            // ticket resolves
+            {
+                let mut state = shared.state.lock().unwrap();
+                state.metrics.in_flight -= 1;
+                state.metrics.class_mut(job.priority).completed += 1;
+            }
+            let _ = job.reply.send(result);
+        }
+    }
+}
+
+#[cfg(test)]
+impl<E, H> OpenQueue<E, H>
+where
+    E: FieldElement + 'static,
+    H: ElementHasher<BaseField = E::BaseField> + Send + Sync + 'static,
+{
+    /// Creates a queue without workers, so that requests stay queued until `test_resume`.
+    pub fn test_paused(capacity: usize) -> Self {
+        Self::without_workers(capacity)
+    }
+
+    pub fn test_resume(&mut self, concurrency: usize) {
+        self.spawn_workers(concurrency);
+    }
+
+    /// Returns the priority of the request the next idle worker would pick.
+    pub fn test_next_priority(&self) -> Option<Priority> {
+        let state = self.shared.state.lock().unwrap();
+        state
+            .critical
+            .front()
+            .or_else(|| state.best_effort.front())
+            .map(|job| job.priority)
+    }
+}
+
+impl<E, H> Drop for OpenQueue<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn drop(&mut self) {
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            state.shutdown = true;
+            // dropping the waiting jobs resolves their tickets as cancelled
+            state.critical.clear();
+            state.best_effort.clear();
+        }
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+// OPEN TICKET
+// ================================================================================================
+
+/// Handle to the result of a queued opening.
+#[derive(Debug)]
+pub struct OpenTicket {
+    result: mpsc::Receiver<OpenResult>,
+}
+
+impl OpenTicket {
+    /// Blocks until the opening has been computed, has failed, or was cancelled.
+    pub fn wait(self) -> OpenResult {
+        self.result
+            .recv()
+            .unwrap_or(Err(FridaError::OpeningCancelled))
+    }
+}
@@ -0,0 +1,107 @@
+use winter_crypto::Hasher;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{proof::FridaProof, ProverCommitment};
+
+// SAMPLE RECEIPT
+// ================================================================================================
+
+/// An opening of a single position, which can be verified on its own and forwarded between peers
+/// without the rest of the samples it was drawn with.
+///
+/// `evaluations` holds one value per committed polynomial, `proof` carries the Merkle branches of
+/// every FRI layer for `position`, and `binding` is the digest of the commitment the sample was
+/// opened against, allowing samples of unrelated commitments to be dropped before verification.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SampleReceipt<E: FieldElement, H: Hasher> {
+    pub position: usize,
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+    pub binding: H::Digest,
+}
+
+impl<E: FieldElement, H: Hasher> Clone for SampleReceipt<E, H> {
+    fn clone(&self) -> Self {
+        SampleReceipt {
+            position: self.position,
+            evaluations: self.evaluations.clone(),
+            proof: self.proof.clone(),
+            binding: self.binding,
+        }
+    }
+}
+
+impl<H: Hasher> ProverCommitment<H> {
+    /// Returns a digest binding the roots and metadata of this commitment, data roots included.
+    /// Data checksums are left out, so that commitments with and without them share the same
+    /// binding. The root digest of the commitment is the byte encoding of this digest; see
+    /// `ProverCommitment::root_digest`.
+    ///
+    /// Every field is written as in `Serializable`: variable-length fields are prefixed with
+    /// their length, and optional ones with a 0/1 presence tag, so that no two commitments
+    /// hash the same bytes.
+    pub fn binding(&self) -> H::Digest {
+        let mut bytes = Vec::new();
+        self.roots.write_into(&mut bytes);
+        self.domain_size.write_into(&mut bytes);
+        self.poly_count.write_into(&mut bytes);
+        self.batch_leaf_encoding.write_into(&mut bytes);
+        self.num_partitions.write_into(&mut bytes);
+        match &self.app_ids_root {
+            Some(root) => {
+                bytes.write_u8(1);
+                root.write_into(&mut bytes);
+            }
+            None => bytes.write_u8(0),
+        }
+        self.ood_evaluation.write_into(&mut bytes);
+        match &self.dispersal_hint {
+            Some(hint) => {
+                bytes.write_u8(1);
+                hint.write_into(&mut bytes);
+            }
+            None => bytes.write_u8(0),
+        }
+        match &self.data_roots {
+            Some(roots) => {
+                bytes.write_u8(1);
+                roots.write_into(&mut bytes);
+            }
+            None => bytes.write_u8(0),
+        }
+        match &self.profile {
+            Some(profile) => {
+                bytes.write_u8(1);
+                profile.write_into(&mut bytes);
+            }
+            None => bytes.write_u8(0),
+        }
+        H::hash(&bytes)
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Serializable for SampleReceipt<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.position.write_into(target);
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+        self.binding.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for SampleReceipt<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let position = usize::read_from(source)?;
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+        let binding = H::Digest::read_from(source)?;
+
+        Ok(SampleReceipt {
+            position,
+            evaluations,
+            proof,
+            binding,
+        })
+    }
+}
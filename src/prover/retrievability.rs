@@ -0,0 +1,169 @@
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_rand_utils::rand_array;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{epoch::draw_seeded_positions, proof::FridaProof, FridaProver, ProverCommitment};
+use crate::{error::FridaError, verifier::das::FridaDasVerifier};
+
+/// Domain separator absorbed along with the seed of a challenge, so that challenged positions
+/// cannot collide with positions drawn by other protocols from the same commitment.
+const RETRIEVABILITY_DOMAIN: &[u8] = b"frida-por-v1";
+
+// RETRIEVABILITY CHALLENGE
+// ================================================================================================
+
+// Storage providers are audited with a challenge/response protocol: an auditor sends a fresh
+// random seed, from which both sides derive the positions of the commitment the provider must
+// open, and accepts the response only if it opens them by the deadline of the challenge. As the
+// seed is unpredictable, a provider who dropped part of the data cannot answer for long.
+
+/// Challenge sent by an auditor to the holder of a commitment: open the positions derived from
+/// `seed` by `deadline`.
+///
+/// The deadline is expressed in whatever clock the deployment agrees on, e.g. a unix timestamp in
+/// seconds or a block height; it is only ever compared with the time a response was received at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetrievabilityChallenge {
+    pub seed: [u8; 32],
+    /// Number of positions drawn from the seed, before deduplication.
+    pub num_queries: usize,
+    pub deadline: u64,
+}
+
+/// Response of a storage provider to a `RetrievabilityChallenge`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RetrievabilityResponse<E: FieldElement> {
+    /// Evaluations at the challenged positions, laid out as for `FridaDasVerifier::verify`.
+    pub evaluations: Vec<E>,
+    pub proof: FridaProof,
+}
+
+impl RetrievabilityChallenge {
+    /// Creates a challenge from a fresh random seed.
+    pub fn random(num_queries: usize, deadline: u64) -> Self {
+        RetrievabilityChallenge {
+            seed: rand_array(),
+            num_queries,
+            deadline,
+        }
+    }
+
+    /// Returns the positions of `commitment` the challenge asks for, sorted and deduplicated.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BadNumQueries` if the number of queries of the challenge is zero or
+    /// not smaller than the domain.
+    pub fn positions<E, HHst, H>(
+        &self,
+        commitment: &ProverCommitment<H>,
+    ) -> Result<Vec<usize>, FridaError>
+    where
+        E: FieldElement,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        draw_seeded_positions::<E, HHst, H>(
+            commitment,
+            RETRIEVABILITY_DOMAIN,
+            &self.seed,
+            self.num_queries,
+        )
+    }
+
+    /// Checks that `response`, received at time `now`, answers this challenge for `commitment`,
+    /// made with `options` and with `HHst` as the transcript hasher.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ChallengeExpired` if the response was received after the deadline,
+    /// and the errors of `FridaDasVerifier::verify` if it does not open the challenged positions.
+    pub fn verify_response<E, HHst, H>(
+        &self,
+        commitment: &ProverCommitment<H>,
+        response: &RetrievabilityResponse<E>,
+        now: u64,
+        options: &FriOptions,
+    ) -> Result<(), FridaError>
+    where
+        E: FieldElement,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        if now > self.deadline {
+            return Err(FridaError::ChallengeExpired(self.deadline, now));
+        }
+
+        let positions = self.positions::<E, HHst, H>(commitment)?;
+        let verifier =
+            FridaDasVerifier::<E, HHst, H>::from_commitment(commitment, options.clone())?;
+        verifier.verify(&response.proof, &response.evaluations, &positions)
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Answers `challenge` with the openings of the positions it asks for; the public coin
+    /// deriving them must use `HHst`, as for the commitment of this prover.
+    ///
+    /// # Errors
+    /// Returns the errors of `RetrievabilityChallenge::positions` and `open`, e.g.
+    /// `FridaError::PositionPruned` if the prover was pruned.
+    pub fn respond_to_challenge<HHst>(
+        &self,
+        challenge: &RetrievabilityChallenge,
+    ) -> Result<RetrievabilityResponse<E>, FridaError>
+    where
+        HHst: ElementHasher<BaseField = E::BaseField>,
+    {
+        let positions = challenge.positions::<E, HHst, H>(&self.commitment())?;
+        Ok(RetrievabilityResponse {
+            evaluations: self.get_evaluations(&positions)?,
+            proof: self.open(&positions)?,
+        })
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for RetrievabilityChallenge {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.seed);
+        self.num_queries.write_into(target);
+        target.write_u64(self.deadline);
+    }
+}
+
+impl Deserializable for RetrievabilityChallenge {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let seed = source.read_array()?;
+        let num_queries = usize::read_from(source)?;
+        let deadline = source.read_u64()?;
+
+        Ok(RetrievabilityChallenge {
+            seed,
+            num_queries,
+            deadline,
+        })
+    }
+}
+
+impl<E: FieldElement> Serializable for RetrievabilityResponse<E> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for RetrievabilityResponse<E> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(RetrievabilityResponse { evaluations, proof })
+    }
+}
@@ -0,0 +1,276 @@
+use core::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{builder::FridaProverBuilder, cost::CostModel, FridaProver, ProverCommitment};
+use crate::{core::queries::calculate_batch_security_level, error::FridaError};
+
+/// Weight of the latest observation in the slowdown of a `CommitmentScheduler`, so that it
+/// follows changes in load within a few commitments without jumping on a single outlier.
+const SLOWDOWN_SMOOTHING: f64 = 0.25;
+
+// COMMIT PROFILE
+// ================================================================================================
+
+/// FRI parameters a commitment is made with, recorded in commitments built with
+/// `FridaProverBuilder::with_commit_profile` so that verifiers know the options to check them
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitProfile {
+    pub blowup_factor: usize,
+    pub folding_factor: usize,
+    pub remainder_max_degree: usize,
+}
+
+impl CommitProfile {
+    /// # Panics
+    /// Panics if the parameters are not valid `FriOptions`.
+    pub fn new(blowup_factor: usize, folding_factor: usize, remainder_max_degree: usize) -> Self {
+        // the parameters are checked by the options
+        FriOptions::new(blowup_factor, folding_factor, remainder_max_degree);
+        CommitProfile {
+            blowup_factor,
+            folding_factor,
+            remainder_max_degree,
+        }
+    }
+
+    pub fn options(&self) -> FriOptions {
+        FriOptions::new(
+            self.blowup_factor,
+            self.folding_factor,
+            self.remainder_max_degree,
+        )
+    }
+}
+
+// COMMITMENT SCHEDULER
+// ================================================================================================
+
+/// Profile selected by a `CommitmentScheduler` for a commitment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledProfile {
+    pub profile: CommitProfile,
+    /// Predicted commitment time, scaled by the throughput measured so far.
+    pub estimated_time: Duration,
+    /// False if no profile was predicted to finish in time, in which case the fastest one is
+    /// selected.
+    pub on_time: bool,
+}
+
+/// Selects the parameters of commitments which must complete by a deadline, e.g. a block
+/// proposal slot, from a list of profiles in order of preference, typically from the largest
+/// blowup factor down to the fastest parameters. Profiles below a minimum security level are
+/// never selected, however close the deadline.
+///
+/// Commitment times are predicted with a `CostModel`, scaled by the ratio of measured to
+/// predicted times of the commitments observed so far, so that the scheduler adapts to the
+/// machine and its load.
+#[derive(Debug, Clone)]
+pub struct CommitmentScheduler<E: FieldElement> {
+    profiles: Vec<CommitProfile>,
+    /// Security level, in bits, below which profiles are not selected.
+    min_security: u32,
+    cost_model: CostModel,
+    /// Ratio of measured to predicted commitment times, or 1 before any observation.
+    slowdown: f64,
+    observations: usize,
+    _phantom_field_element: PhantomData<E>,
+}
+
+impl<E: FieldElement> CommitmentScheduler<E> {
+    /// Creates a scheduler selecting among `profiles` those which provide at least
+    /// `min_security` bits of security.
+    ///
+    /// # Panics
+    /// Panics if `profiles` is empty.
+    pub fn new(profiles: Vec<CommitProfile>, min_security: u32) -> Self {
+        assert!(
+            !profiles.is_empty(),
+            "a scheduler needs at least one profile"
+        );
+        CommitmentScheduler {
+            profiles,
+            min_security,
+            cost_model: CostModel::default(),
+            slowdown: 1.0,
+            observations: 0,
+            _phantom_field_element: PhantomData,
+        }
+    }
+
+    /// Predicts commitment times with `cost_model`, e.g. as measured by `frida-bench calibrate`,
+    /// instead of the default one.
+    pub fn with_cost_model(mut self, cost_model: CostModel) -> Self {
+        self.cost_model = cost_model;
+        self
+    }
+
+    /// Ratio of measured to predicted commitment times, as observed so far.
+    pub fn slowdown(&self) -> f64 {
+        self.slowdown
+    }
+
+    /// Selects the first profile predicted to commit to `data_size` bytes within `time_left`, or
+    /// the fastest profile if none is. Profiles whose commitments opened at `num_queries`
+    /// positions fall below the minimum security level, or whose domain cannot hold the data or
+    /// is too small to be folded, are skipped.
+    ///
+    /// # Errors
+    /// Returns the error for the last profile, e.g. `FridaError::InsufficientSecurity`, if no
+    /// profile can commit to `data_size` bytes securely.
+    pub fn select(
+        &self,
+        data_size: usize,
+        num_queries: usize,
+        time_left: Duration,
+    ) -> Result<ScheduledProfile, FridaError> {
+        let mut fastest: Option<ScheduledProfile> = None;
+        let mut error = None;
+        for &profile in &self.profiles {
+            let cost = match self
+                .check_security(&profile, data_size, num_queries)
+                .and_then(|_| {
+                    self.cost_model
+                        .estimate_commit_cost::<E>(data_size, &profile.options())
+                }) {
+                Ok(cost) => cost,
+                Err(e) => {
+                    error = Some(e);
+                    continue;
+                }
+            };
+            // estimates too large for a duration are never on time
+            let estimated_time =
+                Duration::try_from_secs_f64(cost.estimated_time_ms * self.slowdown / 1e3)
+                    .unwrap_or(Duration::MAX);
+            let scheduled = ScheduledProfile {
+                profile,
+                estimated_time,
+                on_time: estimated_time <= time_left,
+            };
+            if scheduled.on_time {
+                return Ok(scheduled);
+            }
+            if fastest.is_none_or(|f| estimated_time < f.estimated_time) {
+                fastest = Some(scheduled);
+            }
+        }
+        fastest.ok_or_else(|| error.expect("every profile failed"))
+    }
+
+    /// Accounts for a commitment to `data_size` bytes under `profile` which took `elapsed`, so
+    /// that later selections follow the measured throughput. Observations the cost model cannot
+    /// predict, or predicts to take no time, are ignored.
+    pub fn observe(&mut self, profile: &CommitProfile, data_size: usize, elapsed: Duration) {
+        let Ok(cost) = self
+            .cost_model
+            .estimate_commit_cost::<E>(data_size, &profile.options())
+        else {
+            return;
+        };
+        let ratio = elapsed.as_secs_f64() * 1e3 / cost.estimated_time_ms;
+        if !ratio.is_finite() {
+            return;
+        }
+        self.slowdown = if self.observations == 0 {
+            ratio
+        } else {
+            self.slowdown + SLOWDOWN_SMOOTHING * (ratio - self.slowdown)
+        };
+        self.observations += 1;
+    }
+
+    /// Checks that commitments to `data_size` bytes under `profile`, opened at `num_queries`
+    /// positions, provide the minimum security level of this scheduler.
+    fn check_security(
+        &self,
+        profile: &CommitProfile,
+        data_size: usize,
+        num_queries: usize,
+    ) -> Result<(), FridaError> {
+        let level =
+            calculate_batch_security_level::<E>(data_size, &profile.options(), 1, num_queries)?;
+        if level < self.min_security {
+            return Err(FridaError::InsufficientSecurity(level, self.min_security));
+        }
+        Ok(())
+    }
+
+    /// Commits to `data` under the profile selected for `deadline`, recording the profile in the
+    /// commitment, and observes the time the commitment took.
+    ///
+    /// # Errors
+    /// Returns the errors of `select` and `FridaProverBuilder::commitment`.
+    #[allow(clippy::type_complexity)]
+    pub fn commit_by<H, HHst>(
+        &mut self,
+        data: &[u8],
+        num_queries: usize,
+        deadline: Instant,
+    ) -> Result<(ProverCommitment<H>, FridaProver<E, H>, Vec<usize>), FridaError>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+    {
+        let start = Instant::now();
+        let scheduled = self.select(
+            data.len(),
+            num_queries,
+            deadline.saturating_duration_since(start),
+        )?;
+        let builder = FridaProverBuilder::<E, H, HHst>::new(scheduled.profile.options())
+            .with_commit_profile(scheduled.profile);
+        let result = builder.commitment(data, num_queries)?;
+        self.observe(&scheduled.profile, data.len(), start.elapsed());
+        Ok(result)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for CommitProfile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.blowup_factor.write_into(target);
+        self.folding_factor.write_into(target);
+        self.remainder_max_degree.write_into(target);
+    }
+}
+
+impl Deserializable for CommitProfile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blowup_factor = usize::read_from(source)?;
+        let folding_factor = usize::read_from(source)?;
+        let remainder_max_degree = usize::read_from(source)?;
+
+        if blowup_factor < 2 || !blowup_factor.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "blowup factor must be a power of two of at least 2, but was {blowup_factor}"
+            )));
+        }
+        if ![2, 4, 8, 16].contains(&folding_factor) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "folding factor must be 2, 4, 8 or 16, but was {folding_factor}"
+            )));
+        }
+        if !remainder_max_degree
+            .checked_add(1)
+            .is_some_and(usize::is_power_of_two)
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "remainder degree must be one less than a power of two, but was {remainder_max_degree}"
+            )));
+        }
+
+        Ok(CommitProfile {
+            blowup_factor,
+            folding_factor,
+            remainder_max_degree,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use winter_crypto::hashers::Blake3_256;
+use winter_fri::FriOptions;
+
+use super::builder::FridaProverBuilder;
+use crate::{
+    core::{
+        data::{EncodingOptions, Packing},
+        f31,
+    },
+    verifier::das::FridaDasVerifier,
+};
+
+// SMALL-FIELD BACKEND
+// ================================================================================================
+
+/// Evaluations of commitments in the 31-bit field of `core::f31`, which are elements of its
+/// degree-4 extension so that challenges are drawn from the extension.
+pub type F31Element = f31::QuarticElement;
+
+/// BLAKE3 over the 31-bit field, hashing both the Merkle trees and the transcript.
+pub type F31Hasher = Blake3_256<f31::BaseElement>;
+
+pub type F31ProverBuilder = FridaProverBuilder<F31Element, F31Hasher>;
+
+pub type F31DasVerifier = FridaDasVerifier<F31Element, F31Hasher, F31Hasher>;
+
+/// Encoding of data committed in the 31-bit field. The default packing does not fit below the
+/// modulus of the field, and `Packing::Bits` stores as many bytes per element as the default
+/// packing does in the 128-bit field; see `core::f31`.
+pub const F31_ENCODING: EncodingOptions = EncodingOptions {
+    packing: Packing::Bits,
+    sharding: false,
+};
+
+/// Returns a builder of commitments in the 31-bit field, encoding data with `F31_ENCODING`.
+/// Data must be decoded with the same encoding, e.g. with
+/// `core::data::recover_data_with_encoding`.
+pub fn f31_prover_builder(options: FriOptions) -> F31ProverBuilder {
+    FridaProverBuilder::new(options).with_encoding(F31_ENCODING)
+}
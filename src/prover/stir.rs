@@ -0,0 +1,378 @@
+use winter_crypto::{Digest, ElementHasher, Hasher};
+use winter_fri::{folding::fold_positions, FriOptions};
+use winter_math::{fft, polynom, FieldElement};
+use winter_utils::{
+    group_slice_elements, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
+};
+
+use super::{
+    builder::commit_layer, get_evaluations_from_positions, merkle_error, proof::FridaProofLayer,
+    FridaLayer,
+};
+use crate::{
+    core::{
+        random::FridaRandom,
+        stir::{
+            draw_round_indexes, fold_coefficients, folded_domain_point, stir_rounds, StirRound,
+        },
+    },
+    error::FridaError,
+};
+
+// STIR COMMITMENT
+// ================================================================================================
+
+/// Commitment built in the experimental STIR mode by
+/// `FridaProverBuilder::commit_and_prove_stir`.
+///
+/// `roots` holds the root of the tree of the encoded blob, followed by the root of the tree of
+/// the folded polynomial of every round but the last; `proof` shows that the first tree commits
+/// to a polynomial of degree below `domain_size / blowup_factor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StirCommitment<H: Hasher> {
+    pub roots: Vec<H::Digest>,
+    pub proof: StirProof,
+    pub domain_size: usize,
+    pub num_queries: usize,
+}
+
+/// Low-degree proof of a STIR commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StirProof {
+    /// Queried rows of the tested polynomial of every round, with their Merkle paths.
+    rounds: Vec<FridaProofLayer>,
+    /// Value of the folded polynomial of every round but the last at its out-of-domain point.
+    ood_answers: Vec<u8>,
+    /// Coefficients of the polynomial folded in the last round.
+    final_poly: Vec<u8>,
+}
+
+impl StirProof {
+    pub(crate) fn new<E: FieldElement>(
+        rounds: Vec<FridaProofLayer>,
+        ood_answers: &[E],
+        final_poly: &[E],
+    ) -> Self {
+        let mut ood_bytes = Vec::with_capacity(E::ELEMENT_BYTES * ood_answers.len());
+        ood_bytes.write_many(ood_answers);
+        let mut final_bytes = Vec::with_capacity(E::ELEMENT_BYTES * final_poly.len());
+        final_bytes.write_many(final_poly);
+
+        StirProof {
+            rounds,
+            ood_answers: ood_bytes,
+            final_poly: final_bytes,
+        }
+    }
+
+    /// Returns the number of rounds of this proof.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Returns the queried rows of round `index`.
+    pub fn round(&self, index: usize) -> Option<&FridaProofLayer> {
+        self.rounds.get(index)
+    }
+
+    /// Returns the size of this proof in bytes.
+    pub fn size(&self) -> usize {
+        // +4 for the number of rounds and each of the two value lengths
+        self.rounds.iter().fold(
+            self.ood_answers.len() + self.final_poly.len() + 12,
+            |acc, round| acc + round.size(),
+        )
+    }
+
+    /// Parses the out-of-domain answers of this proof.
+    pub fn parse_ood_answers<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        parse_elements(&self.ood_answers)
+    }
+
+    /// Parses the coefficients of the final polynomial of this proof.
+    pub fn parse_final_poly<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        parse_elements(&self.final_poly)
+    }
+}
+
+fn parse_elements<E: FieldElement>(bytes: &[u8]) -> Result<Vec<E>, DeserializationError> {
+    if !bytes.len().is_multiple_of(E::ELEMENT_BYTES) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of bytes ({}) does not divide into whole number of field elements",
+            bytes.len(),
+        )));
+    }
+    let mut reader = SliceReader::new(bytes);
+    let elements = reader.read_many(bytes.len() / E::ELEMENT_BYTES)?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(elements)
+}
+
+impl Serializable for StirProof {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.rounds.write_into(target);
+        self.ood_answers.write_into(target);
+        self.final_poly.write_into(target);
+    }
+}
+
+impl Deserializable for StirProof {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let rounds = Vec::<FridaProofLayer>::read_from(source)?;
+        let ood_answers = Vec::<u8>::read_from(source)?;
+        let final_poly = Vec::<u8>::read_from(source)?;
+
+        Ok(StirProof {
+            rounds,
+            ood_answers,
+            final_poly,
+        })
+    }
+}
+
+// STIR PROVER
+// ================================================================================================
+
+/// Prover of a STIR commitment. Only the tree of the encoded blob is kept, as it is all that
+/// sample openings need once the low-degree proof of the commitment has been checked.
+#[derive(Debug)]
+pub struct StirProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    layer: FridaLayer<E, H>,
+    domain_size: usize,
+    folding_factor: usize,
+}
+
+impl<E, H> StirProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens the encoded blob at `positions`, to be checked by `StirVerifier::verify`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::NoPositions` if `positions` is empty, and
+    /// `FridaError::PositionOutOfDomain` if a position lies outside of the domain.
+    pub fn open(&self, positions: &[usize]) -> Result<FridaProofLayer, FridaError> {
+        if positions.is_empty() {
+            return Err(FridaError::NoPositions);
+        }
+        self.check_domain(positions)?;
+        let indexes = fold_positions(positions, self.domain_size, self.folding_factor);
+        match self.folding_factor {
+            4 => open_rows::<E, H, 4>(&self.layer, &indexes),
+            8 => open_rows::<E, H, 8>(&self.layer, &indexes),
+            16 => open_rows::<E, H, 16>(&self.layer, &indexes),
+            _ => Err(FridaError::UnsupportedFoldingFactor(self.folding_factor)),
+        }
+    }
+
+    /// Returns the evaluations of the encoded blob at `positions`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PositionOutOfDomain` if a position lies outside of the domain.
+    pub fn get_evaluations(&self, positions: &[usize]) -> Result<Vec<E>, FridaError> {
+        self.check_domain(positions)?;
+        Ok(get_evaluations_from_positions(
+            &self.layer.evaluations,
+            positions,
+            1,
+            self.domain_size,
+            self.folding_factor,
+        ))
+    }
+
+    fn check_domain(&self, positions: &[usize]) -> Result<(), FridaError> {
+        match positions.iter().find(|&&p| p >= self.domain_size) {
+            Some(&position) => Err(FridaError::PositionOutOfDomain(position, self.domain_size)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the root of the tree of the encoded blob.
+    pub fn root(&self) -> H::Digest {
+        *self.layer.tree.root()
+    }
+
+    /// Size of the evaluation domain of the encoded blob.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Evaluations of the encoded blob, as committed in the tree.
+    pub fn evaluations(&self) -> &[E] {
+        &self.layer.evaluations
+    }
+}
+
+/// Builds a STIR commitment to `evaluations`, the Reed-Solomon encoding of a blob.
+pub(crate) fn prove<E, H, HHst>(
+    options: &FriOptions,
+    evaluations: Vec<E>,
+    num_queries: usize,
+) -> Result<(StirCommitment<H>, StirProver<E, H>), FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    let rounds = stir_rounds(options, evaluations.len(), num_queries)?;
+    // static dispatch for folding factor parameter
+    let folding_factor = options.folding_factor();
+    let (roots, proof, layer) = match folding_factor {
+        4 => prove_rounds::<E, H, HHst, 4>(options, &evaluations, &rounds)?,
+        8 => prove_rounds::<E, H, HHst, 8>(options, &evaluations, &rounds)?,
+        16 => prove_rounds::<E, H, HHst, 16>(options, &evaluations, &rounds)?,
+        _ => return Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
+    };
+
+    Ok((
+        StirCommitment {
+            roots,
+            proof,
+            domain_size: evaluations.len(),
+            num_queries,
+        },
+        StirProver {
+            layer,
+            domain_size: evaluations.len(),
+            folding_factor,
+        },
+    ))
+}
+
+/// Runs every round of the protocol with folding factor N, returning the roots of the committed
+/// trees, the proof, and the tree of the encoded blob.
+#[allow(clippy::type_complexity)]
+fn prove_rounds<E, H, HHst, const N: usize>(
+    options: &FriOptions,
+    evaluations: &[E],
+    rounds: &[StirRound],
+) -> Result<(Vec<H::Digest>, StirProof, FridaLayer<E, H>), FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+{
+    let domain_offset = options.domain_offset::<E::BaseField>();
+
+    // the evaluations are those of a polynomial over the domain shifted by the domain offset
+    let mut coefficients = evaluations.to_vec();
+    let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
+    fft::interpolate_poly_with_offset(&mut coefficients, &inv_twiddles, domain_offset);
+    coefficients.truncate(rounds[0].degree);
+
+    let first_layer = commit_layer::<E, H, N>(evaluations)?;
+    let mut roots = vec![*first_layer.tree.root()];
+    let mut layer: Option<FridaLayer<E, H>> = None;
+    let mut openings = Vec::with_capacity(rounds.len());
+    let mut ood_answers = Vec::with_capacity(rounds.len() - 1);
+    let mut public_coin = FridaRandom::<E, HHst, H>::new();
+
+    let (last_round, inner_rounds) = rounds.split_last().expect("no STIR rounds");
+    for (depth, round) in inner_rounds.iter().enumerate() {
+        let current = layer.as_ref().unwrap_or(&first_layer);
+        public_coin.reseed(&roots[depth].as_bytes());
+        let alpha = public_coin.draw()?;
+        let folded = fold_coefficients(&coefficients, N, alpha);
+
+        // evaluate the folded polynomial over a domain half the size of the current one
+        let twiddles = fft::get_twiddles(folded.len());
+        let folded_evaluations = fft::evaluate_poly_with_offset(
+            &folded,
+            &twiddles,
+            domain_offset,
+            round.domain_size / 2 / folded.len(),
+        );
+        let folded_layer = commit_layer::<E, H, N>(&folded_evaluations)?;
+        roots.push(*folded_layer.tree.root());
+
+        // bind the folded polynomial to its value at an out-of-domain point
+        public_coin.reseed(&roots[depth + 1].as_bytes());
+        let ood_point = public_coin.draw()?;
+        let ood_answer = polynom::eval(&folded, ood_point);
+        ood_answers.push(ood_answer);
+        public_coin.reseed(&H::hash_elements(&[ood_answer]).as_bytes());
+        let comb = public_coin.draw()?;
+
+        let indexes = draw_round_indexes(&public_coin, round, N)?;
+        openings.push(open_rows::<E, H, N>(current, &indexes)?);
+
+        // the next round tests the folded polynomial, constrained to the values it takes at the
+        // out-of-domain point and at the queried points
+        let mut points = vec![ood_point];
+        points.extend(indexes.iter().map(|&index| {
+            E::from(folded_domain_point(
+                round.domain_size,
+                N,
+                domain_offset,
+                index,
+            ))
+        }));
+        coefficients = constrain(&folded, &points, comb);
+        layer = Some(folded_layer);
+    }
+
+    // the polynomial folded in the last round is sent in the clear
+    let current = layer.as_ref().unwrap_or(&first_layer);
+    public_coin.reseed(&roots[inner_rounds.len()].as_bytes());
+    let alpha = public_coin.draw()?;
+    let final_poly = fold_coefficients(&coefficients, N, alpha);
+    public_coin.reseed(&H::hash_elements(&final_poly).as_bytes());
+    let indexes = draw_round_indexes(&public_coin, last_round, N)?;
+    openings.push(open_rows::<E, H, N>(current, &indexes)?);
+
+    let proof = StirProof::new(openings, &ood_answers, &final_poly);
+    Ok((roots, proof, first_layer))
+}
+
+/// Returns the coefficients of `(f - answers) / vanishing * correction`, where `answers`
+/// interpolates the values `f` takes at `points`, `vanishing` vanishes at `points` and
+/// `correction` is `sum_l (comb * x)^l` for `l` up to the number of points, which brings the
+/// degree bound of the quotient back to that of `f`.
+fn constrain<E: FieldElement>(coefficients: &[E], points: &[E], comb: E) -> Vec<E> {
+    let values = points
+        .iter()
+        .map(|&point| polynom::eval(coefficients, point))
+        .collect::<Vec<_>>();
+    let answers = polynom::interpolate(points, &values, false);
+
+    let mut quotient = polynom::sub(coefficients, &answers);
+    for &point in points {
+        polynom::syn_div_in_place(&mut quotient, 1, point);
+    }
+
+    let correction = (0..=points.len())
+        .scan(E::ONE, |power, _| {
+            let current = *power;
+            *power *= comb;
+            Some(current)
+        })
+        .collect::<Vec<_>>();
+    let mut result = polynom::mul(&quotient, &correction);
+    result.truncate(coefficients.len());
+    result
+}
+
+/// Opens the rows of `layer` at `indexes`.
+fn open_rows<E, H, const N: usize>(
+    layer: &FridaLayer<E, H>,
+    indexes: &[usize],
+) -> Result<FridaProofLayer, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let proof = layer.tree.prove_batch(indexes).map_err(merkle_error)?;
+    let rows = group_slice_elements::<E, N>(&layer.evaluations);
+    Ok(FridaProofLayer::new(
+        indexes.iter().map(|&index| rows[index]).collect(),
+        proof,
+    ))
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+
+use winter_crypto::Hasher;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::ProverCommitment;
+use crate::error::FridaError;
+
+/// Domain separator of the signed message, so that supersession signatures cannot be replayed as
+/// signatures of other messages.
+const SUPERSEDE_DOMAIN: &[u8] = b"frida-supersede-v1";
+
+// SIGNATURES
+// ================================================================================================
+
+/// Signs supersession records. The scheme is left to the application, e.g. the keys coordinators
+/// already use for block proposals.
+pub trait RecordSigner {
+    fn sign(&self, message: &[u8]) -> Vec<u8>;
+}
+
+/// Checks the signatures of supersession records made by a `RecordSigner`.
+pub trait RecordVerifier {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool;
+}
+
+// SUPERSEDE RECORD
+// ================================================================================================
+
+/// Signed record replacing the commitment of a block by another one, e.g. when the data of the
+/// block is republished after an encoding fault was detected in the original commitment.
+///
+/// Commitments are identified by their `ProverCommitment::binding` digest. The `epoch` numbers
+/// the supersessions of a block, starting at 1, so that a record cannot be replayed once its block
+/// has moved on, e.g. to switch the block back to a commitment which a later record replaced.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SupersedeRecord<H: Hasher> {
+    pub block: u64,
+    pub epoch: u64,
+    pub old: H::Digest,
+    pub new: H::Digest,
+    pub signature: Vec<u8>,
+}
+
+impl<H: Hasher> Clone for SupersedeRecord<H> {
+    fn clone(&self) -> Self {
+        SupersedeRecord {
+            block: self.block,
+            epoch: self.epoch,
+            old: self.old,
+            new: self.new,
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> SupersedeRecord<H> {
+    /// Creates a record, signed by `signer`, replacing commitment `old` of `block` by `new` in
+    /// supersession `epoch` of the block; see `CanonicalCommitments::next_epoch`.
+    pub fn new(
+        block: u64,
+        epoch: u64,
+        old: &ProverCommitment<H>,
+        new: &ProverCommitment<H>,
+        signer: &impl RecordSigner,
+    ) -> Self {
+        let mut record = SupersedeRecord {
+            block,
+            epoch,
+            old: old.binding(),
+            new: new.binding(),
+            signature: Vec::new(),
+        };
+        record.signature = signer.sign(&record.message());
+        record
+    }
+
+    /// Returns the message signed by the record: its block, epoch and both bindings.
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = SUPERSEDE_DOMAIN.to_vec();
+        self.block.write_into(&mut message);
+        self.epoch.write_into(&mut message);
+        self.old.write_into(&mut message);
+        self.new.write_into(&mut message);
+        message
+    }
+
+    /// Checks the signature of the record with `verifier`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidSupersession` if the signature is rejected, or if the record
+    /// replaces a commitment by itself.
+    pub fn verify(&self, verifier: &impl RecordVerifier) -> Result<(), FridaError> {
+        if self.old == self.new || !verifier.verify(&self.message(), &self.signature) {
+            return Err(FridaError::InvalidSupersession);
+        }
+        Ok(())
+    }
+
+    /// True if the record replaces `commitment`.
+    pub fn supersedes(&self, commitment: &ProverCommitment<H>) -> bool {
+        self.old == commitment.binding()
+    }
+}
+
+impl<H: Hasher> Serializable for SupersedeRecord<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block.write_into(target);
+        self.epoch.write_into(target);
+        self.old.write_into(target);
+        self.new.write_into(target);
+        self.signature.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for SupersedeRecord<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block = u64::read_from(source)?;
+        let epoch = u64::read_from(source)?;
+        let old = H::Digest::read_from(source)?;
+        let new = H::Digest::read_from(source)?;
+        let signature = Vec::<u8>::read_from(source)?;
+
+        Ok(SupersedeRecord {
+            block,
+            epoch,
+            old,
+            new,
+            signature,
+        })
+    }
+}
+
+// CANONICAL COMMITMENTS
+// ================================================================================================
+
+/// Tracks the canonical commitment of every block, as replaced by verified supersession records.
+#[derive(Debug)]
+pub struct CanonicalCommitments<H: Hasher> {
+    canonical: BTreeMap<u64, H::Digest>,
+    /// Epoch of the last supersession applied to each block. It is kept when a block is
+    /// registered again, so that records applied before cannot be replayed.
+    epochs: BTreeMap<u64, u64>,
+    /// Records applied to each block, oldest first.
+    history: BTreeMap<u64, Vec<SupersedeRecord<H>>>,
+}
+
+impl<H: Hasher> Default for CanonicalCommitments<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher> CanonicalCommitments<H> {
+    pub fn new() -> Self {
+        CanonicalCommitments {
+            canonical: BTreeMap::new(),
+            epochs: BTreeMap::new(),
+            history: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the original commitment of `block`, replacing any previous state of the block
+    /// but its epoch.
+    pub fn register(&mut self, block: u64, commitment: &ProverCommitment<H>) {
+        self.canonical.insert(block, commitment.binding());
+        self.history.remove(&block);
+    }
+
+    /// Verifies `record` with `verifier`, and makes its new commitment the canonical one of its
+    /// block.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidSupersession` if the record is not validly signed, and
+    /// `FridaError::StaleSupersession` if its block is unknown, if its epoch is not the next one
+    /// of the block, e.g. because it is replayed, or if the commitment it replaces is no longer
+    /// canonical.
+    pub fn apply(
+        &mut self,
+        record: SupersedeRecord<H>,
+        verifier: &impl RecordVerifier,
+    ) -> Result<(), FridaError> {
+        record.verify(verifier)?;
+        let canonical = self
+            .canonical
+            .get_mut(&record.block)
+            .ok_or(FridaError::StaleSupersession)?;
+        let epoch = self.epochs.entry(record.block).or_default();
+        if record.epoch != *epoch + 1 || *canonical != record.old {
+            return Err(FridaError::StaleSupersession);
+        }
+
+        *canonical = record.new;
+        *epoch = record.epoch;
+        self.history.entry(record.block).or_default().push(record);
+        Ok(())
+    }
+
+    /// Binding of the canonical commitment of `block`, if registered.
+    pub fn canonical(&self, block: u64) -> Option<H::Digest> {
+        self.canonical.get(&block).copied()
+    }
+
+    /// Epoch the next supersession record of `block` must carry.
+    pub fn next_epoch(&self, block: u64) -> u64 {
+        self.epochs.get(&block).map_or(1, |epoch| epoch + 1)
+    }
+
+    /// True if `commitment` is the canonical commitment of `block`.
+    pub fn is_canonical(&self, block: u64, commitment: &ProverCommitment<H>) -> bool {
+        self.canonical(block) == Some(commitment.binding())
+    }
+
+    /// Records applied to `block`, oldest first.
+    pub fn history(&self, block: u64) -> &[SupersedeRecord<H>] {
+        self.history.get(&block).map_or(&[][..], Vec::as_slice)
+    }
+}
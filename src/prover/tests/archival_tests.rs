@@ -0,0 +1,63 @@
+use winter_fri::FriOptions;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    prover::{proof::FridaProof, BatchLeafEncoding, Commitment, ProverCommitment},
+    utils::test_utils::*,
+};
+
+#[test]
+fn stripped_commitments_keep_roots_and_metadata() {
+    let options = FriOptions::new(2, 4, 3);
+    let builder = TestFridaProverBuilder::new(options.clone())
+        .with_ood_check(true)
+        .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+    let data_list = (0..3).map(|_| rand_vector::<u8>(300)).collect::<Vec<_>>();
+    let (commitment, prover) = builder.commit_and_prove_batch(&data_list, 8).unwrap();
+
+    let root_digest = commitment.root_digest();
+    let full_size = commitment.to_bytes().len();
+    let stripped = commitment.to_prover_commitment();
+    assert_eq!(stripped, prover.commitment());
+    assert_eq!(stripped.root_digest(), root_digest);
+    assert!(stripped.to_bytes().len() < full_size);
+
+    let stripped = commitment.strip_proof();
+    assert_eq!(stripped, prover.commitment());
+
+    // archived commitments still verify openings
+    let verifier = TestFridaDasVerifier::from_commitment(&stripped, options).unwrap();
+    let positions = [1, 6, 11];
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+}
+
+#[test]
+fn stripped_proofs_can_be_reattached() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let (commitment, _) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 16)
+        .unwrap();
+    let commitment_bytes = commitment.to_bytes();
+    let proof = commitment.proof.clone();
+    let num_queries = commitment.num_queries;
+
+    // archive the stripped commitment and the proof separately, then restore both
+    let stripped_bytes = commitment.strip_proof().to_bytes();
+    let proof_bytes = proof.to_bytes();
+    let restored = ProverCommitment::<Blake3>::read_from_bytes(&stripped_bytes)
+        .unwrap()
+        .attach_proof(
+            FridaProof::read_from_bytes(&proof_bytes).unwrap(),
+            num_queries,
+        );
+    assert_eq!(restored.to_bytes(), commitment_bytes);
+    assert_eq!(
+        Commitment::<Blake3>::read_from_bytes(&commitment_bytes).unwrap(),
+        restored
+    );
+    TestFridaDasVerifier::new(restored, options).unwrap();
+}
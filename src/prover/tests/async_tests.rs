@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use winter_rand_utils::rand_vector;
+
+use crate::{error::FridaError, utils::test_utils::*};
+
+#[tokio::test]
+async fn commit_and_open_async_match_sync() {
+    let builder = Arc::new(TestFridaProverBuilder::new(test_options()));
+    let data = rand_vector::<u8>(200);
+
+    let (commitment, prover) = builder.clone().commit_async(data.clone(), 4).await.unwrap();
+    let (expected_commitment, expected_prover) = builder.commit_and_prove(&data, 4).unwrap();
+    assert_eq!(commitment, expected_commitment);
+
+    let positions = vec![0, 3, 7];
+    let proof = Arc::new(prover)
+        .open_async(positions.clone())
+        .await
+        .unwrap();
+    assert_eq!(proof, expected_prover.open(&positions).unwrap());
+}
+
+#[tokio::test]
+async fn commit_batch_async_matches_sync() {
+    let builder = Arc::new(TestFridaProverBuilder::new(test_options()));
+    let data_list = (0..4).map(|_| rand_vector::<u8>(64)).collect::<Vec<_>>();
+
+    let (commitment, _) = builder
+        .clone()
+        .commit_batch_async(data_list.clone(), 4)
+        .await
+        .unwrap();
+    let (expected_commitment, _) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+    assert_eq!(commitment, expected_commitment);
+}
+
+#[tokio::test]
+async fn commit_async_reports_errors() {
+    let builder = Arc::new(TestFridaProverBuilder::new(test_options()));
+    let result = builder.commit_async(rand_vector::<u8>(200), 0).await;
+    assert_eq!(result.unwrap_err(), FridaError::BadNumQueries(0));
+}
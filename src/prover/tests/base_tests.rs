@@ -1,9 +1,11 @@
 use winter_fri::{FriOptions, FriProver};
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
 use winter_utils::Serializable;
 
-use crate::utils::test_utils::*;
+use crate::{core::data::build_evaluations_from_data, utils::test_utils::*};
 
-use crate::prover::builder::FridaProverBuilder;
+use crate::{error::FridaError, prover::builder::FridaProverBuilder};
 
 // TEST TRAIT IMPLEMENTATION
 // ================================================================================================
@@ -62,8 +64,10 @@ fn fri_trait_check(
     let mut frida_channel = test_build_prover_channel(trace_length, &options);
     let frida_proof = {
         let frida_prover = FridaProverBuilder::new(options);
-        let prover = frida_prover.test_build_layers(&mut frida_channel, evaluations);
-        prover.open(&positions)
+        let prover = frida_prover
+            .test_build_layers(&mut frida_channel, evaluations)
+            .unwrap();
+        prover.open(&positions).unwrap()
     };
 
     assert_eq!(channel.commitments, frida_channel.commitments);
@@ -71,3 +75,56 @@ fn fri_trait_check(
     // Skipping 1 byte because frida_proof has batch layer information encoded
     assert_eq!(fri_proof.to_bytes(), frida_proof.to_bytes()[1..]);
 }
+
+#[test]
+fn batch_security_threshold() {
+    let options = FriOptions::new(2, 2, 0);
+    let data_list = vec![vec![1u8; 64]; 4];
+
+    // (8 - log2(4)) * log2(2) = 6 bits of query security
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_min_batch_security(7);
+    assert_eq!(
+        prover_builder
+            .commit_and_prove_batch(&data_list, 8)
+            .unwrap_err(),
+        FridaError::InsufficientSecurity(6, 7)
+    );
+    assert_eq!(
+        prover_builder.commitment_batch(&data_list, 8).unwrap_err(),
+        FridaError::InsufficientSecurity(6, 7)
+    );
+
+    let prover_builder = TestFridaProverBuilder::new(options).with_min_batch_security(6);
+    assert!(prover_builder.commit_and_prove_batch(&data_list, 8).is_ok());
+}
+
+#[test]
+fn evaluation_at_reads_positions_in_natural_order() {
+    let options = FriOptions::new(2, 4, 3);
+    let data_list = (0..3).map(|_| rand_vector::<u8>(200)).collect::<Vec<_>>();
+    let (commitment, prover, _) = TestFridaProverBuilder::new(options)
+        .commitment_batch(&data_list, 4)
+        .unwrap();
+    let domain_size = commitment.domain_size;
+    let blob_evaluations = data_list
+        .iter()
+        .map(|data| build_evaluations_from_data::<BaseElement>(data, domain_size, 2).unwrap())
+        .collect::<Vec<_>>();
+
+    let positions = (0..domain_size).collect::<Vec<_>>();
+    let rows = prover.evaluations_at(&positions).unwrap();
+    for (position, row) in rows.iter().enumerate() {
+        let expected = blob_evaluations
+            .iter()
+            .map(|evaluations| evaluations[position])
+            .collect::<Vec<_>>();
+        assert_eq!(*row, expected);
+        assert_eq!(prover.evaluation_at(position).unwrap(), expected);
+    }
+    assert_eq!(rows.concat(), prover.get_evaluations(&positions).unwrap());
+
+    assert_eq!(
+        prover.evaluation_at(domain_size).unwrap_err(),
+        FridaError::PositionOutOfDomain(domain_size, domain_size)
+    );
+}
@@ -0,0 +1,97 @@
+use winter_rand_utils::rand_vector;
+
+use crate::{error::FridaError, prover::bucketed::verify_bucket, utils::test_utils::*};
+
+fn test_data_list() -> Vec<Vec<u8>> {
+    [60, 1000, 64, 70, 900]
+        .iter()
+        .map(|&len| rand_vector::<u8>(len))
+        .collect()
+}
+
+#[test]
+fn bucketed_commitment_groups_blobs_by_size() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = test_data_list();
+    let (commitment, provers) = builder.commitment_bucketed(&data_list, 4).unwrap();
+
+    assert_eq!(commitment.buckets.len(), 2);
+    assert_eq!(commitment.buckets[0].blobs, vec![0, 2, 3]);
+    assert_eq!(commitment.buckets[1].blobs, vec![1, 4]);
+    assert!(
+        commitment.buckets[0].commitment.domain_size < commitment.buckets[1].commitment.domain_size
+    );
+
+    // every bucket matches a batched commitment to its blobs alone, and opens as such
+    for (bucket, prover) in commitment.buckets.iter().zip(&provers) {
+        let bucket_data = bucket
+            .blobs
+            .iter()
+            .map(|&blob| data_list[blob].clone())
+            .collect::<Vec<_>>();
+        let (batch_commitment, _, positions) = builder.commitment_batch(&bucket_data, 4).unwrap();
+        assert_eq!(bucket.commitment, batch_commitment);
+        assert_eq!(prover.commitment(), batch_commitment);
+
+        let verifier =
+            TestFridaDasVerifier::from_commitment(&bucket.commitment, options.clone()).unwrap();
+        let proof = prover.open(&positions).unwrap();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+        verifier.verify(&proof, &evaluations, &positions).unwrap();
+    }
+}
+
+#[test]
+fn bucketed_commitment_reports_less_padding_than_a_batch() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data_list = test_data_list();
+    let (commitment, _) = builder.commitment_bucketed(&data_list, 4).unwrap();
+    let (batch_commitment, _, _) = builder.commitment_batch(&data_list, 4).unwrap();
+
+    let padding = commitment.padding;
+    assert_eq!(
+        padding.batched_evaluations,
+        batch_commitment.poly_count * batch_commitment.domain_size
+    );
+    assert!(padding.required_evaluations <= padding.committed_evaluations);
+    assert!(padding.committed_evaluations < padding.batched_evaluations);
+    assert!(padding.padding_overhead() < padding.batched_overhead());
+}
+
+#[test]
+fn bucket_commitments_are_bound_to_the_super_root() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let mut data_list = test_data_list();
+    // a blob alone in its size class is committed on its own
+    data_list.push(rand_vector::<u8>(5000));
+    let (commitment, provers) = builder.commitment_bucketed(&data_list, 4).unwrap();
+    assert_eq!(commitment.buckets.len(), 3);
+    assert_eq!(provers[2].commitment().poly_count, 1);
+
+    for (index, bucket) in commitment.buckets.iter().enumerate() {
+        let path = commitment.bucket_path(index);
+        assert!(verify_bucket::<Blake3>(
+            &commitment.super_root,
+            index,
+            &bucket.commitment,
+            &path
+        ));
+        let other = (index + 1) % commitment.buckets.len();
+        assert!(!verify_bucket::<Blake3>(
+            &commitment.super_root,
+            other,
+            &bucket.commitment,
+            &path
+        ));
+    }
+}
+
+#[test]
+fn bucketed_commitment_rejects_empty_batches() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    assert_eq!(
+        builder.commitment_bucketed(&[], 4).unwrap_err(),
+        FridaError::BadDataLength()
+    );
+}
@@ -0,0 +1,79 @@
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    prover::{cache::CommitmentCacheMetrics, LayerStorage},
+    utils::test_utils::*,
+};
+
+#[test]
+fn cached_commitments_match_computed_ones() {
+    let options = test_options();
+    let uncached = TestFridaProverBuilder::new(options.clone());
+    let builder = TestFridaProverBuilder::new(options).with_commitment_cache(4);
+    let data = rand_vector::<u8>(500);
+
+    let (commitment, prover, positions) = uncached.commitment(&data, 4).unwrap();
+    for _ in 0..2 {
+        let (cached, cached_prover, cached_positions) = builder.commitment(&data, 4).unwrap();
+        assert_eq!(cached, commitment);
+        assert_eq!(cached_positions, positions);
+        assert_eq!(
+            cached_prover.open(&positions).unwrap(),
+            prover.open(&positions).unwrap()
+        );
+    }
+    assert_eq!(
+        builder.commitment_cache().unwrap().metrics(),
+        CommitmentCacheMetrics {
+            entries: 1,
+            hits: 1,
+            misses: 1
+        }
+    );
+}
+
+#[test]
+fn cache_keys_cover_data_and_configuration() {
+    let builder = TestFridaProverBuilder::new(test_options())
+        .with_layer_storage(LayerStorage::Lazy)
+        .with_commitment_cache(8);
+    let data = rand_vector::<u8>(500);
+
+    let (commitment, ..) = builder.commitment(&data, 4).unwrap();
+    let (other, ..) = builder.commitment(&rand_vector::<u8>(500), 4).unwrap();
+    assert_ne!(other, commitment);
+    builder.commitment(&data, 8).unwrap();
+    let (batch, ..) = builder.commitment_batch(&[data.clone()], 4).unwrap();
+    assert_eq!(batch.poly_count, 1);
+    let metrics = builder.commitment_cache().unwrap().metrics();
+    assert_eq!((metrics.entries, metrics.hits), (4, 0));
+
+    // another configuration does not reuse commitments of the first one
+    let builder = builder.with_ood_check(true);
+    let (ood_commitment, ..) = builder.commitment(&data, 4).unwrap();
+    assert_ne!(ood_commitment, commitment);
+    assert_eq!(builder.commitment_cache().unwrap().metrics().hits, 0);
+}
+
+#[test]
+fn least_recently_used_commitments_are_evicted() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_commitment_cache(2);
+    let blobs = (0..3).map(|_| rand_vector::<u8>(200)).collect::<Vec<_>>();
+
+    builder.commitment(&blobs[0], 4).unwrap();
+    builder.commitment(&blobs[1], 4).unwrap();
+    builder.commitment(&blobs[0], 4).unwrap();
+    builder.commitment(&blobs[2], 4).unwrap();
+    let cache = builder.commitment_cache().unwrap();
+    assert_eq!(cache.metrics().entries, 2);
+    assert_eq!(cache.metrics().hits, 1);
+
+    // the second blob was evicted, the first one was used more recently
+    builder.commitment(&blobs[0], 4).unwrap();
+    assert_eq!(cache.metrics().hits, 2);
+    builder.commitment(&blobs[1], 4).unwrap();
+    assert_eq!(cache.metrics().hits, 2);
+
+    cache.clear();
+    assert_eq!(cache.metrics().entries, 0);
+}
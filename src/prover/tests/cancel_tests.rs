@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex};
+
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    prover::{cancel::CancellationToken, progress::Phase},
+    utils::test_utils::*,
+};
+
+#[test]
+fn test_cancelled_commitments_fail() {
+    let token = CancellationToken::new();
+    let builder = TestFridaProverBuilder::new(test_options())
+        .with_cancellation(token.clone())
+        .with_commitment_cache(2);
+    let data = rand_vector::<u8>(500);
+
+    // commitments complete as usual until the token is cancelled
+    let (commitment, _) = builder.commit_and_prove(&data, 4).unwrap();
+    let (plain, _) = TestFridaProverBuilder::new(test_options())
+        .commit_and_prove(&data, 4)
+        .unwrap();
+    assert_eq!(commitment, plain);
+
+    token.cancel();
+    assert!(token.clone().is_cancelled());
+    let other = rand_vector::<u8>(500);
+    assert_eq!(
+        builder.commit_and_prove(&other, 4).err(),
+        Some(FridaError::Cancelled)
+    );
+    assert_eq!(
+        builder.commitment(&other, 4).err(),
+        Some(FridaError::Cancelled)
+    );
+    assert_eq!(
+        builder
+            .commitment_batch(&[data.clone(), other.clone()], 4)
+            .err(),
+        Some(FridaError::Cancelled)
+    );
+    assert_eq!(builder.encode(&other, 4).err(), Some(FridaError::Cancelled));
+    assert_eq!(builder.commitment_cache().unwrap().metrics().entries, 0);
+}
+
+#[test]
+fn test_cancellation_between_layers() {
+    let token = CancellationToken::new();
+    let phases = Arc::new(Mutex::new(Vec::new()));
+    let (sink, cancel) = (phases.clone(), token.clone());
+    let builder = TestFridaProverBuilder::new(test_options())
+        .with_cancellation(token)
+        .with_progress_sink(move |phase: Phase| {
+            sink.lock().unwrap().push(phase);
+            if let Phase::LayerHashing { layer: 1, .. } = phase {
+                cancel.cancel();
+            }
+        });
+
+    let data = rand_vector::<u8>(500);
+    assert_eq!(
+        builder.commitment(&data, 4).err(),
+        Some(FridaError::Cancelled)
+    );
+    // the layer in progress completes, and no later phase starts
+    let phases = phases.lock().unwrap();
+    assert!(matches!(
+        phases.last(),
+        Some(Phase::LayerHashing { layer: 1, .. })
+    ));
+}
+
+#[test]
+fn test_cancelled_openings_fail() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(500);
+    let (_, prover, positions) = builder.commitment(&data, 4).unwrap();
+
+    let token = CancellationToken::new();
+    assert_eq!(
+        prover.open_with_cancellation(&positions, &token).unwrap(),
+        prover.open(&positions).unwrap()
+    );
+    token.cancel();
+    assert_eq!(
+        prover.open_with_cancellation(&positions, &token).err(),
+        Some(FridaError::Cancelled)
+    );
+}
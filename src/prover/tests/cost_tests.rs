@@ -0,0 +1,57 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    prover::cost::{estimate_commit_cost, CostModel},
+    utils::test_utils::*,
+};
+
+#[test]
+fn commit_cost_matches_committed_shape() {
+    for (options, data_size) in [
+        (test_options(), 200),
+        (FriOptions::new(2, 4, 3), 4096),
+        (FriOptions::new(8, 16, 7), 10_000),
+    ] {
+        let data = rand_vector::<u8>(data_size);
+        let (commitment, _prover) = TestFridaProverBuilder::new(options.clone())
+            .commit_and_prove(&data, 4)
+            .unwrap();
+
+        let cost = estimate_commit_cost::<BaseElement>(data_size, &options).unwrap();
+        assert_eq!(cost.fft_size, commitment.domain_size);
+        assert_eq!(cost.num_layers, commitment.roots.len() - 1);
+        assert!(cost.merkle_leaves >= commitment.domain_size / options.folding_factor());
+        assert!(cost.estimated_time_ms > 0.0);
+    }
+}
+
+#[test]
+fn commit_cost_scales_with_the_model() {
+    let options = FriOptions::new(2, 4, 3);
+    let model = CostModel::default();
+    let doubled = CostModel {
+        fft_ns_per_butterfly: 2.0 * model.fft_ns_per_butterfly,
+        hash_ns_per_element: 2.0 * model.hash_ns_per_element,
+        merge_ns_per_node: 2.0 * model.merge_ns_per_node,
+    };
+
+    let cost = model
+        .estimate_commit_cost::<BaseElement>(64 * 1024, &options)
+        .unwrap();
+    let doubled_cost = doubled
+        .estimate_commit_cost::<BaseElement>(64 * 1024, &options)
+        .unwrap();
+    assert!((doubled_cost.estimated_time_ms - 2.0 * cost.estimated_time_ms).abs() < 1e-9);
+}
+
+#[test]
+fn commit_cost_rejects_oversized_data() {
+    let options = FriOptions::new(2, 4, 3);
+    assert!(matches!(
+        estimate_commit_cost::<BaseElement>(1 << 30, &options),
+        Err(FridaError::DomainSizeTooBig(_))
+    ));
+}
@@ -0,0 +1,213 @@
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{
+        data_root::{DataInclusionProof, DataRoots},
+        FridaProver, ProverCommitment,
+    },
+    utils::test_utils::*,
+    winterfell::f128::BaseElement,
+};
+
+const CHUNK_SIZE: usize = 32;
+
+#[test]
+fn test_data_inclusion_single_blob() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_data_roots(CHUNK_SIZE);
+    let data = rand_vector::<u8>(200);
+    let (commitment, prover, _) = builder.commitment(&data, 4).unwrap();
+    assert_eq!(commitment, prover.commitment());
+
+    // the FRI roots are the same as without data roots
+    let (plain, _, _) = TestFridaProverBuilder::new(test_options())
+        .commitment(&data, 4)
+        .unwrap();
+    assert_eq!(plain.roots, commitment.roots);
+    assert_eq!(plain.data_roots, None);
+
+    let roots = commitment.data_roots.as_ref().unwrap();
+    assert_eq!(roots.chunk_size, CHUNK_SIZE);
+    assert_eq!(roots.roots.len(), 1);
+
+    // every chunk, the last shorter one included, can be opened on its own or along others
+    for chunks in [0..1, 3..5, 6..7, 0..7] {
+        let proof =
+            DataInclusionProof::new::<Blake3>(&data, 0, CHUNK_SIZE, chunks.clone()).unwrap();
+        commitment.verify_data_inclusion(&proof).unwrap();
+        let offset = proof.offset(CHUNK_SIZE);
+        assert_eq!(offset, chunks.start * CHUNK_SIZE);
+        assert_eq!(
+            proof.bytes(),
+            data[offset..usize::min(chunks.end * CHUNK_SIZE, data.len())]
+        );
+    }
+    assert_eq!(
+        DataInclusionProof::new::<Blake3>(&data, 0, CHUNK_SIZE, 6..8).err(),
+        Some(FridaError::DataChunksOutOfBounds(8, 7))
+    );
+}
+
+#[test]
+fn test_data_inclusion_byte_range() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_data_roots(CHUNK_SIZE);
+    let data = rand_vector::<u8>(200);
+    let (commitment, _, _) = builder.commitment(&data, 4).unwrap();
+
+    let proof = DataInclusionProof::for_byte_range::<Blake3>(&data, 0, CHUNK_SIZE, 40..70).unwrap();
+    commitment.verify_data_inclusion(&proof).unwrap();
+    let offset = proof.offset(CHUNK_SIZE);
+    assert_eq!(offset, 32);
+    assert_eq!(&proof.bytes()[40 - offset..70 - offset], &data[40..70]);
+
+    assert_eq!(
+        DataInclusionProof::for_byte_range::<Blake3>(&data, 0, CHUNK_SIZE, 190..201).err(),
+        Some(FridaError::ByteRangeOutOfBounds(190, 201))
+    );
+}
+
+#[test]
+fn test_data_inclusion_batch() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_data_roots(CHUNK_SIZE);
+    let data_list = vec![
+        rand_vector::<u8>(200),
+        rand_vector::<u8>(64),
+        rand_vector::<u8>(5),
+    ];
+    let (commitment, prover, _) = builder.commitment_batch(&data_list, 4).unwrap();
+    assert_eq!(commitment, prover.commitment());
+    assert_eq!(commitment.data_roots.as_ref().unwrap().roots.len(), 3);
+
+    for (blob, data) in data_list.iter().enumerate() {
+        let proof = DataInclusionProof::new::<Blake3>(data, blob, CHUNK_SIZE, 0..1).unwrap();
+        commitment.verify_data_inclusion(&proof).unwrap();
+    }
+
+    // chunks of a blob do not verify against the root of another one
+    let mut proof = DataInclusionProof::new::<Blake3>(&data_list[0], 0, CHUNK_SIZE, 0..2).unwrap();
+    proof.blob = 1;
+    assert!(commitment.verify_data_inclusion(&proof).is_err());
+    proof.blob = 3;
+    assert_eq!(
+        commitment.verify_data_inclusion(&proof),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
+
+#[test]
+fn test_data_inclusion_rejects_tampering() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_data_roots(CHUNK_SIZE);
+    let data = rand_vector::<u8>(200);
+    let (commitment, _, _) = builder.commitment(&data, 4).unwrap();
+    let proof = DataInclusionProof::new::<Blake3>(&data, 0, CHUNK_SIZE, 2..4).unwrap();
+
+    let mut tampered = proof.clone();
+    tampered.chunks[1][0] ^= 1;
+    assert_eq!(
+        commitment.verify_data_inclusion(&tampered),
+        Err(FridaError::InvalidDataInclusion)
+    );
+
+    // the length of the blob is bound by every leaf
+    let mut tampered = proof.clone();
+    tampered.data_len = 160;
+    assert_eq!(
+        commitment.verify_data_inclusion(&tampered),
+        Err(FridaError::InvalidDataInclusion)
+    );
+    let mut tampered = proof.clone();
+    tampered.data_len = 64;
+    assert_eq!(
+        commitment.verify_data_inclusion(&tampered),
+        Err(FridaError::DataChunksOutOfBounds(4, 2))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.first_chunk = 3;
+    assert!(commitment.verify_data_inclusion(&tampered).is_err());
+    let mut tampered = proof;
+    tampered.paths.push(0);
+    assert_eq!(
+        commitment.verify_data_inclusion(&tampered),
+        Err(FridaError::InvalidDataInclusion)
+    );
+}
+
+#[test]
+fn test_data_inclusion_requires_data_roots() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(200);
+    let (commitment, _, _) = builder.commitment(&data, 4).unwrap();
+    let proof = DataInclusionProof::new::<Blake3>(&data, 0, CHUNK_SIZE, 0..1).unwrap();
+    assert_eq!(
+        commitment.verify_data_inclusion(&proof),
+        Err(FridaError::DataRootsNotCommitted)
+    );
+}
+
+#[test]
+fn test_data_roots_serialization() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_data_roots(CHUNK_SIZE);
+    let data = rand_vector::<u8>(200);
+    let (commitment, prover, _) = builder.commitment(&data, 4).unwrap();
+
+    let decoded = ProverCommitment::<Blake3>::read_from_bytes(&commitment.to_bytes()).unwrap();
+    assert_eq!(decoded, commitment);
+    let decoded = FridaProver::<BaseElement, Blake3>::read_from_bytes(&prover.to_bytes()).unwrap();
+    assert_eq!(decoded.commitment(), commitment);
+
+    let proof = DataInclusionProof::new::<Blake3>(&data, 0, CHUNK_SIZE, 1..3).unwrap();
+    let decoded = DataInclusionProof::read_from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(decoded, proof);
+    let roots = commitment.data_roots.unwrap();
+    let decoded_roots = DataRoots::<Blake3>::read_from_bytes(&roots.to_bytes()).unwrap();
+    assert_eq!(decoded_roots, roots);
+    decoded_roots.verify(&decoded).unwrap();
+
+    // empty chunks cannot be decoded
+    let empty = DataRoots::<Blake3> {
+        chunk_size: 0,
+        roots: roots.roots,
+    };
+    assert!(DataRoots::<Blake3>::read_from_bytes(&empty.to_bytes()).is_err());
+}
+
+#[test]
+fn test_data_roots_are_bound() {
+    let options = test_options();
+    let data = rand_vector::<u8>(200);
+    let (commitment, prover) = TestFridaProverBuilder::new(options.clone())
+        .with_data_roots(CHUNK_SIZE)
+        .commit_and_prove(&data, 4)
+        .unwrap();
+    let (plain, _, _) = TestFridaProverBuilder::new(options.clone())
+        .commitment(&data, 4)
+        .unwrap();
+    let with_roots = prover.commitment();
+    assert_ne!(plain.binding(), with_roots.binding());
+    assert_ne!(plain.root_digest(), with_roots.root_digest());
+
+    // verifiers hold the roots the commitment carries, under the same root digest
+    assert_eq!(commitment.data_roots, with_roots.data_roots);
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    assert_eq!(verifier.data_roots(), with_roots.data_roots.as_ref());
+    verifier
+        .check_root_digest(&with_roots.root_digest())
+        .unwrap();
+    let proof = DataInclusionProof::new::<Blake3>(&data, 0, CHUNK_SIZE, 2..4).unwrap();
+    verifier.data_roots().unwrap().verify(&proof).unwrap();
+
+    // swapping the roots changes the binding
+    let mut swapped = with_roots.clone();
+    swapped.data_roots.as_mut().unwrap().roots[0] = plain.roots[0];
+    assert_ne!(swapped.binding(), with_roots.binding());
+    assert_ne!(swapped.root_digest(), with_roots.root_digest());
+
+    // the presence tag of the roots is either 0 or 1
+    let mut bytes = with_roots.to_bytes();
+    let tag = bytes.len() - with_roots.data_roots.unwrap().to_bytes().len() - 2;
+    assert_eq!(bytes[tag], 1);
+    bytes[tag] = 2;
+    assert!(ProverCommitment::<Blake3>::read_from_bytes(&bytes).is_err());
+}
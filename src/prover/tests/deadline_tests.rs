@@ -0,0 +1,96 @@
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    prover::{
+        deadline::{DispersalHint, DispersalStatus},
+        ProverCommitment,
+    },
+    utils::test_utils::*,
+    verifier::attestation::{Attestation, AttestationAggregator},
+};
+
+const HINT: DispersalHint = DispersalHint {
+    deadline: 100,
+    expected_shares: 3,
+};
+
+#[test]
+fn test_dispersal_status() {
+    assert_eq!(HINT.status(50, 1), DispersalStatus::Pending);
+    assert_eq!(HINT.status(100, 2), DispersalStatus::Pending);
+    assert_eq!(HINT.status(101, 2), DispersalStatus::Overdue);
+    assert_eq!(HINT.status(101, 3), DispersalStatus::Dispersed);
+    assert_eq!(HINT.status(0, 4), DispersalStatus::Dispersed);
+}
+
+#[test]
+fn test_commitments_carry_dispersal_hint() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(200);
+
+    let (plain, _, _) = builder.commitment(&data, 4).unwrap();
+    assert_eq!(plain.dispersal_hint, None);
+    assert_eq!(plain.dispersal_status(1000, 0), None);
+    assert!(!plain.is_overdue(1000, 0));
+
+    let builder = builder.with_dispersal_hint(HINT);
+    let (commitment, prover, _) = builder.commitment(&data, 4).unwrap();
+    assert_eq!(commitment.dispersal_hint, Some(HINT));
+    assert_eq!(commitment, prover.commitment());
+    assert!(commitment.is_overdue(101, 2));
+    assert!(!commitment.is_overdue(101, 3));
+
+    // the hint travels with the commitment, and is bound by it
+    let decoded = ProverCommitment::<Blake3>::read_from_bytes(&commitment.to_bytes()).unwrap();
+    assert_eq!(decoded, commitment);
+    assert_eq!(plain.roots, commitment.roots);
+    assert_ne!(plain.binding(), commitment.binding());
+
+    // receipts of hinted commitments still verify
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, test_options()).unwrap();
+    verifier
+        .verify_receipt(&prover.sample_receipt(5).unwrap())
+        .unwrap();
+}
+
+#[test]
+fn test_aggregator_flags_overdue_dispersal() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_dispersal_hint(HINT);
+    let (commitment, _, _) = builder.commitment(&rand_vector::<u8>(200), 4).unwrap();
+
+    let assignments = vec![vec![0, 1], vec![1, 2], vec![5]];
+    let keys = (0..3).map(TestKey).collect::<Vec<_>>();
+    let mut aggregator =
+        AttestationAggregator::new(&commitment, assignments.clone(), keys, 2).unwrap();
+    assert_eq!(
+        aggregator.dispersal_status(10),
+        Some(DispersalStatus::Pending)
+    );
+
+    aggregator
+        .add(Attestation::new(
+            &commitment,
+            0,
+            &assignments[0],
+            &TestKey(0),
+        ))
+        .unwrap();
+    assert_eq!(
+        aggregator.dispersal_status(101),
+        Some(DispersalStatus::Overdue)
+    );
+
+    aggregator
+        .add(Attestation::new(
+            &commitment,
+            1,
+            &assignments[1],
+            &TestKey(1),
+        ))
+        .unwrap();
+    assert_eq!(
+        aggregator.dispersal_status(101),
+        Some(DispersalStatus::Dispersed)
+    );
+}
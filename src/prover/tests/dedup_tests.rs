@@ -0,0 +1,48 @@
+use winter_fri::FriOptions;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    core::{
+        data::recover_data_from_evaluations,
+        dedup::{decode_deduped, ChunkingOptions},
+    },
+    utils::test_utils::*,
+};
+
+#[test]
+fn deduped_batch_verifies_and_decodes() {
+    let options = FriOptions::new(2, 4, 3);
+    let shared = rand_vector::<u8>(32 * 1024);
+    let data_list = (0..4)
+        .map(|i| {
+            let mut blob = rand_vector::<u8>(100 * (i + 1));
+            blob.extend_from_slice(&shared);
+            blob
+        })
+        .collect::<Vec<_>>();
+
+    let (commitment, prover, manifest) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove_deduped(&data_list, &ChunkingOptions::default(), 16)
+        .unwrap();
+    assert_eq!(commitment.poly_count, 1);
+    assert!(manifest.store_size().unwrap() < 2 * shared.len());
+
+    let domain_size = commitment.domain_size;
+    let positions = (0..domain_size).collect::<Vec<_>>();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+
+    let (verifier, _coin) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
+    let proof = prover.open(&positions[..8]).unwrap();
+    verifier
+        .verify(&proof, &evaluations[..8], &positions[..8])
+        .unwrap();
+
+    let blob = recover_data_from_evaluations(
+        &evaluations,
+        &positions,
+        domain_size,
+        options.blowup_factor(),
+    )
+    .unwrap();
+    assert_eq!(decode_deduped(&blob).unwrap(), data_list);
+}
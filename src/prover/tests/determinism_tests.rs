@@ -0,0 +1,94 @@
+use winter_fri::FriOptions;
+use winter_utils::Serializable;
+
+use crate::{
+    prover::LayerStorage,
+    utils::{parallel::ParallelOptions, test_utils::*},
+};
+
+/// Fixed data, so that commitments can be compared across runs and builds.
+fn fixed_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect()
+}
+
+/// Serializes the commitments of single blobs and batches under every configuration which
+/// changes how layers are built.
+fn commitment_bytes() -> Vec<Vec<u8>> {
+    let data = fixed_data(3000);
+    let batch = vec![
+        data[..700].to_vec(),
+        data[700..2000].to_vec(),
+        data[2000..].to_vec(),
+    ];
+    let builders = [
+        TestFridaProverBuilder::new(test_options()),
+        TestFridaProverBuilder::new(FriOptions::new(2, 4, 3))
+            .with_layer_storage(LayerStorage::Lazy),
+        TestFridaProverBuilder::new(FriOptions::new(4, 2, 1)).with_folding_schedule(vec![8, 2]),
+        TestFridaProverBuilder::new(FriOptions::new(2, 8, 1)).with_ood_check(true),
+    ];
+
+    builders
+        .iter()
+        .flat_map(|builder| {
+            let (single, _) = builder.commit_and_prove(&data, 16).unwrap();
+            let (batched, _) = builder.commit_and_prove_batch(&batch, 16).unwrap();
+            [single.to_bytes(), batched.to_bytes()]
+        })
+        .collect()
+}
+
+#[test]
+fn commitments_are_reproducible() {
+    assert_eq!(commitment_bytes(), commitment_bytes());
+}
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn commitments_do_not_depend_on_thread_count() {
+    use winter_utils::rayon::ThreadPoolBuilder;
+
+    let with_threads = |num_threads| {
+        ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap()
+            .install(commitment_bytes)
+    };
+
+    let single_threaded = with_threads(1);
+    for num_threads in [2, 3, 8] {
+        assert_eq!(with_threads(num_threads), single_threaded);
+    }
+}
+
+#[test]
+fn commitments_do_not_depend_on_parallelism_options() {
+    let data = fixed_data(3000);
+    let batch = (0..17)
+        .map(|i| data[i * 100..i * 100 + 300].to_vec())
+        .collect::<Vec<_>>();
+    let options = FriOptions::new(2, 4, 3);
+    let commit = |parallelism| {
+        let builder = TestFridaProverBuilder::new(options.clone()).with_parallelism(parallelism);
+        let (single, _) = builder.commit_and_prove(&data, 16).unwrap();
+        let (batched, prover) = builder.commit_and_prove_batch(&batch, 16).unwrap();
+        [
+            single.to_bytes(),
+            batched.to_bytes(),
+            prover.open(&[0, 5, 20]).unwrap().to_bytes(),
+        ]
+    };
+
+    let default = commit(ParallelOptions::default());
+    for rows_per_task in [1, 3, 1024, usize::MAX] {
+        assert_eq!(commit(ParallelOptions::fixed(rows_per_task)), default);
+    }
+    let tuned = ParallelOptions {
+        rows_per_task: None,
+        min_task_work: 1,
+    };
+    assert_eq!(commit(tuned), default);
+}
@@ -0,0 +1,77 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    prover::{
+        diff::{bucket_positions, diff_commitments},
+        FridaProver, PruneLevel,
+    },
+    utils::test_utils::*,
+};
+
+#[test]
+fn identical_blobs_have_no_diff() {
+    let data = rand_vector::<u8>(200);
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover_a) = builder.commit_and_prove(&data, 8).unwrap();
+    let (_, prover_b) = builder.commit_and_prove(&data, 8).unwrap();
+
+    assert!(diff_commitments(&prover_a, &prover_b).unwrap().is_empty());
+}
+
+#[test]
+fn diff_covers_changed_positions() {
+    let options = FriOptions::new(2, 4, 3);
+    let data_a = rand_vector::<u8>(200);
+    let mut data_b = data_a.clone();
+    data_b[17] ^= 1;
+
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let (com_a, prover_a) = builder.commit_and_prove(&data_a, 8).unwrap();
+    let (_, prover_b) = builder.commit_and_prove(&data_b, 8).unwrap();
+
+    let buckets = diff_commitments(&prover_a, &prover_b).unwrap();
+    assert!(!buckets.is_empty());
+
+    let evaluations = |prover: &FridaProver<BaseElement, Blake3>, positions: &[usize]| {
+        prover.get_evaluations(positions).unwrap()
+    };
+    let bucket_count = com_a.domain_size / options.folding_factor();
+    for bucket in 0..bucket_count {
+        let positions = bucket_positions(bucket, com_a.domain_size, options.folding_factor());
+        let changed = evaluations(&prover_a, &positions) != evaluations(&prover_b, &positions);
+        assert_eq!(changed, buckets.contains(&bucket));
+    }
+}
+
+#[test]
+fn diff_rejects_incompatible_commitments() {
+    let data = rand_vector::<u8>(200);
+    let (_, prover_a) = TestFridaProverBuilder::new(test_options())
+        .commit_and_prove(&data, 8)
+        .unwrap();
+    let (_, prover_b) = TestFridaProverBuilder::new(FriOptions::new(2, 4, 3))
+        .commit_and_prove(&data, 8)
+        .unwrap();
+
+    assert_eq!(
+        diff_commitments(&prover_a, &prover_b),
+        Err(FridaError::IncompatibleCommitments)
+    );
+}
+
+#[test]
+fn diff_rejects_pruned_provers() {
+    let data = rand_vector::<u8>(200);
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover_a) = builder.commit_and_prove(&data, 8).unwrap();
+    let (_, mut prover_b) = builder.commit_and_prove(&data, 8).unwrap();
+    prover_b.prune(PruneLevel::Commitment).unwrap();
+
+    assert_eq!(
+        diff_commitments(&prover_a, &prover_b),
+        Err(FridaError::FirstLayerUnavailable)
+    );
+}
@@ -0,0 +1,231 @@
+use winter_crypto::Hasher;
+use winter_fri::FriOptions;
+use winter_math::{fields::f128::BaseElement, FieldElement};
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{
+        distributed::{
+            PartitionCoordinator, PartitionFold, PartitionOpening, PartitionTask, PartitionWorker,
+        },
+        Commitment, FridaProver,
+    },
+    utils::test_utils::*,
+};
+
+type TestPartitionWorker = PartitionWorker<BaseElement, Blake3>;
+type TestPartitionOpening = PartitionOpening<BaseElement, <Blake3 as Hasher>::Digest>;
+
+/// Has the `workers` build and fold their layers with `coordinator`, returning the rows of every
+/// layer each of them must open.
+fn fold_workers(
+    coordinator: &mut PartitionCoordinator<'_, BaseElement, Blake3, Blake3>,
+    workers: &mut [TestPartitionWorker],
+) -> Result<Vec<Vec<Vec<usize>>>, FridaError> {
+    let mut roots = workers.iter().map(|w| w.root()).collect::<Vec<_>>();
+    loop {
+        let alpha = coordinator.commit_partitions(&roots)?;
+        roots.clear();
+        let mut folded = Vec::new();
+        for worker in workers.iter_mut() {
+            let fold = worker.fold(alpha)?;
+            assert_eq!(
+                PartitionFold::read_from_bytes(&fold.to_bytes()).unwrap(),
+                fold
+            );
+            match fold {
+                PartitionFold::Root(root) => roots.push(root),
+                PartitionFold::Evaluations(evaluations) => folded.push(evaluations),
+            }
+        }
+        if !folded.is_empty() {
+            return coordinator.fold_partitions(&folded);
+        }
+    }
+}
+
+/// Commits to `data` with one worker per partition, opening the requested rows as given by
+/// `tamper`.
+fn distributed_commitment(
+    builder: &TestFridaProverBuilder,
+    data: &[u8],
+    tamper: impl Fn(&mut Vec<TestPartitionOpening>),
+) -> Result<(Commitment<Blake3>, FridaProver<BaseElement, Blake3>), FridaError> {
+    let (mut coordinator, tasks) = builder.distribute(data, 4)?;
+    let mut workers = tasks
+        .iter()
+        .map(TestPartitionWorker::new)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rows = fold_workers(&mut coordinator, &mut workers)?;
+    let mut openings = workers
+        .iter()
+        .zip(&rows)
+        .map(|(w, rows)| w.open(rows))
+        .collect::<Result<Vec<_>, _>>()?;
+    tamper(&mut openings);
+    coordinator.finish(&openings)
+}
+
+#[test]
+fn distributed_commitments_match_local_ones() {
+    let data = rand_vector::<u8>(500);
+    for num_partitions in [1, 2, 4] {
+        for options in [test_options(), FriOptions::new(8, 4, 1)] {
+            let builder = TestFridaProverBuilder::new(options.clone())
+                .with_num_partitions(num_partitions)
+                .with_data_checksums(true);
+            let (local, local_prover) = builder.commit_and_prove(&data, 4).unwrap();
+            let (commitment, prover) = distributed_commitment(&builder, &data, |_| {}).unwrap();
+            assert_eq!(commitment, local);
+            assert_eq!(prover.commitment(), local_prover.commitment());
+
+            let positions =
+                TestFridaDasVerifier::verify_commitment_positions(&commitment, options.clone())
+                    .unwrap();
+            let proof = prover.open(&positions).unwrap();
+            assert_eq!(proof, local_prover.open(&positions).unwrap());
+            let evaluations = local_prover.get_evaluations(&positions).unwrap();
+            let (verifier, _) = TestFridaDasVerifier::new(commitment, options).unwrap();
+            verifier.verify(&proof, &evaluations, &positions).unwrap();
+        }
+    }
+}
+
+#[test]
+fn tasks_and_openings_round_trip() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let (mut coordinator, tasks) = builder.distribute(&rand_vector::<u8>(500), 4).unwrap();
+    let tasks = tasks
+        .iter()
+        .map(|task| PartitionTask::read_from_bytes(&task.to_bytes()).unwrap())
+        .collect::<Vec<_>>();
+    let mut workers = tasks
+        .iter()
+        .map(|task| TestPartitionWorker::new(task).unwrap())
+        .collect::<Vec<_>>();
+
+    let rows = fold_workers(&mut coordinator, &mut workers).unwrap();
+    for (worker, rows) in workers.iter().zip(&rows) {
+        let opening = worker.open(rows).unwrap();
+        assert_eq!(
+            TestPartitionOpening::read_from_bytes(&opening.to_bytes()).unwrap(),
+            opening
+        );
+    }
+}
+
+#[test]
+fn workers_only_exchange_their_partitions() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone()).with_num_partitions(4);
+    let data = rand_vector::<u8>(2000);
+    let (mut coordinator, tasks) = builder.distribute(&data, 4).unwrap();
+
+    // tasks hold the blob reduced to their partition, rather than the whole blob
+    let domain_size = tasks[0].domain_size;
+    assert!(tasks
+        .iter()
+        .all(|task| task.coefficients.len() == domain_size / 4));
+    assert!(domain_size / 4 < domain_size / options.blowup_factor());
+
+    // workers fold their partitions over several layers, and only send their last folded
+    // partitions, smaller than two rows of the next layer
+    let mut workers = tasks
+        .iter()
+        .map(|task| TestPartitionWorker::new(task).unwrap())
+        .collect::<Vec<_>>();
+    assert!(tasks[0].folding_factors.len() > 1);
+    let mut roots = workers.iter().map(|w| w.root()).collect::<Vec<_>>();
+    for _ in 1..tasks[0].folding_factors.len() {
+        let alpha = coordinator.commit_partitions(&roots).unwrap();
+        roots = workers
+            .iter_mut()
+            .map(|w| match w.fold(alpha).unwrap() {
+                PartitionFold::Root(root) => root,
+                PartitionFold::Evaluations(_) => panic!("workers have more layers to build"),
+            })
+            .collect();
+    }
+    let alpha = coordinator.commit_partitions(&roots).unwrap();
+    for worker in workers.iter_mut() {
+        let PartitionFold::Evaluations(folded) = worker.fold(alpha).unwrap() else {
+            panic!("workers have built all their layers");
+        };
+        assert!(folded.len() < 2 * 2);
+    }
+    assert!(matches!(
+        workers[0].fold(alpha),
+        Err(FridaError::UnexpectedMessage)
+    ));
+}
+
+#[test]
+fn tampered_openings_are_rejected() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let data = rand_vector::<u8>(500);
+
+    let result = distributed_commitment(&builder, &data, |openings| {
+        openings[1].values[0][0] += BaseElement::ONE;
+    });
+    assert!(matches!(result, Err(FridaError::InvalidPartitionShare(1))));
+
+    let result = distributed_commitment(&builder, &data, |openings| {
+        openings.pop();
+    });
+    assert!(matches!(
+        result,
+        Err(FridaError::PartitionCountMismatch(2, 1))
+    ));
+}
+
+#[test]
+fn workers_only_open_their_partition() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let (mut coordinator, tasks) = builder.distribute(&rand_vector::<u8>(500), 4).unwrap();
+    let mut workers = tasks
+        .iter()
+        .map(|task| TestPartitionWorker::new(task).unwrap())
+        .collect::<Vec<_>>();
+
+    // rows are only opened once the worker has built all its layers
+    assert!(matches!(
+        workers[0].open(&[vec![0]]),
+        Err(FridaError::UnexpectedMessage)
+    ));
+    let mut rows = fold_workers(&mut coordinator, &mut workers)
+        .unwrap()
+        .remove(0);
+    rows[0] = vec![0, 2];
+    assert!(workers[0].open(&rows).is_ok());
+    rows[0] = vec![1];
+    assert!(matches!(
+        workers[0].open(&rows),
+        Err(FridaError::RowOutsidePartition(1, 0))
+    ));
+}
+
+#[test]
+fn coordinators_check_their_rounds() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let (mut coordinator, _) = builder.distribute(&rand_vector::<u8>(500), 4).unwrap();
+    assert!(matches!(
+        coordinator.fold_partitions(&[]),
+        Err(FridaError::UnexpectedMessage)
+    ));
+    assert!(matches!(
+        coordinator.commit_partitions(&[]),
+        Err(FridaError::PartitionCountMismatch(2, 0))
+    ));
+}
+
+#[test]
+fn distribution_fails_when_the_whole_first_layer_is_needed() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_ood_check(true);
+    assert!(matches!(
+        builder.distribute(&rand_vector::<u8>(500), 4),
+        Err(FridaError::DistributedProvingUnsupported)
+    ));
+}
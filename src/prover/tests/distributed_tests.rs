@@ -1,5 +1,5 @@
 use crate::{
-    core::data::encoded_data_element_count,
+    core::{assignment::compute_position_assignments, coverage, data::encoded_data_element_count},
     prover::{
         batch_data_to_evaluations, builder::FridaProverBuilder, get_evaluations_from_positions,
     },
@@ -8,6 +8,7 @@ use crate::{
 
 use crate::{
     core::data::build_evaluations_from_data,
+    utils::test_utils::test_options,
     verifier::das::FridaDasVerifier,
     winterfell::{f128::BaseElement, Blake3_256, FriOptions},
 };
@@ -15,47 +16,6 @@ use winter_rand_utils::{rand_value, rand_vector};
 
 type Blake3 = Blake3_256<BaseElement>;
 
-// This helper function would live in your application logic, not the library.
-fn compute_position_assignments(
-    n_validators: usize,
-    query_positions: &[usize],
-    h: usize,
-) -> Vec<Vec<usize>> {
-    let s = query_positions.len();
-    let n = n_validators;
-    if n == 0 {
-        return vec![];
-    }
-    if n <= s {
-        let span_length = s.saturating_sub(h) + 1;
-        (1..=n)
-            .map(|i| {
-                let offset = (i - 1) % s;
-                (0..span_length)
-                    .map(|j| query_positions[(offset + j) % s])
-                    .collect()
-            })
-            .collect()
-    } else {
-        let n_prime = (n / s) * s;
-        if n_prime == 0 {
-            return vec![Vec::new(); n];
-        }
-        let replication_factor = n_prime / s;
-        let h_prime = h.saturating_sub(n - n_prime).div_ceil(replication_factor);
-        let base_subsets = compute_position_assignments(s, query_positions, h_prime);
-        (1..=n)
-            .map(|i| {
-                if i <= n_prime {
-                    base_subsets[(i - 1) % s].clone()
-                } else {
-                    Vec::new()
-                }
-            })
-            .collect()
-    }
-}
-
 #[test]
 fn test_distributed_proof_workflow() {
     // 1. SETUP: A block producer sets up the prover.
@@ -76,16 +36,7 @@ fn test_distributed_proof_workflow() {
     let validator_positions = compute_position_assignments(n_validators, &base_positions, h);
 
     // 4. PROVE: The producer generates a specific, small proof for each validator.
-    let validator_proofs: Vec<_> = validator_positions
-        .iter()
-        .map(|positions| {
-            if positions.is_empty() {
-                None
-            } else {
-                Some(prover.open(positions))
-            }
-        })
-        .collect();
+    let validator_proofs = prover.open_for_validators(&validator_positions).unwrap();
 
     // 5. VERIFY: Each validator independently verifies their assigned proof.
     let all_evaluations = build_evaluations_from_data::<BaseElement>(
@@ -148,16 +99,7 @@ fn test_distributed_proof_workflow_batch() {
     let validator_positions = compute_position_assignments(n_validators, &base_positions, h);
 
     // 4. PROVE: The producer generates a specific, small proof for each validator.
-    let validator_proofs: Vec<_> = validator_positions
-        .iter()
-        .map(|positions| {
-            if positions.is_empty() {
-                None
-            } else {
-                Some(prover.open(positions))
-            }
-        })
-        .collect();
+    let validator_proofs = prover.open_for_validators(&validator_positions).unwrap();
 
     // 5. VERIFY: Each validator independently verifies their assigned proof.
     let blowup_factor = options.blowup_factor();
@@ -181,6 +123,7 @@ fn test_distributed_proof_workflow_batch() {
         domain_size,
         blowup_factor,
         options.folding_factor(),
+        &Default::default(),
     )
     .unwrap();
 
@@ -215,3 +158,43 @@ fn test_distributed_proof_workflow_batch() {
         }
     }
 }
+
+#[test]
+fn batch_data_to_evaluations_rejects_poly_count_mismatch() {
+    let data_list = vec![rand_vector::<u8>(100), rand_vector::<u8>(100)];
+    let result =
+        batch_data_to_evaluations::<BaseElement>(&data_list, 3, 64, 2, 2, &Default::default());
+    assert_eq!(result, Err(FridaError::ProofPolyCountMismatch));
+}
+
+#[test]
+fn open_for_validators_matches_open() {
+    let options = test_options();
+    let (_, prover, _) = FridaProverBuilder::<BaseElement, Blake3>::new(options)
+        .commitment(&rand_vector::<u8>(300), 4)
+        .unwrap();
+
+    let assignments = vec![vec![1, 7], vec![], vec![3], vec![0, 2, 40]];
+    let proofs = prover.open_for_validators(&assignments).unwrap();
+    assert_eq!(proofs.len(), assignments.len());
+    for (positions, proof) in assignments.iter().zip(proofs) {
+        assert_eq!(
+            proof,
+            (!positions.is_empty()).then(|| prover.open(positions).unwrap())
+        );
+    }
+}
+
+#[test]
+fn test_position_assignments_cover_with_h_honest_validators() {
+    let query_positions = (0..16).map(|p| p * 5).collect::<Vec<_>>();
+    let s = query_positions.len();
+    for n_validators in [s, 2 * s, 2 * s + 3, 3 * s + 1] {
+        for h in [1, 4, s] {
+            let assignments = compute_position_assignments(n_validators, &query_positions, h);
+            let min_honest =
+                coverage::min_honest_validators(&assignments, &query_positions).unwrap();
+            assert!(min_honest <= h, "n = {n_validators}, h = {h}: {min_honest}");
+        }
+    }
+}
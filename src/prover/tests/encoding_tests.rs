@@ -0,0 +1,84 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    core::data::{build_evaluations_with_encoding, EncodingOptions},
+    error::FridaError,
+    prover::BatchLeafEncoding,
+    utils::test_utils::*,
+};
+
+#[test]
+fn committed_encoding_matches_commitment() {
+    let options = FriOptions::new(2, 4, 3);
+    let builder = TestFridaProverBuilder::new(options.clone()).with_data_checksums(true);
+    let data = rand_vector::<u8>(500);
+
+    let encoding = builder.encode(&data, 8).unwrap();
+    assert_eq!(encoding.poly_count(), 1);
+    assert_eq!(encoding.num_queries(), 8);
+    let expected = build_evaluations_with_encoding::<BaseElement>(
+        &data,
+        encoding.domain_size(),
+        options.blowup_factor(),
+        &EncodingOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(encoding.evaluations(), [expected]);
+
+    let (commitment, prover, positions) = builder.commit(encoding).unwrap();
+    let (expected, _, expected_positions) = builder.commitment(&data, 8).unwrap();
+    assert_eq!(commitment, expected);
+    assert_eq!(positions, expected_positions);
+    assert_eq!(prover.commitment(), expected);
+}
+
+#[test]
+fn committed_batch_encoding_matches_commitment() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_data_checksums(true);
+    let data_list = (0..3)
+        .map(|i| rand_vector::<u8>(200 + 50 * i))
+        .collect::<Vec<_>>();
+
+    let encoding = builder.encode_batch(&data_list, 8).unwrap();
+    assert_eq!(encoding.poly_count(), 3);
+    let (commitment, _, positions) = builder.commit(encoding).unwrap();
+    let (expected, _, expected_positions) = builder.commitment_batch(&data_list, 8).unwrap();
+    assert_eq!(commitment, expected);
+    assert_eq!(positions, expected_positions);
+
+    assert_eq!(
+        builder.encode_batch(&data_list[..1], 8).err(),
+        Some(FridaError::SinglePolyBatch)
+    );
+}
+
+#[test]
+fn encoding_is_reused_across_layer_settings() {
+    let options = FriOptions::new(2, 4, 3);
+    let data_list = (0..4).map(|_| rand_vector::<u8>(400)).collect::<Vec<_>>();
+    let encoding = TestFridaProverBuilder::new(options.clone())
+        .encode_batch(&data_list, 16)
+        .unwrap();
+
+    for builder in [
+        TestFridaProverBuilder::new(options.clone()).with_num_partitions(2),
+        TestFridaProverBuilder::new(options.clone())
+            .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob),
+        TestFridaProverBuilder::new(options.clone()).with_ood_check(true),
+    ] {
+        let (commitment, _, positions) = builder.commit(encoding.clone()).unwrap();
+        let (expected, _, expected_positions) = builder.commitment_batch(&data_list, 16).unwrap();
+        assert_eq!(commitment, expected);
+        assert_eq!(positions, expected_positions);
+    }
+
+    let builder = TestFridaProverBuilder::new(FriOptions::new(4, 4, 3));
+    assert_eq!(
+        builder
+            .commit(encoding)
+            .map(|(commitment, _, _)| commitment),
+        Err(FridaError::EncodingMismatch(2, 4))
+    );
+}
@@ -0,0 +1,115 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::epoch::{EpochChallenge, EpochCommitment},
+    utils::test_utils::*,
+};
+
+type TestEpochCommitment = EpochCommitment<BaseElement, Blake3>;
+
+#[test]
+fn renewed_commitments_open_the_original_one() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(500);
+    let (_, prover) = builder.commit_and_prove(&data, 4).unwrap();
+    let original = prover.commitment();
+
+    let mut windows = Vec::new();
+    for epoch in [1, 2] {
+        let challenge = EpochChallenge::random(epoch, 8);
+        let (renewal, _) = builder.recommit(&data, &challenge).unwrap();
+        let positions = renewal
+            .verify::<Blake3>(&original, &challenge, &test_options())
+            .unwrap();
+        assert_eq!(
+            positions,
+            challenge
+                .positions::<BaseElement, Blake3, Blake3>(&original)
+                .unwrap()
+        );
+        windows.push(positions);
+    }
+    // every epoch samples a fresh window of the domain
+    assert_ne!(windows[0], windows[1]);
+}
+
+#[test]
+fn epochs_rotate_the_sampled_window() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let commitment = prover.commitment();
+
+    // a salt reused in another epoch does not sample the same window
+    let challenge = EpochChallenge::random(1, 8);
+    let next = EpochChallenge {
+        epoch: 2,
+        ..challenge
+    };
+    assert_ne!(
+        challenge
+            .positions::<BaseElement, Blake3, Blake3>(&commitment)
+            .unwrap(),
+        next.positions::<BaseElement, Blake3, Blake3>(&commitment)
+            .unwrap()
+    );
+}
+
+#[test]
+fn renewals_of_other_data_are_discontinuous() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let challenge = EpochChallenge::random(1, 8);
+    let (renewal, _) = builder
+        .recommit(&rand_vector::<u8>(500), &challenge)
+        .unwrap();
+    assert_eq!(
+        renewal.verify::<Blake3>(&prover.commitment(), &challenge, &test_options()),
+        Err(FridaError::EpochDiscontinuity)
+    );
+}
+
+#[test]
+fn renewals_only_answer_their_challenge() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(500);
+    let challenge = EpochChallenge::random(1, 8);
+    let (renewal, prover) = builder.recommit(&data, &challenge).unwrap();
+
+    // the auditor sets the challenge, including the number of queries
+    for other in [
+        EpochChallenge::random(1, 8),
+        EpochChallenge {
+            epoch: 2,
+            ..challenge
+        },
+        EpochChallenge {
+            num_queries: 16,
+            ..challenge
+        },
+    ] {
+        assert!(renewal
+            .verify::<Blake3>(&prover.commitment(), &other, &test_options())
+            .is_err());
+    }
+}
+
+#[test]
+fn epoch_commitments_round_trip() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let challenge = EpochChallenge::random(1, 8);
+    let (renewal, prover) = builder
+        .recommit(&rand_vector::<u8>(500), &challenge)
+        .unwrap();
+    let renewal = TestEpochCommitment::read_from_bytes(&renewal.to_bytes()).unwrap();
+    let challenge = EpochChallenge::read_from_bytes(&challenge.to_bytes()).unwrap();
+    renewal
+        .verify::<Blake3>(&prover.commitment(), &challenge, &test_options())
+        .unwrap();
+}
@@ -0,0 +1,115 @@
+use std::io;
+
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    prover::{
+        framed::{FramedProofReader, FramedProofWriter},
+        proof::FridaProof,
+    },
+    utils::test_utils::*,
+};
+
+fn test_proofs() -> Vec<FridaProof> {
+    let prover_builder = TestFridaProverBuilder::new(test_options());
+    let (commitment, _) = prover_builder
+        .commit_and_prove(&rand_vector::<u8>(4096), 16)
+        .unwrap();
+    let data_list = vec![rand_vector(200), rand_vector(50), rand_vector(70)];
+    let (batch_commitment, _) = prover_builder
+        .commit_and_prove_batch(&data_list, 8)
+        .unwrap();
+    vec![commitment.proof, batch_commitment.proof]
+}
+
+#[test]
+fn framed_proofs_round_trip() {
+    for proof in test_proofs() {
+        let writer = FramedProofWriter::new(&proof);
+        let mut stream = Vec::new();
+        writer.write_frames(&mut stream, 0).unwrap();
+
+        let mut reader = FramedProofReader::new();
+        reader.read_frames(&mut stream.as_slice()).unwrap();
+        assert!(reader.is_complete());
+        assert_eq!(reader.next_frame(), writer.num_frames());
+        assert_eq!(reader.finish().unwrap(), proof);
+    }
+}
+
+#[test]
+fn interrupted_transfers_resume() {
+    for proof in test_proofs() {
+        let writer = FramedProofWriter::new(&proof);
+        let mut stream = Vec::new();
+        writer.write_frames(&mut stream, 0).unwrap();
+
+        // the connection drops in the middle of the third frame
+        let cut = writer.frame(0).len() + writer.frame(1).len() + 5;
+        let mut reader = FramedProofReader::new();
+        let err = reader.read_frames(&mut &stream[..cut]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(!reader.is_complete());
+        assert_eq!(reader.next_frame(), 2);
+
+        let mut resumed = Vec::new();
+        writer
+            .write_frames(&mut resumed, reader.next_frame())
+            .unwrap();
+        reader.read_frames(&mut resumed.as_slice()).unwrap();
+        assert_eq!(reader.finish().unwrap(), proof);
+    }
+}
+
+#[test]
+fn incomplete_transfers_are_not_finished() {
+    let proof = &test_proofs()[0];
+    let writer = FramedProofWriter::new(proof);
+    let mut reader = FramedProofReader::new();
+    reader.push_frame(&writer.frame(0)).unwrap();
+    reader.push_frame(&writer.frame(1)).unwrap();
+    let err = reader.finish().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn corrupted_frames_are_rejected() {
+    let proof = &test_proofs()[1];
+    let writer = FramedProofWriter::new(proof);
+    let mut reader = FramedProofReader::new();
+    reader.push_frame(&writer.frame(0)).unwrap();
+
+    let mut frame = writer.frame(1);
+    let middle = frame.len() / 2;
+    frame[middle] ^= 1;
+    let err = reader.push_frame(&frame).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(reader.next_frame(), 1);
+
+    // the frame is simply sent again
+    for index in 1..writer.num_frames() {
+        reader.push_frame(&writer.frame(index)).unwrap();
+    }
+    assert_eq!(reader.finish().unwrap(), *proof);
+}
+
+#[test]
+fn out_of_order_frames_are_rejected() {
+    let proof = &test_proofs()[0];
+    let writer = FramedProofWriter::new(proof);
+    let mut reader = FramedProofReader::new();
+    let err = reader.push_frame(&writer.frame(1)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    reader.push_frame(&writer.frame(0)).unwrap();
+    let err = reader.push_frame(&writer.frame(2)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // frames of another proof are rejected too
+    let other = &test_proofs()[1];
+    let err = reader
+        .push_frame(&FramedProofWriter::new(other).frame(1))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(reader.next_frame(), 1);
+}
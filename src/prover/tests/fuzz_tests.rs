@@ -0,0 +1,124 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::{rand_value, rand_vector};
+
+use crate::{
+    error::FridaError,
+    prover::{BatchLeafEncoding, FridaProver, LayerStorage, PruneLevel},
+    utils::test_utils::*,
+};
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+const NUM_RUNS: usize = 200;
+
+/// Provers covering every layer representation: full, lazy, batched and pruned, along with their
+/// domain size and the positions they retained if pruned.
+fn test_provers() -> Vec<(TestFridaProver, usize, Option<Vec<usize>>)> {
+    let options = test_options();
+    let data = rand_vector::<u8>(200);
+    let data_list = (0..3).map(|_| rand_vector::<u8>(100)).collect::<Vec<_>>();
+
+    let full = TestFridaProverBuilder::new(options.clone());
+    let per_blob = TestFridaProverBuilder::new(options.clone())
+        .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+    let lazy = TestFridaProverBuilder::new(options).with_layer_storage(LayerStorage::Lazy);
+    let (commitment, single, _) = full.commitment(&data, 4).unwrap();
+    let domain_size = commitment.domain_size;
+    let (_, batched, _) = per_blob.commitment_batch(&data_list, 4).unwrap();
+    let (batched_domain_size, lazy_batched) = {
+        let (commitment, prover, _) = lazy.commitment_batch(&data_list, 4).unwrap();
+        (commitment.domain_size, prover)
+    };
+    let (_, lazy_single, _) = lazy.commitment(&data, 4).unwrap();
+
+    let retained = vec![1, 6, domain_size - 1];
+    let (_, mut pruned, _) = full.commitment(&data, 4).unwrap();
+    pruned
+        .prune(PruneLevel::Positions(retained.clone()))
+        .unwrap();
+
+    vec![
+        (single, domain_size, None),
+        (lazy_single, domain_size, None),
+        (batched, batched_domain_size, None),
+        (lazy_batched, batched_domain_size, None),
+        (pruned, domain_size, Some(retained)),
+    ]
+}
+
+/// Draws a slice of positions which may be empty, repeat positions, or lie past the domain.
+fn rand_positions(domain_size: usize) -> Vec<usize> {
+    let len = rand_value::<u64>() as usize % 6;
+    (0..len)
+        .map(|_| match rand_value::<u64>() % 4 {
+            0 => usize::MAX - rand_value::<u64>() as usize % 4,
+            1 => domain_size + rand_value::<u64>() as usize % domain_size,
+            _ => rand_value::<u64>() as usize % domain_size,
+        })
+        .collect()
+}
+
+#[test]
+fn malformed_positions_never_panic() {
+    for (prover, domain_size, retained) in test_provers() {
+        for _ in 0..NUM_RUNS {
+            let positions = rand_positions(domain_size);
+            let result = prover.open(&positions);
+            let evaluations = prover.get_evaluations(&positions);
+
+            match positions.iter().find(|&&p| p >= domain_size) {
+                _ if positions.is_empty() => assert_eq!(result, Err(FridaError::NoPositions)),
+                Some(&position) => {
+                    let expected = FridaError::PositionOutOfDomain(position, domain_size);
+                    assert_eq!(result, Err(expected.clone()));
+                    assert_eq!(evaluations, Err(expected));
+                }
+                None if retained.is_none() => {
+                    assert_eq!(result.is_ok(), evaluations.is_ok());
+                    assert!(result.is_ok());
+                }
+                // positions sharing a leaf with a retained one remain openable after pruning
+                None => assert_eq!(result.is_ok(), prover.can_open(&positions)),
+            }
+
+            let receipts = prover.sample_receipts(&positions);
+            assert_eq!(
+                receipts.is_ok(),
+                positions.iter().all(|&p| prover.can_open(&[p]))
+            );
+        }
+    }
+}
+
+#[test]
+fn malformed_cells_and_blobs_never_panic() {
+    for (prover, domain_size, _) in test_provers() {
+        for _ in 0..NUM_RUNS {
+            let positions = rand_positions(domain_size);
+            let blob = rand_value::<u64>() as usize % 4;
+            let _ = prover.open_blob(blob, &positions);
+            let _ = prover.open_for_validators(&[positions.clone(), vec![]]);
+            if let Some(&col) = positions.first() {
+                let _ = prover.get_cell(blob, col);
+            }
+        }
+    }
+}
+
+#[test]
+fn malformed_pruning_never_panics() {
+    for (mut prover, domain_size, retained) in test_provers() {
+        let commitment = prover.commitment();
+        for _ in 0..NUM_RUNS / 10 {
+            let positions = rand_positions(domain_size);
+            // an empty slice keeps nothing, as `PruneLevel::Commitment` does
+            let valid = positions.iter().all(|&p| p < domain_size)
+                && (retained.is_none() || positions.is_empty() || prover.can_open(&positions));
+            let result = prover.prune(PruneLevel::Positions(positions));
+            if !valid {
+                assert!(result.is_err());
+            }
+            assert_eq!(prover.commitment(), commitment);
+        }
+    }
+}
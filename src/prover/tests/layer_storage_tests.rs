@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, DeserializationError, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{FridaProver, LayerStorage, ProverLayer, PruneLevel, PrunedLayer},
+    utils::test_utils::*,
+};
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+fn check_lazy_matches_full(options: FriOptions, data_list: &[Vec<u8>]) {
+    let full_builder = TestFridaProverBuilder::new(options.clone());
+    let lazy_builder = TestFridaProverBuilder::new(options).with_layer_storage(LayerStorage::Lazy);
+
+    let ((full_commitment, full), (lazy_commitment, mut lazy)) = if data_list.len() == 1 {
+        (
+            full_builder.commit_and_prove(&data_list[0], 8).unwrap(),
+            lazy_builder.commit_and_prove(&data_list[0], 8).unwrap(),
+        )
+    } else {
+        (
+            full_builder.commit_and_prove_batch(data_list, 8).unwrap(),
+            lazy_builder.commit_and_prove_batch(data_list, 8).unwrap(),
+        )
+    };
+    assert_eq!(lazy_commitment, full_commitment);
+    assert_eq!(lazy.commitment(), full.commitment());
+
+    let positions = vec![0, 3, 17, full_commitment.domain_size - 1];
+    assert_eq!(
+        lazy.open(&positions).unwrap(),
+        full.open(&positions).unwrap()
+    );
+    assert_eq!(
+        lazy.get_evaluations(&positions).unwrap(),
+        full.get_evaluations(&positions).unwrap()
+    );
+
+    // lazy layers survive a snapshot round trip
+    let restored = TestFridaProver::read_from_bytes(&lazy.to_bytes()).unwrap();
+    assert_eq!(
+        restored.open(&positions).unwrap(),
+        full.open(&positions).unwrap()
+    );
+    assert!(lazy.to_bytes().len() < full.to_bytes().len());
+
+    // and can be pruned like full ones
+    lazy.prune(PruneLevel::Positions(positions.clone()))
+        .unwrap();
+    assert_eq!(
+        lazy.open(&positions).unwrap(),
+        full.open(&positions).unwrap()
+    );
+}
+
+#[test]
+fn lazy_layers_folding_2() {
+    check_lazy_matches_full(test_options(), &[rand_vector::<u8>(2000)]);
+    check_lazy_matches_full(
+        test_options(),
+        &[rand_vector::<u8>(500), rand_vector::<u8>(700)],
+    );
+}
+
+#[test]
+fn lazy_layers_folding_4() {
+    check_lazy_matches_full(FriOptions::new(4, 4, 3), &[rand_vector::<u8>(3000)]);
+    check_lazy_matches_full(
+        FriOptions::new(4, 4, 3),
+        &(0..3).map(|_| rand_vector::<u8>(900)).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn lazy_layers_folding_8() {
+    check_lazy_matches_full(FriOptions::new(2, 8, 7), &[rand_vector::<u8>(8000)]);
+    check_lazy_matches_full(
+        FriOptions::new(2, 8, 7),
+        &(0..4).map(|_| rand_vector::<u8>(4000)).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn lazy_layers_folding_16() {
+    check_lazy_matches_full(FriOptions::new(2, 16, 15), &[rand_vector::<u8>(30000)]);
+    check_lazy_matches_full(
+        FriOptions::new(2, 16, 15),
+        &(0..2).map(|_| rand_vector::<u8>(30000)).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn large_position_sets_open_identically() {
+    // full layers are queried in parallel with the `concurrent` feature, lazy ones in order
+    let options = FriOptions::new(2, 4, 3);
+    let data_list = (0..3).map(|_| rand_vector::<u8>(3000)).collect::<Vec<_>>();
+    let (commitment, full) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove_batch(&data_list, 8)
+        .unwrap();
+    let (_, lazy) = TestFridaProverBuilder::new(options.clone())
+        .with_layer_storage(LayerStorage::Lazy)
+        .commit_and_prove_batch(&data_list, 8)
+        .unwrap();
+
+    let positions = (0..commitment.domain_size).collect::<Vec<_>>();
+    let proof = full.open(&positions).unwrap();
+    assert_eq!(proof, lazy.open(&positions).unwrap());
+
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    verifier
+        .verify(
+            &proof,
+            &full.get_evaluations(&positions).unwrap(),
+            &positions,
+        )
+        .unwrap();
+}
+
+#[test]
+fn malformed_lazy_snapshots_are_rejected() {
+    let (_, prover) = TestFridaProverBuilder::new(test_options())
+        .with_layer_storage(LayerStorage::Lazy)
+        .commit_and_prove(&rand_vector::<u8>(2000), 8)
+        .unwrap();
+    assert!(matches!(prover.layers[1], ProverLayer::Lazy(_)));
+    let root = prover.layers[0].root();
+
+    // a lazy first layer has no layer to be regenerated from
+    let mut forged = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    forged.layers[0] = ProverLayer::Lazy(root);
+    assert!(matches!(
+        TestFridaProver::read_from_bytes(&forged.to_bytes()),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // neither has a lazy layer following a pruned one
+    let mut forged = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    forged.layers[0] = ProverLayer::Pruned(PrunedLayer {
+        root,
+        rows: BTreeMap::new(),
+    });
+    assert!(matches!(
+        TestFridaProver::read_from_bytes(&forged.to_bytes()),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // a lazy layer whose root does not match the regenerated layer is rejected when opening
+    let mut forged = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    forged.layers[1] = ProverLayer::Lazy(root);
+    let forged = TestFridaProver::read_from_bytes(&forged.to_bytes()).unwrap();
+    assert_eq!(
+        forged.open(&[0, 3]),
+        Err(FridaError::RegeneratedLayerMismatch(1))
+    );
+}
@@ -1,5 +1,101 @@
+#[cfg(all(test, feature = "async"))]
+mod async_tests;
+
+#[cfg(test)]
+mod archival_tests;
+
 #[cfg(test)]
 mod base_tests;
 
+#[cfg(test)]
+mod bucketed_tests;
+
+#[cfg(test)]
+mod cache_tests;
+
+#[cfg(test)]
+mod cancel_tests;
+
+#[cfg(test)]
+mod cost_tests;
+
+#[cfg(test)]
+mod data_root_tests;
+
+#[cfg(test)]
+mod deadline_tests;
+
+#[cfg(test)]
+mod dedup_tests;
+
+#[cfg(test)]
+mod determinism_tests;
+
+#[cfg(test)]
+mod diff_tests;
+
+#[cfg(test)]
+mod distributed_proving_tests;
+
 #[cfg(test)]
 mod distributed_tests;
+
+#[cfg(test)]
+mod encoding_tests;
+
+#[cfg(test)]
+mod epoch_tests;
+
+#[cfg(test)]
+mod framed_tests;
+
+#[cfg(test)]
+mod fuzz_tests;
+
+#[cfg(test)]
+mod layer_storage_tests;
+
+#[cfg(test)]
+mod partition_tests;
+
+#[cfg(test)]
+mod pipeline_tests;
+
+#[cfg(test)]
+mod progress_tests;
+
+#[cfg(test)]
+mod proof_size_tests;
+
+#[cfg(test)]
+mod prune_tests;
+
+#[cfg(test)]
+mod queue_tests;
+
+#[cfg(test)]
+mod retrievability_tests;
+
+#[cfg(test)]
+mod scheduler_tests;
+
+#[cfg(test)]
+mod shard_tests;
+
+#[cfg(all(test, feature = "f31"))]
+mod small_field_tests;
+
+#[cfg(test)]
+mod stream_tests;
+
+#[cfg(test)]
+mod supersede_tests;
+
+#[cfg(test)]
+mod traits_tests;
+
+#[cfg(test)]
+mod transcript_tests;
+
+#[cfg(test)]
+mod winter_conversion_tests;
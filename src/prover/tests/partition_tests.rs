@@ -0,0 +1,189 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{
+        diff::{bucket_positions, diff_commitments},
+        proof::FridaProof,
+        BatchLeafEncoding, Commitment, FridaProver, LayerStorage, PruneLevel,
+    },
+    utils::test_utils::*,
+};
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+#[test]
+fn partitioned_proofs_verify() {
+    let data = rand_vector::<u8>(500);
+    let data_list = (0..4).map(|_| rand_vector::<u8>(200)).collect::<Vec<_>>();
+    let (plain, ..) = TestFridaProverBuilder::new(test_options())
+        .commitment(&data, 4)
+        .unwrap();
+
+    for num_partitions in [2, 4] {
+        for layer_storage in [LayerStorage::Full, LayerStorage::Lazy] {
+            let builder = TestFridaProverBuilder::new(test_options())
+                .with_num_partitions(num_partitions)
+                .with_layer_storage(layer_storage);
+            let (commitment, prover, positions) = builder.commitment(&data, 4).unwrap();
+            let (batch_commitment, batch_prover, batch_positions) =
+                builder.commitment_batch(&data_list, 4).unwrap();
+            // the leaves of every layer are laid out by partition
+            assert_ne!(commitment.roots[0], plain.roots[0]);
+
+            for (commitment, prover, positions) in [
+                (commitment, prover, positions),
+                (batch_commitment, batch_prover, batch_positions),
+            ] {
+                let proof = prover.open(&positions).unwrap();
+                assert_eq!(proof.num_partitions(), num_partitions);
+                let evaluations = prover.get_evaluations(&positions).unwrap();
+                let verifier =
+                    TestFridaDasVerifier::from_commitment(&commitment, test_options()).unwrap();
+                verifier.verify(&proof, &evaluations, &positions).unwrap();
+            }
+        }
+    }
+}
+
+#[test]
+fn partitioned_das_commitments_verify() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(4);
+    let data_list = (0..3).map(|_| rand_vector::<u8>(300)).collect::<Vec<_>>();
+    let (commitment, prover) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+    assert_eq!(commitment.proof.num_partitions(), 4);
+
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, test_options()).unwrap();
+    let positions = [1, 7, 12];
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+}
+
+#[test]
+fn proofs_do_not_verify_with_other_partitions() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let (commitment, prover, positions) = builder.commitment(&rand_vector::<u8>(500), 4).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, test_options()).unwrap();
+
+    // the number of partitions is the last byte of a proof, stored as its log2; verifiers take
+    // it from the commitment instead
+    let mut bytes = prover.open(&positions).unwrap().to_bytes();
+    for log2 in [0, 2, 40] {
+        *bytes.last_mut().unwrap() = log2;
+        let proof = FridaProof::read_from_bytes(&bytes).unwrap();
+        assert_eq!(
+            verifier.verify(&proof, &evaluations, &positions),
+            Err(FridaError::PartitionMismatch(2, 1 << log2))
+        );
+    }
+
+    // a commitment claiming other partitions has another binding and root digest, which its
+    // verifiers recompute, and does not verify either
+    let mut relabeled = commitment.clone();
+    relabeled.num_partitions = 1;
+    assert_ne!(relabeled.binding(), commitment.binding());
+    assert_ne!(relabeled.root_digest(), commitment.root_digest());
+    assert_eq!(
+        verifier.check_root_digest(&relabeled.root_digest()),
+        Err(FridaError::RootDigestMismatch)
+    );
+    let proof = prover.open(&positions).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&relabeled, test_options()).unwrap();
+    assert_eq!(
+        verifier.verify(&proof, &evaluations, &positions),
+        Err(FridaError::PartitionMismatch(1, 2))
+    );
+}
+
+#[test]
+fn commitments_carry_their_partitions() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(4);
+    let (commitment, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    assert_eq!(commitment.num_partitions, 4);
+    assert_eq!(prover.commitment().num_partitions, 4);
+    assert_eq!(commitment.to_prover_commitment(), prover.commitment());
+
+    // the embedded proof must be made for the partitions of the commitment
+    let mut relabeled = commitment;
+    relabeled.num_partitions = 2;
+    let bytes = relabeled.to_bytes();
+    let relabeled = Commitment::<Blake3>::read_from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        TestFridaDasVerifier::new(relabeled, test_options()),
+        Err(FridaError::InvalidDASCommitment)
+    ));
+}
+
+#[test]
+fn pruned_and_restored_partitioned_provers_open_the_same_proofs() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let (_, mut prover, positions) = builder.commitment(&rand_vector::<u8>(500), 4).unwrap();
+    let proof = prover.open(&positions).unwrap();
+
+    let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    assert_eq!(restored.open(&positions).unwrap(), proof);
+
+    prover
+        .prune(PruneLevel::Positions(positions.clone()))
+        .unwrap();
+    assert_eq!(prover.open(&positions).unwrap(), proof);
+    let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    assert_eq!(restored.open(&positions).unwrap(), proof);
+}
+
+#[test]
+fn commitments_fail_with_more_partitions_than_rows() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(1 << 20);
+    assert!(matches!(
+        builder.commitment(&rand_vector::<u8>(500), 4),
+        Err(FridaError::TooManyPartitions(partitions, _)) if partitions == 1 << 20
+    ));
+}
+
+#[test]
+fn partitioned_provers_do_not_open_blobs() {
+    let builder = TestFridaProverBuilder::new(test_options())
+        .with_num_partitions(2)
+        .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+    let data_list = (0..2).map(|_| rand_vector::<u8>(200)).collect::<Vec<_>>();
+    let (_, prover, positions) = builder.commitment_batch(&data_list, 4).unwrap();
+    assert!(matches!(
+        prover.open_blob(0, &positions),
+        Err(FridaError::BlobOpeningUnsupported)
+    ));
+}
+
+#[test]
+fn diff_reports_buckets_of_partitioned_provers() {
+    let data_a = rand_vector::<u8>(500);
+    let mut data_b = data_a.clone();
+    data_b[17] ^= 1;
+
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(4);
+    let (commitment, prover_a) = builder.commit_and_prove(&data_a, 8).unwrap();
+    let (_, prover_b) = builder.commit_and_prove(&data_b, 8).unwrap();
+    let buckets = diff_commitments(&prover_a, &prover_b).unwrap();
+    assert!(!buckets.is_empty());
+
+    let domain_size = commitment.domain_size;
+    for bucket in 0..domain_size / 2 {
+        let positions = bucket_positions(bucket, domain_size, 2);
+        let changed = prover_a.get_evaluations(&positions).unwrap()
+            != prover_b.get_evaluations(&positions).unwrap();
+        assert_eq!(changed, buckets.contains(&bucket));
+    }
+
+    let (_, unpartitioned) = TestFridaProverBuilder::new(test_options())
+        .commit_and_prove(&data_b, 8)
+        .unwrap();
+    assert_eq!(
+        diff_commitments(&prover_a, &unpartitioned),
+        Err(FridaError::IncompatibleCommitments)
+    );
+}
@@ -0,0 +1,100 @@
+use winter_rand_utils::rand_vector;
+
+use crate::{error::FridaError, prover::pipeline::FridaBatchPipeline, utils::test_utils::*};
+
+fn test_blobs() -> Vec<Vec<u8>> {
+    [200, 1, 4096, 64, 1000]
+        .iter()
+        .map(|&len| rand_vector::<u8>(len))
+        .collect()
+}
+
+#[test]
+fn pipeline_matches_sequential_commitments() {
+    let options = test_options();
+    let blobs = test_blobs();
+    let pipeline = FridaBatchPipeline::new(TestFridaProverBuilder::new(options.clone()), 4)
+        .with_queue_depth(2);
+    let builder = TestFridaProverBuilder::new(options);
+
+    let results = pipeline.commit_all(blobs.clone()).collect::<Vec<_>>();
+    assert_eq!(results.len(), blobs.len());
+    for (blob, result) in blobs.iter().zip(results) {
+        let (commitment, prover) = result.unwrap();
+        let (expected_commitment, expected_prover) = builder.commit_and_prove(blob, 4).unwrap();
+        assert_eq!(commitment, expected_commitment);
+        let positions = (0..commitment.domain_size).collect::<Vec<_>>();
+        assert_eq!(
+            prover.evaluations_at(&positions).unwrap(),
+            expected_prover.evaluations_at(&positions).unwrap()
+        );
+    }
+}
+
+#[test]
+fn pipeline_reports_errors_in_order() {
+    let options = test_options();
+    let pipeline = FridaBatchPipeline::new(TestFridaProverBuilder::new(options), 64);
+    let blobs = vec![rand_vector::<u8>(4096), rand_vector::<u8>(1)];
+
+    let mut results = pipeline.commit_all(blobs);
+    assert!(results.next().unwrap().is_ok());
+    assert_eq!(
+        results.next().unwrap().unwrap_err(),
+        FridaError::BadNumQueries(64)
+    );
+    assert!(results.next().is_none());
+}
+
+#[test]
+fn pipeline_with_no_blobs_is_empty() {
+    let pipeline = FridaBatchPipeline::new(TestFridaProverBuilder::new(test_options()), 4);
+    assert!(pipeline.commit_all(vec![]).next().is_none());
+}
+
+#[test]
+fn dropped_stream_stops_pipeline() {
+    let pipeline = FridaBatchPipeline::new(TestFridaProverBuilder::new(test_options()), 4);
+    let mut results = pipeline.commit_all(test_blobs());
+    assert!(results.next().unwrap().is_ok());
+    drop(results);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn pipeline_as_stream() {
+    use core::pin::Pin;
+    use futures_core::Stream;
+    use std::{
+        sync::Arc,
+        task::{Context, Poll, Wake},
+        thread::{self, Thread},
+    };
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let blobs = test_blobs();
+    let pipeline = FridaBatchPipeline::new(TestFridaProverBuilder::new(test_options()), 4);
+    let mut stream = pipeline.commit_all(blobs.clone());
+
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut count = 0;
+    loop {
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(result)) => {
+                result.unwrap();
+                count += 1;
+            }
+            Poll::Ready(None) => break,
+            Poll::Pending => thread::park(),
+        }
+    }
+    assert_eq!(count, blobs.len());
+}
@@ -0,0 +1,82 @@
+use std::sync::{Arc, Mutex};
+
+use winter_fri::FriOptions;
+use winter_rand_utils::rand_vector;
+
+use crate::{prover::progress::Phase, utils::test_utils::*};
+
+/// Returns a builder reporting its phases to the returned list.
+fn recording_builder(options: FriOptions) -> (TestFridaProverBuilder, Arc<Mutex<Vec<Phase>>>) {
+    let phases = Arc::new(Mutex::new(Vec::new()));
+    let sink = phases.clone();
+    let builder = TestFridaProverBuilder::new(options)
+        .with_progress_sink(move |phase: Phase| sink.lock().unwrap().push(phase));
+    (builder, phases)
+}
+
+fn layer_phases(num_layers: usize) -> impl Iterator<Item = Phase> {
+    (0..num_layers).map(move |layer| Phase::LayerHashing { layer, num_layers })
+}
+
+#[test]
+fn test_progress_single_blob() {
+    let options = test_options();
+    let (builder, phases) = recording_builder(options.clone());
+    let data = rand_vector::<u8>(500);
+
+    let (commitment, _) = builder.commit_and_prove(&data, 4).unwrap();
+    let num_layers = commitment.roots.len();
+    assert!(num_layers > 1);
+
+    let mut expected = vec![
+        Phase::Encoding {
+            blob: 0,
+            num_blobs: 1,
+        },
+        Phase::Fft {
+            blob: 0,
+            num_blobs: 1,
+        },
+    ];
+    expected.extend(layer_phases(num_layers));
+    expected.push(Phase::ProofBuilding);
+    assert_eq!(*phases.lock().unwrap(), expected);
+
+    // the sink does not change the commitment
+    let (plain, _) = TestFridaProverBuilder::new(options)
+        .commit_and_prove(&data, 4)
+        .unwrap();
+    assert_eq!(plain, commitment);
+}
+
+#[test]
+fn test_progress_batch() {
+    let (builder, phases) = recording_builder(test_options());
+    let data_list = vec![rand_vector::<u8>(300), rand_vector::<u8>(200)];
+
+    let (commitment, _, _) = builder.commitment_batch(&data_list, 4).unwrap();
+    let mut expected = (0..2)
+        .flat_map(|blob| {
+            [
+                Phase::Encoding { blob, num_blobs: 2 },
+                Phase::Fft { blob, num_blobs: 2 },
+            ]
+        })
+        .collect::<Vec<_>>();
+    expected.extend(layer_phases(commitment.roots.len()));
+    assert_eq!(*phases.lock().unwrap(), expected);
+}
+
+#[test]
+fn test_progress_skips_encoding_of_encodings() {
+    let (builder, phases) = recording_builder(test_options());
+    let data = rand_vector::<u8>(500);
+
+    let encoding = builder.encode(&data, 4).unwrap();
+    phases.lock().unwrap().clear();
+    let (commitment, _, _) = builder.commit(encoding).unwrap();
+    assert_eq!(
+        *phases.lock().unwrap(),
+        layer_phases(commitment.roots.len()).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,72 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{error::FridaError, prover::proof::estimate_proof_size, utils::test_utils::*};
+
+#[test]
+fn single_position_estimate_is_exact() {
+    for (options, batch_size) in [
+        (test_options(), 1),
+        (FriOptions::new(2, 4, 3), 1),
+        (FriOptions::new(2, 4, 3), 4),
+    ] {
+        let data_list = (0..batch_size)
+            .map(|_| rand_vector::<u8>(1000))
+            .collect::<Vec<_>>();
+        let builder = TestFridaProverBuilder::new(options.clone());
+        let (commitment, prover) = if batch_size == 1 {
+            builder.commit_and_prove(&data_list[0], 8)
+        } else {
+            builder.commit_and_prove_batch(&data_list, 8)
+        }
+        .unwrap();
+
+        let size = prover.open(&[3]).unwrap().size();
+        assert_eq!(prover.proof_size_estimate(1), size);
+        assert_eq!(
+            estimate_proof_size::<BaseElement, Blake3>(
+                &options,
+                commitment.domain_size,
+                commitment.poly_count,
+                1
+            ),
+            Ok(size)
+        );
+    }
+}
+
+#[test]
+fn random_positions_estimate_is_close() {
+    let options = FriOptions::new(2, 4, 3);
+    let data = rand_vector::<u8>(64 * 1024);
+    let (commitment, prover) = TestFridaProverBuilder::new(options)
+        .commit_and_prove(&data, 8)
+        .unwrap();
+
+    let num_positions = 64;
+    let average = (0..16)
+        .map(|_| {
+            let positions = rand_vector::<u64>(num_positions)
+                .into_iter()
+                .map(|v| v as usize % commitment.domain_size)
+                .collect::<Vec<_>>();
+            prover.open(&positions).unwrap().size()
+        })
+        .sum::<usize>() as f64
+        / 16.0;
+
+    let estimate = prover.proof_size_estimate(num_positions) as f64;
+    assert!((estimate - average).abs() / average < 0.05);
+}
+
+#[test]
+fn estimate_rejects_invalid_domains() {
+    let options = FriOptions::new(8, 2, 7);
+    for domain_size in [0, 48, 4] {
+        assert_eq!(
+            estimate_proof_size::<BaseElement, Blake3>(&options, domain_size, 1, 1),
+            Err(FridaError::InvalidDomainSize(domain_size))
+        );
+    }
+}
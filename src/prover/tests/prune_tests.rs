@@ -0,0 +1,119 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{FridaProver, ProverCommitment, PruneLevel},
+    utils::test_utils::*,
+};
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+fn test_provers() -> Vec<(TestFridaProver, ProverCommitment<Blake3>, Vec<usize>)> {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (commitment, prover, positions) = builder.commitment(&rand_vector::<u8>(200), 4).unwrap();
+    let data_list = (0..4).map(|_| rand_vector::<u8>(64)).collect::<Vec<_>>();
+    let (batch_commitment, batch_prover, batch_positions) =
+        builder.commitment_batch(&data_list, 4).unwrap();
+    vec![
+        (prover, commitment, positions),
+        (batch_prover, batch_commitment, batch_positions),
+    ]
+}
+
+#[test]
+fn pruned_prover_opens_retained_positions() {
+    for (mut prover, commitment, positions) in test_provers() {
+        let proof = prover.open(&positions).unwrap();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+        let subset = positions[1..].to_vec();
+        let subset_proof = prover.open(&subset).unwrap();
+
+        prover
+            .prune(PruneLevel::Positions(positions.clone()))
+            .unwrap();
+        assert_eq!(prover.commitment(), commitment);
+        assert!(prover.can_open(&positions));
+        assert!(prover.can_open(&subset));
+        assert_eq!(prover.open(&positions).unwrap(), proof);
+        assert_eq!(prover.open(&subset).unwrap(), subset_proof);
+        assert_eq!(prover.get_evaluations(&positions).unwrap(), evaluations);
+
+        // the pruned proof still verifies
+        let verifier = TestFridaDasVerifier::from_commitment(&commitment, test_options()).unwrap();
+        verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+        // and survives a snapshot round trip
+        let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+        assert_eq!(restored.open(&positions).unwrap(), proof);
+    }
+}
+
+#[test]
+fn pruned_prover_cannot_open_other_positions() {
+    for (mut prover, commitment, positions) in test_provers() {
+        // positions sharing a leaf with a retained one remain openable, so pick one which doesn't
+        let bucket_count = commitment.domain_size / 2;
+        let other = (0..commitment.domain_size)
+            .find(|p| {
+                positions
+                    .iter()
+                    .all(|q| q % bucket_count != p % bucket_count)
+            })
+            .unwrap();
+        assert!(prover.can_open(&[other]));
+
+        prover
+            .prune(PruneLevel::Positions(positions.clone()))
+            .unwrap();
+        assert!(!prover.can_open(&[other]));
+
+        prover.prune(PruneLevel::Commitment).unwrap();
+        assert!(!prover.can_open(&positions));
+        assert_eq!(prover.commitment(), commitment);
+    }
+}
+
+#[test]
+fn opening_pruned_position_fails() {
+    let (mut prover, _, positions) = test_provers().remove(0);
+    prover
+        .prune(PruneLevel::Positions(positions[..1].to_vec()))
+        .unwrap();
+    assert!(matches!(
+        prover.open(&positions),
+        Err(FridaError::PositionPruned(_))
+    ));
+    assert!(matches!(
+        prover.get_evaluations(&positions),
+        Err(FridaError::PositionPruned(_))
+    ));
+
+    // a failed pruning leaves the prover untouched
+    let commitment = prover.commitment();
+    assert!(matches!(
+        prover.prune(PruneLevel::Positions(positions.clone())),
+        Err(FridaError::PositionPruned(_))
+    ));
+    assert_eq!(prover.commitment(), commitment);
+    prover.open(&positions[..1]).unwrap();
+}
+
+#[test]
+fn inconsistent_snapshots_are_rejected() {
+    let corruptions: [fn(&mut TestFridaProver); 5] = [
+        |prover| prover.poly_count += 1,
+        |prover| prover.domain_size *= 2,
+        |prover| prover.folding_factors[0] = 4,
+        |prover| prover.num_partitions = prover.domain_size,
+        |prover| prover.data_checksums = vec![Default::default(); prover.poly_count + 1],
+    ];
+    for corrupt in corruptions {
+        for (mut prover, _, _) in test_provers() {
+            assert!(TestFridaProver::read_from_bytes(&prover.to_bytes()).is_ok());
+            corrupt(&mut prover);
+            assert!(TestFridaProver::read_from_bytes(&prover.to_bytes()).is_err());
+        }
+    }
+}
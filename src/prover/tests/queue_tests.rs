@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    prover::{
+        queue::{ClassMetrics, OpenQueue, Priority},
+        FridaProver,
+    },
+    utils::test_utils::*,
+};
+
+type TestOpenQueue = OpenQueue<BaseElement, Blake3>;
+
+fn test_prover() -> Arc<FridaProver<BaseElement, Blake3>> {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover, _) = builder.commitment(&rand_vector::<u8>(200), 4).unwrap();
+    Arc::new(prover)
+}
+
+#[test]
+fn queued_openings_match_direct_openings() {
+    let prover = test_prover();
+    let queue = TestOpenQueue::new(2, 8);
+
+    let tickets = (0..6)
+        .map(|i| {
+            let priority = if i % 2 == 0 {
+                Priority::Critical
+            } else {
+                Priority::BestEffort
+            };
+            let positions = vec![i, i + 7];
+            let ticket = queue
+                .submit(prover.clone(), positions.clone(), priority)
+                .unwrap();
+            (positions, ticket)
+        })
+        .collect::<Vec<_>>();
+
+    for (positions, ticket) in tickets {
+        assert_eq!(ticket.wait().unwrap(), prover.open(&positions).unwrap());
+    }
+
+    let metrics = queue.metrics();
+    assert_eq!(metrics.critical.completed, 3);
+    assert_eq!(metrics.best_effort.completed, 3);
+    assert_eq!(metrics.in_flight, 0);
+}
+
+#[test]
+fn critical_openings_are_served_first() {
+    let prover = test_prover();
+    let queue = TestOpenQueue::test_paused(4);
+
+    let _best_effort = queue
+        .submit(prover.clone(), vec![0], Priority::BestEffort)
+        .unwrap();
+    assert_eq!(queue.test_next_priority(), Some(Priority::BestEffort));
+    let _critical = queue.submit(prover, vec![1], Priority::Critical).unwrap();
+    assert_eq!(queue.test_next_priority(), Some(Priority::Critical));
+}
+
+#[test]
+fn full_queue_applies_backpressure() {
+    let prover = test_prover();
+    let mut queue = TestOpenQueue::test_paused(2);
+
+    let oldest = queue
+        .submit(prover.clone(), vec![0], Priority::BestEffort)
+        .unwrap();
+    let newest = queue
+        .submit(prover.clone(), vec![1], Priority::BestEffort)
+        .unwrap();
+
+    // best-effort requests are refused once the queue is full
+    assert_eq!(
+        queue
+            .submit(prover.clone(), vec![2], Priority::BestEffort)
+            .unwrap_err(),
+        FridaError::QueueFull
+    );
+
+    // while critical requests evict the most recent best-effort ones
+    let critical = (3..5)
+        .map(|i| {
+            queue
+                .submit(prover.clone(), vec![i], Priority::Critical)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(newest.wait().unwrap_err(), FridaError::OpeningCancelled);
+    assert_eq!(oldest.wait().unwrap_err(), FridaError::OpeningCancelled);
+
+    // until only critical requests are left
+    assert_eq!(
+        queue
+            .submit(prover.clone(), vec![5], Priority::Critical)
+            .unwrap_err(),
+        FridaError::QueueFull
+    );
+
+    assert_eq!(
+        queue.metrics().best_effort,
+        ClassMetrics {
+            queued: 0,
+            submitted: 3,
+            completed: 0,
+            rejected: 1,
+            evicted: 2,
+        }
+    );
+
+    queue.test_resume(1);
+    for ticket in critical {
+        ticket.wait().unwrap();
+    }
+    assert_eq!(
+        queue.metrics().critical,
+        ClassMetrics {
+            queued: 0,
+            submitted: 3,
+            completed: 2,
+            rejected: 1,
+            evicted: 0,
+        }
+    );
+}
+
+#[test]
+fn dropping_queue_cancels_waiting_openings() {
+    let prover = test_prover();
+    let queue = TestOpenQueue::test_paused(2);
+    let ticket = queue.submit(prover, vec![0], Priority::Critical).unwrap();
+    drop(queue);
+    assert_eq!(ticket.wait().unwrap_err(), FridaError::OpeningCancelled);
+}
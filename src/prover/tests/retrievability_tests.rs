@@ -0,0 +1,95 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{
+        retrievability::{RetrievabilityChallenge, RetrievabilityResponse},
+        PruneLevel,
+    },
+    utils::test_utils::*,
+};
+
+#[test]
+fn responses_answer_their_challenge() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let commitment = prover.commitment();
+
+    let challenge = RetrievabilityChallenge::random(8, 100);
+    let response = prover.respond_to_challenge::<Blake3>(&challenge).unwrap();
+    challenge
+        .verify_response::<BaseElement, Blake3, Blake3>(
+            &commitment,
+            &response,
+            100,
+            &test_options(),
+        )
+        .unwrap();
+
+    // responses are bound to the seed they answer
+    let other = RetrievabilityChallenge::random(8, 100);
+    assert_ne!(
+        other.positions::<BaseElement, Blake3, Blake3>(&commitment),
+        challenge.positions::<BaseElement, Blake3, Blake3>(&commitment)
+    );
+    assert!(other
+        .verify_response::<BaseElement, Blake3, Blake3>(&commitment, &response, 0, &test_options())
+        .is_err());
+}
+
+#[test]
+fn late_responses_are_rejected() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let challenge = RetrievabilityChallenge::random(8, 100);
+    let response = prover.respond_to_challenge::<Blake3>(&challenge).unwrap();
+    assert_eq!(
+        challenge.verify_response::<BaseElement, Blake3, Blake3>(
+            &prover.commitment(),
+            &response,
+            101,
+            &test_options()
+        ),
+        Err(FridaError::ChallengeExpired(100, 101))
+    );
+}
+
+#[test]
+fn pruned_provers_cannot_respond() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, mut prover, positions) = builder.commitment(&rand_vector::<u8>(500), 4).unwrap();
+    prover.prune(PruneLevel::Positions(positions)).unwrap();
+    let challenge = RetrievabilityChallenge::random(32, 100);
+    assert!(matches!(
+        prover.respond_to_challenge::<Blake3>(&challenge),
+        Err(FridaError::PositionPruned(_))
+    ));
+}
+
+#[test]
+fn challenges_and_responses_round_trip() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let challenge = RetrievabilityChallenge::random(8, 100);
+    let response = prover.respond_to_challenge::<Blake3>(&challenge).unwrap();
+
+    let challenge = RetrievabilityChallenge::read_from_bytes(&challenge.to_bytes()).unwrap();
+    let response =
+        RetrievabilityResponse::<BaseElement>::read_from_bytes(&response.to_bytes()).unwrap();
+    challenge
+        .verify_response::<BaseElement, Blake3, Blake3>(
+            &prover.commitment(),
+            &response,
+            0,
+            &test_options(),
+        )
+        .unwrap();
+}
@@ -0,0 +1,237 @@
+use std::time::{Duration, Instant};
+
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    core::queries::calculate_batch_security_level,
+    error::FridaError,
+    prover::{
+        cost::CostModel,
+        scheduler::{CommitProfile, CommitmentScheduler},
+        Commitment, ProverCommitment,
+    },
+    utils::test_utils::*,
+    winterfell::f128::BaseElement,
+};
+
+const DATA_SIZE: usize = 4096;
+const NUM_QUERIES: usize = 16;
+
+fn profiles() -> Vec<CommitProfile> {
+    vec![
+        CommitProfile::new(8, 2, 7),
+        CommitProfile::new(4, 4, 7),
+        CommitProfile::new(2, 16, 7),
+    ]
+}
+
+fn estimated_ms(profile: &CommitProfile) -> f64 {
+    CostModel::default()
+        .estimate_commit_cost::<BaseElement>(DATA_SIZE, &profile.options())
+        .unwrap()
+        .estimated_time_ms
+}
+
+#[test]
+fn test_scheduler_selects_largest_profile_in_time() {
+    let scheduler = CommitmentScheduler::<BaseElement>::new(profiles(), 0);
+    let profiles = profiles();
+
+    let scheduled = scheduler
+        .select(DATA_SIZE, NUM_QUERIES, Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(scheduled.profile, profiles[0]);
+    assert!(scheduled.on_time);
+
+    // with just enough time for the second profile, the first one is skipped
+    let budget = Duration::from_secs_f64(estimated_ms(&profiles[1]) / 1e3);
+    assert!(estimated_ms(&profiles[0]) > estimated_ms(&profiles[1]));
+    let scheduled = scheduler.select(DATA_SIZE, NUM_QUERIES, budget).unwrap();
+    assert_eq!(scheduled.profile, profiles[1]);
+    assert!(scheduled.on_time);
+
+    // without enough time for any profile, the fastest one is selected
+    let fastest = profiles
+        .iter()
+        .min_by(|a, b| estimated_ms(a).total_cmp(&estimated_ms(b)))
+        .unwrap();
+    let scheduled = scheduler
+        .select(DATA_SIZE, NUM_QUERIES, Duration::ZERO)
+        .unwrap();
+    assert_eq!(scheduled.profile, *fastest);
+    assert!(!scheduled.on_time);
+}
+
+#[test]
+fn test_scheduler_follows_measured_throughput() {
+    let mut scheduler = CommitmentScheduler::<BaseElement>::new(profiles(), 0);
+    let profile = profiles()[0];
+    let predicted = estimated_ms(&profile);
+    assert_eq!(scheduler.slowdown(), 1.0);
+
+    scheduler.observe(
+        &profile,
+        DATA_SIZE,
+        Duration::from_secs_f64(predicted * 2.0 / 1e3),
+    );
+    assert!((scheduler.slowdown() - 2.0).abs() < 1e-3);
+    scheduler.observe(
+        &profile,
+        DATA_SIZE,
+        Duration::from_secs_f64(predicted * 4.0 / 1e3),
+    );
+    assert!((scheduler.slowdown() - 2.5).abs() < 1e-3);
+
+    // the budget which fit the first profile before the slowdown no longer does
+    let budget = Duration::from_secs_f64(predicted * 1.5 / 1e3);
+    let scheduled = scheduler.select(DATA_SIZE, NUM_QUERIES, budget).unwrap();
+    assert_ne!(scheduled.profile, profile);
+}
+
+#[test]
+fn test_scheduled_commitments_record_profile() {
+    let mut scheduler = CommitmentScheduler::<BaseElement>::new(profiles(), 0);
+    let data = rand_vector::<u8>(DATA_SIZE);
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let (commitment, prover, positions) = scheduler
+        .commit_by::<Blake3, Blake3>(&data, NUM_QUERIES, deadline)
+        .unwrap();
+    let profile = commitment.profile.unwrap();
+    assert_eq!(profile, profiles()[0]);
+    assert_eq!(prover.commitment(), commitment);
+    assert_ne!(scheduler.slowdown(), 1.0);
+
+    // verifiers take the options from the commitment
+    let decoded = ProverCommitment::<Blake3>::read_from_bytes(&commitment.to_bytes()).unwrap();
+    assert_eq!(decoded, commitment);
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    TestFridaDasVerifier::from_commitment(&decoded, profile.options())
+        .unwrap()
+        .verify(&proof, &evaluations, &positions)
+        .unwrap();
+
+    // the roots are those of the same options without the profile, which is bound on its own
+    let (plain, _, _) = TestFridaProverBuilder::new(profile.options())
+        .commitment(&data, NUM_QUERIES)
+        .unwrap();
+    assert_eq!(plain.profile, None);
+    assert_eq!(plain.roots, commitment.roots);
+    assert_ne!(plain.binding(), commitment.binding());
+    assert_ne!(plain.root_digest(), commitment.root_digest());
+}
+
+#[test]
+fn test_commitments_are_verified_with_their_profile() {
+    let profile = profiles()[0];
+    let data = rand_vector::<u8>(DATA_SIZE);
+    let builder = TestFridaProverBuilder::new(profile.options()).with_commit_profile(profile);
+    let (commitment, prover) = builder.commit_and_prove(&data, NUM_QUERIES).unwrap();
+    let bytes = commitment.to_bytes();
+    let verifier = TestFridaDasVerifier::new(commitment, profile.options())
+        .unwrap()
+        .0;
+    assert_eq!(verifier.profile(), Some(profile));
+    assert_eq!(verifier.root_digest(), prover.commitment().root_digest());
+
+    // the options of the verifier must be those of the profile
+    let other = profiles()[1];
+    assert_eq!(
+        TestFridaDasVerifier::new(
+            Commitment::read_from_bytes(&bytes).unwrap(),
+            other.options()
+        )
+        .err(),
+        Some(FridaError::ProfileMismatch)
+    );
+    assert_eq!(
+        TestFridaDasVerifier::from_commitment(&prover.commitment(), other.options()).err(),
+        Some(FridaError::ProfileMismatch)
+    );
+
+    // profiles are read with a strict presence tag
+    let mut bytes = bytes;
+    let tag = bytes.len() - profile.to_bytes().len() - 1;
+    assert_eq!(bytes[tag], 1);
+    bytes[tag] = 2;
+    assert!(Commitment::<Blake3>::read_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_scheduler_enforces_minimum_security() {
+    let profiles = profiles();
+    let levels = profiles
+        .iter()
+        .map(|profile| {
+            calculate_batch_security_level::<BaseElement>(
+                DATA_SIZE,
+                &profile.options(),
+                1,
+                NUM_QUERIES,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(levels[2] < levels[1]);
+
+    // the fastest profile is below the floor, so it is not selected even when late
+    let scheduler = CommitmentScheduler::<BaseElement>::new(profiles.clone(), levels[1]);
+    let scheduled = scheduler
+        .select(DATA_SIZE, NUM_QUERIES, Duration::ZERO)
+        .unwrap();
+    assert_ne!(scheduled.profile, profiles[2]);
+    assert!(!scheduled.on_time);
+
+    // no profile meets a floor above all of them
+    let max_level = *levels.iter().max().unwrap();
+    let scheduler = CommitmentScheduler::<BaseElement>::new(profiles, max_level + 1);
+    assert_eq!(
+        scheduler.select(DATA_SIZE, NUM_QUERIES, Duration::from_secs(60)),
+        Err(FridaError::InsufficientSecurity(
+            levels[levels.len() - 1],
+            max_level + 1
+        ))
+    );
+}
+
+#[test]
+fn test_scheduler_ignores_unpredictable_observations() {
+    let cost_model = CostModel {
+        fft_ns_per_butterfly: 0.0,
+        hash_ns_per_element: 0.0,
+        merge_ns_per_node: 0.0,
+    };
+    let mut scheduler =
+        CommitmentScheduler::<BaseElement>::new(profiles(), 0).with_cost_model(cost_model);
+    let profile = profiles()[0];
+
+    // commitments predicted to take no time do not change the slowdown
+    for elapsed in [Duration::ZERO, Duration::from_millis(5)] {
+        scheduler.observe(&profile, DATA_SIZE, elapsed);
+        assert_eq!(scheduler.slowdown(), 1.0);
+    }
+    let scheduled = scheduler
+        .select(DATA_SIZE, NUM_QUERIES, Duration::ZERO)
+        .unwrap();
+    assert_eq!(scheduled.profile, profile);
+}
+
+#[test]
+fn test_commit_profile_serialization() {
+    let profile = CommitProfile::new(4, 8, 15);
+    assert_eq!(
+        CommitProfile::read_from_bytes(&profile.to_bytes()).unwrap(),
+        profile
+    );
+
+    for (blowup_factor, folding_factor, remainder_max_degree) in [(3, 2, 7), (4, 3, 7), (4, 2, 6)] {
+        let invalid = CommitProfile {
+            blowup_factor,
+            folding_factor,
+            remainder_max_degree,
+        };
+        assert!(CommitProfile::read_from_bytes(&invalid.to_bytes()).is_err());
+    }
+}
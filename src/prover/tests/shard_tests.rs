@@ -0,0 +1,94 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    core::data::{encoding_plan, recover_sharded_data, EncodingOptions},
+    utils::test_utils::*,
+};
+
+fn sharding() -> EncodingOptions {
+    EncodingOptions::default().with_sharding(true)
+}
+
+#[test]
+fn sharded_commitment_uses_smaller_domains() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone()).with_encoding(sharding());
+    let capacity = sharding().capacity::<BaseElement>(64);
+    let data = rand_vector::<u8>(capacity + 100);
+
+    let shards = builder.commitment_sharded(&data, 4).unwrap();
+    assert_eq!(shards.len(), 2);
+    let domain_sizes = shards
+        .iter()
+        .map(|(commitment, _)| commitment.domain_size)
+        .collect::<Vec<_>>();
+    assert_eq!(domain_sizes, vec![128, 16]);
+    assert!(shards
+        .iter()
+        .all(|(commitment, _)| commitment.poly_count == 1));
+
+    // a single commitment needs the next power of two domain
+    let (commitment, _, _) = TestFridaProverBuilder::new(options)
+        .commitment(&data, 4)
+        .unwrap();
+    assert_eq!(commitment.domain_size, 256);
+    assert!(domain_sizes.iter().sum::<usize>() < commitment.domain_size);
+}
+
+#[test]
+fn sharded_commitment_opens_and_recovers_data() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone()).with_encoding(sharding());
+    let data = rand_vector::<u8>(1100);
+    let plan = encoding_plan::<BaseElement>(data.len(), options.blowup_factor(), &sharding());
+    assert!(plan.is_sharded());
+
+    let shards = builder.commitment_sharded(&data, 4).unwrap();
+    let samples = shards
+        .iter()
+        .map(|(commitment, prover)| {
+            let positions = (1..commitment.domain_size).step_by(2).collect::<Vec<_>>();
+            let verifier =
+                TestFridaDasVerifier::from_commitment(commitment, options.clone()).unwrap();
+            let proof = prover.open(&positions).unwrap();
+            let evaluations = prover.get_evaluations(&positions).unwrap();
+            verifier.verify(&proof, &evaluations, &positions).unwrap();
+            (evaluations, positions)
+        })
+        .collect::<Vec<_>>();
+    let samples = samples
+        .iter()
+        .map(|(evaluations, positions)| (&evaluations[..], &positions[..]))
+        .collect::<Vec<_>>();
+
+    let recovered = recover_sharded_data(
+        &samples,
+        &plan.domain_sizes,
+        options.blowup_factor(),
+        &sharding(),
+    )
+    .unwrap();
+    assert_eq!(recovered, data);
+}
+
+#[test]
+fn unsharded_data_is_committed_once() {
+    let options = test_options();
+    let capacity = sharding().capacity::<BaseElement>(64);
+
+    // data filling a power of two number of elements is not split
+    let builder = TestFridaProverBuilder::new(options.clone()).with_encoding(sharding());
+    let data = rand_vector::<u8>(capacity);
+    let shards = builder.commitment_sharded(&data, 4).unwrap();
+    assert_eq!(shards.len(), 1);
+    let (expected, _, _) = builder.commitment(&data, 4).unwrap();
+    assert_eq!(shards[0].0, expected);
+
+    // neither is any data without sharding
+    let builder = TestFridaProverBuilder::new(options);
+    let data = rand_vector::<u8>(capacity + 100);
+    let shards = builder.commitment_sharded(&data, 4).unwrap();
+    assert_eq!(shards.len(), 1);
+    assert_eq!(shards[0].0.domain_size, 256);
+}
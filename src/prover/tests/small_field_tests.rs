@@ -0,0 +1,95 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    core::{
+        data::{encoded_data_element_count, recover_data_with_encoding, EncodingOptions},
+        queries::{calculate_num_queries, calculate_num_queries_in_field, field_security_level},
+    },
+    error::FridaError,
+    prover::small_field::{f31_prover_builder, F31DasVerifier, F31Element, F31_ENCODING},
+    utils::test_utils::test_options,
+};
+
+#[test]
+fn small_field_commitments_verify_and_decode() {
+    let options = test_options();
+    let data = rand_vector::<u8>(1000);
+    let (commitment, prover) = f31_prover_builder(options.clone())
+        .commit_and_prove(&data, 16)
+        .unwrap();
+    let domain_size = commitment.domain_size;
+    let (verifier, _) = F31DasVerifier::new(commitment, options.clone()).unwrap();
+
+    let positions = (0..domain_size / 2).collect::<Vec<_>>();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier
+        .verify(&prover.open(&positions).unwrap(), &evaluations, &positions)
+        .unwrap();
+    assert_eq!(
+        recover_data_with_encoding(&evaluations, &positions, domain_size, 2, &F31_ENCODING),
+        Ok(data)
+    );
+}
+
+#[test]
+fn small_field_batches_verify() {
+    let options = FriOptions::new(4, 2, 3);
+    let data_list = vec![rand_vector::<u8>(300), rand_vector(100), rand_vector(200)];
+    let (commitment, prover) = f31_prover_builder(options.clone())
+        .commit_and_prove_batch(&data_list, 8)
+        .unwrap();
+    let (verifier, _) = F31DasVerifier::new(commitment, options).unwrap();
+
+    let positions = [2, 9, 17];
+    let proof = prover.open(&positions).unwrap();
+    let mut evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    evaluations[4] += F31Element::from(1u32);
+    assert!(matches!(
+        verifier.verify(&proof, &evaluations, &positions),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
+
+#[test]
+fn small_fields_match_the_density_of_the_128_bit_field() {
+    // 30 bits per 32-bit coordinate pack as many bytes as 15 bytes per 128-bit element
+    for data_size in [0, 100, 4096, 100_000] {
+        assert_eq!(
+            F31_ENCODING.element_count::<F31Element>(data_size),
+            encoded_data_element_count::<f128::BaseElement>(data_size)
+        );
+    }
+}
+
+#[test]
+fn small_fields_limit_the_security_level() {
+    let options = FriOptions::new(8, 4, 7);
+    let f31_level = field_security_level::<F31Element>(1 << 20, &options, 1);
+    let f128_level = field_security_level::<f128::BaseElement>(1 << 20, &options, 1);
+    assert!((96..110).contains(&f31_level), "{f31_level} bits");
+    // the extension has 2^124 elements
+    assert_eq!(f128_level, f31_level + 4);
+
+    assert!(
+        calculate_num_queries_in_field::<F31Element>(1 << 16, &options, 1, 96, &F31_ENCODING)
+            .is_ok()
+    );
+    assert!(matches!(
+        calculate_num_queries_in_field::<F31Element>(1 << 16, &options, 1, 128, &F31_ENCODING),
+        Err(FridaError::FieldTooSmall(128, _))
+    ));
+    assert_eq!(
+        calculate_num_queries_in_field::<f128::BaseElement>(
+            1 << 16,
+            &options,
+            1,
+            100,
+            &EncodingOptions::default()
+        ),
+        calculate_num_queries(1 << 16, &options, 1, 100)
+    );
+}
@@ -0,0 +1,76 @@
+use std::io::{self, Cursor};
+
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{prover::proof::FridaProof, utils::test_utils::*};
+
+fn test_proofs() -> Vec<FridaProof> {
+    let prover_builder = TestFridaProverBuilder::new(test_options());
+    let (commitment, _) = prover_builder
+        .commit_and_prove(&rand_vector::<u8>(4096), 16)
+        .unwrap();
+    let data_list = vec![rand_vector(200), rand_vector(50), rand_vector(70)];
+    let (batch_commitment, _) = prover_builder
+        .commit_and_prove_batch(&data_list, 8)
+        .unwrap();
+    vec![commitment.proof, batch_commitment.proof]
+}
+
+#[test]
+fn streamed_proofs_match_serialized_bytes() {
+    for proof in test_proofs() {
+        let mut bytes = Vec::new();
+        proof.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes, proof.to_bytes());
+
+        let read = FridaProof::read_from_reader(&mut bytes.as_slice()).unwrap();
+        assert_eq!(read, proof);
+    }
+}
+
+#[test]
+fn streamed_proofs_are_read_back_to_back() {
+    let proofs = test_proofs();
+    let mut stream = Vec::new();
+    for proof in proofs.iter() {
+        proof.write_to(&mut stream).unwrap();
+    }
+
+    let mut source = Cursor::new(stream);
+    for proof in proofs.iter() {
+        assert_eq!(&FridaProof::read_from_reader(&mut source).unwrap(), proof);
+    }
+    assert_eq!(source.position() as usize, source.get_ref().len());
+}
+
+#[test]
+fn streamed_proof_errors() {
+    let bytes = test_proofs()[0].to_bytes();
+
+    let truncated = FridaProof::read_from_reader(&mut &bytes[..bytes.len() - 1]).unwrap_err();
+    assert_eq!(truncated.kind(), io::ErrorKind::UnexpectedEof);
+
+    // a forged length is only trusted as far as the stream goes
+    let mut forged = vec![0, 1];
+    forged.extend_from_slice(&u32::MAX.to_le_bytes());
+    forged.extend_from_slice(&[1; 16]);
+    let forged = FridaProof::read_from_reader(&mut forged.as_slice()).unwrap_err();
+    assert_eq!(forged.kind(), io::ErrorKind::UnexpectedEof);
+
+    let empty_layer = [0, 1, 0, 0, 0, 0];
+    let empty_layer = FridaProof::read_from_reader(&mut empty_layer.as_slice()).unwrap_err();
+    assert_eq!(empty_layer.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn batch_layer_tag_is_strict() {
+    for proof in test_proofs() {
+        // the batch layer tag leads the proof, and is either 0 or 1
+        let mut bytes = proof.to_bytes();
+        bytes[0] = 2;
+        assert!(FridaProof::read_from_bytes(&bytes).is_err());
+        let err = FridaProof::read_from_reader(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
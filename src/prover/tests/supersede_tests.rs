@@ -0,0 +1,126 @@
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::supersede::{CanonicalCommitments, SupersedeRecord},
+    utils::test_utils::*,
+};
+
+#[test]
+fn supersede_record_verification() {
+    let key = TestKey(0);
+    let old = test_commitment(&rand_vector::<u8>(200));
+    let new = test_commitment(&rand_vector::<u8>(200));
+
+    let record = SupersedeRecord::new(7, 1, &old, &new, &key);
+    assert!(record.supersedes(&old));
+    assert!(!record.supersedes(&new));
+    assert_eq!(record.verify(&key), Ok(()));
+    assert_eq!(
+        record.verify(&TestKey(1)),
+        Err(FridaError::InvalidSupersession)
+    );
+
+    let decoded = SupersedeRecord::<Blake3>::read_from_bytes(&record.to_bytes()).unwrap();
+    assert_eq!(decoded, record);
+
+    // the signature covers the block, the epoch and both commitments
+    let mut moved = record.clone();
+    moved.block = 8;
+    assert_eq!(moved.verify(&key), Err(FridaError::InvalidSupersession));
+    let mut replayed = record.clone();
+    replayed.epoch = 2;
+    assert_eq!(replayed.verify(&key), Err(FridaError::InvalidSupersession));
+    let mut swapped = record;
+    std::mem::swap(&mut swapped.old, &mut swapped.new);
+    assert_eq!(swapped.verify(&key), Err(FridaError::InvalidSupersession));
+
+    let noop = SupersedeRecord::new(7, 1, &old, &old, &key);
+    assert_eq!(noop.verify(&key), Err(FridaError::InvalidSupersession));
+}
+
+#[test]
+fn canonical_commitments_follow_supersessions() {
+    let key = TestKey(0);
+    let first = test_commitment(&rand_vector::<u8>(200));
+    let second = test_commitment(&rand_vector::<u8>(200));
+    let third = test_commitment(&rand_vector::<u8>(200));
+
+    let mut canonical = CanonicalCommitments::<Blake3>::new();
+    canonical.register(1, &first);
+    assert!(canonical.is_canonical(1, &first));
+
+    // records for unknown blocks are rejected
+    assert_eq!(
+        canonical.apply(SupersedeRecord::new(2, 1, &first, &second, &key), &key),
+        Err(FridaError::StaleSupersession)
+    );
+
+    assert_eq!(canonical.next_epoch(1), 1);
+    canonical
+        .apply(SupersedeRecord::new(1, 1, &first, &second, &key), &key)
+        .unwrap();
+    assert_eq!(canonical.next_epoch(1), 2);
+    assert!(canonical.is_canonical(1, &second));
+    assert!(!canonical.is_canonical(1, &first));
+
+    // the original commitment cannot be superseded a second time
+    assert_eq!(
+        canonical.apply(SupersedeRecord::new(1, 2, &first, &third, &key), &key),
+        Err(FridaError::StaleSupersession)
+    );
+    assert_eq!(
+        canonical.apply(
+            SupersedeRecord::new(1, 2, &second, &third, &TestKey(2)),
+            &key
+        ),
+        Err(FridaError::InvalidSupersession)
+    );
+
+    // records must carry the next epoch of their block
+    assert_eq!(
+        canonical.apply(SupersedeRecord::new(1, 3, &second, &third, &key), &key),
+        Err(FridaError::StaleSupersession)
+    );
+    canonical
+        .apply(SupersedeRecord::new(1, 2, &second, &third, &key), &key)
+        .unwrap();
+    assert_eq!(canonical.canonical(1), Some(third.binding()));
+    let history = canonical.history(1);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].old, first.binding());
+    assert_eq!(history[1].new, third.binding());
+    assert!(canonical.history(2).is_empty());
+}
+
+#[test]
+fn supersede_records_cannot_be_replayed() {
+    let key = TestKey(0);
+    let first = test_commitment(&rand_vector::<u8>(200));
+    let second = test_commitment(&rand_vector::<u8>(200));
+
+    let mut canonical = CanonicalCommitments::<Blake3>::new();
+    canonical.register(1, &first);
+    let forward = SupersedeRecord::new(1, 1, &first, &second, &key);
+    canonical.apply(forward.clone(), &key).unwrap();
+    canonical
+        .apply(SupersedeRecord::new(1, 2, &second, &first, &key), &key)
+        .unwrap();
+    assert!(canonical.is_canonical(1, &first));
+
+    // the first record replaces the canonical commitment again, but its epoch has passed
+    assert_eq!(
+        canonical.apply(forward.clone(), &key),
+        Err(FridaError::StaleSupersession)
+    );
+
+    // registering the block again keeps its epoch
+    canonical.register(1, &first);
+    assert_eq!(canonical.next_epoch(1), 3);
+    assert_eq!(
+        canonical.apply(forward, &key),
+        Err(FridaError::StaleSupersession)
+    );
+    assert!(canonical.is_canonical(1, &first));
+}
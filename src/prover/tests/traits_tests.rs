@@ -0,0 +1,110 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    prover::{FridaProvable, Protocol, PruneLevel},
+    utils::test_utils::*,
+    verifier::FridaVerifier,
+};
+
+/// Serves the openings of every validator through the trait alone, as generic subsystems do.
+fn serve<P: FridaProvable<BaseElement>>(
+    prover: &P,
+    assignments: &[Vec<usize>],
+) -> Vec<(P::Proof, Vec<BaseElement>)> {
+    assignments
+        .iter()
+        .map(|positions| {
+            (
+                prover.open(positions).unwrap(),
+                prover.get_evaluations(positions).unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// Prover of a blob held in the clear, whose proofs are the opened positions themselves.
+struct MockProver(Vec<BaseElement>);
+
+impl FridaProvable<BaseElement> for MockProver {
+    type Proof = Vec<usize>;
+
+    fn open(&self, positions: &[usize]) -> Result<Vec<usize>, FridaError> {
+        Ok(positions.to_vec())
+    }
+
+    fn get_evaluations(&self, positions: &[usize]) -> Result<Vec<BaseElement>, FridaError> {
+        Ok(positions.iter().map(|&p| self.0[p]).collect())
+    }
+
+    fn domain_size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn poly_count(&self) -> usize {
+        1
+    }
+
+    fn first_layer(&self) -> Result<&[BaseElement], FridaError> {
+        Ok(&self.0)
+    }
+}
+
+#[test]
+fn test_generic_code_with_mock_prover() {
+    let prover = MockProver(rand_vector(16));
+    let served = serve(&prover, &[vec![1, 4], vec![15]]);
+    assert_eq!(served[0], (vec![1, 4], vec![prover.0[1], prover.0[4]]));
+    assert_eq!(served[1], (vec![15], vec![prover.0[15]]));
+}
+
+#[test]
+fn test_frida_prover_through_trait() {
+    let options = test_options();
+    let data_list = (0..4).map(|_| rand_vector::<u8>(64)).collect::<Vec<_>>();
+    let (commitment, mut prover, _) = TestFridaProverBuilder::new(options.clone())
+        .commitment_batch(&data_list, 4)
+        .unwrap();
+    assert_eq!(FridaProvable::domain_size(&prover), commitment.domain_size);
+    assert_eq!(FridaProvable::poly_count(&prover), 4);
+    #[allow(deprecated)]
+    let raw_first_layer = prover.get_first_layer_evaluations().unwrap();
+    assert_eq!(prover.first_layer().unwrap(), raw_first_layer);
+    assert_eq!(
+        prover.first_layer().unwrap().len(),
+        4 * commitment.domain_size
+    );
+
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let assignments = [vec![0, 9], vec![3]];
+    for ((proof, evaluations), positions) in serve(&prover, &assignments).iter().zip(&assignments) {
+        FridaVerifier::verify(&verifier, proof, evaluations, positions).unwrap();
+    }
+
+    prover.prune(PruneLevel::Commitment).unwrap();
+    assert_eq!(
+        prover.first_layer().err(),
+        Some(FridaError::FirstLayerUnavailable)
+    );
+    #[allow(deprecated)]
+    let raw_first_layer = prover.get_first_layer_evaluations();
+    assert_eq!(raw_first_layer, Err(FridaError::FirstLayerUnavailable));
+}
+
+#[test]
+fn test_stir_prover_through_trait() {
+    let options = FriOptions::new(2, 8, 7);
+    let (commitment, prover) = TestFridaProverBuilder::new(options)
+        .with_protocol(Protocol::Stir)
+        .commit_and_prove_stir(&rand_vector::<u8>(30_000), 16)
+        .unwrap();
+    assert_eq!(FridaProvable::domain_size(&prover), commitment.domain_size);
+    assert_eq!(FridaProvable::poly_count(&prover), 1);
+    assert_eq!(prover.first_layer().unwrap().len(), commitment.domain_size);
+
+    let served = serve(&prover, &[vec![2, 100]]);
+    assert_eq!(served[0].0, prover.open(&[2, 100]).unwrap());
+    assert_eq!(served[0].1, prover.get_evaluations(&[2, 100]).unwrap());
+}
@@ -0,0 +1,114 @@
+use winter_crypto::Hasher;
+use winter_fri::{FriOptions, ProverChannel};
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    core::transcript::{Transcript, TranscriptEntry},
+    error::FridaError,
+    utils::test_utils::*,
+};
+
+type TestTranscript = Transcript<BaseElement, <Blake3 as Hasher>::Digest>;
+
+#[test]
+fn transcript_follows_commitment() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(200);
+    let (commitment, _, transcript) = builder.commit_and_prove_with_transcript(&data, 8).unwrap();
+    let (plain_commitment, _) = builder.commit_and_prove(&data, 8).unwrap();
+    assert_eq!(commitment, plain_commitment);
+
+    let roots = transcript
+        .entries
+        .iter()
+        .filter_map(|entry| match entry {
+            TranscriptEntry::Commitment(root) => Some(*root),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(roots, commitment.roots);
+
+    // every layer root is followed by its folding challenge, and the remainder by the positions
+    for pair in transcript.entries[..transcript.entries.len() - 2].chunks(2) {
+        assert!(matches!(pair[0], TranscriptEntry::Commitment(_)));
+        assert!(matches!(pair[1], TranscriptEntry::Alpha(_)));
+    }
+    assert!(matches!(
+        transcript.entries[transcript.entries.len() - 1],
+        TranscriptEntry::Positions { positions: ref p, .. } if p.len() == 8
+    ));
+
+    transcript.verify::<Blake3, Blake3>().unwrap();
+}
+
+#[test]
+fn transcript_records_ood_sample_and_xi() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_ood_check(true);
+    let data = rand_vector::<u8>(200);
+    let (_, _, transcript) = builder.commit_and_prove_with_transcript(&data, 8).unwrap();
+    assert!(matches!(
+        transcript.entries[1],
+        TranscriptEntry::OodSample { .. }
+    ));
+    transcript.verify::<Blake3, Blake3>().unwrap();
+
+    let mut channel = TestFridaProverChannel::new(32, 4);
+    channel.commit_fri_layer(Blake3::hash(b"root"));
+    let xi = channel.draw_xi(3).unwrap();
+    channel.draw_fri_alpha();
+    assert_eq!(channel.transcript().entries[1], TranscriptEntry::Xi(xi));
+    channel.transcript().verify::<Blake3, Blake3>().unwrap();
+}
+
+#[test]
+fn manipulated_challenges_are_detected() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let data = rand_vector::<u8>(200);
+    let (_, _, transcript) = builder.commit_and_prove_with_transcript(&data, 8).unwrap();
+
+    let mut tampered = transcript.clone();
+    let TranscriptEntry::Alpha(alpha) = &mut tampered.entries[1] else {
+        panic!("expected the first folding challenge");
+    };
+    *alpha += BaseElement::from(1u64);
+    assert_eq!(
+        tampered.verify::<Blake3, Blake3>(),
+        Err(FridaError::TranscriptMismatch(1))
+    );
+
+    let mut tampered = transcript.clone();
+    let last = tampered.entries.len() - 1;
+    let TranscriptEntry::Positions { positions, .. } = &mut tampered.entries[last] else {
+        panic!("expected the query positions");
+    };
+    positions[0] ^= 1;
+    assert_eq!(
+        tampered.verify::<Blake3, Blake3>(),
+        Err(FridaError::TranscriptMismatch(last))
+    );
+
+    // changing a commitment changes every challenge derived after it
+    let mut tampered = transcript;
+    tampered.entries[0] = TranscriptEntry::Commitment(Blake3::hash(b"other root"));
+    assert_eq!(
+        tampered.verify::<Blake3, Blake3>(),
+        Err(FridaError::TranscriptMismatch(1))
+    );
+}
+
+#[test]
+fn transcript_serialization_roundtrip() {
+    let builder = TestFridaProverBuilder::new(FriOptions::new(2, 4, 1)).with_ood_check(true);
+    let (_, _, transcript) = builder
+        .commit_and_prove_with_transcript(&rand_vector::<u8>(300), 16)
+        .unwrap();
+
+    let bytes = transcript.to_bytes();
+    let decoded = TestTranscript::read_from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, transcript);
+    decoded.verify::<Blake3, Blake3>().unwrap();
+
+    assert!(TestTranscript::read_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
@@ -0,0 +1,58 @@
+use winter_fri::{FriOptions, FriProver};
+use winter_rand_utils::rand_vector;
+use winter_utils::Serializable;
+
+use crate::{error::FridaError, prover::proof::FridaProof, utils::test_utils::*};
+
+#[test]
+fn proofs_convert_to_and_from_winter_fri() {
+    let options = test_options();
+    let (commitment, _) = TestFridaProverBuilder::new(options)
+        .with_num_partitions(2)
+        .commit_and_prove(&rand_vector::<u8>(4096), 16)
+        .unwrap();
+
+    let fri_proof = commitment.proof.to_fri_proof().unwrap();
+    assert_eq!(fri_proof.to_bytes(), commitment.proof.to_bytes()[1..]);
+    assert_eq!(fri_proof.num_partitions(), 2);
+    assert_eq!(FridaProof::from_fri_proof(&fri_proof), commitment.proof);
+}
+
+#[test]
+fn winter_fri_proofs_are_imported() {
+    let options = FriOptions::new(4, 4, 3);
+    let evaluations = test_build_evaluations(64, 4);
+    let mut reference_channel = test_build_prover_channel(64, &options);
+    let mut reference_prover = FriProver::new(options.clone());
+    reference_prover.build_layers(&mut reference_channel, evaluations.clone());
+
+    let mut frida_channel = test_build_prover_channel(64, &options);
+    let frida_prover = TestFridaProverBuilder::new(options)
+        .test_build_layers(&mut frida_channel, evaluations)
+        .unwrap();
+
+    let positions = frida_channel.draw_query_positions();
+    let imported = FridaProof::from_fri_proof(&reference_prover.build_proof(&positions));
+    assert_eq!(imported, frida_prover.open(&positions).unwrap());
+}
+
+#[test]
+fn batched_proofs_only_convert_lossily() {
+    let data_list = vec![rand_vector(200), rand_vector(50), rand_vector(70)];
+    let (commitment, _) = TestFridaProverBuilder::new(test_options())
+        .commit_and_prove_batch(&data_list, 8)
+        .unwrap();
+    assert!(matches!(
+        commitment.proof.to_fri_proof(),
+        Err(FridaError::BatchLayerNotConvertible)
+    ));
+
+    let fri_proof = commitment.proof.to_fri_proof_lossy();
+    assert_eq!(fri_proof.num_layers(), commitment.proof.num_layers());
+    let imported = FridaProof::from_fri_proof(&fri_proof);
+    assert!(!imported.has_batch_layer());
+    assert!(commitment
+        .proof
+        .to_bytes()
+        .ends_with(&imported.to_bytes()[1..]));
+}
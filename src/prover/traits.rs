@@ -0,0 +1,97 @@
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+
+use super::{
+    proof::{FridaProof, FridaProofLayer},
+    stir::StirProver,
+    FridaProver,
+};
+use crate::error::FridaError;
+
+// FRIDA PROVABLE
+// ================================================================================================
+
+/// Prover of a committed blob, or batch of blobs, whatever the protocol it was committed with.
+///
+/// Subsystems serving openings, e.g. pipelines, services or stores, can be written against this
+/// trait, and tested with mock provers.
+pub trait FridaProvable<E: FieldElement> {
+    /// Proof opening positions of the commitment, checked by the matching `FridaVerifier`.
+    type Proof;
+
+    /// Builds the proof opening the commitment at `positions`, or fails if they cannot be opened.
+    fn open(&self, positions: &[usize]) -> Result<Self::Proof, FridaError>;
+
+    /// Returns the evaluations of every committed polynomial at `positions`, in the order expected
+    /// by the verifier, or fails if they cannot be opened.
+    fn get_evaluations(&self, positions: &[usize]) -> Result<Vec<E>, FridaError>;
+
+    /// Size of the evaluation domain of the commitment.
+    fn domain_size(&self) -> usize;
+
+    /// Number of polynomials committed to; 1 unless the commitment is batched.
+    fn poly_count(&self) -> usize;
+
+    /// Evaluations of the first committed layer, i.e. of the encoded blobs, interleaved as the
+    /// layer is committed, or an error if the prover no longer holds them.
+    fn first_layer(&self) -> Result<&[E], FridaError>;
+}
+
+impl<E, H> FridaProvable<E> for FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type Proof = FridaProof;
+
+    fn open(&self, positions: &[usize]) -> Result<FridaProof, FridaError> {
+        FridaProver::open(self, positions)
+    }
+
+    fn get_evaluations(&self, positions: &[usize]) -> Result<Vec<E>, FridaError> {
+        FridaProver::get_evaluations(self, positions)
+    }
+
+    fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    fn poly_count(&self) -> usize {
+        self.poly_count
+    }
+
+    /// # Errors
+    /// Returns `FridaError::FirstLayerUnavailable` if the prover was pruned, or committed without
+    /// any FRI layer.
+    fn first_layer(&self) -> Result<&[E], FridaError> {
+        self.first_layer_evaluations()
+    }
+}
+
+impl<E, H> FridaProvable<E> for StirProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type Proof = FridaProofLayer;
+
+    fn open(&self, positions: &[usize]) -> Result<FridaProofLayer, FridaError> {
+        StirProver::open(self, positions)
+    }
+
+    fn get_evaluations(&self, positions: &[usize]) -> Result<Vec<E>, FridaError> {
+        StirProver::get_evaluations(self, positions)
+    }
+
+    fn domain_size(&self) -> usize {
+        self.domain_size()
+    }
+
+    fn poly_count(&self) -> usize {
+        1
+    }
+
+    fn first_layer(&self) -> Result<&[E], FridaError> {
+        Ok(self.evaluations())
+    }
+}
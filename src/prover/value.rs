@@ -0,0 +1,121 @@
+use std::ops::Range;
+
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{proof::FridaProof, FridaProver};
+use crate::{core::data::value_byte_range, error::FridaError};
+
+// VALUE OPENING
+// ================================================================================================
+
+/// An opening of data element `index` of a single-blob commitment, the evaluation at position
+/// `index * blowup_factor`, which holds bytes `range` of the data.
+///
+/// Along with the evaluation at position 0, which holds the length of the data, the opening binds
+/// `value` to the bytes of the data it encodes, so that a commitment can be used as a vector
+/// commitment to the data bytes, with one entry per data element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueOpening<E: FieldElement> {
+    pub index: usize,
+    pub range: Range<usize>,
+    pub value: E,
+    /// Evaluation at position 0, equal to `value` for element 0.
+    pub length_prefix: E,
+    /// Opening of the positions returned by `positions`.
+    pub proof: FridaProof,
+}
+
+impl<E: FieldElement> ValueOpening<E> {
+    /// Returns the sorted positions opened for data element `index` of data committed with
+    /// `blowup_factor`.
+    pub fn positions(index: usize, blowup_factor: usize) -> Vec<usize> {
+        if index == 0 {
+            vec![0]
+        } else {
+            vec![0, index.saturating_mul(blowup_factor)]
+        }
+    }
+
+    /// Returns the evaluations at `positions`, one per position.
+    pub(crate) fn evaluations(&self) -> Vec<E> {
+        if self.index == 0 {
+            vec![self.length_prefix]
+        } else {
+            vec![self.length_prefix, self.value]
+        }
+    }
+}
+
+impl<E, H> FridaProver<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens data element `index` of the blob of a single-blob commitment, along with the range
+    /// of the data bytes it holds. `blowup_factor` must be the one the data was committed with,
+    /// using the default `EncodingOptions`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ProofPolyCountMismatch` if the commitment is batched, and
+    /// `FridaError::ValueOutOfBounds` if the element holds none of the data.
+    pub fn open_value(
+        &self,
+        index: usize,
+        blowup_factor: usize,
+    ) -> Result<ValueOpening<E>, FridaError> {
+        if self.poly_count != 1 {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+
+        let length_prefix = self.get_evaluations(&[0])?[0];
+        let range = value_byte_range(&length_prefix, index)?;
+        let positions = ValueOpening::<E>::positions(index, blowup_factor);
+        let value = *self
+            .get_evaluations(&positions)?
+            .last()
+            .expect("position of the value is opened");
+
+        Ok(ValueOpening {
+            index,
+            range,
+            value,
+            length_prefix,
+            proof: self.open(&positions)?,
+        })
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement> Serializable for ValueOpening<E> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.index.write_into(target);
+        self.range.start.write_into(target);
+        self.range.end.write_into(target);
+        self.value.write_into(target);
+        self.length_prefix.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement> Deserializable for ValueOpening<E> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let index = usize::read_from(source)?;
+        let start = usize::read_from(source)?;
+        let end = usize::read_from(source)?;
+        let value = E::read_from(source)?;
+        let length_prefix = E::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(ValueOpening {
+            index,
+            range: start..end,
+            value,
+            length_prefix,
+            proof,
+        })
+    }
+}
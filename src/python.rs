@@ -0,0 +1,253 @@
+//! Python bindings, exposed as the `frida_poc` module when built with the `python` feature, e.g.
+//! through `maturin develop --features python`.
+//!
+//! Data and proofs cross the boundary as `bytes`, evaluations as lists of `int`. Every function
+//! works over the f128 field with Blake3 hashing, like the CLI.
+//!
+//! Errors of the library are raised as `frida_poc.FridaError`, and malformed inputs, e.g. bytes
+//! which are not a commitment, as `ValueError`, of which `FridaError` is a subclass.
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*, types::PyBytes};
+use winter_utils::{Deserializable, Serializable, SliceReader};
+
+use crate::{
+    constants::MAX_DOMAIN_SIZE,
+    core::{
+        data::{
+            build_evaluations_from_data, encoded_data_element_count, recover_data_from_evaluations,
+        },
+        queries,
+        schedule::fri_options,
+    },
+    error,
+    prover::{builder::FridaProverBuilder, proof::FridaProof, Commitment, FridaProver},
+    verifier::das::FridaDasVerifier,
+    winterfell::{f128::BaseElement, Blake3_256, StarkField},
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+create_exception!(
+    frida_poc,
+    FridaError,
+    PyValueError,
+    "Raised when committing, opening, verifying or decoding fails."
+);
+
+impl From<error::FridaError> for PyErr {
+    fn from(err: error::FridaError) -> PyErr {
+        FridaError::new_err(err.to_string())
+    }
+}
+
+fn value_error(err: impl ToString) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// Checks that data can be encoded over, or decoded from, a domain of `domain_size`.
+fn check_domain(domain_size: usize, blowup_factor: usize) -> PyResult<()> {
+    if blowup_factor < 2 || !blowup_factor.is_power_of_two() {
+        return Err(error::FridaError::InvalidBlowupFactor.into());
+    }
+    if !domain_size.is_power_of_two() || domain_size <= blowup_factor {
+        return Err(value_error(format!(
+            "domain size must be a power of two above the blowup factor, but was {domain_size}"
+        )));
+    }
+    if domain_size > MAX_DOMAIN_SIZE {
+        return Err(error::FridaError::DomainSizeTooBig(domain_size).into());
+    }
+    Ok(())
+}
+
+fn to_elements(values: Vec<u128>) -> PyResult<Vec<BaseElement>> {
+    values
+        .into_iter()
+        .map(|value| {
+            if value >= BaseElement::MODULUS {
+                return Err(value_error(format!("{value} is not a field element")));
+            }
+            Ok(BaseElement::new(value))
+        })
+        .collect()
+}
+
+fn to_ints(elements: &[BaseElement]) -> Vec<u128> {
+    elements.iter().map(|e| e.as_int()).collect()
+}
+
+// PROVER
+// ================================================================================================
+
+/// Prover holding the layers of a commitment, from which any of its positions can be opened.
+#[pyclass(name = "Prover")]
+struct PyProver(FridaProver<BaseElement, Blake3>);
+
+#[pymethods]
+impl PyProver {
+    /// Opens `positions`, returning the serialized proof. Positions outside of the domain raise
+    /// `FridaError`.
+    fn open<'py>(&self, py: Python<'py>, positions: Vec<usize>) -> PyResult<Bound<'py, PyBytes>> {
+        let proof = self.0.open(&positions)?;
+        Ok(PyBytes::new_bound(py, &proof.to_bytes()))
+    }
+
+    /// Returns the evaluations of every committed polynomial at `positions`. Positions outside of
+    /// the domain raise `FridaError`.
+    fn evaluations(&self, positions: Vec<usize>) -> PyResult<Vec<u128>> {
+        let evaluations = self.0.get_evaluations(&positions)?;
+        Ok(to_ints(&evaluations))
+    }
+
+    /// Returns the size of the evaluation domain.
+    fn domain_size(&self) -> usize {
+        self.0.commitment().domain_size
+    }
+
+    /// Returns a snapshot of the prover, which can be restored with `Prover.from_bytes`.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new_bound(py, &self.0.to_bytes())
+    }
+
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        FridaProver::read_from_bytes(bytes)
+            .map(PyProver)
+            .map_err(value_error)
+    }
+}
+
+// FUNCTIONS
+// ================================================================================================
+
+/// Commits to `data`, returning the serialized commitment, proof of `num_queries` positions
+/// included, and the prover.
+#[pyfunction]
+#[pyo3(signature = (data, num_queries, blowup_factor=8, folding_factor=2, max_remainder_degree=7))]
+fn commit<'py>(
+    py: Python<'py>,
+    data: &[u8],
+    num_queries: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+) -> PyResult<(Bound<'py, PyBytes>, PyProver)> {
+    let options = fri_options(blowup_factor, folding_factor, max_remainder_degree)?;
+    let (commitment, prover) = FridaProverBuilder::<BaseElement, Blake3>::new(options)
+        .commit_and_prove(data, num_queries)?;
+    Ok((
+        PyBytes::new_bound(py, &commitment.to_bytes()),
+        PyProver(prover),
+    ))
+}
+
+/// Verifies that `evaluations` at `positions` are opened by `proof` against `commitment`.
+///
+/// Returns `False` if verification fails, and raises `ValueError` if any of the inputs is
+/// malformed.
+#[pyfunction]
+#[pyo3(signature = (
+    commitment,
+    proof,
+    evaluations,
+    positions,
+    blowup_factor=8,
+    folding_factor=2,
+    max_remainder_degree=7
+))]
+fn verify(
+    commitment: &[u8],
+    proof: &[u8],
+    evaluations: Vec<u128>,
+    positions: Vec<usize>,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+) -> PyResult<bool> {
+    let options = fri_options(blowup_factor, folding_factor, max_remainder_degree)?;
+    let commitment = Commitment::<Blake3>::read_from_bytes(commitment).map_err(value_error)?;
+    let evaluations = to_elements(evaluations)?;
+
+    let Ok((verifier, _)) =
+        FridaDasVerifier::<BaseElement, Blake3, Blake3>::new(commitment, options)
+    else {
+        return Ok(false);
+    };
+    let limits = verifier.proof_limits(positions.len());
+    let proof =
+        FridaProof::read_with_limits(&mut SliceReader::new(proof), &limits).map_err(value_error)?;
+    Ok(verifier.verify(&proof, &evaluations, &positions).is_ok())
+}
+
+/// Returns the number of queries needed to reach `lambda_security` bits of security.
+#[pyfunction]
+#[pyo3(signature = (
+    data_size,
+    batch_size=1,
+    lambda_security=128,
+    blowup_factor=8,
+    folding_factor=2,
+    max_remainder_degree=7
+))]
+fn calculate_num_queries(
+    data_size: usize,
+    batch_size: usize,
+    lambda_security: u32,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+) -> PyResult<usize> {
+    let options = fri_options(blowup_factor, folding_factor, max_remainder_degree)?;
+    Ok(queries::calculate_num_queries(
+        data_size,
+        &options,
+        batch_size,
+        lambda_security,
+    )?)
+}
+
+/// Reed-Solomon encodes `data` into evaluations over a domain of `domain_size`.
+#[pyfunction]
+fn encode(data: &[u8], domain_size: usize, blowup_factor: usize) -> PyResult<Vec<u128>> {
+    check_domain(domain_size, blowup_factor)?;
+    if encoded_data_element_count::<BaseElement>(data.len()) > domain_size / blowup_factor {
+        return Err(value_error(format!(
+            "{} bytes cannot be encoded over a domain of size {domain_size}",
+            data.len()
+        )));
+    }
+    let evaluations = build_evaluations_from_data::<BaseElement>(data, domain_size, blowup_factor)?;
+    Ok(to_ints(&evaluations))
+}
+
+/// Decodes data from evaluations at `positions`, at least `domain_size / blowup_factor` of them.
+#[pyfunction]
+fn decode<'py>(
+    py: Python<'py>,
+    evaluations: Vec<u128>,
+    positions: Vec<usize>,
+    domain_size: usize,
+    blowup_factor: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    check_domain(domain_size, blowup_factor)?;
+    if let Some(position) = positions.iter().find(|&&p| p >= domain_size) {
+        return Err(value_error(format!(
+            "position {position} is outside of the domain of size {domain_size}"
+        )));
+    }
+    let evaluations = to_elements(evaluations)?;
+    let data = recover_data_from_evaluations(&evaluations, &positions, domain_size, blowup_factor)?;
+    Ok(PyBytes::new_bound(py, &data))
+}
+
+#[pymodule]
+fn frida_poc(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("FridaError", module.py().get_type_bound::<FridaError>())?;
+    module.add_class::<PyProver>()?;
+    module.add_function(wrap_pyfunction!(commit, module)?)?;
+    module.add_function(wrap_pyfunction!(verify, module)?)?;
+    module.add_function(wrap_pyfunction!(calculate_num_queries, module)?)?;
+    module.add_function(wrap_pyfunction!(encode, module)?)?;
+    module.add_function(wrap_pyfunction!(decode, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,544 @@
+//! Byte-level specification of serialized commitments and proofs.
+//!
+//! `WireSpec` describes, for a given set of FRI options and commitment shape, every field written
+//! by the `Serializable` implementations of `Commitment` and `FridaProof`, so that third-party
+//! encoders can be written from it and checked against it with `WireSpec::check_conformance`.
+use std::fmt::{self, Write};
+
+use winter_crypto::Hasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_utils::{Deserializable, Serializable, SliceReader};
+
+use crate::{
+    core::schedule::layer_folding_factors, error::FridaError, prover::scheduler::CommitProfile,
+};
+
+// FIELD SPECIFICATION
+// ================================================================================================
+
+/// How the bytes of a field are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    U8,
+    /// Little-endian `u16`.
+    U16,
+    /// Little-endian `u32`.
+    U32,
+    /// `usize` as written by winter-utils, i.e. a vint64 of 1 to 9 bytes.
+    VarUint,
+    /// `size` bytes for every unit counted by the previous field.
+    Repeated(usize),
+}
+
+/// Constraint on the value of an integer field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constraint {
+    Any,
+    Equals(u64),
+    OneOf(Vec<u64>),
+    /// A positive multiple of the given value.
+    PositiveMultipleOf(u64),
+    /// Between the given bounds, both included.
+    Between(u64, u64),
+}
+
+impl Constraint {
+    fn accepts(&self, value: u64) -> bool {
+        match self {
+            Constraint::Any => true,
+            Constraint::Equals(expected) => value == *expected,
+            Constraint::OneOf(values) => values.contains(&value),
+            Constraint::PositiveMultipleOf(unit) => value > 0 && value % unit == 0,
+            Constraint::Between(min, max) => (*min..=*max).contains(&value),
+        }
+    }
+}
+
+/// A field of a serialized structure, in the order it is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpec {
+    pub name: String,
+    pub encoding: Encoding,
+    pub constraint: Constraint,
+}
+
+impl FieldSpec {
+    fn new(name: impl Into<String>, encoding: Encoding, constraint: Constraint) -> Self {
+        FieldSpec {
+            name: name.into(),
+            encoding,
+            constraint,
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::U8 => write!(f, "u8"),
+            Encoding::U16 => write!(f, "u16 le"),
+            Encoding::U32 => write!(f, "u32 le"),
+            Encoding::VarUint => write!(f, "vint64"),
+            Encoding::Repeated(size) => write!(f, "{size} bytes per unit of the previous field"),
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Any => write!(f, "-"),
+            Constraint::Equals(value) => write!(f, "= {value}"),
+            Constraint::OneOf(values) => write!(f, "one of {values:?}"),
+            Constraint::PositiveMultipleOf(unit) => write!(f, "positive multiple of {unit}"),
+            Constraint::Between(min, max) => write!(f, "in {min}..={max}"),
+        }
+    }
+}
+
+// WIRE SPECIFICATION
+// ================================================================================================
+
+/// Layout of the commitments and proofs of a commitment shape: a domain, a number of blobs and
+/// the FRI options they were committed with, for elements of `E` hashed with `H`.
+#[derive(Debug, Clone)]
+pub struct WireSpec {
+    element_bytes: usize,
+    digest_bytes: usize,
+    domain_size: usize,
+    poly_count: usize,
+    max_num_queries: usize,
+    folding_factors: Vec<usize>,
+    remainder_len: usize,
+    /// Chunk size of the data roots of the commitment, if it carries any.
+    data_roots: Option<usize>,
+    /// Size of the commit profile recording the options, if the commitment carries one.
+    profile_bytes: usize,
+}
+
+impl WireSpec {
+    /// Specifies the commitments of `poly_count` polynomials over a domain of `domain_size`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidWireSpec` if the domain is empty, or if several polynomials
+    /// would be committed without any FRI layer to batch them in.
+    pub fn new<E: FieldElement, H: Hasher>(
+        options: &FriOptions,
+        domain_size: usize,
+        poly_count: usize,
+    ) -> Result<Self, FridaError> {
+        Self::new_with_schedule::<E, H>(options, &[], domain_size, poly_count)
+    }
+
+    /// Same as `new`, for commitments whose layers are folded by `schedule`, as set with
+    /// `FridaProverBuilder::with_folding_schedule`.
+    pub fn new_with_schedule<E: FieldElement, H: Hasher>(
+        options: &FriOptions,
+        schedule: &[usize],
+        domain_size: usize,
+        poly_count: usize,
+    ) -> Result<Self, FridaError> {
+        let invalid = FridaError::InvalidWireSpec(domain_size, poly_count);
+        let max_num_queries = domain_size.checked_sub(1).ok_or(invalid.clone())?;
+        let folding_factors = layer_folding_factors(options, schedule, domain_size);
+        if poly_count > 1 && folding_factors.is_empty() {
+            return Err(invalid);
+        }
+        let remainder_len =
+            domain_size / options.blowup_factor() / folding_factors.iter().product::<usize>();
+        let profile = CommitProfile {
+            blowup_factor: options.blowup_factor(),
+            folding_factor: options.folding_factor(),
+            remainder_max_degree: options.remainder_max_degree(),
+        };
+        Ok(WireSpec {
+            element_bytes: E::ELEMENT_BYTES,
+            digest_bytes: H::Digest::default().to_bytes().len(),
+            domain_size,
+            poly_count,
+            max_num_queries,
+            folding_factors,
+            remainder_len: remainder_len.max(1),
+            data_roots: None,
+            profile_bytes: profile.to_bytes().len(),
+        })
+    }
+
+    /// Specifies commitments carrying data roots over chunks of `chunk_size` bytes, as built with
+    /// `FridaProverBuilder::with_data_roots`.
+    pub fn with_data_roots(mut self, chunk_size: usize) -> Self {
+        self.data_roots = Some(chunk_size);
+        self
+    }
+
+    /// Fields of a serialized `FridaProof` opening any number of positions.
+    pub fn proof_layout(&self) -> Vec<FieldSpec> {
+        let is_batch = self.poly_count > 1;
+        let mut fields = vec![FieldSpec::new(
+            "proof.has_batch_layer",
+            Encoding::U8,
+            Constraint::Equals(is_batch as u64),
+        )];
+        // batches are checked to have a batch layer when the spec is built
+        let layers = match self.folding_factors.split_first() {
+            Some((&folding_factor, layers)) if is_batch => {
+                let row_bytes = self.poly_count * folding_factor * self.element_bytes;
+                self.push_layer(&mut fields, "proof.batch_layer", row_bytes);
+                layers
+            }
+            _ => &self.folding_factors[..],
+        };
+        fields.push(FieldSpec::new(
+            "proof.num_layers",
+            Encoding::U8,
+            Constraint::Equals(layers.len() as u64),
+        ));
+        for (i, &folding_factor) in layers.iter().enumerate() {
+            let name = format!("proof.layers[{i}]");
+            self.push_layer(&mut fields, &name, folding_factor * self.element_bytes);
+        }
+        let min_rows = self.min_rows();
+        fields.extend([
+            FieldSpec::new(
+                "proof.remainder.len",
+                Encoding::U16,
+                Constraint::Equals((self.remainder_len * self.element_bytes) as u64),
+            ),
+            FieldSpec::new("proof.remainder", Encoding::Repeated(1), Constraint::Any),
+            // every partition holds at least one row of the smallest layer
+            FieldSpec::new(
+                "proof.num_partitions_log2",
+                Encoding::U8,
+                Constraint::Between(0, min_rows.ilog2() as u64),
+            ),
+        ]);
+        fields
+    }
+
+    /// Fields of a serialized `Commitment`, which embeds the proof of its own query positions.
+    pub fn commitment_layout(&self) -> Vec<FieldSpec> {
+        let mut fields = vec![
+            FieldSpec::new(
+                "roots.len",
+                Encoding::VarUint,
+                Constraint::Equals(self.folding_factors.len() as u64 + 1),
+            ),
+            FieldSpec::new(
+                "roots",
+                Encoding::Repeated(self.digest_bytes),
+                Constraint::Any,
+            ),
+        ];
+        fields.extend(self.proof_layout());
+        fields.extend([
+            FieldSpec::new(
+                "domain_size",
+                Encoding::VarUint,
+                Constraint::Equals(self.domain_size as u64),
+            ),
+            FieldSpec::new(
+                "num_queries",
+                Encoding::VarUint,
+                Constraint::Between(1, self.max_num_queries as u64),
+            ),
+            FieldSpec::new(
+                "poly_count",
+                Encoding::VarUint,
+                Constraint::Equals(self.poly_count as u64),
+            ),
+            FieldSpec::new(
+                "ood_evaluation.len",
+                Encoding::VarUint,
+                Constraint::OneOf(vec![0, self.element_bytes as u64]),
+            ),
+            FieldSpec::new("ood_evaluation", Encoding::Repeated(1), Constraint::Any),
+            FieldSpec::new(
+                "batch_leaf_encoding",
+                Encoding::U8,
+                match self.poly_count {
+                    1 => Constraint::Equals(0),
+                    _ => Constraint::OneOf(vec![0, 1]),
+                },
+            ),
+            FieldSpec::new(
+                "num_partitions",
+                Encoding::VarUint,
+                Constraint::OneOf(
+                    (0..=self.min_rows().ilog2())
+                        .map(|log2| 1 << log2)
+                        .collect(),
+                ),
+            ),
+        ]);
+        fields.extend(self.data_roots_layout());
+        fields.extend([
+            // a profile, if any, records the options of the commitment
+            FieldSpec::new("profile.tag", Encoding::U8, Constraint::OneOf(vec![0, 1])),
+            FieldSpec::new(
+                "profile",
+                Encoding::Repeated(self.profile_bytes),
+                Constraint::Any,
+            ),
+        ]);
+        fields
+    }
+
+    /// Fields of the data roots of a commitment, one per blob if the commitment carries any.
+    fn data_roots_layout(&self) -> Vec<FieldSpec> {
+        let Some(chunk_size) = self.data_roots else {
+            return vec![FieldSpec::new(
+                "data_roots.tag",
+                Encoding::U8,
+                Constraint::Equals(0),
+            )];
+        };
+        vec![
+            FieldSpec::new("data_roots.tag", Encoding::U8, Constraint::Equals(1)),
+            FieldSpec::new(
+                "data_roots.chunk_size",
+                Encoding::VarUint,
+                Constraint::Equals(chunk_size as u64),
+            ),
+            FieldSpec::new(
+                "data_roots.roots.len",
+                Encoding::VarUint,
+                Constraint::Equals(self.poly_count as u64),
+            ),
+            FieldSpec::new(
+                "data_roots.roots",
+                Encoding::Repeated(self.digest_bytes),
+                Constraint::Any,
+            ),
+        ]
+    }
+
+    /// Rows of the smallest layer, each partition of which holds at least one.
+    fn min_rows(&self) -> usize {
+        self.domain_size / self.folding_factors.iter().product::<usize>()
+    }
+
+    /// Appends the fields of a proof layer whose queried rows take `row_bytes` each. Merkle paths
+    /// are a batch Merkle proof as serialized by winter-crypto, and are not specified further.
+    fn push_layer(&self, fields: &mut Vec<FieldSpec>, name: &str, row_bytes: usize) {
+        fields.extend([
+            FieldSpec::new(
+                format!("{name}.values.len"),
+                Encoding::U32,
+                Constraint::PositiveMultipleOf(row_bytes as u64),
+            ),
+            FieldSpec::new(
+                format!("{name}.values"),
+                Encoding::Repeated(1),
+                Constraint::Any,
+            ),
+            FieldSpec::new(format!("{name}.paths.len"), Encoding::U32, Constraint::Any),
+            FieldSpec::new(
+                format!("{name}.paths"),
+                Encoding::Repeated(1),
+                Constraint::Any,
+            ),
+        ]);
+    }
+
+    /// Returns a plain-text table of the commitment layout, one field per line.
+    pub fn describe(&self) -> String {
+        let mut description = format!(
+            "Commitment over a domain of {} with {} polynomial(s), {}-byte field elements and \
+             {}-byte digests; layers folded by {:?}\n",
+            self.domain_size,
+            self.poly_count,
+            self.element_bytes,
+            self.digest_bytes,
+            self.folding_factors,
+        );
+        for field in self.commitment_layout() {
+            writeln!(
+                description,
+                "{:<32} {:<40} {}",
+                field.name, field.encoding, field.constraint
+            )
+            .expect("writing to a string cannot fail");
+        }
+        description
+    }
+
+    // CONFORMANCE
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that `bytes` are a serialized `Commitment` laid out as `commitment_layout`
+    /// describes, with no trailing bytes. Merkle paths, digests and field elements are not
+    /// checked beyond their sizes.
+    ///
+    /// # Errors
+    /// Returns `FridaError::NonConformingEncoding` with the first field which does not conform.
+    pub fn check_conformance(&self, bytes: &[u8]) -> Result<(), FridaError> {
+        check_layout(&self.commitment_layout(), bytes)
+    }
+
+    /// Same as `check_conformance`, for a serialized `FridaProof`.
+    pub fn check_proof_conformance(&self, bytes: &[u8]) -> Result<(), FridaError> {
+        check_layout(&self.proof_layout(), bytes)
+    }
+}
+
+/// Walks `fields` over `bytes`, checking the value of every integer field.
+fn check_layout(fields: &[FieldSpec], bytes: &[u8]) -> Result<(), FridaError> {
+    let mut offset = 0;
+    let mut previous = 0;
+    for field in fields {
+        let error = || FridaError::NonConformingEncoding(field.name.clone(), offset);
+        let remaining = &bytes[offset..];
+        let (value, size) = match field.encoding {
+            Encoding::U8 => (*remaining.first().ok_or_else(error)? as u64, 1),
+            Encoding::U16 => {
+                let value = remaining.get(..2).ok_or_else(error)?;
+                (u16::from_le_bytes(value.try_into().unwrap()) as u64, 2)
+            }
+            Encoding::U32 => {
+                let value = remaining.get(..4).ok_or_else(error)?;
+                (u32::from_le_bytes(value.try_into().unwrap()) as u64, 4)
+            }
+            Encoding::VarUint => {
+                let value =
+                    usize::read_from(&mut SliceReader::new(remaining)).map_err(|_| error())?;
+                (value as u64, value.to_bytes().len())
+            }
+            Encoding::Repeated(size) => {
+                let len = usize::try_from(previous)
+                    .ok()
+                    .and_then(|units| units.checked_mul(size))
+                    .filter(|&len| len <= remaining.len())
+                    .ok_or_else(error)?;
+                (previous, len)
+            }
+        };
+        if !field.constraint.accepts(value) {
+            return Err(error());
+        }
+        offset += size;
+        previous = value;
+    }
+    if offset != bytes.len() {
+        return Err(FridaError::NonConformingEncoding("end".to_string(), offset));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use winter_math::fields::f128::BaseElement;
+    use winter_rand_utils::rand_vector;
+
+    fn spec_of(options: &FriOptions, domain_size: usize, poly_count: usize) -> WireSpec {
+        WireSpec::new::<BaseElement, Blake3>(options, domain_size, poly_count).unwrap()
+    }
+
+    #[test]
+    fn test_commitments_conform() {
+        let options = FriOptions::new(2, 4, 3);
+        let builder = TestFridaProverBuilder::new(options.clone());
+
+        let (commitment, prover) = builder
+            .commit_and_prove(&rand_vector::<u8>(500), 4)
+            .unwrap();
+        let spec = spec_of(&options, commitment.domain_size, 1);
+        spec.check_conformance(&commitment.to_bytes()).unwrap();
+        let proof = prover.open(&[0, 3, 17]).unwrap();
+        spec.check_proof_conformance(&proof.to_bytes()).unwrap();
+
+        let (commitment, _) = TestFridaProverBuilder::new(options.clone())
+            .with_commit_profile(CommitProfile::new(2, 4, 3))
+            .commit_and_prove(&rand_vector::<u8>(500), 4)
+            .unwrap();
+        spec.check_conformance(&commitment.to_bytes()).unwrap();
+
+        let (commitment, _) = TestFridaProverBuilder::new(options.clone())
+            .with_data_roots(32)
+            .commit_and_prove(&rand_vector::<u8>(500), 4)
+            .unwrap();
+        assert!(spec.check_conformance(&commitment.to_bytes()).is_err());
+        spec.clone()
+            .with_data_roots(32)
+            .check_conformance(&commitment.to_bytes())
+            .unwrap();
+
+        let data_list = (0..3).map(|_| rand_vector::<u8>(200)).collect::<Vec<_>>();
+        let (commitment, _) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+        spec_of(&options, commitment.domain_size, 3)
+            .check_conformance(&commitment.to_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_nonconforming_bytes_are_located() {
+        let options = FriOptions::new(2, 4, 3);
+        let (commitment, _) = TestFridaProverBuilder::new(options.clone())
+            .commit_and_prove(&rand_vector::<u8>(500), 4)
+            .unwrap();
+        let bytes = commitment.to_bytes();
+
+        // another shape
+        assert!(matches!(
+            spec_of(&options, commitment.domain_size * 4, 1).check_conformance(&bytes),
+            Err(FridaError::NonConformingEncoding(field, _)) if field == "roots.len"
+        ));
+        assert!(matches!(
+            spec_of(&options, commitment.domain_size, 2).check_conformance(&bytes),
+            Err(FridaError::NonConformingEncoding(field, _)) if field == "proof.has_batch_layer"
+        ));
+
+        // truncated, or followed by trailing bytes
+        let spec = spec_of(&options, commitment.domain_size, 1);
+        assert!(spec.check_conformance(&bytes[..bytes.len() - 1]).is_err());
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert_eq!(
+            spec.check_conformance(&extended),
+            Err(FridaError::NonConformingEncoding(
+                "end".to_string(),
+                bytes.len()
+            ))
+        );
+
+        // a remainder of the wrong size
+        let mut proof = commitment.proof.clone();
+        proof
+            .test_remainder_mut()
+            .truncate(BaseElement::ELEMENT_BYTES);
+        let proof_bytes = proof.to_bytes();
+        assert!(matches!(
+            spec.check_proof_conformance(&proof_bytes),
+            Err(FridaError::NonConformingEncoding(field, _)) if field == "proof.remainder.len"
+        ));
+    }
+
+    #[test]
+    fn test_description_lists_every_field() {
+        let options = test_options();
+        let spec = spec_of(&options, 64, 4);
+        let description = spec.describe();
+        for field in spec.commitment_layout() {
+            assert!(description.contains(&field.name));
+        }
+        assert!(description.contains("proof.batch_layer.values.len"));
+        assert!(description.contains("proof.layers[0].values"));
+    }
+
+    #[test]
+    fn test_invalid_shapes_are_rejected() {
+        let options = test_options();
+        assert_eq!(
+            WireSpec::new::<BaseElement, Blake3>(&options, 0, 1).err(),
+            Some(FridaError::InvalidWireSpec(0, 1))
+        );
+        // a domain holding only the remainder has no layer to batch polynomials in
+        assert_eq!(
+            WireSpec::new::<BaseElement, Blake3>(&options, 4, 2).err(),
+            Some(FridaError::InvalidWireSpec(4, 2))
+        );
+        assert!(WireSpec::new::<BaseElement, Blake3>(&options, 4, 1).is_ok());
+    }
+}
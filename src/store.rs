@@ -0,0 +1,357 @@
+//! A simple on-disk store for commitments, prover snapshots and cached openings.
+//!
+//! Entries are content addressed: everything related to a commitment lives in a directory named
+//! after the hex encoding of its key, the `ProverCommitment::root_digest` of its roots and
+//! parameters. Cached openings are stored under the hash of their positions.
+//!
+//! ```text
+//! <root>/<key>/commitment.bin
+//! <root>/<key>/prover.bin
+//! <root>/<key>/openings/<hash of positions>.bin
+//! ```
+
+use std::{
+    fmt, fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use winter_crypto::{Digest, ElementHasher};
+use winter_math::FieldElement;
+use winter_utils::{Deserializable, DeserializationError, Serializable, SliceReader};
+
+use crate::{
+    prover::{proof::FridaProof, Commitment, FridaProver, ProverCommitment},
+    utils::encoding,
+};
+
+const COMMITMENT_FILE: &str = "commitment.bin";
+const PROVER_FILE: &str = "prover.bin";
+const OPENINGS_DIR: &str = "openings";
+
+// ERRORS
+// ================================================================================================
+
+#[derive(Debug)]
+pub enum StoreError {
+    IoError(io::Error),
+    DeserializationError(DeserializationError),
+    /// A stored entry does not hash to the key it is stored under.
+    KeyMismatch,
+    /// A key could not be parsed from its hex encoding.
+    InvalidKey(String),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::IoError(e) => write!(f, "IO error: {e}"),
+            StoreError::DeserializationError(e) => write!(f, "Deserialization error: {e}"),
+            StoreError::KeyMismatch => write!(f, "Stored entry does not match its key"),
+            StoreError::InvalidKey(key) => write!(f, "Invalid commitment key: {key}"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StoreError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StoreError {
+    fn from(err: io::Error) -> Self {
+        StoreError::IoError(err)
+    }
+}
+
+impl From<DeserializationError> for StoreError {
+    fn from(err: DeserializationError) -> Self {
+        StoreError::DeserializationError(err)
+    }
+}
+
+// STORE
+// ================================================================================================
+
+/// Commitments, prover snapshots and cached openings of many commitments, kept on disk.
+pub struct FridaStore<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    root: PathBuf,
+    _phantom_field_element: PhantomData<E>,
+    _phantom_hasher: PhantomData<H>,
+}
+
+impl<E, H> FridaStore<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Opens the store at `root`, creating the directory if it does not exist yet.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(FridaStore {
+            root,
+            _phantom_field_element: PhantomData,
+            _phantom_hasher: PhantomData,
+        })
+    }
+
+    /// Returns the key a commitment with the given roots and parameters is stored under, its
+    /// root digest.
+    pub fn key(commitment: &ProverCommitment<H>) -> [u8; 32] {
+        commitment.root_digest()
+    }
+
+    /// Returns the keys of all commitments with an entry in the store.
+    pub fn keys(&self) -> Result<Vec<[u8; 32]>, StoreError> {
+        let mut keys = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(key) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| key_from_hex(name).ok())
+            {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    pub fn put_commitment(&self, commitment: &Commitment<H>) -> Result<[u8; 32], StoreError> {
+        let key = Self::key(&commitment.to_prover_commitment());
+        self.write(&key, Path::new(COMMITMENT_FILE), &commitment.to_bytes())?;
+        Ok(key)
+    }
+
+    pub fn get_commitment(&self, key: &[u8; 32]) -> Result<Option<Commitment<H>>, StoreError> {
+        let Some(bytes) = self.read(key, Path::new(COMMITMENT_FILE))? else {
+            return Ok(None);
+        };
+        let commitment = Commitment::<H>::read_from_bytes(&bytes)?;
+        if Self::key(&commitment.to_prover_commitment()) != *key {
+            return Err(StoreError::KeyMismatch);
+        }
+        Ok(Some(commitment))
+    }
+
+    /// Stores a snapshot of `prover`, from which any position of its commitment can be opened.
+    pub fn put_prover(&self, prover: &FridaProver<E, H>) -> Result<[u8; 32], StoreError> {
+        let key = Self::key(&prover.commitment());
+        self.write(&key, Path::new(PROVER_FILE), &prover.to_bytes())?;
+        Ok(key)
+    }
+
+    pub fn get_prover(&self, key: &[u8; 32]) -> Result<Option<FridaProver<E, H>>, StoreError> {
+        let Some(bytes) = self.read(key, Path::new(PROVER_FILE))? else {
+            return Ok(None);
+        };
+        let prover = FridaProver::<E, H>::read_from_bytes(&bytes)?;
+        if Self::key(&prover.commitment()) != *key {
+            return Err(StoreError::KeyMismatch);
+        }
+        Ok(Some(prover))
+    }
+
+    /// Caches the opening of `positions` of the commitment stored under `key`.
+    pub fn put_opening(
+        &self,
+        key: &[u8; 32],
+        positions: &[usize],
+        evaluations: &[E],
+        proof: &FridaProof,
+    ) -> Result<(), StoreError> {
+        let mut bytes = positions.to_vec().to_bytes();
+        evaluations.to_vec().write_into(&mut bytes);
+        proof.write_into(&mut bytes);
+        self.write(key, &opening_path::<H>(positions), &bytes)
+    }
+
+    /// Returns the cached evaluations and proof of `positions`, if any.
+    pub fn get_opening(
+        &self,
+        key: &[u8; 32],
+        positions: &[usize],
+    ) -> Result<Option<(Vec<E>, FridaProof)>, StoreError> {
+        let Some(bytes) = self.read(key, &opening_path::<H>(positions))? else {
+            return Ok(None);
+        };
+        let mut reader = SliceReader::new(&bytes);
+        if Vec::<usize>::read_from(&mut reader)? != positions {
+            return Err(StoreError::KeyMismatch);
+        }
+        let evaluations = Vec::<E>::read_from(&mut reader)?;
+        let proof = FridaProof::read_from(&mut reader)?;
+        Ok(Some((evaluations, proof)))
+    }
+
+    /// Removes everything stored under `key`.
+    pub fn remove(&self, key: &[u8; 32]) -> Result<(), StoreError> {
+        match fs::remove_dir_all(self.entry_path(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn entry_path(&self, key: &[u8; 32]) -> PathBuf {
+        self.root.join(key_to_hex(key))
+    }
+
+    /// Writes through a temporary file, so that readers never observe a partially written entry.
+    fn write(&self, key: &[u8; 32], file: &Path, bytes: &[u8]) -> Result<(), StoreError> {
+        let path = self.entry_path(key).join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn read(&self, key: &[u8; 32], file: &Path) -> Result<Option<Vec<u8>>, StoreError> {
+        match fs::read(self.entry_path(key).join(file)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+pub fn key_to_hex(key: &[u8; 32]) -> String {
+    encoding::to_hex(key)
+}
+
+pub fn key_from_hex(hex: &str) -> Result<[u8; 32], StoreError> {
+    let invalid = || StoreError::InvalidKey(hex.to_string());
+    let bytes = encoding::from_hex(hex).ok_or_else(invalid)?;
+    bytes.try_into().map_err(|_| invalid())
+}
+
+fn opening_path<H: ElementHasher>(positions: &[usize]) -> PathBuf {
+    let digest = H::hash(&positions.to_vec().to_bytes());
+    Path::new(OPENINGS_DIR).join(format!("{}.bin", encoding::to_hex(&digest.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use winter_math::fields::f128::BaseElement;
+    use winter_rand_utils::{rand_value, rand_vector};
+
+    type TestStore = FridaStore<BaseElement, Blake3>;
+
+    /// A store in a fresh temporary directory, removed when dropped.
+    struct TempStore(TestStore);
+
+    impl TempStore {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("frida-store-{:x}", rand_value::<u64>()));
+            TempStore(TestStore::open(root).unwrap())
+        }
+    }
+
+    impl Drop for TempStore {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0.root);
+        }
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let store = TempStore::new();
+        let builder = TestFridaProverBuilder::new(test_options());
+        let (commitment, prover) = builder
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+
+        let key = store.0.put_commitment(&commitment).unwrap();
+        assert_eq!(key, commitment.root_digest());
+        assert_eq!(store.0.put_prover(&prover).unwrap(), key);
+        assert_eq!(store.0.keys().unwrap(), vec![key]);
+        assert_eq!(store.0.get_commitment(&key).unwrap().unwrap(), commitment);
+
+        // the restored prover opens the same proofs
+        let restored = store.0.get_prover(&key).unwrap().unwrap();
+        let positions = vec![1, 5, 9];
+        let proof = restored.open(&positions).unwrap();
+        assert_eq!(proof, prover.open(&positions).unwrap());
+
+        let evaluations = restored.get_evaluations(&positions).unwrap();
+        store
+            .0
+            .put_opening(&key, &positions, &evaluations, &proof)
+            .unwrap();
+        assert_eq!(
+            store.0.get_opening(&key, &positions).unwrap(),
+            Some((evaluations, proof))
+        );
+        assert_eq!(store.0.get_opening(&key, &[1, 5]).unwrap(), None);
+
+        store.0.remove(&key).unwrap();
+        assert!(store.0.keys().unwrap().is_empty());
+        assert!(store.0.get_commitment(&key).unwrap().is_none());
+        assert!(store.0.get_prover(&key).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_store_batch_prover() {
+        let store = TempStore::new();
+        let builder = TestFridaProverBuilder::new(test_options());
+        let data_list = (0..4).map(|_| rand_vector::<u8>(64)).collect::<Vec<_>>();
+        let (commitment, prover) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+
+        let key = store.0.put_prover(&prover).unwrap();
+        assert_eq!(store.0.put_commitment(&commitment).unwrap(), key);
+        let restored = store.0.get_prover(&key).unwrap().unwrap();
+        assert_eq!(restored.open(&[0, 3]), prover.open(&[0, 3]));
+    }
+
+    #[test]
+    fn test_store_detects_mismatched_entries() {
+        let store = TempStore::new();
+        let builder = TestFridaProverBuilder::new(test_options());
+        let (commitment, _) = builder
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+        let (other, _) = builder
+            .commit_and_prove(&rand_vector::<u8>(200), 4)
+            .unwrap();
+
+        let key = store.0.put_commitment(&commitment).unwrap();
+        let other_key = store.0.put_commitment(&other).unwrap();
+        fs::copy(
+            store.0.entry_path(&other_key).join(COMMITMENT_FILE),
+            store.0.entry_path(&key).join(COMMITMENT_FILE),
+        )
+        .unwrap();
+        assert!(matches!(
+            store.0.get_commitment(&key),
+            Err(StoreError::KeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_key_hex_roundtrip() {
+        let key = <Blake3 as winter_crypto::Hasher>::hash(b"frida").as_bytes();
+        let hex = key_to_hex(&key);
+        assert_eq!(key_from_hex(&hex).unwrap(), key);
+        assert!(key_from_hex(&hex[2..]).is_err());
+        assert!(key_from_hex(&hex.replace(&hex[..2], "zz")).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+// OUTPUT VECTORS
+// ================================================================================================
+
+/// Allocates a vector of `len` elements which the caller overwrites in full before reading it.
+///
+/// By default the elements are left uninitialized, which saves a pass over large evaluation
+/// vectors; with the `forbid-unsafe` feature they are set to their default value instead, so that
+/// a caller failing to overwrite an element reads that value rather than uninitialized memory.
+///
+/// The function is private to the crate, as safe code outside of it could read the uninitialized
+/// elements; every caller writes each element before returning or reading the vector, checking
+/// the sizes this relies on even in release builds.
+#[cfg(not(feature = "forbid-unsafe"))]
+pub(crate) fn output_vector<T: Default + Clone>(len: usize) -> Vec<T> {
+    // SAFETY: callers overwrite every element before reading any
+    unsafe { winter_utils::uninit_vector(len) }
+}
+
+/// Allocates a vector of `len` elements which the caller overwrites in full before reading it.
+#[cfg(feature = "forbid-unsafe")]
+pub(crate) fn output_vector<T: Default + Clone>(len: usize) -> Vec<T> {
+    vec![T::default(); len]
+}
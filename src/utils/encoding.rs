@@ -0,0 +1,111 @@
+//! Hex and base64 string encodings of byte strings, for values shown in logs, CLIs and JSON.
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `bytes` as lowercase hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Decodes a hex string of either case, returning `None` if it is not valid hex.
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    // `from_str_radix` would also accept a leading '+'
+    if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Encodes `bytes` as padded base64 with the standard alphabet.
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (i, &b)| group | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(group >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Decodes padded base64 with the standard alphabet, returning `None` if it is not valid base64.
+pub fn from_base64(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.as_bytes();
+    if encoded.len() % 4 != 0 {
+        return None;
+    }
+    let padding = encoded.iter().rev().take_while(|&&c| c == b'=').count();
+    if padding > 2 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+    for (n, chunk) in encoded.chunks(4).enumerate() {
+        let is_last = n == encoded.len() / 4 - 1;
+        let len = if is_last { 4 - padding } else { 4 };
+        let mut group = 0u32;
+        for (i, &c) in chunk[..len].iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+            group |= value << (18 - 6 * i);
+        }
+        // padding bits must be zero for the encoding to be canonical
+        let num_bytes = len * 6 / 8;
+        if group & (0xffffff >> (8 * num_bytes)) != 0 {
+            return None;
+        }
+        bytes.extend((0..num_bytes).map(|i| (group >> (16 - 8 * i)) as u8));
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[0x00, 0xab, 0x7f]), "00ab7f");
+        assert_eq!(from_hex("00AB7f"), Some(vec![0x00, 0xab, 0x7f]));
+        assert_eq!(from_hex(""), Some(vec![]));
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+        assert_eq!(from_hex("+f"), None);
+        assert_eq!(from_hex("00+f"), None);
+    }
+
+    #[test]
+    fn test_base64() {
+        // test vectors of RFC 4648
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (bytes, encoded) in vectors {
+            assert_eq!(to_base64(bytes.as_bytes()), encoded);
+            assert_eq!(from_base64(encoded).unwrap(), bytes.as_bytes());
+        }
+
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(from_base64(&to_base64(&bytes)).unwrap(), bytes);
+
+        for invalid in ["Zg=", "Zg===", "Z===", "Zh==", "Zm9v!A==", "=Zm9"] {
+            assert_eq!(from_base64(invalid), None, "{invalid}");
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub mod alloc;
+pub mod encoding;
+pub mod parallel;
 #[cfg(any(test, feature = "cli"))]
 pub mod test_utils;
@@ -0,0 +1,106 @@
+#[cfg(feature = "concurrent")]
+use winter_utils::iterators::*;
+
+// PARALLELISM OPTIONS
+// ================================================================================================
+
+/// Default minimum number of field elements read by a parallel task when its length is tuned to
+/// the shape of the layer.
+pub const DEFAULT_MIN_TASK_WORK: usize = 4096;
+
+/// Sizes of the tasks the loops over the rows of a layer are split into, run in parallel with the
+/// `concurrent` feature and one after the other without it.
+///
+/// Rows are split into consecutive tasks of a fixed number of rows, which only depends on these
+/// options and on the shape of the layer, never on the number of threads, so that every loop is
+/// split the same way on every machine. The split does not affect the values computed, and
+/// therefore neither commitments nor proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelOptions {
+    /// Fixed number of rows per task, overriding `min_task_work` when set.
+    pub rows_per_task: Option<usize>,
+    /// Minimum number of field elements read per task, from which the number of rows per task is
+    /// derived by the width of the rows, i.e. the number of polynomials times the folding factor
+    /// for the batch layer. Small domains with few polynomials thus run in a single task, while
+    /// large batches are split into tasks of fewer rows.
+    pub min_task_work: usize,
+}
+
+impl Default for ParallelOptions {
+    fn default() -> Self {
+        ParallelOptions {
+            rows_per_task: None,
+            min_task_work: DEFAULT_MIN_TASK_WORK,
+        }
+    }
+}
+
+impl ParallelOptions {
+    /// Options splitting every loop into tasks of `rows_per_task` rows, whatever their width.
+    pub fn fixed(rows_per_task: usize) -> Self {
+        ParallelOptions {
+            rows_per_task: Some(rows_per_task),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the number of rows of `row_width` elements handed to every task.
+    pub fn task_rows(&self, row_width: usize) -> usize {
+        self.rows_per_task
+            .unwrap_or_else(|| self.min_task_work.div_ceil(row_width.max(1)))
+            .max(1)
+    }
+
+    /// Calls `f` with the index of every row of `rows` and a mutable reference to it, in tasks of
+    /// `task_rows(row_width)` rows.
+    pub(crate) fn for_each_row_mut<T, F>(&self, rows: &mut [T], row_width: usize, f: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut T) + Send + Sync,
+    {
+        let task_rows = self.task_rows(row_width);
+        #[cfg(feature = "concurrent")]
+        let tasks = rows.par_chunks_mut(task_rows);
+        #[cfg(not(feature = "concurrent"))]
+        let tasks = rows.chunks_mut(task_rows);
+        tasks.enumerate().for_each(|(task, chunk)| {
+            for (i, row) in chunk.iter_mut().enumerate() {
+                f(task * task_rows + i, row);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_rows() {
+        let options = ParallelOptions::default();
+        assert_eq!(options.task_rows(1), DEFAULT_MIN_TASK_WORK);
+        assert_eq!(options.task_rows(0), DEFAULT_MIN_TASK_WORK);
+        assert_eq!(options.task_rows(8), DEFAULT_MIN_TASK_WORK / 8);
+        assert_eq!(options.task_rows(3), DEFAULT_MIN_TASK_WORK.div_ceil(3));
+        assert_eq!(options.task_rows(DEFAULT_MIN_TASK_WORK * 2), 1);
+
+        let options = ParallelOptions::fixed(1024);
+        assert_eq!(options.task_rows(1), 1024);
+        assert_eq!(options.task_rows(64), 1024);
+        assert_eq!(ParallelOptions::fixed(0).task_rows(1), 1);
+    }
+
+    #[test]
+    fn test_for_each_row_mut_visits_every_row_once() {
+        for options in [
+            ParallelOptions::default(),
+            ParallelOptions::fixed(1),
+            ParallelOptions::fixed(7),
+            ParallelOptions::fixed(10_000),
+        ] {
+            let mut rows = vec![0usize; 1000];
+            options.for_each_row_mut(&mut rows, 4, |i, row| *row += i + 1);
+            assert!(rows.iter().enumerate().all(|(i, &row)| row == i + 1));
+        }
+    }
+}
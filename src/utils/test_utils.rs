@@ -1,10 +1,13 @@
 use winter_crypto::hashers::Blake3_256;
+use winter_crypto::{Digest, Hasher};
 use winter_fri::FriOptions;
 use winter_math::fields::f128;
 use winter_math::{fft, FieldElement};
 
 use crate::prover::builder::FridaProverBuilder;
 use crate::prover::channel::FridaProverChannel;
+use crate::prover::supersede::{RecordSigner, RecordVerifier};
+use crate::prover::ProverCommitment;
 use crate::verifier::das::FridaDasVerifier;
 
 use serde::Deserialize;
@@ -19,6 +22,36 @@ pub type TestFridaProverBuilder = FridaProverBuilder<f128::BaseElement, Blake3>;
 pub type TestFridaProverChannel = FridaProverChannel<f128::BaseElement, Blake3, Blake3>;
 pub type TestFridaDasVerifier = FridaDasVerifier<f128::BaseElement, Blake3, Blake3>;
 
+/// FRI options small enough for tests to commit to a few hundred bytes quickly.
+pub fn test_options() -> FriOptions {
+    FriOptions::new(2, 2, 1)
+}
+
+/// Commits to `data` with `test_options` and 4 queries.
+pub fn test_commitment(data: &[u8]) -> ProverCommitment<Blake3> {
+    TestFridaProverBuilder::new(test_options())
+        .commitment(data, 4)
+        .unwrap()
+        .0
+}
+
+/// Keyed hash standing in for a signature scheme.
+pub struct TestKey(pub u32);
+
+impl RecordSigner for TestKey {
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        Blake3::hash(&[&self.0.to_be_bytes()[..], message].concat())
+            .as_bytes()
+            .to_vec()
+    }
+}
+
+impl RecordVerifier for TestKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        self.sign(message) == signature
+    }
+}
+
 pub fn test_build_prover_channel(
     trace_length: usize,
     options: &FriOptions,
@@ -94,11 +127,16 @@ impl<'a> CleanupFiles<'a> {
 impl Drop for CleanupFiles<'_> {
     fn drop(&mut self) {
         for path in &self.paths {
-            if path.exists() {
-                fs::remove_file(path).unwrap_or_else(|err| {
-                    eprintln!("Failed to remove file {}: {}", path.display(), err);
-                });
-            }
+            let result = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else if path.exists() {
+                fs::remove_file(path)
+            } else {
+                continue;
+            };
+            result.unwrap_or_else(|err| {
+                eprintln!("Failed to remove {}: {}", path.display(), err);
+            });
         }
     }
 }
@@ -0,0 +1,193 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use winter_crypto::{Digest, ElementHasher, Hasher};
+use winter_math::FieldElement;
+
+use super::das::FridaDasVerifier;
+use crate::{error::FridaError, prover::proof::FridaProof};
+
+// ADAPTIVE SAMPLING
+// ================================================================================================
+
+/// Sizes of the rounds of an `AdaptiveSampling`: the first round requests `initial` positions,
+/// and every following one `growth` times as many as the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscalationPolicy {
+    pub initial: usize,
+    pub growth: usize,
+}
+
+impl EscalationPolicy {
+    /// # Panics
+    /// Panics if `initial` or `growth` is 0.
+    pub fn new(initial: usize, growth: usize) -> Self {
+        assert!(
+            initial > 0,
+            "the first round must request at least one position"
+        );
+        assert!(growth > 0, "rounds cannot shrink to zero positions");
+        EscalationPolicy { initial, growth }
+    }
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        EscalationPolicy {
+            initial: 8,
+            growth: 2,
+        }
+    }
+}
+
+/// Outcome of a round of an `AdaptiveSampling`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SamplingStep {
+    /// Positions to fetch and submit in the next round, sorted.
+    Fetch(Vec<usize>),
+    /// The positions verified so far reach the target security level.
+    Confident { security_bits: u32 },
+}
+
+/// Sampling client which verifies a commitment in rounds of growing size, as bandwidth allows,
+/// until the distinct positions verified reach a target security level, instead of fetching
+/// every query at once.
+///
+/// Positions are drawn with `H` from a seed which must be kept secret by the client, so that the
+/// provider cannot predict the positions of later rounds. Every round is verified in full with
+/// `FridaDasVerifier::verify`; a round which fails to verify ends the sampling with its error.
+#[derive(Debug)]
+pub struct AdaptiveSampling<H: Hasher> {
+    domain_size: usize,
+    target_bits: u32,
+    /// Number of distinct positions needed to reach `target_bits`.
+    required: usize,
+    round_size: usize,
+    growth: usize,
+    seed: Vec<u8>,
+    counter: u64,
+    verified: BTreeSet<usize>,
+    pending: Vec<usize>,
+    _phantom_hash: PhantomData<H>,
+}
+
+impl<H: Hasher> AdaptiveSampling<H> {
+    /// Starts sampling the commitment of `verifier` until `target_bits` of security, with rounds
+    /// sized by `policy` and positions drawn with `H` from the secret `seed`. The positions of
+    /// the first round are returned by `pending`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InsufficientSecurity` if opening every position of the domain does
+    /// not reach `target_bits`.
+    pub fn new<E, HHst, HRandom>(
+        verifier: &FridaDasVerifier<E, HHst, HRandom>,
+        target_bits: u32,
+        policy: EscalationPolicy,
+        seed: &[u8],
+    ) -> Result<Self, FridaError>
+    where
+        E: FieldElement,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        HRandom: ElementHasher<BaseField = E::BaseField>,
+    {
+        let domain_size = verifier.domain_size();
+        let max_level = verifier.security_level(domain_size);
+        if max_level < target_bits {
+            return Err(FridaError::InsufficientSecurity(max_level, target_bits));
+        }
+
+        // the security level grows with the number of positions, so the smallest one reaching
+        // the target is found by bisection
+        let (mut low, mut high) = (0, domain_size);
+        while low < high {
+            let mid = (low + high) / 2;
+            if verifier.security_level(mid) >= target_bits {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        let mut sampling = AdaptiveSampling {
+            domain_size,
+            target_bits,
+            required: low,
+            round_size: policy.initial,
+            growth: policy.growth,
+            seed: seed.to_vec(),
+            counter: 0,
+            verified: BTreeSet::new(),
+            pending: Vec::new(),
+            _phantom_hash: PhantomData,
+        };
+        sampling.draw_round();
+        Ok(sampling)
+    }
+
+    /// Positions to fetch and submit in the current round, sorted; empty once the target is
+    /// reached.
+    pub fn pending(&self) -> &[usize] {
+        &self.pending
+    }
+
+    /// Number of distinct positions verified so far.
+    pub fn num_verified(&self) -> usize {
+        self.verified.len()
+    }
+
+    /// Number of distinct positions needed to reach the target security level.
+    pub fn num_required(&self) -> usize {
+        self.required
+    }
+
+    /// Verifies `proof` and `evaluations`, opening the pending positions, with `verifier`, and
+    /// returns either the positions of the next round or the security level reached.
+    ///
+    /// # Errors
+    /// Returns the errors of `FridaDasVerifier::verify` for the pending positions, which remain
+    /// pending.
+    pub fn submit<E, HHst, HRandom>(
+        &mut self,
+        verifier: &FridaDasVerifier<E, HHst, HRandom>,
+        proof: &FridaProof,
+        evaluations: &[E],
+    ) -> Result<SamplingStep, FridaError>
+    where
+        E: FieldElement,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        HRandom: ElementHasher<BaseField = E::BaseField>,
+    {
+        if !self.pending.is_empty() {
+            verifier.verify(proof, evaluations, &self.pending)?;
+            self.verified.extend(self.pending.drain(..));
+        }
+
+        let security_bits = verifier.security_level(self.verified.len());
+        if security_bits >= self.target_bits {
+            return Ok(SamplingStep::Confident { security_bits });
+        }
+        self.round_size = self.round_size.saturating_mul(self.growth);
+        self.draw_round();
+        Ok(SamplingStep::Fetch(self.pending.clone()))
+    }
+
+    /// Draws the positions of the next round, which never exceeds the positions still needed to
+    /// reach the target.
+    fn draw_round(&mut self) {
+        let size = self
+            .round_size
+            .min(self.required.saturating_sub(self.verified.len()));
+        let mut positions = BTreeSet::new();
+        while positions.len() < size {
+            let digest = H::hash(&[&self.seed[..], &self.counter.to_le_bytes()].concat());
+            let bytes = digest.as_bytes();
+            let value = u64::from_le_bytes(bytes[..8].try_into().expect("digests hold 8 bytes"));
+            let position = (value % self.domain_size as u64) as usize;
+            if !self.verified.contains(&position) {
+                positions.insert(position);
+            }
+            self.counter += 1;
+        }
+        self.pending = positions.into_iter().collect();
+    }
+}
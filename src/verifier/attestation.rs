@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use winter_crypto::Hasher;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{
+        deadline::{DispersalHint, DispersalStatus},
+        supersede::{RecordSigner, RecordVerifier},
+        ProverCommitment,
+    },
+};
+
+/// Domain separator of the message signed by attestations.
+const ATTESTATION_DOMAIN: &[u8] = b"frida-attestation-v1";
+
+// ATTESTATION
+// ================================================================================================
+
+/// Signed availability vote of a validator, stating that it verified and holds the evaluations
+/// at the positions assigned to it of the commitment with root digest `root_digest`.
+///
+/// Commitments are identified by their `ProverCommitment::root_digest`, and positions by
+/// `positions_hash`, so that the size of an attestation does not depend on its assignment.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Attestation<H: Hasher> {
+    pub root_digest: [u8; 32],
+    pub validator_id: u32,
+    pub positions_hash: H::Digest,
+    pub signature: Vec<u8>,
+}
+
+impl<H: Hasher> Clone for Attestation<H> {
+    fn clone(&self) -> Self {
+        Attestation {
+            root_digest: self.root_digest,
+            validator_id: self.validator_id,
+            positions_hash: self.positions_hash,
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+impl<H: Hasher> Attestation<H> {
+    /// Creates the attestation of validator `validator_id` to `positions` of `commitment`,
+    /// signed by `signer`.
+    pub fn new(
+        commitment: &ProverCommitment<H>,
+        validator_id: u32,
+        positions: &[usize],
+        signer: &impl RecordSigner,
+    ) -> Self {
+        let mut attestation = Attestation {
+            root_digest: commitment.root_digest(),
+            validator_id,
+            positions_hash: positions_hash::<H>(positions),
+            signature: Vec::new(),
+        };
+        attestation.signature = signer.sign(&attestation.message());
+        attestation
+    }
+
+    /// Returns the message signed by the attestation.
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = ATTESTATION_DOMAIN.to_vec();
+        message.write_bytes(&self.root_digest);
+        self.validator_id.write_into(&mut message);
+        self.positions_hash.write_into(&mut message);
+        message
+    }
+
+    /// Checks that the attestation was signed with `key` for `positions` of `commitment`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidAttestation` if the attestation is bound to another
+    /// commitment, covers other positions, or is not validly signed.
+    pub fn verify(
+        &self,
+        key: &impl RecordVerifier,
+        commitment: &ProverCommitment<H>,
+        positions: &[usize],
+    ) -> Result<(), FridaError> {
+        self.verify_root(key, &commitment.root_digest(), positions)
+    }
+
+    fn verify_root(
+        &self,
+        key: &impl RecordVerifier,
+        root_digest: &[u8; 32],
+        positions: &[usize],
+    ) -> Result<(), FridaError> {
+        if self.root_digest != *root_digest
+            || self.positions_hash != positions_hash::<H>(positions)
+            || !key.verify(&self.message(), &self.signature)
+        {
+            return Err(FridaError::InvalidAttestation);
+        }
+        Ok(())
+    }
+}
+
+/// Hash of the positions assigned to a validator, in the order they were assigned.
+pub fn positions_hash<H: Hasher>(positions: &[usize]) -> H::Digest {
+    let mut bytes = Vec::new();
+    positions.to_vec().write_into(&mut bytes);
+    H::hash(&bytes)
+}
+
+// AGGREGATOR
+// ================================================================================================
+
+/// Collects the attestations of the validators of a commitment, and tells when enough of them
+/// were received to consider its data available.
+///
+/// Validator `i` is assigned `assignments[i]`, and signs with `keys[i]`. The data is available
+/// once `threshold` distinct validators attested, i.e. `h` in the deFRIDA setting, where any `h`
+/// validators are assumed to include an honest one.
+pub struct AttestationAggregator<H: Hasher, V: RecordVerifier> {
+    root_digest: [u8; 32],
+    assignments: Vec<Vec<usize>>,
+    keys: Vec<V>,
+    threshold: usize,
+    dispersal_hint: Option<DispersalHint>,
+    attestations: BTreeMap<u32, Attestation<H>>,
+}
+
+impl<H: Hasher, V: RecordVerifier> AttestationAggregator<H, V> {
+    /// Creates an aggregator of the attestations to `commitment`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BadAttestationThreshold` if `threshold` is zero or larger than the
+    /// number of validators, or if `assignments` and `keys` do not have one entry per validator.
+    pub fn new(
+        commitment: &ProverCommitment<H>,
+        assignments: Vec<Vec<usize>>,
+        keys: Vec<V>,
+        threshold: usize,
+    ) -> Result<Self, FridaError> {
+        if assignments.len() != keys.len() || threshold == 0 || threshold > keys.len() {
+            return Err(FridaError::BadAttestationThreshold(threshold, keys.len()));
+        }
+        Ok(AttestationAggregator {
+            root_digest: commitment.root_digest(),
+            assignments,
+            keys,
+            threshold,
+            dispersal_hint: commitment.dispersal_hint,
+            attestations: BTreeMap::new(),
+        })
+    }
+
+    /// Verifies `attestation` and counts it towards the threshold. Returns `false` if its
+    /// validator had already attested.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidAttestation` if the attestation is not made by a validator of
+    /// the commitment, or does not verify against its key and assignment.
+    pub fn add(&mut self, attestation: Attestation<H>) -> Result<bool, FridaError> {
+        let index = attestation.validator_id as usize;
+        let (Some(key), Some(positions)) = (self.keys.get(index), self.assignments.get(index))
+        else {
+            return Err(FridaError::InvalidAttestation);
+        };
+        attestation.verify_root(key, &self.root_digest, positions)?;
+        if self.attestations.contains_key(&attestation.validator_id) {
+            return Ok(false);
+        }
+        self.attestations
+            .insert(attestation.validator_id, attestation);
+        Ok(true)
+    }
+
+    /// Number of distinct validators which attested so far.
+    pub fn count(&self) -> usize {
+        self.attestations.len()
+    }
+
+    /// Distinct positions held by the validators which attested so far.
+    pub fn coverage(&self) -> BTreeSet<usize> {
+        self.attestations
+            .keys()
+            .flat_map(|&index| self.assignments[index as usize].iter().copied())
+            .collect()
+    }
+
+    /// True once `threshold` distinct validators attested.
+    pub fn is_available(&self) -> bool {
+        self.count() >= self.threshold
+    }
+
+    /// Status at time `now` of the dispersal announced by the commitment, counting the positions
+    /// covered by the attestations received so far as sampled shares; `None` if the commitment
+    /// carries no dispersal hint.
+    pub fn dispersal_status(&self, now: u64) -> Option<DispersalStatus> {
+        self.dispersal_hint
+            .map(|hint| hint.status(now, self.coverage().len()))
+    }
+
+    /// Attestations received so far, keyed by validator.
+    pub fn attestations(&self) -> &BTreeMap<u32, Attestation<H>> {
+        &self.attestations
+    }
+}
+
+// ED25519
+// ================================================================================================
+
+#[cfg(feature = "ed25519")]
+impl RecordSigner for ed25519_dalek::SigningKey {
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        ed25519_dalek::Signer::sign(self, message)
+            .to_bytes()
+            .to_vec()
+    }
+}
+
+#[cfg(feature = "ed25519")]
+impl RecordVerifier for ed25519_dalek::VerifyingKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        ed25519_dalek::Signature::from_slice(signature)
+            .is_ok_and(|signature| self.verify_strict(message, &signature).is_ok())
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for Attestation<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.root_digest);
+        self.validator_id.write_into(target);
+        self.positions_hash.write_into(target);
+        self.signature.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for Attestation<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let root_digest = source.read_array()?;
+        let validator_id = u32::read_from(source)?;
+        let positions_hash = H::Digest::read_from(source)?;
+        let signature = Vec::<u8>::read_from(source)?;
+
+        Ok(Attestation {
+            root_digest,
+            validator_id,
+            positions_hash,
+            signature,
+        })
+    }
+}
@@ -1,8 +1,15 @@
 use winter_crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
 use winter_fri::{VerifierChannel, VerifierError};
 use winter_math::FieldElement;
+use winter_utils::DeserializationError;
 
-use crate::{error::FridaError, prover::proof::FridaProof};
+use crate::{
+    error::FridaError,
+    prover::{
+        proof::{FridaProof, ProofLimits},
+        BatchLeafEncoding,
+    },
+};
 
 pub struct FridaVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     layer_commitments: Vec<H::Digest>,
@@ -24,41 +31,121 @@ where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
+    /// Parses `proof`, whose layers are folded by `folding_factors`, the batch layer included,
+    /// once it is checked against `limits`. The leaves of the batch layer are hashed with
+    /// `leaf_encoding`. A single polynomial may have no FRI layer at all, in which case the
+    /// proof only carries its remainder.
     pub fn new(
         proof: &FridaProof,
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
-        folding_factor: usize,
+        folding_factors: &[usize],
+        poly_count: usize,
+        leaf_encoding: BatchLeafEncoding,
+        limits: &ProofLimits,
+    ) -> Result<Self, FridaError> {
+        Self::parse(
+            proof,
+            layer_commitments,
+            domain_size,
+            folding_factors,
+            poly_count,
+            leaf_encoding,
+            limits,
+            true,
+        )
+    }
+
+    /// Same as `new`, but leaves the FRI layers of `proof` unparsed, so that they are loaded one
+    /// at a time with `load_layer` as they are verified.
+    pub fn new_streaming(
+        proof: &FridaProof,
+        layer_commitments: Vec<H::Digest>,
+        domain_size: usize,
+        folding_factors: &[usize],
+        poly_count: usize,
+        leaf_encoding: BatchLeafEncoding,
+        limits: &ProofLimits,
+    ) -> Result<Self, FridaError> {
+        Self::parse(
+            proof,
+            layer_commitments,
+            domain_size,
+            folding_factors,
+            poly_count,
+            leaf_encoding,
+            limits,
+            false,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn parse(
+        proof: &FridaProof,
+        layer_commitments: Vec<H::Digest>,
+        domain_size: usize,
+        folding_factors: &[usize],
         poly_count: usize,
+        leaf_encoding: BatchLeafEncoding,
+        limits: &ProofLimits,
+        parse_layers: bool,
     ) -> Result<Self, FridaError> {
         assert!(poly_count != 0, "poly_count must be greater than 0");
+        if poly_count == 1 && proof.has_batch_layer() {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+        proof
+            .check_limits(limits)
+            .map_err(FridaError::DeserializationError)?;
 
-        let mut domain_size = domain_size;
+        // every partition holds at least one row of the smallest layer
         let num_partitions = proof.num_partitions();
+        let min_rows = domain_size / folding_factors.iter().product::<usize>();
+        if num_partitions > min_rows {
+            return Err(FridaError::DeserializationError(
+                DeserializationError::InvalidValue(format!(
+                    "expected at most {min_rows} partitions, but the proof has {num_partitions}"
+                )),
+            ));
+        }
+
+        let mut domain_size = domain_size;
 
         let remainder = proof
             .parse_remainder()
             .map_err(FridaError::DeserializationError)?;
 
+        let mut layer_folding_factors = folding_factors;
         let batch_data = if poly_count > 1 {
+            // batches are combined in their first layer, so they cannot do without one
+            let Some(&first_folding_factor) = folding_factors.first() else {
+                return Err(FridaError::NotEnoughDataPoints());
+            };
             let (batch_layer_queries, batch_layer_proof) = proof
-                .parse_batch_layer::<H, E>(domain_size, folding_factor, poly_count)
+                .parse_batch_layer::<H, E>(
+                    domain_size,
+                    first_folding_factor,
+                    poly_count,
+                    leaf_encoding,
+                )
                 .map_err(FridaError::DeserializationError)?;
-            domain_size /= folding_factor;
+            domain_size /= first_folding_factor;
+            layer_folding_factors = &folding_factors[1..];
             Some(BatchData {
                 batch_layer_queries: Some(batch_layer_queries),
                 batch_layer_proof: Some(batch_layer_proof),
             })
         } else {
-            if proof.has_batch_layer() {
-                return Err(FridaError::ProofPolyCountMismatch);
-            }
             None
         };
 
-        let (layer_queries, layer_proofs) = proof
-            .parse_layers::<H, E>(domain_size, folding_factor)
-            .map_err(FridaError::DeserializationError)?;
+        let (layer_queries, layer_proofs) = if parse_layers {
+            proof
+                .parse_layers::<H, E>(domain_size, layer_folding_factors)
+                .map_err(FridaError::DeserializationError)?
+        } else {
+            (Vec::new(), Vec::new())
+        };
         Ok(Self {
             layer_commitments,
             poly_count,
@@ -70,6 +157,27 @@ where
         })
     }
 
+    /// Remainder polynomial carried by the proof, in coefficient form.
+    pub fn remainder(&self) -> &[E] {
+        &self.remainder
+    }
+
+    /// Parses FRI layer `index` of `proof`, folded by `folding_factor` into a domain of
+    /// `domain_size`, as the next layer to be read from this channel.
+    pub fn load_layer(
+        &mut self,
+        proof: &FridaProof,
+        index: usize,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<(), DeserializationError> {
+        let (queries, layer_proof) =
+            proof.parse_layer::<H, E>(index, domain_size, folding_factor)?;
+        self.layer_queries.push(queries);
+        self.layer_proofs.push(layer_proof);
+        Ok(())
+    }
+
     pub fn read_batch_layer_queries(
         &mut self,
         positions: &[usize],
@@ -0,0 +1,125 @@
+use winter_crypto::ElementHasher;
+use winter_math::FieldElement;
+
+use super::das::FridaDasVerifier;
+use crate::{error::FridaError, prover::proof::FridaProof};
+
+// CROSS VERIFICATION
+// ================================================================================================
+
+// Two commitments to the same data made with different parameters, e.g. while migrating to another
+// hasher or blowup factor, are evaluations of the same polynomial over domains of different sizes.
+// Both domains are generated by roots of unity, so the smaller one is a subgroup of the larger one:
+// position `p` of the shared domain is position `p * (domain_size / shared_domain_size)` of each
+// commitment's own domain.
+
+/// One side of a cross verification: the verifier of a commitment, and a proof opening that
+/// commitment at the shared positions mapped to its domain with `cross_positions`.
+pub struct CrossOpening<'a, E, HHst, H>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    pub verifier: &'a FridaDasVerifier<E, HHst, H>,
+    pub proof: &'a FridaProof,
+}
+
+/// Returns the size of the domain shared by two commitments with the given domain sizes.
+pub fn shared_domain_size(domain_size_a: usize, domain_size_b: usize) -> usize {
+    usize::min(domain_size_a, domain_size_b)
+}
+
+/// Maps positions of the shared domain to the positions of the same points in a commitment's own
+/// domain, which must be at least as large.
+///
+/// # Errors
+/// Returns `FridaError::IncompatibleCommitments` if the shared domain is empty or is not a
+/// subgroup of the domain, and `FridaError::PositionOutOfDomain` if a position lies outside of
+/// the shared domain.
+pub fn cross_positions(
+    shared_positions: &[usize],
+    shared_domain_size: usize,
+    domain_size: usize,
+) -> Result<Vec<usize>, FridaError> {
+    if shared_domain_size == 0 || !domain_size.is_multiple_of(shared_domain_size) {
+        return Err(FridaError::IncompatibleCommitments);
+    }
+    if let Some(&position) = shared_positions.iter().find(|&&p| p >= shared_domain_size) {
+        return Err(FridaError::PositionOutOfDomain(
+            position,
+            shared_domain_size,
+        ));
+    }
+    let stride = domain_size / shared_domain_size;
+    Ok(shared_positions.iter().map(|p| p * stride).collect())
+}
+
+/// Verifies that two commitments open to the same `evaluations` at `shared_positions`.
+///
+/// Positions index the shared domain of both commitments (see `shared_domain_size`), and
+/// evaluations are laid out as for `FridaDasVerifier::verify`. Each proof must open its commitment
+/// at the positions returned by `cross_positions` for its domain.
+pub fn verify_cross<E, HHstA, HA, HHstB, HB>(
+    commitment_a: CrossOpening<'_, E, HHstA, HA>,
+    commitment_b: CrossOpening<'_, E, HHstB, HB>,
+    shared_positions: &[usize],
+    evaluations: &[E],
+) -> Result<(), FridaError>
+where
+    E: FieldElement,
+    HHstA: ElementHasher<BaseField = E::BaseField>,
+    HA: ElementHasher<BaseField = E::BaseField>,
+    HHstB: ElementHasher<BaseField = E::BaseField>,
+    HB: ElementHasher<BaseField = E::BaseField>,
+{
+    if commitment_a.verifier.poly_count() != commitment_b.verifier.poly_count() {
+        return Err(FridaError::ProofPolyCountMismatch);
+    }
+
+    let domain_size_a = commitment_a.verifier.domain_size();
+    let domain_size_b = commitment_b.verifier.domain_size();
+    let shared_domain_size = shared_domain_size(domain_size_a, domain_size_b);
+    let positions_a = cross_positions(shared_positions, shared_domain_size, domain_size_a)?;
+    let positions_b = cross_positions(shared_positions, shared_domain_size, domain_size_b)?;
+
+    commitment_a
+        .verifier
+        .verify(commitment_a.proof, evaluations, &positions_a)?;
+    commitment_b
+        .verifier
+        .verify(commitment_b.proof, evaluations, &positions_b)
+}
+
+/// Decodes the data of two single-blob commitments made in different fields, e.g. with
+/// `prover::multi_field::commit_multi_field`, from verified `(position, evaluation)` samples of
+/// each (see `FridaDasVerifier::reconstruct_poly`), and returns it if both decode to the same
+/// bytes.
+///
+/// # Errors
+/// Returns `FridaError::FieldDataMismatch` if the decoded bytes differ, and
+/// `FridaError::ProofPolyCountMismatch` if either commitment is a batch.
+pub fn verify_same_data<EA, HHstA, HA, EB, HHstB, HB>(
+    verifier_a: &FridaDasVerifier<EA, HHstA, HA>,
+    samples_a: &[(usize, EA)],
+    verifier_b: &FridaDasVerifier<EB, HHstB, HB>,
+    samples_b: &[(usize, EB)],
+) -> Result<Vec<u8>, FridaError>
+where
+    EA: FieldElement,
+    HHstA: ElementHasher<BaseField = EA::BaseField>,
+    HA: ElementHasher<BaseField = EA::BaseField>,
+    EB: FieldElement,
+    HHstB: ElementHasher<BaseField = EB::BaseField>,
+    HB: ElementHasher<BaseField = EB::BaseField>,
+{
+    if verifier_a.poly_count() != 1 || verifier_b.poly_count() != 1 {
+        return Err(FridaError::ProofPolyCountMismatch);
+    }
+
+    let data = verifier_a.reconstruct_poly(0, samples_a)?;
+    if verifier_b.reconstruct_poly(0, samples_b)? != data {
+        return Err(FridaError::FieldDataMismatch);
+    }
+    Ok(data)
+}
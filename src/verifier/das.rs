@@ -1,21 +1,41 @@
 use std::marker::PhantomData;
 use std::mem;
-use winter_crypto::{Digest, ElementHasher};
-use winter_fri::utils::map_positions_to_indexes;
+use std::sync::{Arc, OnceLock};
+use winter_crypto::{BatchMerkleProof, Digest, ElementHasher, MerkleTree};
 use winter_fri::VerifierChannel;
 use winter_fri::{folding::fold_positions, FriOptions, VerifierError};
 use winter_math::{polynom, FieldElement, StarkField};
-use winter_utils::{group_slice_elements, iter_mut};
-
-#[cfg(feature = "concurrent")]
-use winter_utils::iterators::*;
+use winter_utils::{group_slice_elements, SliceReader};
 
 use super::channel::FridaVerifierChannel;
-use super::{eval_horner, get_batch_query_values};
+use super::index_cache::{
+    PositionIndexCache, PositionIndexCacheMetrics, POSITION_INDEX_CACHE_CAPACITY,
+};
+use super::{combine_batch_values, combine_rows, eval_horner, get_batch_query_values};
 use crate::{
-    core::random::FridaRandom,
+    core::{
+        data::{
+            byte_range_positions, decode_byte_range, recover_data_from_evaluations,
+            value_byte_range,
+        },
+        deep::{draw_ood_sample, ood_evaluation_bytes, parse_ood_evaluation, OodSample},
+        random::FridaRandom,
+        schedule::{layer_folding_factors, validate_folding_schedule},
+    },
     error::FridaError,
-    prover::{proof::FridaProof, Commitment},
+    prover::{
+        blob::BlobOpening,
+        byte_range::ByteRangeProof,
+        cell::Cell,
+        data_root::DataRoots,
+        deadline::DispersalHint,
+        disclosure::{verify_app_ids_path, AppOpening},
+        proof::{FridaProof, ProofLimits},
+        receipt::SampleReceipt,
+        scheduler::CommitProfile,
+        value::ValueOpening,
+        BatchLeafEncoding, Commitment, ProverCommitment,
+    },
     verifier::get_query_values,
 };
 
@@ -32,18 +52,42 @@ where
     xi: Option<Vec<E>>,
     layer_alphas: Vec<E>,
     options: FriOptions,
-    num_partitions: usize,
+    /// Folding factor of every FRI layer, the batch layer included.
+    folding_factors: Vec<usize>,
     poly_count: usize,
+    app_ids_root: Option<HRandom::Digest>,
+    dispersal_hint: Option<DispersalHint>,
+    ood_sample: Option<OodSample<E>>,
+    batch_leaf_encoding: BatchLeafEncoding,
+    /// Partitions of the layers of the commitment, which every proof must be made for.
+    num_partitions: usize,
+    data_roots: Option<DataRoots<HRandom>>,
+    profile: Option<CommitProfile>,
+    /// Remainder polynomial of the commitment, kept once a proof carrying it has been checked
+    /// against the last root.
+    remainder: OnceLock<Vec<E>>,
+    /// Merkle indexes of the position sets verified so far, reused by later proofs for the same
+    /// positions.
+    position_indexes: PositionIndexCache,
     _phantom_hash_hst: PhantomData<HHst>,
     _phantom_hash_random: PhantomData<HRandom>,
 }
 
 struct RandomlyDrawn<E: FieldElement> {
     xi: Option<Vec<E>>,
+    ood_sample: Option<OodSample<E>>,
     layer_alphas: Vec<E>,
     positions: Vec<usize>,
 }
 
+/// Queried positions and their evaluations in the layer being verified.
+struct LayerState<E: FieldElement> {
+    domain_generator: E::BaseField,
+    domain_size: usize,
+    positions: Vec<usize>,
+    evaluations: Vec<E>,
+}
+
 type DrawResult<E, HHst, HRandom> =
     Result<(RandomlyDrawn<E>, FridaRandom<E, HHst, HRandom>), FridaError>;
 
@@ -61,64 +105,104 @@ where
         das_commitment: Commitment<HRandom>,
         options: FriOptions,
     ) -> Result<(Self, FridaRandom<E, HHst, HRandom>), FridaError> {
+        Self::new_with_schedule(das_commitment, options, &[])
+    }
+
+    /// Same as `new`, for a commitment whose layers were folded by `schedule`, as set with
+    /// `FridaProverBuilder::with_folding_schedule`.
+    pub fn new_with_schedule(
+        das_commitment: Commitment<HRandom>,
+        options: FriOptions,
+        schedule: &[usize],
+    ) -> Result<(Self, FridaRandom<E, HHst, HRandom>), FridaError> {
+        let (verifier, public_coin, _) = Self::build(&das_commitment, options, schedule)?;
+        Ok((verifier, public_coin))
+    }
+
+    /// Replays the transcript of `das_commitment`, checks the proof it embeds, and returns the
+    /// query positions that proof must open, in the order they were drawn, repetitions included.
+    ///
+    /// # Errors
+    /// Returns the errors of `new`.
+    pub fn verify_commitment_positions(
+        das_commitment: &Commitment<HRandom>,
+        options: FriOptions,
+    ) -> Result<Vec<usize>, FridaError> {
+        Self::verify_commitment_positions_with_schedule(das_commitment, options, &[])
+    }
+
+    /// Same as `verify_commitment_positions`, for a commitment whose layers were folded by
+    /// `schedule`.
+    pub fn verify_commitment_positions_with_schedule(
+        das_commitment: &Commitment<HRandom>,
+        options: FriOptions,
+        schedule: &[usize],
+    ) -> Result<Vec<usize>, FridaError> {
+        let (_, _, positions) = Self::build(das_commitment, options, schedule)?;
+        Ok(positions)
+    }
+
+    /// Returns the query positions drawn by the transcript of `das_commitment`, without checking
+    /// its proof. Positions do not depend on the options of the commitment.
+    pub(crate) fn replay_positions(
+        das_commitment: &Commitment<HRandom>,
+    ) -> Result<Vec<usize>, FridaError> {
+        let (drawn, _) = Self::draw_randomly(das_commitment, 0, &[], das_commitment.domain_size)?;
+        Ok(drawn.positions)
+    }
+
+    /// Builds the verifier of `das_commitment`, along with the public coin and the query positions
+    /// drawn from its transcript, once the proof it embeds is checked at these positions.
+    #[allow(clippy::type_complexity)]
+    fn build(
+        das_commitment: &Commitment<HRandom>,
+        options: FriOptions,
+        schedule: &[usize],
+    ) -> Result<(Self, FridaRandom<E, HHst, HRandom>, Vec<usize>), FridaError> {
+        validate_folding_schedule(&options, schedule)?;
+        check_profile(das_commitment.profile, &options)?;
         let domain_size = das_commitment.domain_size;
-        let num_partitions = das_commitment.proof.num_partitions();
         let max_poly_degree = domain_size / options.blowup_factor() - 1;
+        let folding_factors = layer_folding_factors(&options, schedule, domain_size);
 
         let (drawn, public_coin) = Self::draw_randomly(
-            &das_commitment,
+            das_commitment,
             max_poly_degree,
-            options.folding_factor(),
+            &folding_factors,
             domain_size,
         )?;
 
         // read layer commitments from the channel and use them to build a list of alphas
         let poly_count = das_commitment.poly_count;
-        let layer_commitments = das_commitment.roots;
+        let layer_commitments = das_commitment.roots.clone();
 
+        let limits = ProofLimits::new::<E, HRandom>(
+            &options,
+            domain_size,
+            &folding_factors,
+            poly_count,
+            drawn.positions.len(),
+        );
         let mut verifier_channel = FridaVerifierChannel::<E, HRandom>::new(
             &das_commitment.proof,
             layer_commitments.clone(),
             domain_size,
-            options.folding_factor(),
+            &folding_factors,
             poly_count,
+            das_commitment.batch_leaf_encoding,
+            &limits,
         )
         .map_err(|_e| FridaError::InvalidDASCommitment)?;
 
         // get query value from commitment
-        let query_values = {
-            let folded_positions =
-                fold_positions(&drawn.positions, domain_size, options.folding_factor());
-
-            let folding_factor = options.folding_factor();
-            match folding_factor {
-                2 => Ok(Self::get_query_values_from_commitment::<2>(
-                    &verifier_channel,
-                    &drawn.positions,
-                    &folded_positions,
-                    domain_size,
-                )),
-                4 => Ok(Self::get_query_values_from_commitment::<4>(
-                    &verifier_channel,
-                    &drawn.positions,
-                    &folded_positions,
-                    domain_size,
-                )),
-                8 => Ok(Self::get_query_values_from_commitment::<8>(
-                    &verifier_channel,
-                    &drawn.positions,
-                    &folded_positions,
-                    domain_size,
-                )),
-                16 => Ok(Self::get_query_values_from_commitment::<16>(
-                    &verifier_channel,
-                    &drawn.positions,
-                    &folded_positions,
-                    domain_size,
-                )),
-                _ => Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
-            }?
-        };
+        let query_values = Self::query_values_from_proof(
+            &verifier_channel,
+            &drawn.positions,
+            domain_size,
+            &folding_factors,
+            options.domain_offset(),
+        )?
+        .ok_or(FridaError::InvalidDASCommitment)?;
 
         let domain_generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
 
@@ -130,17 +214,29 @@ where
             xi: drawn.xi,
             layer_alphas: drawn.layer_alphas,
             options,
-            num_partitions,
+            folding_factors,
             poly_count,
+            app_ids_root: None,
+            dispersal_hint: None,
+            ood_sample: drawn.ood_sample,
+            batch_leaf_encoding: das_commitment.batch_leaf_encoding,
+            num_partitions: das_commitment.num_partitions,
+            data_roots: das_commitment.data_roots.clone(),
+            profile: das_commitment.profile,
+            remainder: OnceLock::new(),
+            position_indexes: PositionIndexCache::new(POSITION_INDEX_CACHE_CAPACITY),
             _phantom_hash_hst: PhantomData,
             _phantom_hash_random: PhantomData,
         };
 
         verifier
-            .check_auth(&mut verifier_channel, &query_values, &drawn.positions)
+            .check_num_partitions(&das_commitment.proof)
+            .map_err(|_e| FridaError::InvalidDASCommitment)?;
+        verifier
+            .check_auth(&mut verifier_channel, &query_values, &drawn.positions, None)
             .map_err(|_e| FridaError::InvalidDASCommitment)?;
 
-        Ok((verifier, public_coin))
+        Ok((verifier, public_coin, drawn.positions))
     }
 
     pub fn verify(
@@ -149,36 +245,446 @@ where
         evaluations: &[E],
         positions: &[usize],
     ) -> Result<(), FridaError> {
+        self.check_num_partitions(proof)?;
         let mut verifier_channel = FridaVerifierChannel::<E, HRandom>::new(
             proof,
             self.layer_commitments.clone(),
             self.domain_size,
-            self.options.folding_factor(),
+            &self.folding_factors,
+            self.poly_count,
+            self.batch_leaf_encoding,
+            &self.proof_limits(positions.len()),
+        )?;
+
+        self.check_auth(&mut verifier_channel, evaluations, positions, None)
+            .map_err(FridaError::FailToVerify)
+    }
+
+    /// Checks only the remainder of `proof`: that it parses, that its degree is within the bound
+    /// of the last layer, and that it hashes to the last root of the commitment.
+    ///
+    /// This is NOT a sound verification: a proof which passes it may still open none of the
+    /// committed evaluations. It is only meant as a cheap plausibility filter, e.g. to drop
+    /// garbage proofs while gossiping, ahead of a full `verify`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::DeserializationError` if the remainder cannot be parsed, and
+    /// `FridaError::FailToVerify` if its degree or its hash does not match the commitment.
+    pub fn verify_remainder_only(&self, proof: &FridaProof) -> Result<(), FridaError> {
+        // the degree bound is checked before parsing, so that oversized remainders are not read
+        let max_degree_plus_1 = self
+            .folding_factors
+            .iter()
+            .fold(self.max_poly_degree + 1, |degree, &folding_factor| {
+                degree / folding_factor
+            });
+        if proof.num_remainder_elements::<E>() > max_degree_plus_1 {
+            return Err(FridaError::FailToVerify(
+                VerifierError::RemainderDegreeMismatch(max_degree_plus_1 - 1),
+            ));
+        }
+        let remainder = proof
+            .parse_remainder::<E>()
+            .map_err(FridaError::DeserializationError)?;
+
+        // the remainder is committed to by the root following those of the FRI layers
+        if self.layer_commitments.len() != self.folding_factors.len() + 1 {
+            return Err(FridaError::FailToVerify(
+                VerifierError::LayerCommitmentMismatch,
+            ));
+        }
+        let root = self.layer_commitments[self.folding_factors.len()];
+        if HRandom::hash_elements(&remainder) != root {
+            return Err(FridaError::FailToVerify(
+                VerifierError::RemainderCommitmentMismatch,
+            ));
+        }
+        self.remainder.get_or_init(|| remainder);
+        Ok(())
+    }
+
+    /// Returns the value of the remainder polynomial of the commitment at `position` of the
+    /// remainder domain, i.e. the domain of the commitment shrunk by every folding factor. For a
+    /// commitment without FRI layers, this is the value of the committed polynomial itself at
+    /// `position` of its domain, e.g. to compare with the data of a systematic layout.
+    ///
+    /// The remainder is kept from the first proof checked by this verifier, with `verify`, one of
+    /// its variants or `verify_remainder_only`, whose remainder hashes to the last root of the
+    /// commitment. It is thus the committed remainder, but it is only known to be consistent with
+    /// the FRI layers of the commitment once a proof has passed `verify`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::RemainderNotVerified` if no proof has been checked yet, and
+    /// `FridaError::PositionOutOfDomain` if `position` lies outside of the remainder domain.
+    pub fn evaluate_remainder_at(&self, position: usize) -> Result<E, FridaError> {
+        let remainder = self
+            .remainder
+            .get()
+            .ok_or(FridaError::RemainderNotVerified)?;
+        let folding = self.folding_factors.iter().product::<usize>();
+        let domain_size = self.domain_size / folding;
+        if position >= domain_size {
+            return Err(FridaError::PositionOutOfDomain(position, domain_size));
+        }
+
+        let generator = self.domain_generator.exp_vartime((folding as u64).into());
+        let x = self.options.domain_offset() * generator.exp_vartime((position as u64).into());
+        Ok(eval_horner::<E>(remainder, x))
+    }
+
+    /// Same as `verify`, but parses the FRI layers of `proof` one at a time as they are checked,
+    /// so that at most one of them is held in memory at any time. This bounds the memory used to
+    /// verify proofs opening thousands of positions, e.g. in full-node cross-checks.
+    ///
+    /// # Errors
+    /// Returns the errors of `verify`; a FRI layer which cannot be parsed is rejected as not
+    /// opening its commitment.
+    pub fn verify_streaming(
+        &self,
+        proof: &FridaProof,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), FridaError> {
+        self.check_num_partitions(proof)?;
+        let mut verifier_channel = FridaVerifierChannel::<E, HRandom>::new_streaming(
+            proof,
+            self.layer_commitments.clone(),
+            self.domain_size,
+            &self.folding_factors,
             self.poly_count,
+            self.batch_leaf_encoding,
+            &self.proof_limits(positions.len()),
         )?;
 
-        self.check_auth(&mut verifier_channel, evaluations, positions)
-            .map_err(|_e| FridaError::FailToVerify)
+        self.check_auth(&mut verifier_channel, evaluations, positions, Some(proof))
+            .map_err(FridaError::FailToVerify)
+    }
+
+    /// Same as `verify`, for a sampler which did not receive the evaluations at the positions in
+    /// `missing`, e.g. because part of the shares gossiped to it were lost. `evaluations` holds
+    /// those of the other positions, in the order of `positions`.
+    ///
+    /// The proof is checked at every position, the missing ones included, but only the received
+    /// evaluations are checked against it; the positions whose evaluations could not be checked
+    /// are returned, sorted, so that they can be sampled again.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnknownErasure` if a missing position is not among `positions`, and
+    /// `FridaError::FailToVerify` if the proof or a received evaluation is rejected.
+    pub fn verify_with_erasures(
+        &self,
+        proof: &FridaProof,
+        evaluations: &[E],
+        positions: &[usize],
+        missing: &[usize],
+    ) -> Result<Vec<usize>, FridaError> {
+        if let Some(&position) = missing.iter().find(|p| !positions.contains(p)) {
+            return Err(FridaError::UnknownErasure(position));
+        }
+        let received = positions.iter().filter(|p| !missing.contains(p)).count();
+        if evaluations.len() != received * self.poly_count {
+            return Err(FridaError::FailToVerify(
+                VerifierError::NumPositionEvaluationMismatch(received, evaluations.len()),
+            ));
+        }
+
+        self.check_num_partitions(proof)?;
+        let mut verifier_channel = FridaVerifierChannel::<E, HRandom>::new(
+            proof,
+            self.layer_commitments.clone(),
+            self.domain_size,
+            &self.folding_factors,
+            self.poly_count,
+            self.batch_leaf_encoding,
+            &self.proof_limits(positions.len()),
+        )?;
+        // the proof opens the missing evaluations along with the rows holding them
+        let opened = Self::query_values_from_proof(
+            &verifier_channel,
+            positions,
+            self.domain_size,
+            &self.folding_factors,
+            self.options.domain_offset(),
+        )?
+        .ok_or(FridaError::FailToVerify(
+            VerifierError::LayerCommitmentMismatch,
+        ))?;
+        let mut received_values = evaluations.chunks(self.poly_count);
+        let completed = positions
+            .iter()
+            .zip(opened.chunks(self.poly_count))
+            .flat_map(|(position, opened)| {
+                if missing.contains(position) {
+                    opened
+                } else {
+                    received_values
+                        .next()
+                        .expect("received evaluations were counted")
+                }
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        self.check_auth(&mut verifier_channel, &completed, positions, None)
+            .map_err(FridaError::FailToVerify)?;
+
+        let mut unchecked = missing.to_vec();
+        unchecked.sort_unstable();
+        unchecked.dedup();
+        Ok(unchecked)
+    }
+
+    /// Verifies a single-position receipt against the commitment of this verifier.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PositionOutOfDomain` if the position of the receipt lies outside of
+    /// the domain, `FridaError::ReceiptBindingMismatch` if the receipt is bound to another
+    /// commitment, and the errors of `verify` otherwise.
+    pub fn verify_receipt(&self, receipt: &SampleReceipt<E, HRandom>) -> Result<(), FridaError> {
+        if receipt.position >= self.domain_size {
+            return Err(FridaError::PositionOutOfDomain(
+                receipt.position,
+                self.domain_size,
+            ));
+        }
+        if receipt.binding != self.commitment().binding() {
+            return Err(FridaError::ReceiptBindingMismatch);
+        }
+
+        self.verify(&receipt.proof, &receipt.evaluations, &[receipt.position])
+    }
+
+    /// Verifies that `opening` opens the blob bound to `app_id` in the commitment of this
+    /// verifier, and returns the evaluations of that blob at the opened positions.
+    ///
+    /// Only verifiers created with `from_commitment` know the app ids root of their commitment.
+    pub fn verify_app_opening(
+        &self,
+        app_id: &[u8],
+        opening: &AppOpening<E, HRandom>,
+    ) -> Result<Vec<E>, FridaError> {
+        let root = self
+            .app_ids_root
+            .as_ref()
+            .ok_or(FridaError::AppIdsNotCommitted)?;
+        if opening.blob >= self.poly_count {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+        if !verify_app_ids_path::<HRandom>(
+            root,
+            opening.blob,
+            HRandom::hash(app_id),
+            &opening.app_id_path,
+        ) {
+            return Err(FridaError::AppIdMismatch);
+        }
+
+        self.verify(&opening.proof, &opening.evaluations, &opening.positions)?;
+        Ok(opening
+            .evaluations
+            .iter()
+            .skip(opening.blob)
+            .step_by(self.poly_count)
+            .copied()
+            .collect())
+    }
+
+    /// Verifies that `cell` holds the value of polynomial `cell.row` at position `cell.col` of the
+    /// commitment of this verifier.
+    pub fn verify_cell(&self, cell: &Cell<E>) -> Result<(), FridaError> {
+        if cell.row >= self.poly_count || cell.col >= self.domain_size {
+            return Err(FridaError::CellOutOfBounds(cell.row, cell.col));
+        }
+        if cell.proof.siblings.len() + 1 != self.poly_count {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+
+        self.verify(&cell.proof.proof, &cell.column(), &[cell.col])
+    }
+
+    /// Verifies that `opening` opens blob `opening.blob` of the commitment of this verifier, and
+    /// returns its evaluations at `opening.positions`, in order.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BlobOpeningUnsupported` if the commitment is not batched with
+    /// `BatchLeafEncoding::PerBlob`, `FridaError::CellOutOfBounds` if the blob or a position
+    /// lies outside of the commitment, and `FridaError::InvalidBlobOpening` if the opening does
+    /// not lead to the root of the first layer.
+    pub fn verify_blob_opening(
+        &self,
+        opening: &BlobOpening<E, HRandom>,
+    ) -> Result<Vec<E>, FridaError> {
+        if self.poly_count < 2 || self.batch_leaf_encoding != BatchLeafEncoding::PerBlob {
+            return Err(FridaError::BlobOpeningUnsupported);
+        }
+        if let Some(&position) = opening
+            .positions
+            .iter()
+            .find(|&&position| opening.blob >= self.poly_count || position >= self.domain_size)
+        {
+            return Err(FridaError::CellOutOfBounds(opening.blob, position));
+        }
+        if opening.positions.is_empty() {
+            return Err(FridaError::InvalidBlobOpening);
+        }
+
+        let folding_factor = self.folding_factors[0];
+        let row_length = self.domain_size / folding_factor;
+        let indexes = fold_positions(&opening.positions, self.domain_size, folding_factor);
+        let leaves = opening
+            .leaves(indexes.len(), folding_factor, self.poly_count)
+            .ok_or(FridaError::InvalidBlobOpening)?;
+        let mut reader = SliceReader::new(&opening.paths);
+        let proof =
+            BatchMerkleProof::<HRandom>::deserialize(&mut reader, leaves, row_length.ilog2() as u8)
+                .map_err(|_| FridaError::InvalidBlobOpening)?;
+        if reader.has_more_bytes() {
+            return Err(FridaError::InvalidBlobOpening);
+        }
+        MerkleTree::verify_batch(&self.layer_commitments[0], &indexes, &proof)
+            .map_err(|_| FridaError::InvalidBlobOpening)?;
+
+        Ok(opening
+            .positions
+            .iter()
+            .map(|&position| {
+                let index = indexes
+                    .iter()
+                    .position(|&index| index == position % row_length)
+                    .expect("positions were folded into the opened leaves");
+                opening.values[index * folding_factor + position / row_length]
+            })
+            .collect())
+    }
+
+    /// Recovers the data of polynomial `poly_index` of the commitment of this verifier from
+    /// `samples` of its evaluations, given as `(position, evaluation)` pairs, without the
+    /// evaluations of the other polynomials of the batch. The samples should have been verified
+    /// beforehand, e.g. as the columns of receipts or cells.
+    ///
+    /// At least `domain_size / blowup_factor` distinct positions are needed; duplicated positions
+    /// only count once. The data must have been encoded with the default `EncodingOptions`.
+    pub fn reconstruct_poly(
+        &self,
+        poly_index: usize,
+        samples: &[(usize, E)],
+    ) -> Result<Vec<u8>, FridaError> {
+        if poly_index >= self.poly_count {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+
+        let mut samples = samples.to_vec();
+        samples.sort_unstable_by_key(|&(position, _)| position);
+        samples.dedup_by_key(|&mut (position, _)| position);
+        if let Some(&(position, _)) = samples.last() {
+            if position >= self.domain_size {
+                return Err(FridaError::PositionOutOfDomain(position, self.domain_size));
+            }
+        }
+
+        let (positions, evaluations): (Vec<usize>, Vec<E>) = samples.into_iter().unzip();
+        recover_data_from_evaluations(
+            &evaluations,
+            &positions,
+            self.domain_size,
+            self.options.blowup_factor(),
+        )
+    }
+
+    /// Verifies that `bytes` are found at the offset of `proof` in the blob it opens, which must
+    /// have been committed with the blowup factor of this verifier.
+    pub fn verify_byte_range(
+        &self,
+        proof: &ByteRangeProof<E>,
+        bytes: &[u8],
+    ) -> Result<(), FridaError> {
+        if proof.blob >= self.poly_count {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+        let end = proof
+            .offset
+            .checked_add(bytes.len())
+            .ok_or(FridaError::ByteRangeOutOfBounds(proof.offset, usize::MAX))?;
+        let range = proof.offset..end;
+
+        let positions =
+            byte_range_positions::<E>(&range, self.options.blowup_factor(), self.domain_size)?;
+        self.verify(&proof.proof, &proof.evaluations, &positions)?;
+
+        if decode_byte_range(&proof.blob_evaluations(self.poly_count), &range)? != bytes {
+            return Err(FridaError::ByteRangeMismatch);
+        }
+        Ok(())
+    }
+
+    /// Verifies that `opening` opens a data element of the blob of a single-blob commitment, and
+    /// that the element encodes `bytes` as the bytes of its range of the data. The data must have
+    /// been committed with the blowup factor of this verifier.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ValueOutOfBounds` if the element holds none of the data,
+    /// `FridaError::ByteRangeMismatch` if the range of the opening or `bytes` do not match the
+    /// element, and the errors of `verify` if the opening does not verify.
+    pub fn verify_value(&self, opening: &ValueOpening<E>, bytes: &[u8]) -> Result<(), FridaError> {
+        if self.poly_count != 1 {
+            return Err(FridaError::ProofPolyCountMismatch);
+        }
+        let range = value_byte_range(&opening.length_prefix, opening.index)?;
+        if range != opening.range || (opening.index == 0 && opening.value != opening.length_prefix)
+        {
+            return Err(FridaError::ByteRangeMismatch);
+        }
+
+        let positions = ValueOpening::<E>::positions(opening.index, self.options.blowup_factor());
+        if let Some(&position) = positions.last().filter(|&&p| p >= self.domain_size) {
+            return Err(FridaError::PositionOutOfDomain(position, self.domain_size));
+        }
+        let evaluations = opening.evaluations();
+        self.verify(&opening.proof, &evaluations, &positions)?;
+
+        if decode_byte_range(&evaluations, &range)? != bytes {
+            return Err(FridaError::ByteRangeMismatch);
+        }
+        Ok(())
     }
 
     /// Creates a new verifier instance from `ProverCommitment`.
     pub fn from_commitment(
-        commitment: &crate::prover::ProverCommitment<HRandom>,
+        commitment: &ProverCommitment<HRandom>,
         options: FriOptions,
     ) -> Result<Self, FridaError> {
+        Self::from_commitment_with_schedule(commitment, options, &[])
+    }
+
+    /// Same as `from_commitment`, for a commitment whose layers were folded by `schedule`.
+    pub fn from_commitment_with_schedule(
+        commitment: &ProverCommitment<HRandom>,
+        options: FriOptions,
+        schedule: &[usize],
+    ) -> Result<Self, FridaError> {
+        validate_folding_schedule(&options, schedule)?;
+        check_profile(commitment.profile, &options)?;
         let domain_size = commitment.domain_size;
+        let folding_factors = layer_folding_factors(&options, schedule, domain_size);
         let max_poly_degree = domain_size / options.blowup_factor() - 1;
 
+        let ood_evaluation = parse_ood_evaluation::<E>(&commitment.ood_evaluation)
+            .map_err(FridaError::DeserializationError)?;
+
         // Create a public coin and derive the challenges (`alpha` and `xi`) from the roots.
         let mut public_coin = FridaRandom::<E, HHst, HRandom>::new();
         let mut layer_alphas = Vec::with_capacity(commitment.roots.len());
         let mut xi = None;
+        let mut ood_sample = None;
 
         for (depth, root) in commitment.roots.iter().enumerate() {
             public_coin.reseed(&root.as_bytes());
             if depth == 0 && commitment.poly_count > 1 {
                 xi = Some(public_coin.draw_xi(commitment.poly_count)?);
             }
+            if let Some(value) = ood_evaluation.filter(|_| depth == 0) {
+                ood_sample = Some(draw_ood_sample(&mut public_coin, |_| value)?);
+            }
             let alpha = public_coin.draw()?;
             layer_alphas.push(alpha);
         }
@@ -193,19 +699,75 @@ where
             xi,
             layer_alphas,
             options,
-            // We set num_partitions to a default of 1; it's derived from the proof during verification anyway.
-            num_partitions: 1,
+            folding_factors,
             poly_count: commitment.poly_count,
+            app_ids_root: commitment.app_ids_root,
+            dispersal_hint: commitment.dispersal_hint,
+            ood_sample,
+            batch_leaf_encoding: commitment.batch_leaf_encoding,
+            num_partitions: commitment.num_partitions,
+            data_roots: commitment.data_roots.clone(),
+            profile: commitment.profile,
+            remainder: OnceLock::new(),
+            position_indexes: PositionIndexCache::new(POSITION_INDEX_CACHE_CAPACITY),
             _phantom_hash_hst: PhantomData,
             _phantom_hash_random: PhantomData,
         })
     }
 
+    /// Creates a verifier of a single-blob commitment whose folding challenges were sent by an
+    /// interactive verifier instead of being drawn from the roots; see `verifier::interactive`.
+    pub(crate) fn from_challenges(
+        commitment: &ProverCommitment<HRandom>,
+        options: FriOptions,
+        layer_alphas: Vec<E>,
+    ) -> Self {
+        let domain_size = commitment.domain_size;
+
+        Self {
+            max_poly_degree: domain_size / options.blowup_factor() - 1,
+            domain_size,
+            domain_generator: E::BaseField::get_root_of_unity(domain_size.ilog2()),
+            layer_commitments: commitment.roots.clone(),
+            xi: None,
+            layer_alphas,
+            folding_factors: layer_folding_factors(&options, &[], domain_size),
+            options,
+            poly_count: 1,
+            app_ids_root: None,
+            dispersal_hint: None,
+            ood_sample: None,
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            num_partitions: commitment.num_partitions,
+            data_roots: commitment.data_roots.clone(),
+            profile: commitment.profile,
+            remainder: OnceLock::new(),
+            position_indexes: PositionIndexCache::new(POSITION_INDEX_CACHE_CAPACITY),
+            _phantom_hash_hst: PhantomData,
+            _phantom_hash_random: PhantomData,
+        }
+    }
+
+    /// Checks that `proof` is made for the partitions of the commitment, rather than trusting the
+    /// number it carries.
+    fn check_num_partitions(&self, proof: &FridaProof) -> Result<(), FridaError> {
+        if proof.num_partitions() != self.num_partitions {
+            return Err(FridaError::PartitionMismatch(
+                self.num_partitions,
+                proof.num_partitions(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks `evaluations` at `positions` against the proof read from `channel`; the FRI layers
+    /// of `stream`, if any, are loaded into the channel one at a time as they are checked.
     fn check_auth(
         &self,
         channel: &mut FridaVerifierChannel<E, HRandom>,
         evaluations: &[E],
         positions: &[usize],
+        stream: Option<&FridaProof>,
     ) -> Result<(), VerifierError> {
         if evaluations.len() != positions.len() * channel.poly_count {
             return Err(VerifierError::NumPositionEvaluationMismatch(
@@ -214,14 +776,58 @@ where
             ));
         }
 
-        // static dispatch for folding factor parameter
-        let folding_factor = self.options.folding_factor();
+        self.verify_layers(channel, evaluations, positions, stream)
+    }
+
+    /// Reads the evaluations at `positions` from the first layer opened by the proof of
+    /// `verifier_channel`, whose layers are folded by `folding_factors`. Proofs without any
+    /// layer are evaluated from their remainder, over the domain shifted by `domain_offset`.
+    fn query_values_from_proof(
+        verifier_channel: &FridaVerifierChannel<E, HRandom>,
+        positions: &[usize],
+        domain_size: usize,
+        folding_factors: &[usize],
+        domain_offset: E::BaseField,
+    ) -> Result<Option<Vec<E>>, FridaError> {
+        let Some(&folding_factor) = folding_factors.first() else {
+            let remainder = verifier_channel.remainder();
+            let generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+            let values = positions
+                .iter()
+                .map(|&position| {
+                    let x = domain_offset * generator.exp_vartime((position as u64).into());
+                    eval_horner::<E>(remainder, x)
+                })
+                .collect();
+            return Ok(Some(values));
+        };
+        let folded_positions = fold_positions(positions, domain_size, folding_factor);
         match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
-            _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+            2 => Ok(Self::get_query_values_from_commitment::<2>(
+                verifier_channel,
+                positions,
+                &folded_positions,
+                domain_size,
+            )),
+            4 => Ok(Self::get_query_values_from_commitment::<4>(
+                verifier_channel,
+                positions,
+                &folded_positions,
+                domain_size,
+            )),
+            8 => Ok(Self::get_query_values_from_commitment::<8>(
+                verifier_channel,
+                positions,
+                &folded_positions,
+                domain_size,
+            )),
+            16 => Ok(Self::get_query_values_from_commitment::<16>(
+                verifier_channel,
+                positions,
+                &folded_positions,
+                domain_size,
+            )),
+            _ => Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
         }
     }
 
@@ -230,15 +836,13 @@ where
         positions: &[usize],
         folded_positions: &[usize],
         domain_size: usize,
-    ) -> Vec<E> {
+    ) -> Option<Vec<E>> {
         if verifier_channel.poly_count > 1 {
             let layer_values = verifier_channel
                 .batch_data
-                .as_ref()
-                .unwrap()
+                .as_ref()?
                 .batch_layer_queries
-                .as_ref()
-                .unwrap();
+                .as_ref()?;
 
             get_batch_query_values::<E, N>(
                 layer_values,
@@ -248,125 +852,70 @@ where
                 verifier_channel.poly_count,
             )
         } else {
-            let layer_values = group_slice_elements(&verifier_channel.layer_queries[0]);
+            let layer_values = group_slice_elements(verifier_channel.layer_queries.first()?);
             get_query_values::<E, N>(layer_values, positions, folded_positions, domain_size)
         }
     }
 
-    /// This is the actual implementation of the verification procedure described above, but it
-    /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
+    /// This is the actual implementation of the verification procedure described above. Every
+    /// layer is checked by `verify_layer` with its own folding factor.
+    fn verify_layers(
         &self,
         channel: &mut FridaVerifierChannel<E, HRandom>,
         evaluations: &[E],
         positions: &[usize],
+        stream: Option<&FridaProof>,
     ) -> Result<(), VerifierError> {
-        let original_domain_size = self.domain_size;
-        let poly_count = channel.poly_count;
-        let folding_factor = self.options.folding_factor();
         let domain_offset: E::BaseField = self.options.domain_offset();
 
-        let mut domain_generator = self.domain_generator;
-
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| domain_generator.exp_vartime(((original_domain_size / N * i) as u64).into()))
-            .collect::<Vec<_>>();
-
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_size = original_domain_size;
+        let mut layer = LayerState {
+            domain_generator: self.domain_generator,
+            domain_size: self.domain_size,
+            positions: positions.to_vec(),
+            evaluations: evaluations.to_vec(),
+        };
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
-        let mut positions = positions.to_vec();
-        let mut evaluations = evaluations.to_vec();
-
-        let num_fri_layers = self.options.num_fri_layers(original_domain_size);
-        for depth in 0..num_fri_layers {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions = fold_positions(&positions, domain_size, folding_factor);
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                folding_factor,
-                self.num_partitions,
-            );
-            // read query values from the specified indexes in the Merkle tree
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = if poly_count > 1 && depth == 0 {
-                let xi = self.xi.as_ref().expect("xi values not set");
-                let layer_values =
-                    channel.read_batch_layer_queries(&position_indexes, &layer_commitment)?;
-                let mut combined_layer_values: Vec<[E; N]> =
-                    vec![[E::default(); N]; layer_values.len() / poly_count / N];
-                iter_mut!(combined_layer_values, 1024)
-                    .enumerate()
-                    .for_each(|(i, b)| {
-                        iter_mut!(b, 1024).enumerate().for_each(|(j, f)| {
-                            let start = i * (poly_count * N) + poly_count * j;
-                            layer_values[start..start + poly_count]
-                                .iter()
-                                .enumerate()
-                                .for_each(|(j, e)| {
-                                    *f += *e * xi[j];
-                                });
-                        });
-                    });
-
-                let mut new_eval = vec![E::default(); evaluations.len() / poly_count];
-                iter_mut!(new_eval, 1024).enumerate().for_each(|(i, f)| {
-                    evaluations[i * poly_count..i * poly_count + poly_count]
-                        .iter()
-                        .enumerate()
-                        .for_each(|(j, e)| {
-                            *f += *e * xi[j];
-                        });
-                });
-                evaluations = new_eval;
-                combined_layer_values
-            } else {
-                channel.read_layer_queries(&position_indexes, &layer_commitment)?
-            };
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
-            }
 
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * domain_offset;
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
-            let alpha = self.layer_alphas[depth];
+        // every FRI layer has a commitment, and the remainder is committed to by the last root
+        let num_fri_layers = self.folding_factors.len();
+        if self.layer_commitments.len() != num_fri_layers + 1 {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+        // the batch layer, if any, is carried separately from the regular FRI layers
+        let batch_offset = usize::from(channel.batch_data.is_some());
+        let num_proof_layers = stream.map_or(channel.layer_queries.len(), FridaProof::num_layers);
+        if num_proof_layers != num_fri_layers - batch_offset {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+        for (depth, &folding_factor) in self.folding_factors.iter().enumerate() {
+            // streamed layers are parsed right before they are checked, and dropped once folded;
+            // a layer which cannot be parsed does not open its commitment
+            if let Some(proof) = stream.filter(|_| depth >= batch_offset) {
+                let domain_size = layer.domain_size / folding_factor;
+                channel
+                    .load_layer(proof, depth - batch_offset, domain_size, folding_factor)
+                    .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+            }
 
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+            // static dispatch for folding factor parameter
+            match folding_factor {
+                2 => self.verify_layer::<2>(channel, depth, &mut layer)?,
+                4 => self.verify_layer::<4>(channel, depth, &mut layer)?,
+                8 => self.verify_layer::<8>(channel, depth, &mut layer)?,
+                16 => self.verify_layer::<16>(channel, depth, &mut layer)?,
+                _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+            }
 
             // make sure next degree reduction does not result in degree truncation
-            if max_degree_plus_1 % N != 0 {
+            if !max_degree_plus_1.is_multiple_of(folding_factor) {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
-                    N,
+                    folding_factor,
                     depth,
                 ));
             }
-
-            // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
-            max_degree_plus_1 /= N;
-            domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
+            max_degree_plus_1 /= folding_factor;
         }
 
         // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
@@ -380,10 +929,18 @@ where
             ));
         }
 
-        for (&position, evaluation) in positions.iter().zip(evaluations) {
+        // the remainder is only checked at the queried positions below, so it must also be
+        // bound to the commitment made before the query positions were drawn
+        let remainder_commitment = HRandom::hash_elements(&remainder_poly);
+        if remainder_commitment != self.layer_commitments[num_fri_layers] {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
+        self.remainder.get_or_init(|| remainder_poly.clone());
+
+        for (&position, evaluation) in layer.positions.iter().zip(layer.evaluations) {
             let comp_eval = eval_horner::<E>(
                 &remainder_poly,
-                domain_offset * domain_generator.exp_vartime((position as u64).into()),
+                domain_offset * layer.domain_generator.exp_vartime((position as u64).into()),
             );
             if comp_eval != evaluation {
                 return Err(VerifierError::InvalidRemainderFolding);
@@ -393,22 +950,114 @@ where
         Ok(())
     }
 
+    /// Checks the layer at `depth` against the evaluations carried in `layer`, and folds them by
+    /// the folding factor N of this layer into the evaluations of the next one.
+    fn verify_layer<const N: usize>(
+        &self,
+        channel: &mut FridaVerifierChannel<E, HRandom>,
+        depth: usize,
+        layer: &mut LayerState<E>,
+    ) -> Result<(), VerifierError> {
+        let poly_count = channel.poly_count;
+        let domain_offset: E::BaseField = self.options.domain_offset();
+        let domain_size = layer.domain_size;
+
+        // roots of unity used in computing x coordinates in the folded domain
+        let folding_roots = (0..N)
+            .map(|i| {
+                self.domain_generator
+                    .exp_vartime(((self.domain_size / N * i) as u64).into())
+            })
+            .collect::<Vec<_>>();
+
+        // determine which evaluations were queried in the folded layer
+        let mut folded_positions = fold_positions(&layer.positions, domain_size, N);
+        // determine where these evaluations are in the commitment Merkle tree, whose leaves are
+        // laid out by the partitions of the proof
+        let num_partitions = channel.read_fri_num_partitions();
+        let position_indexes =
+            self.map_positions_to_indexes(&folded_positions, domain_size, N, num_partitions);
+        // read query values from the specified indexes in the Merkle tree
+        let layer_commitment = self.layer_commitments[depth];
+        // TODO: add layer depth to the potential error message
+        let mut layer_values = if poly_count > 1 && depth == 0 {
+            let xi = self.xi.as_ref().expect("xi values not set");
+            let layer_values =
+                channel.read_batch_layer_queries(&position_indexes, &layer_commitment)?;
+            let combined_values = combine_rows(&layer_values, xi);
+            layer.evaluations = combine_rows(&layer.evaluations, xi);
+
+            let num_rows = combined_values.len() / N;
+            group_slice_elements::<E, N>(&combined_values[..num_rows * N]).to_vec()
+        } else {
+            channel.read_layer_queries(&position_indexes, &layer_commitment)?
+        };
+        let query_values = get_query_values::<E, N>(
+            &layer_values,
+            &layer.positions,
+            &folded_positions,
+            domain_size,
+        )
+        .ok_or(VerifierError::LayerCommitmentMismatch)?;
+        if layer.evaluations != query_values {
+            return Err(VerifierError::InvalidLayerFolding(depth));
+        }
+
+        // build a set of x coordinates for each row polynomial
+        #[rustfmt::skip]
+        let xs = folded_positions.iter().map(|&i| {
+            let xe = layer.domain_generator.exp_vartime((i as u64).into()) * domain_offset;
+            folding_roots.iter()
+                .map(|&r| E::from(xe * r))
+                .collect::<Vec<_>>().try_into().unwrap()
+        })
+        .collect::<Vec<[E; N]>>();
+
+        // the first layer is folded as its DEEP quotient by the out-of-domain evaluation, if any
+        if let Some(sample) = self.ood_sample.as_ref().filter(|_| depth == 0) {
+            for (row, row_xs) in layer_values.iter_mut().zip(&xs) {
+                for (value, &x) in row.iter_mut().zip(row_xs) {
+                    *value = sample.quotient(x, *value);
+                }
+            }
+        }
+
+        // interpolate x and y values into row polynomials
+        let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+
+        // calculate the pseudo-random value used for linear combination in layer folding
+        let alpha = self.layer_alphas[depth];
+
+        // check that when the polynomials are evaluated at alpha, the result is equal to
+        // the corresponding column value
+        layer.evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+
+        // update variables for the next layer
+        layer.domain_generator = layer.domain_generator.exp_vartime((N as u32).into());
+        layer.domain_size /= N;
+        mem::swap(&mut layer.positions, &mut folded_positions);
+        Ok(())
+    }
+
     /// Constructs a pseudorandom public coin and draws values for the verifier in the pedefined order,
     /// reseeding it appropriately.
     fn draw_randomly(
         das_commitment: &Commitment<HRandom>,
         max_poly_degree: usize,
-        folding_factor: usize,
+        folding_factors: &[usize],
         domain_size: usize,
     ) -> DrawResult<E, HHst, HRandom> {
         let mut public_coin = FridaRandom::<E, HHst, HRandom>::new();
 
         let poly_count = das_commitment.poly_count;
+        let ood_evaluation = parse_ood_evaluation::<E>(&das_commitment.ood_evaluation)
+            .map_err(FridaError::DeserializationError)?;
 
         // read layer commitments from the channel and use them to build a list of alphas
         let alpha_commitments = &das_commitment.roots[..];
 
         let mut xi = None;
+        let mut ood_sample = None;
         let mut layer_alphas = Vec::with_capacity(alpha_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in alpha_commitments.iter().enumerate() {
@@ -416,20 +1065,27 @@ where
             if depth == 0 && poly_count > 1 {
                 xi = Some(public_coin.draw_xi(poly_count)?)
             }
+            if let Some(value) = ood_evaluation.filter(|_| depth == 0) {
+                ood_sample = Some(draw_ood_sample(&mut public_coin, |_| value)?);
+            }
 
             let alpha = public_coin.draw()?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != alpha_commitments.len() - 1 && max_degree_plus_1 % folding_factor != 0 {
-                return Err(FridaError::DegreeTruncation(
-                    max_degree_plus_1 - 1,
-                    folding_factor,
-                    depth,
-                ));
+            if let Some(&folding_factor) = folding_factors.get(depth) {
+                if depth != alpha_commitments.len() - 1
+                    && !max_degree_plus_1.is_multiple_of(folding_factor)
+                {
+                    return Err(FridaError::DegreeTruncation(
+                        max_degree_plus_1 - 1,
+                        folding_factor,
+                        depth,
+                    ));
+                }
+                max_degree_plus_1 /= folding_factor;
             }
-            max_degree_plus_1 /= folding_factor;
         }
 
         let positions =
@@ -437,6 +1093,7 @@ where
         Ok((
             RandomlyDrawn {
                 xi,
+                ood_sample,
                 layer_alphas,
                 positions,
             },
@@ -444,8 +1101,176 @@ where
         ))
     }
 
-    #[cfg(test)]
+    /// Largest proof accepted for an opening of `num_positions` positions of the commitment of
+    /// this verifier.
+    pub fn proof_limits(&self, num_positions: usize) -> ProofLimits {
+        ProofLimits::new::<E, HRandom>(
+            &self.options,
+            self.domain_size,
+            &self.folding_factors,
+            self.poly_count,
+            num_positions,
+        )
+    }
+
+    /// Size of the evaluation domain of the commitment.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns the root digest of the commitment of this verifier; see
+    /// `ProverCommitment::root_digest`.
+    pub fn root_digest(&self) -> [u8; 32] {
+        self.commitment().root_digest()
+    }
+
+    /// Rebuilds the commitment of this verifier, without data checksums, to compute its binding.
+    fn commitment(&self) -> ProverCommitment<HRandom> {
+        ProverCommitment {
+            roots: self.layer_commitments.clone(),
+            domain_size: self.domain_size,
+            poly_count: self.poly_count,
+            data_checksums: Vec::new(),
+            app_ids_root: self.app_ids_root,
+            dispersal_hint: self.dispersal_hint,
+            ood_evaluation: ood_evaluation_bytes(self.ood_sample.as_ref()),
+            batch_leaf_encoding: self.batch_leaf_encoding,
+            num_partitions: self.num_partitions,
+            data_roots: self.data_roots.clone(),
+            profile: self.profile,
+        }
+    }
+
+    /// Checks that `digest`, e.g. the key a commitment was stored under or the subject of an
+    /// attestation, identifies the commitment of this verifier.
+    ///
+    /// # Errors
+    /// Returns `FridaError::RootDigestMismatch` if the digest was computed over other roots or
+    /// parameters.
+    pub fn check_root_digest(&self, digest: &[u8; 32]) -> Result<(), FridaError> {
+        if self.root_digest() != *digest {
+            return Err(FridaError::RootDigestMismatch);
+        }
+        Ok(())
+    }
+
+    /// Number of polynomials committed to; 1 unless the commitment is batched.
+    pub fn poly_count(&self) -> usize {
+        self.poly_count
+    }
+
+    /// Options the commitment was built with.
+    pub fn options(&self) -> &FriOptions {
+        &self.options
+    }
+
+    /// Roots of the FRI layers of the commitment, followed by the commitment to its remainder.
+    pub fn layer_commitments(&self) -> &[HRandom::Digest] {
+        &self.layer_commitments
+    }
+
+    /// Folding factor of every FRI layer, the batch layer included.
+    pub fn folding_factors(&self) -> &[usize] {
+        &self.folding_factors
+    }
+
+    /// Folding randomness drawn after every root of the commitment, in order.
     pub fn layer_alphas(&self) -> &Vec<E> {
         &self.layer_alphas
     }
+
+    /// Coefficients combining the polynomials of a batched commitment into its first layer.
+    pub fn xi(&self) -> Option<&[E]> {
+        self.xi.as_deref()
+    }
+
+    /// Combines the evaluations of every polynomial of the batch at a number of points, laid out
+    /// as for `combine_batch_values`, with the coefficients `xi` of the commitment, e.g. the
+    /// values of the batch layer parsed from a proof with `FridaProof::parse_batch_layer`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ProofPolyCountMismatch` if the commitment is not batched, and the
+    /// errors of `combine_batch_values` otherwise.
+    pub fn combine_batch_evaluations(&self, values: &[E]) -> Result<Vec<E>, FridaError> {
+        let xi = self.xi.as_ref().ok_or(FridaError::ProofPolyCountMismatch)?;
+        combine_batch_values(values, xi)
+    }
+
+    /// Out-of-domain sample of the first layer, if the commitment was built with an OOD check.
+    pub fn ood_sample(&self) -> Option<&OodSample<E>> {
+        self.ood_sample.as_ref()
+    }
+
+    /// Returns this verifier if its commitment carries an out-of-domain evaluation. Verifiers
+    /// relying on the OOD check for their soundness, e.g. with low blowup factors, must require it,
+    /// as a prover may otherwise commit without one and be verified by plain FRI.
+    ///
+    /// # Errors
+    /// Returns `FridaError::OodEvaluationMissing` if the commitment was built without OOD check.
+    pub fn require_ood(self) -> Result<Self, FridaError> {
+        if self.ood_sample.is_none() {
+            return Err(FridaError::OodEvaluationMissing);
+        }
+        Ok(self)
+    }
+
+    /// Leaf encoding of the first layer of the commitment.
+    pub fn batch_leaf_encoding(&self) -> BatchLeafEncoding {
+        self.batch_leaf_encoding
+    }
+
+    /// Partitions the layers of the commitment are split into.
+    pub fn num_partitions(&self) -> usize {
+        self.num_partitions
+    }
+
+    /// Data roots of the commitment, if it was made with them, against which applications check
+    /// `DataInclusionProof`s; they are part of the root digest of the commitment.
+    pub fn data_roots(&self) -> Option<&DataRoots<HRandom>> {
+        self.data_roots.as_ref()
+    }
+
+    /// FRI parameters recorded in the commitment, if it was made with a commit profile.
+    pub fn profile(&self) -> Option<CommitProfile> {
+        self.profile
+    }
+
+    /// Counters of the Merkle indexes of position sets cached by this verifier, which are reused
+    /// by every proof verified for the same positions.
+    pub fn position_index_cache_metrics(&self) -> PositionIndexCacheMetrics {
+        self.position_indexes.metrics()
+    }
+
+    /// Maps `folded_positions` into the Merkle tree of a layer, reusing the indexes of an earlier
+    /// proof for the same positions.
+    pub(crate) fn map_positions_to_indexes(
+        &self,
+        folded_positions: &[usize],
+        domain_size: usize,
+        folding_factor: usize,
+        num_partitions: usize,
+    ) -> Arc<[usize]> {
+        self.position_indexes.map_positions_to_indexes(
+            folded_positions,
+            domain_size,
+            folding_factor,
+            num_partitions,
+        )
+    }
+
+    #[cfg(test)]
+    pub fn test_xi_mut(&mut self) -> Option<&mut Vec<E>> {
+        self.xi.as_mut()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that a commitment made with `profile`, if any, is verified with the options it records.
+fn check_profile(profile: Option<CommitProfile>, options: &FriOptions) -> Result<(), FridaError> {
+    match profile {
+        Some(profile) if profile.options() != *options => Err(FridaError::ProfileMismatch),
+        _ => Ok(()),
+    }
 }
@@ -0,0 +1,169 @@
+use winter_crypto::{
+    hashers::{Blake3_256, Sha3_256},
+    ElementHasher,
+};
+use winter_fri::FriOptions;
+use winter_math::{
+    fields::{f128, f64},
+    FieldElement,
+};
+use winter_utils::{Deserializable, DeserializationError, SliceReader};
+
+use super::das::FridaDasVerifier;
+#[cfg(feature = "f31")]
+use crate::core::f31;
+#[cfg(feature = "keccak")]
+use crate::core::keccak::Keccak256;
+use crate::{
+    error::FridaError,
+    prover::{proof::FridaProof, Commitment},
+};
+
+// PARAMETER IDS
+// ================================================================================================
+
+/// Field of the evaluations of a commitment, as selected at runtime by `verify_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FieldId {
+    /// The 64-bit field `winter_math::fields::f64`.
+    F64 = 0,
+    /// The 128-bit field `winter_math::fields::f128`.
+    F128 = 1,
+    /// The degree-4 extension of the 31-bit field `core::f31`, with the `f31` feature; rejected
+    /// as unsupported otherwise.
+    F31x4 = 2,
+}
+
+impl TryFrom<u8> for FieldId {
+    type Error = FridaError;
+
+    fn try_from(id: u8) -> Result<Self, FridaError> {
+        match id {
+            0 => Ok(FieldId::F64),
+            1 => Ok(FieldId::F128),
+            2 => Ok(FieldId::F31x4),
+            _ => Err(FridaError::UnsupportedFieldId(id)),
+        }
+    }
+}
+
+/// Hasher of the Merkle trees and transcript of a commitment, as selected at runtime by
+/// `verify_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum HasherId {
+    Blake3_256 = 0,
+    Sha3_256 = 1,
+    /// `core::keccak::Keccak256`, for commitments also checked by a Solidity verifier, with the
+    /// `keccak` feature; rejected as unsupported otherwise.
+    Keccak256 = 2,
+}
+
+impl TryFrom<u8> for HasherId {
+    type Error = FridaError;
+
+    fn try_from(id: u8) -> Result<Self, FridaError> {
+        match id {
+            0 => Ok(HasherId::Blake3_256),
+            1 => Ok(HasherId::Sha3_256),
+            2 => Ok(HasherId::Keccak256),
+            _ => Err(FridaError::UnsupportedHasherId(id)),
+        }
+    }
+}
+
+// BYTE-LEVEL VERIFICATION
+// ================================================================================================
+
+/// Verifies that the evaluations at `positions` are opened by a proof against a commitment, all
+/// of them serialized, selecting the field and hasher they were made with at runtime. FFI, WASM
+/// and service layers can then expose a single entry point for every parameter set.
+///
+/// `commitment_bytes` is a serialized `Commitment`, made with `options` and hashed with
+/// `hasher_id` for both its Merkle trees and its transcript, and `proof_bytes` a serialized
+/// `FridaProof`. `evals_bytes` concatenates the serialized evaluations, laid out as for
+/// `FridaDasVerifier::verify`, each taking `ELEMENT_BYTES` bytes of the field.
+///
+/// # Errors
+/// Returns `FridaError::DeserializationError` if any input cannot be deserialized in the selected
+/// field, and the errors of `FridaDasVerifier::new` and `FridaDasVerifier::verify` otherwise.
+pub fn verify_bytes(
+    field_id: FieldId,
+    hasher_id: HasherId,
+    options: &FriOptions,
+    commitment_bytes: &[u8],
+    proof_bytes: &[u8],
+    positions: &[usize],
+    evals_bytes: &[u8],
+) -> Result<(), FridaError> {
+    let inputs = Inputs {
+        options,
+        commitment_bytes,
+        proof_bytes,
+        positions,
+        evals_bytes,
+    };
+    match field_id {
+        FieldId::F64 => inputs.verify_in_field::<f64::BaseElement>(hasher_id),
+        FieldId::F128 => inputs.verify_in_field::<f128::BaseElement>(hasher_id),
+        #[cfg(feature = "f31")]
+        FieldId::F31x4 => inputs.verify_in_field::<f31::QuarticElement>(hasher_id),
+        #[cfg(not(feature = "f31"))]
+        FieldId::F31x4 => Err(FridaError::UnsupportedFieldId(FieldId::F31x4 as u8)),
+    }
+}
+
+/// Serialized inputs of `verify_bytes`, common to every instantiation.
+struct Inputs<'a> {
+    options: &'a FriOptions,
+    commitment_bytes: &'a [u8],
+    proof_bytes: &'a [u8],
+    positions: &'a [usize],
+    evals_bytes: &'a [u8],
+}
+
+impl Inputs<'_> {
+    fn verify_in_field<E: FieldElement>(&self, hasher_id: HasherId) -> Result<(), FridaError> {
+        match hasher_id {
+            HasherId::Blake3_256 => self.verify::<E, Blake3_256<E::BaseField>>(),
+            HasherId::Sha3_256 => self.verify::<E, Sha3_256<E::BaseField>>(),
+            #[cfg(feature = "keccak")]
+            HasherId::Keccak256 => self.verify::<E, Keccak256<E::BaseField>>(),
+            #[cfg(not(feature = "keccak"))]
+            HasherId::Keccak256 => Err(FridaError::UnsupportedHasherId(HasherId::Keccak256 as u8)),
+        }
+    }
+
+    fn verify<E, H>(&self) -> Result<(), FridaError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let commitment = Commitment::<H>::read_from_bytes(self.commitment_bytes)
+            .map_err(FridaError::DeserializationError)?;
+        if self.evals_bytes.len() % E::ELEMENT_BYTES != 0 {
+            return Err(FridaError::DeserializationError(
+                DeserializationError::InvalidValue(format!(
+                    "{} evaluation bytes are not a whole number of {} byte elements",
+                    self.evals_bytes.len(),
+                    E::ELEMENT_BYTES
+                )),
+            ));
+        }
+        let evaluations = self
+            .evals_bytes
+            .chunks(E::ELEMENT_BYTES)
+            .map(E::read_from_bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(FridaError::DeserializationError)?;
+
+        let (verifier, _) = FridaDasVerifier::<E, H, H>::new(commitment, self.options.clone())?;
+        let proof = FridaProof::read_with_limits(
+            &mut SliceReader::new(self.proof_bytes),
+            &verifier.proof_limits(self.positions.len()),
+        )
+        .map_err(FridaError::DeserializationError)?;
+        verifier.verify(&proof, &evaluations, self.positions)
+    }
+}
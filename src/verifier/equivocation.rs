@@ -0,0 +1,204 @@
+use winter_crypto::{ElementHasher, Hasher};
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{
+    cross::{cross_positions, shared_domain_size},
+    das::FridaDasVerifier,
+};
+use crate::{
+    error::FridaError,
+    prover::{proof::FridaProof, ProverCommitment},
+};
+
+// EQUIVOCATION PROOF
+// ================================================================================================
+
+// Two commitments claimed for the same blob of a block must commit to the same polynomial, even if
+// they were made over domains of different sizes: evaluated at the same point, e.g. at a position
+// of their shared domain (see `cross::shared_domain_size`), they agree. Valid openings of both
+// commitments which disagree at a shared position are therefore evidence that at least one of them
+// was not committed to the claimed blob, which slashing integrations can act upon.
+
+/// Opening of one of two commitments claimed for the same blob, at the shared positions of an
+/// equivocation check.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EquivocationOpening<E: FieldElement, H: Hasher> {
+    pub commitment: ProverCommitment<H>,
+    /// Evaluations at the shared positions mapped to the domain of `commitment` with
+    /// `cross::cross_positions`, laid out as for `FridaDasVerifier::verify`.
+    pub evaluations: Vec<E>,
+    /// Proof opening `commitment` at the mapped positions.
+    pub proof: FridaProof,
+}
+
+/// Evidence that two commitments claimed for blob `blob_index` of `block` are not commitments to
+/// the same data: both are validly opened at `shared_positions`, and the evaluations of the blob
+/// differ at one of them at least.
+///
+/// In a batched commitment, the blob is the polynomial at `blob_index`; a commitment to a single
+/// blob holds it as its only polynomial. That both commitments were claimed for the blob, e.g. by
+/// signed block proposals, must be established by the integration.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EquivocationProof<E: FieldElement, H: Hasher> {
+    pub block: u64,
+    pub blob_index: usize,
+    pub shared_positions: Vec<usize>,
+    pub first: EquivocationOpening<E, H>,
+    pub second: EquivocationOpening<E, H>,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> EquivocationProof<E, H> {
+    /// Checks both openings, made with `options` and with `HHst` as the transcript hasher, and
+    /// returns the first shared position at which they disagree on the blob.
+    ///
+    /// # Errors
+    /// Returns an error if either opening does not verify, if the blob is not committed to by
+    /// either commitment, or `FridaError::NoEquivocation` if the openings agree at every shared
+    /// position.
+    pub fn verify<HHst>(&self, options: &FriOptions) -> Result<usize, FridaError>
+    where
+        HHst: ElementHasher<BaseField = E::BaseField>,
+    {
+        let shared_size = shared_domain_size(
+            self.first.commitment.domain_size,
+            self.second.commitment.domain_size,
+        );
+        if let Some(&position) = self.shared_positions.iter().find(|&&p| p >= shared_size) {
+            return Err(FridaError::PositionOutOfDomain(position, shared_size));
+        }
+
+        let first = self.blob_evaluations::<HHst>(&self.first, shared_size, options)?;
+        let second = self.blob_evaluations::<HHst>(&self.second, shared_size, options)?;
+        first
+            .iter()
+            .zip(second.iter())
+            .position(|(a, b)| a != b)
+            .map(|i| self.shared_positions[i])
+            .ok_or(FridaError::NoEquivocation)
+    }
+
+    /// Verifies `opening` and returns the evaluations of the blob at the shared positions.
+    fn blob_evaluations<HHst>(
+        &self,
+        opening: &EquivocationOpening<E, H>,
+        shared_size: usize,
+        options: &FriOptions,
+    ) -> Result<Vec<E>, FridaError>
+    where
+        HHst: ElementHasher<BaseField = E::BaseField>,
+    {
+        let commitment = &opening.commitment;
+        let poly_index = match commitment.poly_count {
+            1 => 0,
+            poly_count if self.blob_index < poly_count => self.blob_index,
+            _ => return Err(FridaError::ProofPolyCountMismatch),
+        };
+
+        let verifier =
+            FridaDasVerifier::<E, HHst, H>::from_commitment(commitment, options.clone())?;
+        let positions =
+            cross_positions(&self.shared_positions, shared_size, commitment.domain_size)?;
+        verifier.verify(&opening.proof, &opening.evaluations, &positions)?;
+
+        Ok(opening
+            .evaluations
+            .iter()
+            .skip(poly_index)
+            .step_by(commitment.poly_count)
+            .copied()
+            .collect())
+    }
+}
+
+/// Checks two openings of commitments claimed for blob `blob_index` of `block` at the same
+/// `shared_positions`, and returns an `EquivocationProof` if their evaluations of the blob differ,
+/// or `None` if they agree.
+///
+/// Every opening must open its commitment at the shared positions mapped to its domain with
+/// `cross::cross_positions`; commitments are checked with `options` and with `HHst` as the
+/// transcript hasher.
+///
+/// # Errors
+/// Returns an error if either opening does not verify, as no conclusion can be drawn from it, or
+/// if the blob is not committed to by either commitment.
+pub fn detect_equivocation<E, HHst, H>(
+    block: u64,
+    blob_index: usize,
+    shared_positions: &[usize],
+    first: EquivocationOpening<E, H>,
+    second: EquivocationOpening<E, H>,
+    options: &FriOptions,
+) -> Result<Option<EquivocationProof<E, H>>, FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let proof = EquivocationProof {
+        block,
+        blob_index,
+        shared_positions: shared_positions.to_vec(),
+        first,
+        second,
+    };
+    match proof.verify::<HHst>(options) {
+        Ok(_) => Ok(Some(proof)),
+        Err(FridaError::NoEquivocation) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement, H: Hasher> Serializable for EquivocationOpening<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.commitment.write_into(target);
+        self.evaluations.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for EquivocationOpening<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let commitment = ProverCommitment::<H>::read_from(source)?;
+        let evaluations = Vec::<E>::read_from(source)?;
+        let proof = FridaProof::read_from(source)?;
+
+        Ok(EquivocationOpening {
+            commitment,
+            evaluations,
+            proof,
+        })
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Serializable for EquivocationProof<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block.write_into(target);
+        self.blob_index.write_into(target);
+        self.shared_positions.write_into(target);
+        self.first.write_into(target);
+        self.second.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for EquivocationProof<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block = u64::read_from(source)?;
+        let blob_index = usize::read_from(source)?;
+        let shared_positions = Vec::<usize>::read_from(source)?;
+        let first = EquivocationOpening::read_from(source)?;
+        let second = EquivocationOpening::read_from(source)?;
+
+        Ok(EquivocationProof {
+            block,
+            blob_index,
+            shared_positions,
+            first,
+            second,
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use winter_fri::utils::map_positions_to_indexes;
+
+/// Number of position sets whose Merkle indexes are kept by every `FridaDasVerifier`, enough for
+/// the layers of a few distinct position sets verified repeatedly.
+pub(crate) const POSITION_INDEX_CACHE_CAPACITY: usize = 64;
+
+/// Counters of the position index cache of a `FridaDasVerifier`; see
+/// `FridaDasVerifier::position_index_cache_metrics`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PositionIndexCacheMetrics {
+    /// Position sets currently cached.
+    pub entries: usize,
+    /// Indexes returned from the cache.
+    pub hits: u64,
+    /// Indexes computed because they were not cached.
+    pub misses: u64,
+}
+
+/// Position set of a layer, along with the layout of the layer it is mapped into.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    domain_size: usize,
+    folding_factor: usize,
+    num_partitions: usize,
+    folded_positions: Vec<usize>,
+}
+
+// POSITION INDEX CACHE
+// ================================================================================================
+
+/// Results of `map_positions_to_indexes` keyed by their arguments, so that the many proofs checked
+/// for the same positions of a commitment, e.g. by a light node re-verifying the samples it holds,
+/// do not map them into the Merkle trees of every layer again.
+///
+/// Lookups only take a shared lock and return the cached indexes behind an `Arc`, so verifiers
+/// shared between threads do not serialise on cache hits. Once full, an arbitrary entry is evicted
+/// for every new position set.
+#[derive(Debug)]
+pub(crate) struct PositionIndexCache {
+    capacity: usize,
+    entries: RwLock<HashMap<Key, Arc<[usize]>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl PositionIndexCache {
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub(crate) fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "position index cache capacity must be greater than zero"
+        );
+        PositionIndexCache {
+            capacity,
+            entries: RwLock::new(HashMap::with_capacity(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> PositionIndexCacheMetrics {
+        PositionIndexCacheMetrics {
+            entries: self.entries.read().unwrap().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the result of `map_positions_to_indexes` for the given arguments, computing and
+    /// caching it if it was not cached.
+    pub(crate) fn map_positions_to_indexes(
+        &self,
+        folded_positions: &[usize],
+        domain_size: usize,
+        folding_factor: usize,
+        num_partitions: usize,
+    ) -> Arc<[usize]> {
+        let key = Key {
+            domain_size,
+            folding_factor,
+            num_partitions,
+            folded_positions: folded_positions.to_vec(),
+        };
+        if let Some(indexes) = self.entries.read().unwrap().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return indexes.clone();
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let indexes: Arc<[usize]> = map_positions_to_indexes(
+            folded_positions,
+            domain_size,
+            folding_factor,
+            num_partitions,
+        )
+        .into();
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() == self.capacity && !entries.contains_key(&key) {
+            let evicted = entries.keys().next().cloned().expect("cache is not empty");
+            entries.remove(&evicted);
+        }
+        entries.insert(key, indexes.clone());
+        indexes
+    }
+}
@@ -0,0 +1,175 @@
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+use winter_rand_utils::rand_value;
+
+use super::das::FridaDasVerifier;
+use crate::{
+    core::schedule::layer_folding_factors,
+    error::FridaError,
+    prover::{
+        interactive::{InMemoryTransport, InteractiveProver, Message, Party},
+        BatchLeafEncoding, ProverCommitment,
+    },
+};
+
+// INTERACTIVE VERIFIER
+// ================================================================================================
+
+/// Verifier taking part in an interactive run of the protocol, which answers every layer root
+/// with a challenge of its own randomness and queries random positions once the remainder is
+/// received.
+///
+/// Runs are meant for teaching and protocol analysis: commitments made this way are only
+/// convincing to the verifier which took part in them.
+#[derive(Debug)]
+pub struct InteractiveVerifier<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    options: FriOptions,
+    domain_size: usize,
+    num_queries: usize,
+    num_layers: usize,
+    /// Roots of the committed layers, followed by the hash of the remainder once received.
+    roots: Vec<H::Digest>,
+    challenges: Vec<E>,
+    positions: Vec<usize>,
+}
+
+impl<E, H> InteractiveVerifier<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Creates a verifier of a blob encoded over `domain_size` elements, which queries
+    /// `num_queries` random positions.
+    ///
+    /// # Errors
+    /// Returns `FridaError::BadNumQueries` unless `num_queries` is positive and smaller than the
+    /// domain, and `FridaError::NotEnoughDataPoints` if the domain is too small to be folded.
+    pub fn new(
+        options: FriOptions,
+        domain_size: usize,
+        num_queries: usize,
+    ) -> Result<Self, FridaError> {
+        if num_queries == 0 || num_queries >= domain_size {
+            return Err(FridaError::BadNumQueries(num_queries));
+        }
+        let num_layers = layer_folding_factors(&options, &[], domain_size).len();
+        if num_layers == 0 {
+            return Err(FridaError::NotEnoughDataPoints());
+        }
+
+        Ok(InteractiveVerifier {
+            options,
+            domain_size,
+            num_queries,
+            num_layers,
+            roots: Vec::new(),
+            challenges: Vec::new(),
+            positions: Vec::new(),
+        })
+    }
+
+    /// Handles a message of the prover, and returns the message to answer it with, or `None`
+    /// once the answer to the queries was accepted.
+    ///
+    /// # Errors
+    /// Returns `FridaError::UnexpectedMessage` if `message` does not fit the current round, and
+    /// `FridaError::FailToVerify` if the answer to the queries is rejected.
+    pub fn respond(
+        &mut self,
+        message: Message<E, H::Digest>,
+    ) -> Result<Option<Message<E, H::Digest>>, FridaError> {
+        match message {
+            Message::LayerRoot(root) if self.roots.len() < self.num_layers => {
+                self.roots.push(root);
+                let alpha = rand_value::<E>();
+                self.challenges.push(alpha);
+                Ok(Some(Message::Challenge(alpha)))
+            }
+            Message::Remainder(remainder) if self.roots.len() == self.num_layers => {
+                self.roots.push(H::hash_elements(&remainder));
+                let mut positions = (0..self.num_queries)
+                    .map(|_| rand_value::<u64>() as usize % self.domain_size)
+                    .collect::<Vec<_>>();
+                positions.sort_unstable();
+                positions.dedup();
+                self.positions = positions;
+                Ok(Some(Message::Queries(self.positions.clone())))
+            }
+            Message::Answer { evaluations, proof } if self.roots.len() > self.num_layers => {
+                let commitment = ProverCommitment::<H> {
+                    roots: self.roots.clone(),
+                    domain_size: self.domain_size,
+                    poly_count: 1,
+                    data_checksums: Vec::new(),
+                    app_ids_root: None,
+                    ood_evaluation: Vec::new(),
+                    batch_leaf_encoding: BatchLeafEncoding::Flat,
+                    num_partitions: 1,
+                    dispersal_hint: None,
+                    data_roots: None,
+                    profile: None,
+                };
+                let verifier = FridaDasVerifier::<E, H, H>::from_challenges(
+                    &commitment,
+                    self.options.clone(),
+                    self.challenges.clone(),
+                );
+                verifier.verify(&proof, &evaluations, &self.positions)?;
+                Ok(None)
+            }
+            _ => Err(FridaError::UnexpectedMessage),
+        }
+    }
+
+    // STATE
+    // --------------------------------------------------------------------------------------------
+
+    /// Roots received so far, followed by the hash of the remainder once received.
+    pub fn roots(&self) -> &[H::Digest] {
+        &self.roots
+    }
+
+    /// Challenges sent so far, one per layer root.
+    pub fn challenges(&self) -> &[E] {
+        &self.challenges
+    }
+
+    /// Positions queried once the remainder was received, or empty before.
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+}
+
+/// Runs `prover` and `verifier` against each other over `transport`, until the verifier accepts
+/// the answer to its queries or either party fails.
+pub fn run_interactive<E, H>(
+    prover: &mut InteractiveProver<E, H>,
+    verifier: &mut InteractiveVerifier<E, H>,
+    transport: &mut InMemoryTransport<E, H::Digest>,
+) -> Result<(), FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let mut message = prover.respond(None)?;
+    loop {
+        transport.send(Party::Prover, message);
+        let received = transport
+            .receive(Party::Verifier)
+            .ok_or(FridaError::UnexpectedMessage)?;
+        let Some(reply) = verifier.respond(received)? else {
+            return Ok(());
+        };
+
+        transport.send(Party::Verifier, reply);
+        let received = transport
+            .receive(Party::Prover)
+            .ok_or(FridaError::UnexpectedMessage)?;
+        message = prover.respond(Some(received))?;
+    }
+}
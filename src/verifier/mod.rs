@@ -1,31 +1,53 @@
 use winter_math::FieldElement;
 
+use crate::{
+    error::FridaError,
+    utils::{alloc::output_vector, parallel::ParallelOptions},
+};
+
 mod channel;
 
+pub mod adaptive;
+pub mod attestation;
+pub mod cross;
 pub mod das;
+pub mod dispatch;
+pub mod equivocation;
+pub mod index_cache;
+pub mod interactive;
+pub mod multiproof;
+pub mod oblivious;
+pub mod recursive;
+pub mod report;
+pub mod stir;
+pub mod thresholds;
+pub mod traits;
+
+pub use traits::FridaVerifier;
 
 #[cfg(test)]
 mod tests;
 
+/// Picks the evaluations at `positions` out of the queried rows of a layer. Returns `None` if the
+/// rows do not cover all of the positions, which can only happen for a malformed proof.
 fn get_query_values<E: FieldElement, const N: usize>(
     values: &[[E; N]],
     positions: &[usize],
     folded_positions: &[usize],
     domain_size: usize,
-) -> Vec<E> {
+) -> Option<Vec<E>> {
     let row_length = domain_size / N;
 
     let mut result = Vec::new();
     for position in positions {
         let idx = folded_positions
             .iter()
-            .position(|&v| v == position % row_length)
-            .unwrap();
-        let value = values[idx][position / row_length];
+            .position(|&v| v == position % row_length)?;
+        let value = values.get(idx)?[position / row_length];
         result.push(value);
     }
 
-    result
+    Some(result)
 }
 
 fn get_batch_query_values<E: FieldElement, const N: usize>(
@@ -34,20 +56,49 @@ fn get_batch_query_values<E: FieldElement, const N: usize>(
     folded_positions: &[usize],
     domain_size: usize,
     poly_count: usize,
-) -> Vec<E> {
+) -> Option<Vec<E>> {
     let row_length = domain_size / N;
     let mut result = Vec::with_capacity(poly_count * positions.len());
     for position in positions.iter() {
         let idx = folded_positions
             .iter()
-            .position(|&v| v == position % row_length)
-            .unwrap();
+            .position(|&v| v == position % row_length)?;
         let start = idx * (poly_count * N) + (position / row_length) * poly_count;
-        values[start..start + poly_count].iter().for_each(|e| {
+        values.get(start..start + poly_count)?.iter().for_each(|e| {
             result.push(*e);
         });
     }
-    result
+    Some(result)
+}
+
+/// Combines the evaluations of the `xi.len()` polynomials of a batch with the batching
+/// coefficients `xi`, as the verifier does before folding the batch layer. `values` holds the
+/// evaluations of every polynomial at one point after the other, as both the rows of the batch
+/// layer and the evaluations given to `FridaDasVerifier::verify` do, so that external tools, e.g.
+/// fraud-proof generators, can recompute the combination step on its own.
+///
+/// # Errors
+/// Returns `FridaError::BatchValuesMismatch` if `xi` is empty or the number of values is not a
+/// multiple of the number of polynomials.
+pub fn combine_batch_values<E: FieldElement>(values: &[E], xi: &[E]) -> Result<Vec<E>, FridaError> {
+    if xi.is_empty() || values.len() % xi.len() != 0 {
+        return Err(FridaError::BatchValuesMismatch(values.len(), xi.len()));
+    }
+    Ok(combine_rows(values, xi))
+}
+
+/// Same as `combine_batch_values`, ignoring trailing values which do not make up a whole point.
+/// Points are combined in parallel with the `concurrent` feature, as the prover combines them.
+fn combine_rows<E: FieldElement>(values: &[E], xi: &[E]) -> Vec<E> {
+    let poly_count = xi.len();
+    let mut combined: Vec<E> = output_vector(values.len() / poly_count);
+    ParallelOptions::default().for_each_row_mut(&mut combined, poly_count, |i, f| {
+        *f = values[i * poly_count..(i + 1) * poly_count]
+            .iter()
+            .zip(xi)
+            .fold(E::ZERO, |acc, (&value, &x)| acc + value * x);
+    });
+    combined
 }
 
 // Evaluates a polynomial with coefficients in an extension field at a point in the base field.
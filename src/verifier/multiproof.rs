@@ -0,0 +1,174 @@
+use winter_crypto::{ElementHasher, MerkleTree};
+use winter_fri::{
+    folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError,
+};
+use winter_math::FieldElement;
+use winter_utils::DeserializationError;
+
+use super::das::FridaDasVerifier;
+use crate::{
+    core::{random::FridaRandom, schedule::layer_folding_factors},
+    error::FridaError,
+    prover::{
+        multiproof::{FirstLayerOpening, MultiProof},
+        ProverCommitment,
+    },
+};
+
+/// Domain separator absorbed before the commitments of a multiproof, so that its coefficients
+/// cannot collide with challenges drawn by other protocols from the same commitments.
+const MULTIPROOF_DOMAIN: &[u8] = b"frida-multiproof-v1";
+
+// MULTIPROOF VERIFICATION
+// ================================================================================================
+
+/// Draws the coefficients combining the polynomials of `commitments` in a multiproof, one per
+/// polynomial in commitment order, from a public coin seeded with the root digest of every
+/// commitment.
+pub fn multiproof_coefficients<E, HHst, H>(
+    commitments: &[ProverCommitment<H>],
+) -> Result<Vec<E>, FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let mut public_coin = FridaRandom::<E, HHst, H>::new();
+    public_coin.reseed(MULTIPROOF_DOMAIN);
+    for commitment in commitments {
+        public_coin.reseed(&commitment.root_digest());
+    }
+    public_coin.draw_xi(commitments.iter().map(|c| c.poly_count).sum())
+}
+
+/// Verifies that `proof` proves the first layer of every one of `commitments`, made with
+/// `options` and with `HHst` as the transcript hasher, to be the evaluations of low degree
+/// polynomials, with at least `num_queries` queries.
+///
+/// This replaces checking the proof embedded in every `Commitment`, e.g. for the blobs of a block.
+/// Only the first layer of every commitment is covered: later layers are not opened, but they
+/// are determined by the first one, and openings folding into layers inconsistent with it are
+/// rejected by `FridaDasVerifier::verify` as usual.
+///
+/// The number of queries is set by the verifier rather than read from the proof: the prover
+/// picks the number of positions its transcript draws, and a proof of a single query would
+/// otherwise verify as well as one of the intended number.
+///
+/// # Errors
+/// Returns `FridaError::BadNumQueries` if the combined commitment has fewer than `num_queries`
+/// queries, `FridaError::MultiProofMismatch` if the commitments do not share the domain of the
+/// combined commitment, or if the proof does not open each of them,
+/// `FridaError::PartitionMismatch` if an opening is not split into the partitions of its
+/// commitment, and `FridaError::FailToVerify` if an opening does not match its commitment or if
+/// the combination of the openings does not verify against the combined commitment.
+pub fn verify_multiproof<E, HHst, H>(
+    commitments: &[ProverCommitment<H>],
+    proof: &MultiProof<H>,
+    options: &FriOptions,
+    num_queries: usize,
+) -> Result<(), FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let domain_size = proof.combined.domain_size;
+    if commitments.is_empty()
+        || proof.combined.poly_count != 1
+        || commitments.len() != proof.openings.len()
+        || commitments
+            .iter()
+            .any(|c| c.domain_size != domain_size || c.poly_count == 0)
+    {
+        return Err(FridaError::MultiProofMismatch);
+    }
+    if num_queries == 0 || proof.combined.num_queries < num_queries {
+        return Err(FridaError::BadNumQueries(proof.combined.num_queries));
+    }
+
+    let positions = FridaDasVerifier::<E, HHst, H>::replay_positions(&proof.combined)?;
+    let folding_factors = layer_folding_factors(options, &[], domain_size);
+    let Some(&folding_factor) = folding_factors.first() else {
+        return Err(FridaError::MultiProofMismatch);
+    };
+    let min_rows = domain_size / folding_factors.iter().product::<usize>();
+    let rows = fold_positions(&positions, domain_size, folding_factor);
+
+    let coefficients = multiproof_coefficients::<E, HHst, H>(commitments)?;
+    let mut combined = vec![E::ZERO; positions.len()];
+    let mut coefficients = coefficients.as_slice();
+    for (commitment, opening) in commitments.iter().zip(&proof.openings) {
+        let (commitment_coefficients, rest) = coefficients.split_at(commitment.poly_count);
+        coefficients = rest;
+        let values = read_first_layer(commitment, opening, &rows, folding_factor, min_rows)?;
+        let row_length = domain_size / folding_factor;
+        for (value, &position) in combined.iter_mut().zip(&positions) {
+            let row = rows
+                .iter()
+                .position(|&row| row == position % row_length)
+                .expect("folded rows cover every position");
+            let start = (row * folding_factor + position / row_length) * commitment.poly_count;
+            *value += values[start..start + commitment.poly_count]
+                .iter()
+                .zip(commitment_coefficients)
+                .fold(E::ZERO, |acc, (&e, &c)| acc + e * c);
+        }
+    }
+
+    let verifier = FridaDasVerifier::<E, HHst, H>::from_commitment(
+        &proof.combined.to_prover_commitment(),
+        options.clone(),
+    )?;
+    verifier.verify(&proof.combined.proof, &combined, &positions)
+}
+
+/// Parses the rows of the first layer of `commitment` opened at `rows`, and checks them against
+/// its first root.
+fn read_first_layer<E, H>(
+    commitment: &ProverCommitment<H>,
+    opening: &FirstLayerOpening,
+    rows: &[usize],
+    folding_factor: usize,
+    min_rows: usize,
+) -> Result<Vec<E>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let num_partitions = opening.num_partitions;
+    if num_partitions != commitment.num_partitions {
+        return Err(FridaError::PartitionMismatch(
+            commitment.num_partitions,
+            num_partitions,
+        ));
+    }
+    if !num_partitions.is_power_of_two() || num_partitions > min_rows {
+        return Err(FridaError::DeserializationError(
+            DeserializationError::InvalidValue(format!(
+                "expected a power of two up to {min_rows} partitions, but got {num_partitions}"
+            )),
+        ));
+    }
+    let root = commitment
+        .roots
+        .first()
+        .ok_or(FridaError::MultiProofMismatch)?;
+
+    let (values, merkle_proof) = opening
+        .layer
+        .parse::<H, E>(
+            commitment.domain_size,
+            folding_factor,
+            commitment.poly_count,
+            commitment.batch_leaf_encoding,
+        )
+        .map_err(FridaError::DeserializationError)?;
+    if values.len() != rows.len() * folding_factor * commitment.poly_count {
+        return Err(FridaError::MultiProofMismatch);
+    }
+    let indexes =
+        map_positions_to_indexes(rows, commitment.domain_size, folding_factor, num_partitions);
+    MerkleTree::<H>::verify_batch(root, &indexes, &merkle_proof)
+        .map_err(|_| FridaError::FailToVerify(VerifierError::LayerCommitmentMismatch))?;
+    Ok(values)
+}
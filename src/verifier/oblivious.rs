@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+
+use winter_crypto::{Digest, ElementHasher, Hasher};
+use winter_math::FieldElement;
+
+use super::das::FridaDasVerifier;
+use crate::{error::FridaError, prover::receipt::SampleReceipt};
+
+// OBLIVIOUS QUERY
+// ================================================================================================
+
+/// Request of a sampler which hides the positions it samples among decoys, so that a provider
+/// answering it cannot tell which of its answers will be checked, and thus cannot bias its
+/// answers toward the sampled positions.
+///
+/// The provider is sent the sorted union of the sampled and decoy positions, and answers every
+/// one of them with a self-contained `SampleReceipt`, e.g. through
+/// `FridaProver::sample_receipts`; only the receipts of the sampled positions are verified. The
+/// decoys are drawn from a seed which must be kept secret by the sampler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObliviousQuery {
+    positions: Vec<usize>,
+    sampled: Vec<usize>,
+}
+
+impl ObliviousQuery {
+    /// Hides `sampled` among `num_decoys` other positions of a domain of `domain_size`, drawn
+    /// with `H` from the secret `seed`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::PositionOutOfDomain` if a sampled position lies outside of the domain,
+    /// and `FridaError::ObliviousQueryTooLarge` if the domain has fewer positions than the
+    /// sampled and decoy positions together.
+    pub fn new<H: Hasher>(
+        sampled: &[usize],
+        num_decoys: usize,
+        domain_size: usize,
+        seed: &[u8],
+    ) -> Result<Self, FridaError> {
+        if let Some(&position) = sampled.iter().find(|&&position| position >= domain_size) {
+            return Err(FridaError::PositionOutOfDomain(position, domain_size));
+        }
+        let mut positions = sampled.iter().copied().collect::<BTreeSet<_>>();
+        let num_positions = positions.len() + num_decoys;
+        if num_positions > domain_size {
+            return Err(FridaError::ObliviousQueryTooLarge(
+                num_positions,
+                domain_size,
+            ));
+        }
+
+        let mut counter = 0u64;
+        while positions.len() < num_positions {
+            let digest = H::hash(&[seed, &counter.to_le_bytes()].concat());
+            let bytes = digest.as_bytes();
+            let value = u64::from_le_bytes(bytes[..8].try_into().expect("digests hold 8 bytes"));
+            positions.insert((value % domain_size as u64) as usize);
+            counter += 1;
+        }
+
+        Ok(ObliviousQuery {
+            positions: positions.into_iter().collect(),
+            sampled: sampled.to_vec(),
+        })
+    }
+
+    /// Positions to request from the provider, sorted, which hold the sampled ones.
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    /// Positions actually sampled, to be kept by the sampler.
+    pub fn sampled(&self) -> &[usize] {
+        &self.sampled
+    }
+
+    /// Checks that `receipts` answer every requested position, in order, and verifies those of
+    /// the sampled positions with `verifier`. Returns the evaluations of every polynomial at each
+    /// sampled position, in the order of `sampled()`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::ObliviousResponseMismatch` if the receipts do not answer the
+    /// requested positions, and the error of `FridaDasVerifier::verify_receipt` if the receipt of
+    /// a sampled position is rejected.
+    pub fn verify<E, HHst, HRandom>(
+        &self,
+        verifier: &FridaDasVerifier<E, HHst, HRandom>,
+        receipts: &[SampleReceipt<E, HRandom>],
+    ) -> Result<Vec<Vec<E>>, FridaError>
+    where
+        E: FieldElement,
+        HHst: ElementHasher<BaseField = E::BaseField>,
+        HRandom: ElementHasher<BaseField = E::BaseField>,
+    {
+        if receipts.len() != self.positions.len()
+            || receipts
+                .iter()
+                .zip(self.positions.iter())
+                .any(|(receipt, &position)| receipt.position != position)
+        {
+            return Err(FridaError::ObliviousResponseMismatch);
+        }
+
+        self.sampled
+            .iter()
+            .map(|position| {
+                let index = self
+                    .positions
+                    .binary_search(position)
+                    .expect("sampled positions are requested");
+                let receipt = &receipts[index];
+                verifier.verify_receipt(receipt)?;
+                Ok(receipt.evaluations.clone())
+            })
+            .collect()
+    }
+}
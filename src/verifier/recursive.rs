@@ -0,0 +1,227 @@
+use winter_crypto::{Digest, ElementHasher};
+use winter_fri::folding::fold_positions;
+use winter_math::{FieldElement, StarkField};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::das::FridaDasVerifier;
+use crate::{error::FridaError, prover::proof::FridaProof};
+
+// RECURSIVE STATEMENT
+// ================================================================================================
+
+// The statement of a verified opening is flattened into elements of the base field, so that it
+// can be fed to a STARK or SNARK circuit recursively verifying the opening. Integers, which must
+// fit in 32 bits, and digests are split into little-endian 16-bit limbs, which fit in any
+// supported field, BabyBear included; extension field elements are laid out as their base
+// elements.
+//
+// The public inputs are, in order:
+// - the domain size, the number of polynomials and the number of FRI layers, the batch layer
+//   included, followed by the folding factor of every layer;
+// - the root of every layer, followed by the commitment to the remainder;
+// - the batching coefficients of a batched commitment, and the point, value and combination
+//   coefficient of the out-of-domain sample of a commitment built with one;
+// - the folding randomness drawn after every root;
+// - the number of opened positions, followed by the positions and their evaluations.
+//
+// The witness is the number of partitions of the proof, followed for every layer by the leaf
+// index, the values and the authentication path of every queried row, in the order the positions
+// fold into, and then by the number of coefficients of the remainder and the coefficients.
+
+/// Verification statement of an opening, flattened into base field elements; see
+/// `FridaDasVerifier::recursive_statement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursiveStatement<B> {
+    /// Inputs the recursive proof is checked against, computable without the FRI proof with
+    /// `FridaDasVerifier::recursive_public_inputs`.
+    pub public_inputs: Vec<B>,
+    /// Openings of the FRI proof, only needed to produce the recursive proof.
+    pub witness: Vec<B>,
+}
+
+impl<B: StarkField> RecursiveStatement<B> {
+    /// Hash of the public inputs with `H`, which the circuit is expected to expose.
+    pub fn public_inputs_hash<H: ElementHasher<BaseField = B>>(&self) -> H::Digest {
+        H::hash_elements(&self.public_inputs)
+    }
+}
+
+impl<E, HHst, HRandom> FridaDasVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Verifies that `proof` opens `evaluations` at `positions`, and exports the statement of the
+    /// opening for a circuit recursively verifying it.
+    ///
+    /// # Errors
+    /// Returns the errors of `verify`, and those of `recursive_public_inputs`.
+    pub fn recursive_statement(
+        &self,
+        proof: &FridaProof,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<RecursiveStatement<E::BaseField>, FridaError> {
+        self.verify(proof, evaluations, positions)?;
+
+        let mut witness = Vec::new();
+        let num_partitions = self.num_partitions();
+        push_usize(&mut witness, num_partitions)?;
+
+        // the batch layer, if any, is parsed separately from the regular FRI layers
+        let folding_factors = self.folding_factors();
+        let mut layers = Vec::with_capacity(folding_factors.len());
+        let mut layer_domain_size = self.domain_size();
+        let mut layer_folding_factors = folding_factors;
+        if self.poly_count() > 1 {
+            let batch_layer = proof
+                .parse_batch_layer::<HRandom, E>(
+                    layer_domain_size,
+                    folding_factors[0],
+                    self.poly_count(),
+                    self.batch_leaf_encoding(),
+                )
+                .map_err(FridaError::DeserializationError)?;
+            layers.push(batch_layer);
+            layer_domain_size /= folding_factors[0];
+            layer_folding_factors = &folding_factors[1..];
+        }
+        let (layer_values, layer_proofs) = proof
+            .parse_layers::<HRandom, E>(layer_domain_size, layer_folding_factors)
+            .map_err(FridaError::DeserializationError)?;
+        layers.extend(layer_values.into_iter().zip(layer_proofs));
+
+        let mut domain_size = self.domain_size();
+        let mut folded_positions = positions.to_vec();
+        for (depth, ((values, merkle_proof), &folding_factor)) in
+            layers.into_iter().zip(folding_factors).enumerate()
+        {
+            folded_positions = fold_positions(&folded_positions, domain_size, folding_factor);
+            let indexes = self.map_positions_to_indexes(
+                &folded_positions,
+                domain_size,
+                folding_factor,
+                num_partitions,
+            );
+            let paths = merkle_proof
+                .into_paths(&indexes)
+                .map_err(|e| FridaError::MerkleError(e.to_string()))?;
+            let row_size = if depth == 0 {
+                self.poly_count() * folding_factor
+            } else {
+                folding_factor
+            };
+            for ((&index, row), path) in indexes.iter().zip(values.chunks(row_size)).zip(&paths) {
+                push_usize(&mut witness, index)?;
+                witness.extend_from_slice(E::slice_as_base_elements(row));
+                for digest in path {
+                    push_digest(&mut witness, digest);
+                }
+            }
+            domain_size /= folding_factor;
+        }
+
+        let remainder = proof
+            .parse_remainder::<E>()
+            .map_err(FridaError::DeserializationError)?;
+        push_usize(&mut witness, remainder.len())?;
+        witness.extend_from_slice(E::slice_as_base_elements(&remainder));
+
+        Ok(RecursiveStatement {
+            public_inputs: self.recursive_public_inputs(evaluations, positions)?,
+            witness,
+        })
+    }
+
+    /// Returns the public inputs of the statement opening `evaluations` at `positions`, as
+    /// exported by `recursive_statement`.
+    ///
+    /// # Errors
+    /// Returns `FridaError::RecursiveInputOutOfRange` if a position does not fit in 32 bits.
+    pub fn recursive_public_inputs(
+        &self,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<Vec<E::BaseField>, FridaError> {
+        let mut inputs = Vec::new();
+        push_usize(&mut inputs, self.domain_size())?;
+        push_usize(&mut inputs, self.poly_count())?;
+        push_usize(&mut inputs, self.folding_factors().len())?;
+        for &folding_factor in self.folding_factors() {
+            push_usize(&mut inputs, folding_factor)?;
+        }
+        for root in self.layer_commitments() {
+            push_digest(&mut inputs, root);
+        }
+        if let Some(xi) = self.xi() {
+            inputs.extend_from_slice(E::slice_as_base_elements(xi));
+        }
+        if let Some(sample) = self.ood_sample() {
+            let sample = [sample.point, sample.value, sample.comb];
+            inputs.extend_from_slice(E::slice_as_base_elements(&sample));
+        }
+        inputs.extend_from_slice(E::slice_as_base_elements(self.layer_alphas()));
+        push_usize(&mut inputs, positions.len())?;
+        for &position in positions {
+            push_usize(&mut inputs, position)?;
+        }
+        inputs.extend_from_slice(E::slice_as_base_elements(evaluations));
+        Ok(inputs)
+    }
+
+    /// Hash of the public inputs of the statement opening `evaluations` at `positions` with `H`,
+    /// against which the recursive proof of the opening is checked.
+    ///
+    /// # Errors
+    /// Returns the errors of `recursive_public_inputs`.
+    pub fn recursive_public_inputs_hash<H: ElementHasher<BaseField = E::BaseField>>(
+        &self,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<H::Digest, FridaError> {
+        let inputs = self.recursive_public_inputs(evaluations, positions)?;
+        Ok(H::hash_elements(&inputs))
+    }
+}
+
+fn push_usize<B: FieldElement>(elements: &mut Vec<B>, value: usize) -> Result<(), FridaError> {
+    let value = u32::try_from(value).map_err(|_| FridaError::RecursiveInputOutOfRange(value))?;
+    push_limbs(elements, &value.to_le_bytes());
+    Ok(())
+}
+
+fn push_digest<B: FieldElement, D: Digest>(elements: &mut Vec<B>, digest: &D) {
+    push_limbs(elements, &digest.as_bytes());
+}
+
+/// Splits `bytes` into little-endian 16-bit limbs, the last one padded with zeros.
+fn push_limbs<B: FieldElement>(elements: &mut Vec<B>, bytes: &[u8]) {
+    for limb in bytes.chunks(2) {
+        let mut limb_bytes = [0; 2];
+        limb_bytes[..limb.len()].copy_from_slice(limb);
+        elements.push(B::from(u16::from_le_bytes(limb_bytes)));
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<B: FieldElement> Serializable for RecursiveStatement<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.public_inputs.write_into(target);
+        self.witness.write_into(target);
+    }
+}
+
+impl<B: FieldElement> Deserializable for RecursiveStatement<B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let public_inputs = Vec::<B>::read_from(source)?;
+        let witness = Vec::<B>::read_from(source)?;
+
+        Ok(RecursiveStatement {
+            public_inputs,
+            witness,
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use winter_crypto::ElementHasher;
+use winter_fri::VerifierError;
+use winter_math::FieldElement;
+
+use super::das::FridaDasVerifier;
+use crate::{error::FridaError, prover::proof::FridaProof};
+
+// FAILURE CODES
+// ================================================================================================
+
+/// Machine-readable reason of a verification failure, stable across releases so that telemetry
+/// pipelines can aggregate failures without parsing error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "json",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FailureCode {
+    /// The proof could not be parsed, or exceeds the sizes implied by the verifier options.
+    MalformedProof,
+    /// The number of evaluations does not match the number of positions.
+    EvaluationCountMismatch,
+    /// A Merkle path does not lead to the root of its layer.
+    LayerCommitmentMismatch,
+    /// The values of a layer are not the folding of those of the previous one.
+    LayerFolding,
+    /// The remainder does not hash to its commitment.
+    RemainderCommitmentMismatch,
+    /// The remainder is not the folding of the last layer.
+    RemainderFolding,
+    /// The remainder polynomial has a higher degree than expected.
+    RemainderDegree,
+    /// A layer degree cannot be divided evenly by its folding factor.
+    DegreeTruncation,
+    /// A layer is folded by an unsupported factor.
+    UnsupportedFoldingFactor,
+    /// The proof does not open as many polynomials as the commitment holds.
+    PolyCountMismatch,
+    /// Any other failure; see the error itself.
+    Other,
+}
+
+impl FailureCode {
+    /// Numeric code of the failure, for compact telemetry records.
+    pub fn as_u16(&self) -> u16 {
+        match self {
+            FailureCode::MalformedProof => 1,
+            FailureCode::EvaluationCountMismatch => 2,
+            FailureCode::LayerCommitmentMismatch => 3,
+            FailureCode::LayerFolding => 4,
+            FailureCode::RemainderCommitmentMismatch => 5,
+            FailureCode::RemainderFolding => 6,
+            FailureCode::RemainderDegree => 7,
+            FailureCode::DegreeTruncation => 8,
+            FailureCode::UnsupportedFoldingFactor => 9,
+            FailureCode::PolyCountMismatch => 10,
+            FailureCode::Other => 255,
+        }
+    }
+
+    /// Returns the code of `error`, along with the index of the layer it occurred at when known.
+    pub fn from_error(error: &FridaError) -> (Self, Option<usize>) {
+        match error {
+            FridaError::DeserializationError(_) | FridaError::InvalidDASCommitment => {
+                (FailureCode::MalformedProof, None)
+            }
+            FridaError::ProofPolyCountMismatch => (FailureCode::PolyCountMismatch, None),
+            FridaError::DegreeTruncation(layer, ..) => {
+                (FailureCode::DegreeTruncation, Some(*layer))
+            }
+            FridaError::UnsupportedFoldingFactor(_) => {
+                (FailureCode::UnsupportedFoldingFactor, None)
+            }
+            FridaError::FailToVerify(error) => match error {
+                VerifierError::NumPositionEvaluationMismatch(..) => {
+                    (FailureCode::EvaluationCountMismatch, None)
+                }
+                VerifierError::LayerCommitmentMismatch => {
+                    (FailureCode::LayerCommitmentMismatch, None)
+                }
+                VerifierError::InvalidLayerFolding(layer) => {
+                    (FailureCode::LayerFolding, Some(*layer))
+                }
+                VerifierError::RemainderCommitmentMismatch => {
+                    (FailureCode::RemainderCommitmentMismatch, None)
+                }
+                VerifierError::InvalidRemainderFolding => (FailureCode::RemainderFolding, None),
+                VerifierError::RemainderDegreeMismatch(_) => (FailureCode::RemainderDegree, None),
+                VerifierError::DegreeTruncation(_, _, layer) => {
+                    (FailureCode::DegreeTruncation, Some(*layer))
+                }
+                VerifierError::UnsupportedFoldingFactor(_) => {
+                    (FailureCode::UnsupportedFoldingFactor, None)
+                }
+                _ => (FailureCode::Other, None),
+            },
+            _ => (FailureCode::Other, None),
+        }
+    }
+}
+
+// VERIFY REPORT
+// ================================================================================================
+
+/// Outcome of a verification, as recorded by DAS telemetry.
+///
+/// `layer` is the index of the layer a failure occurred at, when the verifier could tell it, and
+/// `position` the opened position when a single one was verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+pub struct VerifyReport {
+    pub ok: bool,
+    pub code: Option<FailureCode>,
+    pub layer: Option<usize>,
+    pub position: Option<usize>,
+}
+
+impl VerifyReport {
+    /// Builds the report of a verification of `positions` which ended with `result`.
+    pub fn from_result(result: &Result<(), FridaError>, positions: &[usize]) -> Self {
+        let position = match positions {
+            [position] => Some(*position),
+            _ => None,
+        };
+        match result {
+            Ok(()) => VerifyReport {
+                ok: true,
+                code: None,
+                layer: None,
+                position,
+            },
+            Err(error) => {
+                let (code, layer) = FailureCode::from_error(error);
+                VerifyReport {
+                    ok: false,
+                    code: Some(code),
+                    layer,
+                    position,
+                }
+            }
+        }
+    }
+
+    /// Serializes the report as a single-line JSON object, e.g.
+    /// `{"ok":false,"code":"layer_folding","layer":0,"position":12}`.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("reports always serialize")
+    }
+}
+
+impl<E, HHst, HRandom> FridaDasVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Same as `verify`, reporting the outcome as a `VerifyReport` instead of an error.
+    pub fn verify_report(
+        &self,
+        proof: &FridaProof,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> VerifyReport {
+        VerifyReport::from_result(&self.verify(proof, evaluations, positions), positions)
+    }
+}
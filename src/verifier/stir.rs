@@ -0,0 +1,291 @@
+use core::marker::PhantomData;
+
+use winter_crypto::{Digest, ElementHasher, MerkleTree};
+use winter_fri::{folding::fold_positions, FriOptions, VerifierError};
+use winter_math::{polynom, FieldElement, StarkField};
+
+use crate::{
+    core::{
+        random::FridaRandom,
+        stir::{draw_round_indexes, folded_domain_point, stir_rounds, StirRound},
+    },
+    error::FridaError,
+    prover::{proof::FridaProofLayer, stir::StirCommitment},
+};
+
+// STIR VERIFIER
+// ================================================================================================
+
+/// Verifier of commitments built in the experimental STIR mode.
+///
+/// The low-degree proof of a commitment is checked once, by `new`; samples of the committed blob
+/// are then opened from the tree of its encoding alone and checked by `verify`.
+pub struct StirVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    root: HRandom::Digest,
+    domain_size: usize,
+    folding_factor: usize,
+    options: FriOptions,
+    _phantom_field_element: PhantomData<E>,
+    _phantom_hash_hst: PhantomData<HHst>,
+}
+
+/// Points the polynomial tested in a round was constrained to, so that its values can be
+/// computed from those of the folded polynomial committed in the previous round.
+struct Constraints<E: FieldElement> {
+    points: Vec<E>,
+    /// Polynomial interpolating the values expected at `points`.
+    answers: Vec<E>,
+    comb: E,
+}
+
+impl<E: FieldElement> Constraints<E> {
+    /// Returns the value at `x` of the constrained polynomial, given the value `value` of the
+    /// folded polynomial at `x`.
+    fn apply(&self, x: E, value: E) -> E {
+        let vanishing = self
+            .points
+            .iter()
+            .fold(E::ONE, |acc, &point| acc * (x - point));
+        let quotient = (value - polynom::eval(&self.answers, x)) / vanishing;
+        let correction = (0..=self.points.len())
+            .fold((E::ZERO, E::ONE), |(sum, power), _| {
+                (sum + power, power * self.comb * x)
+            })
+            .0;
+        quotient * correction
+    }
+}
+
+impl<E, HHst, HRandom> StirVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Checks the low-degree proof of `commitment`, and returns a verifier of samples of the
+    /// committed blob.
+    pub fn new(
+        commitment: &StirCommitment<HRandom>,
+        options: FriOptions,
+    ) -> Result<Self, FridaError> {
+        let rounds = stir_rounds(&options, commitment.domain_size, commitment.num_queries)?;
+        if commitment.roots.len() != rounds.len() || commitment.proof.num_rounds() != rounds.len() {
+            return Err(FridaError::InvalidDASCommitment);
+        }
+        // static dispatch for folding factor parameter
+        let folding_factor = options.folding_factor();
+        let domain_offset = options.domain_offset();
+        match folding_factor {
+            4 => verify_rounds::<E, HHst, HRandom, 4>(commitment, &rounds, domain_offset)?,
+            8 => verify_rounds::<E, HHst, HRandom, 8>(commitment, &rounds, domain_offset)?,
+            16 => verify_rounds::<E, HHst, HRandom, 16>(commitment, &rounds, domain_offset)?,
+            _ => return Err(FridaError::UnsupportedFoldingFactor(folding_factor)),
+        }
+
+        Ok(StirVerifier {
+            root: commitment.roots[0],
+            domain_size: commitment.domain_size,
+            folding_factor,
+            options,
+            _phantom_field_element: PhantomData,
+            _phantom_hash_hst: PhantomData,
+        })
+    }
+
+    /// Checks that `proof`, as returned by `StirProver::open`, opens the encoded blob to
+    /// `evaluations` at `positions`.
+    pub fn verify(
+        &self,
+        proof: &FridaProofLayer,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), FridaError> {
+        if evaluations.len() != positions.len() {
+            return Err(FridaError::FailToVerify(
+                VerifierError::NumPositionEvaluationMismatch(positions.len(), evaluations.len()),
+            ));
+        }
+        if let Some(&position) = positions.iter().find(|&&p| p >= self.domain_size) {
+            return Err(FridaError::PositionOutOfDomain(position, self.domain_size));
+        }
+
+        let row_length = self.domain_size / self.folding_factor;
+        let indexes = fold_positions(positions, self.domain_size, self.folding_factor);
+        let values =
+            open_rows::<E, HRandom>(proof, &self.root, &indexes, row_length, self.folding_factor)?;
+
+        for (&position, &evaluation) in positions.iter().zip(evaluations) {
+            let row = indexes
+                .iter()
+                .position(|&index| index == position % row_length)
+                .expect("every position is folded into an index");
+            if values[row * self.folding_factor + position / row_length] != evaluation {
+                return Err(FridaError::FailToVerify(
+                    VerifierError::InvalidLayerFolding(0),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Options the commitment was built with.
+    pub fn options(&self) -> &FriOptions {
+        &self.options
+    }
+}
+
+/// Parses the rows opened by `proof` at `indexes` of a tree with `row_length` leaves of
+/// `folding_factor` values each, and checks them against `root`.
+fn open_rows<E, H>(
+    proof: &FridaProofLayer,
+    root: &H::Digest,
+    indexes: &[usize],
+    row_length: usize,
+    folding_factor: usize,
+) -> Result<Vec<E>, FridaError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let (values, merkle_proof) = proof
+        .parse::<H, E>(row_length, folding_factor)
+        .map_err(FridaError::DeserializationError)?;
+    if values.len() != indexes.len() * folding_factor {
+        return Err(FridaError::FailToVerify(
+            VerifierError::LayerCommitmentMismatch,
+        ));
+    }
+    MerkleTree::<H>::verify_batch(root, indexes, &merkle_proof)
+        .map_err(|_| FridaError::FailToVerify(VerifierError::LayerCommitmentMismatch))?;
+    Ok(values)
+}
+
+/// Replays the rounds of the proof of `commitment` with folding factor N.
+fn verify_rounds<E, HHst, HRandom, const N: usize>(
+    commitment: &StirCommitment<HRandom>,
+    rounds: &[StirRound],
+    domain_offset: E::BaseField,
+) -> Result<(), FridaError>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    let proof = &commitment.proof;
+    let ood_answers = proof
+        .parse_ood_answers::<E>()
+        .map_err(FridaError::DeserializationError)?;
+    let final_poly = proof
+        .parse_final_poly::<E>()
+        .map_err(FridaError::DeserializationError)?;
+    let (last_round, inner_rounds) = rounds.split_last().expect("no STIR rounds");
+    if ood_answers.len() != inner_rounds.len() {
+        return Err(FridaError::InvalidDASCommitment);
+    }
+    if final_poly.len() > last_round.degree / N {
+        return Err(FridaError::FailToVerify(
+            VerifierError::RemainderDegreeMismatch(last_round.degree / N - 1),
+        ));
+    }
+
+    let mut public_coin = FridaRandom::<E, HHst, HRandom>::new();
+    let mut constraints = None;
+    for (depth, round) in rounds.iter().enumerate() {
+        public_coin.reseed(&commitment.roots[depth].as_bytes());
+        let alpha = public_coin.draw()?;
+
+        let next_constraints = if depth < inner_rounds.len() {
+            public_coin.reseed(&commitment.roots[depth + 1].as_bytes());
+            let ood_point = public_coin.draw()?;
+            public_coin.reseed(&HRandom::hash_elements(&ood_answers[depth..depth + 1]).as_bytes());
+            let comb = public_coin.draw()?;
+            Some((ood_point, comb))
+        } else {
+            public_coin.reseed(&HRandom::hash_elements(&final_poly).as_bytes());
+            None
+        };
+        let indexes = draw_round_indexes(&public_coin, round, N)?;
+
+        let row_length = round.domain_size / N;
+        let values = open_rows::<E, HRandom>(
+            proof.round(depth).expect("number of rounds was checked"),
+            &commitment.roots[depth],
+            &indexes,
+            row_length,
+            N,
+        )?;
+
+        // fold every queried row; rows past the first one hold values of the polynomial folded
+        // in the previous round, which are first mapped to the constrained polynomial
+        let generator = E::BaseField::get_root_of_unity(round.domain_size.ilog2());
+        let folding_roots = (0..N)
+            .map(|j| generator.exp_vartime(((row_length * j) as u64).into()))
+            .collect::<Vec<_>>();
+        let folded_values = indexes
+            .iter()
+            .zip(values.chunks(N))
+            .map(|(&index, row)| {
+                let x = domain_offset * generator.exp_vartime((index as u64).into());
+                let xs = folding_roots
+                    .iter()
+                    .map(|&root| E::from(x * root))
+                    .collect::<Vec<_>>();
+                let ys = match &constraints {
+                    Some(constraints) => xs
+                        .iter()
+                        .zip(row)
+                        .map(|(&x, &value)| constraints.apply(x, value))
+                        .collect::<Vec<_>>(),
+                    None => row.to_vec(),
+                };
+                polynom::eval(&polynom::interpolate(&xs, &ys, false), alpha)
+            })
+            .collect::<Vec<_>>();
+
+        let points = indexes
+            .iter()
+            .map(|&index| {
+                E::from(folded_domain_point(
+                    round.domain_size,
+                    N,
+                    domain_offset,
+                    index,
+                ))
+            })
+            .collect::<Vec<_>>();
+        match next_constraints {
+            Some((ood_point, comb)) => {
+                let mut all_points = vec![ood_point];
+                all_points.extend_from_slice(&points);
+                let mut all_answers = vec![ood_answers[depth]];
+                all_answers.extend_from_slice(&folded_values);
+                constraints = Some(Constraints {
+                    answers: polynom::interpolate(&all_points, &all_answers, false),
+                    points: all_points,
+                    comb,
+                });
+            }
+            None => {
+                // the last round is checked against the polynomial sent in the clear
+                for (point, value) in points.iter().zip(folded_values) {
+                    if polynom::eval(&final_poly, *point) != value {
+                        return Err(FridaError::FailToVerify(
+                            VerifierError::InvalidRemainderFolding,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::{
+    error::FridaError,
+    utils::test_utils::*,
+    verifier::adaptive::{AdaptiveSampling, EscalationPolicy, SamplingStep},
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+use winter_rand_utils::rand_vector;
+
+#[test]
+fn test_adaptive_sampling_escalates_until_target() {
+    let options = FriOptions::new(8, 4, 3);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let (commitment, prover, _) = prover_builder
+        .commitment(&rand_vector::<u8>(2000), 8)
+        .unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let target_bits = 40;
+    let mut sampling = AdaptiveSampling::<Blake3>::new(
+        &verifier,
+        target_bits,
+        EscalationPolicy::new(2, 2),
+        b"secret seed",
+    )
+    .unwrap();
+    let required = sampling.num_required();
+    assert!(verifier.security_level(required) >= target_bits);
+    assert!(verifier.security_level(required - 1) < target_bits);
+
+    let mut round_sizes = Vec::new();
+    let mut positions = sampling.pending().to_vec();
+    let security_bits = loop {
+        round_sizes.push(positions.len());
+        let proof = prover.open(&positions).unwrap();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+        match sampling.submit(&verifier, &proof, &evaluations).unwrap() {
+            SamplingStep::Fetch(next) => {
+                assert_eq!(next, sampling.pending());
+                assert!(next.iter().all(|position| !positions.contains(position)));
+                positions = next;
+            }
+            SamplingStep::Confident { security_bits } => break security_bits,
+        }
+    };
+
+    assert!(security_bits >= target_bits);
+    assert_eq!(sampling.num_verified(), required);
+    assert!(sampling.pending().is_empty());
+    // rounds double until the last one, which only fetches the positions still needed
+    let (last, full) = round_sizes.split_last().unwrap();
+    assert!(full.iter().enumerate().all(|(i, &size)| size == 2 << i));
+    assert!(*last <= 2 << full.len());
+    assert_eq!(round_sizes.iter().sum::<usize>(), required);
+}
+
+#[test]
+fn test_adaptive_sampling_rejects_bad_rounds() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let (commitment, prover, _) = prover_builder
+        .commitment(&rand_vector::<u8>(500), 8)
+        .unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let max_level = verifier.security_level(verifier.domain_size());
+    assert_eq!(
+        AdaptiveSampling::<Blake3>::new(
+            &verifier,
+            max_level + 1,
+            EscalationPolicy::default(),
+            b"seed",
+        )
+        .err(),
+        Some(FridaError::InsufficientSecurity(max_level, max_level + 1))
+    );
+
+    let mut sampling =
+        AdaptiveSampling::<Blake3>::new(&verifier, 20, EscalationPolicy::default(), b"seed")
+            .unwrap();
+    let positions = sampling.pending().to_vec();
+    let proof = prover.open(&positions).unwrap();
+    let mut evaluations = prover.get_evaluations(&positions).unwrap();
+    evaluations[0] += BaseElement::ONE;
+    assert!(sampling.submit(&verifier, &proof, &evaluations).is_err());
+    assert_eq!(sampling.pending(), positions);
+    assert_eq!(sampling.num_verified(), 0);
+}
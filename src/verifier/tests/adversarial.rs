@@ -0,0 +1,293 @@
+use crate::{
+    core::data::build_evaluations_from_data, error::FridaError, prover::proof::FridaProof,
+    utils::test_utils::*,
+};
+use winter_fri::VerifierError;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{DeserializationError, Serializable, SliceReader};
+
+// SETUP
+// ================================================================================================
+
+struct Honest {
+    verifier: TestFridaDasVerifier,
+    proof: FridaProof,
+    evaluations: Vec<BaseElement>,
+    positions: Vec<usize>,
+}
+
+impl Honest {
+    fn verify(&self, proof: &FridaProof, evaluations: &[BaseElement]) -> Result<(), FridaError> {
+        self.verifier.verify(proof, evaluations, &self.positions)
+    }
+
+    fn verify_proof(&self, proof: &FridaProof) -> Result<(), FridaError> {
+        self.verify(proof, &self.evaluations)
+    }
+}
+
+/// Builds an honest non-batched opening over three FRI layers.
+fn honest_opening() -> Honest {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(200);
+    let (commitment, prover, _) = prover_builder.commitment(&data, 4).unwrap();
+    assert_eq!(options.num_fri_layers(commitment.domain_size), 3);
+
+    let all_evaluations: Vec<BaseElement> =
+        build_evaluations_from_data(&data, commitment.domain_size, options.blowup_factor())
+            .unwrap();
+    let positions = vec![1, 5, commitment.domain_size - 1];
+    let evaluations = positions.iter().map(|&p| all_evaluations[p]).collect();
+
+    Honest {
+        verifier: TestFridaDasVerifier::from_commitment(&commitment, options).unwrap(),
+        proof: prover.open(&positions).unwrap(),
+        evaluations,
+        positions,
+    }
+}
+
+/// Builds an honest batched opening with one FRI layer after the batch layer.
+fn honest_batch_opening() -> Honest {
+    let poly_count = 4;
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = (0..poly_count)
+        .map(|_| rand_vector::<u8>(64))
+        .collect::<Vec<_>>();
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 4).unwrap();
+    assert_eq!(options.num_fri_layers(commitment.domain_size), 2);
+
+    let positions = vec![1, 6, commitment.domain_size - 1];
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+
+    Honest {
+        verifier: TestFridaDasVerifier::from_commitment(&commitment, options).unwrap(),
+        proof: prover.open(&positions).unwrap(),
+        evaluations,
+        positions,
+    }
+}
+
+fn assert_rejected_with(result: Result<(), FridaError>, expected: VerifierError) {
+    assert_eq!(result, Err(FridaError::FailToVerify(expected)));
+}
+
+fn assert_rejected_as_malformed(result: Result<(), FridaError>) {
+    assert!(
+        matches!(result, Err(FridaError::DeserializationError(_))),
+        "expected a deserialization error, got {result:?}"
+    );
+}
+
+// TAMPERED PROOFS
+// ================================================================================================
+
+#[test]
+fn honest_proofs_are_accepted() {
+    let honest = honest_opening();
+    honest.verify_proof(&honest.proof).unwrap();
+
+    let honest = honest_batch_opening();
+    honest.verify_proof(&honest.proof).unwrap();
+}
+
+#[test]
+fn flipped_layer_value_is_rejected() {
+    let honest = honest_opening();
+    for layer in 0..honest.proof.num_layers() {
+        let mut proof = honest.proof.clone();
+        proof.test_layers_mut()[layer].test_values_mut()[0] ^= 1;
+        assert_rejected_with(
+            honest.verify_proof(&proof),
+            VerifierError::LayerCommitmentMismatch,
+        );
+    }
+}
+
+#[test]
+fn flipped_evaluation_is_rejected() {
+    let honest = honest_opening();
+    let mut evaluations = honest.evaluations.clone();
+    evaluations[1] += BaseElement::new(1);
+    assert_rejected_with(
+        honest.verify(&honest.proof, &evaluations),
+        VerifierError::InvalidLayerFolding(0),
+    );
+}
+
+#[test]
+fn swapped_layers_are_rejected() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_layers_mut().swap(0, 1);
+    assert_rejected_with(
+        honest.verify_proof(&proof),
+        VerifierError::LayerCommitmentMismatch,
+    );
+}
+
+#[test]
+fn missing_layer_is_rejected() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_layers_mut().pop();
+    assert_rejected_with(
+        honest.verify_proof(&proof),
+        VerifierError::LayerCommitmentMismatch,
+    );
+}
+
+#[test]
+fn truncated_merkle_path_is_rejected() {
+    let honest = honest_opening();
+    for layer in 0..honest.proof.num_layers() {
+        let mut proof = honest.proof.clone();
+        proof.test_layers_mut()[layer].test_paths_mut().pop();
+        assert_rejected_as_malformed(honest.verify_proof(&proof));
+    }
+}
+
+#[test]
+fn wrong_remainder_is_rejected() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_remainder_mut()[0] ^= 1;
+    assert_rejected_with(
+        honest.verify_proof(&proof),
+        VerifierError::RemainderCommitmentMismatch,
+    );
+}
+
+#[test]
+fn flipped_batch_layer_value_is_rejected() {
+    let honest = honest_batch_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_batch_layer_mut().unwrap().test_values_mut()[0] ^= 1;
+    assert_rejected_with(
+        honest.verify_proof(&proof),
+        VerifierError::LayerCommitmentMismatch,
+    );
+}
+
+#[test]
+fn truncated_batch_merkle_path_is_rejected() {
+    let honest = honest_batch_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_batch_layer_mut().unwrap().test_paths_mut().pop();
+    assert_rejected_as_malformed(honest.verify_proof(&proof));
+}
+
+#[test]
+fn mismatched_xi_is_rejected() {
+    let mut honest = honest_batch_opening();
+    honest.verifier.test_xi_mut().unwrap().swap(0, 1);
+    assert_rejected_with(
+        honest.verify_proof(&honest.proof),
+        VerifierError::InvalidLayerFolding(1),
+    );
+}
+
+#[test]
+fn batch_proof_for_single_commitment_is_rejected() {
+    let single = honest_opening();
+    let batch = honest_batch_opening();
+    assert_eq!(
+        single.verify(&batch.proof, &single.evaluations),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
+
+// OVERSIZED PROOFS
+// ================================================================================================
+
+fn assert_rejected_by_limits(result: Result<(), FridaError>) {
+    match result {
+        Err(FridaError::DeserializationError(DeserializationError::InvalidValue(message))) => {
+            assert!(message.starts_with("expected at most"), "{message}")
+        }
+        result => panic!("expected the proof to exceed its limits, got {result:?}"),
+    }
+}
+
+#[test]
+fn honest_proofs_are_within_limits() {
+    for honest in [honest_opening(), honest_batch_opening()] {
+        let limits = honest.verifier.proof_limits(honest.positions.len());
+        assert_eq!(honest.proof.check_limits(&limits), Ok(()));
+    }
+}
+
+#[test]
+fn garbage_layers_are_rejected_before_parsing() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    let layer = proof.test_layers_mut()[0].clone();
+    proof.test_layers_mut().resize(255, layer);
+    assert_rejected_by_limits(honest.verify_proof(&proof));
+}
+
+#[test]
+fn oversized_remainder_is_rejected() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_remainder_mut().resize(1 << 15, 0);
+    assert_rejected_by_limits(honest.verify_proof(&proof));
+}
+
+#[test]
+fn oversized_layer_values_are_rejected() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_layers_mut()[1]
+        .test_values_mut()
+        .resize(1 << 16, 0);
+    assert_rejected_by_limits(honest.verify_proof(&proof));
+
+    let honest = honest_batch_opening();
+    let mut proof = honest.proof.clone();
+    proof
+        .test_batch_layer_mut()
+        .unwrap()
+        .test_values_mut()
+        .resize(1 << 16, 0);
+    assert_rejected_by_limits(honest.verify_proof(&proof));
+}
+
+#[test]
+fn oversized_layer_paths_are_rejected() {
+    let honest = honest_opening();
+    let mut proof = honest.proof.clone();
+    proof.test_layers_mut()[0]
+        .test_paths_mut()
+        .resize(1 << 16, 0);
+    assert_rejected_by_limits(honest.verify_proof(&proof));
+}
+
+#[test]
+fn proofs_are_read_within_limits() {
+    for honest in [honest_opening(), honest_batch_opening()] {
+        let limits = honest.verifier.proof_limits(honest.positions.len());
+        let bytes = honest.proof.to_bytes();
+        let read = FridaProof::read_with_limits(&mut SliceReader::new(&bytes), &limits).unwrap();
+        assert_eq!(read, honest.proof);
+    }
+}
+
+#[test]
+fn oversized_lengths_are_rejected_before_reading() {
+    let honest = honest_opening();
+    let limits = honest.verifier.proof_limits(honest.positions.len());
+
+    // no batch layer, one layer claiming 2^32 - 1 bytes of values, which the source does not hold
+    let mut bytes = vec![0, 1];
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    match FridaProof::read_with_limits(&mut SliceReader::new(&bytes), &limits) {
+        Err(DeserializationError::InvalidValue(message)) => {
+            assert!(message.starts_with("expected at most"), "{message}")
+        }
+        result => panic!("expected the lengths to exceed the limits, got {result:?}"),
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    error::FridaError,
+    prover::{disclosure::AppOpening, ProverCommitment},
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestAppOpening = AppOpening<BaseElement, Blake3>;
+
+fn app_ids() -> Vec<Vec<u8>> {
+    vec![
+        b"rollup-a".to_vec(),
+        b"rollup-b".to_vec(),
+        b"rollup-c".to_vec(),
+    ]
+}
+
+#[test]
+fn test_app_openings() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(300), rand_vector(100), rand_vector(250)];
+
+    let (commitment, prover, _) = prover_builder
+        .commitment_batch_with_app_ids(&data_list, &app_ids(), 8)
+        .unwrap();
+    assert!(commitment.app_ids_root.is_some());
+    assert_eq!(prover.commitment(), commitment);
+
+    // the root travels with the commitment
+    let commitment = ProverCommitment::<Blake3>::read_from_bytes(&commitment.to_bytes()).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let positions = vec![1, 7, 12];
+    let columns = prover.get_evaluations(&positions).unwrap();
+    for (blob, app_id) in app_ids().iter().enumerate() {
+        let opening = prover.open_app(app_id, &positions).unwrap();
+        assert_eq!(opening.blob, blob);
+
+        let opening = TestAppOpening::read_from_bytes(&opening.to_bytes()).unwrap();
+        let evaluations = verifier.verify_app_opening(app_id, &opening).unwrap();
+        let expected = columns
+            .iter()
+            .skip(blob)
+            .step_by(3)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(evaluations, expected);
+    }
+}
+
+#[test]
+fn test_app_openings_of_other_apps() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(200), rand_vector(200), rand_vector(200)];
+
+    let (commitment, prover, _) = prover_builder
+        .commitment_batch_with_app_ids(&data_list, &app_ids(), 8)
+        .unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    // a blob of the same bucket served for another app
+    let opening = prover.open_app(b"rollup-a", &[3, 9]).unwrap();
+    assert_eq!(
+        verifier.verify_app_opening(b"rollup-b", &opening),
+        Err(FridaError::AppIdMismatch)
+    );
+
+    let mut tampered = opening.clone();
+    tampered.blob = 1;
+    assert_eq!(
+        verifier.verify_app_opening(b"rollup-a", &tampered),
+        Err(FridaError::AppIdMismatch)
+    );
+
+    let mut tampered = opening.clone();
+    tampered.blob = 3;
+    assert_eq!(
+        verifier.verify_app_opening(b"rollup-a", &tampered),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+
+    assert_eq!(
+        prover.open_app(b"rollup-d", &[3]),
+        Err(FridaError::UnknownAppId)
+    );
+}
+
+#[test]
+fn test_app_ids_must_be_committed() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(200), rand_vector(200), rand_vector(200)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    assert_eq!(commitment.app_ids_root, None);
+    assert_eq!(
+        prover.open_app(b"rollup-a", &[3]),
+        Err(FridaError::AppIdsNotCommitted)
+    );
+
+    let (bound_commitment, bound_prover, _) = prover_builder
+        .commitment_batch_with_app_ids(&data_list, &app_ids(), 8)
+        .unwrap();
+    assert_ne!(commitment.binding(), bound_commitment.binding());
+
+    // verifiers built from the DAS commitment alone do not know the root
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let opening = bound_prover.open_app(b"rollup-a", &[3]).unwrap();
+    assert_eq!(
+        verifier.verify_app_opening(b"rollup-a", &opening),
+        Err(FridaError::AppIdsNotCommitted)
+    );
+
+    assert_eq!(
+        prover_builder
+            .commitment_batch_with_app_ids(&data_list, &app_ids()[..2], 8)
+            .err(),
+        Some(FridaError::ProofPolyCountMismatch)
+    );
+    let duplicated = vec![b"a".to_vec(), b"b".to_vec(), b"a".to_vec()];
+    assert_eq!(
+        prover_builder
+            .commitment_batch_with_app_ids(&data_list, &duplicated, 8)
+            .err(),
+        Some(FridaError::DuplicateAppId)
+    );
+}
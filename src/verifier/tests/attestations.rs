@@ -0,0 +1,125 @@
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    utils::test_utils::*,
+    verifier::attestation::{Attestation, AttestationAggregator},
+};
+
+#[test]
+fn attestation_verification() {
+    let commitment = test_commitment(&rand_vector::<u8>(200));
+    let positions = [3, 12, 20];
+
+    let attestation = Attestation::new(&commitment, 2, &positions, &TestKey(2));
+    assert_eq!(attestation.root_digest, commitment.root_digest());
+    assert_eq!(
+        attestation.verify(&TestKey(2), &commitment, &positions),
+        Ok(())
+    );
+
+    let decoded = Attestation::<Blake3>::read_from_bytes(&attestation.to_bytes()).unwrap();
+    assert_eq!(decoded, attestation);
+
+    // attestations only count for their signer, commitment and positions
+    assert_eq!(
+        attestation.verify(&TestKey(3), &commitment, &positions),
+        Err(FridaError::InvalidAttestation)
+    );
+    assert_eq!(
+        attestation.verify(
+            &TestKey(2),
+            &test_commitment(&rand_vector::<u8>(200)),
+            &positions
+        ),
+        Err(FridaError::InvalidAttestation)
+    );
+    assert_eq!(
+        attestation.verify(&TestKey(2), &commitment, &positions[1..]),
+        Err(FridaError::InvalidAttestation)
+    );
+    let mut moved = attestation;
+    moved.validator_id = 3;
+    assert_eq!(
+        moved.verify(&TestKey(2), &commitment, &positions),
+        Err(FridaError::InvalidAttestation)
+    );
+}
+
+#[test]
+fn aggregator_reaches_threshold() {
+    let commitment = test_commitment(&rand_vector::<u8>(200));
+    let assignments = vec![vec![0, 4], vec![4, 9], vec![17], vec![30, 31]];
+    let keys = (0..4).map(TestKey).collect::<Vec<_>>();
+    let mut aggregator =
+        AttestationAggregator::new(&commitment, assignments.clone(), keys, 3).unwrap();
+
+    let attest = |validator: u32| {
+        Attestation::new(
+            &commitment,
+            validator,
+            &assignments[validator as usize],
+            &TestKey(validator),
+        )
+    };
+
+    assert_eq!(aggregator.add(attest(0)), Ok(true));
+    assert_eq!(aggregator.add(attest(1)), Ok(true));
+    assert_eq!(aggregator.add(attest(1)), Ok(false));
+    assert_eq!(aggregator.count(), 2);
+    assert!(!aggregator.is_available());
+    assert_eq!(
+        aggregator.coverage().into_iter().collect::<Vec<_>>(),
+        vec![0, 4, 9]
+    );
+
+    // attestations of unknown validators, or signed with another key, are not counted
+    let forged = Attestation::new(&commitment, 2, &assignments[2], &TestKey(0));
+    assert_eq!(aggregator.add(forged), Err(FridaError::InvalidAttestation));
+    let unknown = Attestation::new(&commitment, 4, &[1], &TestKey(4));
+    assert_eq!(aggregator.add(unknown), Err(FridaError::InvalidAttestation));
+    assert!(!aggregator.is_available());
+
+    assert_eq!(aggregator.add(attest(3)), Ok(true));
+    assert!(aggregator.is_available());
+    assert_eq!(aggregator.coverage().len(), 5);
+}
+
+#[test]
+fn aggregator_rejects_bad_threshold() {
+    let commitment = test_commitment(&rand_vector::<u8>(200));
+    let assignments = vec![vec![0], vec![1]];
+
+    for threshold in [0, 3] {
+        let keys = vec![TestKey(0), TestKey(1)];
+        assert!(matches!(
+            AttestationAggregator::new(&commitment, assignments.clone(), keys, threshold),
+            Err(FridaError::BadAttestationThreshold(_, 2))
+        ));
+    }
+    assert!(matches!(
+        AttestationAggregator::new(&commitment, assignments, vec![TestKey(0)], 1),
+        Err(FridaError::BadAttestationThreshold(1, 1))
+    ));
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn ed25519_attestation() {
+    use ed25519_dalek::SigningKey;
+
+    let commitment = test_commitment(&rand_vector::<u8>(200));
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let attestation = Attestation::new(&commitment, 0, &[5, 6], &key);
+
+    assert_eq!(
+        attestation.verify(&key.verifying_key(), &commitment, &[5, 6]),
+        Ok(())
+    );
+    let other = SigningKey::from_bytes(&[8; 32]).verifying_key();
+    assert_eq!(
+        attestation.verify(&other, &commitment, &[5, 6]),
+        Err(FridaError::InvalidAttestation)
+    );
+}
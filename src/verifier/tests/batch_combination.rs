@@ -0,0 +1,120 @@
+use winter_fri::folding::fold_positions;
+use winter_rand_utils::{rand_value, rand_vector};
+
+use crate::{
+    error::FridaError,
+    utils::test_utils::*,
+    verifier::combine_batch_values,
+    winterfell::{f128::BaseElement, FieldElement},
+};
+
+fn manual_combination(values: &[BaseElement], xi: &[BaseElement]) -> Vec<BaseElement> {
+    let mut result = vec![BaseElement::ZERO; values.len() / xi.len()];
+    for (i, value) in values.iter().enumerate() {
+        result[i / xi.len()] += *value * xi[i % xi.len()];
+    }
+    result
+}
+
+#[test]
+fn test_combine_batch_values() {
+    for poly_count in [1, 2, 3, 17] {
+        let xi = rand_vector::<BaseElement>(poly_count);
+        for num_points in [0, 1, 5] {
+            let values = rand_vector::<BaseElement>(poly_count * num_points);
+            assert_eq!(
+                combine_batch_values(&values, &xi).unwrap(),
+                manual_combination(&values, &xi)
+            );
+        }
+    }
+
+    // a single polynomial is only scaled by its coefficient
+    let values = rand_vector::<BaseElement>(4);
+    let xi = [rand_value::<BaseElement>()];
+    let scaled = values
+        .iter()
+        .map(|&value| value * xi[0])
+        .collect::<Vec<_>>();
+    assert_eq!(combine_batch_values(&values, &xi).unwrap(), scaled);
+}
+
+#[test]
+fn test_combine_batch_values_mismatch() {
+    let values = rand_vector::<BaseElement>(7);
+    assert_eq!(
+        combine_batch_values(&values, &rand_vector::<BaseElement>(2)),
+        Err(FridaError::BatchValuesMismatch(7, 2))
+    );
+    assert_eq!(
+        combine_batch_values(&values, &[]),
+        Err(FridaError::BatchValuesMismatch(7, 0))
+    );
+    assert_eq!(
+        combine_batch_values::<BaseElement>(&[], &[]),
+        Err(FridaError::BatchValuesMismatch(0, 0))
+    );
+}
+
+#[test]
+fn test_combine_batch_evaluations() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let positions = [1, 7, 12];
+
+    let (commitment, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(200), 4)
+        .unwrap();
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    assert_eq!(
+        verifier.combine_batch_evaluations(&evaluations),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+
+    for poly_count in [2, 3, 17] {
+        let data_list = (0..poly_count)
+            .map(|_| rand_vector::<u8>(200))
+            .collect::<Vec<_>>();
+        let (commitment, prover) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+        let (verifier, _) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
+        let xi = verifier.xi().unwrap();
+        assert_eq!(xi.len(), poly_count);
+
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+        let combined = verifier.combine_batch_evaluations(&evaluations).unwrap();
+        assert_eq!(combined, manual_combination(&evaluations, xi));
+
+        // the rows of the batch layer combine into the values the opened evaluations combine to
+        let proof = prover.open(&positions).unwrap();
+        let domain_size = verifier.domain_size();
+        let folding_factor = verifier.folding_factors()[0];
+        let (layer_values, _) = proof
+            .parse_batch_layer::<Blake3, BaseElement>(
+                domain_size,
+                folding_factor,
+                poly_count,
+                verifier.batch_leaf_encoding(),
+            )
+            .unwrap();
+        let combined_rows = verifier.combine_batch_evaluations(&layer_values).unwrap();
+        let row_length = domain_size / folding_factor;
+        let folded_positions = fold_positions(&positions, domain_size, folding_factor);
+        for (&position, &value) in positions.iter().zip(&combined) {
+            let row = folded_positions
+                .iter()
+                .position(|&folded| folded == position % row_length)
+                .unwrap();
+            let column = position / row_length;
+            assert_eq!(combined_rows[row * folding_factor + column], value);
+        }
+
+        assert_eq!(
+            verifier.combine_batch_evaluations(&evaluations[1..]),
+            Err(FridaError::BatchValuesMismatch(
+                evaluations.len() - 1,
+                poly_count
+            ))
+        );
+    }
+}
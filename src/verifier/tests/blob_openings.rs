@@ -0,0 +1,139 @@
+use crate::{
+    error::FridaError,
+    prover::{blob::BlobOpening, BatchLeafEncoding, Commitment},
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestBlobOpening = BlobOpening<BaseElement, Blake3>;
+
+fn data_list() -> Vec<Vec<u8>> {
+    vec![rand_vector::<u8>(300), rand_vector(100), rand_vector(250)]
+}
+
+#[test]
+fn test_per_blob_commitment() {
+    let options = FriOptions::new(2, 4, 3);
+    let prover_builder = TestFridaProverBuilder::new(options.clone())
+        .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+
+    let (commitment, prover) = prover_builder
+        .commit_and_prove_batch(&data_list(), 8)
+        .unwrap();
+    assert_eq!(commitment.batch_leaf_encoding, BatchLeafEncoding::PerBlob);
+    let bytes = commitment.to_bytes();
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
+
+    // full openings keep working, with the leaves hashed per blob
+    let positions = [1, 6, 30];
+    verifier
+        .verify(
+            &prover.open(&positions).unwrap(),
+            &prover.get_evaluations(&positions).unwrap(),
+            &positions,
+        )
+        .unwrap();
+
+    // and are rejected by a verifier expecting flat leaves
+    let mut flat = Commitment::<Blake3>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(flat.batch_leaf_encoding, BatchLeafEncoding::PerBlob);
+    flat.batch_leaf_encoding = BatchLeafEncoding::Flat;
+    assert!(TestFridaDasVerifier::new(flat, options).is_err());
+}
+
+#[test]
+fn test_blob_openings() {
+    let options = FriOptions::new(2, 4, 3);
+    let prover_builder = TestFridaProverBuilder::new(options.clone())
+        .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list(), 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let positions = [0, 5, 17, 5 + commitment.domain_size / 4];
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    for blob in 0..3 {
+        let opening = prover.open_blob(blob, &positions).unwrap();
+        // the two positions sharing a leaf are opened together
+        assert_eq!(opening.values.len(), 3 * 4);
+        assert_eq!(opening.siblings.len(), 3 * 2);
+
+        let opening = TestBlobOpening::read_from_bytes(&opening.to_bytes()).unwrap();
+        let values = verifier.verify_blob_opening(&opening).unwrap();
+        let expected = evaluations.iter().skip(blob).step_by(3).copied();
+        assert_eq!(values, expected.collect::<Vec<_>>());
+    }
+
+    assert_eq!(
+        prover.open_blob(3, &[0]),
+        Err(FridaError::CellOutOfBounds(3, 0))
+    );
+    assert_eq!(
+        prover.open_blob(0, &[commitment.domain_size]),
+        Err(FridaError::CellOutOfBounds(0, commitment.domain_size))
+    );
+}
+
+#[test]
+fn test_tampered_blob_openings() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone())
+        .with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list(), 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let opening = prover.open_blob(1, &[3, 12]).unwrap();
+    verifier.verify_blob_opening(&opening).unwrap();
+
+    let mut tampered = opening.clone();
+    tampered.values[0] += BaseElement::ONE;
+    assert_eq!(
+        verifier.verify_blob_opening(&tampered),
+        Err(FridaError::InvalidBlobOpening)
+    );
+
+    // the values of a blob do not open another one
+    let mut moved = opening.clone();
+    moved.blob = 2;
+    assert_eq!(
+        verifier.verify_blob_opening(&moved),
+        Err(FridaError::InvalidBlobOpening)
+    );
+
+    let mut truncated = opening.clone();
+    truncated.siblings.pop();
+    assert_eq!(
+        verifier.verify_blob_opening(&truncated),
+        Err(FridaError::InvalidBlobOpening)
+    );
+
+    let mut other_positions = opening;
+    other_positions.positions = vec![4, 12];
+    assert_eq!(
+        verifier.verify_blob_opening(&other_positions),
+        Err(FridaError::InvalidBlobOpening)
+    );
+}
+
+#[test]
+fn test_flat_commitments_do_not_open_blobs() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list(), 8).unwrap();
+    assert_eq!(commitment.batch_leaf_encoding, BatchLeafEncoding::Flat);
+    assert_eq!(
+        prover.open_blob(0, &[1]),
+        Err(FridaError::BlobOpeningUnsupported)
+    );
+
+    // single blobs have nothing to hide, whatever the encoding
+    let prover_builder = prover_builder.with_batch_leaf_encoding(BatchLeafEncoding::PerBlob);
+    let (commitment, prover, _) = prover_builder.commitment(&data_list()[0], 8).unwrap();
+    assert_eq!(commitment.batch_leaf_encoding, BatchLeafEncoding::Flat);
+    assert_eq!(
+        prover.open_blob(0, &[1]),
+        Err(FridaError::BlobOpeningUnsupported)
+    );
+}
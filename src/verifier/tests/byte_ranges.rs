@@ -0,0 +1,105 @@
+use crate::{
+    error::FridaError,
+    prover::byte_range::ByteRangeProof,
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestByteRangeProof = ByteRangeProof<BaseElement>;
+
+#[test]
+fn test_byte_range_proofs() {
+    let options = FriOptions::new(4, 2, 1);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(2500);
+
+    let (commitment, prover, _) = prover_builder.commitment(&data, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    for range in [0..0, 0..5, 5..9, 1000..2000, 2400..2500, 0..2500] {
+        let proof = prover.open_byte_range(0, range.clone(), 4).unwrap();
+        // proofs travel as bytes between peers
+        let proof = TestByteRangeProof::read_from_bytes(&proof.to_bytes()).unwrap();
+        verifier.verify_byte_range(&proof, &data[range]).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_byte_range_proofs() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(300), rand_vector(100), rand_vector(250)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    for (blob, data) in data_list.iter().enumerate() {
+        let proof = prover.open_byte_range(blob, 40..90, 2).unwrap();
+        verifier.verify_byte_range(&proof, &data[40..90]).unwrap();
+
+        // the same range of another blob
+        let other = &data_list[(blob + 1) % data_list.len()];
+        assert_eq!(
+            verifier.verify_byte_range(&proof, &other[40..90]),
+            Err(FridaError::ByteRangeMismatch)
+        );
+    }
+
+    // the second blob is shorter than the others
+    assert_eq!(
+        prover.open_byte_range(1, 90..101, 2),
+        Err(FridaError::ByteRangeOutOfBounds(90, 101))
+    );
+    assert!(prover.open_byte_range(0, 90..101, 2).is_ok());
+
+    // blobs past the batch are reported rather than panicking
+    assert_eq!(
+        prover.open_byte_range(data_list.len(), 0..10, 2),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
+
+#[test]
+fn test_tampered_byte_range_proofs() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(500);
+
+    let (commitment, prover, _) = prover_builder.commitment(&data, 4).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let proof = prover.open_byte_range(0, 100..200, 2).unwrap();
+    verifier.verify_byte_range(&proof, &data[100..200]).unwrap();
+
+    let mut wrong_bytes = data[100..200].to_vec();
+    wrong_bytes[50] ^= 1;
+    assert_eq!(
+        verifier.verify_byte_range(&proof, &wrong_bytes),
+        Err(FridaError::ByteRangeMismatch)
+    );
+
+    let mut wrong_evaluation = proof.clone();
+    wrong_evaluation.evaluations[1] += BaseElement::ONE;
+    assert!(matches!(
+        verifier.verify_byte_range(&wrong_evaluation, &data[100..200]),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    // shifting the range opens other positions than the proof
+    let mut wrong_offset = proof.clone();
+    wrong_offset.offset = 300;
+    assert!(matches!(
+        verifier.verify_byte_range(&wrong_offset, &data[300..400]),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    // bytes past the end of the data cannot be opened, even though they are committed to as padding
+    assert_eq!(
+        prover.open_byte_range(0, 450..510, 2),
+        Err(FridaError::ByteRangeOutOfBounds(450, 510))
+    );
+    let tail = prover.open_byte_range(0, 450..500, 2).unwrap();
+    let mut padded = data[450..].to_vec();
+    padded.push(0);
+    assert!(verifier.verify_byte_range(&tail, &padded).is_err());
+}
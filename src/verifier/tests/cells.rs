@@ -0,0 +1,94 @@
+use crate::{
+    error::FridaError,
+    prover::cell::Cell,
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestCell = Cell<BaseElement>;
+
+#[test]
+fn test_batch_cells() {
+    let options = FriOptions::new(2, 4, 3);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(300), rand_vector(100), rand_vector(250)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    for (row, col) in [(0, 0), (1, 5), (2, 17), (2, commitment.domain_size - 1)] {
+        let cell = prover.get_cell(row, col).unwrap();
+        assert_eq!(cell.value, prover.get_evaluations(&[col]).unwrap()[row]);
+        assert_eq!(cell.column(), prover.get_evaluations(&[col]).unwrap());
+
+        // cells travel as bytes between peers, behind a fixed-size header
+        let bytes = cell.to_bytes();
+        assert_eq!(bytes[..4], (row as u32).to_le_bytes());
+        assert_eq!(bytes[4..8], (col as u32).to_le_bytes());
+        let cell = TestCell::read_from_bytes(&bytes).unwrap();
+        verifier.verify_cell(&cell).unwrap();
+    }
+}
+
+#[test]
+fn test_single_poly_cells() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(500);
+
+    let (commitment, prover, _) = prover_builder.commitment(&data, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let cell = prover.get_cell(0, 9).unwrap();
+    assert!(cell.proof.siblings.is_empty());
+    verifier.verify_cell(&cell).unwrap();
+
+    assert_eq!(
+        prover.get_cell(1, 9),
+        Err(FridaError::CellOutOfBounds(1, 9))
+    );
+}
+
+#[test]
+fn test_tampered_cells() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(200), rand_vector(200)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let domain_size = commitment.domain_size;
+    let cell = prover.get_cell(1, 3).unwrap();
+
+    assert_eq!(
+        prover.get_cell(0, domain_size),
+        Err(FridaError::CellOutOfBounds(0, domain_size))
+    );
+
+    let mut tampered = cell.clone();
+    tampered.value += BaseElement::ONE;
+    assert!(matches!(
+        verifier.verify_cell(&tampered),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    // the value of another row at the same column
+    let mut tampered = cell.clone();
+    tampered.row = 0;
+    assert!(verifier.verify_cell(&tampered).is_err());
+
+    let mut tampered = cell.clone();
+    tampered.row = 2;
+    assert_eq!(
+        verifier.verify_cell(&tampered),
+        Err(FridaError::CellOutOfBounds(2, 3))
+    );
+
+    let mut tampered = cell.clone();
+    tampered.proof.siblings.clear();
+    assert_eq!(
+        verifier.verify_cell(&tampered),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
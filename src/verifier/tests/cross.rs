@@ -0,0 +1,161 @@
+use crate::error::FridaError;
+use crate::prover::builder::FridaProverBuilder;
+use crate::utils::test_utils::*;
+use crate::verifier::cross::{cross_positions, shared_domain_size, verify_cross, CrossOpening};
+use crate::verifier::das::FridaDasVerifier;
+use winter_crypto::hashers::Sha3_256;
+use winter_fri::FriOptions;
+use winter_math::{fields::f128::BaseElement, FieldElement};
+use winter_rand_utils::rand_vector;
+
+type Sha3 = Sha3_256<BaseElement>;
+type Sha3FridaProverBuilder = FridaProverBuilder<BaseElement, Sha3>;
+type Sha3FridaDasVerifier = FridaDasVerifier<BaseElement, Sha3, Sha3>;
+
+#[test]
+fn test_cross_verification_across_hashers_and_blowups() {
+    let options_a = test_options();
+    let options_b = FriOptions::new(4, 4, 3);
+    let data_list = [rand_vector::<u8>(300), rand_vector(200)];
+
+    for data_list in [&data_list[..1], &data_list[..]] {
+        let (commitment_a, prover_a, _) = if data_list.len() == 1 {
+            TestFridaProverBuilder::new(options_a.clone()).commitment(&data_list[0], 4)
+        } else {
+            TestFridaProverBuilder::new(options_a.clone()).commitment_batch(data_list, 4)
+        }
+        .unwrap();
+        let (commitment_b, prover_b, _) = if data_list.len() == 1 {
+            Sha3FridaProverBuilder::new(options_b.clone()).commitment(&data_list[0], 4)
+        } else {
+            Sha3FridaProverBuilder::new(options_b.clone()).commitment_batch(data_list, 4)
+        }
+        .unwrap();
+        assert!(commitment_a.domain_size < commitment_b.domain_size);
+
+        let shared_size = shared_domain_size(commitment_a.domain_size, commitment_b.domain_size);
+        let shared_positions = [0, 3, 17, shared_size - 1];
+        let positions_a =
+            cross_positions(&shared_positions, shared_size, commitment_a.domain_size).unwrap();
+        let positions_b =
+            cross_positions(&shared_positions, shared_size, commitment_b.domain_size).unwrap();
+        let evaluations = prover_a.get_evaluations(&positions_a).unwrap();
+        assert_eq!(evaluations, prover_b.get_evaluations(&positions_b).unwrap());
+
+        let verifier_a =
+            TestFridaDasVerifier::from_commitment(&commitment_a, options_a.clone()).unwrap();
+        let verifier_b =
+            Sha3FridaDasVerifier::from_commitment(&commitment_b, options_b.clone()).unwrap();
+        let proof_a = prover_a.open(&positions_a).unwrap();
+        let proof_b = prover_b.open(&positions_b).unwrap();
+        let opening_a = || CrossOpening {
+            verifier: &verifier_a,
+            proof: &proof_a,
+        };
+        let opening_b = || CrossOpening {
+            verifier: &verifier_b,
+            proof: &proof_b,
+        };
+
+        verify_cross(opening_a(), opening_b(), &shared_positions, &evaluations).unwrap();
+        verify_cross(opening_b(), opening_a(), &shared_positions, &evaluations).unwrap();
+
+        let mut wrong_evaluations = evaluations.clone();
+        wrong_evaluations[1] += BaseElement::ONE;
+        assert!(matches!(
+            verify_cross(
+                opening_a(),
+                opening_b(),
+                &shared_positions,
+                &wrong_evaluations
+            ),
+            Err(FridaError::FailToVerify(_))
+        ));
+
+        assert_eq!(
+            verify_cross(opening_a(), opening_b(), &[shared_size], &evaluations),
+            Err(FridaError::PositionOutOfDomain(shared_size, shared_size))
+        );
+    }
+}
+
+#[test]
+fn test_cross_verification_of_different_data() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+
+    let (commitment_a, prover_a, _) = prover_builder.commitment(&rand_vector(300), 4).unwrap();
+    let (commitment_b, prover_b, _) = prover_builder.commitment(&rand_vector(300), 4).unwrap();
+    let verifier_a = TestFridaDasVerifier::from_commitment(&commitment_a, options.clone()).unwrap();
+    let verifier_b = TestFridaDasVerifier::from_commitment(&commitment_b, options).unwrap();
+
+    // each commitment verifies against its own evaluations, but they are not the same
+    let positions = [2, 9];
+    let proof_a = prover_a.open(&positions).unwrap();
+    let proof_b = prover_b.open(&positions).unwrap();
+    let evaluations = prover_a.get_evaluations(&positions).unwrap();
+    verifier_a
+        .verify(&proof_a, &evaluations, &positions)
+        .unwrap();
+    assert!(matches!(
+        verify_cross(
+            CrossOpening {
+                verifier: &verifier_a,
+                proof: &proof_a,
+            },
+            CrossOpening {
+                verifier: &verifier_b,
+                proof: &proof_b,
+            },
+            &positions,
+            &evaluations,
+        ),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
+
+#[test]
+fn test_cross_verification_poly_count_mismatch() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = [rand_vector(100), rand_vector(100)];
+
+    let (commitment_a, prover_a, _) = prover_builder.commitment(&data_list[0], 4).unwrap();
+    let (commitment_b, prover_b, _) = prover_builder.commitment_batch(&data_list, 4).unwrap();
+    let verifier_a = TestFridaDasVerifier::from_commitment(&commitment_a, options.clone()).unwrap();
+    let verifier_b = TestFridaDasVerifier::from_commitment(&commitment_b, options).unwrap();
+
+    let positions = [1];
+    assert_eq!(
+        verify_cross(
+            CrossOpening {
+                verifier: &verifier_a,
+                proof: &prover_a.open(&positions).unwrap(),
+            },
+            CrossOpening {
+                verifier: &verifier_b,
+                proof: &prover_b.open(&positions).unwrap(),
+            },
+            &positions,
+            &prover_a.get_evaluations(&positions).unwrap(),
+        ),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
+
+#[test]
+fn test_cross_positions_invalid_domains() {
+    assert_eq!(cross_positions(&[1, 3], 8, 32), Ok(vec![4, 12]));
+    assert_eq!(
+        cross_positions(&[1], 32, 8),
+        Err(FridaError::IncompatibleCommitments)
+    );
+    assert_eq!(
+        cross_positions(&[1], 0, 8),
+        Err(FridaError::IncompatibleCommitments)
+    );
+    assert_eq!(
+        cross_positions(&[8], 8, 32),
+        Err(FridaError::PositionOutOfDomain(8, 8))
+    );
+}
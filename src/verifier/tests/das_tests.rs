@@ -1,10 +1,18 @@
+use crate::core::data::build_evaluations_from_data;
+use crate::error::FridaError;
+use crate::prover::builder::FridaProverBuilder;
+use crate::prover::deadline::DispersalHint;
 use crate::prover::proof::FridaProof;
-use crate::prover::Commitment;
+use crate::prover::{BatchLeafEncoding, Commitment, FridaProver, ProverCommitment};
 use crate::utils::test_utils::*;
+use crate::verifier::das::FridaDasVerifier;
+use winter_crypto::hashers::{Blake3_192, Sha3_256};
+use winter_crypto::Digest;
 use winter_fri::folding::fold_positions;
-use winter_fri::{FriOptions, ProverChannel};
-use winter_math::fields::f128::BaseElement;
+use winter_fri::{FriOptions, ProverChannel, VerifierError};
+use winter_math::{fields::f128::BaseElement, FieldElement};
 use winter_rand_utils::{rand_value, rand_vector};
+use winter_utils::{Deserializable, Serializable};
 
 #[test]
 fn test_drawn_alpha() {
@@ -24,12 +32,14 @@ fn test_drawn_alpha() {
 
     // instantiate the prover and generate the proof
     let prover_builder = TestFridaProverBuilder::new(options.clone());
-    let prover = prover_builder.test_build_layers(&mut channel, evaluations);
+    let prover = prover_builder
+        .test_build_layers(&mut channel, evaluations)
+        .unwrap();
     let prover_drawn_alpha = channel.public_coin.test_drawn_alphas();
     let roots = channel.commitments.clone();
 
     let positions = channel.draw_query_positions();
-    let proof = prover.open(&positions);
+    let proof = prover.open(&positions).unwrap();
 
     let (verifier, _coin) = TestFridaDasVerifier::new(
         Commitment {
@@ -38,6 +48,11 @@ fn test_drawn_alpha() {
             domain_size,
             num_queries: 32,
             poly_count: 1,
+            ood_evaluation: Vec::new(),
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            num_partitions: 1,
+            data_roots: None,
+            profile: None,
         },
         options.clone(),
     )
@@ -63,7 +78,7 @@ fn test_drawn_alpha() {
     let prover_drawn_alpha = channel.public_coin.test_drawn_alphas();
     let roots = channel.commitments.clone();
     let positions = channel.draw_query_positions();
-    let proof = prover.open(&positions);
+    let proof = prover.open(&positions).unwrap();
 
     let (verifier, _coin) = TestFridaDasVerifier::new(
         Commitment {
@@ -72,6 +87,11 @@ fn test_drawn_alpha() {
             domain_size: commitment.domain_size,
             num_queries: 32,
             poly_count: 10,
+            ood_evaluation: Vec::new(),
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            num_partitions: 1,
+            data_roots: None,
+            profile: None,
         },
         options.clone(),
     )
@@ -111,7 +131,7 @@ fn test_drawn_alpha_batch() {
     let prover_drawn_alpha = channel.public_coin.test_drawn_alphas();
     let roots = channel.commitments.clone();
     let positions = channel.draw_query_positions();
-    let proof = prover.open(&positions);
+    let proof = prover.open(&positions).unwrap();
 
     let (verifier, _coin) = TestFridaDasVerifier::new(
         Commitment {
@@ -120,6 +140,11 @@ fn test_drawn_alpha_batch() {
             domain_size: commitment.domain_size,
             num_queries: 32,
             poly_count: 10,
+            ood_evaluation: Vec::new(),
+            batch_leaf_encoding: BatchLeafEncoding::Flat,
+            num_partitions: 1,
+            data_roots: None,
+            profile: None,
         },
         options.clone(),
     )
@@ -132,13 +157,12 @@ fn test_drawn_alpha_batch() {
 }
 
 fn verify_batch(
-    data_evaluations: &[BaseElement],
+    prover: &FridaProver<BaseElement, Blake3>,
     proof: FridaProof,
     commitment: Commitment<Blake3>,
     options: FriOptions,
     domain_size: usize,
 ) {
-    let poly_count = commitment.poly_count;
     let folding_factor = options.folding_factor();
 
     let (verifier, coin) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
@@ -147,17 +171,7 @@ fn verify_batch(
     query_positions.dedup();
     query_positions = fold_positions(&query_positions, domain_size, folding_factor);
 
-    let mut evaluations = vec![];
-    for position in query_positions.iter() {
-        let bucket = position % (domain_size / folding_factor);
-        let start_index = (position / (domain_size / folding_factor)) * poly_count;
-        data_evaluations[bucket * poly_count * folding_factor + start_index
-            ..bucket * poly_count * folding_factor + start_index + poly_count]
-            .iter()
-            .for_each(|e| {
-                evaluations.push(*e);
-            });
-    }
+    let evaluations = prover.evaluations_at(&query_positions).unwrap().concat();
 
     verifier
         .verify(&proof, &evaluations, &query_positions)
@@ -184,13 +198,7 @@ fn test_verify_batch() {
     let proof = commitment.proof.clone();
     let domain_size = commitment.domain_size;
 
-    verify_batch(
-        prover.get_first_layer_evaluations(),
-        proof,
-        commitment,
-        options,
-        domain_size,
-    );
+    verify_batch(&prover, proof, commitment, options, domain_size);
 }
 
 #[test]
@@ -210,11 +218,307 @@ fn test_batching_only_batch_layer() {
     let proof = commitment.proof.clone();
     let domain_size = commitment.domain_size;
 
-    verify_batch(
-        prover.get_first_layer_evaluations(),
-        proof,
-        commitment,
-        options,
-        domain_size,
+    verify_batch(&prover, proof, commitment, options, domain_size);
+}
+
+#[test]
+fn test_mixed_hashers() {
+    type Blake3Tree = Blake3_192<BaseElement>;
+    type Sha3Transcript = Sha3_256<BaseElement>;
+
+    let options = FriOptions::new(2, 2, 0);
+    let prover_builder =
+        FridaProverBuilder::<BaseElement, Blake3Tree, Sha3Transcript>::new(options.clone());
+
+    let data = rand_vector::<u8>(200);
+    let (commitment, prover) = prover_builder.commit_and_prove(&data, 4).unwrap();
+
+    // roots are 24-byte Blake3 digests and must survive a serialization round trip
+    let commitment_bytes = commitment.to_bytes();
+    let commitment = Commitment::<Blake3Tree>::read_from_bytes(&commitment_bytes).unwrap();
+
+    let domain_size = commitment.domain_size;
+    let evaluations: Vec<BaseElement> =
+        build_evaluations_from_data(&data, domain_size, options.blowup_factor()).unwrap();
+    let positions = [1, domain_size / 2 + 1];
+    let proof = prover.open(&positions).unwrap();
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+
+    let (verifier, _coin) = FridaDasVerifier::<BaseElement, Sha3Transcript, Blake3Tree>::new(
+        Commitment::<Blake3Tree>::read_from_bytes(&commitment_bytes).unwrap(),
+        options.clone(),
+    )
+    .unwrap();
+    verifier
+        .verify(&proof, &queried_evaluations, &positions)
+        .unwrap();
+
+    // a verifier using a different transcript hasher derives different challenges
+    assert!(
+        FridaDasVerifier::<BaseElement, Blake3Tree, Blake3Tree>::new(commitment, options).is_err()
+    );
+}
+
+/// Rewrites the serialized remainder of `proof`, which sits right before the trailing
+/// partition byte and is prefixed by its length as a `u16`.
+fn tamper_remainder(proof: &FridaProof, tamper: impl FnOnce(&mut Vec<u8>)) -> FridaProof {
+    let bytes = proof.to_bytes();
+    let remainder_len = proof.num_remainder_elements::<BaseElement>() * BaseElement::ELEMENT_BYTES;
+    let remainder_start = bytes.len() - 1 - remainder_len;
+
+    let mut remainder = bytes[remainder_start..bytes.len() - 1].to_vec();
+    tamper(&mut remainder);
+
+    let mut tampered = bytes[..remainder_start - 2].to_vec();
+    tampered.extend_from_slice(&(remainder.len() as u16).to_le_bytes());
+    tampered.extend_from_slice(&remainder);
+    tampered.push(bytes[bytes.len() - 1]);
+    FridaProof::read_from_bytes(&tampered).unwrap()
+}
+
+fn remainder_test_setup() -> (
+    ProverCommitment<Blake3>,
+    FridaProof,
+    Vec<BaseElement>,
+    Vec<usize>,
+    FriOptions,
+) {
+    let options = FriOptions::new(2, 2, 7);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(200);
+    let (commitment, prover, _) = prover_builder.commitment(&data, 4).unwrap();
+
+    let evaluations: Vec<BaseElement> =
+        build_evaluations_from_data(&data, commitment.domain_size, options.blowup_factor())
+            .unwrap();
+    let positions = vec![1, 2, commitment.domain_size - 1];
+    let queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect();
+    let proof = prover.open(&positions).unwrap();
+
+    (commitment, proof, queried_evaluations, positions, options)
+}
+
+#[test]
+fn test_remainder_commitment_mismatch() {
+    let (commitment, proof, evaluations, positions, options) = remainder_test_setup();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    let proof = tamper_remainder(&proof, |remainder| remainder[0] ^= 1);
+    assert_eq!(
+        verifier.verify(&proof, &evaluations, &positions),
+        Err(FridaError::FailToVerify(
+            VerifierError::RemainderCommitmentMismatch
+        ))
+    );
+}
+
+#[test]
+fn test_remainder_degree_too_large() {
+    let (commitment, proof, evaluations, positions, options) = remainder_test_setup();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let max_degree = proof.num_remainder_elements::<BaseElement>() - 1;
+    let proof = tamper_remainder(&proof, |remainder| {
+        remainder.resize(remainder.len() * 2, 0);
+    });
+    assert_eq!(
+        verifier.verify(&proof, &evaluations, &positions),
+        Err(FridaError::FailToVerify(
+            VerifierError::RemainderDegreeMismatch(max_degree)
+        ))
+    );
+}
+
+#[test]
+fn test_missing_remainder_commitment() {
+    let (mut commitment, proof, evaluations, positions, options) = remainder_test_setup();
+    commitment.roots.pop();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    assert_eq!(
+        verifier.verify(&proof, &evaluations, &positions),
+        Err(FridaError::FailToVerify(
+            VerifierError::LayerCommitmentMismatch
+        ))
+    );
+}
+
+#[test]
+fn test_commitment_positions_are_replayed() {
+    let options = test_options();
+    let data = rand_vector::<u8>(500);
+    for schedule in [vec![], vec![8, 2]] {
+        let builder =
+            TestFridaProverBuilder::new(options.clone()).with_folding_schedule(schedule.clone());
+        let (commitment, _) = builder.commit_and_prove(&data, 16).unwrap();
+        let (_, _, positions) = builder.commitment(&data, 16).unwrap();
+
+        let replayed = TestFridaDasVerifier::verify_commitment_positions_with_schedule(
+            &commitment,
+            options.clone(),
+            &schedule,
+        )
+        .unwrap();
+        assert_eq!(replayed, positions);
+
+        let mut covered = positions.clone();
+        covered.sort_unstable();
+        covered.dedup();
+        assert_eq!(
+            commitment
+                .covered_positions::<BaseElement, Blake3>()
+                .unwrap(),
+            covered
+        );
+    }
+}
+
+#[test]
+fn test_commitment_positions_require_a_valid_proof() {
+    let options = test_options();
+    let data = rand_vector::<u8>(500);
+    let (mut commitment, _) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&data, 16)
+        .unwrap();
+    let covered = commitment
+        .covered_positions::<BaseElement, Blake3>()
+        .unwrap();
+
+    commitment.proof.test_remainder_mut()[0] ^= 1;
+    assert_eq!(
+        TestFridaDasVerifier::verify_commitment_positions(&commitment, options),
+        Err(FridaError::InvalidDASCommitment)
+    );
+    // positions only depend on the transcript, which the proof is not part of
+    assert_eq!(
+        commitment
+            .covered_positions::<BaseElement, Blake3>()
+            .unwrap(),
+        covered
+    );
+}
+
+#[test]
+fn test_remainder_only_verification() {
+    let (commitment, proof, evaluations, positions, options) = remainder_test_setup();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options.clone()).unwrap();
+    verifier.verify_remainder_only(&proof).unwrap();
+
+    // the filter is not sound: tampered layers go unnoticed
+    let mut tampered = proof.clone();
+    tampered.test_layers_mut()[0].test_values_mut()[0] ^= 1;
+    verifier.verify_remainder_only(&tampered).unwrap();
+    assert!(verifier
+        .verify(&tampered, &evaluations, &positions)
+        .is_err());
+
+    let tampered = tamper_remainder(&proof, |remainder| remainder[0] ^= 1);
+    assert_eq!(
+        verifier.verify_remainder_only(&tampered),
+        Err(FridaError::FailToVerify(
+            VerifierError::RemainderCommitmentMismatch
+        ))
+    );
+
+    let max_degree = proof.num_remainder_elements::<BaseElement>() - 1;
+    let tampered = tamper_remainder(&proof, |remainder| {
+        remainder.resize(remainder.len() * 2, 0);
+    });
+    assert_eq!(
+        verifier.verify_remainder_only(&tampered),
+        Err(FridaError::FailToVerify(
+            VerifierError::RemainderDegreeMismatch(max_degree)
+        ))
+    );
+
+    let tampered = tamper_remainder(&proof, |remainder| remainder.truncate(1));
+    assert!(matches!(
+        verifier.verify_remainder_only(&tampered),
+        Err(FridaError::DeserializationError(_))
+    ));
+
+    let mut commitment = commitment;
+    commitment.roots.pop();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    assert_eq!(
+        verifier.verify_remainder_only(&proof),
+        Err(FridaError::FailToVerify(
+            VerifierError::LayerCommitmentMismatch
+        ))
+    );
+}
+
+#[test]
+fn test_root_digest() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(500);
+    let (commitment, _, _) = builder.commitment(&data, 4).unwrap();
+    let digest = commitment.root_digest();
+
+    let (das_commitment, _) = builder.commit_and_prove(&data, 4).unwrap();
+    assert_eq!(das_commitment.root_digest(), digest);
+
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options.clone()).unwrap();
+    assert_eq!(verifier.root_digest(), digest);
+    verifier.check_root_digest(&digest).unwrap();
+    let verifier = TestFridaDasVerifier::new(das_commitment, options.clone())
+        .unwrap()
+        .0;
+    verifier.check_root_digest(&digest).unwrap();
+
+    // the digest binds the parameters as well as the roots
+    let bytes = commitment.to_bytes();
+    let mut other = ProverCommitment::<Blake3>::read_from_bytes(&bytes).unwrap();
+    other.poly_count += 1;
+    assert_ne!(other.root_digest(), digest);
+    let mut other = ProverCommitment::<Blake3>::read_from_bytes(&bytes).unwrap();
+    other.roots.pop();
+    assert_ne!(other.root_digest(), digest);
+
+    // and every other field of the binding, which it is the byte encoding of
+    assert_eq!(commitment.binding().as_bytes(), digest);
+    let corruptions: [fn(&mut ProverCommitment<Blake3>); 5] = [
+        |other| other.num_partitions *= 2,
+        |other| other.app_ids_root = Some(other.roots[0]),
+        |other| other.ood_evaluation = vec![1],
+        |other| {
+            other.dispersal_hint = Some(DispersalHint {
+                deadline: 100,
+                expected_shares: 4,
+            })
+        },
+        |other| other.batch_leaf_encoding = BatchLeafEncoding::PerBlob,
+    ];
+    for corrupt in corruptions {
+        let mut other = ProverCommitment::<Blake3>::read_from_bytes(&bytes).unwrap();
+        corrupt(&mut other);
+        assert_ne!(other.root_digest(), digest);
+        assert_eq!(other.binding().as_bytes(), other.root_digest());
+    }
+
+    // verifiers recompute the digest over the app ids root and dispersal hint they were built with
+    let mut hinted = commitment.clone();
+    hinted.dispersal_hint = Some(DispersalHint {
+        deadline: 100,
+        expected_shares: 4,
+    });
+    let hinted_verifier = TestFridaDasVerifier::from_commitment(&hinted, options.clone()).unwrap();
+    hinted_verifier
+        .check_root_digest(&hinted.root_digest())
+        .unwrap();
+    assert_eq!(
+        hinted_verifier.check_root_digest(&digest),
+        Err(FridaError::RootDigestMismatch)
+    );
+
+    let (other, _, _) = builder.commitment(&rand_vector::<u8>(500), 4).unwrap();
+    assert_eq!(
+        verifier.check_root_digest(&other.root_digest()),
+        Err(FridaError::RootDigestMismatch)
     );
 }
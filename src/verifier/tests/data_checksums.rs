@@ -0,0 +1,71 @@
+use crate::{
+    core::data::recover_data_from_evaluations,
+    error::FridaError,
+    prover::ProverCommitment,
+    utils::test_utils::*,
+    winterfell::{Deserializable, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+#[test]
+fn test_data_checksums() {
+    let options = test_options();
+    let data: Vec<u8> = rand_vector(200);
+
+    let (plain, _, _) = TestFridaProverBuilder::new(options.clone())
+        .commitment(&data, 4)
+        .unwrap();
+    assert!(plain.data_checksums.is_empty());
+    assert_eq!(
+        plain.check_data(0, &data),
+        Err(FridaError::DataChecksumsNotCommitted)
+    );
+
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_data_checksums(true);
+    let (commitment, prover, _) = prover_builder.commitment(&data, 4).unwrap();
+    assert_eq!(commitment.data_checksums.len(), 1);
+    assert_eq!(commitment, prover.commitment());
+    assert_eq!(
+        commitment,
+        ProverCommitment::<Blake3>::read_from_bytes(&commitment.to_bytes()).unwrap()
+    );
+    // checksums do not change what receipts are bound to
+    assert_eq!(commitment.binding(), plain.binding());
+
+    // a reconstructing client decodes the data from every evaluation
+    let positions = (0..commitment.domain_size).collect::<Vec<_>>();
+    let recovered = recover_data_from_evaluations(
+        &prover.get_evaluations(&positions).unwrap(),
+        &positions,
+        commitment.domain_size,
+        options.blowup_factor(),
+    )
+    .unwrap();
+    commitment.check_data(0, &recovered).unwrap();
+
+    assert_eq!(
+        commitment.check_data(0, &recovered[1..]),
+        Err(FridaError::DataChecksumMismatch(0))
+    );
+    assert_eq!(
+        commitment.check_data(1, &recovered),
+        Err(FridaError::BlobOutOfRange(1, 1))
+    );
+}
+
+#[test]
+fn test_batch_data_checksums() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options).with_data_checksums(true);
+    let data_list = vec![rand_vector(100), rand_vector(50), rand_vector(70)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 4).unwrap();
+    assert_eq!(commitment, prover.commitment());
+    for (blob, data) in data_list.iter().enumerate() {
+        commitment.check_data(blob, data).unwrap();
+    }
+    assert_eq!(
+        commitment.check_data(1, &data_list[2]),
+        Err(FridaError::DataChecksumMismatch(1))
+    );
+}
@@ -0,0 +1,196 @@
+use winter_crypto::{Digest, RandomCoin, RandomCoinError};
+use winter_fri::{DefaultVerifierChannel, FriOptions, FriProof, FriProver, FriVerifier};
+use winter_math::{fields::f128::BaseElement, FieldElement};
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    core::random::FridaRandom,
+    prover::{builder::FridaProverBuilder, proof::FridaProof, BatchLeafEncoding, ProverCommitment},
+    utils::test_utils::*,
+};
+
+// REFERENCE VERIFIER COIN
+// ================================================================================================
+
+/// Exposes the Frida transcript as a winter-fri `RandomCoin`, so that the reference verifier
+/// draws the same alphas and query positions as the Frida prover channel.
+struct FridaCoin(FridaRandom<BaseElement, Blake3, Blake3>);
+
+impl RandomCoin for FridaCoin {
+    type BaseField = BaseElement;
+    type Hasher = Blake3;
+
+    fn new(_seed: &[Self::BaseField]) -> Self {
+        Self(FridaRandom::new())
+    }
+
+    fn reseed(&mut self, data: <Blake3 as winter_crypto::Hasher>::Digest) {
+        self.0.reseed(&data.as_bytes());
+    }
+
+    fn check_leading_zeros(&self, _value: u64) -> u32 {
+        0
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        self.0
+            .draw()
+            .map(E::from)
+            .map_err(|_| RandomCoinError::FailedToDrawFieldElement(1))
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        _nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.0
+            .draw_query_positions(num_values, domain_size)
+            .map_err(|_| RandomCoinError::FailedToDrawIntegers(num_values, 0, 1))
+    }
+}
+
+// HARNESS
+// ================================================================================================
+
+struct Outcomes {
+    reference: bool,
+    frida: bool,
+}
+
+/// Runs the same opening through the winter-fri reference verifier and the Frida verifier.
+fn verify_both(
+    proof: &FridaProof,
+    roots: &[<Blake3 as winter_crypto::Hasher>::Digest],
+    evaluations: &[BaseElement],
+    positions: &[usize],
+    domain_size: usize,
+    options: &FriOptions,
+) -> Outcomes {
+    // skip the leading byte, which is the only Frida-specific part of a non-batched proof
+    let reference = FriProof::read_from_bytes(&proof.to_bytes()[1..])
+        .ok()
+        .and_then(|fri_proof| {
+            DefaultVerifierChannel::<BaseElement, Blake3>::new(
+                fri_proof,
+                roots.to_vec(),
+                domain_size,
+                options.folding_factor(),
+            )
+            .ok()
+        })
+        .is_some_and(|mut channel| {
+            let mut coin = FridaCoin(FridaRandom::new());
+            let max_poly_degree = domain_size / options.blowup_factor() - 1;
+            FriVerifier::new(&mut channel, &mut coin, options.clone(), max_poly_degree)
+                .and_then(|verifier| verifier.verify(&mut channel, evaluations, positions))
+                .is_ok()
+        });
+
+    let commitment = ProverCommitment::<Blake3> {
+        roots: roots.to_vec(),
+        domain_size,
+        poly_count: 1,
+        data_checksums: Vec::new(),
+        app_ids_root: None,
+        ood_evaluation: Vec::new(),
+        batch_leaf_encoding: BatchLeafEncoding::Flat,
+        num_partitions: 1,
+        dispersal_hint: None,
+        data_roots: None,
+        profile: None,
+    };
+    let frida = TestFridaDasVerifier::from_commitment(&commitment, options.clone())
+        .and_then(|verifier| verifier.verify(proof, evaluations, positions))
+        .is_ok();
+
+    Outcomes { reference, frida }
+}
+
+fn differential_check(
+    trace_length_e: usize,
+    lde_blowup_e: usize,
+    folding_factor_e: usize,
+    max_remainder_degree: usize,
+) {
+    let trace_length = 1 << trace_length_e;
+    let lde_blowup = 1 << lde_blowup_e;
+    let folding_factor = 1 << folding_factor_e;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_degree);
+    let evaluations = test_build_evaluations(trace_length, lde_blowup);
+
+    // both provers must commit to identical layer roots
+    let mut reference_channel = test_build_prover_channel(trace_length, &options);
+    let mut reference_prover = FriProver::new(options.clone());
+    reference_prover.build_layers(&mut reference_channel, evaluations.clone());
+
+    let mut frida_channel = test_build_prover_channel(trace_length, &options);
+    let frida_prover = FridaProverBuilder::new(options.clone())
+        .test_build_layers(&mut frida_channel, evaluations.clone())
+        .unwrap();
+    assert_eq!(reference_channel.commitments, frida_channel.commitments);
+
+    let positions = frida_channel.draw_query_positions();
+    let proof = frida_prover.open(&positions).unwrap();
+    assert_eq!(
+        reference_prover.build_proof(&positions).to_bytes(),
+        proof.to_bytes()[1..]
+    );
+
+    let roots = frida_channel.commitments.clone();
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+    let check = |proof: &FridaProof, evaluations: &[BaseElement]| {
+        verify_both(
+            proof,
+            &roots,
+            evaluations,
+            &positions,
+            domain_size,
+            &options,
+        )
+    };
+
+    // both verifiers accept the honest opening
+    let honest = check(&proof, &queried_evaluations);
+    assert!(honest.reference && honest.frida);
+
+    // and both reject a wrong evaluation
+    let mut wrong_evaluations = queried_evaluations.clone();
+    wrong_evaluations[0] += BaseElement::ONE;
+    let outcome = check(&proof, &wrong_evaluations);
+    assert!(!outcome.reference && !outcome.frida);
+
+    // as well as a tampered query value in any layer
+    for layer in 0..proof.num_layers() {
+        let mut tampered = proof.clone();
+        tampered.test_layers_mut()[layer].test_values_mut()[0] ^= 1;
+        let outcome = check(&tampered, &queried_evaluations);
+        assert!(!outcome.reference && !outcome.frida, "layer {layer}");
+    }
+}
+
+#[test]
+fn differential_folding_2() {
+    differential_check(10, 3, 1, 7);
+}
+
+#[test]
+fn differential_folding_4() {
+    differential_check(10, 2, 2, 15);
+}
+
+#[test]
+fn differential_folding_8() {
+    differential_check(9, 1, 3, 3);
+}
+
+#[test]
+fn differential_folding_16() {
+    differential_check(12, 1, 4, 255);
+}
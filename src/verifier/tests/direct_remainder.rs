@@ -0,0 +1,60 @@
+use crate::{
+    error::FridaError,
+    prover::FridaProver,
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+#[test]
+fn test_direct_remainder() {
+    // blowup 2 with a remainder of up to 8 evaluations: a 20 bytes blob is never folded
+    let options = FriOptions::new(2, 4, 3);
+    let data = rand_vector::<u8>(20);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    assert_eq!(
+        prover_builder.commit_and_prove(&data, 4).err(),
+        Some(FridaError::NotEnoughDataPoints())
+    );
+
+    let prover_builder = prover_builder.with_direct_remainder(true);
+    let (commitment, prover) = prover_builder.commit_and_prove(&data, 4).unwrap();
+    assert_eq!(commitment.roots.len(), 1);
+    let domain_size = commitment.domain_size;
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
+
+    let positions = vec![0, 3, domain_size - 1];
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    let verifier = TestFridaDasVerifier::from_commitment(&prover.commitment(), options).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    assert_eq!(restored.open(&positions).unwrap(), proof);
+
+    let mut tampered = evaluations;
+    tampered[1] += BaseElement::ONE;
+    assert!(verifier.verify(&proof, &tampered, &positions).is_err());
+}
+
+#[test]
+fn test_direct_remainder_leaves_larger_data_folded() {
+    let options = FriOptions::new(2, 4, 3);
+    let prover_builder = TestFridaProverBuilder::new(options).with_direct_remainder(true);
+
+    let (commitment, _) = prover_builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    assert!(commitment.roots.len() > 1);
+
+    // batches are combined in their first layer, so they still need one
+    let data_list = vec![rand_vector::<u8>(20), rand_vector::<u8>(20)];
+    assert_eq!(
+        prover_builder.commit_and_prove_batch(&data_list, 4).err(),
+        Some(FridaError::NotEnoughDataPoints())
+    );
+}
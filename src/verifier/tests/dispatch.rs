@@ -0,0 +1,177 @@
+use winter_crypto::{
+    hashers::{Blake3_256, Sha3_256},
+    ElementHasher,
+};
+use winter_fri::FriOptions;
+use winter_math::{
+    fields::{f128, f64},
+    FieldElement,
+};
+use winter_rand_utils::rand_vector;
+use winter_utils::Serializable;
+
+#[cfg(feature = "keccak")]
+use crate::core::keccak::Keccak256;
+use crate::{
+    core::data::EncodingOptions,
+    error::FridaError,
+    prover::builder::FridaProverBuilder,
+    utils::test_utils::test_options,
+    verifier::dispatch::{verify_bytes, FieldId, HasherId},
+};
+#[cfg(feature = "f31")]
+use crate::{core::f31, prover::small_field::F31_ENCODING};
+
+/// Serialized commitment, proof, positions and evaluations of an opening in field `E` with
+/// hasher `H`, of data packed with `encoding`.
+fn serialized_opening<E, H>(
+    options: &FriOptions,
+    encoding: EncodingOptions,
+) -> (Vec<u8>, Vec<u8>, Vec<usize>, Vec<u8>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let (commitment, prover) = FridaProverBuilder::<E, H>::new(options.clone())
+        .with_encoding(encoding)
+        .commit_and_prove(&rand_vector::<u8>(200), 4)
+        .unwrap();
+    let positions = vec![1, 4, 9];
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    (
+        commitment.to_bytes(),
+        proof.to_bytes(),
+        positions,
+        E::elements_as_bytes(&evaluations).to_vec(),
+    )
+}
+
+fn check_dispatch<E, H>(field_id: FieldId, hasher_id: HasherId, encoding: EncodingOptions)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let options = test_options();
+    let (commitment, proof, positions, evaluations) =
+        serialized_opening::<E, H>(&options, encoding);
+    let verify = |field_id, hasher_id, evaluations: &[u8]| {
+        verify_bytes(
+            field_id,
+            hasher_id,
+            &options,
+            &commitment,
+            &proof,
+            &positions,
+            evaluations,
+        )
+    };
+    let evaluations = evaluations.as_slice();
+
+    verify(field_id, hasher_id, evaluations)
+        .unwrap_or_else(|e| panic!("{field_id:?} with {hasher_id:?} rejected: {e}"));
+    assert!(verify(field_id, hasher_id, &evaluations[1..]).is_err());
+
+    // other parameter sets do not verify the opening
+    for other_hasher in [
+        HasherId::Blake3_256,
+        HasherId::Sha3_256,
+        HasherId::Keccak256,
+    ] {
+        if other_hasher != hasher_id {
+            assert!(verify(field_id, other_hasher, evaluations).is_err());
+        }
+    }
+    let other_field = match field_id {
+        FieldId::F64 => FieldId::F128,
+        FieldId::F128 => FieldId::F64,
+        FieldId::F31x4 => FieldId::F128,
+    };
+    assert!(verify(other_field, hasher_id, evaluations).is_err());
+}
+
+#[test]
+fn every_parameter_set_is_dispatched() {
+    type F64 = f64::BaseElement;
+    type F128 = f128::BaseElement;
+    let default = EncodingOptions::default();
+    check_dispatch::<F64, Blake3_256<F64>>(FieldId::F64, HasherId::Blake3_256, default);
+    check_dispatch::<F64, Sha3_256<F64>>(FieldId::F64, HasherId::Sha3_256, default);
+    check_dispatch::<F128, Blake3_256<F128>>(FieldId::F128, HasherId::Blake3_256, default);
+    check_dispatch::<F128, Sha3_256<F128>>(FieldId::F128, HasherId::Sha3_256, default);
+}
+
+#[cfg(feature = "keccak")]
+#[test]
+fn keccak_parameter_sets_are_dispatched() {
+    type F64 = f64::BaseElement;
+    type F128 = f128::BaseElement;
+    let default = EncodingOptions::default();
+    check_dispatch::<F64, Keccak256<F64>>(FieldId::F64, HasherId::Keccak256, default);
+    check_dispatch::<F128, Keccak256<F128>>(FieldId::F128, HasherId::Keccak256, default);
+}
+
+#[cfg(not(feature = "keccak"))]
+#[test]
+fn keccak_is_unsupported_without_feature() {
+    type F128 = f128::BaseElement;
+    let options = test_options();
+    let (commitment, proof, positions, evaluations) =
+        serialized_opening::<F128, Blake3_256<F128>>(&options, EncodingOptions::default());
+    assert_eq!(
+        verify_bytes(
+            FieldId::F128,
+            HasherId::Keccak256,
+            &options,
+            &commitment,
+            &proof,
+            &positions,
+            &evaluations,
+        ),
+        Err(FridaError::UnsupportedHasherId(2))
+    );
+}
+
+#[cfg(feature = "f31")]
+#[test]
+fn small_field_parameter_sets_are_dispatched() {
+    type F31 = f31::BaseElement;
+    type F31x4 = f31::QuarticElement;
+    check_dispatch::<F31x4, Blake3_256<F31>>(FieldId::F31x4, HasherId::Blake3_256, F31_ENCODING);
+    check_dispatch::<F31x4, Sha3_256<F31>>(FieldId::F31x4, HasherId::Sha3_256, F31_ENCODING);
+    #[cfg(feature = "keccak")]
+    check_dispatch::<F31x4, Keccak256<F31>>(FieldId::F31x4, HasherId::Keccak256, F31_ENCODING);
+}
+
+#[cfg(not(feature = "f31"))]
+#[test]
+fn small_field_is_unsupported_without_feature() {
+    type F128 = f128::BaseElement;
+    let options = test_options();
+    let (commitment, proof, positions, evaluations) =
+        serialized_opening::<F128, Blake3_256<F128>>(&options, EncodingOptions::default());
+    assert_eq!(
+        verify_bytes(
+            FieldId::F31x4,
+            HasherId::Blake3_256,
+            &options,
+            &commitment,
+            &proof,
+            &positions,
+            &evaluations,
+        ),
+        Err(FridaError::UnsupportedFieldId(2))
+    );
+}
+
+#[test]
+fn parameter_ids_are_parsed() {
+    assert_eq!(FieldId::try_from(1), Ok(FieldId::F128));
+    assert_eq!(FieldId::try_from(2), Ok(FieldId::F31x4));
+    assert_eq!(FieldId::try_from(3), Err(FridaError::UnsupportedFieldId(3)));
+    assert_eq!(HasherId::try_from(2), Ok(HasherId::Keccak256));
+    assert_eq!(
+        HasherId::try_from(3),
+        Err(FridaError::UnsupportedHasherId(3))
+    );
+}
@@ -1,6 +1,6 @@
 use crate::{
     error::FridaError,
-    prover::{builder::FridaProverBuilder, proof::FridaProof, Commitment},
+    prover::{builder::FridaProverBuilder, proof::FridaProof, BatchLeafEncoding, Commitment},
     utils::test_utils::*,
     winterfell::{f128, rand_array, FriOptions, Hasher},
 };
@@ -24,6 +24,11 @@ fn test_e2e_verify() {
                 domain_size,
                 num_queries: 32,
                 poly_count: 1,
+                ood_evaluation: Vec::new(),
+                batch_leaf_encoding: BatchLeafEncoding::Flat,
+                num_partitions: 1,
+                data_roots: None,
+                profile: None,
             },
             options.clone(),
         )?;
@@ -51,16 +56,18 @@ fn test_e2e_verify() {
 
         // instantiate the prover and generate the proof
         let prover_builder = FridaProverBuilder::new(options.clone());
-        let prover = prover_builder.test_build_layers(&mut channel, evaluations.clone());
+        let prover = prover_builder
+            .test_build_layers(&mut channel, evaluations.clone())
+            .unwrap();
 
         let positions = channel.draw_query_positions();
-        let proof = prover.open(&positions);
+        let proof = prover.open(&positions).unwrap();
 
         let positions_for_opening = rand_array::<u64, 5>()
             .iter()
             .map(|v| usize::min(*v as usize, lde_blowup * trace_length - 1))
             .collect::<Vec<_>>();
-        let opening_proof = prover.open(&positions_for_opening);
+        let opening_proof = prover.open(&positions_for_opening).unwrap();
 
         // make sure the proof can be verified
         let commitments = channel.commitments.clone();
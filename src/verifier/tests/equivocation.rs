@@ -0,0 +1,125 @@
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{FridaProver, ProverCommitment},
+    utils::test_utils::*,
+    verifier::{
+        cross::{cross_positions, shared_domain_size},
+        equivocation::{detect_equivocation, EquivocationOpening, EquivocationProof},
+    },
+};
+
+type TestEquivocationProof = EquivocationProof<BaseElement, Blake3>;
+
+const SHARED_POSITIONS: [usize; 4] = [0, 3, 5, 14];
+
+/// Opens `prover` at `SHARED_POSITIONS` of its domain shared with a commitment over
+/// `other_domain_size` points.
+fn opening(
+    commitment: ProverCommitment<Blake3>,
+    prover: &FridaProver<BaseElement, Blake3>,
+    other_domain_size: usize,
+) -> EquivocationOpening<BaseElement, Blake3> {
+    let shared_size = shared_domain_size(commitment.domain_size, other_domain_size);
+    let positions =
+        cross_positions(&SHARED_POSITIONS, shared_size, commitment.domain_size).unwrap();
+    EquivocationOpening {
+        evaluations: prover.get_evaluations(&positions).unwrap(),
+        proof: prover.open(&positions).unwrap(),
+        commitment,
+    }
+}
+
+#[test]
+fn conflicting_commitments_are_proven_equivocating() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let (first, first_prover, _) = builder.commitment(&rand_vector::<u8>(300), 4).unwrap();
+    let (second, second_prover, _) = builder.commitment(&rand_vector::<u8>(300), 4).unwrap();
+    let (first_size, second_size) = (first.domain_size, second.domain_size);
+
+    let proof = detect_equivocation::<_, Blake3, _>(
+        7,
+        2,
+        &SHARED_POSITIONS,
+        opening(first, &first_prover, second_size),
+        opening(second, &second_prover, first_size),
+        &options,
+    )
+    .unwrap()
+    .expect("random blobs disagree at some shared position");
+    assert_eq!((proof.block, proof.blob_index), (7, 2));
+
+    // the artifact is checked on its own by whoever acts upon it
+    let proof = TestEquivocationProof::read_from_bytes(&proof.to_bytes()).unwrap();
+    let position = proof.verify::<Blake3>(&options).unwrap();
+    assert!(SHARED_POSITIONS.contains(&position));
+}
+
+#[test]
+fn consistent_commitments_are_not_equivocating() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(100), rand_vector::<u8>(300)];
+
+    // the blob committed alone and as blob 1 of a batch over a larger domain
+    let (single, single_prover, _) = builder.commitment(&data_list[1], 4).unwrap();
+    let (batch, batch_prover, _) = builder.commitment_batch(&data_list, 4).unwrap();
+    let (single_size, batch_size) = (single.domain_size, batch.domain_size);
+
+    let result = detect_equivocation::<_, Blake3, _>(
+        7,
+        1,
+        &SHARED_POSITIONS,
+        opening(single, &single_prover, batch_size),
+        opening(batch, &batch_prover, single_size),
+        &options,
+    );
+    assert_eq!(result, Ok(None));
+}
+
+#[test]
+fn invalid_openings_are_not_evidence() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(300);
+    let (first, first_prover, _) = builder.commitment(&data, 4).unwrap();
+    let (second, second_prover, _) = builder.commitment(&data, 4).unwrap();
+    let size = first.domain_size;
+
+    // a forged evaluation makes the evaluations differ, but its opening does not verify
+    let mut forged = opening(second, &second_prover, size);
+    forged.evaluations[0] += BaseElement::new(1);
+    let result = detect_equivocation::<_, Blake3, _>(
+        7,
+        0,
+        &SHARED_POSITIONS,
+        opening(first, &first_prover, size),
+        forged,
+        &options,
+    );
+    assert!(matches!(result, Err(FridaError::FailToVerify(_))));
+}
+
+#[test]
+fn blobs_missing_from_batches_are_rejected() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(100), rand_vector::<u8>(300)];
+    let (first, first_prover, _) = builder.commitment_batch(&data_list, 4).unwrap();
+    let (second, second_prover, _) = builder.commitment_batch(&data_list, 4).unwrap();
+    let size = first.domain_size;
+
+    let result = detect_equivocation::<_, Blake3, _>(
+        7,
+        2,
+        &SHARED_POSITIONS,
+        opening(first, &first_prover, size),
+        opening(second, &second_prover, size),
+        &options,
+    );
+    assert_eq!(result, Err(FridaError::ProofPolyCountMismatch));
+}
@@ -0,0 +1,80 @@
+use crate::{
+    error::FridaError,
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, FieldElement},
+};
+use winter_fri::VerifierError;
+use winter_rand_utils::rand_vector;
+
+const POSITIONS: [usize; 6] = [1, 5, 9, 20, 33, 60];
+
+#[test]
+fn test_verify_with_erasures() {
+    let options = test_options();
+    let builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = [rand_vector::<u8>(300), rand_vector(200), rand_vector(250)];
+
+    for data_list in [&data_list[..1], &data_list[..]] {
+        let (commitment, prover, _) = if data_list.len() == 1 {
+            builder.commitment(&data_list[0], 4)
+        } else {
+            builder.commitment_batch(data_list, 4)
+        }
+        .unwrap();
+        let verifier = TestFridaDasVerifier::from_commitment(&commitment, options.clone()).unwrap();
+        let proof = prover.open(&POSITIONS).unwrap();
+
+        let missing = [20, 5];
+        let received = POSITIONS
+            .iter()
+            .copied()
+            .filter(|p| !missing.contains(p))
+            .collect::<Vec<_>>();
+        let evaluations = prover.get_evaluations(&received).unwrap();
+        assert_eq!(
+            verifier.verify_with_erasures(&proof, &evaluations, &POSITIONS, &missing),
+            Ok(vec![5, 20])
+        );
+
+        // without erasures, this is a regular verification
+        let all_evaluations = prover.get_evaluations(&POSITIONS).unwrap();
+        assert_eq!(
+            verifier.verify_with_erasures(&proof, &all_evaluations, &POSITIONS, &[]),
+            Ok(vec![])
+        );
+        assert_eq!(
+            verifier.verify_with_erasures(&proof, &[], &POSITIONS, &POSITIONS),
+            Ok(POSITIONS.to_vec())
+        );
+
+        // received evaluations are still checked
+        let mut tampered = evaluations.clone();
+        tampered[0] += BaseElement::ONE;
+        assert!(matches!(
+            verifier.verify_with_erasures(&proof, &tampered, &POSITIONS, &missing),
+            Err(FridaError::FailToVerify(_))
+        ));
+    }
+}
+
+#[test]
+fn test_verify_with_erasures_bad_inputs() {
+    let options = test_options();
+    let (commitment, prover, _) = TestFridaProverBuilder::new(options.clone())
+        .commitment(&rand_vector::<u8>(300), 4)
+        .unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let proof = prover.open(&POSITIONS).unwrap();
+    let evaluations = prover.get_evaluations(&POSITIONS[1..]).unwrap();
+
+    assert_eq!(
+        verifier.verify_with_erasures(&proof, &evaluations, &POSITIONS, &[2]),
+        Err(FridaError::UnknownErasure(2))
+    );
+    assert_eq!(
+        verifier.verify_with_erasures(&proof, &evaluations, &POSITIONS, &[1, 5]),
+        Err(FridaError::FailToVerify(
+            VerifierError::NumPositionEvaluationMismatch(4, 5)
+        ))
+    );
+}
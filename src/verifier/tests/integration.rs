@@ -24,7 +24,7 @@ fn test_frida_das_verify_short() {
 
         // query for a position
         let open_position = [1];
-        let proof = prover.open(&open_position);
+        let proof = prover.open(&open_position).unwrap();
 
         let domain_size = 8;
         let evaluations: Vec<BaseElement> =
@@ -60,7 +60,7 @@ fn test_frida_das_verify() {
 
     // query for a position
     let open_position = [1];
-    let proof = prover.open(&open_position);
+    let proof = prover.open(&open_position).unwrap();
 
     let domain_size = (encoded_element_count - 1).next_power_of_two() * options.blowup_factor();
     let evaluations: Vec<BaseElement> =
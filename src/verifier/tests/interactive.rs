@@ -0,0 +1,125 @@
+use crate::{
+    error::FridaError,
+    prover::interactive::{InMemoryTransport, InteractiveProver, Message, Party},
+    utils::test_utils::*,
+    verifier::interactive::{run_interactive, InteractiveVerifier},
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+use winter_rand_utils::rand_vector;
+
+type TestInteractiveProver = InteractiveProver<BaseElement, Blake3>;
+type TestInteractiveVerifier = InteractiveVerifier<BaseElement, Blake3>;
+
+fn setup(options: &FriOptions) -> (TestInteractiveProver, TestInteractiveVerifier) {
+    let prover = TestInteractiveProver::new(options.clone(), &rand_vector::<u8>(10_000)).unwrap();
+    let verifier = TestInteractiveVerifier::new(options.clone(), prover.domain_size(), 16).unwrap();
+    (prover, verifier)
+}
+
+#[test]
+fn test_interactive_run() {
+    let options = FriOptions::new(2, 4, 3);
+    let (mut prover, mut verifier) = setup(&options);
+    let mut transport = InMemoryTransport::new();
+    run_interactive(&mut prover, &mut verifier, &mut transport).unwrap();
+
+    // every layer root is answered by a challenge, then come the remainder, queries and answer
+    let num_layers = prover.num_layers();
+    let transcript = transport.transcript();
+    assert_eq!(transcript.len(), 2 * num_layers + 3);
+    for (i, (party, message)) in transcript[..2 * num_layers].iter().enumerate() {
+        if i % 2 == 0 {
+            assert_eq!(*party, Party::Prover);
+            assert!(matches!(message, Message::LayerRoot(_)));
+        } else {
+            assert_eq!(*party, Party::Verifier);
+            assert!(matches!(message, Message::Challenge(_)));
+        }
+    }
+    assert!(matches!(
+        transcript[2 * num_layers].1,
+        Message::Remainder(_)
+    ));
+    assert!(matches!(
+        transcript[2 * num_layers + 1].1,
+        Message::Queries(_)
+    ));
+    assert!(matches!(
+        transcript[2 * num_layers + 2].1,
+        Message::Answer { .. }
+    ));
+
+    assert_eq!(prover.challenges(), verifier.challenges());
+    assert_eq!(
+        prover.prover().unwrap().commitment().roots,
+        verifier.roots()
+    );
+}
+
+#[test]
+fn test_interactive_prover_ignoring_challenges_is_rejected() {
+    let options = FriOptions::new(2, 4, 3);
+    let (mut prover, mut verifier) = setup(&options);
+
+    // the prover folds every layer by another challenge than the one it was sent
+    let mut message = prover.respond(None).unwrap();
+    let reply = loop {
+        let reply = verifier.respond(message).unwrap().unwrap();
+        message = match reply {
+            Message::Challenge(alpha) => prover
+                .respond(Some(Message::Challenge(alpha + BaseElement::ONE)))
+                .unwrap(),
+            reply => break reply,
+        };
+    };
+    let answer = prover.respond(Some(reply)).unwrap();
+    assert!(matches!(
+        verifier.respond(answer),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
+
+#[test]
+fn test_interactive_wrong_evaluations_are_rejected() {
+    let options = FriOptions::new(4, 2, 1);
+    let (mut prover, mut verifier) = setup(&options);
+
+    let mut message = prover.respond(None).unwrap();
+    let answer = loop {
+        let reply = verifier.respond(message).unwrap().unwrap();
+        message = prover.respond(Some(reply)).unwrap();
+        if let Message::Answer { .. } = message {
+            break message;
+        }
+    };
+    let Message::Answer {
+        mut evaluations,
+        proof,
+    } = answer
+    else {
+        unreachable!();
+    };
+    evaluations[0] += BaseElement::ONE;
+    assert!(verifier
+        .respond(Message::Answer { evaluations, proof })
+        .is_err());
+}
+
+#[test]
+fn test_interactive_unexpected_messages() {
+    let options = FriOptions::new(2, 4, 3);
+    let (mut prover, mut verifier) = setup(&options);
+
+    assert_eq!(
+        prover.respond(Some(Message::Queries(vec![0]))),
+        Err(FridaError::UnexpectedMessage)
+    );
+    assert_eq!(
+        verifier.respond(Message::Queries(vec![0])),
+        Err(FridaError::UnexpectedMessage)
+    );
+
+    // a second root cannot be sent before the first one is answered
+    prover.respond(None).unwrap();
+    assert_eq!(prover.respond(None), Err(FridaError::UnexpectedMessage));
+}
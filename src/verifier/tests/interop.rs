@@ -0,0 +1,180 @@
+use winter_fri::FriOptions;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    prover::{proof::FridaProof, Commitment, FridaProver, LayerStorage, ProverCommitment},
+    utils::test_utils::*,
+};
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+// HARNESS
+// ================================================================================================
+
+/// A way of configuring the builder, along with the folding schedule verifiers must be created
+/// with to match it.
+struct Case {
+    name: &'static str,
+    options: FriOptions,
+    schedule: Vec<usize>,
+    builder: fn(FriOptions, &[usize]) -> TestFridaProverBuilder,
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            name: "default",
+            options: test_options(),
+            schedule: vec![],
+            builder: |options, _| TestFridaProverBuilder::new(options),
+        },
+        Case {
+            name: "folding by 4",
+            options: FriOptions::new(4, 4, 3),
+            schedule: vec![],
+            builder: |options, _| TestFridaProverBuilder::new(options),
+        },
+        Case {
+            name: "lazy layers",
+            options: test_options(),
+            schedule: vec![],
+            builder: |options, _| {
+                TestFridaProverBuilder::new(options).with_layer_storage(LayerStorage::Lazy)
+            },
+        },
+        Case {
+            name: "ood check",
+            options: FriOptions::new(2, 4, 3),
+            schedule: vec![],
+            builder: |options, _| TestFridaProverBuilder::new(options).with_ood_check(true),
+        },
+        Case {
+            name: "folding schedule",
+            options: test_options(),
+            schedule: vec![8, 2],
+            builder: |options, schedule| {
+                TestFridaProverBuilder::new(options).with_folding_schedule(schedule.to_vec())
+            },
+        },
+    ]
+}
+
+/// Checks that a proof of `prover` opening `positions` verifies against verifiers built from both
+/// commitment types, before and after a serialization round trip of the proof.
+fn check_openings(
+    case: &Case,
+    prover: &TestFridaProver,
+    commitment: Commitment<Blake3>,
+    positions: &[usize],
+) {
+    let (verifier, coin) =
+        TestFridaDasVerifier::new_with_schedule(commitment, case.options.clone(), &case.schedule)
+            .unwrap_or_else(|e| panic!("{}: commitment rejected: {e}", case.name));
+    let prover_verifier = TestFridaDasVerifier::from_commitment_with_schedule(
+        &prover.commitment(),
+        case.options.clone(),
+        &case.schedule,
+    )
+    .unwrap();
+    assert_eq!(
+        verifier.layer_alphas(),
+        prover_verifier.layer_alphas(),
+        "{}: commitment types derive different challenges",
+        case.name
+    );
+
+    // positions chosen by the prover side
+    let proof = prover.open(positions).unwrap();
+    let evaluations = prover.get_evaluations(positions).unwrap();
+    let proof = FridaProof::read_from_bytes(&proof.to_bytes()).unwrap();
+    for verifier in [&verifier, &prover_verifier] {
+        verifier
+            .verify(&proof, &evaluations, positions)
+            .unwrap_or_else(|e| panic!("{}: opening rejected: {e}", case.name));
+    }
+
+    // and positions drawn by the verifier side
+    let mut drawn = coin
+        .draw_query_positions(4, verifier.domain_size())
+        .unwrap();
+    drawn.sort_unstable();
+    drawn.dedup();
+    let proof = prover.open(&drawn).unwrap();
+    let evaluations = prover.get_evaluations(&drawn).unwrap();
+    prover_verifier
+        .verify(&proof, &evaluations, &drawn)
+        .unwrap_or_else(|e| panic!("{}: drawn opening rejected: {e}", case.name));
+}
+
+/// Checks that both commitment flows of the builder agree on the roots and metadata, and that
+/// their serialized forms survive a round trip.
+fn check_commitments(
+    case: &Case,
+    commitment: &Commitment<Blake3>,
+    prover_commitment: &ProverCommitment<Blake3>,
+    prover: &TestFridaProver,
+) {
+    assert_eq!(commitment.roots, prover_commitment.roots, "{}", case.name);
+    assert_eq!(commitment.domain_size, prover_commitment.domain_size);
+    assert_eq!(commitment.poly_count, prover_commitment.poly_count);
+    assert_eq!(commitment.ood_evaluation, prover_commitment.ood_evaluation);
+    assert_eq!(prover.commitment(), *prover_commitment, "{}", case.name);
+
+    let bytes = commitment.to_bytes();
+    assert_eq!(
+        Commitment::<Blake3>::read_from_bytes(&bytes).unwrap(),
+        *commitment
+    );
+    let bytes = prover_commitment.to_bytes();
+    assert_eq!(
+        ProverCommitment::<Blake3>::read_from_bytes(&bytes).unwrap(),
+        *prover_commitment
+    );
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn single_blob_commitments_interoperate() {
+    let data = rand_vector::<u8>(500);
+    for case in cases() {
+        let builder = (case.builder)(case.options.clone(), &case.schedule);
+        let (commitment, prover) = builder.commit_and_prove(&data, 4).unwrap();
+        let (prover_commitment, _, positions) = builder.commitment(&data, 4).unwrap();
+        check_commitments(&case, &commitment, &prover_commitment, &prover);
+
+        // a commitment read back from bytes feeds the verifier just as well
+        let commitment = Commitment::<Blake3>::read_from_bytes(&commitment.to_bytes()).unwrap();
+        check_openings(&case, &prover, commitment, &positions);
+    }
+}
+
+#[test]
+fn batched_commitments_interoperate() {
+    let data_list = [300, 500, 120]
+        .iter()
+        .map(|&len| rand_vector::<u8>(len))
+        .collect::<Vec<_>>();
+    for case in cases() {
+        let builder = (case.builder)(case.options.clone(), &case.schedule);
+        let (commitment, prover) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+        let (prover_commitment, _, positions) = builder.commitment_batch(&data_list, 4).unwrap();
+        check_commitments(&case, &commitment, &prover_commitment, &prover);
+        check_openings(&case, &prover, commitment, &positions);
+    }
+}
+
+#[test]
+fn restored_provers_interoperate() {
+    let data = rand_vector::<u8>(500);
+    for case in cases() {
+        let builder = (case.builder)(case.options.clone(), &case.schedule);
+        let (commitment, prover) = builder.commit_and_prove(&data, 4).unwrap();
+        let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+        assert_eq!(restored.commitment(), prover.commitment(), "{}", case.name);
+        check_openings(&case, &restored, commitment, &[0, 7, 21]);
+    }
+}
@@ -6,3 +6,102 @@ mod e2e;
 
 #[cfg(test)]
 mod das_tests;
+
+#[cfg(test)]
+mod adversarial;
+
+#[cfg(test)]
+mod differential;
+
+#[cfg(test)]
+mod interop;
+
+#[cfg(test)]
+mod receipts;
+
+#[cfg(test)]
+mod cross;
+
+#[cfg(test)]
+mod byte_ranges;
+
+#[cfg(test)]
+mod data_checksums;
+
+#[cfg(test)]
+mod cells;
+
+#[cfg(test)]
+mod reconstruction;
+
+#[cfg(test)]
+mod app_openings;
+
+#[cfg(test)]
+mod schedules;
+
+#[cfg(test)]
+mod stir;
+
+#[cfg(test)]
+mod ood;
+
+#[cfg(test)]
+mod interactive;
+
+#[cfg(test)]
+mod multi_field;
+
+#[cfg(test)]
+mod erasures;
+
+#[cfg(test)]
+mod attestations;
+
+#[cfg(test)]
+mod traits;
+
+#[cfg(test)]
+mod blob_openings;
+
+#[cfg(test)]
+mod oblivious;
+
+#[cfg(test)]
+mod reports;
+
+#[cfg(test)]
+mod direct_remainder;
+
+#[cfg(test)]
+mod streaming;
+
+#[cfg(test)]
+mod thresholds;
+
+#[cfg(test)]
+mod equivocation;
+
+#[cfg(test)]
+mod dispatch;
+
+#[cfg(test)]
+mod recursive;
+
+#[cfg(test)]
+mod values;
+
+#[cfg(test)]
+mod batch_combination;
+
+#[cfg(test)]
+mod multiproof;
+
+#[cfg(test)]
+mod remainder_evaluation;
+
+#[cfg(test)]
+mod adaptive_sampling;
+
+#[cfg(test)]
+mod position_indexes;
@@ -0,0 +1,113 @@
+use winter_crypto::{hashers::Blake3_256, ElementHasher};
+use winter_fri::FriOptions;
+use winter_math::{
+    fields::{f128, f64},
+    FieldElement,
+};
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    prover::{
+        builder::FridaProverBuilder,
+        multi_field::{commit_f64_f128, commit_multi_field},
+        Commitment, FridaProver,
+    },
+    utils::test_utils::test_options,
+    verifier::{cross::verify_same_data, das::FridaDasVerifier},
+};
+
+type F64Builder = FridaProverBuilder<f64::BaseElement, Blake3_256<f64::BaseElement>>;
+type F128Builder = FridaProverBuilder<f128::BaseElement, Blake3_256<f128::BaseElement>>;
+type F64Verifier =
+    FridaDasVerifier<f64::BaseElement, Blake3_256<f64::BaseElement>, Blake3_256<f64::BaseElement>>;
+type F128Verifier = FridaDasVerifier<
+    f128::BaseElement,
+    Blake3_256<f128::BaseElement>,
+    Blake3_256<f128::BaseElement>,
+>;
+
+/// Verifies the commitment, then opens and verifies enough positions to decode the blob.
+fn verified_samples<E, H>(
+    commitment: Commitment<H>,
+    prover: &FridaProver<E, H>,
+    options: &FriOptions,
+) -> (FridaDasVerifier<E, H, H>, Vec<(usize, E)>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let domain_size = commitment.domain_size;
+    let (verifier, _) = FridaDasVerifier::new(commitment, options.clone()).unwrap();
+    let positions = (0..domain_size / options.blowup_factor()).collect::<Vec<_>>();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier
+        .verify(&prover.open(&positions).unwrap(), &evaluations, &positions)
+        .unwrap();
+    (verifier, positions.into_iter().zip(evaluations).collect())
+}
+
+#[test]
+fn f64_and_f128_commitments_decode_to_the_same_data() {
+    let options = test_options();
+    let data = rand_vector::<u8>(300);
+    let committed = commit_f64_f128(options.clone(), &data, 8).unwrap();
+    // the 64-bit field packs fewer bytes per element, so it needs a larger domain
+    assert!(committed.commitment_a.domain_size > committed.commitment_b.domain_size);
+
+    let (verifier_a, samples_a): (F64Verifier, _) =
+        verified_samples(committed.commitment_a, &committed.prover_a, &options);
+    let (verifier_b, samples_b): (F128Verifier, _) =
+        verified_samples(committed.commitment_b, &committed.prover_b, &options);
+
+    assert_eq!(
+        verify_same_data(&verifier_a, &samples_a, &verifier_b, &samples_b),
+        Ok(data)
+    );
+}
+
+#[test]
+fn different_data_is_detected() {
+    let options = test_options();
+    let data = rand_vector::<u8>(200);
+    let mut other = data.clone();
+    other[42] ^= 1;
+
+    let committed = commit_f64_f128(options.clone(), &data, 8).unwrap();
+    let builder = F128Builder::new(options.clone());
+    let (commitment, prover) = builder.commit_and_prove(&other, 8).unwrap();
+
+    let (verifier_a, samples_a): (F64Verifier, _) =
+        verified_samples(committed.commitment_a, &committed.prover_a, &options);
+    let (verifier_b, samples_b): (F128Verifier, _) =
+        verified_samples(commitment, &prover, &options);
+
+    assert_eq!(
+        verify_same_data(&verifier_a, &samples_a, &verifier_b, &samples_b),
+        Err(FridaError::FieldDataMismatch)
+    );
+}
+
+#[test]
+fn batches_are_rejected() {
+    let options = test_options();
+    let data_list = vec![rand_vector::<u8>(100), rand_vector::<u8>(100)];
+    let builder = F128Builder::new(options.clone());
+    let (commitment, _) = builder.commit_and_prove_batch(&data_list, 8).unwrap();
+    let (batch_verifier, _) = F128Verifier::new(commitment, options.clone()).unwrap();
+
+    let committed = commit_multi_field(
+        &F64Builder::new(options.clone()),
+        &builder,
+        &data_list[0],
+        8,
+    )
+    .unwrap();
+    let (verifier_a, samples_a): (F64Verifier, _) =
+        verified_samples(committed.commitment_a, &committed.prover_a, &options);
+
+    assert_eq!(
+        verify_same_data(&verifier_a, &samples_a, &batch_verifier, &[]),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
@@ -0,0 +1,166 @@
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{
+    error::FridaError,
+    prover::{multiproof::MultiProof, FridaProver, ProverCommitment},
+    utils::test_utils::*,
+    verifier::multiproof::verify_multiproof,
+    winterfell::{f128::BaseElement, FriOptions},
+};
+
+type TestProver = FridaProver<BaseElement, Blake3>;
+
+fn verify(
+    commitments: &[ProverCommitment<Blake3>],
+    proof: &MultiProof<Blake3>,
+) -> Result<(), FridaError> {
+    verify_multiproof::<BaseElement, Blake3, Blake3>(commitments, proof, &test_options(), 8)
+}
+
+/// Commits to three single blobs and a batch of two, all over the same domain.
+fn block() -> (Vec<ProverCommitment<Blake3>>, Vec<TestProver>) {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let mut commitments = Vec::new();
+    let mut provers = Vec::new();
+    for _ in 0..3 {
+        let (commitment, prover, _) = builder.commitment(&rand_vector::<u8>(500), 8).unwrap();
+        commitments.push(commitment);
+        provers.push(prover);
+    }
+    let data_list = (0..2).map(|_| rand_vector::<u8>(500)).collect::<Vec<_>>();
+    let (commitment, prover, _) = builder.commitment_batch(&data_list, 8).unwrap();
+    commitments.push(commitment);
+    provers.push(prover);
+    (commitments, provers)
+}
+
+#[test]
+fn test_multiproof() {
+    let (commitments, provers) = block();
+    let builder = TestFridaProverBuilder::new(test_options());
+    let proof = builder
+        .prove_multi(&provers.iter().collect::<Vec<_>>(), 16)
+        .unwrap();
+    assert_eq!(proof.openings.len(), commitments.len());
+    assert_eq!(proof.openings[0].num_partitions, 2);
+    verify(&commitments, &proof).unwrap();
+
+    let bytes = proof.to_bytes();
+    let proof = MultiProof::<Blake3>::read_from_bytes(&bytes).unwrap();
+    verify(&commitments, &proof).unwrap();
+
+    // a single commitment is proven the same way
+    let proof = builder.prove_multi(&[&provers[3]], 16).unwrap();
+    verify(&commitments[3..], &proof).unwrap();
+}
+
+#[test]
+fn test_multiproof_requires_the_verifier_query_count() {
+    let (commitments, provers) = block();
+    let builder = TestFridaProverBuilder::new(test_options());
+    let provers = provers.iter().collect::<Vec<_>>();
+
+    // the prover picks the queries of its proof, so that the verifier sets a floor on them
+    let proof = builder.prove_multi(&provers, 4).unwrap();
+    assert_eq!(
+        verify(&commitments, &proof),
+        Err(FridaError::BadNumQueries(4))
+    );
+    assert_eq!(
+        verify_multiproof::<BaseElement, Blake3, Blake3>(&commitments, &proof, &test_options(), 4),
+        Ok(())
+    );
+    assert_eq!(
+        verify_multiproof::<BaseElement, Blake3, Blake3>(&commitments, &proof, &test_options(), 0),
+        Err(FridaError::BadNumQueries(4))
+    );
+}
+
+#[test]
+fn test_multiproof_checks_folding_factors_first() {
+    let (_, provers) = block();
+    let builder = TestFridaProverBuilder::new(FriOptions::new(2, 4, 1));
+    assert_eq!(
+        builder
+            .prove_multi(&provers.iter().collect::<Vec<_>>(), 16)
+            .err(),
+        Some(FridaError::MultiProofMismatch)
+    );
+}
+
+#[test]
+fn test_multiproof_rejects_other_commitments() {
+    let (commitments, provers) = block();
+    let builder = TestFridaProverBuilder::new(test_options());
+    let proof = builder
+        .prove_multi(&provers.iter().collect::<Vec<_>>(), 16)
+        .unwrap();
+
+    // commitments are combined in order
+    let mut swapped = commitments.clone();
+    swapped.swap(0, 1);
+    assert!(matches!(
+        verify(&swapped, &proof),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    // openings must match the first root of their commitment
+    let mut tampered = proof;
+    tampered.openings.swap(0, 1);
+    assert!(matches!(
+        verify(&commitments, &tampered),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    assert_eq!(
+        verify(&commitments[..3], &tampered),
+        Err(FridaError::MultiProofMismatch)
+    );
+    assert_eq!(verify(&[], &tampered), Err(FridaError::MultiProofMismatch));
+}
+
+#[test]
+fn test_multiproof_rejects_high_degree_layers() {
+    let (mut commitments, mut provers) = block();
+    let builder = TestFridaProverBuilder::new(test_options());
+
+    // a first layer which is not the evaluation of a low degree polynomial
+    let domain_size = commitments[0].domain_size;
+    let mut channel = test_build_prover_channel(domain_size / 2, &test_options());
+    let bogus = builder
+        .test_build_layers(&mut channel, rand_vector::<BaseElement>(domain_size))
+        .unwrap();
+    commitments.push(bogus.commitment());
+    provers.push(bogus);
+
+    let proof = builder
+        .prove_multi(&provers.iter().collect::<Vec<_>>(), 16)
+        .unwrap();
+    assert!(matches!(
+        verify(&commitments, &proof),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
+
+#[test]
+fn test_multiproof_requires_a_shared_domain() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (_, small, _) = builder.commitment(&rand_vector::<u8>(100), 8).unwrap();
+    let (_, large, _) = builder.commitment(&rand_vector::<u8>(2000), 8).unwrap();
+    assert!(matches!(
+        builder.prove_multi(&[&small, &large], 16),
+        Err(FridaError::MultiProofMismatch)
+    ));
+    assert!(matches!(
+        builder.prove_multi(&[], 16),
+        Err(FridaError::MultiProofMismatch)
+    ));
+
+    // the combined commitment must cover the domain of every commitment
+    let proof = builder.prove_multi(&[&small], 8).unwrap();
+    assert_eq!(
+        verify(&[large.commitment()], &proof),
+        Err(FridaError::MultiProofMismatch)
+    );
+}
@@ -0,0 +1,84 @@
+use crate::{
+    error::FridaError,
+    utils::test_utils::*,
+    verifier::oblivious::ObliviousQuery,
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+use winter_rand_utils::rand_vector;
+
+#[test]
+fn test_oblivious_query() {
+    let options = FriOptions::new(2, 4, 1);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector(1000), rand_vector(500), rand_vector(700)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let sampled = [9, 3, 20];
+    let query =
+        ObliviousQuery::new::<Blake3>(&sampled, 13, commitment.domain_size, b"secret").unwrap();
+    assert_eq!(query.positions().len(), 16);
+    assert!(query.positions().windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(sampled.iter().all(|p| query.positions().contains(p)));
+    assert_eq!(query.sampled(), sampled);
+
+    // decoys only depend on the secret seed
+    let other =
+        ObliviousQuery::new::<Blake3>(&sampled, 13, commitment.domain_size, b"secret").unwrap();
+    assert_eq!(other, query);
+    let other =
+        ObliviousQuery::new::<Blake3>(&sampled, 13, commitment.domain_size, b"other").unwrap();
+    assert_ne!(other.positions(), query.positions());
+
+    let receipts = prover.sample_receipts(query.positions()).unwrap();
+    let evaluations = query.verify(&verifier, &receipts).unwrap();
+    for (position, evaluations) in sampled.iter().zip(evaluations) {
+        assert_eq!(evaluations, prover.get_evaluations(&[*position]).unwrap());
+    }
+}
+
+#[test]
+fn test_oblivious_response_checks() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let (commitment, prover, _) = prover_builder.commitment(&rand_vector(300), 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let query = ObliviousQuery::new::<Blake3>(&[4], 5, commitment.domain_size, b"seed").unwrap();
+    let mut receipts = prover.sample_receipts(query.positions()).unwrap();
+
+    // a provider withholding or reordering positions is caught whichever they are
+    assert_eq!(
+        query.verify(&verifier, &receipts[1..]),
+        Err(FridaError::ObliviousResponseMismatch)
+    );
+    receipts.swap(0, 1);
+    assert_eq!(
+        query.verify(&verifier, &receipts),
+        Err(FridaError::ObliviousResponseMismatch)
+    );
+    receipts.swap(0, 1);
+
+    // only the sampled positions are verified
+    let sampled = query.positions().binary_search(&4).unwrap();
+    let decoy = (sampled + 1) % receipts.len();
+    receipts[decoy].evaluations[0] += BaseElement::ONE;
+    assert!(query.verify(&verifier, &receipts).is_ok());
+    receipts[sampled].evaluations[0] += BaseElement::ONE;
+    assert!(query.verify(&verifier, &receipts).is_err());
+}
+
+#[test]
+fn test_oblivious_query_bounds() {
+    assert_eq!(
+        ObliviousQuery::new::<Blake3>(&[1, 16], 2, 16, b"seed"),
+        Err(FridaError::PositionOutOfDomain(16, 16))
+    );
+    assert_eq!(
+        ObliviousQuery::new::<Blake3>(&[1, 2, 2], 15, 16, b"seed"),
+        Err(FridaError::ObliviousQueryTooLarge(17, 16))
+    );
+    let query = ObliviousQuery::new::<Blake3>(&[1, 2, 2], 14, 16, b"seed").unwrap();
+    assert_eq!(query.positions(), (0..16).collect::<Vec<_>>());
+}
@@ -0,0 +1,131 @@
+use crate::{
+    error::FridaError,
+    prover::{FridaProver, LayerStorage},
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+fn check_ood(options: FriOptions, data_list: &[Vec<u8>]) {
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_ood_check(true);
+    let (commitment, prover) = if data_list.len() == 1 {
+        prover_builder.commit_and_prove(&data_list[0], 8)
+    } else {
+        prover_builder.commit_and_prove_batch(data_list, 8)
+    }
+    .unwrap();
+    assert_eq!(commitment.ood_evaluation.len(), BaseElement::ELEMENT_BYTES);
+    assert_eq!(
+        prover.commitment().ood_evaluation,
+        commitment.ood_evaluation
+    );
+
+    let positions = vec![0, 5, 33, commitment.domain_size - 1];
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    let proof = prover.open(&positions).unwrap();
+
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options.clone()).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&prover.commitment(), options.clone())
+        .unwrap()
+        .require_ood()
+        .unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    // provers restored from a snapshot keep their sample
+    let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    assert_eq!(restored.commitment(), prover.commitment());
+    assert_eq!(restored.open(&positions).unwrap(), proof);
+
+    // a verifier without the evaluation folds the first layer itself, and rejects the proof
+    let mut commitment = prover.commitment();
+    commitment.ood_evaluation = Vec::new();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options.clone()).unwrap();
+    assert!(verifier.verify(&proof, &evaluations, &positions).is_err());
+    assert_eq!(
+        verifier.require_ood().err(),
+        Some(FridaError::OodEvaluationMissing)
+    );
+}
+
+#[test]
+fn test_required_ood_rejects_plain_commitments() {
+    let options = FriOptions::new(2, 4, 3);
+    let (commitment, _) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&rand_vector::<u8>(2000), 8)
+        .unwrap();
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    assert_eq!(
+        verifier.require_ood().err(),
+        Some(FridaError::OodEvaluationMissing)
+    );
+}
+
+#[test]
+fn test_ood_check_single_blob() {
+    check_ood(FriOptions::new(2, 4, 3), &[rand_vector::<u8>(20_000)]);
+    check_ood(FriOptions::new(8, 2, 1), &[rand_vector::<u8>(3000)]);
+}
+
+#[test]
+fn test_ood_check_batch() {
+    check_ood(
+        FriOptions::new(2, 4, 3),
+        &(0..3).map(|_| rand_vector::<u8>(5000)).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn test_ood_check_with_lazy_layers() {
+    let options = FriOptions::new(2, 8, 7);
+    let data_list = (0..2).map(|_| rand_vector::<u8>(8000)).collect::<Vec<_>>();
+
+    let full_builder = TestFridaProverBuilder::new(options.clone()).with_ood_check(true);
+    let lazy_builder = TestFridaProverBuilder::new(options)
+        .with_ood_check(true)
+        .with_layer_storage(LayerStorage::Lazy);
+    let (full_commitment, full) = full_builder.commit_and_prove_batch(&data_list, 8).unwrap();
+    let (lazy_commitment, lazy) = lazy_builder.commit_and_prove_batch(&data_list, 8).unwrap();
+    assert_eq!(lazy_commitment, full_commitment);
+
+    let positions = vec![1, 64, full_commitment.domain_size - 3];
+    assert_eq!(
+        lazy.open(&positions).unwrap(),
+        full.open(&positions).unwrap()
+    );
+}
+
+#[test]
+fn test_ood_check_changes_inner_layers() {
+    let options = FriOptions::new(2, 4, 3);
+    let data = rand_vector::<u8>(10_000);
+    let (plain, _) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&data, 8)
+        .unwrap();
+    let (deep, _) = TestFridaProverBuilder::new(options)
+        .with_ood_check(true)
+        .commit_and_prove(&data, 8)
+        .unwrap();
+
+    assert!(plain.ood_evaluation.is_empty());
+    assert_eq!(plain.roots[0], deep.roots[0]);
+    assert_ne!(plain.roots[1..], deep.roots[1..]);
+}
+
+#[test]
+fn test_wrong_ood_evaluation_is_rejected() {
+    let options = FriOptions::new(2, 4, 3);
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_ood_check(true);
+    let (mut commitment, _) = prover_builder
+        .commit_and_prove(&rand_vector::<u8>(10_000), 8)
+        .unwrap();
+
+    let value = BaseElement::read_from_bytes(&commitment.ood_evaluation).unwrap();
+    commitment.ood_evaluation = (value + BaseElement::ONE).to_bytes();
+    assert!(matches!(
+        TestFridaDasVerifier::new(commitment, options),
+        Err(FridaError::InvalidDASCommitment)
+    ));
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use winter_fri::{folding::fold_positions, utils::map_positions_to_indexes};
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    utils::test_utils::*,
+    verifier::index_cache::{PositionIndexCacheMetrics, POSITION_INDEX_CACHE_CAPACITY},
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+
+#[test]
+fn repeated_positions_reuse_cached_indexes() {
+    let options = FriOptions::new(2, 2, 7);
+    let data = rand_vector::<u8>(2000);
+    let (commitment, prover) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&data, 8)
+        .unwrap();
+    let (verifier, positions) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    let num_layers = verifier.folding_factors().len();
+
+    // the positions of the commitment were mapped into every layer when it was checked
+    let metrics = verifier.position_index_cache_metrics();
+    assert_eq!(
+        metrics,
+        PositionIndexCacheMetrics {
+            entries: num_layers,
+            hits: 0,
+            misses: num_layers as u64,
+        }
+    );
+
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    for _ in 0..3 {
+        verifier.verify(&proof, &evaluations, &positions).unwrap();
+    }
+    assert_eq!(
+        verifier.position_index_cache_metrics(),
+        PositionIndexCacheMetrics {
+            hits: 3 * num_layers as u64,
+            ..metrics
+        }
+    );
+
+    // cached indexes do not spare the checks of the proof against the commitment
+    let mut tampered = evaluations.clone();
+    tampered[0] += BaseElement::ONE;
+    assert!(verifier.verify(&proof, &tampered, &positions).is_err());
+
+    // other positions are mapped on their own, and do not evict the cached ones
+    let other_positions = vec![positions[0]];
+    let proof = prover.open(&other_positions).unwrap();
+    let evaluations = prover.get_evaluations(&other_positions).unwrap();
+    verifier
+        .verify(&proof, &evaluations, &other_positions)
+        .unwrap();
+    let metrics = verifier.position_index_cache_metrics();
+    assert!(metrics.misses > num_layers as u64);
+    assert_eq!(metrics.entries as u64, metrics.misses);
+}
+
+#[test]
+fn position_index_cache_is_bounded() {
+    let options = FriOptions::new(2, 2, 7);
+    let data = rand_vector::<u8>(2000);
+    let (commitment, prover) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&data, 8)
+        .unwrap();
+    let domain_size = commitment.domain_size;
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    assert!(domain_size > POSITION_INDEX_CACHE_CAPACITY);
+
+    for position in 0..domain_size {
+        let positions = vec![position];
+        let proof = prover.open(&positions).unwrap();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+        verifier.verify(&proof, &evaluations, &positions).unwrap();
+    }
+    assert_eq!(
+        verifier.position_index_cache_metrics().entries,
+        POSITION_INDEX_CACHE_CAPACITY
+    );
+}
+
+#[test]
+fn repeated_query_returns_cached_mapping() {
+    let options = FriOptions::new(2, 2, 7);
+    let data = rand_vector::<u8>(2000);
+    let (commitment, _) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&data, 8)
+        .unwrap();
+    let domain_size = commitment.domain_size;
+    let (verifier, positions) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    let folded_positions = fold_positions(&positions, domain_size, 2);
+
+    let indexes = verifier.map_positions_to_indexes(&folded_positions, domain_size, 2, 1);
+    assert_eq!(
+        *indexes,
+        map_positions_to_indexes(&folded_positions, domain_size, 2, 1)
+    );
+    let metrics = verifier.position_index_cache_metrics();
+    let cached = verifier.map_positions_to_indexes(&folded_positions, domain_size, 2, 1);
+    assert!(Arc::ptr_eq(&indexes, &cached));
+    assert_eq!(
+        verifier.position_index_cache_metrics().hits,
+        metrics.hits + 1
+    );
+
+    // the same positions mapped into a layer of another layout are not served from the cache
+    let other = verifier.map_positions_to_indexes(&folded_positions, domain_size, 2, 2);
+    assert!(!Arc::ptr_eq(&indexes, &other));
+    assert_eq!(
+        *other,
+        map_positions_to_indexes(&folded_positions, domain_size, 2, 2)
+    );
+    assert_eq!(
+        verifier.position_index_cache_metrics().misses,
+        metrics.misses + 1
+    );
+}
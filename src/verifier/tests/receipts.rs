@@ -0,0 +1,165 @@
+use crate::{
+    error::FridaError,
+    prover::{deadline::DispersalHint, receipt::SampleReceipt, ProverCommitment},
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestSampleReceipt = SampleReceipt<BaseElement, Blake3>;
+
+#[test]
+fn test_prover_commitment_matches_builder() {
+    let options = FriOptions::new(4, 2, 3);
+    let prover_builder = TestFridaProverBuilder::new(options);
+
+    let (commitment, prover, _) = prover_builder.commitment(&rand_vector(300), 8).unwrap();
+    assert_eq!(commitment, prover.commitment());
+
+    let data_list = vec![rand_vector(100), rand_vector(50), rand_vector(70)];
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    assert_eq!(commitment, prover.commitment());
+}
+
+#[test]
+fn test_sample_receipts() {
+    let options = FriOptions::new(2, 4, 1);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector(100), rand_vector(50), rand_vector(70)];
+
+    let (commitment, prover, positions) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    for position in positions {
+        // receipts travel as bytes between peers
+        let receipt = TestSampleReceipt::read_from_bytes(
+            &prover.sample_receipt(position).unwrap().to_bytes(),
+        )
+        .unwrap();
+        assert_eq!(receipt.evaluations.len(), data_list.len());
+        verifier.verify_receipt(&receipt).unwrap();
+    }
+}
+
+#[test]
+fn test_tampered_sample_receipts() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+
+    let (commitment, prover, _) = prover_builder.commitment(&rand_vector(200), 4).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let receipt = prover.sample_receipt(3).unwrap();
+    verifier.verify_receipt(&receipt).unwrap();
+
+    let mut wrong_evaluation = receipt.clone();
+    wrong_evaluation.evaluations[0] += BaseElement::ONE;
+    assert!(matches!(
+        verifier.verify_receipt(&wrong_evaluation),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    let mut wrong_position = receipt.clone();
+    wrong_position.position = 4;
+    assert!(matches!(
+        verifier.verify_receipt(&wrong_position),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    // positions past the domain are rejected rather than looked up in the proof
+    let mut outside = receipt.clone();
+    outside.position = commitment.domain_size;
+    assert_eq!(
+        verifier.verify_receipt(&outside),
+        Err(FridaError::PositionOutOfDomain(
+            commitment.domain_size,
+            commitment.domain_size
+        ))
+    );
+    assert_eq!(
+        prover.sample_receipt(commitment.domain_size).err(),
+        Some(FridaError::PositionOutOfDomain(
+            commitment.domain_size,
+            commitment.domain_size
+        ))
+    );
+
+    // a receipt for the same position of another commitment
+    let (_, other_prover, _) = prover_builder.commitment(&rand_vector(200), 4).unwrap();
+    assert_eq!(
+        verifier.verify_receipt(&other_prover.sample_receipt(3).unwrap()),
+        Err(FridaError::ReceiptBindingMismatch)
+    );
+}
+
+#[test]
+fn test_commitment_string_encodings() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options).with_data_checksums(true);
+    let (commitment, _, _) = prover_builder.commitment(&rand_vector(200), 4).unwrap();
+
+    let hex = commitment.to_hex();
+    assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(
+        ProverCommitment::<Blake3>::from_hex(&hex).unwrap(),
+        commitment
+    );
+    assert_eq!(
+        ProverCommitment::<Blake3>::from_hex(&hex.to_uppercase()).unwrap(),
+        commitment
+    );
+    assert_eq!(
+        ProverCommitment::<Blake3>::from_base64(&commitment.to_base64()).unwrap(),
+        commitment
+    );
+    assert!(ProverCommitment::<Blake3>::from_hex(&hex[1..]).is_err());
+    assert!(ProverCommitment::<Blake3>::from_base64("not base64").is_err());
+
+    let fingerprint = commitment.fingerprint();
+    assert_eq!(fingerprint.len(), 16);
+    let (other, _, _) = prover_builder.commitment(&rand_vector(200), 4).unwrap();
+    assert_ne!(other.fingerprint(), fingerprint);
+}
+
+#[test]
+fn test_bindings_are_injective() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options);
+    let (commitment, _, _) = prover_builder.commitment(&rand_vector(300), 8).unwrap();
+    let root = commitment.roots[0];
+
+    // an app ids root cannot be passed off as out-of-domain evaluation bytes
+    let mut with_root = commitment.clone();
+    with_root.app_ids_root = Some(root);
+    with_root.ood_evaluation = Vec::new();
+    let mut with_ood = commitment.clone();
+    with_ood.app_ids_root = None;
+    with_ood.ood_evaluation = root.to_bytes();
+    assert_ne!(with_root.binding(), with_ood.binding());
+
+    // nor can a dispersal hint be passed off as the tail of the evaluation bytes
+    let hint = DispersalHint {
+        deadline: 100,
+        expected_shares: 4,
+    };
+    let mut with_hint = commitment.clone();
+    with_hint.ood_evaluation = Vec::new();
+    with_hint.dispersal_hint = Some(hint);
+    let mut with_tail = commitment;
+    with_tail.ood_evaluation = [vec![1], hint.to_bytes()].concat();
+    with_tail.dispersal_hint = None;
+    assert_ne!(with_hint.binding(), with_tail.binding());
+}
+
+#[test]
+fn test_commitments_with_invalid_domains_are_rejected() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options);
+    let (commitment, _, _) = prover_builder.commitment(&rand_vector(300), 8).unwrap();
+
+    // the domain bounds the positions read after it, e.g. in shares
+    for domain_size in [0, 3, 96, 1 << 40] {
+        let mut invalid = commitment.clone();
+        invalid.domain_size = domain_size;
+        assert!(ProverCommitment::<Blake3>::read_from_bytes(&invalid.to_bytes()).is_err());
+    }
+}
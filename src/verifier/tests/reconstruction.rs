@@ -0,0 +1,69 @@
+use crate::{error::FridaError, utils::test_utils::*, winterfell::f128::BaseElement};
+use winter_rand_utils::rand_vector;
+
+#[test]
+fn test_reconstruct_poly_from_batch() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(300), rand_vector(100), rand_vector(250)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let domain_size = commitment.domain_size;
+    let poly_count = commitment.poly_count;
+
+    // exactly domain_size / blowup_factor columns, none of them at even positions
+    let positions = (1..domain_size).step_by(2).collect::<Vec<_>>();
+    let columns = prover.get_evaluations(&positions).unwrap();
+
+    for (poly_index, data) in data_list.iter().enumerate() {
+        let mut samples = positions
+            .iter()
+            .enumerate()
+            .map(|(i, &position)| (position, columns[i * poly_count + poly_index]))
+            .collect::<Vec<(usize, BaseElement)>>();
+        samples.reverse();
+        assert_eq!(
+            &verifier.reconstruct_poly(poly_index, &samples).unwrap(),
+            data
+        );
+
+        // duplicated samples do not make up for missing ones
+        samples[0] = samples[1];
+        assert_eq!(
+            verifier.reconstruct_poly(poly_index, &samples),
+            Err(FridaError::NotEnoughDataPoints())
+        );
+    }
+}
+
+#[test]
+fn test_reconstruct_poly_bad_input() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data_list = vec![rand_vector::<u8>(100), rand_vector(100)];
+
+    let (commitment, prover, _) = prover_builder.commitment_batch(&data_list, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let domain_size = commitment.domain_size;
+
+    let samples = (0..domain_size)
+        .map(|position| (position, prover.get_evaluations(&[position]).unwrap()[0]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        &verifier.reconstruct_poly(0, &samples).unwrap(),
+        &data_list[0]
+    );
+
+    assert_eq!(
+        verifier.reconstruct_poly(2, &samples),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+
+    let mut out_of_domain = samples.clone();
+    out_of_domain.push((domain_size, samples[0].1));
+    assert_eq!(
+        verifier.reconstruct_poly(0, &out_of_domain),
+        Err(FridaError::PositionOutOfDomain(domain_size, domain_size))
+    );
+}
@@ -0,0 +1,145 @@
+use winter_crypto::{ByteDigest, ElementHasher, MerkleTree};
+use winter_fri::folding::fold_positions;
+use winter_math::{fields::f128::BaseElement, StarkField};
+use winter_rand_utils::rand_vector;
+use winter_utils::{Deserializable, Serializable};
+
+use crate::{error::FridaError, utils::test_utils::*, verifier::recursive::RecursiveStatement};
+
+/// Reads a digest back from its 16-bit limbs in a statement.
+fn read_digest(limbs: &[BaseElement]) -> ByteDigest<32> {
+    let mut bytes = [0; 32];
+    for (chunk, limb) in bytes.chunks_mut(2).zip(limbs) {
+        chunk.copy_from_slice(&(limb.as_int() as u16).to_le_bytes());
+    }
+    ByteDigest::new(bytes)
+}
+
+/// Reads an integer back from its two 16-bit limbs in a statement.
+fn read_usize(limbs: &[BaseElement]) -> usize {
+    limbs[0].as_int() as usize | (limbs[1].as_int() as usize) << 16
+}
+
+#[test]
+fn statements_carry_the_public_inputs_of_the_opening() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let data_list = (0..3).map(|_| rand_vector::<u8>(200)).collect::<Vec<_>>();
+    let (single, single_prover) = builder.commit_and_prove(&data_list[0], 4).unwrap();
+    let (batch, batch_prover) = builder.commit_and_prove_batch(&data_list, 4).unwrap();
+
+    let positions = [1, 7, 12];
+    for (commitment, prover) in [(single, single_prover), (batch, batch_prover)] {
+        let proof = prover.open(&positions).unwrap();
+        let evaluations = prover.get_evaluations(&positions).unwrap();
+        let (verifier, _) = TestFridaDasVerifier::new(commitment, test_options()).unwrap();
+
+        let statement = verifier
+            .recursive_statement(&proof, &evaluations, &positions)
+            .unwrap();
+        assert_eq!(
+            statement.public_inputs,
+            verifier
+                .recursive_public_inputs(&evaluations, &positions)
+                .unwrap()
+        );
+        assert_eq!(
+            statement.public_inputs_hash::<Blake3>(),
+            verifier
+                .recursive_public_inputs_hash::<Blake3>(&evaluations, &positions)
+                .unwrap()
+        );
+        assert_eq!(
+            statement.public_inputs_hash::<Blake3>(),
+            Blake3::hash_elements(&statement.public_inputs)
+        );
+        assert!(statement.public_inputs.ends_with(&evaluations));
+        assert_eq!(read_usize(&statement.witness[..2]), 2);
+        assert_eq!(
+            read_usize(&statement.public_inputs[..2]),
+            verifier.domain_size()
+        );
+
+        let bytes = statement.to_bytes();
+        assert_eq!(
+            RecursiveStatement::read_from_bytes(&bytes).unwrap(),
+            statement
+        );
+    }
+}
+
+#[test]
+fn witnesses_authenticate_the_rows_of_the_first_layer() {
+    let builder = TestFridaProverBuilder::new(test_options()).with_num_partitions(2);
+    let (commitment, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(200), 4)
+        .unwrap();
+    let positions = [1, 7, 12];
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, test_options()).unwrap();
+    let statement = verifier
+        .recursive_statement(&proof, &evaluations, &positions)
+        .unwrap();
+
+    // every row is its leaf index, two values and a path of 32-byte digests
+    let root = verifier.layer_commitments()[0];
+    let depth = (verifier.domain_size() / 2).ilog2() as usize;
+    let row_len = 2 + 2 + 16 * (depth + 1);
+    let num_rows = fold_positions(&positions, verifier.domain_size(), 2).len();
+    for row in statement.witness[2..2 + num_rows * row_len].chunks(row_len) {
+        let index = read_usize(&row[..2]);
+        let path = row[4..].chunks(16).map(read_digest).collect::<Vec<_>>();
+        assert_eq!(path[0], Blake3::hash_elements(&row[2..4]));
+        MerkleTree::<Blake3>::verify(root, index, &path).unwrap();
+    }
+}
+
+#[test]
+fn statements_fit_in_small_fields() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (commitment, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(200), 4)
+        .unwrap();
+    let positions = [1, 7, 12];
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, test_options()).unwrap();
+    let statement = verifier
+        .recursive_statement(&proof, &evaluations, &positions)
+        .unwrap();
+
+    // sizes and roots are split into limbs below 2^16, which fit in the BabyBear field
+    let num_layers = verifier.folding_factors().len();
+    let roots_start = 2 * (3 + num_layers);
+    let num_roots = verifier.layer_commitments().len();
+    let limbs = &statement.public_inputs[..roots_start + 16 * num_roots];
+    assert!(limbs.iter().all(|limb| limb.as_int() < 1 << 16));
+    assert_eq!(read_usize(&limbs[4..6]), num_layers);
+    assert_eq!(
+        read_digest(&limbs[roots_start..roots_start + 16]),
+        verifier.layer_commitments()[0]
+    );
+
+    // positions which do not fit in 32 bits are rejected rather than truncated
+    assert_eq!(
+        verifier.recursive_public_inputs(&evaluations, &[1 << 40, 7, 12]),
+        Err(FridaError::RecursiveInputOutOfRange(1 << 40))
+    );
+}
+
+#[test]
+fn statements_are_not_exported_for_invalid_openings() {
+    let builder = TestFridaProverBuilder::new(test_options());
+    let (commitment, prover) = builder
+        .commit_and_prove(&rand_vector::<u8>(200), 4)
+        .unwrap();
+    let positions = [1, 7, 12];
+    let proof = prover.open(&positions).unwrap();
+    let mut evaluations = prover.get_evaluations(&positions).unwrap();
+    evaluations[0] += BaseElement::from(1u32);
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, test_options()).unwrap();
+    assert!(matches!(
+        verifier.recursive_statement(&proof, &evaluations, &positions),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
@@ -0,0 +1,70 @@
+use winter_math::fft;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    error::FridaError,
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+
+#[test]
+fn direct_remainder_evaluates_committed_polynomial() {
+    let options = FriOptions::new(2, 4, 3);
+    let data = rand_vector::<u8>(20);
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_direct_remainder(true);
+    let (commitment, prover) = prover_builder.commit_and_prove(&data, 4).unwrap();
+    let domain_size = commitment.domain_size;
+    let (verifier, positions) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    assert_eq!(
+        verifier.evaluate_remainder_at(0),
+        Err(FridaError::RemainderNotVerified)
+    );
+
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    let all_positions = (0..domain_size).collect::<Vec<_>>();
+    let expected = prover.get_evaluations(&all_positions).unwrap();
+    for (position, value) in all_positions.into_iter().zip(expected) {
+        assert_eq!(verifier.evaluate_remainder_at(position).unwrap(), value);
+    }
+    assert_eq!(
+        verifier.evaluate_remainder_at(domain_size),
+        Err(FridaError::PositionOutOfDomain(domain_size, domain_size))
+    );
+}
+
+#[test]
+fn folded_remainder_is_evaluated_over_remainder_domain() {
+    let options = FriOptions::new(2, 2, 7);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let (commitment, prover) = prover_builder
+        .commit_and_prove(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let verifier =
+        TestFridaDasVerifier::from_commitment(&prover.commitment(), options.clone()).unwrap();
+    verifier.verify_remainder_only(&commitment.proof).unwrap();
+
+    let remainder_domain_size =
+        verifier.domain_size() / verifier.folding_factors().iter().product::<usize>();
+    let mut values = (0..remainder_domain_size)
+        .map(|position| verifier.evaluate_remainder_at(position).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        verifier.evaluate_remainder_at(remainder_domain_size),
+        Err(FridaError::PositionOutOfDomain(
+            remainder_domain_size,
+            remainder_domain_size
+        ))
+    );
+
+    // the values interpolate back into the committed remainder
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(remainder_domain_size);
+    fft::interpolate_poly_with_offset(&mut values, &inv_twiddles, options.domain_offset());
+    let remainder = commitment.proof.parse_remainder::<BaseElement>().unwrap();
+    assert_eq!(values[..remainder.len()], remainder[..]);
+    assert!(values[remainder.len()..]
+        .iter()
+        .all(|&value| value == BaseElement::ZERO));
+}
@@ -0,0 +1,64 @@
+use crate::{
+    prover::proof::FridaProof,
+    utils::test_utils::*,
+    verifier::report::{FailureCode, VerifyReport},
+    winterfell::f128::BaseElement,
+};
+use winter_rand_utils::rand_vector;
+
+#[test]
+fn test_verify_reports() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let (commitment, prover, _) = prover_builder.commitment(&rand_vector(200), 4).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    let positions = [1, 7];
+    let proof = prover.open(&positions).unwrap();
+    let mut evaluations = prover.get_evaluations(&positions).unwrap();
+    let report = verifier.verify_report(&proof, &evaluations, &positions);
+    assert_eq!(
+        report,
+        VerifyReport {
+            ok: true,
+            code: None,
+            layer: None,
+            position: None,
+        }
+    );
+
+    evaluations[0] += BaseElement::new(1);
+    let report = verifier.verify_report(&proof, &evaluations, &positions);
+    assert!(!report.ok);
+    assert_eq!(report.code, Some(FailureCode::LayerFolding));
+    assert_eq!(report.layer, Some(0));
+
+    let report = verifier.verify_report(&proof, &evaluations[..1], &positions);
+    assert_eq!(report.code, Some(FailureCode::EvaluationCountMismatch));
+
+    // single positions are reported along with the failure
+    let mut proof = prover.open(&[3]).unwrap();
+    proof.test_layers_mut()[1].test_values_mut()[0] ^= 1;
+    let report = verifier.verify_report(&proof, &prover.get_evaluations(&[3]).unwrap(), &[3]);
+    assert_eq!(report.code, Some(FailureCode::LayerCommitmentMismatch));
+    assert_eq!(report.position, Some(3));
+
+    let report = verifier.verify_report(&FridaProof::new_dummy(), &[], &[3]);
+    assert_eq!(report.code, Some(FailureCode::MalformedProof));
+    assert_eq!(FailureCode::MalformedProof.as_u16(), 1);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_reports() {
+    let report = VerifyReport {
+        ok: false,
+        code: Some(FailureCode::LayerFolding),
+        layer: Some(0),
+        position: Some(12),
+    };
+    assert_eq!(
+        report.to_json(),
+        r#"{"ok":false,"code":"layer_folding","layer":0,"position":12}"#
+    );
+}
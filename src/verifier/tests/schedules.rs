@@ -0,0 +1,102 @@
+use crate::{
+    error::FridaError,
+    prover::{FridaProver, LayerStorage},
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+fn check_schedule(options: FriOptions, schedule: &[usize], data_list: &[Vec<u8>]) {
+    let prover_builder =
+        TestFridaProverBuilder::new(options.clone()).with_folding_schedule(schedule.to_vec());
+    let (commitment, prover) = if data_list.len() == 1 {
+        prover_builder.commit_and_prove(&data_list[0], 8)
+    } else {
+        prover_builder.commit_and_prove_batch(data_list, 8)
+    }
+    .unwrap();
+
+    let positions = vec![0, 5, 33, commitment.domain_size - 1];
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    let proof = prover.open(&positions).unwrap();
+
+    let (verifier, _) =
+        TestFridaDasVerifier::new_with_schedule(commitment, options.clone(), schedule).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    // provers restored from a snapshot keep their schedule
+    let restored = TestFridaProver::read_from_bytes(&prover.to_bytes()).unwrap();
+    assert_eq!(restored.open(&positions).unwrap(), proof);
+
+    // a verifier folding by a different schedule rejects the proof
+    let verifier = TestFridaDasVerifier::from_commitment(&prover.commitment(), options).unwrap();
+    assert!(verifier.verify(&proof, &evaluations, &positions).is_err());
+}
+
+#[test]
+fn test_schedule_single_blob() {
+    check_schedule(
+        FriOptions::new(2, 4, 3),
+        &[16, 16, 4],
+        &[rand_vector::<u8>(20_000)],
+    );
+    check_schedule(
+        FriOptions::new(4, 2, 1),
+        &[8, 2],
+        &[rand_vector::<u8>(3000)],
+    );
+}
+
+#[test]
+fn test_schedule_batch() {
+    check_schedule(
+        FriOptions::new(2, 4, 3),
+        &[8, 16, 2],
+        &(0..3).map(|_| rand_vector::<u8>(5000)).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn test_schedule_with_lazy_layers() {
+    let options = FriOptions::new(2, 4, 3);
+    let schedule = vec![16, 2, 8];
+    let data = rand_vector::<u8>(10_000);
+
+    let full_builder =
+        TestFridaProverBuilder::new(options.clone()).with_folding_schedule(schedule.clone());
+    let lazy_builder = TestFridaProverBuilder::new(options)
+        .with_folding_schedule(schedule)
+        .with_layer_storage(LayerStorage::Lazy);
+    let (full_commitment, full) = full_builder.commit_and_prove(&data, 8).unwrap();
+    let (lazy_commitment, lazy) = lazy_builder.commit_and_prove(&data, 8).unwrap();
+    assert_eq!(lazy_commitment, full_commitment);
+
+    let positions = vec![1, 64, full_commitment.domain_size - 3];
+    assert_eq!(
+        lazy.open(&positions).unwrap(),
+        full.open(&positions).unwrap()
+    );
+}
+
+#[test]
+fn test_unsupported_schedule_is_rejected() {
+    let options = FriOptions::new(2, 4, 3);
+    let prover_builder =
+        TestFridaProverBuilder::new(options.clone()).with_folding_schedule(vec![4, 3]);
+    assert_eq!(
+        prover_builder
+            .commit_and_prove(&rand_vector::<u8>(1000), 8)
+            .unwrap_err(),
+        FridaError::UnsupportedFoldingFactor(3)
+    );
+
+    let (commitment, _) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&rand_vector::<u8>(1000), 8)
+        .unwrap();
+    assert!(matches!(
+        TestFridaDasVerifier::new_with_schedule(commitment, options, &[32]),
+        Err(FridaError::UnsupportedFoldingFactor(32))
+    ));
+}
@@ -0,0 +1,106 @@
+use crate::{
+    error::FridaError,
+    prover::Protocol,
+    utils::test_utils::*,
+    verifier::stir::StirVerifier,
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+use winter_rand_utils::rand_vector;
+
+type TestStirVerifier = StirVerifier<BaseElement, Blake3, Blake3>;
+
+fn check_stir(options: FriOptions, data_size: usize) {
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_protocol(Protocol::Stir);
+    let (commitment, prover) = prover_builder
+        .commit_and_prove_stir(&rand_vector::<u8>(data_size), 16)
+        .unwrap();
+    assert_eq!(prover.root(), commitment.roots[0]);
+
+    let verifier = TestStirVerifier::new(&commitment, options).unwrap();
+    let positions = vec![0, 7, 31, commitment.domain_size - 1];
+    let proof = prover.open(&positions).unwrap();
+    let mut evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+
+    evaluations[1] += BaseElement::ONE;
+    assert!(verifier.verify(&proof, &evaluations, &positions).is_err());
+}
+
+#[test]
+fn test_stir_folding_4() {
+    check_stir(FriOptions::new(2, 4, 3), 20_000);
+}
+
+#[test]
+fn test_stir_folding_8() {
+    check_stir(FriOptions::new(4, 8, 7), 50_000);
+}
+
+#[test]
+fn test_stir_folding_16() {
+    check_stir(FriOptions::new(2, 16, 15), 100_000);
+}
+
+#[test]
+fn test_stir_rejects_foreign_roots() {
+    let options = FriOptions::new(2, 8, 7);
+    let prover_builder = TestFridaProverBuilder::new(options.clone()).with_protocol(Protocol::Stir);
+    let (mut commitment, _) = prover_builder
+        .commit_and_prove_stir(&rand_vector::<u8>(30_000), 16)
+        .unwrap();
+    let (other, _) = prover_builder
+        .commit_and_prove_stir(&rand_vector::<u8>(30_000), 16)
+        .unwrap();
+
+    commitment.roots[1] = other.roots[1];
+    assert!(TestStirVerifier::new(&commitment, options.clone()).is_err());
+
+    commitment.proof = other.proof;
+    assert!(TestStirVerifier::new(&commitment, options).is_err());
+}
+
+#[test]
+fn test_stir_proof_is_smaller_than_fri() {
+    let options = FriOptions::new(2, 8, 7);
+    let data = rand_vector::<u8>(64 * 1024);
+
+    let (fri_commitment, _) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&data, 32)
+        .unwrap();
+    let (stir_commitment, _) = TestFridaProverBuilder::new(options)
+        .with_protocol(Protocol::Stir)
+        .commit_and_prove_stir(&data, 32)
+        .unwrap();
+
+    assert!(stir_commitment.proof.num_rounds() > 1);
+    assert!(stir_commitment.proof.size() < fri_commitment.proof.size());
+}
+
+#[test]
+fn test_protocol_mismatch() {
+    let options = FriOptions::new(2, 4, 3);
+    let data = rand_vector::<u8>(1000);
+
+    let fri_builder = TestFridaProverBuilder::new(options.clone());
+    assert_eq!(
+        fri_builder.commit_and_prove_stir(&data, 8).unwrap_err(),
+        FridaError::ProtocolMismatch
+    );
+
+    let stir_builder = TestFridaProverBuilder::new(options).with_protocol(Protocol::Stir);
+    assert_eq!(
+        stir_builder.commit_and_prove(&data, 8).unwrap_err(),
+        FridaError::ProtocolMismatch
+    );
+}
+
+#[test]
+fn test_stir_rejects_folding_2() {
+    let prover_builder = TestFridaProverBuilder::new(test_options()).with_protocol(Protocol::Stir);
+    assert_eq!(
+        prover_builder
+            .commit_and_prove_stir(&rand_vector::<u8>(1000), 8)
+            .unwrap_err(),
+        FridaError::UnsupportedFoldingFactor(2)
+    );
+}
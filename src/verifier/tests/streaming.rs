@@ -0,0 +1,88 @@
+use winter_fri::{FriOptions, VerifierError};
+use winter_rand_utils::rand_vector;
+
+use crate::{error::FridaError, utils::test_utils::*};
+
+/// Opens every position of the commitment to `data_list`, and checks that streamed verification
+/// agrees with the regular one, on the honest proof as on a tampered one.
+fn check_streaming_matches_verify(options: FriOptions, schedule: &[usize], data_list: &[Vec<u8>]) {
+    let builder =
+        TestFridaProverBuilder::new(options.clone()).with_folding_schedule(schedule.to_vec());
+    let (commitment, prover, _) = if data_list.len() == 1 {
+        builder.commitment(&data_list[0], 4).unwrap()
+    } else {
+        builder.commitment_batch(data_list, 4).unwrap()
+    };
+    let verifier =
+        TestFridaDasVerifier::from_commitment_with_schedule(&commitment, options, schedule)
+            .unwrap();
+
+    let positions = (0..commitment.domain_size).collect::<Vec<_>>();
+    let proof = prover.open(&positions).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    verifier.verify(&proof, &evaluations, &positions).unwrap();
+    verifier
+        .verify_streaming(&proof, &evaluations, &positions)
+        .unwrap();
+
+    let mut tampered = evaluations.clone();
+    tampered[positions.len() / 2] += tampered[0];
+    assert_eq!(
+        verifier.verify_streaming(&proof, &tampered, &positions),
+        verifier.verify(&proof, &tampered, &positions)
+    );
+    assert!(verifier
+        .verify_streaming(&proof, &tampered, &positions)
+        .is_err());
+}
+
+#[test]
+fn streamed_verification_of_whole_domains() {
+    check_streaming_matches_verify(test_options(), &[], &[rand_vector::<u8>(3000)]);
+    check_streaming_matches_verify(FriOptions::new(4, 4, 3), &[], &[rand_vector::<u8>(3000)]);
+    check_streaming_matches_verify(test_options(), &[8, 2], &[rand_vector::<u8>(3000)]);
+}
+
+#[test]
+fn streamed_verification_of_batches() {
+    let data_list = (0..3).map(|_| rand_vector::<u8>(1000)).collect::<Vec<_>>();
+    check_streaming_matches_verify(test_options(), &[], &data_list);
+    check_streaming_matches_verify(FriOptions::new(2, 4, 3), &[], &data_list);
+}
+
+#[test]
+fn streamed_verification_rejects_malformed_layers() {
+    let options = test_options();
+    let (commitment, prover, positions) = TestFridaProverBuilder::new(options.clone())
+        .commitment(&rand_vector::<u8>(500), 4)
+        .unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let evaluations = prover.get_evaluations(&positions).unwrap();
+    let proof = prover.open(&positions).unwrap();
+
+    // a layer which cannot be parsed is only found once the layers before it are checked
+    let mut truncated = proof.clone();
+    let layers = truncated.test_layers_mut();
+    let last = layers.len() - 1;
+    layers[last].test_paths_mut().pop();
+    assert_eq!(
+        verifier.verify_streaming(&truncated, &evaluations, &positions),
+        Err(FridaError::FailToVerify(
+            VerifierError::LayerCommitmentMismatch
+        ))
+    );
+    assert!(matches!(
+        verifier.verify(&truncated, &evaluations, &positions),
+        Err(FridaError::DeserializationError(_))
+    ));
+
+    // as is a missing layer, before any layer is parsed
+    let mut missing = proof;
+    missing.test_layers_mut().pop();
+    assert_eq!(
+        verifier.verify_streaming(&missing, &evaluations, &positions),
+        Err(FridaError::FailToVerify(
+            VerifierError::LayerCommitmentMismatch
+        ))
+    );
+}
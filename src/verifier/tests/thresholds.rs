@@ -0,0 +1,112 @@
+use winter_fri::VerifierError;
+use winter_math::fields::f128::BaseElement;
+use winter_rand_utils::rand_vector;
+
+use crate::{
+    core::queries::batch_security_level,
+    error::FridaError,
+    prover::FridaProver,
+    utils::test_utils::*,
+    verifier::thresholds::{QueryAcceptance, QueryThresholds},
+};
+
+type TestFridaProver = FridaProver<BaseElement, Blake3>;
+
+fn setup() -> (TestFridaDasVerifier, TestFridaProver, QueryThresholds) {
+    let options = test_options();
+    let (commitment, prover) = TestFridaProverBuilder::new(options.clone())
+        .commit_and_prove(&rand_vector::<u8>(1000), 16)
+        .unwrap();
+    let thresholds = QueryThresholds::for_commitment(&commitment, 8).unwrap();
+    let (verifier, _) = TestFridaDasVerifier::new(commitment, options).unwrap();
+    (verifier, prover, thresholds)
+}
+
+fn verify(
+    verifier: &TestFridaDasVerifier,
+    prover: &TestFridaProver,
+    positions: &[usize],
+    thresholds: QueryThresholds,
+) -> Result<QueryAcceptance, FridaError> {
+    let proof = prover.open(positions).unwrap();
+    let evaluations = prover.get_evaluations(positions).unwrap();
+    verifier.verify_with_thresholds(&proof, &evaluations, positions, thresholds)
+}
+
+#[test]
+fn proofs_are_accepted_by_threshold() {
+    let (verifier, prover, thresholds) = setup();
+    assert_eq!(thresholds, QueryThresholds::new(16, 8));
+
+    let positions = (0..16).map(|i| i * 3).collect::<Vec<_>>();
+    let full = verify(&verifier, &prover, &positions, thresholds).unwrap();
+    assert!(full.is_full());
+    assert_eq!(
+        full.security_bits(),
+        batch_security_level::<BaseElement>(verifier.domain_size(), verifier.options(), 1, 16)
+    );
+
+    let soft = verify(&verifier, &prover, &positions[..10], thresholds).unwrap();
+    assert_eq!(
+        soft,
+        QueryAcceptance::Soft {
+            num_positions: 10,
+            security_bits: verifier.security_level(10),
+        }
+    );
+    assert!(soft.security_bits() < full.security_bits());
+
+    assert_eq!(
+        verify(&verifier, &prover, &positions[..7], thresholds),
+        Err(FridaError::TooFewQueries(7, 8))
+    );
+}
+
+#[test]
+fn repeated_positions_count_once() {
+    let (verifier, prover, thresholds) = setup();
+    let positions = [1, 2, 3, 4, 5, 6, 7, 1, 2, 3];
+    assert_eq!(
+        verify(&verifier, &prover, &positions, thresholds),
+        Err(FridaError::TooFewQueries(7, 8))
+    );
+}
+
+#[test]
+fn soft_accepted_proofs_are_still_verified() {
+    let (verifier, prover, thresholds) = setup();
+    let positions = (0..10).collect::<Vec<_>>();
+    let proof = prover.open(&positions).unwrap();
+    let mut evaluations = prover.get_evaluations(&positions).unwrap();
+    evaluations[4] += BaseElement::new(1);
+
+    assert_eq!(
+        verifier.verify_with_thresholds(&proof, &evaluations, &positions, thresholds),
+        Err(FridaError::FailToVerify(
+            VerifierError::InvalidLayerFolding(0)
+        ))
+    );
+}
+
+#[test]
+fn thresholds_must_fit_the_commitment() {
+    let (commitment, _) = TestFridaProverBuilder::new(test_options())
+        .commit_and_prove(&rand_vector::<u8>(1000), 16)
+        .unwrap();
+    for hard in [0, 17] {
+        assert_eq!(
+            QueryThresholds::for_commitment(&commitment, hard),
+            Err(FridaError::InvalidQueryThreshold(hard, 16))
+        );
+    }
+    assert_eq!(
+        QueryThresholds::for_commitment(&commitment, 16),
+        Ok(QueryThresholds::new(16, 16))
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot exceed the soft threshold")]
+fn hard_threshold_cannot_exceed_soft_one() {
+    QueryThresholds::new(4, 8);
+}
@@ -0,0 +1,86 @@
+use crate::{
+    error::FridaError,
+    prover::Protocol,
+    utils::test_utils::*,
+    verifier::{stir::StirVerifier, FridaVerifier},
+    winterfell::{f128::BaseElement, FieldElement, FriOptions},
+};
+use winter_rand_utils::rand_vector;
+
+/// Checks openings through the trait alone, as generic callers do.
+fn check_openings<V: FridaVerifier<BaseElement>>(
+    verifier: &V,
+    proofs: &[V::Proof],
+    evaluations: &[Vec<BaseElement>],
+    positions: &[Vec<usize>],
+) -> Result<(), FridaError> {
+    let openings = proofs
+        .iter()
+        .zip(evaluations)
+        .zip(positions)
+        .map(|((proof, evaluations), positions)| (proof, &evaluations[..], &positions[..]))
+        .collect::<Vec<_>>();
+    verifier.verify_many(&openings)
+}
+
+#[test]
+fn test_das_verifier_through_trait() {
+    let options = test_options();
+    let data_list = (0..3).map(|_| rand_vector::<u8>(100)).collect::<Vec<_>>();
+    let (commitment, prover, _) = TestFridaProverBuilder::new(options.clone())
+        .commitment_batch(&data_list, 4)
+        .unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options.clone()).unwrap();
+    assert_eq!(FridaVerifier::poly_count(&verifier), 3);
+    assert_eq!(FridaVerifier::options(&verifier), &options);
+
+    let positions = vec![vec![0, 3], vec![9], vec![1, 2, 15]];
+    let proofs = positions
+        .iter()
+        .map(|p| prover.open(p).unwrap())
+        .collect::<Vec<_>>();
+    let mut evaluations = positions
+        .iter()
+        .map(|p| prover.get_evaluations(p).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        check_openings(&verifier, &proofs, &evaluations, &positions),
+        Ok(())
+    );
+
+    evaluations[2][4] += BaseElement::ONE;
+    assert!(matches!(
+        check_openings(&verifier, &proofs, &evaluations, &positions),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
+
+#[test]
+fn test_stir_verifier_through_trait() {
+    let options = FriOptions::new(2, 8, 7);
+    let (commitment, prover) = TestFridaProverBuilder::new(options.clone())
+        .with_protocol(Protocol::Stir)
+        .commit_and_prove_stir(&rand_vector::<u8>(30_000), 16)
+        .unwrap();
+    let verifier =
+        StirVerifier::<BaseElement, Blake3, Blake3>::new(&commitment, options.clone()).unwrap();
+    assert_eq!(FridaVerifier::poly_count(&verifier), 1);
+    assert_eq!(FridaVerifier::options(&verifier), &options);
+
+    let positions = vec![vec![0, 5], vec![commitment.domain_size - 1]];
+    let proofs = positions
+        .iter()
+        .map(|p| prover.open(p).unwrap())
+        .collect::<Vec<_>>();
+    let mut evaluations = positions
+        .iter()
+        .map(|p| prover.get_evaluations(p).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        check_openings(&verifier, &proofs, &evaluations, &positions),
+        Ok(())
+    );
+
+    evaluations[1][0] += BaseElement::ONE;
+    assert!(check_openings(&verifier, &proofs, &evaluations, &positions).is_err());
+}
@@ -0,0 +1,92 @@
+use crate::{
+    error::FridaError,
+    prover::value::ValueOpening,
+    utils::test_utils::*,
+    winterfell::{f128::BaseElement, Deserializable, FieldElement, FriOptions, Serializable},
+};
+use winter_rand_utils::rand_vector;
+
+type TestValueOpening = ValueOpening<BaseElement>;
+
+#[test]
+fn test_value_openings() {
+    let options = FriOptions::new(4, 2, 1);
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(500);
+
+    let (commitment, prover, _) = prover_builder.commitment(&data, 8).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+
+    // the first element holds the 8-byte length prefix, the last one the end of the data
+    let ranges = [(0, 0..7), (1, 7..22), (20, 292..307), (33, 487..500)];
+    for (index, range) in ranges {
+        let opening = prover.open_value(index, 4).unwrap();
+        // openings travel as bytes between peers
+        let opening = TestValueOpening::read_from_bytes(&opening.to_bytes()).unwrap();
+        assert_eq!(opening.range, range);
+        verifier.verify_value(&opening, &data[range]).unwrap();
+    }
+
+    assert_eq!(
+        prover.open_value(33, 4).unwrap().value,
+        prover.get_evaluations(&[33 * 4]).unwrap()[0]
+    );
+    assert_eq!(
+        prover.open_value(34, 4),
+        Err(FridaError::ValueOutOfBounds(34, 34))
+    );
+}
+
+#[test]
+fn test_tampered_value_openings() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options.clone());
+    let data = rand_vector::<u8>(200);
+
+    let (commitment, prover, _) = prover_builder.commitment(&data, 4).unwrap();
+    let verifier = TestFridaDasVerifier::from_commitment(&commitment, options).unwrap();
+    let opening = prover.open_value(3, 2).unwrap();
+    verifier.verify_value(&opening, &data[37..52]).unwrap();
+
+    let mut wrong_bytes = data[37..52].to_vec();
+    wrong_bytes[0] ^= 1;
+    assert_eq!(
+        verifier.verify_value(&opening, &wrong_bytes),
+        Err(FridaError::ByteRangeMismatch)
+    );
+
+    let mut wrong_range = opening.clone();
+    wrong_range.range = 36..51;
+    assert_eq!(
+        verifier.verify_value(&wrong_range, &data[36..51]),
+        Err(FridaError::ByteRangeMismatch)
+    );
+
+    let mut wrong_value = opening.clone();
+    wrong_value.value += BaseElement::ONE;
+    assert!(matches!(
+        verifier.verify_value(&wrong_value, &data[37..52]),
+        Err(FridaError::FailToVerify(_))
+    ));
+
+    // another element holds other bytes, and is opened at another position
+    let mut wrong_index = opening.clone();
+    wrong_index.index = 4;
+    wrong_index.range = 52..67;
+    assert!(matches!(
+        verifier.verify_value(&wrong_index, &data[52..67]),
+        Err(FridaError::FailToVerify(_))
+    ));
+}
+
+#[test]
+fn test_batch_value_openings() {
+    let options = test_options();
+    let prover_builder = TestFridaProverBuilder::new(options);
+    let data_list = vec![rand_vector::<u8>(100), rand_vector(100)];
+    let (_, prover, _) = prover_builder.commitment_batch(&data_list, 4).unwrap();
+    assert_eq!(
+        prover.open_value(1, 2),
+        Err(FridaError::ProofPolyCountMismatch)
+    );
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+use winter_crypto::{ElementHasher, Hasher};
+use winter_math::FieldElement;
+
+use super::das::FridaDasVerifier;
+use crate::{
+    core::queries::batch_security_level,
+    error::FridaError,
+    prover::{proof::FridaProof, Commitment},
+};
+
+// QUERY THRESHOLDS
+// ================================================================================================
+
+/// Numbers of distinct positions a proof must open to be accepted by
+/// `FridaDasVerifier::verify_with_thresholds`.
+///
+/// Proofs opening at least `soft` positions are fully accepted. Proofs opening fewer positions, but
+/// at least `hard`, are soft accepted along with the security level they achieve, so that
+/// consensus layers can respond in a graduated way instead of rejecting them outright. Proofs
+/// opening fewer than `hard` positions are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryThresholds {
+    pub soft: usize,
+    pub hard: usize,
+}
+
+impl QueryThresholds {
+    /// # Panics
+    /// Panics if `hard` is 0 or greater than `soft`.
+    pub fn new(soft: usize, hard: usize) -> Self {
+        assert!(hard > 0, "the hard threshold must be greater than 0");
+        assert!(
+            hard <= soft,
+            "the hard threshold {hard} cannot exceed the soft threshold {soft}"
+        );
+        QueryThresholds { soft, hard }
+    }
+
+    /// Thresholds fully accepting proofs which open as many positions as `commitment` draws, and
+    /// soft accepting those which open at least `hard` of them.
+    ///
+    /// # Errors
+    /// Returns `FridaError::InvalidQueryThreshold` if `hard` is 0 or greater than the number of
+    /// queries of `commitment`, which is read from untrusted input.
+    pub fn for_commitment<H: Hasher>(
+        commitment: &Commitment<H>,
+        hard: usize,
+    ) -> Result<Self, FridaError> {
+        let soft = commitment.num_queries;
+        if hard == 0 || hard > soft {
+            return Err(FridaError::InvalidQueryThreshold(hard, soft));
+        }
+        Ok(QueryThresholds { soft, hard })
+    }
+}
+
+/// Acceptance of a proof verified against `QueryThresholds`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryAcceptance {
+    /// The proof opens at least as many positions as the soft threshold.
+    Full { security_bits: u32 },
+    /// The proof opens fewer positions than the soft threshold, but at least as many as the hard
+    /// one, and only achieves `security_bits`.
+    Soft {
+        num_positions: usize,
+        security_bits: u32,
+    },
+}
+
+impl QueryAcceptance {
+    /// Returns the security level, in bits, achieved by the accepted proof.
+    pub fn security_bits(&self) -> u32 {
+        match self {
+            QueryAcceptance::Full { security_bits } => *security_bits,
+            QueryAcceptance::Soft { security_bits, .. } => *security_bits,
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        matches!(self, QueryAcceptance::Full { .. })
+    }
+}
+
+impl<E, HHst, HRandom> FridaDasVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Same as `verify`, but accepts proofs opening fewer distinct positions than the soft
+    /// threshold of `thresholds`, reporting the security level they achieve.
+    ///
+    /// # Errors
+    /// Returns `FridaError::TooFewQueries` if `positions` holds fewer distinct positions than the
+    /// hard threshold, and the errors of `verify` otherwise.
+    pub fn verify_with_thresholds(
+        &self,
+        proof: &FridaProof,
+        evaluations: &[E],
+        positions: &[usize],
+        thresholds: QueryThresholds,
+    ) -> Result<QueryAcceptance, FridaError> {
+        let num_positions = positions.iter().collect::<BTreeSet<_>>().len();
+        if num_positions < thresholds.hard {
+            return Err(FridaError::TooFewQueries(num_positions, thresholds.hard));
+        }
+        self.verify(proof, evaluations, positions)?;
+
+        let security_bits = self.security_level(num_positions);
+        if num_positions >= thresholds.soft {
+            Ok(QueryAcceptance::Full { security_bits })
+        } else {
+            Ok(QueryAcceptance::Soft {
+                num_positions,
+                security_bits,
+            })
+        }
+    }
+
+    /// Returns the security level, in bits, of an opening of `num_positions` distinct positions
+    /// of the commitment of this verifier.
+    pub fn security_level(&self, num_positions: usize) -> u32 {
+        batch_security_level::<E>(
+            self.domain_size(),
+            self.options(),
+            self.poly_count(),
+            num_positions,
+        )
+    }
+}
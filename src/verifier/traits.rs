@@ -0,0 +1,100 @@
+use winter_crypto::ElementHasher;
+use winter_fri::FriOptions;
+use winter_math::FieldElement;
+
+use super::{das::FridaDasVerifier, stir::StirVerifier};
+use crate::{
+    error::FridaError,
+    prover::proof::{FridaProof, FridaProofLayer},
+};
+
+// FRIDA VERIFIER
+// ================================================================================================
+
+/// Opening of a verifier's commitment: a proof, along with the evaluations it opens at the given
+/// positions.
+pub type Opening<'a, E, P> = (&'a P, &'a [E], &'a [usize]);
+
+/// Verifier of openings of a commitment, whatever the protocol the commitment was built with.
+///
+/// Generic code, e.g. a sampler checking the shares it received, can be written against this
+/// trait and work with every verifier variant.
+pub trait FridaVerifier<E: FieldElement> {
+    /// Proof opening positions of the commitment.
+    type Proof;
+
+    /// Checks that `proof` opens the commitment to `evaluations` at `positions`.
+    fn verify(
+        &self,
+        proof: &Self::Proof,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), FridaError>;
+
+    /// Checks every opening in turn, and returns the error of the first one rejected.
+    fn verify_many(&self, openings: &[Opening<'_, E, Self::Proof>]) -> Result<(), FridaError> {
+        openings
+            .iter()
+            .try_for_each(|&(proof, evaluations, positions)| {
+                self.verify(proof, evaluations, positions)
+            })
+    }
+
+    /// Number of polynomials committed to; every position opens one evaluation of each.
+    fn poly_count(&self) -> usize;
+
+    /// Options the commitment was built with.
+    fn options(&self) -> &FriOptions;
+}
+
+impl<E, HHst, HRandom> FridaVerifier<E> for FridaDasVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    type Proof = FridaProof;
+
+    fn verify(
+        &self,
+        proof: &FridaProof,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), FridaError> {
+        FridaDasVerifier::verify(self, proof, evaluations, positions)
+    }
+
+    fn poly_count(&self) -> usize {
+        FridaDasVerifier::poly_count(self)
+    }
+
+    fn options(&self) -> &FriOptions {
+        FridaDasVerifier::options(self)
+    }
+}
+
+impl<E, HHst, HRandom> FridaVerifier<E> for StirVerifier<E, HHst, HRandom>
+where
+    E: FieldElement,
+    HHst: ElementHasher<BaseField = E::BaseField>,
+    HRandom: ElementHasher<BaseField = E::BaseField>,
+{
+    type Proof = FridaProofLayer;
+
+    fn verify(
+        &self,
+        proof: &FridaProofLayer,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), FridaError> {
+        StirVerifier::verify(self, proof, evaluations, positions)
+    }
+
+    fn poly_count(&self) -> usize {
+        1
+    }
+
+    fn options(&self) -> &FriOptions {
+        StirVerifier::options(self)
+    }
+}
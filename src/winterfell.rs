@@ -26,6 +26,7 @@ pub use winter_utils::{ByteReader, Deserializable, Serializable};
 pub use winter_fri::folding;
 pub use winter_fri::utils::{hash_values, map_positions_to_indexes};
 pub use winter_math::{fft, polynom};
+#[cfg(feature = "concurrent")]
 pub use winter_utils::iterators::*;
 pub use winter_utils::{
     flatten_vector_elements, group_slice_elements, iter_mut, transpose_slice, uninit_vector,